@@ -0,0 +1,258 @@
+//! Mesh conversion and inspection tool
+//!
+//! ```text
+//! dugong-mesh convert <input> <output>
+//! dugong-mesh check <input>
+//! dugong-mesh stats <input>
+//! dugong-mesh surface <input> <output> [patch...]
+//! ```
+//!
+//! Meshes are identified by path: a directory is an OpenFOAM `polyMesh`
+//! directory, `.msh` is a Gmsh ASCII file, which can be read but not
+//! written, `.vtu` is a VTK unstructured grid, which can be written but not
+//! read, and `.dmesh` is a binary mesh container.
+//!
+//! Boundary surfaces are written as ASCII STL (`.stl`) or Wavefront OBJ (`.obj`).
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use dugong_io::binary::{read_binary_mesh, write_binary_mesh};
+use dugong_io::gmsh::read_gmsh;
+use dugong_io::polymesh::{read_polymesh, write_polymesh};
+use dugong_io::surface::write_surface;
+use dugong_io::vtu::write_vtu;
+use dugong_mesh::Mesh;
+
+const USAGE: &str = "usage:
+    dugong-mesh convert <input> <output>   convert between mesh formats
+    dugong-mesh check <input>              check mesh validity and quality
+    dugong-mesh stats <input>              print mesh statistics
+    dugong-mesh surface <input> <output> [patch...]
+                                           export boundary patches (all by
+                                           default) as an STL or OBJ surface";
+
+/// A parsed command line.
+#[derive(Debug, PartialEq)]
+enum Command {
+    Convert {
+        input: PathBuf,
+        output: PathBuf,
+    },
+    Check {
+        input: PathBuf,
+    },
+    Stats {
+        input: PathBuf,
+    },
+    Surface {
+        input: PathBuf,
+        output: PathBuf,
+        patches: Vec<String>,
+    },
+}
+
+/// A mesh file format, chosen from the path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    PolyMesh,
+    Binary,
+    Gmsh,
+    Vtu,
+}
+
+impl Format {
+    fn of(path: &Path) -> Format {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("dmesh") => Format::Binary,
+            Some("msh") => Format::Gmsh,
+            Some("vtu") => Format::Vtu,
+            _ => Format::PolyMesh,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Format::PolyMesh => "polyMesh",
+            Format::Binary => "binary",
+            Format::Gmsh => "Gmsh",
+            Format::Vtu => "VTU",
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let path = |i: usize| {
+        args.get(i)
+            .map(PathBuf::from)
+            .ok_or_else(|| "missing path argument".to_string())
+    };
+    let command = match args.first().map(String::as_str) {
+        Some("convert") => Command::Convert {
+            input: path(1)?,
+            output: path(2)?,
+        },
+        Some("check") => Command::Check { input: path(1)? },
+        Some("stats") => Command::Stats { input: path(1)? },
+        Some("surface") => {
+            return Ok(Command::Surface {
+                input: path(1)?,
+                output: path(2)?,
+                patches: args[3..].to_vec(),
+            });
+        }
+        Some(other) => return Err(format!("unknown command: {other}")),
+        None => return Err("missing command".to_string()),
+    };
+    let expected = if matches!(command, Command::Convert { .. }) {
+        3
+    } else {
+        2
+    };
+    if args.len() > expected {
+        return Err(format!("unexpected argument: {}", args[expected]));
+    }
+    Ok(command)
+}
+
+fn read_mesh(path: &Path) -> Result<Mesh, String> {
+    match Format::of(path) {
+        Format::PolyMesh => read_polymesh(path).map_err(|e| format!("{}: {e}", path.display())),
+        Format::Binary => read_binary_mesh(path).map_err(|e| format!("{}: {e}", path.display())),
+        Format::Gmsh => {
+            let gmsh = read_gmsh(path).map_err(|e| format!("{}: {e}", path.display()))?;
+            for warning in &gmsh.warnings {
+                eprintln!("warning: {}: {warning}", path.display());
+            }
+            Ok(gmsh.mesh)
+        }
+        format => Err(format!("reading {} meshes is not supported", format.name())),
+    }
+}
+
+fn write_mesh(mesh: &Mesh, path: &Path) -> Result<(), String> {
+    match Format::of(path) {
+        Format::PolyMesh => {
+            write_polymesh(mesh, path).map_err(|e| format!("{}: {e}", path.display()))
+        }
+        Format::Binary => {
+            write_binary_mesh(mesh, path).map_err(|e| format!("{}: {e}", path.display()))
+        }
+        Format::Vtu => write_vtu(mesh, path).map_err(|e| format!("{}: {e}", path.display())),
+        format => Err(format!("writing {} meshes is not supported", format.name())),
+    }
+}
+
+fn run(command: Command) -> Result<bool, String> {
+    match command {
+        Command::Convert { input, output } => {
+            let mesh = read_mesh(&input)?;
+            write_mesh(&mesh, &output)?;
+            println!(
+                "Converted {} cells from {} to {}",
+                mesh.n_cells(),
+                input.display(),
+                output.display()
+            );
+            Ok(true)
+        }
+        Command::Check { input } => {
+            let mesh = read_mesh(&input)?;
+            let check = mesh.check();
+            print!("{}\n{check}", mesh.stats());
+            Ok(check.is_ok())
+        }
+        Command::Stats { input } => {
+            print!("{}", read_mesh(&input)?.stats());
+            Ok(true)
+        }
+        Command::Surface {
+            input,
+            output,
+            patches,
+        } => {
+            let mesh = read_mesh(&input)?;
+            let names: Vec<&str> = if patches.is_empty() {
+                mesh.patches().iter().map(|p| p.name()).collect()
+            } else {
+                patches.iter().map(String::as_str).collect()
+            };
+            let surface = mesh.boundary_surface(&names).map_err(|e| e.to_string())?;
+            write_surface(&surface, &output).map_err(|e| format!("{}: {e}", output.display()))?;
+            println!(
+                "Wrote {} triangles from {} patches to {}",
+                surface.n_triangles(),
+                names.len(),
+                output.display()
+            );
+            Ok(true)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {message}\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match run(command) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args_commands() {
+        assert_eq!(
+            parse_args(&args("convert a b.vtu")),
+            Ok(Command::Convert {
+                input: "a".into(),
+                output: "b.vtu".into()
+            })
+        );
+        assert_eq!(
+            parse_args(&args("check mesh")),
+            Ok(Command::Check {
+                input: "mesh".into()
+            })
+        );
+        assert_eq!(
+            parse_args(&args("surface mesh walls.stl inlet wall")),
+            Ok(Command::Surface {
+                input: "mesh".into(),
+                output: "walls.stl".into(),
+                patches: vec!["inlet".into(), "wall".into()],
+            })
+        );
+        assert!(parse_args(&args("surface mesh")).is_err());
+        assert!(parse_args(&args("check")).is_err());
+        assert!(parse_args(&args("stats a b")).is_err());
+        assert!(parse_args(&args("refine a")).is_err());
+        assert!(parse_args(&[]).is_err());
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::of(Path::new("constant/polyMesh")), Format::PolyMesh);
+        assert_eq!(Format::of(Path::new("mesh.dmesh")), Format::Binary);
+        assert_eq!(Format::of(Path::new("mesh.msh")), Format::Gmsh);
+        assert_eq!(Format::of(Path::new("mesh.vtu")), Format::Vtu);
+        assert!(read_mesh(Path::new("mesh.vtu")).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//! Flux-aware convection schemes that need cell gradients, registered by
+//! their case-file names with the field interpolation scheme registry.
+
+use dugong_fields::{FieldError, SurfaceField, SurfaceInterpolation, VolField};
+use dugong_runtime::Value;
+use dugong_types::FieldValue;
+
+use crate::fvc::{GradScheme, Gradient, new_grad_scheme};
+
+mod cubic;
+mod limited;
+mod limiters;
+mod linear_upwind;
+mod quick;
+
+pub use cubic::{Cubic, FourthOrderMidPoint};
+pub use limited::{FluxLimiter, Limited};
+pub use limiters::{LimitedLinear, Minmod, SuperBee, VanLeer};
+pub use linear_upwind::LinearUpwind;
+pub use quick::Quick;
+
+/// Registers the constructor `$constructor` under `$name` for each listed
+/// value type; the value type is inferred from the factory.
+macro_rules! register_convection_scheme {
+    ($name:literal, $constructor:path, [$($ty:ty),*]) => {
+        $(
+            inventory::submit! {
+                dugong_fields::SurfaceInterpolationFactory::<$ty> {
+                    name: $name,
+                    constructor: $constructor,
+                }
+            }
+        )*
+    };
+}
+
+use register_convection_scheme;
+
+/// Builds the gradient scheme following the name of a convection scheme,
+/// or `default` if there are no tokens or a single word that names no
+/// gradient scheme, such as the gradient term `grad(U)` OpenFOAM case
+/// files put there.
+fn gradient_scheme<T: Gradient>(
+    args: &[Value],
+    default: impl FnOnce() -> Box<dyn GradScheme<T>>,
+) -> Result<Box<dyn GradScheme<T>>, FieldError> {
+    match args {
+        [] => Ok(default()),
+        [Value::Word(name)] if T::grad_schemes().all(|f| f.name != name) => Ok(default()),
+        _ => new_grad_scheme(args),
+    }
+}
+
+/// Returns the deferred correction `F_f (φ_f − φ_f^L)` of each internal
+/// face: the flux `flux` times the difference between the face values of
+/// `scheme` and those of the `low_order` scheme whose weights build the
+/// matrix, at the current values of `field`.
+///
+/// Pass it to [`FvMatrix::add_deferred_correction`](crate::FvMatrix::add_deferred_correction)
+/// to keep the bounded low-order stencil in the matrix while converging to
+/// the solution of `scheme`.
+pub fn deferred_correction<T: FieldValue>(
+    field: &VolField<'_, T>,
+    flux: &SurfaceField<'_, f64>,
+    scheme: &dyn SurfaceInterpolation<T>,
+    low_order: &dyn SurfaceInterpolation<T>,
+) -> Vec<T> {
+    let high = scheme.interpolate(field, flux);
+    let low = low_order.interpolate(field, flux);
+    high.iter()
+        .zip(low)
+        .zip(flux.values())
+        .map(|((&h, l), &phi)| (h - l) * phi)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_fields::{Dimensions, Linear, Upwind};
+
+    use super::*;
+    use crate::test_meshes::{box_mesh, field_of};
+
+    #[test]
+    fn test_deferred_correction_is_flux_times_value_difference() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let t = field_of(&mesh, "T", Dimensions::default(), |x| x.x() * x.x());
+        let fluxes = mesh.face_areas().iter().map(|s| -2.0 * s.x()).collect();
+        let phi = SurfaceField::new(&mesh, "phi", Dimensions::default(), fluxes).unwrap();
+        // Flow towards −x: upwind takes the neighbor, 2.25 and 6.25.
+        let correction = deferred_correction(&t, &phi, &Linear, &Upwind);
+        assert!((correction[0] - -2.0 * (1.25 - 2.25)).abs() < 1e-12);
+        assert!((correction[1] - -2.0 * (4.25 - 6.25)).abs() < 1e-12);
+        assert!(
+            deferred_correction(&t, &phi, &Upwind, &Upwind)
+                .iter()
+                .all(|&c| c == 0.0)
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use dugong_fields::FieldError;
+use dugong_runtime::Value;
+use dugong_types::tensor::Vector;
+
+use crate::convection_schemes::limited::{FluxLimiter, Limited};
+use crate::convection_schemes::register_convection_scheme;
+
+/// Rejects tokens after the name of a limiter that takes none.
+fn no_arguments(name: &str, args: &[Value]) -> Result<(), FieldError> {
+    if args.is_empty() {
+        Ok(())
+    } else {
+        Err(FieldError::InvalidEntry {
+            keyword: name.to_string(),
+            reason: "unexpected arguments".into(),
+        })
+    }
+}
+
+/// van Leer's smooth limiter (`vanLeer`), `ψ = (r + |r|) / (1 + |r|)`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VanLeer;
+
+impl FluxLimiter for VanLeer {
+    const NAME: &'static str = "vanLeer";
+
+    fn from_args(args: &[Value]) -> Result<Self, FieldError> {
+        no_arguments(Self::NAME, args)?;
+        Ok(VanLeer)
+    }
+
+    fn limiter(&self, r: f64) -> f64 {
+        (r + r.abs()) / (1.0 + r.abs())
+    }
+}
+
+/// The most diffusive second-order limiter (`Minmod`),
+/// `ψ = max(0, min(r, 1))`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Minmod;
+
+impl FluxLimiter for Minmod {
+    const NAME: &'static str = "Minmod";
+
+    fn from_args(args: &[Value]) -> Result<Self, FieldError> {
+        no_arguments(Self::NAME, args)?;
+        Ok(Minmod)
+    }
+
+    fn limiter(&self, r: f64) -> f64 {
+        r.clamp(0.0, 1.0)
+    }
+}
+
+/// Roe's least diffusive limiter (`SuperBee`),
+/// `ψ = max(0, min(2r, 1), min(r, 2))`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SuperBee;
+
+impl FluxLimiter for SuperBee {
+    const NAME: &'static str = "SuperBee";
+
+    fn from_args(args: &[Value]) -> Result<Self, FieldError> {
+        no_arguments(Self::NAME, args)?;
+        Ok(SuperBee)
+    }
+
+    fn limiter(&self, r: f64) -> f64 {
+        (2.0 * r).min(1.0).max(r.min(2.0)).max(0.0)
+    }
+}
+
+/// Linear interpolation limited towards upwind where `r < k / 2`
+/// (`limitedLinear 1`), `ψ = max(0, min(2r / k, 1))`.
+///
+/// The coefficient `k` in `[0, 1]` trades accuracy for boundedness:
+/// smaller values switch to linear interpolation sooner, and only `k = 1`
+/// stays inside Sweby's TVD region.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LimitedLinear {
+    coefficient: f64,
+}
+
+impl LimitedLinear {
+    /// Builds the limiter with the coefficient `k`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::InvalidEntry`] if `coefficient` is not in
+    /// `[0, 1]`.
+    pub fn new(coefficient: f64) -> Result<Self, FieldError> {
+        if !(0.0..=1.0).contains(&coefficient) {
+            return Err(FieldError::InvalidEntry {
+                keyword: Self::NAME.into(),
+                reason: format!("coefficient {coefficient} is not between 0 and 1"),
+            });
+        }
+        Ok(Self { coefficient })
+    }
+
+    /// Returns the coefficient `k`.
+    pub fn coefficient(&self) -> f64 {
+        self.coefficient
+    }
+}
+
+impl FluxLimiter for LimitedLinear {
+    const NAME: &'static str = "limitedLinear";
+
+    fn from_args(args: &[Value]) -> Result<Self, FieldError> {
+        match args {
+            [k] => Self::new(k.as_scalar().ok_or_else(|| FieldError::InvalidEntry {
+                keyword: Self::NAME.into(),
+                reason: format!("expected a coefficient, found {k}"),
+            })?),
+            _ => Err(FieldError::InvalidEntry {
+                keyword: Self::NAME.into(),
+                reason: "expected a coefficient".into(),
+            }),
+        }
+    }
+
+    fn limiter(&self, r: f64) -> f64 {
+        // k / 2 is kept positive so that k = 0 does not divide by zero.
+        let half_k = (0.5 * self.coefficient).max(f64::MIN_POSITIVE);
+        (r / half_k).clamp(0.0, 1.0)
+    }
+}
+
+register_convection_scheme!("vanLeer", Limited::<_, VanLeer>::from_args, [f64, Vector]);
+register_convection_scheme!("Minmod", Limited::<_, Minmod>::from_args, [f64, Vector]);
+register_convection_scheme!("SuperBee", Limited::<_, SuperBee>::from_args, [f64, Vector]);
+register_convection_scheme!(
+    "limitedLinear",
+    Limited::<_, LimitedLinear>::from_args,
+    [f64, Vector]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Samples of `r` across the Sweby diagram.
+    fn ratios() -> impl Iterator<Item = f64> {
+        (-40..=80).map(|i| f64::from(i) * 0.1)
+    }
+
+    /// Checks that `limiter` lies in Sweby's TVD region and passes through
+    /// `ψ(1) = 1`.
+    fn assert_tvd(limiter: &impl FluxLimiter) {
+        for r in ratios() {
+            let psi = limiter.limiter(r);
+            if r <= 0.0 {
+                assert_eq!(psi, 0.0, "{} at r = {r}", limiter_name(limiter));
+            } else {
+                let bound = (2.0 * r).min(2.0) + 1e-12;
+                assert!(
+                    (0.0..=bound).contains(&psi),
+                    "{} at r = {r}",
+                    limiter_name(limiter)
+                );
+            }
+        }
+    }
+
+    fn limiter_name<L: FluxLimiter>(_: &L) -> &'static str {
+        L::NAME
+    }
+
+    #[test]
+    fn test_limiters_lie_in_sweby_tvd_region() {
+        assert_tvd(&VanLeer);
+        assert_tvd(&Minmod);
+        assert_tvd(&SuperBee);
+        assert_tvd(&LimitedLinear::new(1.0).unwrap());
+        for psi in [
+            VanLeer.limiter(1.0),
+            Minmod.limiter(1.0),
+            SuperBee.limiter(1.0),
+        ] {
+            assert!((psi - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_second_order_limiters_lie_between_minmod_and_superbee() {
+        for r in ratios().filter(|&r| r > 0.0) {
+            let (lower, upper) = (Minmod.limiter(r), SuperBee.limiter(r));
+            let psi = VanLeer.limiter(r);
+            assert!(lower - 1e-12 <= psi && psi <= upper + 1e-12, "r = {r}");
+        }
+        assert_eq!(SuperBee.limiter(0.25), 0.5);
+        assert_eq!(SuperBee.limiter(1.5), 1.5);
+        assert_eq!(SuperBee.limiter(5.0), 2.0);
+        assert_eq!(Minmod.limiter(0.5), 0.5);
+        assert!((VanLeer.limiter(3.0) - 1.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_limited_linear_switches_at_half_coefficient() {
+        let limiter = LimitedLinear::new(1.0).unwrap();
+        assert_eq!(limiter.limiter(0.25), 0.5);
+        assert_eq!(limiter.limiter(0.5), 1.0);
+        assert_eq!(limiter.limiter(4.0), 1.0);
+        assert_eq!(LimitedLinear::new(0.2).unwrap().limiter(0.1), 1.0);
+        assert_eq!(LimitedLinear::new(0.0).unwrap().limiter(1e-3), 1.0);
+        assert!(LimitedLinear::new(1.5).is_err());
+        assert!(LimitedLinear::from_args(&[]).is_err());
+        assert_eq!(
+            LimitedLinear::from_args(&[Value::Label(1)])
+                .unwrap()
+                .coefficient(),
+            1.0
+        );
+        assert!(VanLeer::from_args(&[Value::Label(1)]).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use dugong_fields::{FieldError, Linear, SurfaceField, SurfaceInterpolation, VolField};
+use dugong_runtime::Value;
+use dugong_types::tensor::Vector;
+
+use crate::convection_schemes::{gradient_scheme, register_convection_scheme};
+use crate::fvc::{CellLimited, Gauss, GradScheme, Gradient, Limiter};
+
+/// Extrapolates the upstream cell value to the face with its gradient
+/// (`linearUpwind`), `φ_f = φ_U + (x_f − x_U) · ∇φ_U`.
+///
+/// Second-order accurate. The gradients come from a gradient scheme given
+/// after the name (`linearUpwind cellLimited Gauss linear 1`), by default
+/// the limited `cellLimited Gauss linear 1`, which keeps the face values
+/// within the range of the neighboring cell values.
+#[derive(Debug)]
+pub struct LinearUpwind<T: Gradient> {
+    gradient: Box<dyn GradScheme<T>>,
+}
+
+impl<T: Gradient> LinearUpwind<T> {
+    /// Extrapolates with the gradients of `gradient`.
+    pub fn new(gradient: Box<dyn GradScheme<T>>) -> Self {
+        Self { gradient }
+    }
+
+    /// Returns the gradient scheme.
+    pub fn gradient(&self) -> &dyn GradScheme<T> {
+        self.gradient.as_ref()
+    }
+
+    /// Builds the scheme, with the gradient scheme the arguments specify.
+    pub fn from_args(args: &[Value]) -> Result<Box<dyn SurfaceInterpolation<T>>, FieldError> {
+        let gradient = gradient_scheme(args, || {
+            let gauss = Box::new(Gauss::new(Box::new(Linear)));
+            // Safety: 1 is a valid coefficient.
+            Box::new(CellLimited::new(gauss, Limiter::BarthJespersen, 1.0).unwrap())
+        })?;
+        Ok(Box::new(Self::new(gradient)))
+    }
+}
+
+impl<T: Gradient> SurfaceInterpolation<T> for LinearUpwind<T> {
+    fn type_name(&self) -> &'static str {
+        "linearUpwind"
+    }
+
+    fn weights(&self, field: &VolField<'_, T>, flux: &SurfaceField<'_, f64>) -> Vec<f64> {
+        flux.values()[..field.mesh().n_internal_faces()]
+            .iter()
+            .map(|&phi| if phi >= 0.0 { 1.0 } else { 0.0 })
+            .collect()
+    }
+
+    fn correction(&self, field: &VolField<'_, T>, flux: &SurfaceField<'_, f64>) -> Option<Vec<T>> {
+        let mesh = field.mesh();
+        let gradients = self.gradient.grad(field);
+        let gradients = gradients.internal();
+        let (centers, face_centers) = (mesh.cell_centers(), mesh.face_centers());
+        let correction = mesh
+            .neighbor()
+            .iter()
+            .enumerate()
+            .map(|(f, &n)| {
+                let upwind = if flux.values()[f] >= 0.0 {
+                    mesh.owner()[f]
+                } else {
+                    n
+                };
+                T::along(face_centers[f] - centers[upwind], gradients[upwind])
+            })
+            .collect();
+        Some(correction)
+    }
+}
+
+register_convection_scheme!("linearUpwind", LinearUpwind::from_args, [f64, Vector]);
+
+#[cfg(test)]
+mod tests {
+    use dugong_fields::{Dimensions, new_surface_interpolation};
+
+    use super::*;
+    use crate::test_meshes::{box_mesh, field_of, spec};
+
+    #[test]
+    fn test_linear_upwind_is_exact_for_linear_fields() {
+        let mesh = box_mesh([4, 2, 1], [4.0, 2.0, 1.0]);
+        let u = field_of(&mesh, "U", Dimensions::default(), |x| {
+            Vector::new(x.x() + x.y(), 2.0 * x.x(), 0.0)
+        });
+        for words in [&["linearUpwind"][..], &["linearUpwind", "grad(U)"]] {
+            let scheme = new_surface_interpolation::<Vector>(&spec(words)).unwrap();
+            assert_eq!(scheme.type_name(), "linearUpwind");
+            for sign in [1.0, -1.0] {
+                let flux = SurfaceField::uniform(&mesh, "phi", Dimensions::default(), sign);
+                let faces = u.interpolate(&flux, scheme.as_ref());
+                for (v, x) in faces.values().iter().zip(mesh.face_centers()) {
+                    let expected = Vector::new(x.x() + x.y(), 2.0 * x.x(), 0.0);
+                    assert!((*v - expected).mag() < 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_linear_upwind_limited_gradients_bound_face_values() {
+        let mesh = box_mesh([4, 1, 1], [4.0, 1.0, 1.0]);
+        let values = vec![0.0, 0.0, 1.0, 1.0];
+        let t = VolField::new(&mesh, "T", Dimensions::default(), values).unwrap();
+        let flux = SurfaceField::uniform(&mesh, "phi", Dimensions::default(), 1.0);
+
+        let unlimited = LinearUpwind::<f64>::from_args(&spec(&["Gauss", "linear"])).unwrap();
+        let faces = t.interpolate(&flux, unlimited.as_ref());
+        assert!((faces.internal()[2] - 1.25).abs() < 1e-12);
+
+        let limited = LinearUpwind::<f64>::from_args(&[]).unwrap();
+        let faces = t.interpolate(&flux, limited.as_ref());
+        for &v in faces.internal() {
+            assert!((0.0..=1.0).contains(&v));
+        }
+    }
+}
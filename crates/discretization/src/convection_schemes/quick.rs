@@ -0,0 +1,140 @@
+use dugong_fields::{
+    FieldError, Linear, SurfaceField, SurfaceInterpolation, VolField, linear_weights,
+};
+use dugong_runtime::Value;
+use dugong_types::tensor::Vector;
+
+use crate::convection_schemes::{gradient_scheme, register_convection_scheme};
+use crate::fvc::{Gauss, GradScheme, Gradient};
+
+/// Leonard's quadratic upstream interpolation (`QUICK`),
+///
+/// ```text
+/// φ_f = (φ_L + φ_C + (1 − w_C) d · ∇φ_C) / 2
+/// ```
+///
+/// with `C` the upstream cell, `w_C` its linear weight, `φ_L` the linearly
+/// interpolated value and `d` the vector from `C` to the downstream cell.
+/// On uniform meshes this is the parabola through the downstream, upstream
+/// and far-upstream values, `(6 φ_C + 3 φ_D − φ_UU) / 8`, with the
+/// far-upstream value reconstructed from the gradient. Third-order
+/// accurate there but unbounded.
+///
+/// The gradients come from a gradient scheme given after the name
+/// (`QUICK leastSquares`), by default `Gauss linear`.
+#[derive(Debug)]
+pub struct Quick<T: Gradient> {
+    gradient: Box<dyn GradScheme<T>>,
+}
+
+impl<T: Gradient> Quick<T> {
+    /// Reconstructs the far-upstream values with the gradients of
+    /// `gradient`.
+    pub fn new(gradient: Box<dyn GradScheme<T>>) -> Self {
+        Self { gradient }
+    }
+
+    /// Returns the gradient scheme.
+    pub fn gradient(&self) -> &dyn GradScheme<T> {
+        self.gradient.as_ref()
+    }
+
+    /// Builds the scheme, with the gradient scheme the arguments specify.
+    pub fn from_args(args: &[Value]) -> Result<Box<dyn SurfaceInterpolation<T>>, FieldError> {
+        let gradient = gradient_scheme(args, || Box::new(Gauss::new(Box::new(Linear))))?;
+        Ok(Box::new(Self::new(gradient)))
+    }
+}
+
+impl<T: Gradient> SurfaceInterpolation<T> for Quick<T> {
+    fn type_name(&self) -> &'static str {
+        "QUICK"
+    }
+
+    fn weights(&self, field: &VolField<'_, T>, flux: &SurfaceField<'_, f64>) -> Vec<f64> {
+        let n = field.mesh().n_internal_faces();
+        linear_weights(field.mesh())[..n]
+            .iter()
+            .zip(flux.values())
+            .map(|(&w, &phi)| if phi >= 0.0 { 0.5 * (w + 1.0) } else { 0.5 * w })
+            .collect()
+    }
+
+    fn correction(&self, field: &VolField<'_, T>, flux: &SurfaceField<'_, f64>) -> Option<Vec<T>> {
+        let mesh = field.mesh();
+        let weights = linear_weights(mesh);
+        let gradients = self.gradient.grad(field);
+        let gradients = gradients.internal();
+        let centers = mesh.cell_centers();
+        let correction = mesh
+            .neighbor()
+            .iter()
+            .enumerate()
+            .map(|(f, &n)| {
+                let o = mesh.owner()[f];
+                let d = centers[n] - centers[o];
+                if flux.values()[f] >= 0.0 {
+                    T::along(d, gradients[o]) * (0.5 * (1.0 - weights[f]))
+                } else {
+                    T::along(-d, gradients[n]) * (0.5 * weights[f])
+                }
+            })
+            .collect();
+        Some(correction)
+    }
+}
+
+register_convection_scheme!("QUICK", Quick::from_args, [f64, Vector]);
+
+#[cfg(test)]
+mod tests {
+    use dugong_fields::{Dimensions, new_surface_interpolation};
+
+    use super::*;
+    use crate::test_meshes::{box_mesh, field_of};
+
+    #[test]
+    fn test_quick_is_exact_for_quadratic_on_uniform_mesh() {
+        // Gauss gradients are exact away from the boundary cells.
+        let mesh = box_mesh([5, 1, 1], [5.0, 1.0, 1.0]);
+        let t = field_of(&mesh, "T", Dimensions::default(), |x| x.x() * x.x());
+        let scheme = new_surface_interpolation::<f64>(&[Value::Word("QUICK".into())]).unwrap();
+        assert_eq!(scheme.type_name(), "QUICK");
+        let faces_at = |sign: f64| {
+            let flux = SurfaceField::uniform(&mesh, "phi", Dimensions::default(), sign);
+            t.interpolate(&flux, scheme.as_ref()).internal().to_vec()
+        };
+        let (forward, backward) = (faces_at(1.0), faces_at(-1.0));
+        let exact: Vec<f64> = mesh.face_centers()[..4]
+            .iter()
+            .map(|x| x.x() * x.x())
+            .collect();
+        // Faces whose upstream cell is not a boundary cell.
+        for (v, e) in forward.iter().zip(&exact).skip(1) {
+            assert!((v - e).abs() < 1e-12);
+        }
+        for (v, e) in backward.iter().zip(&exact).take(3) {
+            assert!((v - e).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_quick_is_exact_for_linear_fields_on_stretched_mesh() {
+        let mut mesh = box_mesh([5, 1, 1], [5.0, 1.0, 1.0]);
+        let points = mesh
+            .points()
+            .iter()
+            .map(|p| Vector::new(0.2 * p.x() * p.x(), p.y(), p.z()))
+            .collect();
+        mesh.move_points(points).unwrap();
+        let t = field_of(&mesh, "T", Dimensions::default(), |x| 3.0 * x.x() - 1.0);
+        let scheme = Quick::<f64>::from_args(&[Value::Word("leastSquares".into())]).unwrap();
+        for sign in [1.0, -1.0] {
+            let flux = SurfaceField::uniform(&mesh, "phi", Dimensions::default(), sign);
+            let faces = t.interpolate(&flux, scheme.as_ref());
+            for (v, x) in faces.values().iter().zip(mesh.face_centers()) {
+                assert!((v - (3.0 * x.x() - 1.0)).abs() < 1e-12);
+            }
+        }
+    }
+}
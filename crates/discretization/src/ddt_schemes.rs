@@ -0,0 +1,228 @@
+//! Time derivative schemes discretizing `∂ψ/∂t` from the old-time levels
+//! of a field, selectable by their case-file names in `ddtSchemes`.
+
+use std::fmt::Debug;
+
+use dugong_fields::{FieldError, InterpolationValue, VolField};
+use dugong_mesh::Mesh;
+use dugong_runtime::{Dictionary, Value};
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::fv_matrix::FvMatrix;
+
+mod backward;
+mod crank_nicolson;
+mod euler;
+mod steady_state;
+
+pub use backward::Backward;
+pub use crank_nicolson::CrankNicolson;
+pub use euler::Euler;
+pub use steady_state::SteadyState;
+
+/// The time step a time derivative is discretized over.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeStep {
+    /// The index of the time step, increased by one per step.
+    pub index: usize,
+    /// The time step size `Δt`.
+    pub delta_t: f64,
+    /// The size `Δt₀` of the previous time step.
+    pub delta_t0: f64,
+}
+
+impl TimeStep {
+    /// Returns step `index` of size `delta_t`, following a step of the same
+    /// size.
+    pub fn new(index: usize, delta_t: f64) -> Self {
+        Self {
+            index,
+            delta_t,
+            delta_t0: delta_t,
+        }
+    }
+}
+
+/// A scheme discretizing the time derivative of a field.
+pub trait DdtScheme<T: TimeDerivative>: Debug + Send + Sync {
+    /// Returns the name used in case files, such as `Euler`.
+    fn type_name(&self) -> &'static str;
+
+    /// Discretizes the volume-integrated time derivative `∂(Vψ)/∂t` of
+    /// `field` over `time_step` into matrix coefficients.
+    fn fvm_ddt<'mesh>(
+        &self,
+        field: &VolField<'mesh, T>,
+        time_step: &TimeStep,
+    ) -> FvMatrix<'mesh, T>;
+}
+
+/// Builds a time derivative scheme from the tokens following its name in a
+/// case file, such as `0.9` in `CrankNicolson 0.9`.
+pub type DdtSchemeConstructor<T> = fn(&[Value]) -> Result<Box<dyn DdtScheme<T>>, FieldError>;
+
+/// A time derivative scheme selectable by name at run time.
+///
+/// Submit one per value type with `inventory::submit!`; the first word of
+/// a scheme specification selects it.
+pub struct DdtSchemeFactory<T: 'static> {
+    /// The scheme name used in case files.
+    pub name: &'static str,
+    /// Builds the scheme.
+    pub constructor: DdtSchemeConstructor<T>,
+}
+
+inventory::collect!(DdtSchemeFactory<f64>);
+inventory::collect!(DdtSchemeFactory<Vector>);
+inventory::collect!(DdtSchemeFactory<Tensor>);
+inventory::collect!(DdtSchemeFactory<SymmTensor>);
+inventory::collect!(DdtSchemeFactory<SphericalTensor>);
+
+/// Value types with a registry of run-time selectable time derivative
+/// schemes.
+pub trait TimeDerivative: InterpolationValue {
+    /// Iterates over the registered time derivative schemes.
+    fn ddt_schemes() -> impl Iterator<Item = &'static DdtSchemeFactory<Self>>;
+}
+
+macro_rules! impl_time_derivative {
+    ($($ty:ty),*) => {
+        $(
+            impl TimeDerivative for $ty {
+                fn ddt_schemes() -> impl Iterator<Item = &'static DdtSchemeFactory<Self>> {
+                    inventory::iter::<DdtSchemeFactory<Self>>.into_iter()
+                }
+            }
+        )*
+    };
+}
+
+impl_time_derivative!(f64, Vector, Tensor, SymmTensor, SphericalTensor);
+
+/// Registers the constructor `$constructor` under `$name` for each listed
+/// value type; the value type is inferred from the factory.
+macro_rules! register_ddt_scheme {
+    ($name:literal, $constructor:path, [$($ty:ty),*]) => {
+        $(
+            inventory::submit! {
+                $crate::ddt_schemes::DdtSchemeFactory::<$ty> {
+                    name: $name,
+                    constructor: $constructor,
+                }
+            }
+        )*
+    };
+}
+
+use register_ddt_scheme;
+
+/// Builds the time derivative scheme named by the first word of `spec`,
+/// passing it the remaining tokens.
+///
+/// # Errors
+///
+/// Returns [`FieldError::InvalidEntry`] if `spec` does not start with a
+/// word, [`FieldError::UnknownScheme`] if no scheme of that name is
+/// registered for `T`, and any error of the scheme's constructor.
+pub fn new_ddt_scheme<T: TimeDerivative>(
+    spec: &[Value],
+) -> Result<Box<dyn DdtScheme<T>>, FieldError> {
+    let (name, args) = match spec {
+        [Value::Word(name), args @ ..] => (name, args),
+        _ => {
+            return Err(FieldError::InvalidEntry {
+                keyword: "scheme".into(),
+                reason: "expected a scheme name".into(),
+            });
+        }
+    };
+    let factory =
+        T::ddt_schemes()
+            .find(|f| f.name == name)
+            .ok_or_else(|| FieldError::UnknownScheme {
+                name: name.to_string(),
+            })?;
+    (factory.constructor)(args)
+}
+
+/// Builds the scheme selected for `term` in a `ddtSchemes` dictionary,
+/// falling back to its `default` entry.
+///
+/// # Errors
+///
+/// Returns [`FieldError::InvalidEntry`] if neither `term` nor `default` is
+/// present, and the errors of [`new_ddt_scheme`].
+pub fn select_ddt_scheme<T: TimeDerivative>(
+    schemes: &Dictionary,
+    term: &str,
+) -> Result<Box<dyn DdtScheme<T>>, FieldError> {
+    let spec = schemes
+        .get(term)
+        .or_else(|| schemes.get("default"))
+        .ok_or_else(|| FieldError::InvalidEntry {
+            keyword: term.to_string(),
+            reason: "no scheme and no default".into(),
+        })?;
+    new_ddt_scheme(spec)
+}
+
+/// Returns the cell volumes at the current, old and old-old time levels,
+/// which are all the current volumes on a static mesh.
+fn volumes(mesh: &Mesh) -> (&[f64], &[f64], &[f64]) {
+    let v = mesh.cell_volumes();
+    let v0 = mesh.old_cell_volumes().unwrap_or(v);
+    let v00 = mesh.old_old_cell_volumes().unwrap_or(v0);
+    (v, v0, v00)
+}
+
+/// Rejects tokens after the name of a scheme that takes none.
+fn no_arguments(name: &str, args: &[Value]) -> Result<(), FieldError> {
+    if args.is_empty() {
+        Ok(())
+    } else {
+        Err(FieldError::InvalidEntry {
+            keyword: name.to_string(),
+            reason: "unexpected arguments".into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::spec;
+
+    #[test]
+    fn test_select_ddt_scheme_falls_back_to_default() {
+        let mut schemes = Dictionary::new();
+        schemes.insert("default", spec(&["Euler"]));
+        schemes.insert(
+            "ddt(U)",
+            vec![Value::Word("CrankNicolson".into()), Value::Scalar(0.9)],
+        );
+        let u = select_ddt_scheme::<Vector>(&schemes, "ddt(U)").unwrap();
+        assert_eq!(u.type_name(), "CrankNicolson");
+        let t = select_ddt_scheme::<f64>(&schemes, "ddt(T)").unwrap();
+        assert_eq!(t.type_name(), "Euler");
+        for name in ["backward", "steadyState"] {
+            assert_eq!(
+                new_ddt_scheme::<f64>(&spec(&[name])).unwrap().type_name(),
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_new_ddt_scheme_rejects_bad_specs() {
+        assert!(matches!(
+            new_ddt_scheme::<f64>(&spec(&["localEuler"])),
+            Err(FieldError::UnknownScheme { .. })
+        ));
+        assert!(matches!(
+            new_ddt_scheme::<f64>(&[]),
+            Err(FieldError::InvalidEntry { .. })
+        ));
+        assert!(new_ddt_scheme::<f64>(&spec(&["Euler", "linear"])).is_err());
+        assert!(new_ddt_scheme::<f64>(&spec(&["CrankNicolson"])).is_err());
+    }
+}
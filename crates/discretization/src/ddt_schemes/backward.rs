@@ -0,0 +1,96 @@
+use dugong_fields::{Dimensions, FieldError, VolField};
+use dugong_runtime::Value;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::ddt_schemes::{
+    DdtScheme, TimeDerivative, TimeStep, no_arguments, register_ddt_scheme, volumes,
+};
+use crate::fv_matrix::FvMatrix;
+
+/// Second-order backward differencing over the old and old-old time levels
+/// (`backward`), for variable time steps:
+///
+/// ```text
+/// ∂(Vψ)/∂t ≈ (c V ψ − c₀ V₀ ψ₀ + c₀₀ V₀₀ ψ₀₀) / Δt
+/// c = 1 + Δt / (Δt + Δt₀),  c₀₀ = Δt² / (Δt₀ (Δt + Δt₀)),  c₀ = c + c₀₀
+/// ```
+///
+/// Falls back to [`Euler`](crate::ddt_schemes::Euler) while fewer than two
+/// old-time levels are stored.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Backward;
+
+impl Backward {
+    /// Builds the scheme; it takes no arguments.
+    pub fn from_args<T: TimeDerivative>(
+        args: &[Value],
+    ) -> Result<Box<dyn DdtScheme<T>>, FieldError> {
+        no_arguments("backward", args)?;
+        Ok(Box::new(Backward))
+    }
+}
+
+impl<T: TimeDerivative> DdtScheme<T> for Backward {
+    fn type_name(&self) -> &'static str {
+        "backward"
+    }
+
+    fn fvm_ddt<'mesh>(
+        &self,
+        field: &VolField<'mesh, T>,
+        time_step: &TimeStep,
+    ) -> FvMatrix<'mesh, T> {
+        let rate = Dimensions::mlt(0, 3, -1);
+        let mut matrix = FvMatrix::new(field, field.dimensions() * rate);
+        let (v, v0, v00) = volumes(field.mesh());
+        let (dt, dt0) = (time_step.delta_t, time_step.delta_t0);
+        let (c, c00) = if field.n_old_times() < 2 {
+            (1.0, 0.0)
+        } else {
+            (1.0 + dt / (dt + dt0), dt * dt / (dt0 * (dt + dt0)))
+        };
+        let c0 = c + c00;
+        let (old, old_old) = (field.old_time().internal(), field.old_old_time().internal());
+        for (d, &v) in matrix.diag_mut().iter_mut().zip(v) {
+            *d = c * v / dt;
+        }
+        for (i, b) in matrix.source_mut().iter_mut().enumerate() {
+            *b = (old[i] * (c0 * v0[i]) - old_old[i] * (c00 * v00[i])) * (1.0 / dt);
+        }
+        matrix
+    }
+}
+
+register_ddt_scheme!(
+    "backward",
+    Backward::from_args,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_backward_is_exact_for_quadratic_in_time() {
+        // ψ = t² at t = 0, 1 and 3: ∂ψ/∂t = 6 with Δt = 2, Δt₀ = 1.
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut t = VolField::uniform(&mesh, "T", Dimensions::default(), 0.0);
+        t.store_old_time();
+        t.set_uniform(1.0);
+        let euler = Backward.fvm_ddt(&t, &TimeStep::new(1, 1.0));
+        assert!((euler.evaluate(&t)[0] - 1.0).abs() < 1e-12);
+
+        t.store_old_time();
+        t.set_uniform(9.0);
+        let time_step = TimeStep {
+            index: 2,
+            delta_t: 2.0,
+            delta_t0: 1.0,
+        };
+        for ddt in Backward.fvm_ddt(&t, &time_step).evaluate(&t) {
+            assert!((ddt - 6.0).abs() < 1e-12);
+        }
+    }
+}
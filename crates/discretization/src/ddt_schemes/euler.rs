@@ -0,0 +1,74 @@
+use dugong_fields::{Dimensions, FieldError, VolField};
+use dugong_runtime::Value;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::ddt_schemes::{
+    DdtScheme, TimeDerivative, TimeStep, no_arguments, register_ddt_scheme, volumes,
+};
+use crate::fv_matrix::FvMatrix;
+
+/// First-order implicit Euler differencing (`Euler`),
+/// `∂(Vψ)/∂t ≈ (V ψ − V₀ ψ₀) / Δt`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Euler;
+
+impl Euler {
+    /// Builds the scheme; it takes no arguments.
+    pub fn from_args<T: TimeDerivative>(
+        args: &[Value],
+    ) -> Result<Box<dyn DdtScheme<T>>, FieldError> {
+        no_arguments("Euler", args)?;
+        Ok(Box::new(Euler))
+    }
+}
+
+impl<T: TimeDerivative> DdtScheme<T> for Euler {
+    fn type_name(&self) -> &'static str {
+        "Euler"
+    }
+
+    fn fvm_ddt<'mesh>(
+        &self,
+        field: &VolField<'mesh, T>,
+        time_step: &TimeStep,
+    ) -> FvMatrix<'mesh, T> {
+        let rate = Dimensions::mlt(0, 3, -1);
+        let mut matrix = FvMatrix::new(field, field.dimensions() * rate);
+        let (v, v0, _) = volumes(field.mesh());
+        let old = field.old_time().internal();
+        let r_dt = 1.0 / time_step.delta_t;
+        for (d, &v) in matrix.diag_mut().iter_mut().zip(v) {
+            *d = v * r_dt;
+        }
+        for ((b, &v0), &old) in matrix.source_mut().iter_mut().zip(v0).zip(old) {
+            *b = old * (v0 * r_dt);
+        }
+        matrix
+    }
+}
+
+register_ddt_scheme!(
+    "Euler",
+    Euler::from_args,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_euler_differences_old_time_level() {
+        let mesh = box_mesh([2, 1, 1], [1.0, 0.5, 1.0]);
+        let mut t = VolField::uniform(&mesh, "T", Dimensions::default(), 1.0);
+        t.store_old_time();
+        t.internal_mut().copy_from_slice(&[3.0, 2.0]);
+        let m = Euler.fvm_ddt(&t, &TimeStep::new(1, 0.5));
+        assert_eq!(m.dimensions(), Dimensions::mlt(0, 3, -1));
+        // V = 0.25: (V ψ − V ψ₀) / Δt.
+        let ddt = m.evaluate(&t);
+        assert!((ddt[0] - 1.0).abs() < 1e-12);
+        assert!((ddt[1] - 0.5).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,37 @@
+use dugong_fields::{Dimensions, FieldError, VolField};
+use dugong_runtime::Value;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::ddt_schemes::{DdtScheme, TimeDerivative, TimeStep, no_arguments, register_ddt_scheme};
+use crate::fv_matrix::FvMatrix;
+
+/// Drops the time derivative for steady-state solutions (`steadyState`).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SteadyState;
+
+impl SteadyState {
+    /// Builds the scheme; it takes no arguments.
+    pub fn from_args<T: TimeDerivative>(
+        args: &[Value],
+    ) -> Result<Box<dyn DdtScheme<T>>, FieldError> {
+        no_arguments("steadyState", args)?;
+        Ok(Box::new(SteadyState))
+    }
+}
+
+impl<T: TimeDerivative> DdtScheme<T> for SteadyState {
+    fn type_name(&self) -> &'static str {
+        "steadyState"
+    }
+
+    /// Returns a matrix of zeros.
+    fn fvm_ddt<'mesh>(&self, field: &VolField<'mesh, T>, _: &TimeStep) -> FvMatrix<'mesh, T> {
+        FvMatrix::new(field, field.dimensions() * Dimensions::mlt(0, 3, -1))
+    }
+}
+
+register_ddt_scheme!(
+    "steadyState",
+    SteadyState::from_args,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
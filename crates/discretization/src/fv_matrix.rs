@@ -0,0 +1,698 @@
+use std::ops::{Add, Neg, Sub};
+
+use dugong_fields::{Components, Dimensions, SurfaceField, VolField};
+use dugong_mesh::{Mesh, Patch};
+use dugong_types::FieldValue;
+
+/// The finite-volume discretization of a term of an equation for a field
+/// `ψ`, in lower-diagonal-upper form.
+///
+/// The matrix stands for the volume-integrated term
+///
+/// ```text
+/// ∫ term dV ≈ A ψ − b
+/// ```
+///
+/// so an equation `term = 0` is solved as `A ψ = b`. Row `P` of `A` holds
+/// the diagonal coefficient `a_P`; internal face `f` contributes the
+/// coefficient `upper[f]` of the neighbor value to the owner's row and
+/// `lower[f]` of the owner value to the neighbor's row.
+///
+/// Boundary faces are kept per patch: face `i` of patch `p` adds
+/// `internal_coeffs[p][i]` to the diagonal of its cell and
+/// `boundary_coeffs[p][i]` to the source.
+///
+/// Matrices of the terms of one equation add up with `+` and `-`, as in
+/// `fvm::ddt(&t, &step) - fvm::laplacian(&gamma, &t)`. The
+/// pressure–velocity algorithms work through [`a`](Self::a),
+/// [`h`](Self::h) and [`flux`](Self::flux), which split the equation as
+/// `A ψ = H` per unit volume.
+#[derive(Clone)]
+pub struct FvMatrix<'mesh, T: FieldValue> {
+    mesh: &'mesh Mesh,
+    psi: String,
+    psi_dimensions: Dimensions,
+    dimensions: Dimensions,
+    diag: Vec<f64>,
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+    source: Vec<T>,
+    internal_coeffs: Vec<Vec<f64>>,
+    boundary_coeffs: Vec<Vec<T>>,
+    face_corrections: Vec<T>,
+}
+
+impl<'mesh, T: FieldValue> FvMatrix<'mesh, T> {
+    /// Returns a matrix of zeros for `psi` with the given dimensions of the
+    /// volume-integrated term.
+    pub fn new(psi: &VolField<'mesh, T>, dimensions: Dimensions) -> Self {
+        let mesh = psi.mesh();
+        let n_faces = mesh.n_internal_faces();
+        let sizes = || mesh.patches().iter().map(|p| p.range().len());
+        Self {
+            mesh,
+            psi: psi.name().to_string(),
+            psi_dimensions: psi.dimensions(),
+            dimensions,
+            diag: vec![0.0; mesh.n_cells()],
+            lower: vec![0.0; n_faces],
+            upper: vec![0.0; n_faces],
+            source: vec![T::zero(); mesh.n_cells()],
+            internal_coeffs: sizes().map(|n| vec![0.0; n]).collect(),
+            boundary_coeffs: sizes().map(|n| vec![T::zero(); n]).collect(),
+            face_corrections: vec![T::zero(); n_faces],
+        }
+    }
+
+    /// Returns the mesh.
+    pub fn mesh(&self) -> &'mesh Mesh {
+        self.mesh
+    }
+
+    /// Returns the name of the field the matrix is for.
+    pub fn psi_name(&self) -> &str {
+        &self.psi
+    }
+
+    /// Returns the dimensions of the volume-integrated term.
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    /// Returns the diagonal coefficients, without the boundary
+    /// contributions.
+    pub fn diag(&self) -> &[f64] {
+        &self.diag
+    }
+
+    /// Returns the diagonal coefficients for modification.
+    pub fn diag_mut(&mut self) -> &mut [f64] {
+        &mut self.diag
+    }
+
+    /// Returns the coefficient of the owner value in the neighbor's row of
+    /// each internal face.
+    pub fn lower(&self) -> &[f64] {
+        &self.lower
+    }
+
+    /// Returns the lower coefficients for modification.
+    pub fn lower_mut(&mut self) -> &mut [f64] {
+        &mut self.lower
+    }
+
+    /// Returns the coefficient of the neighbor value in the owner's row of
+    /// each internal face.
+    pub fn upper(&self) -> &[f64] {
+        &self.upper
+    }
+
+    /// Returns the upper coefficients for modification.
+    pub fn upper_mut(&mut self) -> &mut [f64] {
+        &mut self.upper
+    }
+
+    /// Returns the source `b` of each cell, without the boundary
+    /// contributions.
+    pub fn source(&self) -> &[T] {
+        &self.source
+    }
+
+    /// Returns the source for modification.
+    pub fn source_mut(&mut self) -> &mut [T] {
+        &mut self.source
+    }
+
+    /// Returns the diagonal contributions of the faces of each patch.
+    pub fn internal_coeffs(&self) -> &[Vec<f64>] {
+        &self.internal_coeffs
+    }
+
+    /// Returns the diagonal contributions of the boundary faces for
+    /// modification.
+    pub fn internal_coeffs_mut(&mut self) -> &mut [Vec<f64>] {
+        &mut self.internal_coeffs
+    }
+
+    /// Returns the source contributions of the faces of each patch.
+    pub fn boundary_coeffs(&self) -> &[Vec<T>] {
+        &self.boundary_coeffs
+    }
+
+    /// Returns the source contributions of the boundary faces for
+    /// modification.
+    pub fn boundary_coeffs_mut(&mut self) -> &mut [Vec<T>] {
+        &mut self.boundary_coeffs
+    }
+
+    /// Returns the deferred-correction fluxes of the internal faces, summed
+    /// over the calls to
+    /// [`add_deferred_correction`](Self::add_deferred_correction).
+    pub fn face_corrections(&self) -> &[T] {
+        &self.face_corrections
+    }
+
+    /// Adds explicit face fluxes to the source by deferred correction.
+    ///
+    /// `fluxes` holds the part of the outward flux of each internal face
+    /// that the matrix coefficients leave out, such as the difference
+    /// between a high-order scheme and the low-order stencil of the matrix,
+    /// evaluated at the current values. It leaves the owner and enters the
+    /// neighbor, so the term stays conservative. Since matrices are
+    /// reassembled every outer iteration, the correction follows the latest
+    /// values and the converged solution is that of the full scheme.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fluxes` does not have one value per internal face.
+    pub fn add_deferred_correction(&mut self, fluxes: &[T]) {
+        assert_eq!(
+            fluxes.len(),
+            self.mesh.n_internal_faces(),
+            "one correction per internal face"
+        );
+        let (owner, neighbor) = (self.mesh.owner(), self.mesh.neighbor());
+        for (f, (&q, (&o, &n))) in fluxes.iter().zip(owner.iter().zip(neighbor)).enumerate() {
+            self.source[o] = self.source[o] - q;
+            self.source[n] = self.source[n] + q;
+            self.face_corrections[f] = self.face_corrections[f] + q;
+        }
+    }
+
+    /// Evaluates the volume-integrated term `A ψ − b` at the cell values of
+    /// `psi`, boundary contributions included.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `psi` is on another mesh.
+    pub fn evaluate(&self, psi: &VolField<'_, T>) -> Vec<T> {
+        self.check_mesh(psi);
+        let (owner, neighbor) = (self.mesh.owner(), self.mesh.neighbor());
+        let cells = psi.internal();
+        let mut result: Vec<T> = cells
+            .iter()
+            .zip(&self.diag)
+            .zip(&self.source)
+            .map(|((&v, &d), &b)| v * d - b)
+            .collect();
+        for (f, &n) in neighbor.iter().enumerate() {
+            let o = owner[f];
+            result[o] = result[o] + cells[n] * self.upper[f];
+            result[n] = result[n] + cells[o] * self.lower[f];
+        }
+        for ((patch, internal), boundary) in self.boundary_terms() {
+            for ((f, &a), &b) in patch.range().zip(internal).zip(boundary) {
+                let o = owner[f];
+                result[o] = result[o] + cells[o] * a - b;
+            }
+        }
+        result
+    }
+
+    /// Returns the diagonal coefficient of each cell per unit volume,
+    /// boundary contributions included, named `A(<psi>)`.
+    pub fn a(&self) -> VolField<'mesh, f64> {
+        let volumes = self.mesh.cell_volumes();
+        let values = self
+            .total_diag()
+            .into_iter()
+            .zip(volumes)
+            .map(|(d, v)| d / v)
+            .collect();
+        let volume = Dimensions::mlt(0, 3, 0);
+        // Safety: one value per cell.
+        VolField::new(
+            self.mesh,
+            format!("A({})", self.psi),
+            self.dimensions / (self.psi_dimensions * volume),
+            values,
+        )
+        .unwrap()
+    }
+
+    /// Returns the source less the off-diagonal contributions of the
+    /// neighbor values of `psi`, per unit volume, named `H(<psi>)`:
+    ///
+    /// ```text
+    /// H_P = (b_P − Σ_N a_N ψ_N) / V_P
+    /// ```
+    ///
+    /// with the boundary sources included, so that `A ψ − H` is the
+    /// evaluated term per unit volume and the solution satisfies
+    /// `ψ = H / A`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `psi` is on another mesh.
+    pub fn h(&self, psi: &VolField<'_, T>) -> VolField<'mesh, T> {
+        self.check_mesh(psi);
+        let (owner, neighbor) = (self.mesh.owner(), self.mesh.neighbor());
+        let cells = psi.internal();
+        let mut values = self.total_source();
+        for (f, &n) in neighbor.iter().enumerate() {
+            let o = owner[f];
+            values[o] = values[o] - cells[n] * self.upper[f];
+            values[n] = values[n] - cells[o] * self.lower[f];
+        }
+        for (h, &v) in values.iter_mut().zip(self.mesh.cell_volumes()) {
+            *h = *h * (1.0 / v);
+        }
+        // Safety: one value per cell.
+        VolField::new(
+            self.mesh,
+            format!("H({})", self.psi),
+            self.dimensions / Dimensions::mlt(0, 3, 0),
+            values,
+        )
+        .unwrap()
+    }
+
+    /// Returns the outward face fluxes of the term at the cell values of
+    /// `psi`, named `flux(<psi>)`, consistent with the matrix: the net
+    /// outward flux of each cell is its row of [`evaluate`](Self::evaluate)
+    /// when the term is conservative, as the operators of
+    /// [`fvm`](crate::fvm) are.
+    ///
+    /// Internal faces take `upper ψ_N − lower ψ_P` plus their deferred
+    /// correction; boundary faces take `internal ψ_P − boundary` from their
+    /// coefficients.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `psi` is on another mesh.
+    pub fn flux(&self, psi: &VolField<'_, T>) -> SurfaceField<'mesh, T> {
+        self.check_mesh(psi);
+        let (owner, neighbor) = (self.mesh.owner(), self.mesh.neighbor());
+        let cells = psi.internal();
+        let mut fluxes = vec![T::zero(); self.mesh.n_faces()];
+        for (f, &n) in neighbor.iter().enumerate() {
+            let o = owner[f];
+            fluxes[f] =
+                cells[n] * self.upper[f] - cells[o] * self.lower[f] + self.face_corrections[f];
+        }
+        for ((patch, internal), boundary) in self.boundary_terms() {
+            for ((f, &a), &b) in patch.range().zip(internal).zip(boundary) {
+                fluxes[f] = cells[owner[f]] * a - b;
+            }
+        }
+        // Safety: one value per face.
+        SurfaceField::new(
+            self.mesh,
+            format!("flux({})", self.psi),
+            self.dimensions,
+            fluxes,
+        )
+        .unwrap()
+    }
+
+    /// Under-relaxes the matrix implicitly with the factor `alpha` in
+    /// `(0, 1]`, at the current values `psi`.
+    ///
+    /// The diagonal, boundary contributions included, is divided by `alpha`
+    /// and the increase times `psi` is added to the source, so the solution
+    /// is unchanged at convergence while each solve moves only part way
+    /// from `psi`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is not in `(0, 1]` or `psi` is on another mesh.
+    pub fn relax(&mut self, psi: &VolField<'_, T>, alpha: f64) {
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "relaxation factor {alpha} is not in (0, 1]"
+        );
+        self.check_mesh(psi);
+        let increase = 1.0 / alpha - 1.0;
+        for (i, d) in self.total_diag().into_iter().enumerate() {
+            self.diag[i] += increase * d;
+            self.source[i] = self.source[i] + psi.internal()[i] * (increase * d);
+        }
+    }
+
+    /// Fixes the solution in `cells` at `values`, one per cell.
+    ///
+    /// The row of each fixed cell keeps its diagonal and has its source set
+    /// to the diagonal times the value; its coupling to other cells and its
+    /// boundary coefficients are removed, and the rows of unfixed
+    /// neighbors take the fixed value into their source. The diagonal of a
+    /// fixed cell must not vanish.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cells` and `values` differ in length.
+    pub fn set_values(&mut self, cells: &[usize], values: &[T]) {
+        assert_eq!(cells.len(), values.len(), "one value per fixed cell");
+        let mut fixed = vec![None; self.mesh.n_cells()];
+        for (&c, &v) in cells.iter().zip(values) {
+            fixed[c] = Some(v);
+        }
+        let (owner, neighbor) = (self.mesh.owner(), self.mesh.neighbor());
+        for (f, &n) in neighbor.iter().enumerate() {
+            let o = owner[f];
+            match (fixed[o], fixed[n]) {
+                (None, None) => continue,
+                (Some(v), None) => self.source[n] = self.source[n] - v * self.lower[f],
+                (None, Some(v)) => self.source[o] = self.source[o] - v * self.upper[f],
+                (Some(_), Some(_)) => {}
+            }
+            self.upper[f] = 0.0;
+            self.lower[f] = 0.0;
+        }
+        let patches = self.mesh.patches().iter();
+        let coefficients = self
+            .internal_coeffs
+            .iter_mut()
+            .zip(&mut self.boundary_coeffs);
+        for (patch, (internal, boundary)) in patches.zip(coefficients) {
+            for (i, f) in patch.range().enumerate() {
+                if fixed[owner[f]].is_some() {
+                    internal[i] = 0.0;
+                    boundary[i] = T::zero();
+                }
+            }
+        }
+        for (&c, &v) in cells.iter().zip(values) {
+            self.source[c] = v * self.diag[c];
+        }
+    }
+
+    /// Returns the diagonal with the boundary contributions added.
+    fn total_diag(&self) -> Vec<f64> {
+        let owner = self.mesh.owner();
+        let mut diag = self.diag.clone();
+        for ((patch, internal), _) in self.boundary_terms() {
+            for (f, &a) in patch.range().zip(internal) {
+                diag[owner[f]] += a;
+            }
+        }
+        diag
+    }
+
+    /// Returns the source with the boundary contributions added.
+    fn total_source(&self) -> Vec<T> {
+        let owner = self.mesh.owner();
+        let mut source = self.source.clone();
+        for ((patch, _), boundary) in self.boundary_terms() {
+            for (f, &b) in patch.range().zip(boundary) {
+                source[owner[f]] = source[owner[f]] + b;
+            }
+        }
+        source
+    }
+
+    /// Iterates over the patches with their boundary coefficients.
+    fn boundary_terms(&self) -> impl Iterator<Item = ((&Patch, &Vec<f64>), &Vec<T>)> {
+        self.mesh
+            .patches()
+            .iter()
+            .zip(&self.internal_coeffs)
+            .zip(&self.boundary_coeffs)
+    }
+
+    /// Panics unless `psi` is on the mesh of the matrix.
+    fn check_mesh(&self, psi: &VolField<'_, T>) {
+        assert!(
+            std::ptr::eq(self.mesh, psi.mesh()),
+            "field {} is on another mesh",
+            psi.name()
+        );
+    }
+
+    /// Applies `op` to each pair of coefficients of `self` and `other`.
+    fn combine(
+        mut self,
+        other: &Self,
+        operation: &str,
+        op: impl Fn(f64, f64) -> f64,
+        op_t: impl Fn(T, T) -> T,
+    ) -> Self {
+        assert!(
+            std::ptr::eq(self.mesh, other.mesh) && self.psi == other.psi,
+            "{operation} of matrices for {} and {}",
+            self.psi,
+            other.psi
+        );
+        if let Err(error) = self.dimensions.check(other.dimensions, operation) {
+            panic!("{error}");
+        }
+        let pairs = [
+            (&mut self.diag, &other.diag),
+            (&mut self.lower, &other.lower),
+            (&mut self.upper, &other.upper),
+        ];
+        let internal = self.internal_coeffs.iter_mut().zip(&other.internal_coeffs);
+        for (a, b) in pairs.into_iter().chain(internal) {
+            a.iter_mut().zip(b).for_each(|(a, &b)| *a = op(*a, b));
+        }
+        let boundary = self.boundary_coeffs.iter_mut().zip(&other.boundary_coeffs);
+        let values = [
+            (&mut self.source, &other.source),
+            (&mut self.face_corrections, &other.face_corrections),
+        ];
+        for (a, b) in values.into_iter().chain(boundary) {
+            a.iter_mut().zip(b).for_each(|(a, &b)| *a = op_t(*a, b));
+        }
+        self
+    }
+}
+
+impl<'mesh, T: Components> FvMatrix<'mesh, T> {
+    /// Returns the normalized residual of each component of the equation
+    /// `A ψ = b` at the cell values of `psi`:
+    ///
+    /// ```text
+    /// r = Σ |b − A ψ| / (Σ (|A ψ − A ψ̄| + |b − A ψ̄|) + 10⁻²⁰)
+    /// ```
+    ///
+    /// with `ψ̄` the mean cell value, so that the residual does not depend
+    /// on the scale of the equation or an offset of the solution; 1 means
+    /// no convergence at all.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `psi` is on another mesh.
+    pub fn residual(&self, psi: &VolField<'_, T>) -> T {
+        let residuals = self.evaluate(psi);
+        let (owner, neighbor) = (self.mesh.owner(), self.mesh.neighbor());
+        let cells = psi.internal();
+        let n_cells = cells.len() as f64;
+        let mean = cells
+            .iter()
+            .fold(T::zero(), |sum, &v| sum + v * (1.0 / n_cells));
+        let mut row_sums = self.total_diag();
+        for (f, &n) in neighbor.iter().enumerate() {
+            row_sums[owner[f]] += self.upper[f];
+            row_sums[n] += self.lower[f];
+        }
+        let source = self.total_source();
+        let components = (0..T::N_COMPONENTS).map(|c| {
+            let mut norm = 1e-20;
+            let mut sum = 0.0;
+            for i in 0..cells.len() {
+                let b = source[i].component(c);
+                let a_psi = residuals[i].component(c) + b;
+                let a_mean = row_sums[i] * mean.component(c);
+                norm += (a_psi - a_mean).abs() + (b - a_mean).abs();
+                sum += residuals[i].component(c).abs();
+            }
+            sum / norm
+        });
+        T::from_components(&components.collect::<Vec<_>>())
+    }
+}
+
+/// Sums the terms of two matrices for the same field.
+///
+/// # Panics
+///
+/// Panics if the matrices are for different fields or their dimensions
+/// differ.
+impl<'mesh, T: FieldValue> Add for FvMatrix<'mesh, T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.combine(&other, "a + b", |a, b| a + b, |a, b| a + b)
+    }
+}
+
+/// Subtracts the terms of a matrix for the same field.
+///
+/// # Panics
+///
+/// Panics if the matrices are for different fields or their dimensions
+/// differ.
+impl<'mesh, T: FieldValue> Sub for FvMatrix<'mesh, T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.combine(&other, "a - b", |a, b| a - b, |a, b| a - b)
+    }
+}
+
+/// Negates the term.
+impl<'mesh, T: FieldValue> Neg for FvMatrix<'mesh, T> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self {
+        let coeffs = [&mut self.diag, &mut self.lower, &mut self.upper];
+        for c in coeffs.into_iter().chain(&mut self.internal_coeffs) {
+            c.iter_mut().for_each(|c| *c = -*c);
+        }
+        let values = [&mut self.source, &mut self.face_corrections];
+        for b in values.into_iter().chain(&mut self.boundary_coeffs) {
+            b.iter_mut().for_each(|b| *b = -*b);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_fv_matrix_evaluates_coefficients() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let psi = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 3.0]).unwrap();
+        let mut m = FvMatrix::new(&psi, Dimensions::mlt(0, 3, -1));
+        assert_eq!(m.psi_name(), "T");
+        assert_eq!(m.evaluate(&psi), [0.0, 0.0]);
+        m.diag_mut().copy_from_slice(&[2.0, 4.0]);
+        m.upper_mut()[0] = -1.0;
+        m.lower_mut()[0] = -2.0;
+        m.source_mut()[1] = 5.0;
+        let x_max = mesh
+            .patches()
+            .iter()
+            .position(|p| p.name() == "x-max")
+            .unwrap();
+        m.internal_coeffs_mut()[x_max][0] = 1.0;
+        m.boundary_coeffs_mut()[x_max][0] = 0.5;
+        // Row 0: 2·1 − 1·3; row 1: 4·3 − 2·1 − 5 + 1·3 − 0.5.
+        assert_eq!(m.evaluate(&psi), [-1.0, 7.5]);
+    }
+
+    #[test]
+    fn test_fv_matrix_deferred_correction_is_conservative() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let psi = VolField::uniform(&mesh, "T", Dimensions::default(), 0.0);
+        let mut m = FvMatrix::new(&psi, Dimensions::default());
+        m.add_deferred_correction(&[2.0, -1.0]);
+        // Outward fluxes: cell 0 loses 2, cell 1 gains 2 and loses −1.
+        assert_eq!(m.evaluate(&psi), [2.0, -3.0, 1.0]);
+        assert_eq!(m.evaluate(&psi).iter().sum::<f64>(), 0.0);
+    }
+
+    #[test]
+    fn test_fv_matrix_splits_into_a_and_h() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let psi = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 3.0]).unwrap();
+        let mut m = FvMatrix::new(&psi, Dimensions::mlt(0, 3, -1));
+        m.diag_mut().copy_from_slice(&[2.0, 4.0]);
+        m.upper_mut()[0] = -1.0;
+        m.lower_mut()[0] = -2.0;
+        m.source_mut()[1] = 5.0;
+        m.internal_coeffs_mut()[1][0] = 1.0;
+        m.boundary_coeffs_mut()[1][0] = 0.5;
+        let (a, h) = (m.a(), m.h(&psi));
+        assert_eq!(a.name(), "A(T)");
+        assert_eq!(a.dimensions(), Dimensions::mlt(0, 0, -1));
+        assert_eq!(h.name(), "H(T)");
+        let volumes = mesh.cell_volumes();
+        for (i, r) in m.evaluate(&psi).iter().enumerate() {
+            let split = a.internal()[i] * psi.internal()[i] - h.internal()[i];
+            assert!((split * volumes[i] - r).abs() < 1e-12);
+        }
+        // Row 1: H = (5 + 0.5 + 2·1) / V.
+        assert!((h.internal()[1] * volumes[1] - 7.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fv_matrix_relax_keeps_solution() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let psi = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 3.0]).unwrap();
+        let mut m = FvMatrix::new(&psi, Dimensions::default());
+        m.diag_mut().copy_from_slice(&[2.0, 4.0]);
+        m.upper_mut()[0] = -1.0;
+        m.lower_mut()[0] = -1.0;
+        m.internal_coeffs_mut()[0][0] = 1.0;
+        let before = m.evaluate(&psi);
+        m.relax(&psi, 0.5);
+        assert_eq!(m.diag(), [5.0, 8.0]);
+        for (a, b) in m.evaluate(&psi).iter().zip(before) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_fv_matrix_set_values_fixes_cells() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let psi = VolField::new(&mesh, "T", Dimensions::default(), vec![0.0, 0.0, 0.0]).unwrap();
+        let mut m = FvMatrix::new(&psi, Dimensions::default());
+        m.diag_mut().copy_from_slice(&[3.0, 2.0, 3.0]);
+        m.upper_mut().copy_from_slice(&[-1.0, -1.0]);
+        m.lower_mut().copy_from_slice(&[-1.0, -1.0]);
+        m.internal_coeffs_mut()[0][0] = 1.0;
+        m.boundary_coeffs_mut()[0][0] = 4.0;
+        m.set_values(&[0], &[2.0]);
+        assert_eq!(m.upper(), [0.0, -1.0]);
+        assert_eq!(m.lower(), [0.0, -1.0]);
+        assert_eq!(m.internal_coeffs()[0][0], 0.0);
+        assert_eq!(m.boundary_coeffs()[0][0], 0.0);
+        // Row 0 solves 3 ψ = 6; row 1 takes the fixed value into its source.
+        assert_eq!(m.source(), [6.0, 2.0, 0.0]);
+        let fixed = VolField::new(&mesh, "T", Dimensions::default(), vec![2.0, 0.0, 0.0]).unwrap();
+        assert_eq!(m.evaluate(&fixed)[0], 0.0);
+    }
+
+    #[test]
+    fn test_fv_matrix_residual_is_normalized() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let psi = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 2.0, 3.0]).unwrap();
+        let mut m = FvMatrix::new(&psi, Dimensions::default());
+        m.diag_mut().copy_from_slice(&[1.0, 2.0, 1.0]);
+        m.upper_mut().copy_from_slice(&[-1.0, -1.0]);
+        m.lower_mut().copy_from_slice(&[-1.0, -1.0]);
+        m.source_mut().copy_from_slice(&[-1.0, 0.0, 1.0]);
+        // ψ solves A ψ = b.
+        assert!(m.residual(&psi).abs() < 1e-12);
+        m.source_mut()[0] = 0.0;
+        let r = m.residual(&psi);
+        assert!(r > 0.0 && r <= 1.0);
+        // Independent of the scale of the equation.
+        let doubled = m.clone() + m;
+        assert!((doubled.residual(&psi) - r).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fv_matrix_ops_combine_terms() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let psi = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 3.0]).unwrap();
+        let mut a = FvMatrix::new(&psi, Dimensions::default());
+        a.diag_mut().copy_from_slice(&[2.0, 4.0]);
+        a.upper_mut()[0] = -1.0;
+        a.boundary_coeffs_mut()[0][0] = 1.0;
+        let mut b = FvMatrix::new(&psi, Dimensions::default());
+        b.diag_mut().copy_from_slice(&[1.0, 1.0]);
+        b.source_mut()[0] = 2.0;
+        let (ea, eb) = (a.evaluate(&psi), b.evaluate(&psi));
+        let sum = (a.clone() + b.clone()).evaluate(&psi);
+        let difference = (a.clone() - b.clone()).evaluate(&psi);
+        let negated = (-a).evaluate(&psi);
+        for i in 0..2 {
+            assert_eq!(sum[i], ea[i] + eb[i]);
+            assert_eq!(difference[i], ea[i] - eb[i]);
+            assert_eq!(negated[i], -ea[i]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "dimensions differ")]
+    fn test_fv_matrix_add_with_other_dimensions_panics() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let psi = VolField::uniform(&mesh, "T", Dimensions::default(), 1.0);
+        let a = FvMatrix::new(&psi, Dimensions::default());
+        let _ = a + FvMatrix::new(&psi, Dimensions::mlt(0, 3, -1));
+    }
+}
@@ -0,0 +1,463 @@
+//! Run-time selectable sources and constraints of the equations of named
+//! fields (`fvOptions`), such as heat sources, fans or fixed values in a
+//! cell zone.
+//!
+//! Each option acts on the equations of the fields it names, within the
+//! cells it selects. A solver adds the sources of a field to its equation
+//! and applies the constraints before solving:
+//!
+//! ```text
+//! let mut eqn = fvm::ddt(&t, &step) + fvm::div(&phi, &t, &scheme)
+//!     - fvm::laplacian(&gamma, &t) - options.source(&t);
+//! options.constrain(&mut eqn);
+//! // solve, then
+//! options.correct(&mut t);
+//! ```
+
+use std::fmt::Debug;
+
+use dugong_fields::{Components, Dimensions, FieldError, VolField, parse_value};
+use dugong_mesh::Mesh;
+use dugong_runtime::{Dictionary, Value};
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::fv_matrix::FvMatrix;
+
+mod fan_momentum_source;
+mod fixed_value_constraint;
+mod mean_velocity_force;
+mod semi_implicit_source;
+
+pub use fan_momentum_source::FanMomentumSource;
+pub use fixed_value_constraint::FixedValueConstraint;
+pub use mean_velocity_force::MeanVelocityForce;
+pub use semi_implicit_source::{SemiImplicitSource, VolumeMode};
+
+/// The cells an option acts on, with their total volume.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellSelection {
+    cells: Vec<usize>,
+    volume: f64,
+}
+
+impl CellSelection {
+    /// Selects every cell of `mesh`.
+    pub fn all(mesh: &Mesh) -> Self {
+        Self::new(mesh, (0..mesh.n_cells()).collect())
+    }
+
+    /// Selects the cells of the cell zone `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::InvalidEntry`] if `mesh` has no such zone.
+    pub fn zone(mesh: &Mesh, name: &str) -> Result<Self, FieldError> {
+        let zone = mesh
+            .cell_zone(name)
+            .ok_or_else(|| FieldError::InvalidEntry {
+                keyword: "cellZone".into(),
+                reason: format!("no cell zone {name}"),
+            })?;
+        Ok(Self::new(mesh, zone.indices().to_vec()))
+    }
+
+    /// Reads the selection of an option dictionary: `selectionMode all`,
+    /// the default, or `selectionMode cellZone` with a `cellZone` entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::InvalidEntry`] for another mode, a missing
+    /// `cellZone` entry or an unknown zone.
+    pub fn from_dict(mesh: &Mesh, dict: &Dictionary) -> Result<Self, FieldError> {
+        match dict.get_word("selectionMode").unwrap_or("all") {
+            "all" => Ok(Self::all(mesh)),
+            "cellZone" => {
+                let name = dict
+                    .get_word("cellZone")
+                    .ok_or_else(|| FieldError::InvalidEntry {
+                        keyword: "cellZone".into(),
+                        reason: "missing".into(),
+                    })?;
+                Self::zone(mesh, name)
+            }
+            mode => Err(FieldError::InvalidEntry {
+                keyword: "selectionMode".into(),
+                reason: format!("unknown mode {mode}"),
+            }),
+        }
+    }
+
+    /// Returns the selected cells.
+    pub fn cells(&self) -> &[usize] {
+        &self.cells
+    }
+
+    /// Returns the total volume of the selected cells.
+    pub fn volume(&self) -> f64 {
+        self.volume
+    }
+
+    fn new(mesh: &Mesh, cells: Vec<usize>) -> Self {
+        let volumes = mesh.cell_volumes();
+        let volume = cells.iter().map(|&c| volumes[c]).sum();
+        Self { cells, volume }
+    }
+}
+
+/// A source or constraint of the equations of some fields of value type
+/// `T`.
+///
+/// Sources are explicit or linearized terms `S = S_u + S_p ψ` per unit
+/// volume on the right-hand side of `∂ψ/∂t + … = S`; constraints modify
+/// the assembled equation, and corrections the solution.
+pub trait FvOption<T: Components>: Debug + Send + Sync {
+    /// Returns the type name used in case files, such as
+    /// `semiImplicitSource`.
+    fn type_name(&self) -> &'static str;
+
+    /// Returns whether the option acts on the equation of the field `name`.
+    fn applies_to(&self, name: &str) -> bool;
+
+    /// Adds the volume-integrated source of `field` to `matrix`, in the
+    /// convention of [`fvm::su`](crate::fvm::su) and
+    /// [`fvm::sp`](crate::fvm::sp). Adds nothing by default.
+    fn add_source(&self, field: &VolField<'_, T>, matrix: &mut FvMatrix<'_, T>) {
+        let _ = (field, matrix);
+    }
+
+    /// Constrains the assembled equation `matrix` before it is solved.
+    /// Does nothing by default.
+    fn constrain(&mut self, matrix: &mut FvMatrix<'_, T>) {
+        let _ = matrix;
+    }
+
+    /// Corrects `field` after its equation is solved. Does nothing by
+    /// default.
+    fn correct(&mut self, field: &mut VolField<'_, T>) {
+        let _ = field;
+    }
+}
+
+/// Builds an option from its case-file dictionary.
+pub type FvOptionConstructor<T> =
+    fn(&Mesh, &Dictionary) -> Result<Box<dyn FvOption<T>>, FieldError>;
+
+/// An option type selectable by name at run time.
+///
+/// Submit one per value type with `inventory::submit!`; the `type` entry of
+/// an option dictionary selects it.
+pub struct FvOptionFactory<T: 'static> {
+    /// The type name used in case files.
+    pub name: &'static str,
+    /// Builds the option.
+    pub constructor: FvOptionConstructor<T>,
+}
+
+inventory::collect!(FvOptionFactory<f64>);
+inventory::collect!(FvOptionFactory<Vector>);
+inventory::collect!(FvOptionFactory<Tensor>);
+inventory::collect!(FvOptionFactory<SymmTensor>);
+inventory::collect!(FvOptionFactory<SphericalTensor>);
+
+/// Value types with a registry of run-time selectable options.
+pub trait SourceValue: Components {
+    /// Iterates over the registered option types.
+    fn fv_options() -> impl Iterator<Item = &'static FvOptionFactory<Self>>;
+}
+
+macro_rules! impl_source_value {
+    ($($ty:ty),*) => {
+        $(
+            impl SourceValue for $ty {
+                fn fv_options() -> impl Iterator<Item = &'static FvOptionFactory<Self>> {
+                    inventory::iter::<FvOptionFactory<Self>>.into_iter()
+                }
+            }
+        )*
+    };
+}
+
+impl_source_value!(f64, Vector, Tensor, SymmTensor, SphericalTensor);
+
+/// Returns whether an option type `name` is registered for any value type.
+fn is_registered(name: &str) -> bool {
+    fn registered<T: SourceValue>(name: &str) -> bool {
+        T::fv_options().any(|f| f.name == name)
+    }
+    registered::<f64>(name)
+        || registered::<Vector>(name)
+        || registered::<Tensor>(name)
+        || registered::<SymmTensor>(name)
+        || registered::<SphericalTensor>(name)
+}
+
+/// Registers the constructor `$constructor` under `$name` for each listed
+/// value type; the value type is inferred from the factory.
+macro_rules! register_fv_option {
+    ($name:literal, $constructor:path, [$($ty:ty),*]) => {
+        $(
+            inventory::submit! {
+                $crate::fv_options::FvOptionFactory::<$ty> {
+                    name: $name,
+                    constructor: $constructor,
+                }
+            }
+        )*
+    };
+}
+
+use register_fv_option;
+
+/// Builds the option selected by the `type` entry of `dict`.
+///
+/// # Errors
+///
+/// Returns [`FieldError::InvalidEntry`] if `type` is missing or no option
+/// of that name is registered for `T`, and any error of the option's
+/// constructor.
+pub fn new_fv_option<T: SourceValue>(
+    mesh: &Mesh,
+    dict: &Dictionary,
+) -> Result<Box<dyn FvOption<T>>, FieldError> {
+    let name = option_type(dict)?;
+    let factory =
+        T::fv_options()
+            .find(|f| f.name == name)
+            .ok_or_else(|| FieldError::InvalidEntry {
+                keyword: "type".into(),
+                reason: format!("unknown {} option {name}", T::TYPE_NAME),
+            })?;
+    (factory.constructor)(mesh, dict)
+}
+
+/// The options acting on the equations of fields of value type `T`.
+#[derive(Debug)]
+pub struct FvOptions<T: Components> {
+    options: Vec<(String, Box<dyn FvOption<T>>)>,
+}
+
+impl<T: Components> Default for FvOptions<T> {
+    fn default() -> Self {
+        Self {
+            options: Vec::new(),
+        }
+    }
+}
+
+impl<T: SourceValue> FvOptions<T> {
+    /// Returns an empty set of options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the options of an `fvOptions` dictionary with one
+    /// sub-dictionary per option, named by its keyword.
+    ///
+    /// Options of types registered only for other value types, such as a
+    /// `meanVelocityForce` among the options of scalars, are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::InvalidEntry`] for an entry that is not a
+    /// dictionary or of an unknown type, and the errors of
+    /// [`new_fv_option`].
+    pub fn from_dict(mesh: &Mesh, dict: &Dictionary) -> Result<Self, FieldError> {
+        let mut options = Self::new();
+        for (name, values) in dict.iter() {
+            let option = match values {
+                [Value::Dict(option)] => option,
+                _ => {
+                    return Err(FieldError::InvalidEntry {
+                        keyword: name.to_string(),
+                        reason: "expected an option dictionary".into(),
+                    });
+                }
+            };
+            let kind = option_type(option)?;
+            if T::fv_options().any(|f| f.name == kind) {
+                options.push(name, new_fv_option(mesh, option)?);
+            } else if !is_registered(kind) {
+                return Err(FieldError::InvalidEntry {
+                    keyword: name.to_string(),
+                    reason: format!("unknown option type {kind}"),
+                });
+            }
+        }
+        Ok(options)
+    }
+
+    /// Adds the option `option` under `name`.
+    pub fn push(&mut self, name: impl Into<String>, option: Box<dyn FvOption<T>>) {
+        self.options.push((name.into(), option));
+    }
+
+    /// Iterates over the names of the options.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.options.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the number of options.
+    pub fn len(&self) -> usize {
+        self.options.len()
+    }
+
+    /// Returns whether there are no options.
+    pub fn is_empty(&self) -> bool {
+        self.options.is_empty()
+    }
+
+    /// Returns the volume-integrated sources of the options acting on
+    /// `field`, with the dimensions of `∂(Vψ)/∂t`; subtract it from the
+    /// equation of `field`.
+    pub fn source<'mesh>(&self, field: &VolField<'mesh, T>) -> FvMatrix<'mesh, T> {
+        let dimensions = field.dimensions() * Dimensions::mlt(0, 3, -1);
+        let mut matrix = FvMatrix::new(field, dimensions);
+        for (_, option) in self.acting_on(field.name()) {
+            option.add_source(field, &mut matrix);
+        }
+        matrix
+    }
+
+    /// Applies the constraints of the options acting on the field of
+    /// `matrix`.
+    pub fn constrain(&mut self, matrix: &mut FvMatrix<'_, T>) {
+        let name = matrix.psi_name().to_string();
+        for (_, option) in self.acting_on_mut(&name) {
+            option.constrain(matrix);
+        }
+    }
+
+    /// Applies the corrections of the options acting on `field`.
+    pub fn correct(&mut self, field: &mut VolField<'_, T>) {
+        let name = field.name().to_string();
+        for (_, option) in self.acting_on_mut(&name) {
+            option.correct(field);
+        }
+    }
+
+    fn acting_on<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a (String, Box<dyn FvOption<T>>)> {
+        self.options.iter().filter(move |(_, o)| o.applies_to(name))
+    }
+
+    fn acting_on_mut<'a>(
+        &'a mut self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a mut (String, Box<dyn FvOption<T>>)> {
+        self.options
+            .iter_mut()
+            .filter(move |(_, o)| o.applies_to(name))
+    }
+}
+
+/// Returns the `type` entry of an option dictionary.
+fn option_type(dict: &Dictionary) -> Result<&str, FieldError> {
+    dict.get_word("type")
+        .ok_or_else(|| FieldError::InvalidEntry {
+            keyword: "type".into(),
+            reason: "missing".into(),
+        })
+}
+
+/// Looks up and parses the single value of the entry `keyword`.
+fn lookup_value<T: Components>(dict: &Dictionary, keyword: &str) -> Result<T, FieldError> {
+    match dict.get(keyword) {
+        Some([value]) => parse_value(value),
+        _ => None,
+    }
+    .ok_or_else(|| FieldError::InvalidEntry {
+        keyword: keyword.to_string(),
+        reason: format!("expected a {}", T::TYPE_NAME),
+    })
+}
+
+/// Returns the unit vector along the entry `keyword`.
+fn lookup_direction(dict: &Dictionary, keyword: &str) -> Result<Vector, FieldError> {
+    let direction: Vector = lookup_value(dict, keyword)?;
+    let magnitude = direction.mag();
+    if magnitude == 0.0 {
+        return Err(FieldError::InvalidEntry {
+            keyword: keyword.to_string(),
+            reason: "zero vector".into(),
+        });
+    }
+    Ok(direction * (1.0 / magnitude))
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_mesh::Zone;
+
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    pub(super) fn dict(entries: &[(&str, Vec<Value>)]) -> Dictionary {
+        let mut dict = Dictionary::new();
+        for (keyword, values) in entries {
+            dict.insert(*keyword, values.clone());
+        }
+        dict
+    }
+
+    pub(super) fn word(w: &str) -> Value {
+        Value::Word(w.to_string())
+    }
+
+    pub(super) fn list(items: &[f64]) -> Value {
+        Value::List(items.iter().map(|&x| Value::Scalar(x)).collect())
+    }
+
+    #[test]
+    fn test_cell_selection_reads_zone() {
+        let mut mesh = box_mesh([4, 1, 1], [4.0, 1.0, 1.0]);
+        mesh.add_cell_zone(Zone::new("heater", [1, 2])).unwrap();
+        let all = CellSelection::from_dict(&mesh, &Dictionary::new()).unwrap();
+        assert_eq!(all.cells(), [0, 1, 2, 3]);
+        let zone = dict(&[
+            ("selectionMode", vec![word("cellZone")]),
+            ("cellZone", vec![word("heater")]),
+        ]);
+        let heater = CellSelection::from_dict(&mesh, &zone).unwrap();
+        assert_eq!(heater.cells(), [1, 2]);
+        assert!((heater.volume() - 2.0).abs() < 1e-12);
+        let missing = dict(&[
+            ("selectionMode", vec![word("cellZone")]),
+            ("cellZone", vec![word("fan")]),
+        ]);
+        assert!(CellSelection::from_dict(&mesh, &missing).is_err());
+    }
+
+    #[test]
+    fn test_fv_options_from_dict_selects_by_value_type() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let heat = dict(&[
+            ("type", vec![word("semiImplicitSource")]),
+            (
+                "injectionRateSuSp",
+                vec![Value::Dict(dict(&[("T", vec![list(&[1.0, 0.0])])]))],
+            ),
+        ]);
+        let force = dict(&[
+            ("type", vec![word("meanVelocityForce")]),
+            ("Ubar", vec![list(&[1.0, 0.0, 0.0])]),
+        ]);
+        let options = dict(&[
+            ("heater", vec![Value::Dict(heat)]),
+            ("channel", vec![Value::Dict(force)]),
+        ]);
+        let scalars = FvOptions::<f64>::from_dict(&mesh, &options).unwrap();
+        assert_eq!(scalars.names().collect::<Vec<_>>(), ["heater"]);
+        let vectors = FvOptions::<Vector>::from_dict(&mesh, &options).unwrap();
+        assert_eq!(vectors.len(), 2);
+
+        let unknown = dict(&[(
+            "heater",
+            vec![Value::Dict(dict(&[("type", vec![word("radiation")])]))],
+        )]);
+        assert!(FvOptions::<f64>::from_dict(&mesh, &unknown).is_err());
+        let bad = dict(&[("heater", vec![word("on")])]);
+        assert!(FvOptions::<f64>::from_dict(&mesh, &bad).is_err());
+    }
+}
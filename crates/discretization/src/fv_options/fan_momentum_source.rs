@@ -0,0 +1,151 @@
+use dugong_fields::{FieldError, Table, VolField};
+use dugong_mesh::Mesh;
+use dugong_runtime::Dictionary;
+use dugong_types::tensor::Vector;
+
+use crate::fv_matrix::FvMatrix;
+use crate::fv_options::{CellSelection, FvOption, lookup_direction, register_fv_option};
+
+/// The momentum source of a fan occupying the selected cells
+/// (`fanMomentumSource`).
+///
+/// The fan is a layer of thickness `L` across the flow direction `n`. Its
+/// volumetric flow rate `Q = Σ (U · n) V / L` looks up the pressure rise
+/// `Δp(Q)` of the fan curve, which acts as the force `Δp / L n` per unit
+/// volume. Entries: `flowDir`, `thickness`, `fanCurve` as a
+/// [`Table`] of the (kinematic) pressure rise against the flow rate, and
+/// `U`, the velocity field, `U` by default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FanMomentumSource {
+    selection: CellSelection,
+    field: String,
+    flow_dir: Vector,
+    thickness: f64,
+    fan_curve: Table<f64>,
+}
+
+impl FanMomentumSource {
+    /// Creates the source of a fan of `thickness` along the unit vector
+    /// `flow_dir` acting on the velocity `field`.
+    pub fn new(
+        selection: CellSelection,
+        field: impl Into<String>,
+        flow_dir: Vector,
+        thickness: f64,
+        fan_curve: Table<f64>,
+    ) -> Self {
+        Self {
+            selection,
+            field: field.into(),
+            flow_dir,
+            thickness,
+            fan_curve,
+        }
+    }
+
+    /// Returns the volumetric flow rate through the fan at the velocity
+    /// `u`.
+    pub fn flow_rate(&self, u: &VolField<'_, Vector>) -> f64 {
+        let volumes = u.mesh().cell_volumes();
+        let cells = u.internal();
+        let flux: f64 = self
+            .selection
+            .cells()
+            .iter()
+            .map(|&c| cells[c] * self.flow_dir * volumes[c])
+            .sum();
+        flux / self.thickness
+    }
+
+    /// Builds the source from its cell selection, `flowDir`, `thickness`,
+    /// `fanCurve` and optional `U` entries.
+    pub fn from_dict(
+        mesh: &Mesh,
+        dict: &Dictionary,
+    ) -> Result<Box<dyn FvOption<Vector>>, FieldError> {
+        let selection = CellSelection::from_dict(mesh, dict)?;
+        let thickness = dict
+            .get_scalar("thickness")
+            .filter(|&t| t > 0.0)
+            .ok_or_else(|| FieldError::InvalidEntry {
+                keyword: "thickness".into(),
+                reason: "expected a positive thickness".into(),
+            })?;
+        Ok(Box::new(Self::new(
+            selection,
+            dict.get_word("U").unwrap_or("U"),
+            lookup_direction(dict, "flowDir")?,
+            thickness,
+            Table::from_dict(dict, "fanCurve")?,
+        )))
+    }
+}
+
+impl FvOption<Vector> for FanMomentumSource {
+    fn type_name(&self) -> &'static str {
+        "fanMomentumSource"
+    }
+
+    fn applies_to(&self, name: &str) -> bool {
+        name == self.field
+    }
+
+    fn add_source(&self, field: &VolField<'_, Vector>, matrix: &mut FvMatrix<'_, Vector>) {
+        let pressure_rise = self.fan_curve.value(self.flow_rate(field));
+        let force = self.flow_dir * (pressure_rise / self.thickness);
+        let volumes = field.mesh().cell_volumes();
+        for &c in self.selection.cells() {
+            matrix.source_mut()[c] = matrix.source()[c] - force * volumes[c];
+        }
+    }
+}
+
+register_fv_option!("fanMomentumSource", FanMomentumSource::from_dict, [Vector]);
+
+#[cfg(test)]
+mod tests {
+    use dugong_fields::Dimensions;
+    use dugong_mesh::Zone;
+    use dugong_runtime::Value;
+
+    use super::*;
+    use crate::fv_options::FvOptions;
+    use crate::fv_options::tests::{dict, list, word};
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_fan_momentum_source_follows_fan_curve() {
+        let mut mesh = box_mesh([4, 1, 1], [4.0, 1.0, 1.0]);
+        mesh.add_cell_zone(Zone::new("fan", [1])).unwrap();
+        let curve = vec![
+            word("table"),
+            Value::List(vec![list(&[0.0, 10.0]), list(&[2.0, 0.0])]),
+        ];
+        let fan = dict(&[
+            ("type", vec![word("fanMomentumSource")]),
+            ("selectionMode", vec![word("cellZone")]),
+            ("cellZone", vec![word("fan")]),
+            ("flowDir", vec![list(&[2.0, 0.0, 0.0])]),
+            ("thickness", vec![Value::Scalar(1.0)]),
+            ("fanCurve", curve),
+        ]);
+        let options = dict(&[("fan", vec![Value::Dict(fan)])]);
+        let options = FvOptions::<Vector>::from_dict(&mesh, &options).unwrap();
+        let u = VolField::uniform(
+            &mesh,
+            "U",
+            Dimensions::mlt(0, 1, -1),
+            Vector::new(1.0, 0.5, 0.0),
+        );
+        // Q = 1, so Δp = 5 acts on the fan cell along x.
+        let s = options.source(&u).evaluate(&u);
+        assert!((s[1] - Vector::new(5.0, 0.0, 0.0)).mag() < 1e-12);
+        assert_eq!(s[0], Vector::zero());
+        assert_eq!(
+            FvOptions::<f64>::from_dict(&mesh, &dict(&[]))
+                .unwrap()
+                .len(),
+            0
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use dugong_fields::{FieldError, parse_value};
+use dugong_mesh::Mesh;
+use dugong_runtime::Dictionary;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::fv_matrix::FvMatrix;
+use crate::fv_options::{CellSelection, FvOption, SourceValue, register_fv_option};
+
+/// Fixes fields at given values in the selected cells
+/// (`fixedValueConstraint`), such as the temperature of a heated block.
+///
+/// The values are listed per field in a `fieldValues` dictionary, as in
+/// `fieldValues { T 350; U (0 0 0); }`; entries that are not values of
+/// type `T` belong to the options of other value types.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedValueConstraint<T> {
+    selection: CellSelection,
+    values: Vec<(String, T)>,
+}
+
+impl<T: SourceValue> FixedValueConstraint<T> {
+    /// Creates the constraint fixing each named field at its value.
+    pub fn new(selection: CellSelection, values: Vec<(String, T)>) -> Self {
+        Self { selection, values }
+    }
+
+    /// Returns the fixed value of the field `name`, if constrained.
+    pub fn value(&self, name: &str) -> Option<T> {
+        self.values.iter().find(|(n, _)| n == name).map(|&(_, v)| v)
+    }
+
+    /// Builds the constraint from its cell selection and `fieldValues`
+    /// dictionary.
+    pub fn from_dict(mesh: &Mesh, dict: &Dictionary) -> Result<Box<dyn FvOption<T>>, FieldError> {
+        let selection = CellSelection::from_dict(mesh, dict)?;
+        let field_values =
+            dict.get_dict("fieldValues")
+                .ok_or_else(|| FieldError::InvalidEntry {
+                    keyword: "fieldValues".into(),
+                    reason: "missing".into(),
+                })?;
+        let values = field_values
+            .iter()
+            .filter_map(|(name, values)| match values {
+                [value] => parse_value(value).map(|v| (name.to_string(), v)),
+                _ => None,
+            })
+            .collect();
+        Ok(Box::new(Self::new(selection, values)))
+    }
+}
+
+impl<T: SourceValue> FvOption<T> for FixedValueConstraint<T> {
+    fn type_name(&self) -> &'static str {
+        "fixedValueConstraint"
+    }
+
+    fn applies_to(&self, name: &str) -> bool {
+        self.value(name).is_some()
+    }
+
+    fn constrain(&mut self, matrix: &mut FvMatrix<'_, T>) {
+        if let Some(value) = self.value(matrix.psi_name()) {
+            let cells = self.selection.cells();
+            matrix.set_values(cells, &vec![value; cells.len()]);
+        }
+    }
+}
+
+register_fv_option!(
+    "fixedValueConstraint",
+    FixedValueConstraint::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use dugong_fields::{Dimensions, VolField};
+    use dugong_mesh::Zone;
+    use dugong_runtime::Value;
+
+    use super::*;
+    use crate::fv_options::FvOptions;
+    use crate::fv_options::tests::{dict, list, word};
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_fixed_value_constraint_fixes_zone_values() {
+        let mut mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        mesh.add_cell_zone(Zone::new("block", [0])).unwrap();
+        let values = dict(&[
+            ("T", vec![Value::Scalar(350.0)]),
+            ("U", vec![list(&[0.0, 0.0, 0.0])]),
+        ]);
+        let constraint = dict(&[
+            ("type", vec![word("fixedValueConstraint")]),
+            ("selectionMode", vec![word("cellZone")]),
+            ("cellZone", vec![word("block")]),
+            ("fieldValues", vec![Value::Dict(values)]),
+        ]);
+        let options = dict(&[("block", vec![Value::Dict(constraint)])]);
+        let mut options = FvOptions::<f64>::from_dict(&mesh, &options).unwrap();
+
+        let t = VolField::uniform(&mesh, "T", Dimensions::default(), 300.0);
+        let mut eqn = FvMatrix::new(&t, Dimensions::default());
+        eqn.diag_mut().copy_from_slice(&[2.0, 2.0, 2.0]);
+        eqn.upper_mut().copy_from_slice(&[-1.0, -1.0]);
+        eqn.lower_mut().copy_from_slice(&[-1.0, -1.0]);
+        options.constrain(&mut eqn);
+        assert_eq!(eqn.source(), [700.0, 350.0, 0.0]);
+        assert_eq!(eqn.upper(), [0.0, -1.0]);
+
+        let s = VolField::uniform(&mesh, "s", Dimensions::default(), 0.0);
+        let mut other = FvMatrix::new(&s, Dimensions::default());
+        options.constrain(&mut other);
+        assert_eq!(other.source(), [0.0; 3]);
+    }
+}
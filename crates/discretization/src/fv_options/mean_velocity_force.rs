@@ -0,0 +1,202 @@
+use dugong_fields::{FieldError, VolField};
+use dugong_mesh::Mesh;
+use dugong_runtime::Dictionary;
+use dugong_types::tensor::Vector;
+
+use crate::fv_matrix::FvMatrix;
+use crate::fv_options::{CellSelection, FvOption, lookup_value, register_fv_option};
+
+/// Drives the mean velocity in the selected cells to a target
+/// (`meanVelocityForce`), as the pressure gradient driving a periodic
+/// channel.
+///
+/// A uniform force `g n` along the direction `n` of the target mean
+/// velocity `Ubar` is added to the momentum equation. After each solve,
+/// [`correct`](FvOption::correct) compares the volume-averaged velocity
+/// along `n` with `|Ubar|`, raises the force by the relaxed pressure
+/// gradient increment
+///
+/// ```text
+/// δg = α (|Ubar| − ⟨U · n⟩) / ⟨1/A⟩
+/// ```
+///
+/// and corrects the velocity by `n δg / A`, with `A` the diagonal of the
+/// constrained momentum equation. Entries: `Ubar`, `relaxation` (`α`, 1 by
+/// default) and `U`, the velocity field, `U` by default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeanVelocityForce {
+    selection: CellSelection,
+    field: String,
+    flow_dir: Vector,
+    mean_velocity: f64,
+    relaxation: f64,
+    gradient: f64,
+    inverse_diag: Vec<f64>,
+}
+
+impl MeanVelocityForce {
+    /// Creates the force driving the velocity `field` to the mean
+    /// velocity `u_bar`, with the relaxation factor `relaxation`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `u_bar` is zero.
+    pub fn new(
+        selection: CellSelection,
+        field: impl Into<String>,
+        u_bar: Vector,
+        relaxation: f64,
+    ) -> Self {
+        let mean_velocity = u_bar.mag();
+        assert!(mean_velocity > 0.0, "zero target mean velocity");
+        Self {
+            selection,
+            field: field.into(),
+            flow_dir: u_bar * (1.0 / mean_velocity),
+            mean_velocity,
+            relaxation,
+            gradient: 0.0,
+            inverse_diag: Vec::new(),
+        }
+    }
+
+    /// Returns the current driving pressure gradient `g`.
+    pub fn gradient(&self) -> f64 {
+        self.gradient
+    }
+
+    /// Returns the volume average of `U · n` over the selected cells.
+    pub fn mean_velocity(&self, u: &VolField<'_, Vector>) -> f64 {
+        let volumes = u.mesh().cell_volumes();
+        let cells = u.internal();
+        let sum: f64 = self
+            .selection
+            .cells()
+            .iter()
+            .map(|&c| cells[c] * self.flow_dir * volumes[c])
+            .sum();
+        sum / self.selection.volume()
+    }
+
+    /// Builds the force from its cell selection and its `Ubar`,
+    /// `relaxation` and `U` entries.
+    pub fn from_dict(
+        mesh: &Mesh,
+        dict: &Dictionary,
+    ) -> Result<Box<dyn FvOption<Vector>>, FieldError> {
+        let selection = CellSelection::from_dict(mesh, dict)?;
+        let u_bar: Vector = lookup_value(dict, "Ubar")?;
+        if u_bar.mag() == 0.0 {
+            return Err(FieldError::InvalidEntry {
+                keyword: "Ubar".into(),
+                reason: "zero vector".into(),
+            });
+        }
+        let relaxation = dict.get_scalar("relaxation").unwrap_or(1.0);
+        if relaxation <= 0.0 || relaxation > 1.0 {
+            return Err(FieldError::InvalidEntry {
+                keyword: "relaxation".into(),
+                reason: format!("factor {relaxation} is not in (0, 1]"),
+            });
+        }
+        Ok(Box::new(Self::new(
+            selection,
+            dict.get_word("U").unwrap_or("U"),
+            u_bar,
+            relaxation,
+        )))
+    }
+}
+
+impl FvOption<Vector> for MeanVelocityForce {
+    fn type_name(&self) -> &'static str {
+        "meanVelocityForce"
+    }
+
+    fn applies_to(&self, name: &str) -> bool {
+        name == self.field
+    }
+
+    fn add_source(&self, field: &VolField<'_, Vector>, matrix: &mut FvMatrix<'_, Vector>) {
+        let force = self.flow_dir * self.gradient;
+        let volumes = field.mesh().cell_volumes();
+        for &c in self.selection.cells() {
+            matrix.source_mut()[c] = matrix.source()[c] - force * volumes[c];
+        }
+    }
+
+    /// Keeps `1/A` of the selected cells for [`correct`](Self::correct).
+    fn constrain(&mut self, matrix: &mut FvMatrix<'_, Vector>) {
+        let a = matrix.a();
+        self.inverse_diag = self
+            .selection
+            .cells()
+            .iter()
+            .map(|&c| 1.0 / a.internal()[c])
+            .collect();
+    }
+
+    /// Does nothing before the momentum equation is constrained.
+    fn correct(&mut self, field: &mut VolField<'_, Vector>) {
+        if self.inverse_diag.is_empty() {
+            return;
+        }
+        let volumes = field.mesh().cell_volumes();
+        let cells = self.selection.cells();
+        let inverse_mean = cells
+            .iter()
+            .zip(&self.inverse_diag)
+            .map(|(&c, r)| r * volumes[c])
+            .sum::<f64>()
+            / self.selection.volume();
+        let increment =
+            self.relaxation * (self.mean_velocity - self.mean_velocity(field)) / inverse_mean;
+        let values = field.internal_mut();
+        for (&c, &r) in cells.iter().zip(&self.inverse_diag) {
+            values[c] += self.flow_dir * (r * increment);
+        }
+        self.gradient += increment;
+    }
+}
+
+register_fv_option!("meanVelocityForce", MeanVelocityForce::from_dict, [Vector]);
+
+#[cfg(test)]
+mod tests {
+    use dugong_fields::Dimensions;
+    use dugong_runtime::Value;
+
+    use super::*;
+    use crate::fv_options::FvOptions;
+    use crate::fv_options::tests::{dict, list, word};
+    use crate::fvm;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_mean_velocity_force_reaches_target() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let force = dict(&[
+            ("type", vec![word("meanVelocityForce")]),
+            ("Ubar", vec![list(&[2.0, 0.0, 0.0])]),
+        ]);
+        let options = dict(&[("channel", vec![Value::Dict(force)])]);
+        let mut options = FvOptions::<Vector>::from_dict(&mesh, &options).unwrap();
+        let mut u = VolField::uniform(&mesh, "U", Dimensions::mlt(0, 1, -1), Vector::zero());
+        let drag = VolField::uniform(&mesh, "k", Dimensions::mlt(0, 0, -1), 0.5);
+        for _ in 0..3 {
+            // A linear drag balances the force: 0.5 U = g.
+            let mut eqn = fvm::sp(&drag, &u) - options.source(&u);
+            options.constrain(&mut eqn);
+            let (a, h) = (eqn.a(), eqn.h(&u));
+            for (c, v) in u.internal_mut().iter_mut().enumerate() {
+                *v = h.internal()[c] * (1.0 / a.internal()[c]);
+            }
+            options.correct(&mut u);
+            for v in u.internal() {
+                assert!((v.x() - 2.0).abs() < 1e-12);
+            }
+        }
+        let eqn = fvm::sp(&drag, &u) - options.source(&u);
+        assert!(eqn.evaluate(&u).iter().all(|r| r.mag() < 1e-12));
+    }
+}
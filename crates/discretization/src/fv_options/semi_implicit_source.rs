@@ -0,0 +1,198 @@
+use dugong_fields::{FieldError, VolField, parse_value};
+use dugong_mesh::Mesh;
+use dugong_runtime::{Dictionary, Value};
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::fv_matrix::FvMatrix;
+use crate::fv_options::{CellSelection, FvOption, SourceValue, register_fv_option};
+
+/// How the rates of a [`SemiImplicitSource`] relate to the cell volumes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VolumeMode {
+    /// The rates are totals over the selected cells (`absolute`), spread
+    /// over them by volume.
+    #[default]
+    Absolute,
+    /// The rates are per unit volume (`specific`).
+    Specific,
+}
+
+/// A linearized source `S = S_u + S_p ψ` in the selected cells
+/// (`semiImplicitSource`), such as a heat source or a reaction sink.
+///
+/// The rates are listed per field in an `injectionRateSuSp` dictionary as
+/// `(S_u S_p)` pairs, as in `injectionRateSuSp { T (100 0); U ((1 0 0)
+/// -0.1); }`, with `S_u` of the field's type and `S_p` a scalar; entries of
+/// other value types are left to their options. `volumeMode` is
+/// `absolute`, the default, or `specific`. A negative `S_p` strengthens
+/// the diagonal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemiImplicitSource<T> {
+    selection: CellSelection,
+    volume_mode: VolumeMode,
+    rates: Vec<(String, T, f64)>,
+}
+
+impl<T: SourceValue> SemiImplicitSource<T> {
+    /// Creates the source with the rates `(field, S_u, S_p)`.
+    pub fn new(
+        selection: CellSelection,
+        volume_mode: VolumeMode,
+        rates: Vec<(String, T, f64)>,
+    ) -> Self {
+        Self {
+            selection,
+            volume_mode,
+            rates,
+        }
+    }
+
+    /// Returns the volume mode.
+    pub fn volume_mode(&self) -> VolumeMode {
+        self.volume_mode
+    }
+
+    /// Returns the rates `(S_u, S_p)` of the field `name`, if it has a
+    /// source.
+    pub fn rates(&self, name: &str) -> Option<(T, f64)> {
+        self.rates
+            .iter()
+            .find(|(n, _, _)| n == name)
+            .map(|&(_, su, sp)| (su, sp))
+    }
+
+    /// Builds the source from its cell selection, `volumeMode` and
+    /// `injectionRateSuSp` dictionary.
+    pub fn from_dict(mesh: &Mesh, dict: &Dictionary) -> Result<Box<dyn FvOption<T>>, FieldError> {
+        let selection = CellSelection::from_dict(mesh, dict)?;
+        let volume_mode = match dict.get_word("volumeMode") {
+            None | Some("absolute") => VolumeMode::Absolute,
+            Some("specific") => VolumeMode::Specific,
+            Some(mode) => {
+                return Err(FieldError::InvalidEntry {
+                    keyword: "volumeMode".into(),
+                    reason: format!("unknown mode {mode}"),
+                });
+            }
+        };
+        let injection =
+            dict.get_dict("injectionRateSuSp")
+                .ok_or_else(|| FieldError::InvalidEntry {
+                    keyword: "injectionRateSuSp".into(),
+                    reason: "missing".into(),
+                })?;
+        let rates = injection
+            .iter()
+            .filter_map(|(name, values)| match values {
+                [Value::List(pair)] => match pair.as_slice() {
+                    [su, sp] => parse_value(su)
+                        .zip(sp.as_scalar())
+                        .map(|(su, sp)| (name.to_string(), su, sp)),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        Ok(Box::new(Self::new(selection, volume_mode, rates)))
+    }
+}
+
+impl<T: SourceValue> FvOption<T> for SemiImplicitSource<T> {
+    fn type_name(&self) -> &'static str {
+        "semiImplicitSource"
+    }
+
+    fn applies_to(&self, name: &str) -> bool {
+        self.rates(name).is_some()
+    }
+
+    fn add_source(&self, field: &VolField<'_, T>, matrix: &mut FvMatrix<'_, T>) {
+        let Some((su, sp)) = self.rates(field.name()) else {
+            return;
+        };
+        let scale = match self.volume_mode {
+            VolumeMode::Absolute => 1.0 / self.selection.volume(),
+            VolumeMode::Specific => 1.0,
+        };
+        let volumes = field.mesh().cell_volumes();
+        for &c in self.selection.cells() {
+            let v = volumes[c] * scale;
+            matrix.diag_mut()[c] += sp * v;
+            matrix.source_mut()[c] = matrix.source()[c] - su * v;
+        }
+    }
+}
+
+register_fv_option!(
+    "semiImplicitSource",
+    SemiImplicitSource::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use dugong_fields::Dimensions;
+    use dugong_mesh::Zone;
+
+    use super::*;
+    use crate::fv_options::FvOptions;
+    use crate::fv_options::tests::{dict, list, word};
+    use crate::fvm;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_semi_implicit_source_spreads_absolute_rate() {
+        let mut mesh = box_mesh([4, 1, 1], [4.0, 1.0, 1.0]);
+        mesh.add_cell_zone(Zone::new("heater", [1, 2])).unwrap();
+        let rates = dict(&[("T", vec![list(&[10.0, -0.5])])]);
+        let source = dict(&[
+            ("type", vec![word("semiImplicitSource")]),
+            ("selectionMode", vec![word("cellZone")]),
+            ("cellZone", vec![word("heater")]),
+            ("injectionRateSuSp", vec![Value::Dict(rates)]),
+        ]);
+        let options = dict(&[("heater", vec![Value::Dict(source)])]);
+        let options = FvOptions::<f64>::from_dict(&mesh, &options).unwrap();
+        let t = VolField::uniform(&mesh, "T", Dimensions::mlt(0, 0, 0), 2.0);
+        let m = options.source(&t);
+        assert_eq!(m.dimensions(), Dimensions::mlt(0, 3, -1));
+        // S = 10 / 2 − 0.5 / 2 · ψ per unit volume in each zone cell.
+        let evaluated = m.evaluate(&t);
+        for (c, expected) in [0.0, 4.5, 4.5, 0.0].into_iter().enumerate() {
+            assert!((evaluated[c] - expected).abs() < 1e-12);
+        }
+        // The source enters the equation with the opposite sign of a term.
+        let eqn = fvm::sp(
+            &VolField::uniform(&mesh, "k", Dimensions::mlt(0, 0, -1), 1.0),
+            &t,
+        ) - m;
+        assert!(eqn.diag()[1] > 1.0);
+    }
+
+    #[test]
+    fn test_semi_implicit_source_reads_vector_rates() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let rates = dict(&[
+            ("T", vec![list(&[1.0, 0.0])]),
+            (
+                "U",
+                vec![Value::List(vec![
+                    list(&[1.0, 0.0, 0.0]),
+                    Value::Scalar(0.0),
+                ])],
+            ),
+        ]);
+        let source = dict(&[
+            ("type", vec![word("semiImplicitSource")]),
+            ("volumeMode", vec![word("specific")]),
+            ("injectionRateSuSp", vec![Value::Dict(rates)]),
+        ]);
+        let source = SemiImplicitSource::<Vector>::from_dict(&mesh, &source).unwrap();
+        assert!(source.applies_to("U"));
+        assert!(!source.applies_to("T"));
+        let u = VolField::uniform(&mesh, "U", Dimensions::default(), Vector::zero());
+        let mut m = FvMatrix::new(&u, Dimensions::default());
+        source.add_source(&u, &mut m);
+        assert!((m.source()[0].x() + 1.0).abs() < 1e-12);
+    }
+}
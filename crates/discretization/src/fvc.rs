@@ -0,0 +1,27 @@
+//! Explicit finite-volume calculus
+//!
+//! Operators evaluating differential terms of known fields, such as
+//! `fvc::grad(&p)`, returning new fields rather than matrix coefficients.
+
+mod curl;
+mod div;
+mod flux;
+mod grad;
+mod grad_schemes;
+mod laplacian;
+mod reconstruct;
+mod rhie_chow;
+mod surface_integrate;
+
+pub use curl::curl;
+pub use div::{Divergence, div, div_flux};
+pub use flux::{flux, flux_with};
+pub use grad::{Gradient, grad};
+pub use grad_schemes::{
+    CellLimited, Gauss, GradScheme, GradSchemeConstructor, GradSchemeFactory, LeastSquares,
+    Limiter, new_grad_scheme, select_grad_scheme,
+};
+pub use laplacian::{laplacian, laplacian_with};
+pub use reconstruct::reconstruct;
+pub use rhie_chow::{OldTimeFlux, rhie_chow};
+pub use surface_integrate::{average, surface_integrate, surface_sum};
@@ -0,0 +1,48 @@
+use dugong_fields::{Dimensions, VolField};
+use dugong_types::tensor::Vector;
+
+use crate::fvc::grad;
+
+/// Returns the curl `∇ × U` of `field`, named `curl(<name>)`, such as the
+/// vorticity of a velocity field.
+///
+/// It is twice the Hodge dual of the skew part of the Gauss-linear
+/// gradient, `2 *skew(∇U)`. The boundary of the result takes the owner
+/// cell values.
+pub fn curl<'mesh>(field: &VolField<'mesh, Vector>) -> VolField<'mesh, Vector> {
+    let gradient = grad(field);
+    let values = gradient
+        .internal()
+        .iter()
+        .map(|g| g.skew().hodge_dual() * 2.0)
+        .collect();
+    // Safety: one value per cell.
+    VolField::new(
+        field.mesh(),
+        format!("curl({})", field.name()),
+        field.dimensions() / Dimensions::mlt(0, 1, 0),
+        values,
+    )
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::{box_mesh, field_of};
+
+    #[test]
+    fn test_curl_of_linear_field() {
+        // U = (−y + 2z, x, 3x + y): ∇ × U = (1, −1, 2).
+        let mesh = box_mesh([3, 3, 3], [1.0, 2.0, 1.5]);
+        let u = field_of(&mesh, "U", Dimensions::mlt(0, 1, -1), |x| {
+            Vector::new(-x.y() + 2.0 * x.z(), x.x(), 3.0 * x.x() + x.y())
+        });
+        let w = curl(&u);
+        assert_eq!(w.name(), "curl(U)");
+        assert_eq!(w.dimensions(), Dimensions::mlt(0, 0, -1));
+        for &v in w.internal() {
+            assert!((v - Vector::new(1.0, -1.0, 2.0)).mag() < 1e-12);
+        }
+    }
+}
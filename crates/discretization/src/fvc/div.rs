@@ -0,0 +1,151 @@
+use dugong_fields::{Dimensions, SurfaceField, VolField};
+use dugong_types::tensor::{SymmTensor, Tensor, Vector};
+use dugong_types::{FieldValue, HasDiv};
+
+use crate::fvc::surface_integrate::integrate_faces;
+
+/// Values whose divergence can be computed.
+pub trait Divergence: FieldValue + HasDiv {
+    /// Returns the inner product `S · φ` of a face area vector and a value.
+    fn dot(s: Vector, value: Self) -> Self::DivOutput;
+}
+
+impl Divergence for Vector {
+    fn dot(s: Vector, value: Self) -> f64 {
+        s * value
+    }
+}
+
+impl Divergence for Tensor {
+    fn dot(s: Vector, value: Self) -> Vector {
+        s * value
+    }
+}
+
+impl Divergence for SymmTensor {
+    fn dot(s: Vector, value: Self) -> Vector {
+        // S is symmetric, so s · S = S · s.
+        value * s
+    }
+}
+
+/// Returns the divergence of the face values of `field`,
+/// `∇·φ_P = (1/V) Σ_f S_f · φ_f`, named `div(<name>)`.
+///
+/// Boundary faces contribute their values; faces of empty patches do not.
+/// The boundary of the result takes the owner cell values.
+pub fn div<'mesh, T: Divergence>(field: &SurfaceField<'mesh, T>) -> VolField<'mesh, T::DivOutput> {
+    let mesh = field.mesh();
+    let areas = mesh.face_areas();
+    let values = field.values();
+    let sums = integrate_faces(mesh, |f| T::dot(areas[f], values[f]));
+    // Safety: one value per cell.
+    VolField::new(
+        mesh,
+        format!("div({})", field.name()),
+        field.dimensions() / Dimensions::mlt(0, 1, 0),
+        sums,
+    )
+    .unwrap()
+}
+
+/// Returns the divergence of the transport of `field` by the face flux
+/// `flux`, `∇·(F φ)_P = (1/V) Σ_f F_f φ_f`, named `div(<flux>,<name>)`;
+/// OpenFOAM's `fvc::div(phi, vf)`.
+///
+/// The face values are linearly interpolated, with the field's boundary
+/// values on boundary faces; faces of empty patches do not contribute.
+/// The boundary of the result takes the owner cell values.
+///
+/// # Panics
+///
+/// Panics if `flux` is on another mesh.
+pub fn div_flux<'mesh, T: FieldValue>(
+    flux: &SurfaceField<'_, f64>,
+    field: &VolField<'mesh, T>,
+) -> VolField<'mesh, T> {
+    let mesh = field.mesh();
+    assert!(
+        std::ptr::eq(mesh, flux.mesh()),
+        "flux {} is on another mesh",
+        flux.name()
+    );
+    let faces = SurfaceField::interpolate(field);
+    let (fluxes, values) = (flux.values(), faces.values());
+    let sums = integrate_faces(mesh, |f| values[f] * fluxes[f]);
+    let volume = Dimensions::mlt(0, 3, 0);
+    // Safety: one value per cell.
+    VolField::new(
+        mesh,
+        format!("div({},{})", flux.name(), field.name()),
+        flux.dimensions() * field.dimensions() / volume,
+        sums,
+    )
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::{box_mesh, field_of};
+
+    #[test]
+    fn test_div_of_linear_face_values_is_exact() {
+        let mesh = box_mesh([3, 2, 2], [3.0, 2.0, 1.0]);
+        let dimensions = Dimensions::mlt(0, 1, -1);
+        let u = field_of(&mesh, "U", dimensions, |x| {
+            Vector::new(2.0 * x.x(), -x.y(), 3.0 * x.z())
+        });
+        let d = div(&SurfaceField::interpolate(&u));
+        assert_eq!(d.name(), "div(interpolate(U))");
+        assert_eq!(d.dimensions(), Dimensions::mlt(0, 0, -1));
+        for &v in d.internal() {
+            assert!((v - 4.0).abs() < 1e-12);
+        }
+
+        // ∇·(x ⊗ e_x) = e_x.
+        let t = field_of(&mesh, "T", Dimensions::default(), |x| {
+            Tensor::new(x.x(), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0)
+        });
+        for &v in div(&SurfaceField::interpolate(&t)).internal() {
+            assert!((v - Vector::new(1.0, 0.0, 0.0)).mag() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_div_flux_sums_transported_values() {
+        // A uniform flux of 2 along x through unit faces carries T = x.
+        let mesh = box_mesh([4, 1, 1], [4.0, 1.0, 1.0]);
+        let t = field_of(&mesh, "T", Dimensions::default(), |x| x.x());
+        let areas = mesh.face_areas();
+        let fluxes = areas.iter().map(|s| 2.0 * s.x()).collect();
+        let phi = SurfaceField::new(&mesh, "phi", Dimensions::mlt(0, 3, -1), fluxes).unwrap();
+        let d = div_flux(&phi, &t);
+        assert_eq!(d.name(), "div(phi,T)");
+        assert_eq!(d.dimensions(), Dimensions::mlt(0, 0, -1));
+        for &v in d.internal() {
+            assert!((v - 2.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_div_flux_is_conservative() {
+        // Internal faces cancel, leaving the net boundary flux.
+        let mesh = box_mesh([3, 2, 1], [3.0, 2.0, 1.0]);
+        let t = field_of(&mesh, "T", Dimensions::default(), |x| x.x() * x.y());
+        let fluxes = (0..mesh.n_faces()).map(|f| (f % 5) as f64 - 2.0).collect();
+        let phi = SurfaceField::new(&mesh, "phi", Dimensions::default(), fluxes).unwrap();
+        let d = div_flux(&phi, &t);
+        let total: f64 = d
+            .internal()
+            .iter()
+            .zip(mesh.cell_volumes())
+            .map(|(v, vol)| v * vol)
+            .sum();
+        let faces = SurfaceField::interpolate(&t);
+        let boundary: f64 = (mesh.n_internal_faces()..mesh.n_faces())
+            .map(|f| phi.values()[f] * faces.values()[f])
+            .sum();
+        assert!((total - boundary).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,187 @@
+use dugong_fields::{Dimensions, InterpolationValue, SurfaceField, VolField};
+use dugong_mesh::PatchKind;
+use dugong_types::HasGrad;
+use dugong_types::tensor::{Tensor, Vector};
+
+use crate::fvc::GradSchemeFactory;
+use crate::fvc::surface_integrate::integrate_faces;
+
+/// Values whose gradient can be computed, with the products the gradient
+/// operators need.
+///
+/// The gradient follows the OpenFOAM convention `(∇φ)_ij = ∂φ_j / ∂x_i`.
+pub trait Gradient: InterpolationValue + HasGrad {
+    /// Iterates over the registered gradient schemes.
+    fn grad_schemes() -> impl Iterator<Item = &'static GradSchemeFactory<Self>>;
+
+    /// Returns the outer product `d ⊗ φ` of a vector and a value.
+    fn outer(d: Vector, value: Self) -> Self::GradOutput;
+
+    /// Returns the derivative `n · ∇φ` along `n`.
+    fn along(n: Vector, gradient: Self::GradOutput) -> Self;
+
+    /// Scales the gradient of each component of the value by the matching
+    /// component of `factors`.
+    fn scale(gradient: Self::GradOutput, factors: Self) -> Self::GradOutput;
+}
+
+impl Gradient for f64 {
+    fn grad_schemes() -> impl Iterator<Item = &'static GradSchemeFactory<Self>> {
+        inventory::iter::<GradSchemeFactory<Self>>.into_iter()
+    }
+
+    fn outer(d: Vector, value: Self) -> Vector {
+        d * value
+    }
+
+    fn along(n: Vector, gradient: Vector) -> Self {
+        n * gradient
+    }
+
+    fn scale(gradient: Vector, factors: Self) -> Vector {
+        gradient * factors
+    }
+}
+
+impl Gradient for Vector {
+    fn grad_schemes() -> impl Iterator<Item = &'static GradSchemeFactory<Self>> {
+        inventory::iter::<GradSchemeFactory<Self>>.into_iter()
+    }
+
+    fn outer(d: Vector, value: Self) -> Self::GradOutput {
+        d.outer(&value)
+    }
+
+    fn along(n: Vector, gradient: Self::GradOutput) -> Self {
+        n * gradient
+    }
+
+    fn scale(gradient: Self::GradOutput, factors: Self) -> Self::GradOutput {
+        let (x, y, z) = (factors.x(), factors.y(), factors.z());
+        Tensor::new(
+            gradient.xx() * x,
+            gradient.xy() * y,
+            gradient.xz() * z,
+            gradient.yx() * x,
+            gradient.yy() * y,
+            gradient.yz() * z,
+            gradient.zx() * x,
+            gradient.zy() * y,
+            gradient.zz() * z,
+        )
+    }
+}
+
+/// Returns the gradient of `field` by Gauss integration of the linearly
+/// interpolated face values, `∇φ_P = (1/V) Σ_f S_f ⊗ φ_f`, named
+/// `grad(<name>)`.
+///
+/// Boundary faces contribute the field's boundary values; faces of empty
+/// patches do not contribute. On the boundary, the patch-normal part of
+/// the owner cell's gradient is replaced by the patch-normal gradient of
+/// the field's boundary condition.
+pub fn grad<'mesh, T: Gradient>(field: &VolField<'mesh, T>) -> VolField<'mesh, T::GradOutput> {
+    let faces = SurfaceField::interpolate(field);
+    gradient_field(field, gauss_gradients(field, faces.values()))
+}
+
+/// Returns `(1/V) Σ_f S_f ⊗ φ_f` in each cell, for values on all faces,
+/// skipping faces of empty patches.
+pub(crate) fn gauss_gradients<T: Gradient>(
+    field: &VolField<'_, T>,
+    values: &[T],
+) -> Vec<T::GradOutput> {
+    let mesh = field.mesh();
+    let areas = mesh.face_areas();
+    integrate_faces(mesh, |f| T::outer(areas[f], values[f]))
+}
+
+/// Returns the gradient field of `field` with `gradients` in the cells and
+/// boundary values corrected as described for [`grad`].
+pub(crate) fn gradient_field<'mesh, T: Gradient>(
+    field: &VolField<'mesh, T>,
+    gradients: Vec<T::GradOutput>,
+) -> VolField<'mesh, T::GradOutput> {
+    let mesh = field.mesh();
+    let dimensions = field.dimensions() / Dimensions::mlt(0, 1, 0);
+    // Safety: one value per cell.
+    let mut result = VolField::new(
+        mesh,
+        format!("grad({})", field.name()),
+        dimensions,
+        gradients,
+    )
+    .unwrap();
+    for (patch, pf) in mesh.patches().iter().zip(result.patch_fields_mut()) {
+        if *patch.kind() == PatchKind::Empty || patch.kind().is_coupled() {
+            continue;
+        }
+        // Safety: the patch is one of the mesh's.
+        let boundary = field.boundary(patch.name()).unwrap();
+        let context = boundary.context();
+        let sn_grad = boundary.sn_grad();
+        for (i, (g, n)) in pf
+            .values_mut()
+            .iter_mut()
+            .zip(context.normals())
+            .enumerate()
+        {
+            *g = *g + T::outer(n, sn_grad[i] - T::along(n, *g));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::{box_mesh, field_of};
+
+    #[test]
+    fn test_grad_is_exact_for_linear_scalar_field() {
+        let mesh = box_mesh([3, 2, 2], [3.0, 1.0, 2.0]);
+        let dimensions = Dimensions::mlt(1, -1, -2);
+        let p = field_of(&mesh, "p", dimensions, |x| {
+            2.0 * x.x() + 3.0 * x.y() - x.z()
+        });
+        let g = grad(&p);
+        assert_eq!(g.name(), "grad(p)");
+        assert_eq!(g.dimensions(), Dimensions::mlt(1, -2, -2));
+        let expected = Vector::new(2.0, 3.0, -1.0);
+        for &v in g.internal() {
+            assert!((v - expected).mag() < 1e-12);
+        }
+        for pf in g.patch_fields() {
+            for &v in pf.values() {
+                assert!((v - expected).mag() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_grad_of_vector_field_follows_openfoam_convention() {
+        let mesh = box_mesh([3, 2, 1], [3.0, 2.0, 1.0]);
+        // U = (0, x, 0): only ∂U_y/∂x is nonzero, stored as the xy entry.
+        let u = field_of(&mesh, "U", Dimensions::default(), |x| {
+            Vector::new(0.0, x.x(), 0.0)
+        });
+        let g = grad(&u);
+        let expected = Tensor::new(0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        for &v in g.internal() {
+            assert!((v - expected).mag() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_grad_boundary_uses_patch_normal_gradient() {
+        // Zero-gradient walls: the boundary gradient has no normal part.
+        let mesh = box_mesh([4, 1, 1], [4.0, 1.0, 1.0]);
+        let mut t = field_of(&mesh, "T", Dimensions::default(), |x| x.x() * x.x());
+        t.set_boundary_condition("x-max", Box::new(dugong_fields::ZeroGradient))
+            .unwrap();
+        let g = grad(&t);
+        let wall = g.patch_field("x-max").unwrap().values()[0];
+        assert!(wall.x().abs() < 1e-12);
+        assert!((g.internal()[3].x() - wall.x()).abs() > 1.0);
+    }
+}
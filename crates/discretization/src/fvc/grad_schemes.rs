@@ -0,0 +1,159 @@
+use std::fmt::Debug;
+
+use dugong_fields::{FieldError, VolField};
+use dugong_runtime::{Dictionary, Value};
+use dugong_types::tensor::Vector;
+
+use crate::fvc::Gradient;
+
+mod cell_limited;
+mod gauss;
+mod least_squares;
+
+pub use cell_limited::{CellLimited, Limiter};
+pub use gauss::Gauss;
+pub use least_squares::LeastSquares;
+
+/// A scheme computing the cell gradients of a field.
+pub trait GradScheme<T: Gradient>: Debug + Send + Sync {
+    /// Returns the name used in case files, such as `Gauss`.
+    fn type_name(&self) -> &'static str;
+
+    /// Returns the gradient of `field`, named `grad(<name>)`, with boundary
+    /// values corrected as for [`grad`](crate::fvc::grad).
+    fn grad<'mesh>(&self, field: &VolField<'mesh, T>) -> VolField<'mesh, T::GradOutput>;
+}
+
+/// Builds a gradient scheme from the tokens following its name in a case
+/// file, such as `linear` in `Gauss linear`.
+pub type GradSchemeConstructor<T> = fn(&[Value]) -> Result<Box<dyn GradScheme<T>>, FieldError>;
+
+/// A gradient scheme selectable by name at run time.
+///
+/// Submit one per value type with `inventory::submit!`; the first word of
+/// a scheme specification selects it.
+pub struct GradSchemeFactory<T: 'static> {
+    /// The scheme name used in case files.
+    pub name: &'static str,
+    /// Builds the scheme.
+    pub constructor: GradSchemeConstructor<T>,
+}
+
+inventory::collect!(GradSchemeFactory<f64>);
+inventory::collect!(GradSchemeFactory<Vector>);
+
+/// Registers the constructor `$constructor` under `$name` for each listed
+/// value type; the value type is inferred from the factory.
+macro_rules! register_grad_scheme {
+    ($name:literal, $constructor:path, [$($ty:ty),*]) => {
+        $(
+            inventory::submit! {
+                $crate::fvc::GradSchemeFactory::<$ty> {
+                    name: $name,
+                    constructor: $constructor,
+                }
+            }
+        )*
+    };
+}
+
+use register_grad_scheme;
+
+/// Builds the gradient scheme named by the first word of `spec`, passing
+/// it the remaining tokens.
+///
+/// # Errors
+///
+/// Returns [`FieldError::InvalidEntry`] if `spec` does not start with a
+/// word, [`FieldError::UnknownScheme`] if no scheme of that name is
+/// registered for `T`, and any error of the scheme's constructor.
+pub fn new_grad_scheme<T: Gradient>(spec: &[Value]) -> Result<Box<dyn GradScheme<T>>, FieldError> {
+    let (name, args) = match spec {
+        [Value::Word(name), args @ ..] => (name, args),
+        _ => {
+            return Err(FieldError::InvalidEntry {
+                keyword: "scheme".into(),
+                reason: "expected a scheme name".into(),
+            });
+        }
+    };
+    let factory =
+        T::grad_schemes()
+            .find(|f| f.name == name)
+            .ok_or_else(|| FieldError::UnknownScheme {
+                name: name.to_string(),
+            })?;
+    (factory.constructor)(args)
+}
+
+/// Builds the scheme selected for `term` in a `gradSchemes` dictionary,
+/// falling back to its `default` entry.
+///
+/// # Errors
+///
+/// Returns [`FieldError::InvalidEntry`] if neither `term` nor `default` is
+/// present, and the errors of [`new_grad_scheme`].
+pub fn select_grad_scheme<T: Gradient>(
+    schemes: &Dictionary,
+    term: &str,
+) -> Result<Box<dyn GradScheme<T>>, FieldError> {
+    let spec = schemes
+        .get(term)
+        .or_else(|| schemes.get("default"))
+        .ok_or_else(|| FieldError::InvalidEntry {
+            keyword: term.to_string(),
+            reason: "no scheme and no default".into(),
+        })?;
+    new_grad_scheme(spec)
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_fields::Dimensions;
+
+    use super::*;
+    use crate::test_meshes::{box_mesh, field_of, spec};
+
+    #[test]
+    fn test_select_grad_scheme_falls_back_to_default() {
+        let mut schemes = Dictionary::new();
+        schemes.insert("default", spec(&["Gauss", "linear"]));
+        schemes.insert("grad(p)", spec(&["leastSquares"]));
+        let p = select_grad_scheme::<f64>(&schemes, "grad(p)").unwrap();
+        assert_eq!(p.type_name(), "leastSquares");
+        let u = select_grad_scheme::<Vector>(&schemes, "grad(U)").unwrap();
+        assert_eq!(u.type_name(), "Gauss");
+    }
+
+    #[test]
+    fn test_new_grad_scheme_rejects_bad_specs() {
+        assert!(matches!(
+            new_grad_scheme::<f64>(&spec(&["fourth"])),
+            Err(FieldError::UnknownScheme { .. })
+        ));
+        assert!(matches!(
+            new_grad_scheme::<f64>(&[]),
+            Err(FieldError::InvalidEntry { .. })
+        ));
+        assert!(new_grad_scheme::<f64>(&spec(&["Gauss"])).is_err());
+        assert!(new_grad_scheme::<f64>(&spec(&["leastSquares", "linear"])).is_err());
+    }
+
+    #[test]
+    fn test_grad_schemes_agree_on_linear_field() {
+        let mesh = box_mesh([3, 2, 2], [3.0, 1.0, 2.0]);
+        let p = field_of(&mesh, "p", Dimensions::default(), |x| {
+            x.x() - 2.0 * x.y() + 0.5 * x.z()
+        });
+        let expected = Vector::new(1.0, -2.0, 0.5);
+        for name in ["Gauss linear", "leastSquares"] {
+            let words: Vec<&str> = name.split(' ').collect();
+            let scheme = new_grad_scheme::<f64>(&spec(&words)).unwrap();
+            let g = scheme.grad(&p);
+            assert_eq!(g.name(), "grad(p)");
+            for &v in g.internal() {
+                assert!((v - expected).mag() < 1e-12, "{name}");
+            }
+        }
+    }
+}
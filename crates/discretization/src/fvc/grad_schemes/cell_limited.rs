@@ -0,0 +1,291 @@
+use dugong_fields::{FieldError, VolField};
+use dugong_mesh::PatchKind;
+use dugong_runtime::Value;
+use dugong_types::tensor::Vector;
+
+use crate::fvc::Gradient;
+use crate::fvc::grad::gradient_field;
+use crate::fvc::grad_schemes::{GradScheme, new_grad_scheme, register_grad_scheme};
+
+/// The function turning the ratio `r` of the allowed to the extrapolated
+/// change into a limiter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limiter {
+    /// `min(r, 1)`, the Barth–Jespersen limiter (`cellLimited`).
+    BarthJespersen,
+    /// `(r² + 2r) / (r² + r + 2)`, the smooth Venkatakrishnan limiter
+    /// (`cellLimited<Venkatakrishnan>`), which converges better on steady
+    /// problems.
+    Venkatakrishnan,
+}
+
+impl Limiter {
+    /// Returns the limiter for the ratio `r`.
+    pub fn limit(self, r: f64) -> f64 {
+        match self {
+            Limiter::BarthJespersen => r.min(1.0),
+            Limiter::Venkatakrishnan => (r * r + 2.0 * r) / (r * r + r + 2.0),
+        }
+    }
+}
+
+/// Limits the gradients of another scheme (`cellLimited Gauss linear 1`)
+/// so that extrapolating each cell value to its face centers stays within
+/// the range of the values of the face neighbors.
+///
+/// Each component of the gradient is scaled by the smallest limiter over
+/// the cell's faces. The trailing coefficient `k` in `[0, 1]` widens the
+/// allowed range by `(1/k − 1)` times its extent: 1 limits fully, 0 turns
+/// the limiter off. Boundary values count as neighbors; faces of empty
+/// patches are ignored.
+#[derive(Debug)]
+pub struct CellLimited<T: Gradient> {
+    scheme: Box<dyn GradScheme<T>>,
+    limiter: Limiter,
+    coefficient: f64,
+}
+
+impl<T: Gradient> CellLimited<T> {
+    /// Limits the gradients of `scheme` with `limiter` and the coefficient
+    /// `k`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::InvalidEntry`] if `coefficient` is not in
+    /// `[0, 1]`.
+    pub fn new(
+        scheme: Box<dyn GradScheme<T>>,
+        limiter: Limiter,
+        coefficient: f64,
+    ) -> Result<Self, FieldError> {
+        if !(0.0..=1.0).contains(&coefficient) {
+            return Err(FieldError::InvalidEntry {
+                keyword: "cellLimited".into(),
+                reason: format!("coefficient {coefficient} is not between 0 and 1"),
+            });
+        }
+        Ok(Self {
+            scheme,
+            limiter,
+            coefficient,
+        })
+    }
+
+    /// Returns the limited scheme.
+    pub fn scheme(&self) -> &dyn GradScheme<T> {
+        self.scheme.as_ref()
+    }
+
+    /// Builds a Barth–Jespersen limited scheme from the specification of
+    /// the limited scheme followed by the coefficient.
+    pub fn from_args(args: &[Value]) -> Result<Box<dyn GradScheme<T>>, FieldError> {
+        Self::with_limiter(Limiter::BarthJespersen, args)
+    }
+
+    /// Builds a Venkatakrishnan limited scheme from the specification of
+    /// the limited scheme followed by the coefficient.
+    pub fn venkatakrishnan_from_args(args: &[Value]) -> Result<Box<dyn GradScheme<T>>, FieldError> {
+        Self::with_limiter(Limiter::Venkatakrishnan, args)
+    }
+
+    fn with_limiter(
+        limiter: Limiter,
+        args: &[Value],
+    ) -> Result<Box<dyn GradScheme<T>>, FieldError> {
+        let Some((coefficient, spec)) = args.split_last() else {
+            return Err(FieldError::InvalidEntry {
+                keyword: "cellLimited".into(),
+                reason: "expected a gradient scheme and a coefficient".into(),
+            });
+        };
+        let coefficient = coefficient
+            .as_scalar()
+            .ok_or_else(|| FieldError::InvalidEntry {
+                keyword: "cellLimited".into(),
+                reason: format!("expected a coefficient, found {coefficient}"),
+            })?;
+        let scheme = new_grad_scheme(spec)?;
+        Ok(Box::new(Self::new(scheme, limiter, coefficient)?))
+    }
+
+    /// Returns the limiter of each component of the gradient in each cell.
+    fn limiters(&self, field: &VolField<'_, T>, gradients: &[T::GradOutput]) -> Vec<T> {
+        let mesh = field.mesh();
+        let (owner, neighbor) = (mesh.owner(), mesh.neighbor());
+        let n = T::N_COMPONENTS;
+        let cells = field.internal();
+        let mut max: Vec<f64> = cells
+            .iter()
+            .flat_map(|v| (0..n).map(|i| v.component(i)))
+            .collect();
+        let mut min = max.clone();
+        let mut bound = |c: usize, value: T| {
+            for i in 0..n {
+                let v = value.component(i);
+                max[c * n + i] = max[c * n + i].max(v);
+                min[c * n + i] = min[c * n + i].min(v);
+            }
+        };
+        for (f, &nb) in neighbor.iter().enumerate() {
+            bound(owner[f], cells[nb]);
+            bound(nb, cells[owner[f]]);
+        }
+        let boundary_faces = || {
+            field.patch_fields().iter().flat_map(|pf| {
+                let patch = &mesh.patches()[pf.patch()];
+                let faces = if *patch.kind() == PatchKind::Empty {
+                    0..0
+                } else {
+                    patch.range()
+                };
+                faces.zip(pf.values())
+            })
+        };
+        for (f, &value) in boundary_faces() {
+            bound(owner[f], value);
+        }
+
+        // The allowed changes from the cell value.
+        for (c, value) in cells.iter().enumerate() {
+            for i in 0..n {
+                let j = c * n + i;
+                let v = value.component(i);
+                let widen = (1.0 / self.coefficient - 1.0) * (max[j] - min[j]);
+                max[j] += widen - v;
+                min[j] -= widen + v;
+            }
+        }
+
+        let (centers, face_centers) = (mesh.cell_centers(), mesh.face_centers());
+        let mut limiters = vec![1.0_f64; cells.len() * n];
+        let mut limit_face = |c: usize, f: usize| {
+            let change = T::along(face_centers[f] - centers[c], gradients[c]);
+            for i in 0..n {
+                let j = c * n + i;
+                let e = change.component(i);
+                let r = if e > max[j] {
+                    max[j] / e
+                } else if e < min[j] {
+                    min[j] / e
+                } else {
+                    continue;
+                };
+                limiters[j] = limiters[j].min(self.limiter.limit(r));
+            }
+        };
+        for (f, &nb) in neighbor.iter().enumerate() {
+            limit_face(owner[f], f);
+            limit_face(nb, f);
+        }
+        for (f, _) in boundary_faces() {
+            limit_face(owner[f], f);
+        }
+        limiters.chunks(n).map(T::from_components).collect()
+    }
+}
+
+impl<T: Gradient> GradScheme<T> for CellLimited<T> {
+    fn type_name(&self) -> &'static str {
+        match self.limiter {
+            Limiter::BarthJespersen => "cellLimited",
+            Limiter::Venkatakrishnan => "cellLimited<Venkatakrishnan>",
+        }
+    }
+
+    fn grad<'mesh>(&self, field: &VolField<'mesh, T>) -> VolField<'mesh, T::GradOutput> {
+        let unlimited = self.scheme.grad(field);
+        if self.coefficient == 0.0 {
+            return unlimited;
+        }
+        let limiters = self.limiters(field, unlimited.internal());
+        let gradients = unlimited
+            .internal()
+            .iter()
+            .zip(limiters)
+            .map(|(&g, l)| T::scale(g, l))
+            .collect();
+        gradient_field(field, gradients)
+    }
+}
+
+register_grad_scheme!("cellLimited", CellLimited::from_args, [f64, Vector]);
+register_grad_scheme!(
+    "cellLimited<Venkatakrishnan>",
+    CellLimited::venkatakrishnan_from_args,
+    [f64, Vector]
+);
+
+#[cfg(test)]
+mod tests {
+    use dugong_fields::Dimensions;
+
+    use super::*;
+    use crate::fvc::Gauss;
+    use crate::fvc::grad_schemes::LeastSquares;
+    use crate::test_meshes::{box_mesh, field_of};
+
+    fn limited(limiter: Limiter, coefficient: f64) -> CellLimited<f64> {
+        let linear = dugong_fields::Linear::from_args(&[]).unwrap();
+        CellLimited::new(Box::new(Gauss::new(linear)), limiter, coefficient).unwrap()
+    }
+
+    #[test]
+    fn test_cell_limited_keeps_linear_gradient() {
+        let mesh = box_mesh([4, 3, 1], [4.0, 3.0, 1.0]);
+        let p = field_of(&mesh, "p", Dimensions::default(), |x| 2.0 * x.x() - x.y());
+        let scheme = CellLimited::new(Box::new(LeastSquares), Limiter::BarthJespersen, 1.0);
+        let g = scheme.unwrap().grad(&p);
+        for &v in g.internal() {
+            assert!((v - Vector::new(2.0, -1.0, 0.0)).mag() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_cell_limited_bounds_face_extrapolation() {
+        // Cell 2 extrapolates to 2.25 + 0.625 at x = 3, above its largest
+        // neighbor 2.5: r = 0.5 / 0.625 = 0.8.
+        let mesh = box_mesh([4, 1, 1], [4.0, 1.0, 1.0]);
+        let values = vec![0.0, 0.0, 2.0, 2.5];
+        let p = VolField::new(&mesh, "p", Dimensions::default(), values).unwrap();
+        let unlimited = limited(Limiter::BarthJespersen, 0.0).grad(&p);
+        assert!((unlimited.internal()[2].x() - 1.25).abs() < 1e-12);
+
+        let barth = limited(Limiter::BarthJespersen, 1.0).grad(&p);
+        assert!((barth.internal()[2].x() - 1.25 * 0.8).abs() < 1e-12);
+        // Cell 1 would extrapolate below its smallest neighbor.
+        assert!(barth.internal()[1].x().abs() < 1e-12);
+
+        let venkatakrishnan = limited(Limiter::Venkatakrishnan, 1.0).grad(&p);
+        let l = Limiter::Venkatakrishnan.limit(0.8);
+        assert!((venkatakrishnan.internal()[2].x() - 1.25 * l).abs() < 1e-12);
+
+        // k = 0.5 doubles the range to [-4.5, 3], which 0.625 stays within.
+        let relaxed = limited(Limiter::BarthJespersen, 0.5).grad(&p);
+        assert!((relaxed.internal()[2].x() - 1.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cell_limited_parses_nested_spec() {
+        let spec = [
+            Value::Word("Gauss".into()),
+            Value::Word("linear".into()),
+            Value::Label(1),
+        ];
+        let scheme = CellLimited::<Vector>::venkatakrishnan_from_args(&spec).unwrap();
+        assert_eq!(scheme.type_name(), "cellLimited<Venkatakrishnan>");
+        let mut spec = vec![Value::Word("cellLimited".into())];
+        spec.extend([Value::Word("leastSquares".into()), Value::Scalar(0.5)]);
+        assert_eq!(
+            new_grad_scheme::<f64>(&spec).unwrap().type_name(),
+            "cellLimited"
+        );
+        spec.push(Value::Scalar(2.0));
+        assert!(new_grad_scheme::<f64>(&spec).is_err());
+        assert!(CellLimited::<f64>::from_args(&[Value::Scalar(1.0)]).is_err());
+        let bad = [Value::Word("leastSquares".into()), Value::Scalar(1.5)];
+        assert!(matches!(
+            CellLimited::<f64>::from_args(&bad),
+            Err(FieldError::InvalidEntry { .. })
+        ));
+    }
+}
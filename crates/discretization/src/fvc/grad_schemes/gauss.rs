@@ -0,0 +1,62 @@
+use std::fmt;
+
+use dugong_fields::{
+    Dimensions, FieldError, SurfaceField, SurfaceInterpolation, VolField, new_surface_interpolation,
+};
+use dugong_runtime::Value;
+use dugong_types::tensor::Vector;
+
+use crate::fvc::Gradient;
+use crate::fvc::grad::{gauss_gradients, gradient_field};
+use crate::fvc::grad_schemes::{GradScheme, register_grad_scheme};
+
+/// Gauss integration of interpolated face values (`Gauss linear`),
+/// `∇φ_P = (1/V) Σ_f S_f ⊗ φ_f`.
+///
+/// The interpolation scheme is evaluated with a zero flux, so schemes
+/// that follow the flow are of no use here.
+pub struct Gauss<T: Gradient> {
+    interpolation: Box<dyn SurfaceInterpolation<T>>,
+}
+
+impl<T: Gradient> Gauss<T> {
+    /// Integrates the face values of `interpolation`.
+    pub fn new(interpolation: Box<dyn SurfaceInterpolation<T>>) -> Self {
+        Self { interpolation }
+    }
+
+    /// Returns the interpolation scheme.
+    pub fn interpolation(&self) -> &dyn SurfaceInterpolation<T> {
+        self.interpolation.as_ref()
+    }
+}
+
+impl<T: Gradient> fmt::Debug for Gauss<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Gauss")
+            .field("interpolation", &self.interpolation)
+            .finish()
+    }
+}
+
+impl<T: Gradient> Gauss<T> {
+    /// Builds the scheme, with the interpolation scheme the arguments
+    /// specify.
+    pub fn from_args(args: &[Value]) -> Result<Box<dyn GradScheme<T>>, FieldError> {
+        Ok(Box::new(Self::new(new_surface_interpolation(args)?)))
+    }
+}
+
+impl<T: Gradient> GradScheme<T> for Gauss<T> {
+    fn type_name(&self) -> &'static str {
+        "Gauss"
+    }
+
+    fn grad<'mesh>(&self, field: &VolField<'mesh, T>) -> VolField<'mesh, T::GradOutput> {
+        let flux = SurfaceField::uniform(field.mesh(), "phi", Dimensions::default(), 0.0);
+        let faces = field.interpolate(&flux, self.interpolation.as_ref());
+        gradient_field(field, gauss_gradients(field, faces.values()))
+    }
+}
+
+register_grad_scheme!("Gauss", Gauss::from_args, [f64, Vector]);
@@ -0,0 +1,99 @@
+use dugong_fields::{FieldError, VolField};
+use dugong_runtime::Value;
+use dugong_types::FieldValue;
+use dugong_types::tensor::Vector;
+
+use crate::fvc::Gradient;
+use crate::fvc::grad::gradient_field;
+use crate::fvc::grad_schemes::{GradScheme, register_grad_scheme};
+
+/// Weighted least-squares fit of the differences to the face neighbors
+/// (`leastSquares`), with the mesh's precomputed
+/// [least-squares vectors](dugong_mesh::Mesh::least_squares_vectors).
+///
+/// Exact for linear fields on any mesh, it is markedly more accurate than
+/// Gauss integration on skewed and stretched cells. Boundary faces fit the
+/// field's boundary values at the face centers.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LeastSquares;
+
+impl LeastSquares {
+    /// Builds the scheme; it takes no arguments.
+    pub fn from_args<T: Gradient>(args: &[Value]) -> Result<Box<dyn GradScheme<T>>, FieldError> {
+        if !args.is_empty() {
+            return Err(FieldError::InvalidEntry {
+                keyword: "leastSquares".into(),
+                reason: "unexpected arguments".into(),
+            });
+        }
+        Ok(Box::new(LeastSquares))
+    }
+}
+
+impl<T: Gradient> GradScheme<T> for LeastSquares {
+    fn type_name(&self) -> &'static str {
+        "leastSquares"
+    }
+
+    fn grad<'mesh>(&self, field: &VolField<'mesh, T>) -> VolField<'mesh, T::GradOutput> {
+        let mesh = field.mesh();
+        let vectors = mesh.least_squares_vectors();
+        let (owner, neighbor) = (mesh.owner(), mesh.neighbor());
+        let cells = field.internal();
+        let mut gradients = vec![T::GradOutput::zero(); mesh.n_cells()];
+        for (f, &n) in neighbor.iter().enumerate() {
+            let o = owner[f];
+            let delta = cells[n] - cells[o];
+            gradients[o] = gradients[o] + T::outer(vectors.owner()[f], delta);
+            gradients[n] = gradients[n] - T::outer(vectors.neighbor()[f], delta);
+        }
+        for pf in field.patch_fields() {
+            let range = mesh.patches()[pf.patch()].range();
+            for (f, &value) in range.zip(pf.values()) {
+                let o = owner[f];
+                gradients[o] = gradients[o] + T::outer(vectors.owner()[f], value - cells[o]);
+            }
+        }
+        gradient_field(field, gradients)
+    }
+}
+
+register_grad_scheme!("leastSquares", LeastSquares::from_args, [f64, Vector]);
+
+#[cfg(test)]
+mod tests {
+    use dugong_fields::Dimensions;
+
+    use super::*;
+    use crate::fvc::grad;
+    use crate::test_meshes::{box_mesh, field_of};
+
+    #[test]
+    fn test_least_squares_is_exact_on_distorted_mesh() {
+        let mut mesh = box_mesh([4, 3, 1], [4.0, 3.0, 1.0]);
+        // Stretch the cells in x and jitter the points in y.
+        let points = mesh
+            .points()
+            .iter()
+            .map(|p| {
+                let jitter = 0.2 * (5.0 * p.x() + 3.0 * p.y() + 2.0 * p.z()).sin();
+                Vector::new(0.25 * p.x() * p.x(), p.y() + jitter, p.z())
+            })
+            .collect();
+        mesh.move_points(points).unwrap();
+        let p = field_of(&mesh, "p", Dimensions::default(), |x| {
+            3.0 * x.x() + x.y() - 2.0 * x.z()
+        });
+        let expected = Vector::new(3.0, 1.0, -2.0);
+        let g = LeastSquares.grad(&p);
+        for &v in g.internal() {
+            assert!((v - expected).mag() < 1e-10);
+        }
+        let gauss_error = grad(&p)
+            .internal()
+            .iter()
+            .map(|&v| (v - expected).mag())
+            .fold(0.0, f64::max);
+        assert!(gauss_error > 1e-3, "{gauss_error}");
+    }
+}
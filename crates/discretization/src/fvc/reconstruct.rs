@@ -0,0 +1,97 @@
+use dugong_fields::{Dimensions, SurfaceField, VolField};
+use dugong_mesh::PatchKind;
+use dugong_types::tensor::{Tensor, Vector};
+
+/// Returns the cell vectors whose face fluxes best match `flux`, named
+/// `reconstruct(<name>)`; the inverse of [`flux`](crate::fvc::flux).
+///
+/// Each cell solves
+///
+/// ```text
+/// (Σ_f S_f ⊗ S_f / |S_f|) U_P = Σ_f S_f F_f / |S_f|
+/// ```
+///
+/// over its faces, which recovers uniform vector fields exactly. Faces of
+/// empty patches take part with a zero flux, which keeps the system
+/// regular on two-dimensional meshes. The boundary of the result takes the
+/// owner cell values.
+pub fn reconstruct<'mesh>(flux: &SurfaceField<'mesh, f64>) -> VolField<'mesh, Vector> {
+    let mesh = flux.mesh();
+    let (owner, neighbor) = (mesh.owner(), mesh.neighbor());
+    let areas = mesh.face_areas();
+    let mut fluxes = flux.values().to_vec();
+    for patch in mesh.patches() {
+        if *patch.kind() == PatchKind::Empty {
+            fluxes[patch.range()].fill(0.0);
+        }
+    }
+
+    let mut matrices = vec![Tensor::zero(); mesh.n_cells()];
+    let mut sums = vec![Vector::zero(); mesh.n_cells()];
+    let mut add = |c: usize, f: usize| {
+        let s = areas[f];
+        let r_mag = 1.0 / s.mag();
+        matrices[c] += s.outer(&s) * r_mag;
+        // The flux and the area vector both change sign for the neighbor.
+        sums[c] += s * (fluxes[f] * r_mag);
+    };
+    for (f, &n) in neighbor.iter().enumerate() {
+        add(owner[f], f);
+        add(n, f);
+    }
+    for (f, &o) in owner.iter().enumerate().skip(neighbor.len()) {
+        add(o, f);
+    }
+    let values = matrices
+        .iter()
+        .zip(sums)
+        .map(|(m, s)| m.inv() * s)
+        .collect();
+    // Safety: one value per cell.
+    VolField::new(
+        mesh,
+        format!("reconstruct({})", flux.name()),
+        flux.dimensions() / Dimensions::mlt(0, 2, 0),
+        values,
+    )
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fvc::flux;
+    use crate::test_meshes::{box_mesh, field_of};
+
+    #[test]
+    fn test_reconstruct_recovers_uniform_field() {
+        let mut mesh = box_mesh([3, 2, 2], [3.0, 2.0, 2.0]);
+        let points = mesh
+            .points()
+            .iter()
+            .map(|p| Vector::new(p.x() + 0.3 * p.y() * p.z(), p.y(), p.z() + 0.2 * p.x()))
+            .collect();
+        mesh.move_points(points).unwrap();
+        let expected = Vector::new(1.0, -2.0, 0.5);
+        let u = VolField::uniform(&mesh, "U", Dimensions::mlt(0, 1, -1), expected);
+        let phi = flux(&u);
+        let r = reconstruct(&phi);
+        assert_eq!(r.name(), "reconstruct(flux(U))");
+        assert_eq!(r.dimensions(), Dimensions::mlt(0, 1, -1));
+        for &v in r.internal() {
+            assert!((v - expected).mag() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_reconstruct_approximates_linear_field() {
+        let mesh = box_mesh([4, 4, 1], [1.0, 1.0, 0.25]);
+        let u = field_of(&mesh, "U", Dimensions::default(), |x| {
+            Vector::new(x.y(), -x.x(), 0.0)
+        });
+        let r = reconstruct(&flux(&u));
+        for (v, e) in r.internal().iter().zip(u.internal()) {
+            assert!((*v - *e).mag() < 1e-12);
+        }
+    }
+}
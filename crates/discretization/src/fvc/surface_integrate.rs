@@ -0,0 +1,152 @@
+use dugong_fields::{Dimensions, SurfaceField, VolField};
+use dugong_mesh::{Mesh, PatchKind};
+use dugong_types::FieldValue;
+
+/// Returns the net outward sum of the face values of `field` per unit
+/// volume, `(1/V) Σ_f ±q_f`, named `surfaceIntegrate(<name>)`.
+///
+/// Face values count as leaving the owner and entering the neighbor, so
+/// for a face flux this is the cell divergence used in continuity checks.
+/// Faces of empty patches do not contribute. The boundary of the result
+/// takes the owner cell values.
+pub fn surface_integrate<'mesh, T: FieldValue>(
+    field: &SurfaceField<'mesh, T>,
+) -> VolField<'mesh, T> {
+    let mesh = field.mesh();
+    let values = field.values();
+    // Safety: one value per cell.
+    VolField::new(
+        mesh,
+        format!("surfaceIntegrate({})", field.name()),
+        field.dimensions() / Dimensions::mlt(0, 3, 0),
+        integrate_faces(mesh, |f| values[f]),
+    )
+    .unwrap()
+}
+
+/// Returns the sum of the face values of `field` over the faces of each
+/// cell, `Σ_f q_f`, without regard to face orientation, named
+/// `surfaceSum(<name>)`.
+///
+/// Faces of empty patches do not contribute. The boundary of the result
+/// takes the owner cell values.
+pub fn surface_sum<'mesh, T: FieldValue>(field: &SurfaceField<'mesh, T>) -> VolField<'mesh, T> {
+    let mesh = field.mesh();
+    let values = field.values();
+    // Safety: one value per cell.
+    VolField::new(
+        mesh,
+        format!("surfaceSum({})", field.name()),
+        field.dimensions(),
+        sum_faces(mesh, |f| values[f]),
+    )
+    .unwrap()
+}
+
+/// Returns the face-area-weighted average of the face values of `field`
+/// over the faces of each cell, `Σ_f |S_f| q_f / Σ_f |S_f|`, named
+/// `average(<name>)`.
+///
+/// Faces of empty patches do not contribute. The boundary of the result
+/// takes the owner cell values.
+pub fn average<'mesh, T: FieldValue>(field: &SurfaceField<'mesh, T>) -> VolField<'mesh, T> {
+    let mesh = field.mesh();
+    let (areas, values) = (mesh.face_areas(), field.values());
+    let weights = sum_faces(mesh, |f| areas[f].mag());
+    let sums = sum_faces(mesh, |f| values[f] * areas[f].mag());
+    let averages = sums
+        .into_iter()
+        .zip(weights)
+        .map(|(s, w)| s * (1.0 / w))
+        .collect();
+    // Safety: one value per cell.
+    VolField::new(
+        mesh,
+        format!("average({})", field.name()),
+        field.dimensions(),
+        averages,
+    )
+    .unwrap()
+}
+
+/// Returns `(1/V) Σ_f q_f` in each cell, with `face(f)` the outward
+/// quantity `q_f` of the owner of face `f`, skipping faces of empty
+/// patches.
+pub(crate) fn integrate_faces<T: FieldValue>(mesh: &Mesh, face: impl Fn(usize) -> T) -> Vec<T> {
+    let (owner, neighbor) = (mesh.owner(), mesh.neighbor());
+    let mut sums = vec![T::zero(); mesh.n_cells()];
+    for (f, &n) in neighbor.iter().enumerate() {
+        let q = face(f);
+        sums[owner[f]] = sums[owner[f]] + q;
+        sums[n] = sums[n] - q;
+    }
+    add_boundary_faces(mesh, &mut sums, &face);
+    for (s, &v) in sums.iter_mut().zip(mesh.cell_volumes()) {
+        *s = *s * (1.0 / v);
+    }
+    sums
+}
+
+/// Returns `Σ_f q_f` over the faces of each cell, skipping faces of empty
+/// patches.
+fn sum_faces<T: FieldValue>(mesh: &Mesh, face: impl Fn(usize) -> T) -> Vec<T> {
+    let (owner, neighbor) = (mesh.owner(), mesh.neighbor());
+    let mut sums = vec![T::zero(); mesh.n_cells()];
+    for (f, &n) in neighbor.iter().enumerate() {
+        let q = face(f);
+        sums[owner[f]] = sums[owner[f]] + q;
+        sums[n] = sums[n] + q;
+    }
+    add_boundary_faces(mesh, &mut sums, &face);
+    sums
+}
+
+/// Adds `face(f)` of the boundary faces outside empty patches to their
+/// owner cells.
+fn add_boundary_faces<T: FieldValue>(mesh: &Mesh, sums: &mut [T], face: impl Fn(usize) -> T) {
+    let owner = mesh.owner();
+    for patch in mesh.patches() {
+        if *patch.kind() == PatchKind::Empty {
+            continue;
+        }
+        for f in patch.range() {
+            sums[owner[f]] = sums[owner[f]] + face(f);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_surface_integrate_of_uniform_flux_vanishes() {
+        let mesh = box_mesh([3, 2, 1], [3.0, 2.0, 1.0]);
+        let fluxes = mesh.face_areas().iter().map(|s| 2.0 * s.x()).collect();
+        let phi = SurfaceField::new(&mesh, "phi", Dimensions::mlt(0, 3, -1), fluxes).unwrap();
+        let d = surface_integrate(&phi);
+        assert_eq!(d.name(), "surfaceIntegrate(phi)");
+        assert_eq!(d.dimensions(), Dimensions::mlt(0, 0, -1));
+        assert!(d.internal().iter().all(|v| v.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_surface_sum_and_average_ignore_orientation() {
+        // Every unit cube has six faces, internal or boundary.
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let ones = SurfaceField::uniform(&mesh, "one", Dimensions::default(), 1.0);
+        let s = surface_sum(&ones);
+        assert_eq!(s.name(), "surfaceSum(one)");
+        for &v in s.internal() {
+            assert!((v - 6.0).abs() < 1e-12);
+        }
+        let centers = mesh.face_centers().iter().map(|x| x.x()).collect();
+        let x = SurfaceField::new(&mesh, "x", Dimensions::default(), centers).unwrap();
+        let a = average(&x);
+        assert_eq!(a.name(), "average(x)");
+        for (v, c) in a.internal().iter().zip(mesh.cell_centers()) {
+            assert!((v - c.x()).abs() < 1e-12);
+        }
+    }
+}
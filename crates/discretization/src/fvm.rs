@@ -0,0 +1,15 @@
+//! Implicit finite-volume discretization
+//!
+//! Operators discretizing differential terms of the solved field, such as
+//! `fvm::laplacian(&gamma, &t)`, into [`FvMatrix`](crate::FvMatrix)
+//! coefficients.
+
+mod ddt;
+mod div;
+mod laplacian;
+mod sources;
+
+pub use ddt::ddt;
+pub use div::div;
+pub use laplacian::{laplacian, laplacian_with};
+pub use sources::{block_sp, sp, su, su_sp};
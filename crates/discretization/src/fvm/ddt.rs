@@ -0,0 +1,14 @@
+use dugong_fields::VolField;
+
+use crate::ddt_schemes::{DdtScheme, Euler, TimeDerivative, TimeStep};
+use crate::fv_matrix::FvMatrix;
+
+/// Discretizes the time derivative `∂ψ/∂t` of `field` over `time_step`
+/// with the [`Euler`] scheme; select another with
+/// [`select_ddt_scheme`](crate::ddt_schemes::select_ddt_scheme).
+pub fn ddt<'mesh, T: TimeDerivative>(
+    field: &VolField<'mesh, T>,
+    time_step: &TimeStep,
+) -> FvMatrix<'mesh, T> {
+    Euler.fvm_ddt(field, time_step)
+}
@@ -0,0 +1,192 @@
+use dugong_fields::{Dimensions, VolField};
+use dugong_types::FieldValue;
+use dugong_types::tensor::{Tensor, Vector};
+
+use crate::block_fv_matrix::BlockFvMatrix;
+use crate::fv_matrix::FvMatrix;
+
+/// Discretizes the linear source term `s ψ` of `field` implicitly, adding
+/// `s V` to the diagonal; OpenFOAM's `fvm::Sp`.
+///
+/// A term with `s > 0` on the left-hand side of an equation, such as a
+/// sink `−s ψ` moved there, strengthens the diagonal.
+///
+/// # Panics
+///
+/// Panics if `coefficient` is on another mesh.
+pub fn sp<'mesh, T: FieldValue>(
+    coefficient: &VolField<'_, f64>,
+    field: &VolField<'mesh, T>,
+) -> FvMatrix<'mesh, T> {
+    let mut matrix = source_matrix(coefficient, field);
+    let volumes = field.mesh().cell_volumes();
+    for ((d, &s), &v) in matrix
+        .diag_mut()
+        .iter_mut()
+        .zip(coefficient.internal())
+        .zip(volumes)
+    {
+        *d = s * v;
+    }
+    matrix
+}
+
+/// Discretizes the source term `s` of the equation for `field`
+/// explicitly, subtracting `s V` from the source; OpenFOAM's `fvm::Su`.
+///
+/// # Panics
+///
+/// Panics if `source` is on another mesh.
+pub fn su<'mesh, T: FieldValue>(
+    source: &VolField<'_, T>,
+    field: &VolField<'mesh, T>,
+) -> FvMatrix<'mesh, T> {
+    assert!(
+        std::ptr::eq(field.mesh(), source.mesh()),
+        "source {} is on another mesh",
+        source.name()
+    );
+    let volume = Dimensions::mlt(0, 3, 0);
+    let mut matrix = FvMatrix::new(field, source.dimensions() * volume);
+    let volumes = field.mesh().cell_volumes();
+    for ((b, &s), &v) in matrix
+        .source_mut()
+        .iter_mut()
+        .zip(source.internal())
+        .zip(volumes)
+    {
+        *b = -(s * v);
+    }
+    matrix
+}
+
+/// Discretizes the linear source term `s ψ` of `field` implicitly where
+/// `s > 0` and explicitly where `s < 0`; OpenFOAM's `fvm::SuSp`.
+///
+/// Only coefficients that strengthen the diagonal go into the matrix, so
+/// the term never spoils its diagonal dominance whatever the sign of the
+/// source, as for reaction rates that change sign.
+///
+/// # Panics
+///
+/// Panics if `coefficient` is on another mesh.
+pub fn su_sp<'mesh, T: FieldValue>(
+    coefficient: &VolField<'_, f64>,
+    field: &VolField<'mesh, T>,
+) -> FvMatrix<'mesh, T> {
+    let mut matrix = source_matrix(coefficient, field);
+    let volumes = field.mesh().cell_volumes();
+    let cells = field.internal();
+    for (i, &s) in coefficient.internal().iter().enumerate() {
+        matrix.diag_mut()[i] = s.max(0.0) * volumes[i];
+        matrix.source_mut()[i] = cells[i] * -(s.min(0.0) * volumes[i]);
+    }
+    matrix
+}
+
+/// Discretizes the linear source term `K · U` of the vector field `field`
+/// implicitly in all components, adding `K V` to the diagonal blocks.
+///
+/// Unlike [`sp`], the tensor coefficient couples the components, as for
+/// anisotropic porous drag or the Coriolis force `2 Ω × U`.
+///
+/// # Panics
+///
+/// Panics if `coefficient` is on another mesh.
+pub fn block_sp<'mesh>(
+    coefficient: &VolField<'_, Tensor>,
+    field: &VolField<'mesh, Vector>,
+) -> BlockFvMatrix<'mesh> {
+    assert!(
+        std::ptr::eq(field.mesh(), coefficient.mesh()),
+        "coefficient {} is on another mesh",
+        coefficient.name()
+    );
+    let volume = Dimensions::mlt(0, 3, 0);
+    let dimensions = coefficient.dimensions() * field.dimensions() * volume;
+    let mut matrix = BlockFvMatrix::new(field, dimensions);
+    let volumes = field.mesh().cell_volumes();
+    for ((d, &k), &v) in matrix
+        .diag_mut()
+        .iter_mut()
+        .zip(coefficient.internal())
+        .zip(volumes)
+    {
+        *d = k * v;
+    }
+    matrix
+}
+
+/// Returns a matrix of zeros for the term `s ψ`.
+fn source_matrix<'mesh, T: FieldValue>(
+    coefficient: &VolField<'_, f64>,
+    field: &VolField<'mesh, T>,
+) -> FvMatrix<'mesh, T> {
+    assert!(
+        std::ptr::eq(field.mesh(), coefficient.mesh()),
+        "coefficient {} is on another mesh",
+        coefficient.name()
+    );
+    let volume = Dimensions::mlt(0, 3, 0);
+    FvMatrix::new(
+        field,
+        coefficient.dimensions() * field.dimensions() * volume,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_source_terms_evaluate_to_integrated_source() {
+        let mesh = box_mesh([2, 1, 1], [1.0, 1.0, 1.0]);
+        let rate = Dimensions::mlt(0, 0, -1);
+        let s = VolField::new(&mesh, "k", rate, vec![2.0, -3.0]).unwrap();
+        let t = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 4.0]).unwrap();
+        let expected = [1.0, -6.0];
+        for m in [sp(&s, &t), su_sp(&s, &t)] {
+            assert_eq!(m.dimensions(), Dimensions::mlt(0, 3, -1));
+            for (v, e) in m.evaluate(&t).iter().zip(expected) {
+                assert!((v - e).abs() < 1e-12);
+            }
+        }
+        let m = su(&s, &t);
+        assert_eq!(m.diag(), [0.0, 0.0]);
+        for (v, e) in m.evaluate(&t).iter().zip([1.0, -1.5]) {
+            assert!((v - e).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_su_sp_keeps_diagonal_non_negative() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let s = VolField::new(&mesh, "k", Dimensions::default(), vec![2.0, -3.0]).unwrap();
+        let t = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 4.0]).unwrap();
+        let close = |a: &[f64], b: [f64; 2]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-12);
+        let m = su_sp(&s, &t);
+        assert!(close(m.diag(), [2.0, 0.0]));
+        assert!(close(m.source(), [0.0, 12.0]));
+        assert!(close(sp(&s, &t).diag(), [2.0, -3.0]));
+    }
+
+    #[test]
+    fn test_block_sp_couples_components() {
+        // Rotation about z: 2 Ω × U with Ω = e_z.
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let omega = Tensor::new(0.0, -2.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        let k = VolField::uniform(&mesh, "K", Dimensions::mlt(0, 0, -1), omega);
+        let u = VolField::uniform(
+            &mesh,
+            "U",
+            Dimensions::mlt(0, 1, -1),
+            Vector::new(1.0, 0.0, 0.0),
+        );
+        let m = block_sp(&k, &u);
+        assert_eq!(m.dimensions(), Dimensions::mlt(0, 4, -2));
+        for (r, &v) in m.evaluate(&u).iter().zip(mesh.cell_volumes()) {
+            assert!((*r - Vector::new(0.0, 2.0, 0.0) * v).mag() < 1e-12);
+        }
+    }
+}
@@ -2,4 +2,16 @@
 //!
 //! Provides implicit and explicit discretization operators and FvMatrix representation.
 
-// TODO: Implement discretization operators
+mod block_fv_matrix;
+pub mod convection_schemes;
+pub mod ddt_schemes;
+mod fv_matrix;
+pub mod fv_options;
+pub mod fvc;
+pub mod fvm;
+pub mod mules;
+#[cfg(test)]
+mod test_meshes;
+
+pub use block_fv_matrix::BlockFvMatrix;
+pub use fv_matrix::FvMatrix;
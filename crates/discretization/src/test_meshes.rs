@@ -0,0 +1,45 @@
+//! Shared mesh and scheme fixtures for unit tests.
+
+use dugong_fields::{Dimensions, VolField};
+use dugong_mesh::{Mesh, voxel_mesh};
+use dugong_runtime::Value;
+use dugong_types::FieldValue;
+use dugong_types::tensor::Vector;
+
+/// Builds a structured `n[0] x n[1] x n[2]` hex mesh of the box
+/// `[0, l[0]] x [0, l[1]] x [0, l[2]]`.
+///
+/// Cells are numbered with x varying fastest. Boundary faces are grouped
+/// into the patches `x-min`, `x-max`, `y-min`, `y-max`, `z-min`, `z-max`,
+/// followed by the empty patch `void`.
+pub(crate) fn box_mesh(n: [usize; 3], l: [f64; 3]) -> Mesh {
+    let spacing = Vector::new(l[0] / n[0] as f64, l[1] / n[1] as f64, l[2] / n[2] as f64);
+    let labels = vec![1; n[0] * n[1] * n[2]];
+    // Safety: the labels fill the grid and the spacing is positive.
+    voxel_mesh(n, &labels, Vector::zero(), spacing).unwrap()
+}
+
+/// Builds a field of `f` evaluated at the cell centers and, as boundary
+/// values, at the boundary face centers.
+pub(crate) fn field_of<'m, T: FieldValue>(
+    mesh: &'m Mesh,
+    name: &str,
+    dimensions: Dimensions,
+    f: impl Fn(Vector) -> T,
+) -> VolField<'m, T> {
+    let cells = mesh.cell_centers().iter().map(|&c| f(c)).collect();
+    // Safety: one value per cell.
+    let mut field = VolField::new(mesh, name, dimensions, cells).unwrap();
+    for pf in field.patch_fields_mut() {
+        let range = mesh.patches()[pf.patch()].range();
+        for (v, &x) in pf.values_mut().iter_mut().zip(&mesh.face_centers()[range]) {
+            *v = f(x);
+        }
+    }
+    field
+}
+
+/// Builds a scheme specification from words, as read from a dictionary.
+pub(crate) fn spec(words: &[&str]) -> Vec<Value> {
+    words.iter().map(|w| Value::Word(w.to_string())).collect()
+}
@@ -0,0 +1,331 @@
+use std::fmt::Debug;
+use std::ops::Range;
+
+use dugong_mesh::{Mesh, Patch};
+use dugong_runtime::Dictionary;
+use dugong_types::FieldValue;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::error::FieldError;
+use crate::surface_field::SurfaceField;
+use crate::value::Components;
+use crate::vol_field::VolField;
+
+/// The flow fields that flux-aware boundary conditions look up.
+///
+/// Conditions that switch between inflow and outflow read the face flux;
+/// missing fields are treated as zero.
+#[derive(Clone, Copy, Default)]
+pub struct FlowFields<'a> {
+    /// The face flux, positive out of the domain on boundary faces.
+    pub flux: Option<&'a SurfaceField<'a, f64>>,
+    /// The velocity.
+    pub velocity: Option<&'a VolField<'a, Vector>>,
+}
+
+/// The geometry of one boundary patch, as seen by its boundary condition,
+/// with the flow fields it may depend on.
+#[derive(Clone, Copy)]
+pub struct PatchContext<'a> {
+    mesh: &'a Mesh,
+    patch: usize,
+    flow: FlowFields<'a>,
+}
+
+impl<'a> PatchContext<'a> {
+    /// Creates the context of patch index `patch` of `mesh`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `patch` is out of range.
+    pub fn new(mesh: &'a Mesh, patch: usize) -> Self {
+        assert!(patch < mesh.patches().len(), "patch index out of range");
+        Self {
+            mesh,
+            patch,
+            flow: FlowFields::default(),
+        }
+    }
+
+    /// Returns the context with the given flow fields, which must belong to
+    /// the same mesh.
+    pub fn with_flow(self, flow: FlowFields<'a>) -> Self {
+        Self { flow, ..self }
+    }
+
+    /// Returns the mesh.
+    pub fn mesh(&self) -> &'a Mesh {
+        self.mesh
+    }
+
+    /// Returns the index of the patch in [`Mesh::patches`].
+    pub fn index(&self) -> usize {
+        self.patch
+    }
+
+    /// Returns the patch.
+    pub fn patch(&self) -> &'a Patch {
+        &self.mesh.patches()[self.patch]
+    }
+
+    /// Returns the number of faces.
+    pub fn size(&self) -> usize {
+        self.patch().size()
+    }
+
+    /// Returns the mesh face indices of the patch.
+    pub fn faces(&self) -> Range<usize> {
+        self.patch().range()
+    }
+
+    /// Returns the cell next to each face.
+    pub fn face_cells(&self) -> &'a [usize] {
+        &self.mesh.owner()[self.faces()]
+    }
+
+    /// Returns the outward area vector of each face.
+    pub fn face_areas(&self) -> &'a [Vector] {
+        &self.mesh.face_areas()[self.faces()]
+    }
+
+    /// Returns the center of each face.
+    pub fn face_centers(&self) -> &'a [Vector] {
+        &self.mesh.face_centers()[self.faces()]
+    }
+
+    /// Returns the outward unit normal of each face.
+    pub fn normals(&self) -> Vec<Vector> {
+        self.face_areas().iter().map(|&s| s / s.mag()).collect()
+    }
+
+    /// Returns `1 / (n · d)` for each face, where `d` runs from the cell
+    /// center to the face center: the inverse normal distance used in
+    /// patch-normal gradients.
+    pub fn delta_coeffs(&self) -> Vec<f64> {
+        let centers = self.mesh.cell_centers();
+        self.normals()
+            .iter()
+            .zip(self.face_centers())
+            .zip(self.face_cells())
+            .map(|((&n, &xf), &c)| 1.0 / (n * (xf - centers[c])).max(f64::MIN_POSITIVE))
+            .collect()
+    }
+
+    /// Returns the face flux on the patch, if known.
+    pub fn flux(&self) -> Option<&'a [f64]> {
+        self.flow.flux.map(|phi| phi.patch_values(self.patch))
+    }
+
+    /// Returns `true` for each face whose flux enters the domain. Faces
+    /// without a known flux count as outflow.
+    pub fn inflow(&self) -> Vec<bool> {
+        match self.flux() {
+            Some(phi) => phi.iter().map(|&f| f < 0.0).collect(),
+            None => vec![false; self.size()],
+        }
+    }
+
+    /// Returns the velocity on the patch faces, if known.
+    pub fn velocity(&self) -> Option<&'a [Vector]> {
+        self.flow
+            .velocity
+            .map(|u| u.patch_fields()[self.patch].values())
+    }
+
+    /// Returns the values of the cells next to each face.
+    pub fn patch_internal<T: Copy>(&self, internal: &[T]) -> Vec<T> {
+        self.face_cells().iter().map(|&c| internal[c]).collect()
+    }
+}
+
+/// Linear coefficients of a boundary face quantity in the value of the
+/// adjacent cell: the quantity on face `i` is
+/// `internal[i] * φ_P + boundary[i]`.
+///
+/// Implicit discretizations put the `internal` part in the matrix and the
+/// `boundary` part in the source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coefficients<T> {
+    /// Coefficients of the adjacent cell value.
+    pub internal: Vec<f64>,
+    /// Explicit parts.
+    pub boundary: Vec<T>,
+}
+
+/// A boundary condition on one patch of a [`VolField`](crate::VolField).
+///
+/// The condition computes the patch face values from the cell values and
+/// describes the face values and patch-normal gradients as [`Coefficients`]
+/// for implicit assembly. The field stores the face values; the condition
+/// holds whatever data it needs to compute them.
+pub trait BoundaryCondition<T>: Debug + Send + Sync {
+    /// Returns the type name used in case files, such as `fixedValue`.
+    fn type_name(&self) -> &'static str;
+
+    /// Computes the face `values` from the `internal` cell values.
+    fn evaluate(&mut self, patch: &PatchContext<'_>, internal: &[T], values: &mut [T]);
+
+    /// Returns the coefficients of the face values.
+    fn value_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        internal: &[T],
+        values: &[T],
+    ) -> Coefficients<T>;
+
+    /// Returns the coefficients of the patch-normal gradient.
+    fn gradient_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        internal: &[T],
+        values: &[T],
+    ) -> Coefficients<T>;
+
+    /// Updates time-dependent data at the start of a time step. Does
+    /// nothing by default.
+    fn update(&mut self, patch: &PatchContext<'_>, time: f64) {
+        let _ = (patch, time);
+    }
+
+    /// Adds the entries of the condition's case-file dictionary other than
+    /// `type` and `value`, which the field writes. Adds nothing by default.
+    fn write_entries(&self, dict: &mut Dictionary) {
+        let _ = dict;
+    }
+
+    /// Returns a boxed copy of the condition.
+    fn clone_box(&self) -> Box<dyn BoundaryCondition<T>>;
+}
+
+impl<T> Clone for Box<dyn BoundaryCondition<T>> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Builds a boundary condition for a patch from its case-file dictionary.
+pub type Constructor<T> =
+    fn(&PatchContext<'_>, &Dictionary) -> Result<Box<dyn BoundaryCondition<T>>, FieldError>;
+
+/// A boundary condition type selectable by name at run time.
+///
+/// Submit one per value type with `inventory::submit!`; the `type` entry of
+/// a patch dictionary selects it.
+pub struct BoundaryConditionFactory<T: 'static> {
+    /// The type name used in case files.
+    pub name: &'static str,
+    /// Builds the condition.
+    pub constructor: Constructor<T>,
+}
+
+inventory::collect!(BoundaryConditionFactory<f64>);
+inventory::collect!(BoundaryConditionFactory<Vector>);
+inventory::collect!(BoundaryConditionFactory<Tensor>);
+inventory::collect!(BoundaryConditionFactory<SymmTensor>);
+inventory::collect!(BoundaryConditionFactory<SphericalTensor>);
+
+/// Value types with a registry of run-time selectable boundary conditions.
+pub trait BoundaryValue: Components {
+    /// Iterates over the registered boundary condition types.
+    fn factories() -> impl Iterator<Item = &'static BoundaryConditionFactory<Self>>;
+}
+
+macro_rules! impl_boundary_value {
+    ($($ty:ty),*) => {
+        $(
+            impl BoundaryValue for $ty {
+                fn factories() -> impl Iterator<Item = &'static BoundaryConditionFactory<Self>> {
+                    inventory::iter::<BoundaryConditionFactory<Self>>.into_iter()
+                }
+            }
+        )*
+    };
+}
+
+impl_boundary_value!(f64, Vector, Tensor, SymmTensor, SphericalTensor);
+
+/// Builds the boundary condition selected by the `type` entry of `dict`.
+///
+/// # Errors
+///
+/// Returns [`FieldError::InvalidEntry`] if `type` is missing,
+/// [`FieldError::UnknownBoundaryCondition`] if no condition of that name is
+/// registered for `T`, and any error of the condition's constructor.
+pub fn new_boundary_condition<T: BoundaryValue>(
+    patch: &PatchContext<'_>,
+    dict: &Dictionary,
+) -> Result<Box<dyn BoundaryCondition<T>>, FieldError> {
+    let name = dict
+        .get_word("type")
+        .ok_or_else(|| FieldError::InvalidEntry {
+            keyword: "type".into(),
+            reason: "missing".into(),
+        })?;
+    let factory = T::factories().find(|f| f.name == name).ok_or_else(|| {
+        FieldError::UnknownBoundaryCondition {
+            name: name.to_string(),
+        }
+    })?;
+    (factory.constructor)(patch, dict)
+}
+
+/// Returns the value coefficients of faces whose values are fixed at
+/// `values`.
+pub(crate) fn fixed_value_coefficients<T: FieldValue>(values: &[T]) -> Coefficients<T> {
+    Coefficients {
+        internal: vec![0.0; values.len()],
+        boundary: values.to_vec(),
+    }
+}
+
+/// Returns the gradient coefficients `δ (φ_b − φ_P)` of faces whose values
+/// are fixed at `values`.
+pub(crate) fn fixed_value_gradient_coefficients<T: FieldValue>(
+    patch: &PatchContext<'_>,
+    values: &[T],
+) -> Coefficients<T> {
+    let delta = patch.delta_coeffs();
+    Coefficients {
+        internal: delta.iter().map(|&d| -d).collect(),
+        boundary: values.iter().zip(&delta).map(|(&v, &d)| v * d).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_runtime::Value;
+
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_patch_context_geometry() {
+        let mesh = box_mesh([2, 1, 1], [4.0, 1.0, 1.0]);
+        let patch = PatchContext::new(&mesh, mesh.patch_index("x-max").unwrap());
+        assert_eq!(patch.size(), 1);
+        assert_eq!(patch.face_cells(), &[1]);
+        assert!((patch.normals()[0] - Vector::new(1.0, 0.0, 0.0)).mag() < 1e-12);
+        assert!((patch.delta_coeffs()[0] - 1.0).abs() < 1e-12);
+        assert_eq!(patch.patch_internal(&[3.0, 7.0]), vec![7.0]);
+    }
+
+    #[test]
+    fn test_new_boundary_condition_selects_by_type() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let patch = PatchContext::new(&mesh, 0);
+        let mut dict = Dictionary::new();
+        dict.insert("type", vec![Value::Word("calculated".into())]);
+        let bc = new_boundary_condition::<Vector>(&patch, &dict).unwrap();
+        assert_eq!(bc.type_name(), "calculated");
+
+        dict.insert("type", vec![Value::Word("noSuchCondition".into())]);
+        assert!(matches!(
+            new_boundary_condition::<f64>(&patch, &dict),
+            Err(FieldError::UnknownBoundaryCondition { .. })
+        ));
+        assert!(matches!(
+            new_boundary_condition::<f64>(&patch, &Dictionary::new()),
+            Err(FieldError::InvalidEntry { .. })
+        ));
+    }
+}
@@ -0,0 +1,46 @@
+//! Built-in boundary conditions, registered by their case-file type names.
+
+mod calculated;
+mod coded;
+mod expression_fixed_value;
+mod fixed_gradient;
+mod fixed_value;
+mod inlet_outlet;
+mod mixed;
+mod no_slip;
+mod pressure_inlet_outlet_velocity;
+mod symmetry;
+mod total_pressure;
+mod uniform_fixed_value;
+mod zero_gradient;
+
+pub use calculated::Calculated;
+pub use coded::{CodedBC, CodedFn};
+pub use expression_fixed_value::ExpressionFixedValue;
+pub use fixed_gradient::FixedGradient;
+pub use fixed_value::FixedValue;
+pub use inlet_outlet::{InletOutlet, OutletInlet};
+pub use mixed::Mixed;
+pub use no_slip::NoSlip;
+pub use pressure_inlet_outlet_velocity::PressureInletOutletVelocity;
+pub use symmetry::{Slip, Symmetry};
+pub use total_pressure::TotalPressure;
+pub use uniform_fixed_value::UniformFixedValue;
+pub use zero_gradient::ZeroGradient;
+
+/// Registers the constructor `$constructor` under `$name` for each listed
+/// value type; the value type is inferred from the factory.
+macro_rules! register {
+    ($name:literal, $constructor:path, [$($ty:ty),*]) => {
+        $(
+            inventory::submit! {
+                $crate::boundary::BoundaryConditionFactory::<$ty> {
+                    name: $name,
+                    constructor: $constructor,
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use register;
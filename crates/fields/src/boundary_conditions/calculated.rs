@@ -0,0 +1,85 @@
+use dugong_runtime::Dictionary;
+use dugong_types::FieldValue;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::boundary::{
+    BoundaryCondition, Coefficients, PatchContext, fixed_value_coefficients,
+    fixed_value_gradient_coefficients,
+};
+use crate::boundary_conditions::register;
+use crate::error::FieldError;
+
+/// Keeps the face values as they were last set (`calculated`).
+///
+/// This is the condition of derived fields, whose boundary values are
+/// computed along with their cell values. Implicitly, the face values act
+/// as fixed values.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Calculated;
+
+impl Calculated {
+    /// Builds the condition from a patch dictionary; no entries are needed.
+    pub fn from_dict<T: FieldValue>(
+        _patch: &PatchContext<'_>,
+        _dict: &Dictionary,
+    ) -> Result<Box<dyn BoundaryCondition<T>>, FieldError> {
+        Ok(Box::new(Calculated))
+    }
+}
+
+impl<T: FieldValue> BoundaryCondition<T> for Calculated {
+    fn type_name(&self) -> &'static str {
+        "calculated"
+    }
+
+    fn evaluate(&mut self, _patch: &PatchContext<'_>, _internal: &[T], _values: &mut [T]) {}
+
+    fn value_coefficients(
+        &self,
+        _patch: &PatchContext<'_>,
+        _internal: &[T],
+        values: &[T],
+    ) -> Coefficients<T> {
+        fixed_value_coefficients(values)
+    }
+
+    fn gradient_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[T],
+        values: &[T],
+    ) -> Coefficients<T> {
+        fixed_value_gradient_coefficients(patch, values)
+    }
+
+    fn clone_box(&self) -> Box<dyn BoundaryCondition<T>> {
+        Box::new(*self)
+    }
+}
+
+register!(
+    "calculated",
+    Calculated::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_calculated_treats_values_as_fixed() {
+        let mesh = box_mesh([1, 1, 1], [2.0, 1.0, 1.0]);
+        let patch = PatchContext::new(&mesh, mesh.patch_index("x-max").unwrap());
+        let mut values = [5.0];
+        let mut bc = Calculated;
+        bc.evaluate(&patch, &[1.0], &mut values);
+        assert_eq!(values, [5.0]);
+        let value = bc.value_coefficients(&patch, &[1.0], &values);
+        assert_eq!((value.internal, value.boundary), (vec![0.0], vec![5.0]));
+        // The face is half a cell width (1) from the cell center.
+        let grad = bc.gradient_coefficients(&patch, &[1.0], &values);
+        assert_eq!((grad.internal, grad.boundary), (vec![-1.0], vec![5.0]));
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+use std::sync::Arc;
+
+use dugong_types::tensor::Vector;
+
+use crate::boundary::{
+    BoundaryCondition, Coefficients, PatchContext, fixed_value_coefficients,
+    fixed_value_gradient_coefficients,
+};
+use crate::value::Components;
+
+/// The function of a [`CodedBC`]: given the face centers, the time and the
+/// values of the cells next to each face, it writes the face values.
+pub type CodedFn<T> = dyn Fn(&[Vector], f64, &[T], &mut [T]) + Send + Sync;
+
+/// Fixes the face values computed by a user closure (`codedFixedValue`).
+///
+/// Covers bespoke conditions without a dedicated type. The closure runs on
+/// every evaluation with the time of the last
+/// [`update`](BoundaryCondition::update), initially 0. Implicitly, the
+/// computed values act as fixed values.
+#[derive(Clone)]
+pub struct CodedBC<T> {
+    function: Arc<CodedFn<T>>,
+    time: f64,
+}
+
+impl<T> CodedBC<T> {
+    /// Creates the condition from its closure.
+    pub fn new(function: impl Fn(&[Vector], f64, &[T], &mut [T]) + Send + Sync + 'static) -> Self {
+        Self {
+            function: Arc::new(function),
+            time: 0.0,
+        }
+    }
+
+    /// Returns the time the closure is evaluated at.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+}
+
+impl<T> fmt::Debug for CodedBC<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CodedBC")
+            .field("time", &self.time)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Components> BoundaryCondition<T> for CodedBC<T> {
+    fn type_name(&self) -> &'static str {
+        "codedFixedValue"
+    }
+
+    fn evaluate(&mut self, patch: &PatchContext<'_>, internal: &[T], values: &mut [T]) {
+        let cells = patch.patch_internal(internal);
+        (self.function)(patch.face_centers(), self.time, &cells, values);
+    }
+
+    fn value_coefficients(
+        &self,
+        _patch: &PatchContext<'_>,
+        _internal: &[T],
+        values: &[T],
+    ) -> Coefficients<T> {
+        fixed_value_coefficients(values)
+    }
+
+    fn gradient_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[T],
+        values: &[T],
+    ) -> Coefficients<T> {
+        fixed_value_gradient_coefficients(patch, values)
+    }
+
+    fn update(&mut self, _patch: &PatchContext<'_>, time: f64) {
+        self.time = time;
+    }
+
+    fn clone_box(&self) -> Box<dyn BoundaryCondition<T>> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+    use crate::vol_field::VolField;
+
+    #[test]
+    fn test_coded_bc_calls_closure_with_time_and_cells() {
+        let mesh = box_mesh([1, 2, 1], [2.0, 2.0, 1.0]);
+        let mut field = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 2.0]).unwrap();
+        // A parabolic profile in y, scaled by time, plus the cell value.
+        let bc = CodedBC::new(
+            |centers: &[Vector], time, cells: &[f64], values: &mut [f64]| {
+                for ((v, x), c) in values.iter_mut().zip(centers).zip(cells) {
+                    *v = time * x.y() * (2.0 - x.y()) + c;
+                }
+            },
+        );
+        field.set_boundary_condition("x-max", Box::new(bc)).unwrap();
+        assert_eq!(field.patch_field("x-max").unwrap().values(), &[1.0, 2.0]);
+
+        field.update_boundaries(4.0);
+        let patch = field.patch_field("x-max").unwrap();
+        assert_eq!(patch.values(), &[4.0, 5.0]);
+        assert_eq!(patch.condition().type_name(), "codedFixedValue");
+        let context = field.patch_context(patch.patch());
+        let value =
+            patch
+                .condition()
+                .value_coefficients(&context, field.internal(), patch.values());
+        assert_eq!(
+            (value.internal, value.boundary),
+            (vec![0.0; 2], vec![4.0, 5.0])
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use dugong_runtime::Dictionary;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::boundary::{
+    BoundaryCondition, Coefficients, PatchContext, fixed_value_coefficients,
+    fixed_value_gradient_coefficients,
+};
+use crate::boundary_conditions::register;
+use crate::error::FieldError;
+use crate::expression::{Expression, evaluate_at_points, format_expressions, parse_expressions};
+use crate::value::Components;
+
+/// Fixes the face values at expressions of the face center and time
+/// (`exprFixedValue`), such as `valueExpr "4*y*(1-y)*min(t, 1)";`.
+///
+/// Non-scalar values take a list of one expression per component. The
+/// expressions may use `x`, `y`, `z` and `t`, and are re-evaluated at each
+/// [`update`](BoundaryCondition::update).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpressionFixedValue<T> {
+    expressions: Vec<Expression>,
+    values: Vec<T>,
+}
+
+impl<T: Components> ExpressionFixedValue<T> {
+    /// Creates the condition from one expression per component, evaluated
+    /// at time 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`evaluate_at_points`].
+    pub fn new(patch: &PatchContext<'_>, expressions: Vec<Expression>) -> Result<Self, FieldError> {
+        let values = evaluate_at_points(&expressions, patch.face_centers(), 0.0)?;
+        Ok(Self {
+            expressions,
+            values,
+        })
+    }
+
+    /// Returns the expressions, one per component.
+    pub fn expressions(&self) -> &[Expression] {
+        &self.expressions
+    }
+
+    /// Builds the condition from the `valueExpr` entry of a patch
+    /// dictionary; see [`parse_expressions`].
+    pub fn from_dict(
+        patch: &PatchContext<'_>,
+        dict: &Dictionary,
+    ) -> Result<Box<dyn BoundaryCondition<T>>, FieldError> {
+        let values = dict
+            .get("valueExpr")
+            .ok_or_else(|| FieldError::InvalidEntry {
+                keyword: "valueExpr".into(),
+                reason: "missing".into(),
+            })?;
+        let expressions = parse_expressions::<T>("valueExpr", values)?;
+        Ok(Box::new(Self::new(patch, expressions)?))
+    }
+}
+
+impl<T: Components> BoundaryCondition<T> for ExpressionFixedValue<T> {
+    fn type_name(&self) -> &'static str {
+        "exprFixedValue"
+    }
+
+    fn evaluate(&mut self, _patch: &PatchContext<'_>, _internal: &[T], values: &mut [T]) {
+        values.copy_from_slice(&self.values);
+    }
+
+    fn value_coefficients(
+        &self,
+        _patch: &PatchContext<'_>,
+        _internal: &[T],
+        _values: &[T],
+    ) -> Coefficients<T> {
+        fixed_value_coefficients(&self.values)
+    }
+
+    fn gradient_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[T],
+        _values: &[T],
+    ) -> Coefficients<T> {
+        fixed_value_gradient_coefficients(patch, &self.values)
+    }
+
+    fn update(&mut self, patch: &PatchContext<'_>, time: f64) {
+        // Safety: the variables were checked when the condition was built.
+        self.values = evaluate_at_points(&self.expressions, patch.face_centers(), time).unwrap();
+    }
+
+    fn write_entries(&self, dict: &mut Dictionary) {
+        dict.insert("valueExpr", format_expressions(&self.expressions));
+    }
+
+    fn clone_box(&self) -> Box<dyn BoundaryCondition<T>> {
+        Box::new(self.clone())
+    }
+}
+
+register!(
+    "exprFixedValue",
+    ExpressionFixedValue::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use dugong_runtime::Value;
+
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+    use crate::vol_field::VolField;
+
+    #[test]
+    fn test_expression_fixed_value_follows_position_and_time() {
+        let mesh = box_mesh([1, 2, 1], [1.0, 2.0, 1.0]);
+        let mut field = VolField::uniform(&mesh, "U", Dimensions::default(), Vector::zero());
+        let mut dict = Dictionary::new();
+        dict.insert("type", vec![Value::Word("exprFixedValue".into())]);
+        let list = vec![Value::Str("y*t".into()), Value::Label(0), Value::Label(0)];
+        dict.insert("valueExpr", vec![Value::List(list)]);
+        field
+            .set_boundary_condition_from_dict("x-min", &dict)
+            .unwrap();
+        assert_eq!(
+            field.patch_field("x-min").unwrap().values(),
+            &[Vector::zero(); 2]
+        );
+
+        field.update_boundaries(2.0);
+        let patch = field.patch_field("x-min").unwrap();
+        assert_eq!(
+            patch.values(),
+            &[Vector::new(1.0, 0.0, 0.0), Vector::new(3.0, 0.0, 0.0)]
+        );
+        let mut written = Dictionary::new();
+        patch.condition().write_entries(&mut written);
+        let strings = ["y*t", "0", "0"].map(|s| Value::Str(s.into()));
+        assert_eq!(
+            written.get("valueExpr"),
+            Some(&[Value::List(strings.to_vec())][..])
+        );
+
+        dict.insert("valueExpr", vec![Value::Str("p".into())]);
+        let mut p = VolField::uniform(&mesh, "p", Dimensions::default(), 0.0);
+        assert!(matches!(
+            p.set_boundary_condition_from_dict("x-min", &dict),
+            Err(FieldError::InvalidExpression { .. })
+        ));
+    }
+}
@@ -0,0 +1,122 @@
+use dugong_runtime::Dictionary;
+use dugong_types::FieldValue;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::boundary::{BoundaryCondition, Coefficients, PatchContext};
+use crate::boundary_conditions::register;
+use crate::error::FieldError;
+use crate::value::{Components, format_values, lookup_values};
+
+/// Fixes the patch-normal gradient (`fixedGradient`, a Neumann condition):
+/// the face value is `φ_P + g / δ`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedGradient<T> {
+    gradient: Vec<T>,
+}
+
+impl<T: FieldValue> FixedGradient<T> {
+    /// Creates the condition with one gradient per patch face.
+    pub fn new(gradient: Vec<T>) -> Self {
+        Self { gradient }
+    }
+
+    /// Returns the fixed gradients.
+    pub fn gradient(&self) -> &[T] {
+        &self.gradient
+    }
+
+    /// Returns the fixed gradients for modification.
+    pub fn gradient_mut(&mut self) -> &mut [T] {
+        &mut self.gradient
+    }
+}
+
+impl<T: Components> FixedGradient<T> {
+    /// Builds the condition from the `gradient` entry of a patch dictionary.
+    pub fn from_dict(
+        patch: &PatchContext<'_>,
+        dict: &Dictionary,
+    ) -> Result<Box<dyn BoundaryCondition<T>>, FieldError> {
+        let gradient = lookup_values(dict, "gradient", patch.size())?;
+        Ok(Box::new(Self::new(gradient)))
+    }
+}
+
+impl<T: Components> BoundaryCondition<T> for FixedGradient<T> {
+    fn type_name(&self) -> &'static str {
+        "fixedGradient"
+    }
+
+    fn evaluate(&mut self, patch: &PatchContext<'_>, internal: &[T], values: &mut [T]) {
+        let delta = patch.delta_coeffs();
+        for (i, (v, &c)) in values.iter_mut().zip(patch.face_cells()).enumerate() {
+            *v = internal[c] + self.gradient[i] * (1.0 / delta[i]);
+        }
+    }
+
+    fn value_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[T],
+        _values: &[T],
+    ) -> Coefficients<T> {
+        let delta = patch.delta_coeffs();
+        Coefficients {
+            internal: vec![1.0; patch.size()],
+            boundary: self
+                .gradient
+                .iter()
+                .zip(&delta)
+                .map(|(&g, &d)| g * (1.0 / d))
+                .collect(),
+        }
+    }
+
+    fn gradient_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[T],
+        _values: &[T],
+    ) -> Coefficients<T> {
+        Coefficients {
+            internal: vec![0.0; patch.size()],
+            boundary: self.gradient.clone(),
+        }
+    }
+
+    fn write_entries(&self, dict: &mut Dictionary) {
+        dict.insert("gradient", format_values(&self.gradient));
+    }
+
+    fn clone_box(&self) -> Box<dyn BoundaryCondition<T>> {
+        Box::new(self.clone())
+    }
+}
+
+register!(
+    "fixedGradient",
+    FixedGradient::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_fixed_gradient_extrapolates_by_gradient() {
+        // The face is 1 from the cell center.
+        let mesh = box_mesh([1, 1, 1], [2.0, 1.0, 1.0]);
+        let patch = PatchContext::new(&mesh, mesh.patch_index("x-max").unwrap());
+        let internal = [7.0];
+        let mut values = [0.0];
+        let mut bc = FixedGradient::new(vec![2.0]);
+        bc.evaluate(&patch, &internal, &mut values);
+        assert_eq!(values, [9.0]);
+        let value = bc.value_coefficients(&patch, &internal, &values);
+        assert_eq!((value.internal, value.boundary), (vec![1.0], vec![2.0]));
+        let grad = bc.gradient_coefficients(&patch, &internal, &values);
+        assert_eq!((grad.internal, grad.boundary), (vec![0.0], vec![2.0]));
+    }
+}
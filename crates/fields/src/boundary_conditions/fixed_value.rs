@@ -0,0 +1,204 @@
+use dugong_runtime::Dictionary;
+use dugong_types::FieldValue;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::boundary::{
+    BoundaryCondition, Coefficients, PatchContext, fixed_value_coefficients,
+    fixed_value_gradient_coefficients,
+};
+use crate::boundary_conditions::register;
+use crate::error::FieldError;
+use crate::table::Table;
+use crate::value::{Components, lookup_values, parse_value};
+
+/// Fixes the face values (`fixedValue`, a Dirichlet condition).
+///
+/// Spatially varying values can be given per face, computed from the face
+/// centers with [`FixedValue::from_fn`], or sampled from a profile with
+/// [`FixedValue::from_profile`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedValue<T> {
+    values: Vec<T>,
+}
+
+impl<T: FieldValue> FixedValue<T> {
+    /// Creates the condition with one value per patch face.
+    pub fn new(values: Vec<T>) -> Self {
+        Self { values }
+    }
+
+    /// Creates the condition with the value `f(x)` at each face center `x`.
+    pub fn from_fn(patch: &PatchContext<'_>, f: impl Fn(Vector) -> T) -> Self {
+        Self::new(patch.face_centers().iter().map(|&x| f(x)).collect())
+    }
+
+    /// Creates the condition from a profile tabulated against the distance
+    /// `(x − origin) · direction` of each face center `x`.
+    pub fn from_profile(
+        patch: &PatchContext<'_>,
+        origin: Vector,
+        direction: Vector,
+        profile: &Table<T>,
+    ) -> Self {
+        Self::from_fn(patch, |x| profile.value((x - origin) * direction))
+    }
+
+    /// Returns the fixed face values.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns the fixed face values for modification.
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+}
+
+impl<T: Components> FixedValue<T> {
+    /// Builds the condition from the `value` entry of a patch dictionary.
+    pub fn from_dict(
+        patch: &PatchContext<'_>,
+        dict: &Dictionary,
+    ) -> Result<Box<dyn BoundaryCondition<T>>, FieldError> {
+        Ok(Box::new(Self::new(lookup_values(
+            dict,
+            "value",
+            patch.size(),
+        )?)))
+    }
+
+    /// Builds the condition from the `profile` table, `origin` and
+    /// `direction` entries of a patch dictionary (`profileFixedValue`); see
+    /// [`FixedValue::from_profile`] and [`Table::from_dict`].
+    pub fn from_profile_dict(
+        patch: &PatchContext<'_>,
+        dict: &Dictionary,
+    ) -> Result<Box<dyn BoundaryCondition<T>>, FieldError> {
+        let vector = |keyword: &str| {
+            dict.get(keyword)
+                .and_then(|values| match values {
+                    [v] => parse_value::<Vector>(v),
+                    _ => None,
+                })
+                .ok_or_else(|| FieldError::InvalidEntry {
+                    keyword: keyword.to_string(),
+                    reason: "expected a vector".into(),
+                })
+        };
+        let profile = Table::from_dict(dict, "profile")?;
+        let (origin, direction) = (vector("origin")?, vector("direction")?);
+        Ok(Box::new(Self::from_profile(
+            patch, origin, direction, &profile,
+        )))
+    }
+}
+
+impl<T: Components> BoundaryCondition<T> for FixedValue<T> {
+    fn type_name(&self) -> &'static str {
+        "fixedValue"
+    }
+
+    fn evaluate(&mut self, _patch: &PatchContext<'_>, _internal: &[T], values: &mut [T]) {
+        values.copy_from_slice(&self.values);
+    }
+
+    fn value_coefficients(
+        &self,
+        _patch: &PatchContext<'_>,
+        _internal: &[T],
+        _values: &[T],
+    ) -> Coefficients<T> {
+        fixed_value_coefficients(&self.values)
+    }
+
+    fn gradient_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[T],
+        _values: &[T],
+    ) -> Coefficients<T> {
+        fixed_value_gradient_coefficients(patch, &self.values)
+    }
+
+    fn clone_box(&self) -> Box<dyn BoundaryCondition<T>> {
+        Box::new(self.clone())
+    }
+}
+
+register!(
+    "fixedValue",
+    FixedValue::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+register!(
+    "profileFixedValue",
+    FixedValue::from_profile_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use dugong_runtime::Value;
+
+    use super::*;
+    use crate::boundary::new_boundary_condition;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_fixed_value_coefficients_are_dirichlet() {
+        let mesh = box_mesh([1, 1, 1], [2.0, 1.0, 1.0]);
+        let patch = PatchContext::new(&mesh, mesh.patch_index("x-max").unwrap());
+        let mut bc = FixedValue::new(vec![3.0]);
+        let mut values = [0.0];
+        bc.evaluate(&patch, &[1.0], &mut values);
+        assert_eq!(values, [3.0]);
+        let value = bc.value_coefficients(&patch, &[1.0], &values);
+        assert_eq!((value.internal, value.boundary), (vec![0.0], vec![3.0]));
+        let grad = bc.gradient_coefficients(&patch, &[1.0], &values);
+        assert_eq!((grad.internal, grad.boundary), (vec![-1.0], vec![3.0]));
+    }
+
+    #[test]
+    fn test_fixed_value_from_dict_reads_value() {
+        let mesh = box_mesh([1, 1, 1], [2.0, 1.0, 1.0]);
+        let patch = PatchContext::new(&mesh, mesh.patch_index("x-max").unwrap());
+        let mut dict = Dictionary::new();
+        dict.insert("type", vec![Value::Word("fixedValue".into())]);
+        let missing = new_boundary_condition::<Vector>(&patch, &dict);
+        assert!(matches!(missing, Err(FieldError::InvalidEntry { .. })));
+
+        let value = [1.0, 0.0, 0.0].map(Value::Scalar).to_vec();
+        dict.insert(
+            "value",
+            vec![Value::Word("uniform".into()), Value::List(value)],
+        );
+        let mut bc = new_boundary_condition::<Vector>(&patch, &dict).unwrap();
+        let mut values = [Vector::zero()];
+        bc.evaluate(&patch, &[Vector::zero()], &mut values);
+        assert_eq!(values, [Vector::new(1.0, 0.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_fixed_value_from_profile_samples_face_centers() {
+        let mesh = box_mesh([1, 2, 1], [2.0, 2.0, 1.0]);
+        let patch = PatchContext::new(&mesh, mesh.patch_index("x-max").unwrap());
+        let exact = FixedValue::from_fn(&patch, |x| x.y() * x.y());
+        assert_eq!(exact.values(), &[0.25, 2.25]);
+
+        let row = |y: f64, u: f64| Value::List(vec![Value::Scalar(y), Value::Scalar(u)]);
+        let rows = vec![row(0.0, 0.0), row(2.0, 4.0)];
+        let vector = |v: [f64; 3]| vec![Value::List(v.map(Value::Scalar).to_vec())];
+        let mut dict = Dictionary::new();
+        dict.insert("type", vec![Value::Word("profileFixedValue".into())]);
+        dict.insert(
+            "profile",
+            vec![Value::Word("table".into()), Value::List(rows)],
+        );
+        dict.insert("origin", vector([0.0, 0.0, 0.0]));
+        dict.insert("direction", vector([0.0, 1.0, 0.0]));
+        let mut bc = new_boundary_condition::<f64>(&patch, &dict).unwrap();
+        let mut values = [0.0; 2];
+        bc.evaluate(&patch, &[0.0; 2], &mut values);
+        assert_eq!(values, [1.0, 3.0]);
+    }
+}
@@ -0,0 +1,194 @@
+use dugong_runtime::Dictionary;
+use dugong_types::FieldValue;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::boundary::{BoundaryCondition, Coefficients, PatchContext};
+use crate::boundary_conditions::{Mixed, register};
+use crate::error::FieldError;
+use crate::value::{Components, format_values, lookup_values};
+
+/// Fixes the value on inflow faces and extrapolates it on outflow faces
+/// (`inletOutlet`).
+///
+/// Faces are switched by the sign of the face flux each time the condition
+/// is used; see [`PatchContext::inflow`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct InletOutlet<T> {
+    inlet_value: Vec<T>,
+}
+
+/// Fixes the value on outflow faces and extrapolates it on inflow faces
+/// (`outletInlet`), the reverse of [`InletOutlet`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutletInlet<T> {
+    outlet_value: Vec<T>,
+}
+
+/// Returns the mixed condition fixing `value` on the faces where `fixed` is
+/// `true` and extrapolating elsewhere.
+fn switched<T: FieldValue>(value: &[T], fixed: impl IntoIterator<Item = bool>) -> Mixed<T> {
+    let fraction = fixed
+        .into_iter()
+        .map(|f| if f { 1.0 } else { 0.0 })
+        .collect();
+    Mixed::new(value.to_vec(), vec![T::zero(); value.len()], fraction)
+}
+
+impl<T: FieldValue> InletOutlet<T> {
+    /// Creates the condition with one inflow value per patch face.
+    pub fn new(inlet_value: Vec<T>) -> Self {
+        Self { inlet_value }
+    }
+
+    /// Returns the inflow values.
+    pub fn inlet_value(&self) -> &[T] {
+        &self.inlet_value
+    }
+
+    fn mixed(&self, patch: &PatchContext<'_>) -> Mixed<T> {
+        switched(&self.inlet_value, patch.inflow())
+    }
+}
+
+impl<T: FieldValue> OutletInlet<T> {
+    /// Creates the condition with one outflow value per patch face.
+    pub fn new(outlet_value: Vec<T>) -> Self {
+        Self { outlet_value }
+    }
+
+    /// Returns the outflow values.
+    pub fn outlet_value(&self) -> &[T] {
+        &self.outlet_value
+    }
+
+    fn mixed(&self, patch: &PatchContext<'_>) -> Mixed<T> {
+        switched(&self.outlet_value, patch.inflow().into_iter().map(|i| !i))
+    }
+}
+
+impl<T: Components> InletOutlet<T> {
+    /// Builds the condition from the `inletValue` entry of a patch
+    /// dictionary.
+    pub fn from_dict(
+        patch: &PatchContext<'_>,
+        dict: &Dictionary,
+    ) -> Result<Box<dyn BoundaryCondition<T>>, FieldError> {
+        let inlet_value = lookup_values(dict, "inletValue", patch.size())?;
+        Ok(Box::new(Self::new(inlet_value)))
+    }
+}
+
+impl<T: Components> OutletInlet<T> {
+    /// Builds the condition from the `outletValue` entry of a patch
+    /// dictionary.
+    pub fn from_dict(
+        patch: &PatchContext<'_>,
+        dict: &Dictionary,
+    ) -> Result<Box<dyn BoundaryCondition<T>>, FieldError> {
+        let outlet_value = lookup_values(dict, "outletValue", patch.size())?;
+        Ok(Box::new(Self::new(outlet_value)))
+    }
+}
+
+macro_rules! impl_switched {
+    ($ty:ident, $name:literal, $keyword:literal, $data:ident) => {
+        impl<T: Components> BoundaryCondition<T> for $ty<T> {
+            fn type_name(&self) -> &'static str {
+                $name
+            }
+
+            fn evaluate(&mut self, patch: &PatchContext<'_>, internal: &[T], values: &mut [T]) {
+                self.mixed(patch).evaluate(patch, internal, values);
+            }
+
+            fn value_coefficients(
+                &self,
+                patch: &PatchContext<'_>,
+                internal: &[T],
+                values: &[T],
+            ) -> Coefficients<T> {
+                self.mixed(patch)
+                    .value_coefficients(patch, internal, values)
+            }
+
+            fn gradient_coefficients(
+                &self,
+                patch: &PatchContext<'_>,
+                internal: &[T],
+                values: &[T],
+            ) -> Coefficients<T> {
+                self.mixed(patch)
+                    .gradient_coefficients(patch, internal, values)
+            }
+
+            fn write_entries(&self, dict: &mut Dictionary) {
+                dict.insert($keyword, format_values(&self.$data));
+            }
+
+            fn clone_box(&self) -> Box<dyn BoundaryCondition<T>> {
+                Box::new(self.clone())
+            }
+        }
+    };
+}
+
+impl_switched!(InletOutlet, "inletOutlet", "inletValue", inlet_value);
+impl_switched!(OutletInlet, "outletInlet", "outletValue", outlet_value);
+
+register!(
+    "inletOutlet",
+    InletOutlet::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+register!(
+    "outletInlet",
+    OutletInlet::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boundary::FlowFields;
+    use crate::dimensions::Dimensions;
+    use crate::surface_field::SurfaceField;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_inlet_outlet_switches_on_flux_sign() {
+        let mesh = box_mesh([1, 2, 1], [2.0, 2.0, 1.0]);
+        let x_max = mesh.patch_index("x-max").unwrap();
+        let mut phi = SurfaceField::uniform(&mesh, "phi", Dimensions::default(), 0.0);
+        phi.patch_values_mut(x_max).copy_from_slice(&[-1.0, 1.0]);
+        let flow = FlowFields {
+            flux: Some(&phi),
+            velocity: None,
+        };
+        let patch = PatchContext::new(&mesh, x_max).with_flow(flow);
+        let internal = [4.0, 6.0];
+
+        let mut values = [0.0; 2];
+        let mut bc = InletOutlet::new(vec![1.0; 2]);
+        bc.evaluate(&patch, &internal, &mut values);
+        assert_eq!(values, [1.0, 6.0]);
+        let value = bc.value_coefficients(&patch, &internal, &values);
+        assert_eq!(
+            (value.internal, value.boundary),
+            (vec![0.0, 1.0], vec![1.0, 0.0])
+        );
+        let grad = bc.gradient_coefficients(&patch, &internal, &values);
+        assert_eq!(
+            (grad.internal, grad.boundary),
+            (vec![-1.0, 0.0], vec![1.0, 0.0])
+        );
+
+        let mut bc = OutletInlet::new(vec![1.0; 2]);
+        bc.evaluate(&patch, &internal, &mut values);
+        assert_eq!(values, [4.0, 1.0]);
+
+        // Without a flux every face is an outflow face.
+        let mut bc = InletOutlet::new(vec![1.0; 2]);
+        bc.evaluate(&PatchContext::new(&mesh, x_max), &internal, &mut values);
+        assert_eq!(values, [4.0, 6.0]);
+    }
+}
@@ -0,0 +1,228 @@
+use dugong_runtime::Dictionary;
+use dugong_types::FieldValue;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::boundary::{BoundaryCondition, Coefficients, PatchContext};
+use crate::boundary_conditions::register;
+use crate::error::FieldError;
+use crate::value::{Components, format_values, lookup_values};
+
+/// Blends a fixed value and a fixed gradient (`mixed`):
+///
+/// `φ_b = f φ_ref + (1 − f) (φ_P + g_ref / δ)`
+///
+/// where `f` is the value fraction of each face. `f = 1` gives a fixed
+/// value and `f = 0` a fixed gradient. Robin conditions are mixed
+/// conditions; see [`Mixed::robin`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mixed<T> {
+    ref_value: Vec<T>,
+    ref_gradient: Vec<T>,
+    value_fraction: Vec<f64>,
+}
+
+impl<T: FieldValue> Mixed<T> {
+    /// Creates the condition from per-face reference values, reference
+    /// gradients and value fractions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lengths differ.
+    pub fn new(ref_value: Vec<T>, ref_gradient: Vec<T>, value_fraction: Vec<f64>) -> Self {
+        assert!(
+            ref_value.len() == ref_gradient.len() && ref_value.len() == value_fraction.len(),
+            "mixed condition data must have one entry per face"
+        );
+        Self {
+            ref_value,
+            ref_gradient,
+            value_fraction,
+        }
+    }
+
+    /// Creates the Robin condition `α φ + β ∂φ/∂n = γ` on `patch`.
+    ///
+    /// With `α = 0` the condition is the fixed gradient `γ / β`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `α` and `β` are both zero.
+    pub fn robin(patch: &PatchContext<'_>, alpha: f64, beta: f64, gamma: T) -> Self {
+        assert!(
+            alpha != 0.0 || beta != 0.0,
+            "robin coefficients are both zero"
+        );
+        let n = patch.size();
+        if alpha == 0.0 {
+            return Self::new(
+                vec![T::zero(); n],
+                vec![gamma * (1.0 / beta); n],
+                vec![0.0; n],
+            );
+        }
+        let fraction = patch
+            .delta_coeffs()
+            .iter()
+            .map(|&d| alpha / (alpha + beta * d))
+            .collect();
+        Self::new(vec![gamma * (1.0 / alpha); n], vec![T::zero(); n], fraction)
+    }
+
+    /// Returns the reference values.
+    pub fn ref_value(&self) -> &[T] {
+        &self.ref_value
+    }
+
+    /// Returns the reference values for modification.
+    pub fn ref_value_mut(&mut self) -> &mut [T] {
+        &mut self.ref_value
+    }
+
+    /// Returns the reference gradients.
+    pub fn ref_gradient(&self) -> &[T] {
+        &self.ref_gradient
+    }
+
+    /// Returns the reference gradients for modification.
+    pub fn ref_gradient_mut(&mut self) -> &mut [T] {
+        &mut self.ref_gradient
+    }
+
+    /// Returns the value fractions.
+    pub fn value_fraction(&self) -> &[f64] {
+        &self.value_fraction
+    }
+
+    /// Returns the value fractions for modification.
+    pub fn value_fraction_mut(&mut self) -> &mut [f64] {
+        &mut self.value_fraction
+    }
+}
+
+impl<T: Components> Mixed<T> {
+    /// Builds the condition from the `refValue`, `refGradient` and
+    /// `valueFraction` entries of a patch dictionary.
+    pub fn from_dict(
+        patch: &PatchContext<'_>,
+        dict: &Dictionary,
+    ) -> Result<Box<dyn BoundaryCondition<T>>, FieldError> {
+        let n = patch.size();
+        Ok(Box::new(Self::new(
+            lookup_values(dict, "refValue", n)?,
+            lookup_values(dict, "refGradient", n)?,
+            lookup_values(dict, "valueFraction", n)?,
+        )))
+    }
+}
+
+impl<T: Components> BoundaryCondition<T> for Mixed<T> {
+    fn type_name(&self) -> &'static str {
+        "mixed"
+    }
+
+    fn evaluate(&mut self, patch: &PatchContext<'_>, internal: &[T], values: &mut [T]) {
+        let coeffs = self.value_coefficients(patch, internal, values);
+        for ((v, &c), (&a, &b)) in values
+            .iter_mut()
+            .zip(patch.face_cells())
+            .zip(coeffs.internal.iter().zip(&coeffs.boundary))
+        {
+            *v = internal[c] * a + b;
+        }
+    }
+
+    fn value_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[T],
+        _values: &[T],
+    ) -> Coefficients<T> {
+        let delta = patch.delta_coeffs();
+        let (internal, boundary) = (0..patch.size())
+            .map(|i| {
+                let f = self.value_fraction[i];
+                let gradient = self.ref_gradient[i] * (1.0 / delta[i]);
+                (1.0 - f, self.ref_value[i] * f + gradient * (1.0 - f))
+            })
+            .unzip();
+        Coefficients { internal, boundary }
+    }
+
+    fn gradient_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[T],
+        _values: &[T],
+    ) -> Coefficients<T> {
+        let delta = patch.delta_coeffs();
+        let (internal, boundary) = (0..patch.size())
+            .map(|i| {
+                let fd = self.value_fraction[i] * delta[i];
+                let gradient = self.ref_gradient[i] * (1.0 - self.value_fraction[i]);
+                (-fd, self.ref_value[i] * fd + gradient)
+            })
+            .unzip();
+        Coefficients { internal, boundary }
+    }
+
+    fn write_entries(&self, dict: &mut Dictionary) {
+        dict.insert("refValue", format_values(&self.ref_value));
+        dict.insert("refGradient", format_values(&self.ref_gradient));
+        dict.insert("valueFraction", format_values(&self.value_fraction));
+    }
+
+    fn clone_box(&self) -> Box<dyn BoundaryCondition<T>> {
+        Box::new(self.clone())
+    }
+}
+
+register!(
+    "mixed",
+    Mixed::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_mixed_blends_value_and_gradient() {
+        let mesh = box_mesh([1, 1, 1], [2.0, 1.0, 1.0]);
+        let patch = PatchContext::new(&mesh, mesh.patch_index("x-max").unwrap());
+        let mut bc = Mixed::new(vec![10.0], vec![2.0], vec![0.25]);
+        let mut values = [0.0];
+        bc.evaluate(&patch, &[4.0], &mut values);
+        // 0.25 * 10 + 0.75 * (4 + 2 / 1)
+        assert_eq!(values, [7.0]);
+        let value = bc.value_coefficients(&patch, &[4.0], &values);
+        assert_eq!((value.internal, value.boundary), (vec![0.75], vec![4.0]));
+        let grad = bc.gradient_coefficients(&patch, &[4.0], &values);
+        // The gradient coefficients agree with δ (φ_b − φ_P).
+        assert_eq!(grad.internal[0] * 4.0 + grad.boundary[0], values[0] - 4.0);
+        assert_eq!((grad.internal, grad.boundary), (vec![-0.25], vec![4.0]));
+    }
+
+    #[test]
+    fn test_mixed_robin_satisfies_condition() {
+        let mesh = box_mesh([1, 1, 1], [2.0, 1.0, 1.0]);
+        let patch = PatchContext::new(&mesh, mesh.patch_index("x-max").unwrap());
+        let (alpha, beta, gamma) = (2.0, 3.0, 5.0);
+        for mut bc in [
+            Mixed::robin(&patch, alpha, beta, gamma),
+            Mixed::robin(&patch, 0.0, beta, gamma),
+        ] {
+            let alpha = if bc.value_fraction()[0] == 0.0 {
+                0.0
+            } else {
+                alpha
+            };
+            let mut values = [0.0];
+            bc.evaluate(&patch, &[1.5], &mut values);
+            let grad = bc.gradient_coefficients(&patch, &[1.5], &values);
+            let normal_gradient = grad.internal[0] * 1.5 + grad.boundary[0];
+            assert!((alpha * values[0] + beta * normal_gradient - gamma).abs() < 1e-12);
+        }
+    }
+}
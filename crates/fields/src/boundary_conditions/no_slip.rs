@@ -0,0 +1,84 @@
+use dugong_runtime::Dictionary;
+use dugong_types::tensor::Vector;
+
+use crate::boundary::{
+    BoundaryCondition, Coefficients, PatchContext, fixed_value_coefficients,
+    fixed_value_gradient_coefficients,
+};
+use crate::boundary_conditions::register;
+use crate::error::FieldError;
+
+/// Fixes a velocity at zero on a stationary wall (`noSlip`).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NoSlip;
+
+impl NoSlip {
+    /// Builds the condition from a patch dictionary; no entries are needed.
+    pub fn from_dict(
+        _patch: &PatchContext<'_>,
+        _dict: &Dictionary,
+    ) -> Result<Box<dyn BoundaryCondition<Vector>>, FieldError> {
+        Ok(Box::new(NoSlip))
+    }
+}
+
+impl BoundaryCondition<Vector> for NoSlip {
+    fn type_name(&self) -> &'static str {
+        "noSlip"
+    }
+
+    fn evaluate(&mut self, _patch: &PatchContext<'_>, _internal: &[Vector], values: &mut [Vector]) {
+        values.fill(Vector::zero());
+    }
+
+    fn value_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[Vector],
+        _values: &[Vector],
+    ) -> Coefficients<Vector> {
+        fixed_value_coefficients(&vec![Vector::zero(); patch.size()])
+    }
+
+    fn gradient_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[Vector],
+        _values: &[Vector],
+    ) -> Coefficients<Vector> {
+        fixed_value_gradient_coefficients(patch, &vec![Vector::zero(); patch.size()])
+    }
+
+    fn clone_box(&self) -> Box<dyn BoundaryCondition<Vector>> {
+        Box::new(*self)
+    }
+}
+
+register!("noSlip", NoSlip::from_dict, [Vector]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_no_slip_fixes_zero_velocity() {
+        let mesh = box_mesh([1, 1, 1], [2.0, 1.0, 1.0]);
+        let patch = PatchContext::new(&mesh, mesh.patch_index("x-max").unwrap());
+        let internal = [Vector::new(1.0, 2.0, 3.0)];
+        let mut values = [Vector::new(5.0, 0.0, 0.0)];
+        let mut bc = NoSlip;
+        bc.evaluate(&patch, &internal, &mut values);
+        assert_eq!(values, [Vector::zero()]);
+        let value = bc.value_coefficients(&patch, &internal, &values);
+        assert_eq!(
+            (value.internal, value.boundary),
+            (vec![0.0], vec![Vector::zero()])
+        );
+        let grad = bc.gradient_coefficients(&patch, &internal, &values);
+        assert_eq!(
+            (grad.internal, grad.boundary),
+            (vec![-1.0], vec![Vector::zero()])
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use dugong_runtime::Dictionary;
+use dugong_types::tensor::Vector;
+
+use crate::boundary::{BoundaryCondition, Coefficients, PatchContext};
+use crate::boundary_conditions::register;
+use crate::error::FieldError;
+use crate::value::{format_values, lookup_values};
+
+/// The velocity condition of a patch with a prescribed pressure
+/// (`pressureInletOutletVelocity`).
+///
+/// Outflow faces extrapolate the velocity. Inflow faces extrapolate its
+/// normal component, so the pressure drives the inflow, and fix the
+/// tangential component at the tangential velocity, zero unless given.
+/// Implicitly, the face velocity follows the cell velocity, with the
+/// difference treated explicitly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PressureInletOutletVelocity {
+    tangential_velocity: Option<Vec<Vector>>,
+}
+
+impl PressureInletOutletVelocity {
+    /// Creates the condition with one tangential inflow velocity per patch
+    /// face, or none for zero.
+    pub fn new(tangential_velocity: Option<Vec<Vector>>) -> Self {
+        Self {
+            tangential_velocity,
+        }
+    }
+
+    /// Builds the condition from the optional `tangentialVelocity` entry of
+    /// a patch dictionary.
+    pub fn from_dict(
+        patch: &PatchContext<'_>,
+        dict: &Dictionary,
+    ) -> Result<Box<dyn BoundaryCondition<Vector>>, FieldError> {
+        let tangential_velocity = dict
+            .contains("tangentialVelocity")
+            .then(|| lookup_values(dict, "tangentialVelocity", patch.size()))
+            .transpose()?;
+        Ok(Box::new(Self::new(tangential_velocity)))
+    }
+
+    /// Returns the face values less the adjacent cell values.
+    fn corrections(&self, patch: &PatchContext<'_>, internal: &[Vector]) -> Vec<Vector> {
+        let normals = patch.normals();
+        patch
+            .inflow()
+            .into_iter()
+            .enumerate()
+            .map(|(i, inflow)| {
+                if !inflow {
+                    return Vector::zero();
+                }
+                let n = normals[i];
+                let tangential = |u: Vector| u - n * (n * u);
+                let target = self
+                    .tangential_velocity
+                    .as_ref()
+                    .map_or(Vector::zero(), |ut| tangential(ut[i]));
+                target - tangential(internal[patch.face_cells()[i]])
+            })
+            .collect()
+    }
+}
+
+impl BoundaryCondition<Vector> for PressureInletOutletVelocity {
+    fn type_name(&self) -> &'static str {
+        "pressureInletOutletVelocity"
+    }
+
+    fn evaluate(&mut self, patch: &PatchContext<'_>, internal: &[Vector], values: &mut [Vector]) {
+        let corrections = self.corrections(patch, internal);
+        for ((v, &c), dv) in values.iter_mut().zip(patch.face_cells()).zip(corrections) {
+            *v = internal[c] + dv;
+        }
+    }
+
+    fn value_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        internal: &[Vector],
+        _values: &[Vector],
+    ) -> Coefficients<Vector> {
+        Coefficients {
+            internal: vec![1.0; patch.size()],
+            boundary: self.corrections(patch, internal),
+        }
+    }
+
+    fn gradient_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        internal: &[Vector],
+        _values: &[Vector],
+    ) -> Coefficients<Vector> {
+        let delta = patch.delta_coeffs();
+        Coefficients {
+            internal: vec![0.0; patch.size()],
+            boundary: self
+                .corrections(patch, internal)
+                .into_iter()
+                .zip(delta)
+                .map(|(dv, d)| dv * d)
+                .collect(),
+        }
+    }
+
+    fn write_entries(&self, dict: &mut Dictionary) {
+        if let Some(velocity) = &self.tangential_velocity {
+            dict.insert("tangentialVelocity", format_values(velocity));
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn BoundaryCondition<Vector>> {
+        Box::new(self.clone())
+    }
+}
+
+register!(
+    "pressureInletOutletVelocity",
+    PressureInletOutletVelocity::from_dict,
+    [Vector]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boundary::FlowFields;
+    use crate::dimensions::Dimensions;
+    use crate::surface_field::SurfaceField;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_pressure_inlet_outlet_velocity_drops_tangential_inflow() {
+        let mesh = box_mesh([1, 2, 1], [2.0, 2.0, 1.0]);
+        let x_max = mesh.patch_index("x-max").unwrap();
+        let mut phi = SurfaceField::uniform(&mesh, "phi", Dimensions::default(), 0.0);
+        phi.patch_values_mut(x_max).copy_from_slice(&[-1.0, 1.0]);
+        let flow = FlowFields {
+            flux: Some(&phi),
+            velocity: None,
+        };
+        let patch = PatchContext::new(&mesh, x_max).with_flow(flow);
+        let internal = [Vector::new(-1.0, 2.0, 0.0), Vector::new(1.0, 2.0, 0.0)];
+
+        let mut values = [Vector::zero(); 2];
+        let mut bc = PressureInletOutletVelocity::new(None);
+        bc.evaluate(&patch, &internal, &mut values);
+        assert_eq!(values, [Vector::new(-1.0, 0.0, 0.0), internal[1]]);
+        let value = bc.value_coefficients(&patch, &internal, &values);
+        assert_eq!(value.internal, vec![1.0; 2]);
+        assert_eq!(
+            value.boundary,
+            vec![Vector::new(0.0, -2.0, 0.0), Vector::zero()]
+        );
+        let grad = bc.gradient_coefficients(&patch, &internal, &values);
+        assert_eq!(grad.internal, vec![0.0; 2]);
+        assert_eq!(
+            grad.boundary,
+            vec![Vector::new(0.0, -2.0, 0.0), Vector::zero()]
+        );
+
+        let tangential = vec![Vector::new(5.0, 0.0, 3.0); 2];
+        let mut bc = PressureInletOutletVelocity::new(Some(tangential));
+        bc.evaluate(&patch, &internal, &mut values);
+        assert_eq!(values[0], Vector::new(-1.0, 0.0, 3.0));
+    }
+}
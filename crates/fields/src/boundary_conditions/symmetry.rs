@@ -0,0 +1,162 @@
+use dugong_runtime::Dictionary;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::boundary::{BoundaryCondition, Coefficients, PatchContext};
+use crate::boundary_conditions::register;
+use crate::error::FieldError;
+use crate::value::{Components, Transform};
+
+/// Mirrors the field across the patch (`symmetry`, or `symmetryPlane` for
+/// planar patches).
+///
+/// The face value is the average of the adjacent cell value and its
+/// reflection `(I − 2 n n)`: the normal component of a vector vanishes and
+/// its tangential components are extrapolated. Scalars get a zero gradient.
+/// Implicitly, the face value follows the cell value, with the normal part
+/// treated explicitly.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Symmetry;
+
+/// Lets a velocity slip along a wall (`slip`): the normal component
+/// vanishes and the tangential components have zero gradient.
+///
+/// This is the same projection as [`Symmetry`], under the name used for
+/// walls.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Slip;
+
+/// Returns the reflected averages of the adjacent cell values.
+fn mirrored<T: Components + Transform>(patch: &PatchContext<'_>, internal: &[T]) -> Vec<T> {
+    patch
+        .normals()
+        .iter()
+        .zip(patch.face_cells())
+        .map(|(&n, &c)| {
+            let reflection = Tensor::identity() - n.outer(&(n * 2.0));
+            (internal[c] + internal[c].transform(&reflection)) * 0.5
+        })
+        .collect()
+}
+
+/// Returns the face values less the adjacent cell values.
+fn normal_parts<T: Components + Transform>(patch: &PatchContext<'_>, internal: &[T]) -> Vec<T> {
+    let cells = patch.patch_internal(internal);
+    mirrored(patch, internal)
+        .into_iter()
+        .zip(cells)
+        .map(|(b, p)| b - p)
+        .collect()
+}
+
+macro_rules! impl_symmetric {
+    ($ty:ident, $name:literal) => {
+        impl $ty {
+            /// Builds the condition from a patch dictionary; no entries are
+            /// needed.
+            pub fn from_dict<T: Components + Transform>(
+                _patch: &PatchContext<'_>,
+                _dict: &Dictionary,
+            ) -> Result<Box<dyn BoundaryCondition<T>>, FieldError> {
+                Ok(Box::new($ty))
+            }
+        }
+
+        impl<T: Components + Transform> BoundaryCondition<T> for $ty {
+            fn type_name(&self) -> &'static str {
+                $name
+            }
+
+            fn evaluate(&mut self, patch: &PatchContext<'_>, internal: &[T], values: &mut [T]) {
+                values.copy_from_slice(&mirrored(patch, internal));
+            }
+
+            fn value_coefficients(
+                &self,
+                patch: &PatchContext<'_>,
+                internal: &[T],
+                _values: &[T],
+            ) -> Coefficients<T> {
+                Coefficients {
+                    internal: vec![1.0; patch.size()],
+                    boundary: normal_parts(patch, internal),
+                }
+            }
+
+            fn gradient_coefficients(
+                &self,
+                patch: &PatchContext<'_>,
+                internal: &[T],
+                _values: &[T],
+            ) -> Coefficients<T> {
+                let delta = patch.delta_coeffs();
+                Coefficients {
+                    internal: vec![0.0; patch.size()],
+                    boundary: normal_parts(patch, internal)
+                        .into_iter()
+                        .zip(delta)
+                        .map(|(v, d)| v * d)
+                        .collect(),
+                }
+            }
+
+            fn clone_box(&self) -> Box<dyn BoundaryCondition<T>> {
+                Box::new(*self)
+            }
+        }
+    };
+}
+
+impl_symmetric!(Symmetry, "symmetry");
+impl_symmetric!(Slip, "slip");
+
+register!(
+    "symmetry",
+    Symmetry::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+register!(
+    "symmetryPlane",
+    Symmetry::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+register!(
+    "slip",
+    Slip::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_symmetry_removes_normal_component() {
+        let mesh = box_mesh([1, 1, 1], [2.0, 1.0, 1.0]);
+        let patch = PatchContext::new(&mesh, mesh.patch_index("x-max").unwrap());
+        let internal = [Vector::new(3.0, 1.0, 2.0)];
+        let mut values = [Vector::zero()];
+        let mut bc = Symmetry;
+        bc.evaluate(&patch, &internal, &mut values);
+        assert!((values[0] - Vector::new(0.0, 1.0, 2.0)).mag() < 1e-12);
+        let value = bc.value_coefficients(&patch, &internal, &values);
+        assert_eq!(value.internal, vec![1.0]);
+        assert!((value.boundary[0] - Vector::new(-3.0, 0.0, 0.0)).mag() < 1e-12);
+        let grad = bc.gradient_coefficients(&patch, &internal, &values);
+        assert_eq!(grad.internal, vec![0.0]);
+        assert!((grad.boundary[0] - Vector::new(-3.0, 0.0, 0.0)).mag() < 1e-12);
+    }
+
+    #[test]
+    fn test_slip_gives_scalars_zero_gradient() {
+        let mesh = box_mesh([1, 1, 1], [2.0, 1.0, 1.0]);
+        let patch = PatchContext::new(&mesh, mesh.patch_index("x-max").unwrap());
+        let mut values = [0.0];
+        let mut bc = Slip;
+        bc.evaluate(&patch, &[4.0], &mut values);
+        assert_eq!(values, [4.0]);
+        let grad = bc.gradient_coefficients(&patch, &[4.0], &values);
+        assert_eq!((grad.internal, grad.boundary), (vec![0.0], vec![0.0]));
+        assert_eq!(BoundaryCondition::<f64>::type_name(&bc), "slip");
+    }
+}
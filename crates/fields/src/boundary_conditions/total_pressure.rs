@@ -0,0 +1,136 @@
+use dugong_runtime::Dictionary;
+
+use crate::boundary::{
+    BoundaryCondition, Coefficients, PatchContext, fixed_value_coefficients,
+    fixed_value_gradient_coefficients,
+};
+use crate::boundary_conditions::register;
+use crate::error::FieldError;
+use crate::value::{format_values, lookup_values};
+
+/// Fixes the total pressure of inflow (`totalPressure`), for the kinematic
+/// pressure of incompressible flow.
+///
+/// Inflow faces get `p = p0 − |U|² / 2` from the face velocity; outflow
+/// faces get `p0`. Without a known velocity every face gets `p0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TotalPressure {
+    p0: Vec<f64>,
+}
+
+impl TotalPressure {
+    /// Creates the condition with one total pressure per patch face.
+    pub fn new(p0: Vec<f64>) -> Self {
+        Self { p0 }
+    }
+
+    /// Returns the total pressures.
+    pub fn p0(&self) -> &[f64] {
+        &self.p0
+    }
+
+    /// Returns the total pressures for modification.
+    pub fn p0_mut(&mut self) -> &mut [f64] {
+        &mut self.p0
+    }
+
+    /// Builds the condition from the `p0` entry of a patch dictionary.
+    pub fn from_dict(
+        patch: &PatchContext<'_>,
+        dict: &Dictionary,
+    ) -> Result<Box<dyn BoundaryCondition<f64>>, FieldError> {
+        Ok(Box::new(Self::new(lookup_values(
+            dict,
+            "p0",
+            patch.size(),
+        )?)))
+    }
+}
+
+impl BoundaryCondition<f64> for TotalPressure {
+    fn type_name(&self) -> &'static str {
+        "totalPressure"
+    }
+
+    fn evaluate(&mut self, patch: &PatchContext<'_>, _internal: &[f64], values: &mut [f64]) {
+        values.copy_from_slice(&self.p0);
+        let Some(velocity) = patch.velocity() else {
+            return;
+        };
+        for ((v, &u), inflow) in values.iter_mut().zip(velocity).zip(patch.inflow()) {
+            if inflow {
+                *v -= 0.5 * (u * u);
+            }
+        }
+    }
+
+    fn value_coefficients(
+        &self,
+        _patch: &PatchContext<'_>,
+        _internal: &[f64],
+        values: &[f64],
+    ) -> Coefficients<f64> {
+        fixed_value_coefficients(values)
+    }
+
+    fn gradient_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[f64],
+        values: &[f64],
+    ) -> Coefficients<f64> {
+        fixed_value_gradient_coefficients(patch, values)
+    }
+
+    fn write_entries(&self, dict: &mut Dictionary) {
+        dict.insert("p0", format_values(&self.p0));
+    }
+
+    fn clone_box(&self) -> Box<dyn BoundaryCondition<f64>> {
+        Box::new(self.clone())
+    }
+}
+
+register!("totalPressure", TotalPressure::from_dict, [f64]);
+
+#[cfg(test)]
+mod tests {
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+    use crate::boundary::FlowFields;
+    use crate::dimensions::Dimensions;
+    use crate::surface_field::SurfaceField;
+    use crate::test_meshes::box_mesh;
+    use crate::vol_field::VolField;
+
+    #[test]
+    fn test_total_pressure_subtracts_inflow_dynamic_pressure() {
+        let mesh = box_mesh([1, 2, 1], [2.0, 2.0, 1.0]);
+        let x_max = mesh.patch_index("x-max").unwrap();
+        let mut phi = SurfaceField::uniform(&mesh, "phi", Dimensions::default(), 0.0);
+        phi.patch_values_mut(x_max).copy_from_slice(&[-1.0, 1.0]);
+        let u = Vector::new(-2.0, 0.0, 0.0);
+        let velocity = VolField::uniform(&mesh, "U", Dimensions::mlt(0, 1, -1), u);
+        let flow = FlowFields {
+            flux: Some(&phi),
+            velocity: Some(&velocity),
+        };
+        let patch = PatchContext::new(&mesh, x_max).with_flow(flow);
+
+        let mut values = [0.0; 2];
+        let mut bc = TotalPressure::new(vec![10.0; 2]);
+        bc.evaluate(&patch, &[0.0; 2], &mut values);
+        assert_eq!(values, [8.0, 10.0]);
+        let value = bc.value_coefficients(&patch, &[0.0; 2], &values);
+        assert_eq!(
+            (value.internal, value.boundary),
+            (vec![0.0; 2], vec![8.0, 10.0])
+        );
+        let grad = bc.gradient_coefficients(&patch, &[0.0; 2], &values);
+        assert_eq!(grad.internal, vec![-1.0; 2]);
+
+        bc.evaluate(&PatchContext::new(&mesh, x_max), &[0.0; 2], &mut values);
+        assert_eq!(values, [10.0; 2]);
+    }
+}
@@ -0,0 +1,125 @@
+use dugong_runtime::Dictionary;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::boundary::{
+    BoundaryCondition, Coefficients, PatchContext, fixed_value_coefficients,
+    fixed_value_gradient_coefficients,
+};
+use crate::boundary_conditions::register;
+use crate::error::FieldError;
+use crate::table::Table;
+use crate::value::Components;
+
+/// Fixes the face values at one value that varies in time
+/// (`uniformFixedValue`), such as a ramped inlet velocity.
+///
+/// The value is looked up in a [`Table`] at each [`update`] and starts at
+/// the value for time 0.
+///
+/// [`update`]: BoundaryCondition::update
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniformFixedValue<T> {
+    table: Table<T>,
+    value: T,
+}
+
+impl<T: Components> UniformFixedValue<T> {
+    /// Creates the condition from a table of values against time.
+    pub fn new(table: Table<T>) -> Self {
+        let value = table.value(0.0);
+        Self { table, value }
+    }
+
+    /// Returns the table of values against time.
+    pub fn table(&self) -> &Table<T> {
+        &self.table
+    }
+
+    /// Returns the current value.
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// Builds the condition from the `uniformValue` entry of a patch
+    /// dictionary; see [`Table::from_dict`].
+    pub fn from_dict(
+        _patch: &PatchContext<'_>,
+        dict: &Dictionary,
+    ) -> Result<Box<dyn BoundaryCondition<T>>, FieldError> {
+        Ok(Box::new(Self::new(Table::from_dict(dict, "uniformValue")?)))
+    }
+}
+
+impl<T: Components> BoundaryCondition<T> for UniformFixedValue<T> {
+    fn type_name(&self) -> &'static str {
+        "uniformFixedValue"
+    }
+
+    fn evaluate(&mut self, _patch: &PatchContext<'_>, _internal: &[T], values: &mut [T]) {
+        values.fill(self.value);
+    }
+
+    fn value_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[T],
+        _values: &[T],
+    ) -> Coefficients<T> {
+        fixed_value_coefficients(&vec![self.value; patch.size()])
+    }
+
+    fn gradient_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[T],
+        _values: &[T],
+    ) -> Coefficients<T> {
+        fixed_value_gradient_coefficients(patch, &vec![self.value; patch.size()])
+    }
+
+    fn update(&mut self, _patch: &PatchContext<'_>, time: f64) {
+        self.value = self.table.value(time);
+    }
+
+    fn write_entries(&self, dict: &mut Dictionary) {
+        self.table.write_entries(dict, "uniformValue");
+    }
+
+    fn clone_box(&self) -> Box<dyn BoundaryCondition<T>> {
+        Box::new(self.clone())
+    }
+}
+
+register!(
+    "uniformFixedValue",
+    UniformFixedValue::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::table::Interpolation;
+    use crate::test_meshes::box_mesh;
+    use crate::vol_field::VolField;
+
+    #[test]
+    fn test_uniform_fixed_value_follows_time_table() {
+        let mesh = box_mesh([1, 1, 1], [2.0, 1.0, 1.0]);
+        let mut field = VolField::uniform(&mesh, "T", Dimensions::default(), 0.0);
+        let table = Table::new(vec![(0.0, 1.0), (2.0, 5.0)], Interpolation::Linear).unwrap();
+        let bc = UniformFixedValue::new(table);
+        field.set_boundary_condition("x-max", Box::new(bc)).unwrap();
+        assert_eq!(field.patch_field("x-max").unwrap().values(), &[1.0]);
+
+        field.update_boundaries(1.0);
+        let patch = field.patch_field("x-max").unwrap();
+        assert_eq!(patch.values(), &[3.0]);
+        let context = field.patch_context(patch.patch());
+        let value = patch
+            .condition()
+            .value_coefficients(&context, &[0.0], &[0.0]);
+        assert_eq!((value.internal, value.boundary), (vec![0.0], vec![3.0]));
+    }
+}
@@ -0,0 +1,89 @@
+use dugong_runtime::Dictionary;
+use dugong_types::FieldValue;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::boundary::{BoundaryCondition, Coefficients, PatchContext};
+use crate::boundary_conditions::register;
+use crate::error::FieldError;
+
+/// Extrapolates the adjacent cell values to the faces (`zeroGradient`, a
+/// homogeneous Neumann condition).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ZeroGradient;
+
+impl ZeroGradient {
+    /// Builds the condition from a patch dictionary; no entries are needed.
+    pub fn from_dict<T: FieldValue>(
+        _patch: &PatchContext<'_>,
+        _dict: &Dictionary,
+    ) -> Result<Box<dyn BoundaryCondition<T>>, FieldError> {
+        Ok(Box::new(ZeroGradient))
+    }
+}
+
+impl<T: FieldValue> BoundaryCondition<T> for ZeroGradient {
+    fn type_name(&self) -> &'static str {
+        "zeroGradient"
+    }
+
+    fn evaluate(&mut self, patch: &PatchContext<'_>, internal: &[T], values: &mut [T]) {
+        for (v, &c) in values.iter_mut().zip(patch.face_cells()) {
+            *v = internal[c];
+        }
+    }
+
+    fn value_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[T],
+        _values: &[T],
+    ) -> Coefficients<T> {
+        Coefficients {
+            internal: vec![1.0; patch.size()],
+            boundary: vec![T::zero(); patch.size()],
+        }
+    }
+
+    fn gradient_coefficients(
+        &self,
+        patch: &PatchContext<'_>,
+        _internal: &[T],
+        _values: &[T],
+    ) -> Coefficients<T> {
+        Coefficients {
+            internal: vec![0.0; patch.size()],
+            boundary: vec![T::zero(); patch.size()],
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn BoundaryCondition<T>> {
+        Box::new(*self)
+    }
+}
+
+register!(
+    "zeroGradient",
+    ZeroGradient::from_dict,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_zero_gradient_copies_cell_values() {
+        let mesh = box_mesh([2, 1, 1], [4.0, 1.0, 1.0]);
+        let patch = PatchContext::new(&mesh, mesh.patch_index("x-max").unwrap());
+        let internal = [1.0, 7.0];
+        let mut values = [0.0];
+        let mut bc = ZeroGradient;
+        bc.evaluate(&patch, &internal, &mut values);
+        assert_eq!(values, [7.0]);
+        let value = bc.value_coefficients(&patch, &internal, &values);
+        assert_eq!((value.internal, value.boundary), (vec![1.0], vec![0.0]));
+        let grad = bc.gradient_coefficients(&patch, &internal, &values);
+        assert_eq!((grad.internal, grad.boundary), (vec![0.0], vec![0.0]));
+    }
+}
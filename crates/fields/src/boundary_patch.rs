@@ -0,0 +1,209 @@
+use dugong_types::FieldValue;
+
+use crate::boundary::{BoundaryCondition, PatchContext};
+use crate::error::FieldError;
+use crate::patch_field::PatchField;
+use crate::vol_field::VolField;
+
+/// The boundary data of one patch of a [`VolField`], with the cell values
+/// next to it.
+///
+/// Returned by [`VolField::boundary`].
+pub struct BoundaryPatch<'a, 'mesh, T: FieldValue> {
+    context: PatchContext<'mesh>,
+    field: &'a PatchField<T>,
+    internal: &'a [T],
+}
+
+/// The boundary data of one patch of a [`VolField`], for modification.
+///
+/// Returned by [`VolField::boundary_mut`]. Values written through
+/// [`values_mut`](Self::values_mut) last until the boundary conditions are
+/// next evaluated.
+pub struct BoundaryPatchMut<'a, 'mesh, T: FieldValue> {
+    context: PatchContext<'mesh>,
+    field: &'a mut PatchField<T>,
+    internal: &'a [T],
+}
+
+impl<'a, 'mesh, T: FieldValue> BoundaryPatch<'a, 'mesh, T> {
+    /// Returns the patch name.
+    pub fn name(&self) -> &'mesh str {
+        self.context.patch().name()
+    }
+
+    /// Returns the patch geometry.
+    pub fn context(&self) -> &PatchContext<'mesh> {
+        &self.context
+    }
+
+    /// Returns the face values, in patch face order.
+    pub fn values(&self) -> &'a [T] {
+        self.field.values()
+    }
+
+    /// Returns the boundary condition.
+    pub fn condition(&self) -> &'a dyn BoundaryCondition<T> {
+        self.field.condition()
+    }
+
+    /// Returns the values of the cells next to each face.
+    pub fn patch_internal_field(&self) -> Vec<T> {
+        self.context.patch_internal(self.internal)
+    }
+
+    /// Returns the patch-normal gradient on each face, as the boundary
+    /// condition defines it.
+    pub fn sn_grad(&self) -> Vec<T> {
+        sn_grad(&self.context, self.field, self.internal)
+    }
+}
+
+impl<'mesh, T: FieldValue> BoundaryPatchMut<'_, 'mesh, T> {
+    /// Returns the patch name.
+    pub fn name(&self) -> &'mesh str {
+        self.context.patch().name()
+    }
+
+    /// Returns the patch geometry.
+    pub fn context(&self) -> &PatchContext<'mesh> {
+        &self.context
+    }
+
+    /// Returns the face values, in patch face order.
+    pub fn values(&self) -> &[T] {
+        self.field.values()
+    }
+
+    /// Returns the face values for modification.
+    pub fn values_mut(&mut self) -> &mut [T] {
+        self.field.values_mut()
+    }
+
+    /// Returns the boundary condition.
+    pub fn condition(&self) -> &dyn BoundaryCondition<T> {
+        self.field.condition()
+    }
+
+    /// Returns the boundary condition for modification.
+    pub fn condition_mut(&mut self) -> &mut dyn BoundaryCondition<T> {
+        self.field.condition_mut()
+    }
+
+    /// Returns the values of the cells next to each face.
+    pub fn patch_internal_field(&self) -> Vec<T> {
+        self.context.patch_internal(self.internal)
+    }
+
+    /// Returns the patch-normal gradient on each face, as the boundary
+    /// condition defines it.
+    pub fn sn_grad(&self) -> Vec<T> {
+        sn_grad(&self.context, self.field, self.internal)
+    }
+
+    /// Recomputes the face values from the cell values with the boundary
+    /// condition.
+    pub fn evaluate(&mut self) {
+        let (condition, values) = self.field.split_mut();
+        condition.evaluate(&self.context, self.internal, values);
+    }
+}
+
+/// Returns `internal * φ_P + boundary` of the gradient coefficients.
+fn sn_grad<T: FieldValue>(
+    context: &PatchContext<'_>,
+    field: &PatchField<T>,
+    internal: &[T],
+) -> Vec<T> {
+    let coefficients = field
+        .condition()
+        .gradient_coefficients(context, internal, field.values());
+    context
+        .face_cells()
+        .iter()
+        .zip(coefficients.internal)
+        .zip(coefficients.boundary)
+        .map(|((&c, a), b)| internal[c] * a + b)
+        .collect()
+}
+
+impl<'mesh, T: FieldValue> VolField<'mesh, T> {
+    /// Returns the boundary data of the named patch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::PatchNotFound`] for unknown patch names.
+    pub fn boundary(&self, patch: &str) -> Result<BoundaryPatch<'_, 'mesh, T>, FieldError> {
+        let i = self.find_patch(patch)?;
+        Ok(BoundaryPatch {
+            context: self.patch_context(i),
+            field: &self.patch_fields()[i],
+            internal: self.internal(),
+        })
+    }
+
+    /// Returns the boundary data of the named patch for modification.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::PatchNotFound`] for unknown patch names.
+    pub fn boundary_mut(
+        &mut self,
+        patch: &str,
+    ) -> Result<BoundaryPatchMut<'_, 'mesh, T>, FieldError> {
+        let i = self.find_patch(patch)?;
+        let context = self.patch_context(i);
+        let (internal, boundary) = self.internal_and_patch_fields_mut();
+        Ok(BoundaryPatchMut {
+            context,
+            field: &mut boundary[i],
+            internal,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boundary_conditions::{FixedGradient, FixedValue, ZeroGradient};
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_boundary_reads_patch_internal_field_and_sn_grad() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut t = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 3.0]).unwrap();
+        t.set_boundary_condition("x-max", Box::new(FixedValue::new(vec![5.0])))
+            .unwrap();
+        t.set_boundary_condition("x-min", Box::new(FixedGradient::new(vec![2.0])))
+            .unwrap();
+
+        let outlet = t.boundary("x-max").unwrap();
+        assert_eq!(outlet.name(), "x-max");
+        assert_eq!(outlet.values(), &[5.0]);
+        assert_eq!(outlet.patch_internal_field(), [3.0]);
+        // (5 - 3) / 0.5
+        assert!((outlet.sn_grad()[0] - 4.0).abs() < 1e-12);
+        assert!((t.boundary("x-min").unwrap().sn_grad()[0] - 2.0).abs() < 1e-12);
+        assert!(matches!(
+            t.boundary("inlet"),
+            Err(FieldError::PatchNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_boundary_mut_writes_values_until_evaluated() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut t = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 3.0]).unwrap();
+        t.set_boundary_condition("x-max", Box::new(ZeroGradient))
+            .unwrap();
+        let mut outlet = t.boundary_mut("x-max").unwrap();
+        outlet.values_mut()[0] = 7.0;
+        assert_eq!(outlet.condition().type_name(), "zeroGradient");
+        assert_eq!(t.patch_field("x-max").unwrap().values(), &[7.0]);
+
+        let mut outlet = t.boundary_mut("x-max").unwrap();
+        outlet.evaluate();
+        assert_eq!(outlet.values(), &[3.0]);
+    }
+}
@@ -0,0 +1,129 @@
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+use dugong_types::FieldValue;
+
+/// The cell values of a [`VolField`](crate::VolField): either one value per
+/// cell, or a single value shared by every cell.
+///
+/// Uniform storage expands into one value per cell on the first mutable
+/// access. Reading it as a slice expands a cached copy instead, which the
+/// next write discards.
+#[derive(Debug, Clone)]
+pub(crate) enum CellValues<T> {
+    Uniform {
+        value: T,
+        len: usize,
+        expanded: OnceLock<Vec<T>>,
+    },
+    Values(Vec<T>),
+}
+
+impl<T: FieldValue> CellValues<T> {
+    /// Creates uniform storage of `len` cells.
+    pub(crate) fn uniform(value: T, len: usize) -> Self {
+        Self::Uniform {
+            value,
+            len,
+            expanded: OnceLock::new(),
+        }
+    }
+
+    /// Returns the number of cells.
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Self::Uniform { len, .. } => *len,
+            Self::Values(values) => values.len(),
+        }
+    }
+
+    /// Returns the value of cell `i`.
+    pub(crate) fn get(&self, i: usize) -> T {
+        match self {
+            Self::Uniform { value, len, .. } => {
+                assert!(i < *len, "cell {i} out of range for {len} cells");
+                *value
+            }
+            Self::Values(values) => values[i],
+        }
+    }
+
+    /// Returns the shared value if the storage is uniform.
+    pub(crate) fn uniform_value(&self) -> Option<T> {
+        match self {
+            Self::Uniform { value, .. } => Some(*value),
+            Self::Values(_) => None,
+        }
+    }
+
+    /// Returns the values as a slice, expanding a cached copy of uniform
+    /// storage.
+    pub(crate) fn as_slice(&self) -> &[T] {
+        match self {
+            Self::Uniform {
+                value,
+                len,
+                expanded,
+            } => expanded.get_or_init(|| vec![*value; *len]),
+            Self::Values(values) => values,
+        }
+    }
+
+    /// Returns the values as a slice without caching an expansion of
+    /// uniform storage.
+    pub(crate) fn to_slice(&self) -> Cow<'_, [T]> {
+        match self {
+            Self::Uniform {
+                value,
+                len,
+                expanded,
+            } => match expanded.get() {
+                Some(values) => Cow::Borrowed(values),
+                None => Cow::Owned(vec![*value; *len]),
+            },
+            Self::Values(values) => Cow::Borrowed(values),
+        }
+    }
+
+    /// Returns the values for modification, expanding uniform storage.
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
+        if let Self::Uniform {
+            value,
+            len,
+            expanded,
+        } = self
+        {
+            let values = expanded.take().unwrap_or_else(|| vec![*value; *len]);
+            *self = Self::Values(values);
+        }
+        match self {
+            Self::Values(values) => values,
+            Self::Uniform { .. } => unreachable!("uniform storage was expanded"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_values_expand_on_write() {
+        let mut values = CellValues::uniform(2.0, 3);
+        assert_eq!((values.len(), values.get(2)), (3, 2.0));
+        assert!(matches!(values.to_slice(), Cow::Owned(_)));
+        assert_eq!(values.as_slice(), &[2.0; 3]);
+        assert!(matches!(values.to_slice(), Cow::Borrowed(_)));
+        assert_eq!(values.uniform_value(), Some(2.0));
+
+        values.as_mut_slice()[1] = 5.0;
+        assert_eq!(values.uniform_value(), None);
+        assert_eq!(values.as_slice(), &[2.0, 5.0, 2.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_cell_values_uniform_get_checks_range() {
+        CellValues::uniform(1.0, 2).get(2);
+    }
+}
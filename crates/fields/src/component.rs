@@ -0,0 +1,188 @@
+use dugong_types::FieldValue;
+use dugong_types::tensor::{SymmTensor, Tensor};
+
+use crate::error::FieldError;
+use crate::parallel::{map_indexed, update_indexed};
+use crate::value::Components;
+use crate::vol_field::VolField;
+
+impl<'mesh, T: FieldValue> VolField<'mesh, T> {
+    /// Returns a field of `f` applied to every cell and boundary value,
+    /// with [`Calculated`](crate::Calculated) conditions. Uniform fields
+    /// stay uniform.
+    pub(crate) fn map_values<U: FieldValue>(
+        &self,
+        name: String,
+        f: impl Fn(T) -> U + Sync,
+    ) -> VolField<'mesh, U> {
+        let mesh = self.mesh();
+        let mut result = match self.uniform_value() {
+            Some(value) => VolField::uniform(mesh, name, self.dimensions(), f(value)),
+            None => {
+                let values = map_indexed(mesh.n_cells(), |i| f(self.cell_value(i)));
+                // Safety: one value per cell.
+                VolField::new(mesh, name, self.dimensions(), values).unwrap()
+            }
+        };
+        for (to, from) in result
+            .patch_fields_mut()
+            .iter_mut()
+            .zip(self.patch_fields())
+        {
+            for (v, &w) in to.values_mut().iter_mut().zip(from.values()) {
+                *v = f(w);
+            }
+        }
+        result
+    }
+
+    /// Returns the magnitude of every value, named `mag(<name>)`.
+    pub fn mag(&self) -> VolField<'mesh, f64> {
+        self.map_values(format!("mag({})", self.name()), |v| v.mag())
+    }
+}
+
+impl<'mesh, T: Components> VolField<'mesh, T> {
+    /// Returns component `i` as a scalar field named
+    /// `<name>.component(<i>)`, in the order of [`Components`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= T::N_COMPONENTS`.
+    pub fn component(&self, i: usize) -> VolField<'mesh, f64> {
+        assert!(i < T::N_COMPONENTS, "component {i} out of range");
+        self.map_values(format!("{}.component({i})", self.name()), |v| {
+            v.component(i)
+        })
+    }
+
+    /// Replaces component `i` of the cell and boundary values with those of
+    /// `scalar`, such as after a segregated solve for that component.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::DimensionMismatch`] if `scalar` has other
+    /// dimensions; the field is then left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= T::N_COMPONENTS` or `scalar` is on another mesh.
+    pub fn replace_component(
+        &mut self,
+        i: usize,
+        scalar: &VolField<'_, f64>,
+    ) -> Result<(), FieldError> {
+        assert!(i < T::N_COMPONENTS, "component {i} out of range");
+        assert!(
+            std::ptr::eq(self.mesh(), scalar.mesh()),
+            "component field is on another mesh"
+        );
+        scalar.dimensions().check(
+            self.dimensions(),
+            &format!("replacing a component of {}", self.name()),
+        )?;
+        let replace = |v: T, s: f64| {
+            let mut components = [0.0; 9];
+            for (j, c) in components.iter_mut().enumerate().take(T::N_COMPONENTS) {
+                *c = v.component(j);
+            }
+            components[i] = s;
+            T::from_components(&components)
+        };
+        update_indexed(self.internal_mut(), |c, v| replace(v, scalar.cell_value(c)));
+        for (pf, spf) in self
+            .patch_fields_mut()
+            .iter_mut()
+            .zip(scalar.patch_fields())
+        {
+            for (v, &s) in pf.values_mut().iter_mut().zip(spf.values()) {
+                *v = replace(*v, s);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'mesh> VolField<'mesh, Tensor> {
+    /// Returns the deviatoric part `T − (tr T / 3) I` of every value, named
+    /// `dev(<name>)`.
+    pub fn dev(&self) -> VolField<'mesh, Tensor> {
+        self.map_values(format!("dev({})", self.name()), |v| v.dev())
+    }
+
+    /// Returns the symmetric part `(T + Tᵀ) / 2` of every value, named
+    /// `symm(<name>)`.
+    pub fn symm(&self) -> VolField<'mesh, SymmTensor> {
+        self.map_values(format!("symm({})", self.name()), |v| v.symm())
+    }
+}
+
+impl<'mesh> VolField<'mesh, SymmTensor> {
+    /// Returns the deviatoric part `S − (tr S / 3) I` of every value, named
+    /// `dev(<name>)`.
+    pub fn dev(&self) -> VolField<'mesh, SymmTensor> {
+        self.map_values(format!("dev({})", self.name()), |v| v.dev())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_component_and_replace_component_round_trip() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let velocity = vec![Vector::new(1.0, 2.0, 3.0), Vector::new(4.0, 5.0, 6.0)];
+        let mut u = VolField::new(&mesh, "U", Dimensions::default(), velocity).unwrap();
+        let mut uy = u.component(1);
+        assert_eq!(uy.name(), "U.component(1)");
+        assert_eq!(uy.internal(), &[2.0, 5.0]);
+        assert_eq!(uy.patch_field("x-max").unwrap().values(), &[5.0]);
+
+        uy.internal_mut().copy_from_slice(&[-1.0, -2.0]);
+        uy.patch_fields_mut()[1].values_mut()[0] = -3.0;
+        u.replace_component(1, &uy).unwrap();
+        assert_eq!(u.internal()[0], Vector::new(1.0, -1.0, 3.0));
+        assert_eq!(u.internal()[1], Vector::new(4.0, -2.0, 6.0));
+        assert_eq!(
+            u.patch_field("x-max").unwrap().values(),
+            &[Vector::new(4.0, -3.0, 6.0)]
+        );
+
+        let pressure = VolField::uniform(&mesh, "p", Dimensions::mlt(1, -1, -2), 0.0);
+        assert!(matches!(
+            u.replace_component(0, &pressure),
+            Err(FieldError::DimensionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_mag_dev_symm_apply_per_value() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let u = VolField::uniform(
+            &mesh,
+            "U",
+            Dimensions::default(),
+            Vector::new(3.0, 4.0, 0.0),
+        );
+        let mag = u.mag();
+        assert_eq!(mag.name(), "mag(U)");
+        assert_eq!(mag.uniform_value(), Some(5.0));
+        assert_eq!(mag.patch_fields()[0].values(), &[5.0]);
+
+        let t = Tensor::new(3.0, 2.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        let field = VolField::new(&mesh, "T", Dimensions::default(), vec![t; 2]).unwrap();
+        assert_eq!(field.dev().internal()[1], t.dev());
+        let symm = field.symm();
+        assert_eq!(symm.name(), "symm(T)");
+        assert_eq!(
+            symm.internal()[0],
+            SymmTensor::new(3.0, 1.0, 0.0, 0.0, 0.0, 0.0)
+        );
+        assert_eq!(symm.dev().internal()[0].trace(), 0.0);
+    }
+}
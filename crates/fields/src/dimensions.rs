@@ -0,0 +1,162 @@
+use std::fmt;
+use std::ops::{Div, Mul};
+
+use crate::error::FieldError;
+
+/// Names of the base quantities, in exponent order.
+const BASE_NAMES: [&str; 7] = ["kg", "m", "s", "K", "mol", "A", "cd"];
+
+/// Physical dimensions of a field, as SI base-unit exponents checked at run
+/// time.
+///
+/// The exponents are those of mass, length, time, temperature, amount of
+/// substance, current and luminous intensity, in the order of OpenFOAM's
+/// `dimensions [M L T Θ N I J]` entries. Unlike the compile-time
+/// [`Dim`](dugong_types::Dim) quantities, they can be read from case files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Dimensions {
+    exponents: [i32; 7],
+}
+
+impl Dimensions {
+    /// Dimensions of a pure number.
+    pub const DIMENSIONLESS: Self = Self { exponents: [0; 7] };
+
+    /// Creates dimensions from all seven base exponents.
+    pub const fn new(exponents: [i32; 7]) -> Self {
+        Self { exponents }
+    }
+
+    /// Creates mechanical dimensions from mass, length and time exponents.
+    pub const fn mlt(mass: i32, length: i32, time: i32) -> Self {
+        Self::new([mass, length, time, 0, 0, 0, 0])
+    }
+
+    /// Returns the base exponents.
+    pub fn exponents(&self) -> [i32; 7] {
+        self.exponents
+    }
+
+    /// Returns `true` if every exponent is zero.
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Self::DIMENSIONLESS
+    }
+
+    /// Returns the dimensions as a unit expression such as `m s^-1`, or `-`
+    /// for dimensionless quantities.
+    pub fn unit_string(&self) -> String {
+        let parts: Vec<String> = BASE_NAMES
+            .iter()
+            .zip(self.exponents)
+            .filter(|&(_, e)| e != 0)
+            .map(|(name, e)| match e {
+                1 => name.to_string(),
+                _ => format!("{name}^{e}"),
+            })
+            .collect();
+        if parts.is_empty() {
+            "-".into()
+        } else {
+            parts.join(" ")
+        }
+    }
+
+    /// Returns these dimensions if they equal `other`, as the operands of
+    /// `operation` (such as `a + b` or `fvm::laplacian`) must.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::DimensionMismatch`] otherwise.
+    pub fn check(self, other: Self, operation: &str) -> Result<Self, FieldError> {
+        if self == other {
+            Ok(self)
+        } else {
+            Err(FieldError::DimensionMismatch {
+                operation: operation.to_string(),
+                left: self,
+                right: other,
+            })
+        }
+    }
+}
+
+/// Multiplying quantities adds their exponents.
+impl Mul for Dimensions {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(std::array::from_fn(|i| {
+            self.exponents[i] + rhs.exponents[i]
+        }))
+    }
+}
+
+/// Dividing quantities subtracts their exponents.
+impl Div for Dimensions {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self::new(std::array::from_fn(|i| {
+            self.exponents[i] - rhs.exponents[i]
+        }))
+    }
+}
+
+/// A scalar constant with physical dimensions, such as a time step or a
+/// viscosity, for scaling field expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DimensionedScalar {
+    /// The value.
+    pub value: f64,
+    /// The physical dimensions.
+    pub dimensions: Dimensions,
+}
+
+impl DimensionedScalar {
+    /// Creates the constant.
+    pub const fn new(value: f64, dimensions: Dimensions) -> Self {
+        Self { value, dimensions }
+    }
+}
+
+/// Formats as OpenFOAM's bracketed exponent list, e.g. `[0 1 -1 0 0 0 0]`.
+impl fmt::Display for Dimensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [first, rest @ ..] = self.exponents;
+        write!(f, "[{first}")?;
+        for e in rest {
+            write!(f, " {e}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dimensions_display_matches_openfoam_format() {
+        let velocity = Dimensions::mlt(0, 1, -1);
+        assert_eq!(velocity.to_string(), "[0 1 -1 0 0 0 0]");
+        assert_eq!(velocity.unit_string(), "m s^-1");
+        assert_eq!(Dimensions::DIMENSIONLESS.unit_string(), "-");
+        assert!(Dimensions::default().is_dimensionless());
+        assert!(!velocity.is_dimensionless());
+    }
+
+    #[test]
+    fn test_dimensions_arithmetic_and_check() {
+        let velocity = Dimensions::mlt(0, 1, -1);
+        let time = Dimensions::mlt(0, 0, 1);
+        assert_eq!(velocity * time, Dimensions::mlt(0, 1, 0));
+        assert_eq!(velocity / velocity, Dimensions::DIMENSIONLESS);
+        assert_eq!(velocity.check(velocity, "a + b").unwrap(), velocity);
+        let error = velocity.check(time, "U + t").unwrap_err();
+        assert!(matches!(error, FieldError::DimensionMismatch { .. }));
+        assert_eq!(
+            error.to_string(),
+            "dimensions differ in U + t: [0 1 -1 0 0 0 0] (m s^-1) and [0 0 1 0 0 0 0] (s)"
+        );
+    }
+}
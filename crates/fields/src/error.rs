@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+use crate::dimensions::Dimensions;
+
+#[derive(Debug, thiserror::Error)]
+pub enum FieldError {
+    #[error("field {field}: expected {expected} values, got {got}")]
+    SizeMismatch {
+        field: String,
+        expected: usize,
+        got: usize,
+    },
+    #[error("field {field}: patch not found: {patch}")]
+    PatchNotFound { field: String, patch: String },
+    #[error(
+        "no {class} named {name} is registered{}",
+        .found.map(|f| format!(" (found a {f})")).unwrap_or_default()
+    )]
+    FieldNotFound {
+        name: String,
+        class: &'static str,
+        found: Option<&'static str>,
+    },
+    #[error("a {class} named {name} is already registered")]
+    FieldExists { name: String, class: &'static str },
+    #[error("unknown boundary condition type: {name}")]
+    UnknownBoundaryCondition { name: String },
+    #[error("unknown interpolation scheme: {name}")]
+    UnknownScheme { name: String },
+    #[error("invalid entry {keyword}: {reason}")]
+    InvalidEntry { keyword: String, reason: String },
+    #[error(
+        "dimensions differ in {operation}: {left} ({}) and {right} ({})",
+        .left.unit_string(),
+        .right.unit_string()
+    )]
+    DimensionMismatch {
+        operation: String,
+        left: Dimensions,
+        right: Dimensions,
+    },
+    #[error("invalid expression \"{expression}\": {reason}")]
+    InvalidExpression { expression: String, reason: String },
+    #[error("invalid table: {reason}")]
+    InvalidTable { reason: String },
+    #[error("failed to access {path}: {source}")]
+    File {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
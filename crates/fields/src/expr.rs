@@ -0,0 +1,382 @@
+//! Lazy field arithmetic.
+//!
+//! Arithmetic on fields builds an expression tree instead of computing a
+//! temporary field per operator; assigning the expression evaluates every
+//! operator in one loop over the cells:
+//!
+//! ```ignore
+//! let dt = DimensionedScalar::new(1e-3, Dimensions::mlt(0, 0, 1));
+//! u.assign(&a + &b * 2.0 - &grad_p * dt)?;
+//! ```
+//!
+//! Expressions combine cell values only (face values for surface fields).
+//! Assigning to a [`VolField`] then re-evaluates its boundary conditions.
+//!
+//! Expressions also carry physical dimensions: sums and differences require
+//! equal dimensions, scaling by a [`DimensionedScalar`] multiplies them, and
+//! assignment requires the target's dimensions. A mismatch is reported as
+//! [`FieldError::DimensionMismatch`] before any value is computed.
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use dugong_types::FieldValue;
+
+use crate::dimensions::{DimensionedScalar, Dimensions};
+use crate::error::FieldError;
+use crate::parallel::map_indexed;
+use crate::surface_field::SurfaceField;
+use crate::vol_field::VolField;
+
+/// A lazily evaluated field: one value per cell or per face.
+pub trait FieldExpr: Sync {
+    /// The value type.
+    type Value: FieldValue;
+
+    /// Returns the number of values.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if there are no values.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the physical dimensions of the values.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::DimensionMismatch`] if the operands of a sum
+    /// or difference differ in dimensions.
+    fn dimensions(&self) -> Result<Dimensions, FieldError>;
+
+    /// Computes value `i`.
+    fn at(&self, i: usize) -> Self::Value;
+
+    /// Computes every value.
+    fn evaluate(&self) -> Vec<Self::Value> {
+        map_indexed(self.len(), |i| self.at(i))
+    }
+}
+
+impl<T: FieldValue> FieldExpr for &VolField<'_, T> {
+    type Value = T;
+
+    fn len(&self) -> usize {
+        self.mesh().n_cells()
+    }
+
+    fn dimensions(&self) -> Result<Dimensions, FieldError> {
+        Ok(VolField::dimensions(self))
+    }
+
+    fn at(&self, i: usize) -> T {
+        self.cell_value(i)
+    }
+}
+
+impl<T: FieldValue> FieldExpr for VolField<'_, T> {
+    type Value = T;
+
+    fn len(&self) -> usize {
+        self.mesh().n_cells()
+    }
+
+    fn dimensions(&self) -> Result<Dimensions, FieldError> {
+        Ok(VolField::dimensions(self))
+    }
+
+    fn at(&self, i: usize) -> T {
+        self.cell_value(i)
+    }
+}
+
+impl<T: FieldValue> FieldExpr for &SurfaceField<'_, T> {
+    type Value = T;
+
+    fn len(&self) -> usize {
+        self.values().len()
+    }
+
+    fn dimensions(&self) -> Result<Dimensions, FieldError> {
+        Ok(SurfaceField::dimensions(self))
+    }
+
+    fn at(&self, i: usize) -> T {
+        self.values()[i]
+    }
+}
+
+impl<T: FieldValue> FieldExpr for SurfaceField<'_, T> {
+    type Value = T;
+
+    fn len(&self) -> usize {
+        self.values().len()
+    }
+
+    fn dimensions(&self) -> Result<Dimensions, FieldError> {
+        Ok(SurfaceField::dimensions(self))
+    }
+
+    fn at(&self, i: usize) -> T {
+        self.values()[i]
+    }
+}
+
+/// The sum of two expressions.
+#[derive(Debug, Clone, Copy)]
+pub struct Sum<A, B>(A, B);
+
+/// The difference of two expressions.
+#[derive(Debug, Clone, Copy)]
+pub struct Difference<A, B>(A, B);
+
+/// The negation of an expression.
+#[derive(Debug, Clone, Copy)]
+pub struct Negation<A>(A);
+
+/// An expression scaled by a constant, with the constant's dimensions.
+#[derive(Debug, Clone, Copy)]
+pub struct Scaled<A>(A, f64, Dimensions);
+
+/// Panics unless the operands of a binary operator have the same length.
+fn check_lengths(a: &impl FieldExpr, b: &impl FieldExpr) {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "field expression operands differ in length"
+    );
+}
+
+impl<A: FieldExpr, B: FieldExpr<Value = A::Value>> FieldExpr for Sum<A, B> {
+    type Value = A::Value;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn dimensions(&self) -> Result<Dimensions, FieldError> {
+        self.0
+            .dimensions()?
+            .check(self.1.dimensions()?, "field addition")
+    }
+
+    fn at(&self, i: usize) -> A::Value {
+        self.0.at(i) + self.1.at(i)
+    }
+}
+
+impl<A: FieldExpr, B: FieldExpr<Value = A::Value>> FieldExpr for Difference<A, B> {
+    type Value = A::Value;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn dimensions(&self) -> Result<Dimensions, FieldError> {
+        self.0
+            .dimensions()?
+            .check(self.1.dimensions()?, "field subtraction")
+    }
+
+    fn at(&self, i: usize) -> A::Value {
+        self.0.at(i) - self.1.at(i)
+    }
+}
+
+impl<A: FieldExpr> FieldExpr for Negation<A> {
+    type Value = A::Value;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn dimensions(&self) -> Result<Dimensions, FieldError> {
+        self.0.dimensions()
+    }
+
+    fn at(&self, i: usize) -> A::Value {
+        -self.0.at(i)
+    }
+}
+
+impl<A: FieldExpr> FieldExpr for Scaled<A> {
+    type Value = A::Value;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn dimensions(&self) -> Result<Dimensions, FieldError> {
+        Ok(self.0.dimensions()? * self.2)
+    }
+
+    fn at(&self, i: usize) -> A::Value {
+        self.0.at(i) * self.1
+    }
+}
+
+/// Implements the arithmetic operators for an expression type, given its
+/// generic parameters.
+macro_rules! impl_ops {
+    ([$($g:tt)*] $ty:ty) => {
+        impl<$($g)*, R: FieldExpr<Value = <$ty as FieldExpr>::Value>> Add<R> for $ty {
+            type Output = Sum<Self, R>;
+
+            fn add(self, rhs: R) -> Self::Output {
+                check_lengths(&self, &rhs);
+                Sum(self, rhs)
+            }
+        }
+
+        impl<$($g)*, R: FieldExpr<Value = <$ty as FieldExpr>::Value>> Sub<R> for $ty {
+            type Output = Difference<Self, R>;
+
+            fn sub(self, rhs: R) -> Self::Output {
+                check_lengths(&self, &rhs);
+                Difference(self, rhs)
+            }
+        }
+
+        impl<$($g)*> Neg for $ty {
+            type Output = Negation<Self>;
+
+            fn neg(self) -> Self::Output {
+                Negation(self)
+            }
+        }
+
+        impl<$($g)*> Mul<f64> for $ty {
+            type Output = Scaled<Self>;
+
+            fn mul(self, rhs: f64) -> Self::Output {
+                Scaled(self, rhs, Dimensions::DIMENSIONLESS)
+            }
+        }
+
+        impl<$($g)*> Mul<$ty> for f64 {
+            type Output = Scaled<$ty>;
+
+            fn mul(self, rhs: $ty) -> Self::Output {
+                Scaled(rhs, self, Dimensions::DIMENSIONLESS)
+            }
+        }
+
+        impl<$($g)*> Div<f64> for $ty {
+            type Output = Scaled<Self>;
+
+            fn div(self, rhs: f64) -> Self::Output {
+                Scaled(self, 1.0 / rhs, Dimensions::DIMENSIONLESS)
+            }
+        }
+
+        impl<$($g)*> Mul<DimensionedScalar> for $ty {
+            type Output = Scaled<Self>;
+
+            fn mul(self, rhs: DimensionedScalar) -> Self::Output {
+                Scaled(self, rhs.value, rhs.dimensions)
+            }
+        }
+
+        impl<$($g)*> Mul<$ty> for DimensionedScalar {
+            type Output = Scaled<$ty>;
+
+            fn mul(self, rhs: $ty) -> Self::Output {
+                Scaled(rhs, self.value, self.dimensions)
+            }
+        }
+
+        impl<$($g)*> Div<DimensionedScalar> for $ty {
+            type Output = Scaled<Self>;
+
+            fn div(self, rhs: DimensionedScalar) -> Self::Output {
+                Scaled(self, 1.0 / rhs.value, Dimensions::DIMENSIONLESS / rhs.dimensions)
+            }
+        }
+    };
+}
+
+impl_ops!(['a, 'mesh, T: FieldValue] &'a VolField<'mesh, T>);
+impl_ops!(['mesh, T: FieldValue] VolField<'mesh, T>);
+impl_ops!(['a, 'mesh, T: FieldValue] &'a SurfaceField<'mesh, T>);
+impl_ops!(['mesh, T: FieldValue] SurfaceField<'mesh, T>);
+impl_ops!([A: FieldExpr, B: FieldExpr<Value = A::Value>] Sum<A, B>);
+impl_ops!([A: FieldExpr, B: FieldExpr<Value = A::Value>] Difference<A, B>);
+impl_ops!([A: FieldExpr] Negation<A>);
+impl_ops!([A: FieldExpr] Scaled<A>);
+
+#[cfg(test)]
+mod tests {
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+    use crate::boundary_conditions::ZeroGradient;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_field_expr_fuses_operators() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let dims = Dimensions::default();
+        let a = VolField::new(&mesh, "a", dims, vec![1.0, 2.0, 3.0]).unwrap();
+        let b = VolField::new(&mesh, "b", dims, vec![4.0, 5.0, 6.0]).unwrap();
+        let expr = &a + &b * 2.0 - 0.5 * &a / 0.5;
+        assert_eq!(expr.len(), 3);
+        assert_eq!(expr.evaluate(), vec![8.0, 10.0, 12.0]);
+        assert_eq!((-(&a - &b)).at(0), 3.0);
+
+        let mut c = VolField::uniform(&mesh, "c", dims, 0.0);
+        c.set_boundary_condition("x-max", Box::new(ZeroGradient))
+            .unwrap();
+        c.assign(&a * 3.0 + b).unwrap();
+        assert_eq!(c.internal(), &[7.0, 11.0, 15.0]);
+        // Boundary conditions are re-evaluated from the new cell values.
+        assert_eq!(c.patch_field("x-max").unwrap().values(), &[15.0]);
+    }
+
+    #[test]
+    fn test_field_expr_on_surface_vectors() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let u = Vector::new(1.0, 2.0, 3.0);
+        let a = SurfaceField::uniform(&mesh, "a", Dimensions::default(), u);
+        let mut b = SurfaceField::uniform(&mesh, "b", Dimensions::default(), Vector::zero());
+        b.assign(&a + &a * 2.0).unwrap();
+        assert!(b.values().iter().all(|&v| v == u * 3.0));
+    }
+
+    #[test]
+    fn test_field_expr_checks_dimensions() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let velocity = Dimensions::mlt(0, 1, -1);
+        let acceleration = Dimensions::mlt(0, 1, -2);
+        let u = VolField::uniform(&mesh, "U", velocity, 2.0);
+        let a = VolField::uniform(&mesh, "a", acceleration, 4.0);
+        let dt = DimensionedScalar::new(0.5, Dimensions::mlt(0, 0, 1));
+
+        assert_eq!((&u + &a * dt).dimensions().unwrap(), velocity);
+        assert_eq!((&u / dt).dimensions().unwrap(), acceleration);
+        assert!(matches!(
+            (&u + &a).dimensions(),
+            Err(FieldError::DimensionMismatch { .. })
+        ));
+
+        let mut target = VolField::uniform(&mesh, "U", velocity, 0.0);
+        target.assign(&u - &a * dt).unwrap();
+        assert_eq!(target.internal(), &[0.0, 0.0]);
+        assert!(matches!(
+            target.assign(&a * 2.0),
+            Err(FieldError::DimensionMismatch { .. })
+        ));
+        assert!(matches!(
+            target.assign(&u + &a),
+            Err(FieldError::DimensionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "differ in length")]
+    fn test_field_expr_rejects_mismatched_lengths() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let a = VolField::uniform(&mesh, "a", Dimensions::default(), 1.0);
+        let s = SurfaceField::uniform(&mesh, "s", Dimensions::default(), 1.0);
+        let _ = &a + &s;
+    }
+}
@@ -0,0 +1,608 @@
+use std::fmt;
+
+use dugong_runtime::Value;
+use dugong_types::tensor::Vector;
+
+use crate::error::FieldError;
+use crate::parallel::map_indexed;
+use crate::value::Components;
+use crate::vol_field::VolField;
+
+/// A scalar arithmetic expression such as `sin(pi*x)*exp(-t)`, parsed from
+/// text.
+///
+/// Expressions combine numbers, variables, `+ - * / ^`, parentheses and
+/// the functions `sin cos tan asin acos atan sinh cosh tanh exp log log10
+/// sqrt abs sign floor ceil` (one argument) and `pow atan2 min max` (two
+/// arguments). `pi` is the only named constant. Any other name is a
+/// variable; names may contain `.` after the first character, as field
+/// names such as `alpha.water` do. `^` binds tighter than unary minus and
+/// groups to the right.
+#[derive(Debug, Clone)]
+pub struct Expression {
+    text: String,
+    root: Node,
+    variables: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Number(f64),
+    Variable(usize),
+    Negate(Box<Node>),
+    Binary(Operator, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Function {
+    Unary(fn(f64) -> f64),
+    Binary(fn(f64, f64) -> f64),
+}
+
+fn function(name: &str) -> Option<Function> {
+    use Function::{Binary, Unary};
+    Some(match name {
+        "sin" => Unary(f64::sin),
+        "cos" => Unary(f64::cos),
+        "tan" => Unary(f64::tan),
+        "asin" => Unary(f64::asin),
+        "acos" => Unary(f64::acos),
+        "atan" => Unary(f64::atan),
+        "sinh" => Unary(f64::sinh),
+        "cosh" => Unary(f64::cosh),
+        "tanh" => Unary(f64::tanh),
+        "exp" => Unary(f64::exp),
+        "log" => Unary(f64::ln),
+        "log10" => Unary(f64::log10),
+        "sqrt" => Unary(f64::sqrt),
+        "abs" => Unary(f64::abs),
+        "sign" => Unary(|x| if x == 0.0 { 0.0 } else { x.signum() }),
+        "floor" => Unary(f64::floor),
+        "ceil" => Unary(f64::ceil),
+        "pow" => Binary(f64::powf),
+        "atan2" => Binary(f64::atan2),
+        "min" => Binary(f64::min),
+        "max" => Binary(f64::max),
+        _ => return None,
+    })
+}
+
+impl Expression {
+    /// Parses `text`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::InvalidExpression`] on a syntax error, an
+    /// unknown function or a call with the wrong number of arguments.
+    pub fn parse(text: &str) -> Result<Self, FieldError> {
+        let mut parser = Parser {
+            text,
+            pos: 0,
+            variables: Vec::new(),
+        };
+        let root = parser.expression()?;
+        parser.skip_whitespace();
+        if parser.pos < text.len() {
+            return Err(parser.error("unexpected input"));
+        }
+        Ok(Self {
+            text: text.to_string(),
+            root,
+            variables: parser.variables,
+        })
+    }
+
+    /// Returns the text the expression was parsed from.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the names of the variables, in order of first use.
+    pub fn variables(&self) -> &[String] {
+        &self.variables
+    }
+
+    /// Evaluates the expression with `values[i]` for variable `i` of
+    /// [`variables`](Self::variables).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` is shorter than the list of variables.
+    pub fn evaluate(&self, values: &[f64]) -> f64 {
+        self.root.evaluate(&|i| values[i])
+    }
+}
+
+/// Expressions are equal if their texts are.
+impl PartialEq for Expression {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl Node {
+    fn evaluate(&self, variable: &impl Fn(usize) -> f64) -> f64 {
+        match self {
+            Self::Number(x) => *x,
+            Self::Variable(i) => variable(*i),
+            Self::Negate(a) => -a.evaluate(variable),
+            Self::Binary(op, a, b) => {
+                let (a, b) = (a.evaluate(variable), b.evaluate(variable));
+                match op {
+                    Operator::Add => a + b,
+                    Operator::Subtract => a - b,
+                    Operator::Multiply => a * b,
+                    Operator::Divide => a / b,
+                    Operator::Power => a.powf(b),
+                }
+            }
+            Self::Call(Function::Unary(f), args) => f(args[0].evaluate(variable)),
+            Self::Call(Function::Binary(f), args) => {
+                f(args[0].evaluate(variable), args[1].evaluate(variable))
+            }
+        }
+    }
+}
+
+/// A recursive-descent parser over the bytes of an expression.
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    variables: Vec<String>,
+}
+
+impl Parser<'_> {
+    fn error(&self, reason: &str) -> FieldError {
+        FieldError::InvalidExpression {
+            expression: self.text.to_string(),
+            reason: format!("{reason} at position {}", self.pos),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    /// Consumes `c` if it is the next non-blank character.
+    fn eat(&mut self, c: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    /// `term (('+' | '-') term)*`
+    fn expression(&mut self) -> Result<Node, FieldError> {
+        let mut node = self.term()?;
+        loop {
+            let op = if self.eat(b'+') {
+                Operator::Add
+            } else if self.eat(b'-') {
+                Operator::Subtract
+            } else {
+                return Ok(node);
+            };
+            node = Node::Binary(op, Box::new(node), Box::new(self.term()?));
+        }
+    }
+
+    /// `unary (('*' | '/') unary)*`
+    fn term(&mut self) -> Result<Node, FieldError> {
+        let mut node = self.unary()?;
+        loop {
+            let op = if self.eat(b'*') {
+                Operator::Multiply
+            } else if self.eat(b'/') {
+                Operator::Divide
+            } else {
+                return Ok(node);
+            };
+            node = Node::Binary(op, Box::new(node), Box::new(self.unary()?));
+        }
+    }
+
+    /// `('-' | '+') unary | primary ('^' unary)?`
+    fn unary(&mut self) -> Result<Node, FieldError> {
+        if self.eat(b'-') {
+            return Ok(Node::Negate(Box::new(self.unary()?)));
+        }
+        if self.eat(b'+') {
+            return self.unary();
+        }
+        let base = self.primary()?;
+        if self.eat(b'^') {
+            let exponent = self.unary()?;
+            return Ok(Node::Binary(
+                Operator::Power,
+                Box::new(base),
+                Box::new(exponent),
+            ));
+        }
+        Ok(base)
+    }
+
+    /// A number, a name, a call or a parenthesized expression.
+    fn primary(&mut self) -> Result<Node, FieldError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'(') => {
+                self.pos += 1;
+                let node = self.expression()?;
+                if !self.eat(b')') {
+                    return Err(self.error("expected `)`"));
+                }
+                Ok(node)
+            }
+            Some(c) if c.is_ascii_digit() || c == b'.' => self.number(),
+            Some(c) if c.is_ascii_alphabetic() || c == b'_' => self.name(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end")),
+        }
+    }
+
+    fn number(&mut self) -> Result<Node, FieldError> {
+        let start = self.pos;
+        let digits = |p: &mut Self| {
+            while p.peek().is_some_and(|c| c.is_ascii_digit()) {
+                p.pos += 1;
+            }
+        };
+        digits(self);
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            digits(self);
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            let mantissa_end = self.pos;
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                digits(self);
+            } else {
+                self.pos = mantissa_end;
+            }
+        }
+        self.text[start..self.pos]
+            .parse()
+            .map(Node::Number)
+            .map_err(|_| {
+                self.pos = start;
+                self.error("invalid number")
+            })
+    }
+
+    fn name(&mut self) -> Result<Node, FieldError> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'.')
+        {
+            self.pos += 1;
+        }
+        let name = &self.text[start..self.pos];
+        if self.eat(b'(') {
+            let Some(f) = function(name) else {
+                self.pos = start;
+                return Err(self.error(&format!("unknown function `{name}`")));
+            };
+            let mut args = vec![self.expression()?];
+            while self.eat(b',') {
+                args.push(self.expression()?);
+            }
+            if !self.eat(b')') {
+                return Err(self.error("expected `)`"));
+            }
+            let arity = match f {
+                Function::Unary(_) => 1,
+                Function::Binary(_) => 2,
+            };
+            if args.len() != arity {
+                self.pos = start;
+                return Err(self.error(&format!("`{name}` takes {arity} arguments")));
+            }
+            return Ok(Node::Call(f, args));
+        }
+        if name == "pi" {
+            return Ok(Node::Number(std::f64::consts::PI));
+        }
+        let index = match self.variables.iter().position(|v| v == name) {
+            Some(i) => i,
+            None => {
+                self.variables.push(name.to_string());
+                self.variables.len() - 1
+            }
+        };
+        Ok(Node::Variable(index))
+    }
+}
+
+/// Parses one expression per component of `T` from the values of a case
+/// file entry: a string for scalars, such as `"sin(pi*x)"`, or a list such
+/// as `("y*(1-y)" 0 0)` for other types. Numbers stand for constants.
+///
+/// # Errors
+///
+/// Returns [`FieldError::InvalidEntry`] if the values are not of this form
+/// and [`FieldError::InvalidExpression`] if an expression does not parse.
+pub fn parse_expressions<T: Components>(
+    keyword: &str,
+    values: &[Value],
+) -> Result<Vec<Expression>, FieldError> {
+    let items = match values {
+        [Value::List(items)] if T::N_COMPONENTS > 1 => items.as_slice(),
+        [single] if T::N_COMPONENTS == 1 => std::slice::from_ref(single),
+        _ => &[],
+    };
+    if items.len() != T::N_COMPONENTS {
+        return Err(FieldError::InvalidEntry {
+            keyword: keyword.to_string(),
+            reason: format!("expected {} expressions", T::N_COMPONENTS),
+        });
+    }
+    items
+        .iter()
+        .map(|item| match item {
+            Value::Str(text) | Value::Word(text) => Expression::parse(text),
+            Value::Scalar(x) => Expression::parse(&x.to_string()),
+            Value::Label(n) => Expression::parse(&n.to_string()),
+            _ => Err(FieldError::InvalidEntry {
+                keyword: keyword.to_string(),
+                reason: "expected an expression string".into(),
+            }),
+        })
+        .collect()
+}
+
+/// Formats expressions in the form read by [`parse_expressions`].
+pub fn format_expressions(expressions: &[Expression]) -> Vec<Value> {
+    let strings = expressions.iter().map(|e| Value::Str(e.text().to_string()));
+    match expressions {
+        [_] => strings.collect(),
+        _ => vec![Value::List(strings.collect())],
+    }
+}
+
+/// Evaluates one expression per component of `T` at each point, with the
+/// variables `x`, `y`, `z` and `t` and the named scalar `fields`, whose
+/// value at point `i` is `field(f, i)`.
+fn evaluate_components<T: Components>(
+    expressions: &[Expression],
+    points: &[Vector],
+    time: f64,
+    names: &[&str],
+    field: impl Fn(usize, usize) -> f64 + Sync,
+) -> Result<Vec<T>, FieldError> {
+    enum Source {
+        X,
+        Y,
+        Z,
+        Time,
+        Field(usize),
+    }
+    let sources = expressions
+        .iter()
+        .map(|e| {
+            e.variables()
+                .iter()
+                .map(|v| match v.as_str() {
+                    "x" => Ok(Source::X),
+                    "y" => Ok(Source::Y),
+                    "z" => Ok(Source::Z),
+                    "t" => Ok(Source::Time),
+                    name => names
+                        .iter()
+                        .position(|n| *n == name)
+                        .map(Source::Field)
+                        .ok_or_else(|| FieldError::InvalidExpression {
+                            expression: e.text().to_string(),
+                            reason: format!("unknown variable `{name}`"),
+                        }),
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(map_indexed(points.len(), |i| {
+        let p = points[i];
+        let components: Vec<f64> = expressions
+            .iter()
+            .zip(&sources)
+            .map(|(e, sources)| {
+                e.root.evaluate(&|v| match sources[v] {
+                    Source::X => p.x(),
+                    Source::Y => p.y(),
+                    Source::Z => p.z(),
+                    Source::Time => time,
+                    Source::Field(f) => field(f, i),
+                })
+            })
+            .collect();
+        T::from_components(&components)
+    }))
+}
+
+/// Evaluates one expression per component of `T` at each of `points`, with
+/// the variables `x`, `y`, `z` and `t`.
+///
+/// # Errors
+///
+/// Returns [`FieldError::InvalidEntry`] if there is not one expression per
+/// component and [`FieldError::InvalidExpression`] if an expression uses
+/// another variable.
+pub fn evaluate_at_points<T: Components>(
+    expressions: &[Expression],
+    points: &[Vector],
+    time: f64,
+) -> Result<Vec<T>, FieldError> {
+    check_count::<T>(expressions)?;
+    evaluate_components(expressions, points, time, &[], |_, _| 0.0)
+}
+
+fn check_count<T: Components>(expressions: &[Expression]) -> Result<(), FieldError> {
+    if expressions.len() == T::N_COMPONENTS {
+        Ok(())
+    } else {
+        Err(FieldError::InvalidEntry {
+            keyword: "expression".into(),
+            reason: format!(
+                "expected {} expressions, got {}",
+                T::N_COMPONENTS,
+                expressions.len()
+            ),
+        })
+    }
+}
+
+impl<T: Components> VolField<'_, T> {
+    /// Sets the cell and boundary values from one expression per
+    /// component, evaluated at the cell and boundary face centers.
+    ///
+    /// Expressions may use `x`, `y`, `z`, the time `t` and the names of
+    /// `fields`, which take their cell or boundary values. The boundary
+    /// conditions are not re-evaluated, so the expressions also set the
+    /// values of `calculated` patches.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::InvalidEntry`] if there is not one expression
+    /// per component and [`FieldError::InvalidExpression`] if an expression
+    /// uses an unknown variable; the field is then left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of `fields` is on another mesh.
+    pub fn set_from_expressions(
+        &mut self,
+        expressions: &[Expression],
+        time: f64,
+        fields: &[&VolField<'_, f64>],
+    ) -> Result<(), FieldError> {
+        check_count::<T>(expressions)?;
+        let mesh = self.mesh();
+        assert!(
+            fields.iter().all(|f| std::ptr::eq(f.mesh(), mesh)),
+            "expression field is on another mesh"
+        );
+        let names: Vec<&str> = fields.iter().map(|f| f.name()).collect();
+        let internal =
+            evaluate_components(expressions, mesh.cell_centers(), time, &names, |f, i| {
+                fields[f].cell_value(i)
+            })?;
+        let n_internal = mesh.n_internal_faces();
+        let boundary = evaluate_components::<T>(
+            expressions,
+            &mesh.face_centers()[n_internal..],
+            time,
+            &names,
+            |f, i| fields[f].boundary_value(n_internal + i),
+        )?;
+        self.internal_mut().copy_from_slice(&internal);
+        for pf in self.patch_fields_mut() {
+            let range = mesh.patches()[pf.patch()].range();
+            pf.values_mut()
+                .copy_from_slice(&boundary[range.start - n_internal..range.end - n_internal]);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+
+    fn eval(text: &str, values: &[f64]) -> f64 {
+        Expression::parse(text).unwrap().evaluate(values)
+    }
+
+    #[test]
+    fn test_expression_precedence_and_functions() {
+        assert_eq!(eval("1 + 2 * 3", &[]), 7.0);
+        assert_eq!(eval("(1 + 2) * 3", &[]), 9.0);
+        assert_eq!(eval("-2^2", &[]), -4.0);
+        assert_eq!(eval("2^3^2", &[]), 512.0);
+        assert_eq!(eval("8 / 4 / 2", &[]), 1.0);
+        assert_eq!(eval("1.5e2 + .5", &[]), 150.5);
+        assert_eq!(eval("max(2, pow(2, 3)) - min(1, -1)", &[]), 9.0);
+        assert!((eval("sin(pi/2)*exp(0)", &[]) - 1.0).abs() < 1e-15);
+
+        let e = Expression::parse("alpha.water * x + x").unwrap();
+        assert_eq!(e.variables(), ["alpha.water", "x"]);
+        assert_eq!(e.evaluate(&[0.5, 2.0]), 3.0);
+        assert_eq!(e.to_string(), "alpha.water * x + x");
+    }
+
+    #[test]
+    fn test_expression_parse_reports_errors() {
+        for text in ["1 +", "(1", "1 2", "foo(1)", "sin(1, 2)", "3 $ 4", ""] {
+            assert!(
+                matches!(
+                    Expression::parse(text),
+                    Err(FieldError::InvalidExpression { .. })
+                ),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_expressions_reads_components() {
+        let scalar = parse_expressions::<f64>("value", &[Value::Str("2*x".into())]).unwrap();
+        assert_eq!(format_expressions(&scalar), [Value::Str("2*x".into())]);
+        let list = Value::List(vec![
+            Value::Str("y".into()),
+            Value::Label(0),
+            Value::Scalar(1.5),
+        ]);
+        let vector = parse_expressions::<Vector>("value", &[list]).unwrap();
+        let values: Vec<Vector> =
+            evaluate_at_points(&vector, &[Vector::new(0.0, 2.0, 0.0)], 0.0).unwrap();
+        assert_eq!(values, [Vector::new(2.0, 0.0, 1.5)]);
+        assert!(parse_expressions::<Vector>("value", &[Value::Str("y".into())]).is_err());
+    }
+
+    #[test]
+    fn test_set_from_expressions_uses_coordinates_time_and_fields() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let p = VolField::new(&mesh, "p", Dimensions::default(), vec![10.0, 20.0]).unwrap();
+        let mut t = VolField::uniform(&mesh, "T", Dimensions::default(), 0.0);
+        let e = [Expression::parse("x*t + p").unwrap()];
+        t.set_from_expressions(&e, 2.0, &[&p]).unwrap();
+        assert_eq!(t.internal(), &[11.0, 23.0]);
+        // The x-max face is at x = 2 and takes the boundary value of p.
+        assert_eq!(t.patch_field("x-max").unwrap().values(), &[24.0]);
+
+        let unknown = [Expression::parse("q").unwrap()];
+        assert!(matches!(
+            t.set_from_expressions(&unknown, 0.0, &[&p]),
+            Err(FieldError::InvalidExpression { .. })
+        ));
+        assert_eq!(t.internal(), &[11.0, 23.0]);
+    }
+}
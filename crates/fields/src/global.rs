@@ -0,0 +1,129 @@
+use dugong_runtime::{ReduceOp, world};
+
+use crate::parallel::sum_indexed;
+use crate::value::Components;
+use crate::vol_field::VolField;
+
+/// Reduces the components of `value` across the ranks of
+/// [`world`](dugong_runtime::world).
+fn reduce<T: Components>(value: T, op: ReduceOp) -> T {
+    let mut components: Vec<f64> = (0..T::N_COMPONENTS).map(|i| value.component(i)).collect();
+    world().all_reduce(&mut components, op);
+    T::from_components(&components)
+}
+
+impl<T: Components> VolField<'_, T> {
+    /// Returns the sum of the cell values over all ranks.
+    ///
+    /// Reductions use the calling thread's
+    /// [`world`](dugong_runtime::world) communicator, so in serial they are
+    /// local. Like every collective call, they must be made on all ranks.
+    pub fn global_sum(&self) -> T {
+        let local = sum_indexed(self.mesh().n_cells(), T::zero(), |i| self.cell_value(i));
+        reduce(local, ReduceOp::Sum)
+    }
+
+    /// Returns the componentwise minimum of the cell values over all ranks,
+    /// or infinite components if no rank has cells.
+    pub fn global_min(&self) -> T {
+        self.global_extreme(ReduceOp::Min, f64::INFINITY)
+    }
+
+    /// Returns the componentwise maximum of the cell values over all ranks,
+    /// or negative infinite components if no rank has cells.
+    pub fn global_max(&self) -> T {
+        self.global_extreme(ReduceOp::Max, f64::NEG_INFINITY)
+    }
+
+    /// Returns the arithmetic mean of the cell values over all ranks, or
+    /// zero if no rank has cells.
+    pub fn global_average(&self) -> T {
+        let n = self.mesh().n_cells();
+        let local = sum_indexed(n, T::zero(), |i| self.cell_value(i));
+        // The sum and the cell count are reduced together.
+        let mut totals: Vec<f64> = (0..T::N_COMPONENTS).map(|i| local.component(i)).collect();
+        totals.push(n as f64);
+        world().all_reduce(&mut totals, ReduceOp::Sum);
+        // Safety: the count was pushed last.
+        let count = totals.pop().unwrap();
+        if count == 0.0 {
+            return T::zero();
+        }
+        T::from_components(&totals) * (1.0 / count)
+    }
+
+    fn global_extreme(&self, op: ReduceOp, identity: f64) -> T {
+        let mut extreme = vec![identity; T::N_COMPONENTS];
+        if let Some(value) = self.uniform_value() {
+            extreme = (0..T::N_COMPONENTS).map(|j| value.component(j)).collect();
+        } else {
+            for v in self.internal() {
+                for (j, e) in extreme.iter_mut().enumerate() {
+                    *e = op.apply(*e, v.component(j));
+                }
+            }
+        }
+        if self.mesh().n_cells() == 0 {
+            extreme.fill(identity);
+        }
+        world().all_reduce(&mut extreme, op);
+        T::from_components(&extreme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use dugong_runtime::{LocalRanks, set_world};
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_global_reductions_are_local_in_serial() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let values = vec![
+            Vector::new(1.0, -2.0, 0.0),
+            Vector::new(3.0, 4.0, 0.0),
+            Vector::new(-1.0, 1.0, 6.0),
+        ];
+        let u = VolField::new(&mesh, "U", Dimensions::default(), values).unwrap();
+        assert_eq!(u.global_sum(), Vector::new(3.0, 3.0, 6.0));
+        assert_eq!(u.global_min(), Vector::new(-1.0, -2.0, 0.0));
+        assert_eq!(u.global_max(), Vector::new(3.0, 4.0, 6.0));
+        assert_eq!(u.global_average(), Vector::new(1.0, 1.0, 2.0));
+    }
+
+    #[test]
+    fn test_global_reductions_combine_ranks() {
+        // Rank r holds r + 1 cells with the value r + 1.
+        let results: Vec<[f64; 4]> = std::thread::scope(|s| {
+            let handles: Vec<_> = LocalRanks::new(3)
+                .into_iter()
+                .enumerate()
+                .map(|(r, comm)| {
+                    s.spawn(move || {
+                        set_world(Arc::new(comm));
+                        let mesh = box_mesh([r + 1, 1, 1], [1.0, 1.0, 1.0]);
+                        let value = (r + 1) as f64;
+                        let field = VolField::uniform(&mesh, "p", Dimensions::default(), value);
+                        [
+                            field.global_sum(),
+                            field.global_min(),
+                            field.global_max(),
+                            field.global_average(),
+                        ]
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        for [sum, min, max, average] in results {
+            assert_eq!([sum, min, max], [14.0, 1.0, 3.0]);
+            assert!((average - 14.0 / 6.0).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use dugong_mesh::{Mesh, PatchKind};
+use dugong_types::FieldValue;
+use dugong_types::tensor::Vector;
+
+use crate::parallel::map_indexed;
+use crate::vol_field::VolField;
+
+/// Returns the linear interpolation weight of the owner cell on each face.
+///
+/// For an internal face with owner center `P`, neighbor center `N`, face
+/// center `f` and area vector `S`, the weight is
+///
+/// ```text
+/// w = |S · (N − f)| / (|S · (f − P)| + |S · (N − f)|)
+/// ```
+///
+/// so the face value is `w φ_P + (1 − w) φ_N`, exact for fields linear
+/// along the face normal. Boundary faces get weight 1.
+pub fn linear_weights(mesh: &Mesh) -> Vec<f64> {
+    let (areas, face_centers) = (mesh.face_areas(), mesh.face_centers());
+    let centers = mesh.cell_centers();
+    map_indexed(mesh.n_faces(), |f| {
+        let Some(&n) = mesh.neighbor().get(f) else {
+            return 1.0;
+        };
+        let s = areas[f];
+        let to_owner = (s * (face_centers[f] - centers[mesh.owner()[f]])).abs();
+        let to_neighbor = (s * (centers[n] - face_centers[f])).abs();
+        let total = to_owner + to_neighbor;
+        if total > 0.0 {
+            to_neighbor / total
+        } else {
+            0.5
+        }
+    })
+}
+
+/// Inverse-distance weights interpolating cell-centered values to the mesh
+/// points.
+///
+/// A point inside the domain takes the inverse-distance average of the
+/// centers of the cells sharing it. A point on the boundary takes the
+/// average of the centers of the boundary faces sharing it instead, so the
+/// point values follow the boundary values. Faces of empty, wedge and
+/// coupled patches do not count as boundary, which keeps points on the
+/// front and back planes of 2D meshes interpolating from cells.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointWeights {
+    weights: Vec<Vec<(usize, f64)>>,
+    on_boundary: Vec<bool>,
+}
+
+impl PointWeights {
+    /// Computes the weights of every point of `mesh`.
+    pub fn new(mesh: &Mesh) -> Self {
+        let n = mesh.n_points();
+        let mut boundary_faces = vec![Vec::new(); n];
+        for patch in mesh.patches() {
+            let kind = patch.kind();
+            if kind.is_coupled() || matches!(kind, PatchKind::Empty | PatchKind::Wedge) {
+                continue;
+            }
+            for f in patch.range() {
+                for &p in &mesh.faces()[f] {
+                    boundary_faces[p].push(f);
+                }
+            }
+        }
+        let mut point_cells = vec![Vec::new(); n];
+        for (c, points) in mesh.cell_points().iter().enumerate() {
+            for &p in points {
+                point_cells[p].push(c);
+            }
+        }
+
+        let inverse_distance = |p: usize, sources: &[usize], centers: &[Vector]| {
+            let raw: Vec<f64> = sources
+                .iter()
+                .map(|&s| 1.0 / (centers[s] - mesh.points()[p]).mag().max(f64::MIN_POSITIVE))
+                .collect();
+            let total: f64 = raw.iter().sum();
+            sources
+                .iter()
+                .zip(raw)
+                .map(|(&s, w)| (s, w / total))
+                .collect()
+        };
+        let on_boundary: Vec<bool> = boundary_faces.iter().map(|f| !f.is_empty()).collect();
+        let weights = (0..n)
+            .map(|p| {
+                if on_boundary[p] {
+                    inverse_distance(p, &boundary_faces[p], mesh.face_centers())
+                } else {
+                    inverse_distance(p, &point_cells[p], mesh.cell_centers())
+                }
+            })
+            .collect();
+        Self {
+            weights,
+            on_boundary,
+        }
+    }
+
+    /// Returns the weights of point `p` as `(source, weight)` pairs summing
+    /// to one. Sources are cell indices, or mesh face indices for points on
+    /// the boundary.
+    pub fn weights(&self, p: usize) -> &[(usize, f64)] {
+        &self.weights[p]
+    }
+
+    /// Returns `true` if point `p` interpolates from boundary faces.
+    pub fn is_on_boundary(&self, p: usize) -> bool {
+        self.on_boundary[p]
+    }
+
+    /// Interpolates a cell field to the points.
+    pub fn interpolate<T: FieldValue>(&self, field: &VolField<'_, T>) -> Vec<T> {
+        self.weights
+            .iter()
+            .zip(&self.on_boundary)
+            .map(|(weights, &on_boundary)| {
+                weights.iter().fold(T::zero(), |sum, &(s, w)| {
+                    let value = if on_boundary {
+                        field.boundary_value(s)
+                    } else {
+                        field.internal()[s]
+                    };
+                    sum + value * w
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_linear_weights_are_half_on_uniform_mesh() {
+        let mesh = box_mesh([3, 2, 1], [3.0, 2.0, 1.0]);
+        let weights = linear_weights(&mesh);
+        let n = mesh.n_internal_faces();
+        assert!(weights[..n].iter().all(|&w| (w - 0.5).abs() < 1e-12));
+        assert!(weights[n..].iter().all(|&w| w == 1.0));
+    }
+
+    #[test]
+    fn test_point_weights_sum_to_one_and_skip_empty_patches() {
+        let mesh = dugong_mesh::column_mesh(3, 3.0, 1.0).unwrap();
+        let weights = PointWeights::new(&mesh);
+        for p in 0..mesh.n_points() {
+            let total: f64 = weights.weights(p).iter().map(|&(_, w)| w).sum();
+            assert!((total - 1.0).abs() < 1e-12);
+            // Only the end points lie on non-empty patches.
+            let x = mesh.points()[p].x();
+            let at_end = x.abs() < 1e-12 || (x - 3.0).abs() < 1e-12;
+            assert_eq!(weights.is_on_boundary(p), at_end);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Built-in interpolation schemes, registered by their case-file names.
+
+mod harmonic;
+mod linear;
+mod mid_point;
+mod skew_corrected;
+mod upwind;
+
+pub use harmonic::Harmonic;
+pub use linear::Linear;
+pub use mid_point::MidPoint;
+pub use skew_corrected::SkewCorrected;
+pub use upwind::Upwind;
+
+/// Registers the constructor `$constructor` under `$name` for each listed
+/// value type; the value type is inferred from the factory.
+macro_rules! register_scheme {
+    ($name:literal, $constructor:path, [$($ty:ty),*]) => {
+        $(
+            inventory::submit! {
+                $crate::surface_interpolation::SurfaceInterpolationFactory::<$ty> {
+                    name: $name,
+                    constructor: $constructor,
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use register_scheme;
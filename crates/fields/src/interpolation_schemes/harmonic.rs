@@ -0,0 +1,74 @@
+use dugong_runtime::Value;
+
+use crate::error::FieldError;
+use crate::interpolation::linear_weights;
+use crate::interpolation_schemes::register_scheme;
+use crate::surface_field::SurfaceField;
+use crate::surface_interpolation::{SurfaceInterpolation, no_arguments};
+use crate::vol_field::VolField;
+
+/// Interpolates the reciprocal linearly (`harmonic`):
+///
+/// ```text
+/// 1 / φ_f = w / φ_P + (1 − w) / φ_N
+/// ```
+///
+/// with the linear weight `w`. Suited to diffusivities that jump between
+/// materials, where the arithmetic mean overestimates the face flux.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Harmonic;
+
+impl Harmonic {
+    /// Builds the scheme; it takes no arguments.
+    pub fn from_args(args: &[Value]) -> Result<Box<dyn SurfaceInterpolation<f64>>, FieldError> {
+        no_arguments("harmonic", args)?;
+        Ok(Box::new(Harmonic))
+    }
+}
+
+impl SurfaceInterpolation<f64> for Harmonic {
+    fn type_name(&self) -> &'static str {
+        "harmonic"
+    }
+
+    /// Returns the owner weights reproducing the harmonic mean,
+    /// `w φ_N / (w φ_N + (1 − w) φ_P)`. Faces where that is undefined keep
+    /// the linear weight.
+    fn weights(&self, field: &VolField<'_, f64>, _flux: &SurfaceField<'_, f64>) -> Vec<f64> {
+        let mesh = field.mesh();
+        let cells = field.internal();
+        linear_weights(mesh)
+            .into_iter()
+            .take(mesh.n_internal_faces())
+            .enumerate()
+            .map(|(f, w)| {
+                let (p, n) = (cells[mesh.owner()[f]], cells[mesh.neighbor()[f]]);
+                let denominator = w * n + (1.0 - w) * p;
+                if denominator == 0.0 {
+                    w
+                } else {
+                    w * n / denominator
+                }
+            })
+            .collect()
+    }
+}
+
+register_scheme!("harmonic", Harmonic::from_args, [f64]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_harmonic_gives_reciprocal_mean() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let field = VolField::new(&mesh, "k", Dimensions::default(), vec![1.0, 3.0]).unwrap();
+        let flux = SurfaceField::uniform(&mesh, "phi", Dimensions::default(), 0.0);
+        let faces = field.interpolate(&flux, &Harmonic);
+        // 1 / (0.5 / 1 + 0.5 / 3) = 1.5
+        assert!((faces.internal()[0] - 1.5).abs() < 1e-12);
+    }
+}
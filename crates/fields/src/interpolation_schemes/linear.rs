@@ -0,0 +1,44 @@
+use dugong_runtime::Value;
+use dugong_types::FieldValue;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::error::FieldError;
+use crate::interpolation::linear_weights;
+use crate::interpolation_schemes::register_scheme;
+use crate::surface_field::SurfaceField;
+use crate::surface_interpolation::{SurfaceInterpolation, no_arguments};
+use crate::vol_field::VolField;
+
+/// Interpolates linearly along the cell-to-cell line (`linear`), with the
+/// weights of [`linear_weights`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Linear;
+
+impl Linear {
+    /// Builds the scheme; it takes no arguments.
+    pub fn from_args<T: FieldValue>(
+        args: &[Value],
+    ) -> Result<Box<dyn SurfaceInterpolation<T>>, FieldError> {
+        no_arguments("linear", args)?;
+        Ok(Box::new(Linear))
+    }
+}
+
+impl<T: FieldValue> SurfaceInterpolation<T> for Linear {
+    fn type_name(&self) -> &'static str {
+        "linear"
+    }
+
+    fn weights(&self, field: &VolField<'_, T>, _flux: &SurfaceField<'_, f64>) -> Vec<f64> {
+        let mesh = field.mesh();
+        let mut weights = linear_weights(mesh);
+        weights.truncate(mesh.n_internal_faces());
+        weights
+    }
+}
+
+register_scheme!(
+    "linear",
+    Linear::from_args,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
@@ -0,0 +1,40 @@
+use dugong_runtime::Value;
+use dugong_types::FieldValue;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::error::FieldError;
+use crate::interpolation_schemes::register_scheme;
+use crate::surface_field::SurfaceField;
+use crate::surface_interpolation::{SurfaceInterpolation, no_arguments};
+use crate::vol_field::VolField;
+
+/// Takes the arithmetic mean of the owner and neighbor values (`midPoint`),
+/// regardless of the face position.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MidPoint;
+
+impl MidPoint {
+    /// Builds the scheme; it takes no arguments.
+    pub fn from_args<T: FieldValue>(
+        args: &[Value],
+    ) -> Result<Box<dyn SurfaceInterpolation<T>>, FieldError> {
+        no_arguments("midPoint", args)?;
+        Ok(Box::new(MidPoint))
+    }
+}
+
+impl<T: FieldValue> SurfaceInterpolation<T> for MidPoint {
+    fn type_name(&self) -> &'static str {
+        "midPoint"
+    }
+
+    fn weights(&self, field: &VolField<'_, T>, _flux: &SurfaceField<'_, f64>) -> Vec<f64> {
+        vec![0.5; field.mesh().n_internal_faces()]
+    }
+}
+
+register_scheme!(
+    "midPoint",
+    MidPoint::from_args,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
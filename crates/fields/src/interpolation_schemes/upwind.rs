@@ -0,0 +1,68 @@
+use dugong_runtime::Value;
+use dugong_types::FieldValue;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::error::FieldError;
+use crate::interpolation_schemes::register_scheme;
+use crate::surface_field::SurfaceField;
+use crate::surface_interpolation::SurfaceInterpolation;
+use crate::vol_field::VolField;
+
+/// Takes the value of the upstream cell (`upwind`): the owner where the
+/// flux leaves it or is zero, the neighbor otherwise.
+///
+/// First-order accurate but bounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Upwind;
+
+impl Upwind {
+    /// Builds the scheme. The optional flux name (`upwind phi`) is accepted
+    /// for compatibility; the flux is passed when interpolating.
+    pub fn from_args<T: FieldValue>(
+        args: &[Value],
+    ) -> Result<Box<dyn SurfaceInterpolation<T>>, FieldError> {
+        match args {
+            [] | [Value::Word(_)] => Ok(Box::new(Upwind)),
+            _ => Err(FieldError::InvalidEntry {
+                keyword: "upwind".into(),
+                reason: "expected at most a flux name".into(),
+            }),
+        }
+    }
+}
+
+impl<T: FieldValue> SurfaceInterpolation<T> for Upwind {
+    fn type_name(&self) -> &'static str {
+        "upwind"
+    }
+
+    fn weights(&self, field: &VolField<'_, T>, flux: &SurfaceField<'_, f64>) -> Vec<f64> {
+        flux.values()[..field.mesh().n_internal_faces()]
+            .iter()
+            .map(|&phi| if phi >= 0.0 { 1.0 } else { 0.0 })
+            .collect()
+    }
+}
+
+register_scheme!(
+    "upwind",
+    Upwind::from_args,
+    [f64, Vector, Tensor, SymmTensor, SphericalTensor]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_upwind_follows_flux_direction() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let field = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 2.0, 4.0]).unwrap();
+        let mut flux = SurfaceField::uniform(&mesh, "phi", Dimensions::default(), 1.0);
+        flux.values_mut()[1] = -1.0;
+        let faces = field.interpolate(&flux, &Upwind);
+        assert_eq!(faces.internal(), &[1.0, 4.0]);
+    }
+}
@@ -1,5 +1,65 @@
-//! Field types and typestate pattern
+//! Field types
 //!
-//! Provides volume and surface fields with boundary conditions and typestate markers.
+//! Provides volume and surface fields with boundary conditions.
 
-// TODO: Implement field types with typestate
+mod boundary;
+mod boundary_conditions;
+mod boundary_patch;
+mod cell_values;
+mod component;
+mod dimensions;
+mod error;
+pub mod expr;
+mod expression;
+mod filter;
+mod global;
+mod interpolation;
+mod interpolation_schemes;
+mod mapping;
+pub mod parallel;
+mod patch_field;
+mod point_field;
+mod registry;
+mod relaxation;
+mod stats;
+mod surface_field;
+mod surface_interpolation;
+mod table;
+mod tensor_ops;
+#[cfg(test)]
+mod test_meshes;
+mod value;
+mod vol_field;
+
+pub use boundary::{
+    BoundaryCondition, BoundaryConditionFactory, BoundaryValue, Coefficients, Constructor,
+    FlowFields, PatchContext, new_boundary_condition,
+};
+pub use boundary_conditions::{
+    Calculated, CodedBC, CodedFn, ExpressionFixedValue, FixedGradient, FixedValue, InletOutlet,
+    Mixed, NoSlip, OutletInlet, PressureInletOutletVelocity, Slip, Symmetry, TotalPressure,
+    UniformFixedValue, ZeroGradient,
+};
+pub use boundary_patch::{BoundaryPatch, BoundaryPatchMut};
+pub use dimensions::{DimensionedScalar, Dimensions};
+pub use error::FieldError;
+pub use expr::FieldExpr;
+pub use expression::{Expression, evaluate_at_points, format_expressions, parse_expressions};
+pub use interpolation::{PointWeights, linear_weights};
+pub use interpolation_schemes::{Harmonic, Linear, MidPoint, SkewCorrected, Upwind};
+pub use mapping::{MapMethod, map_field};
+pub use patch_field::PatchField;
+pub use point_field::PointField;
+pub use registry::{ObjectRegistry, RegisteredField};
+pub use relaxation::{RelaxationFactors, relax_matrix};
+pub use stats::{Extremum, FieldStats};
+pub use surface_field::SurfaceField;
+pub use surface_interpolation::{
+    InterpolationValue, SchemeConstructor, SurfaceInterpolation, SurfaceInterpolationFactory,
+    new_surface_interpolation, select_surface_interpolation,
+};
+pub use table::{Interpolation, Table};
+pub use value::{
+    Components, Transform, format_values, lookup_values, parse_value, parse_values, to_value,
+};
+pub use vol_field::VolField;
@@ -0,0 +1,321 @@
+use std::collections::HashSet;
+
+use dugong_mesh::{Mesh, MeshSearch, TetDecomposition};
+use dugong_types::FieldValue;
+use dugong_types::tensor::Vector;
+
+use crate::parallel::map_indexed;
+use crate::vol_field::VolField;
+
+/// How [`map_field`] transfers cell values from one mesh to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapMethod {
+    /// Each target cell takes the value of the source cell containing its
+    /// center.
+    NearestCell,
+    /// Each target cell takes the inverse-distance weighted mean of the
+    /// source cell containing its center and that cell's neighbors.
+    InverseDistance,
+    /// Each target cell takes the mean of the source cells it overlaps,
+    /// weighted by the volumes of the intersections. Where both meshes
+    /// cover the same region, the integral `Σ V φ` is preserved.
+    Conservative,
+}
+
+/// Maps `source` onto the cells of `target` with `method`.
+///
+/// Target cells whose center lies outside the source mesh use the owner of
+/// the nearest source boundary face as their containing cell, and
+/// conservative mapping falls back to that cell for target cells that do
+/// not overlap the source mesh. Each target boundary face takes the value
+/// of the nearest source boundary face. The result keeps the name and
+/// dimensions of `source`, with [`Calculated`](crate::Calculated)
+/// conditions and no stored old-time levels.
+///
+/// # Panics
+///
+/// Panics if the source mesh has no cells.
+pub fn map_field<'t, T: FieldValue>(
+    source: &VolField<'_, T>,
+    target: &'t Mesh,
+    method: MapMethod,
+) -> VolField<'t, T> {
+    let src = source.mesh();
+    assert!(src.n_cells() > 0, "cannot map from a mesh without cells");
+    let search = MeshSearch::new(src);
+    let cells = source.internal();
+    let centers = target.cell_centers();
+    let internal = match method {
+        MapMethod::NearestCell => {
+            map_indexed(target.n_cells(), |c| cells[locate(&search, centers[c])])
+        }
+        MapMethod::InverseDistance => map_indexed(target.n_cells(), |c| {
+            inverse_distance(src, cells, locate(&search, centers[c]), centers[c])
+        }),
+        MapMethod::Conservative => {
+            let src_tets = src.tet_decomposition();
+            let target_tets = target.tet_decomposition();
+            map_indexed(target.n_cells(), |c| {
+                let overlaps = overlaps(&search, &src_tets, target, &target_tets, c);
+                let total: f64 = overlaps.iter().map(|&(_, v)| v).sum();
+                if total > 0.0 {
+                    overlaps
+                        .iter()
+                        .fold(T::zero(), |acc, &(s, v)| acc + cells[s] * (v / total))
+                } else {
+                    cells[locate(&search, centers[c])]
+                }
+            })
+        }
+    };
+    // Safety: one value per target cell.
+    let mut field = VolField::new(target, source.name(), source.dimensions(), internal).unwrap();
+    for pf in field.patch_fields_mut() {
+        let range = target.patches()[pf.patch()].range();
+        for (v, &x) in pf
+            .values_mut()
+            .iter_mut()
+            .zip(&target.face_centers()[range])
+        {
+            if let Some((face, _)) = search.nearest_boundary_face(x) {
+                *v = source.boundary_value(face);
+            }
+        }
+    }
+    field
+}
+
+/// Returns the source cell containing `point`, or the owner of the nearest
+/// boundary face if it lies outside the mesh.
+fn locate(search: &MeshSearch<'_>, point: Vector) -> usize {
+    search.find_cell(point).unwrap_or_else(|| {
+        // Safety: a mesh with cells is closed by boundary faces.
+        let (face, _) = search.nearest_boundary_face(point).unwrap();
+        search.mesh().owner()[face]
+    })
+}
+
+/// Returns the mean of the values of `cell` and its neighbors weighted by
+/// their inverse distance to `point`.
+fn inverse_distance<T: FieldValue>(mesh: &Mesh, values: &[T], cell: usize, point: Vector) -> T {
+    let centers = mesh.cell_centers();
+    let scale = mesh.cell_volumes()[cell].cbrt();
+    let mut sum = T::zero();
+    let mut total = 0.0;
+    for &s in std::iter::once(&cell).chain(&mesh.cell_cells()[cell]) {
+        let d = (centers[s] - point).mag();
+        if d <= 1e-12 * scale {
+            return values[s];
+        }
+        sum = sum + values[s] * (1.0 / d);
+        total += 1.0 / d;
+    }
+    sum * (1.0 / total)
+}
+
+/// Returns the source cells overlapping target cell `cell` and the volume
+/// of each overlap.
+///
+/// The search starts from the source cells containing the target cell's
+/// center and points and advances through neighbors while they overlap.
+fn overlaps(
+    search: &MeshSearch<'_>,
+    src_tets: &TetDecomposition,
+    target: &Mesh,
+    target_tets: &TetDecomposition,
+    cell: usize,
+) -> Vec<(usize, f64)> {
+    let src = search.mesh();
+    let tets: Vec<[Vector; 4]> = target_tets.tets()[target_tets.cell_tets(cell)]
+        .iter()
+        .map(|t| t.map(|p| target_tets.points()[p]))
+        .collect();
+    let seeds = std::iter::once(target.cell_centers()[cell])
+        .chain(
+            target.cell_points()[cell]
+                .iter()
+                .map(|&p| target.points()[p]),
+        )
+        .filter_map(|x| search.find_cell(x));
+    let mut visited: HashSet<usize> = HashSet::new();
+    let mut front: Vec<usize> = seeds.filter(|&s| visited.insert(s)).collect();
+    let mut result = Vec::new();
+    while let Some(s) = front.pop() {
+        let volume: f64 = src_tets.tets()[src_tets.cell_tets(s)]
+            .iter()
+            .map(|t| {
+                let clip = t.map(|p| src_tets.points()[p]);
+                tets.iter()
+                    .map(|&tet| intersection_volume(tet, clip))
+                    .sum::<f64>()
+            })
+            .sum();
+        if volume > 0.0 {
+            result.push((s, volume));
+            front.extend(src.cell_cells()[s].iter().filter(|&&n| visited.insert(n)));
+        }
+    }
+    result
+}
+
+/// Returns the volume of the intersection of two tetrahedra.
+///
+/// `subject` is clipped by the four face planes of `clip` in turn; each
+/// clipped piece is a tetrahedron or a prism, split back into tetrahedra.
+fn intersection_volume(subject: [Vector; 4], clip: [Vector; 4]) -> f64 {
+    if !boxes_overlap(&subject, &clip) || tet_volume(clip) == 0.0 {
+        return 0.0;
+    }
+    let mut pieces = vec![subject];
+    for i in 0..4 {
+        let [a, b, c] = [clip[(i + 1) % 4], clip[(i + 2) % 4], clip[(i + 3) % 4]];
+        let mut normal = (b - a).cross(&(c - a));
+        if normal * (clip[i] - a) < 0.0 {
+            normal = -normal;
+        }
+        pieces = pieces
+            .into_iter()
+            .flat_map(|tet| clip_tet(tet, |x| normal * (x - a)))
+            .collect();
+        if pieces.is_empty() {
+            return 0.0;
+        }
+    }
+    pieces.into_iter().map(tet_volume).sum()
+}
+
+/// Returns the part of `tet` where `distance` is not negative, as
+/// tetrahedra.
+fn clip_tet(tet: [Vector; 4], distance: impl Fn(Vector) -> f64) -> Vec<[Vector; 4]> {
+    let d = tet.map(&distance);
+    let (inside, outside): (Vec<usize>, Vec<usize>) = (0..4).partition(|&i| d[i] >= 0.0);
+    // The point where the edge from inside vertex `i` to outside vertex `o`
+    // crosses the plane.
+    let cut = |i: usize, o: usize| tet[i] + (tet[o] - tet[i]) * (d[i] / (d[i] - d[o]));
+    match (inside.as_slice(), outside.as_slice()) {
+        (_, []) => vec![tet],
+        ([], _) => Vec::new(),
+        (&[a], &[b, c, e]) => vec![[tet[a], cut(a, b), cut(a, c), cut(a, e)]],
+        (&[a, b], &[c, e]) => {
+            // The prism between triangles (a, ac, ae) and (b, bc, be).
+            let (ac, ae, bc, be) = (cut(a, c), cut(a, e), cut(b, c), cut(b, e));
+            vec![
+                [tet[a], ac, ae, tet[b]],
+                [ac, ae, tet[b], bc],
+                [ae, tet[b], bc, be],
+            ]
+        }
+        (&[a, b, c], &[e]) => {
+            // The prism between triangles (a, b, c) and (ae, be, ce).
+            let (ae, be, ce) = (cut(a, e), cut(b, e), cut(c, e));
+            vec![
+                [tet[a], tet[b], tet[c], ae],
+                [tet[b], tet[c], ae, be],
+                [tet[c], ae, be, ce],
+            ]
+        }
+        _ => unreachable!("a tetrahedron has four vertices"),
+    }
+}
+
+fn tet_volume([a, b, c, d]: [Vector; 4]) -> f64 {
+    ((b - a).cross(&(c - a)) * (d - a)).abs() / 6.0
+}
+
+fn boxes_overlap(a: &[Vector; 4], b: &[Vector; 4]) -> bool {
+    (0..3).all(|k| {
+        let range = |t: &[Vector; 4]| {
+            t.iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+                    let x = [p.x(), p.y(), p.z()][k];
+                    (lo.min(x), hi.max(x))
+                })
+        };
+        let ((a_lo, a_hi), (b_lo, b_hi)) = (range(a), range(b));
+        a_lo <= b_hi && b_lo <= a_hi
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+
+    fn integral(field: &VolField<'_, f64>) -> f64 {
+        let volumes = field.mesh().cell_volumes();
+        field
+            .internal()
+            .iter()
+            .zip(volumes)
+            .map(|(v, a)| v * a)
+            .sum()
+    }
+
+    #[test]
+    fn test_map_field_nearest_cell_copies_parent_values() {
+        let coarse = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let fine = box_mesh([4, 2, 1], [2.0, 1.0, 1.0]);
+        let field = VolField::new(&coarse, "T", Dimensions::default(), vec![1.0, 3.0]).unwrap();
+        let mapped = map_field(&field, &fine, MapMethod::NearestCell);
+        assert_eq!(mapped.name(), "T");
+        assert_eq!(mapped.internal(), &[1.0, 1.0, 3.0, 3.0, 1.0, 1.0, 3.0, 3.0]);
+        assert_eq!(mapped.patch_field("x-max").unwrap().values(), &[3.0, 3.0]);
+    }
+
+    #[test]
+    fn test_map_field_inverse_distance_stays_within_neighbors() {
+        let coarse = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let fine = box_mesh([6, 1, 1], [3.0, 1.0, 1.0]);
+        let field =
+            VolField::new(&coarse, "T", Dimensions::default(), vec![1.0, 2.0, 4.0]).unwrap();
+        let mapped = map_field(&field, &fine, MapMethod::InverseDistance);
+        let values = mapped.internal();
+        assert!(values.windows(2).all(|w| w[0] <= w[1]));
+        assert!(values[0] > 1.0 && values[5] < 4.0);
+        // x = 1.25 is 0.25 from the center of cell 1 and 0.75 from cell 0.
+        let expected =
+            (1.0 / 0.75 + 2.0 / 0.25 + 4.0 / 1.25) / (1.0 / 0.75 + 1.0 / 0.25 + 1.0 / 1.25);
+        assert!((values[2] - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_map_field_conservative_weights_overlaps() {
+        let source = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let target = box_mesh([2, 1, 1], [3.0, 1.0, 1.0]);
+        let field =
+            VolField::new(&source, "T", Dimensions::default(), vec![1.0, 2.0, 4.0]).unwrap();
+        let mapped = map_field(&field, &target, MapMethod::Conservative);
+        let expected = [(1.0 + 0.5 * 2.0) / 1.5, (0.5 * 2.0 + 4.0) / 1.5];
+        for (v, e) in mapped.internal().iter().zip(expected) {
+            assert!((v - e).abs() < 1e-12);
+        }
+        assert!((integral(&mapped) - integral(&field)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_map_field_conservative_preserves_integral_between_unaligned_meshes() {
+        let source = box_mesh([3, 2, 2], [1.0, 1.0, 1.0]);
+        let target = box_mesh([2, 3, 5], [1.0, 1.0, 1.0]);
+        let values = (0..source.n_cells()).map(|c| (c * c) as f64).collect();
+        let field = VolField::new(&source, "T", Dimensions::default(), values).unwrap();
+        let mapped = map_field(&field, &target, MapMethod::Conservative);
+        assert!((integral(&mapped) - integral(&field)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_intersection_volume_of_overlapping_tets() {
+        let unit = [
+            Vector::new(0.0, 0.0, 0.0),
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+            Vector::new(0.0, 0.0, 1.0),
+        ];
+        assert!((intersection_volume(unit, unit) - 1.0 / 6.0).abs() < 1e-15);
+        // Halving the tetrahedron about the origin leaves an eighth.
+        let half = unit.map(|p| p * 0.5);
+        assert!((intersection_volume(unit, half) - 1.0 / 48.0).abs() < 1e-15);
+        let shifted = unit.map(|p| p + Vector::new(2.0, 0.0, 0.0));
+        assert_eq!(intersection_volume(unit, shifted), 0.0);
+    }
+}
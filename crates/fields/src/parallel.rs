@@ -0,0 +1,28 @@
+//! Data-parallel field kernels for the `parallel` feature.
+//!
+//! The kernels are those of [`dugong_mesh::parallel`], so one
+//! [`set_chunk_size`] applies to the mesh geometry and the fields alike.
+
+pub use dugong_mesh::parallel::{chunk_size, set_chunk_size};
+pub(crate) use dugong_mesh::parallel::{map_indexed, sum_indexed, update_indexed};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kernels_match_sequential_results() {
+        let n = 10_007;
+        let squares = map_indexed(n, |i| i * i);
+        assert_eq!(squares.len(), n);
+        assert!(squares.iter().enumerate().all(|(i, &x)| x == i * i));
+        assert!(map_indexed(0, |i| i).is_empty());
+
+        let mut values = vec![1usize; n];
+        update_indexed(&mut values, |i, v| v + i);
+        assert!(values.iter().enumerate().all(|(i, &x)| x == i + 1));
+
+        assert_eq!(sum_indexed(n, 0usize, |i| i), n * (n - 1) / 2);
+        assert_eq!(sum_indexed(0, 0usize, |i| i), 0);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::boundary::BoundaryCondition;
+
+/// The values of a field on the faces of one boundary patch, with the
+/// boundary condition that computes them.
+#[derive(Debug, Clone)]
+pub struct PatchField<T> {
+    patch: usize,
+    values: Vec<T>,
+    condition: Box<dyn BoundaryCondition<T>>,
+}
+
+impl<T> PatchField<T> {
+    /// Creates the field of patch index `patch` with one value per face.
+    pub(crate) fn new(
+        patch: usize,
+        values: Vec<T>,
+        condition: Box<dyn BoundaryCondition<T>>,
+    ) -> Self {
+        Self {
+            patch,
+            values,
+            condition,
+        }
+    }
+
+    /// Returns the index of the patch in [`Mesh::patches`](dugong_mesh::Mesh::patches).
+    pub fn patch(&self) -> usize {
+        self.patch
+    }
+
+    /// Returns the face values, in patch face order.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns the face values for modification.
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    /// Returns the number of faces.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the patch has no faces.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the boundary condition.
+    pub fn condition(&self) -> &dyn BoundaryCondition<T> {
+        self.condition.as_ref()
+    }
+
+    /// Returns the boundary condition for modification.
+    pub fn condition_mut(&mut self) -> &mut dyn BoundaryCondition<T> {
+        self.condition.as_mut()
+    }
+
+    /// Replaces the boundary condition.
+    pub(crate) fn set_condition(&mut self, condition: Box<dyn BoundaryCondition<T>>) {
+        self.condition = condition;
+    }
+
+    /// Returns the condition and the values, borrowed separately.
+    pub(crate) fn split_mut(&mut self) -> (&mut dyn BoundaryCondition<T>, &mut [T]) {
+        (self.condition.as_mut(), &mut self.values)
+    }
+}
@@ -0,0 +1,144 @@
+use dugong_mesh::Mesh;
+use dugong_types::FieldValue;
+
+use crate::dimensions::Dimensions;
+use crate::error::FieldError;
+use crate::interpolation::PointWeights;
+use crate::vol_field::VolField;
+
+/// A vertex-based field on a mesh: one value per mesh point.
+#[derive(Clone)]
+pub struct PointField<'mesh, T: FieldValue> {
+    mesh: &'mesh Mesh,
+    name: String,
+    dimensions: Dimensions,
+    values: Vec<T>,
+}
+
+impl<'mesh, T: FieldValue> PointField<'mesh, T> {
+    /// Creates a field with `value` at every point.
+    pub fn uniform(
+        mesh: &'mesh Mesh,
+        name: impl Into<String>,
+        dimensions: Dimensions,
+        value: T,
+    ) -> Self {
+        Self {
+            mesh,
+            name: name.into(),
+            dimensions,
+            values: vec![value; mesh.n_points()],
+        }
+    }
+
+    /// Creates a field from per-point values.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::SizeMismatch`] if `values` does not have one
+    /// value per point.
+    pub fn new(
+        mesh: &'mesh Mesh,
+        name: impl Into<String>,
+        dimensions: Dimensions,
+        values: Vec<T>,
+    ) -> Result<Self, FieldError> {
+        let name = name.into();
+        if values.len() != mesh.n_points() {
+            return Err(FieldError::SizeMismatch {
+                field: name,
+                expected: mesh.n_points(),
+                got: values.len(),
+            });
+        }
+        Ok(Self {
+            mesh,
+            name,
+            dimensions,
+            values,
+        })
+    }
+
+    /// Interpolates a cell field to the points with precomputed `weights`,
+    /// which must belong to the field's mesh.
+    ///
+    /// The result keeps the name and dimensions of `field`.
+    pub fn interpolate(field: &VolField<'mesh, T>, weights: &PointWeights) -> Self {
+        Self {
+            mesh: field.mesh(),
+            name: field.name().to_string(),
+            dimensions: field.dimensions(),
+            values: weights.interpolate(field),
+        }
+    }
+
+    /// Returns the mesh the field is defined on.
+    pub fn mesh(&self) -> &'mesh Mesh {
+        self.mesh
+    }
+
+    /// Returns the field name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the physical dimensions of the values.
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    /// Returns the point values.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns the point values for modification.
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_point_field_interpolate_recovers_linear_field() {
+        let mesh = box_mesh([2, 2, 2], [2.0, 2.0, 2.0]);
+        let exact = |x: Vector| x.x() + 2.0 * x.y() - x.z();
+        let cells = mesh.cell_centers().iter().map(|&c| exact(c)).collect();
+        let mut field = VolField::new(&mesh, "T", Dimensions::default(), cells).unwrap();
+        for (i, patch) in mesh.patches().iter().enumerate() {
+            let values = field.patch_fields_mut()[i].values_mut();
+            for (v, f) in values.iter_mut().zip(patch.range()) {
+                *v = exact(mesh.face_centers()[f]);
+            }
+        }
+        let points = PointField::interpolate(&field, &PointWeights::new(&mesh));
+        assert_eq!(points.values().len(), mesh.n_points());
+        // Points surrounded symmetrically by cells or by boundary faces are
+        // exact; box corners and edges are not.
+        for p in [Vector::new(1.0, 1.0, 1.0), Vector::new(1.0, 1.0, 0.0)] {
+            let i = mesh
+                .points()
+                .iter()
+                .position(|&x| (x - p).mag() < 1e-12)
+                .unwrap();
+            assert!((points.values()[i] - exact(p)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_point_field_new_rejects_wrong_length() {
+        let mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        assert!(matches!(
+            PointField::new(&mesh, "d", Dimensions::default(), vec![0.0; 7]),
+            Err(FieldError::SizeMismatch { expected: 8, .. })
+        ));
+        let field = PointField::uniform(&mesh, "d", Dimensions::default(), 1.0);
+        assert_eq!(field.values(), &[1.0; 8]);
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::BTreeMap;
+
+use dugong_mesh::Mesh;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::error::FieldError;
+use crate::surface_field::SurfaceField;
+use crate::vol_field::VolField;
+
+/// A field type that an [`ObjectRegistry`] can hold.
+///
+/// Implemented for volume and surface fields of every value type.
+pub trait RegisteredField<'mesh>: Sized + 'mesh {
+    /// The OpenFOAM class name, such as `volScalarField`.
+    const CLASS: &'static str;
+
+    /// Returns the name the field is registered under.
+    fn field_name(&self) -> &str;
+
+    /// Returns the mesh the field is defined on.
+    fn field_mesh(&self) -> &'mesh Mesh;
+
+    #[doc(hidden)]
+    fn table<'r>(registry: &'r ObjectRegistry<'mesh>) -> &'r BTreeMap<String, Self>;
+
+    #[doc(hidden)]
+    fn table_mut<'r>(registry: &'r mut ObjectRegistry<'mesh>) -> &'r mut BTreeMap<String, Self>;
+}
+
+macro_rules! registry {
+    ($($table:ident: $field:ident<$ty:ty> = $class:literal),* $(,)?) => {
+        /// The fields of a case, looked up by name at run time.
+        ///
+        /// Solvers register the fields they own, and loosely coupled
+        /// components such as turbulence models, function objects and
+        /// source terms find `U`, `phi` or `nut` by name instead of being
+        /// handed them. Names are unique across field types; looking a
+        /// field up with the wrong type is an error.
+        ///
+        /// To modify two fields at once, [`remove`](Self::remove) one and
+        /// [`insert`](Self::insert) it back afterwards.
+        pub struct ObjectRegistry<'mesh> {
+            mesh: &'mesh Mesh,
+            $($table: BTreeMap<String, $field<'mesh, $ty>>,)*
+        }
+
+        impl<'mesh> ObjectRegistry<'mesh> {
+            /// Creates an empty registry for the fields on `mesh`.
+            pub fn new(mesh: &'mesh Mesh) -> Self {
+                Self {
+                    mesh,
+                    $($table: BTreeMap::new(),)*
+                }
+            }
+
+            /// Returns the class of the field registered as `name`.
+            pub fn class_of(&self, name: &str) -> Option<&'static str> {
+                $(
+                    if self.$table.contains_key(name) {
+                        return Some($class);
+                    }
+                )*
+                None
+            }
+
+            /// Returns the names of all registered fields, sorted.
+            pub fn names(&self) -> Vec<&str> {
+                let mut names = Vec::new();
+                $(names.extend(self.$table.keys().map(String::as_str));)*
+                names.sort_unstable();
+                names
+            }
+
+            /// Returns the number of registered fields.
+            pub fn len(&self) -> usize {
+                0 $(+ self.$table.len())*
+            }
+        }
+
+        $(
+            impl<'mesh> RegisteredField<'mesh> for $field<'mesh, $ty> {
+                const CLASS: &'static str = $class;
+
+                fn field_name(&self) -> &str {
+                    self.name()
+                }
+
+                fn field_mesh(&self) -> &'mesh Mesh {
+                    self.mesh()
+                }
+
+                fn table<'r>(registry: &'r ObjectRegistry<'mesh>) -> &'r BTreeMap<String, Self> {
+                    &registry.$table
+                }
+
+                fn table_mut<'r>(
+                    registry: &'r mut ObjectRegistry<'mesh>,
+                ) -> &'r mut BTreeMap<String, Self> {
+                    &mut registry.$table
+                }
+            }
+        )*
+    };
+}
+
+registry!(
+    vol_scalars: VolField<f64> = "volScalarField",
+    vol_vectors: VolField<Vector> = "volVectorField",
+    vol_tensors: VolField<Tensor> = "volTensorField",
+    vol_symm_tensors: VolField<SymmTensor> = "volSymmTensorField",
+    vol_spherical_tensors: VolField<SphericalTensor> = "volSphericalTensorField",
+    surface_scalars: SurfaceField<f64> = "surfaceScalarField",
+    surface_vectors: SurfaceField<Vector> = "surfaceVectorField",
+    surface_tensors: SurfaceField<Tensor> = "surfaceTensorField",
+    surface_symm_tensors: SurfaceField<SymmTensor> = "surfaceSymmTensorField",
+    surface_spherical_tensors: SurfaceField<SphericalTensor> = "surfaceSphericalTensorField",
+);
+
+impl<'mesh> ObjectRegistry<'mesh> {
+    /// Returns the mesh of the registered fields.
+    pub fn mesh(&self) -> &'mesh Mesh {
+        self.mesh
+    }
+
+    /// Returns `true` if no field is registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if a field is registered as `name`, of any type.
+    pub fn contains(&self, name: &str) -> bool {
+        self.class_of(name).is_some()
+    }
+
+    /// Registers `field` under its name, returning the field of the same
+    /// type it replaces, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::FieldExists`] if a field of another type has
+    /// the name; the registry is then unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `field` is on another mesh.
+    pub fn insert<F: RegisteredField<'mesh>>(&mut self, field: F) -> Result<Option<F>, FieldError> {
+        assert!(
+            std::ptr::eq(field.field_mesh(), self.mesh),
+            "field {} is on another mesh",
+            field.field_name()
+        );
+        let name = field.field_name().to_string();
+        if let Some(class) = self.class_of(&name)
+            && class != F::CLASS
+        {
+            return Err(FieldError::FieldExists { name, class });
+        }
+        Ok(F::table_mut(self).insert(name, field))
+    }
+
+    /// Returns the field of type `F` registered as `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::FieldNotFound`] if there is no such field.
+    pub fn get<F: RegisteredField<'mesh>>(&self, name: &str) -> Result<&F, FieldError> {
+        F::table(self)
+            .get(name)
+            .ok_or_else(|| self.not_found::<F>(name))
+    }
+
+    /// Returns the field of type `F` registered as `name` for modification.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::FieldNotFound`] if there is no such field.
+    pub fn get_mut<F: RegisteredField<'mesh>>(&mut self, name: &str) -> Result<&mut F, FieldError> {
+        let error = self.not_found::<F>(name);
+        F::table_mut(self).get_mut(name).ok_or(error)
+    }
+
+    /// Unregisters and returns the field of type `F` registered as `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::FieldNotFound`] if there is no such field.
+    pub fn remove<F: RegisteredField<'mesh>>(&mut self, name: &str) -> Result<F, FieldError> {
+        let error = self.not_found::<F>(name);
+        F::table_mut(self).remove(name).ok_or(error)
+    }
+
+    /// Returns the names of the registered fields of type `F`, sorted.
+    pub fn names_of<F: RegisteredField<'mesh>>(&self) -> Vec<&str> {
+        F::table(self).keys().map(String::as_str).collect()
+    }
+
+    fn not_found<F: RegisteredField<'mesh>>(&self, name: &str) -> FieldError {
+        FieldError::FieldNotFound {
+            name: name.to_string(),
+            class: F::CLASS,
+            found: self.class_of(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_registry_looks_up_fields_by_name_and_type() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut registry = ObjectRegistry::new(&mesh);
+        let none = Dimensions::default();
+        registry
+            .insert(VolField::uniform(&mesh, "U", none, Vector::zero()))
+            .unwrap();
+        registry
+            .insert(VolField::uniform(&mesh, "p", none, 0.0))
+            .unwrap();
+        registry
+            .insert(SurfaceField::uniform(&mesh, "phi", none, 1.0))
+            .unwrap();
+        assert_eq!(registry.names(), ["U", "p", "phi"]);
+        assert_eq!(registry.names_of::<VolField<f64>>(), ["p"]);
+        assert_eq!(registry.class_of("phi"), Some("surfaceScalarField"));
+
+        registry
+            .get_mut::<VolField<f64>>("p")
+            .unwrap()
+            .set_uniform(2.0);
+        assert_eq!(
+            registry.get::<VolField<f64>>("p").unwrap().uniform_value(),
+            Some(2.0)
+        );
+        assert!(matches!(
+            registry.get::<VolField<f64>>("U"),
+            Err(FieldError::FieldNotFound {
+                found: Some("volVectorField"),
+                ..
+            })
+        ));
+        assert!(matches!(
+            registry.get::<SurfaceField<f64>>("nut"),
+            Err(FieldError::FieldNotFound { found: None, .. })
+        ));
+    }
+
+    #[test]
+    fn test_registry_insert_replaces_same_type_only() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut registry = ObjectRegistry::new(&mesh);
+        let none = Dimensions::default();
+        let old = registry
+            .insert(VolField::uniform(&mesh, "p", none, 1.0))
+            .unwrap();
+        assert!(old.is_none());
+        let old = registry
+            .insert(VolField::uniform(&mesh, "p", none, 2.0))
+            .unwrap();
+        assert_eq!(old.unwrap().uniform_value(), Some(1.0));
+        assert!(matches!(
+            registry.insert(SurfaceField::uniform(&mesh, "p", none, 0.0)),
+            Err(FieldError::FieldExists { .. })
+        ));
+
+        let p: VolField<f64> = registry.remove("p").unwrap();
+        assert_eq!(p.uniform_value(), Some(2.0));
+        assert!(registry.is_empty());
+    }
+}
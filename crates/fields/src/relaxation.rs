@@ -0,0 +1,222 @@
+use dugong_runtime::Dictionary;
+use dugong_types::FieldValue;
+
+use crate::error::FieldError;
+use crate::vol_field::VolField;
+
+/// Under-relaxation factors per field and per equation, as given by the
+/// `relaxationFactors` dictionary of a case:
+///
+/// ```text
+/// relaxationFactors
+/// {
+///     fields    { p 0.3; }
+///     equations { U 0.7; default 0.9; }
+/// }
+/// ```
+///
+/// A name without an entry takes the `default` entry of its group, if
+/// any.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelaxationFactors {
+    fields: Vec<(String, f64)>,
+    equations: Vec<(String, f64)>,
+}
+
+impl RelaxationFactors {
+    /// Creates an empty set of factors, which relaxes nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the `fields` and `equations` subdictionaries of a
+    /// `relaxationFactors` dictionary. Both are optional.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::InvalidEntry`] if a factor is not a number in
+    /// `(0, 1]`.
+    pub fn from_dict(dict: &Dictionary) -> Result<Self, FieldError> {
+        let group = |keyword: &str| -> Result<Vec<(String, f64)>, FieldError> {
+            let Some(group) = dict.get_dict(keyword) else {
+                return Ok(Vec::new());
+            };
+            group
+                .iter()
+                .map(|(name, _)| {
+                    let factor = group.get_scalar(name).ok_or_else(|| invalid(name))?;
+                    check_factor(name, factor)?;
+                    Ok((name.to_string(), factor))
+                })
+                .collect()
+        };
+        Ok(Self {
+            fields: group("fields")?,
+            equations: group("equations")?,
+        })
+    }
+
+    /// Sets the explicit relaxation factor of field `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::InvalidEntry`] if `factor` is not in `(0, 1]`.
+    pub fn set_field(&mut self, name: &str, factor: f64) -> Result<(), FieldError> {
+        check_factor(name, factor)?;
+        set(&mut self.fields, name, factor);
+        Ok(())
+    }
+
+    /// Sets the implicit relaxation factor of the equation for `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::InvalidEntry`] if `factor` is not in `(0, 1]`.
+    pub fn set_equation(&mut self, name: &str, factor: f64) -> Result<(), FieldError> {
+        check_factor(name, factor)?;
+        set(&mut self.equations, name, factor);
+        Ok(())
+    }
+
+    /// Returns the explicit relaxation factor of field `name`.
+    pub fn field(&self, name: &str) -> Option<f64> {
+        lookup(&self.fields, name)
+    }
+
+    /// Returns the implicit relaxation factor of the equation for `name`.
+    pub fn equation(&self, name: &str) -> Option<f64> {
+        lookup(&self.equations, name)
+    }
+}
+
+fn lookup(factors: &[(String, f64)], name: &str) -> Option<f64> {
+    let find = |key: &str| factors.iter().find(|(n, _)| n == key).map(|&(_, f)| f);
+    find(name).or_else(|| find("default"))
+}
+
+fn set(factors: &mut Vec<(String, f64)>, name: &str, factor: f64) {
+    match factors.iter_mut().find(|(n, _)| n == name) {
+        Some(entry) => entry.1 = factor,
+        None => factors.push((name.to_string(), factor)),
+    }
+}
+
+fn check_factor(name: &str, factor: f64) -> Result<(), FieldError> {
+    if factor > 0.0 && factor <= 1.0 {
+        Ok(())
+    } else {
+        Err(invalid(name))
+    }
+}
+
+fn invalid(name: &str) -> FieldError {
+    FieldError::InvalidEntry {
+        keyword: name.to_string(),
+        reason: "expected a relaxation factor in (0, 1]".into(),
+    }
+}
+
+impl<T: FieldValue> VolField<'_, T> {
+    /// Relaxes the field with its factor in `factors`, if it has one; see
+    /// [`VolField::relax`].
+    pub fn relax_with(&mut self, factors: &RelaxationFactors) {
+        if let Some(alpha) = factors.field(self.name()) {
+            self.relax(alpha);
+        }
+    }
+}
+
+/// Implicitly under-relaxes the matrix rows of an equation for `psi`.
+///
+/// Each diagonal coefficient is first raised to the sum of the magnitudes
+/// of its row's off-diagonal coefficients, if smaller, so the relaxed
+/// matrix is diagonally dominant. It is then divided by `alpha`, and the
+/// source gains `(a_P' − a_P) ψ` so the converged solution is unchanged:
+///
+/// ```text
+/// a_P' = max(|a_P|, Σ|a_N|) / α,    b' = b + (a_P' − a_P) ψ
+/// ```
+///
+/// # Panics
+///
+/// Panics if the slices do not have the same length.
+pub fn relax_matrix<T: FieldValue>(
+    diagonal: &mut [f64],
+    off_diagonal_sum: &[f64],
+    source: &mut [T],
+    psi: &[T],
+    alpha: f64,
+) {
+    let n = diagonal.len();
+    assert!(
+        off_diagonal_sum.len() == n && source.len() == n && psi.len() == n,
+        "matrix relaxation arguments differ in length"
+    );
+    for i in 0..n {
+        let relaxed = diagonal[i].abs().max(off_diagonal_sum[i]) / alpha;
+        source[i] = source[i] + psi[i] * (relaxed - diagonal[i]);
+        diagonal[i] = relaxed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_runtime::Value;
+
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_relax_blends_with_previous_iteration() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut field = VolField::new(&mesh, "p", Dimensions::default(), vec![0.0, 4.0]).unwrap();
+        field.relax(0.5);
+        assert_eq!(field.internal(), &[0.0, 4.0]);
+
+        field.store_prev_iter();
+        field.internal_mut().copy_from_slice(&[2.0, 8.0]);
+        let mut factors = RelaxationFactors::new();
+        factors.set_field("p", 0.25).unwrap();
+        field.relax_with(&factors);
+        assert_eq!(field.internal(), &[0.5, 5.0]);
+        assert_eq!(field.prev_iter(), Some(&[0.0, 4.0][..]));
+    }
+
+    #[test]
+    fn test_relaxation_factors_from_dict_uses_default() {
+        let mut fields = Dictionary::new();
+        fields.insert("p", vec![Value::Scalar(0.3)]);
+        let mut equations = Dictionary::new();
+        equations.insert("U", vec![Value::Scalar(0.7)]);
+        equations.insert("default", vec![Value::Scalar(0.9)]);
+        let mut dict = Dictionary::new();
+        dict.insert("fields", vec![Value::Dict(fields)]);
+        dict.insert("equations", vec![Value::Dict(equations)]);
+
+        let factors = RelaxationFactors::from_dict(&dict).unwrap();
+        assert_eq!(factors.field("p"), Some(0.3));
+        assert_eq!(factors.field("U"), None);
+        assert_eq!(factors.equation("U"), Some(0.7));
+        assert_eq!(factors.equation("k"), Some(0.9));
+
+        let mut bad = Dictionary::new();
+        bad.insert("p", vec![Value::Scalar(1.5)]);
+        dict.insert("fields", vec![Value::Dict(bad)]);
+        assert!(matches!(
+            RelaxationFactors::from_dict(&dict),
+            Err(FieldError::InvalidEntry { .. })
+        ));
+    }
+
+    #[test]
+    fn test_relax_matrix_preserves_solution_and_dominance() {
+        // Rows 2ψ0 − ψ1 = 1 and −ψ0 + 0.5ψ1 = 1 (the second is not dominant).
+        let mut diagonal = [2.0, 0.5];
+        let mut source = [1.0, 1.0];
+        let psi = [3.0, 4.0];
+        relax_matrix(&mut diagonal, &[1.0, 1.0], &mut source, &psi, 0.5);
+        assert_eq!(diagonal, [4.0, 2.0]);
+        assert_eq!(source, [1.0 + 2.0 * 3.0, 1.0 + 1.5 * 4.0]);
+    }
+}
@@ -0,0 +1,146 @@
+use dugong_types::tensor::Vector;
+
+use crate::parallel::sum_indexed;
+use crate::value::Components;
+use crate::vol_field::VolField;
+
+/// An extreme cell value of a field and where it occurs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Extremum<T> {
+    /// The cell value.
+    pub value: T,
+    /// The cell index.
+    pub cell: usize,
+    /// The cell center.
+    pub location: Vector,
+}
+
+/// Summary statistics of a volume field, for run-time monitoring and
+/// regression tests.
+///
+/// Scalars are ranked by value and other types by magnitude.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldStats<T> {
+    /// The smallest cell value.
+    pub min: Extremum<T>,
+    /// The largest cell value.
+    pub max: Extremum<T>,
+    /// The arithmetic mean of the cell values.
+    pub average: T,
+    /// The mean of the cell values weighted by cell volume.
+    pub volume_average: T,
+    /// The volume-weighted root mean square of the cell value magnitudes,
+    /// `sqrt(Σ|φ|² V / Σ V)`.
+    pub l2_norm: f64,
+    /// The area-weighted mean of the face values of each patch, in patch
+    /// order. Patches without faces give zero.
+    pub patch_averages: Vec<T>,
+}
+
+impl<T: Components> VolField<'_, T> {
+    /// Computes the summary statistics of the field.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mesh has no cells.
+    pub fn stats(&self) -> FieldStats<T> {
+        let mesh = self.mesh();
+        let values = self.internal();
+        let volumes = mesh.cell_volumes();
+        assert!(!values.is_empty(), "field {} has no cells", self.name());
+
+        let rank = |v: &T| {
+            if T::N_COMPONENTS == 1 {
+                v.component(0)
+            } else {
+                v.mag()
+            }
+        };
+        let (mut min, mut max) = (0, 0);
+        for (i, v) in values.iter().enumerate() {
+            if rank(v) < rank(&values[min]) {
+                min = i;
+            }
+            if rank(v) > rank(&values[max]) {
+                max = i;
+            }
+        }
+        let extremum = |cell: usize| Extremum {
+            value: values[cell],
+            cell,
+            location: mesh.cell_centers()[cell],
+        };
+
+        let n = values.len();
+        let total_volume = sum_indexed(n, 0.0, |i| volumes[i]);
+        let sum = sum_indexed(n, T::zero(), |i| values[i]);
+        let weighted = sum_indexed(n, T::zero(), |i| values[i] * volumes[i]);
+        let square = sum_indexed(n, 0.0, |i| values[i].mag() * values[i].mag() * volumes[i]);
+
+        let patch_averages = self
+            .patch_fields()
+            .iter()
+            .zip(mesh.patches())
+            .map(|(field, patch)| {
+                let areas = &mesh.face_areas()[patch.range()];
+                let total_area: f64 = areas.iter().map(Vector::mag).sum();
+                if total_area == 0.0 {
+                    return T::zero();
+                }
+                field
+                    .values()
+                    .iter()
+                    .zip(areas)
+                    .fold(T::zero(), |acc, (&v, s)| acc + v * s.mag())
+                    * (1.0 / total_area)
+            })
+            .collect();
+
+        FieldStats {
+            min: extremum(min),
+            max: extremum(max),
+            average: sum * (1.0 / values.len() as f64),
+            volume_average: weighted * (1.0 / total_volume),
+            l2_norm: (square / total_volume).sqrt(),
+            patch_averages,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_stats_scalar_locates_extrema_and_averages() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let field = VolField::new(&mesh, "T", Dimensions::default(), vec![2.0, -1.0, 5.0]).unwrap();
+        let stats = field.stats();
+        assert_eq!((stats.min.value, stats.min.cell), (-1.0, 1));
+        assert!((stats.min.location - Vector::new(1.5, 0.5, 0.5)).mag() < 1e-12);
+        assert_eq!((stats.max.value, stats.max.cell), (5.0, 2));
+        assert_eq!(stats.average, 2.0);
+        assert_eq!(stats.volume_average, 2.0);
+        assert!((stats.l2_norm - 10.0_f64.sqrt()).abs() < 1e-12);
+
+        let x_max = mesh.patch_index("x-max").unwrap();
+        let y_min = mesh.patch_index("y-min").unwrap();
+        assert_eq!(stats.patch_averages.len(), mesh.patches().len());
+        assert_eq!(stats.patch_averages[x_max], 5.0);
+        assert!((stats.patch_averages[y_min] - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_stats_vector_ranks_by_magnitude() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let values = vec![Vector::new(-3.0, 0.0, 0.0), Vector::new(0.0, 1.0, 0.0)];
+        let field = VolField::new(&mesh, "U", Dimensions::default(), values).unwrap();
+        let stats = field.stats();
+        assert_eq!(stats.min.cell, 1);
+        assert_eq!(stats.max.cell, 0);
+        assert_eq!(stats.average, Vector::new(-1.5, 0.5, 0.0));
+        assert!((stats.l2_norm - 5.0_f64.sqrt()).abs() < 1e-12);
+    }
+}
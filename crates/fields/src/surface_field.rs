@@ -0,0 +1,234 @@
+use dugong_mesh::Mesh;
+use dugong_types::FieldValue;
+
+use crate::dimensions::Dimensions;
+use crate::error::FieldError;
+use crate::expr::FieldExpr;
+use crate::interpolation::linear_weights;
+use crate::parallel::{map_indexed, update_indexed};
+use crate::vol_field::VolField;
+
+/// A face-centered field on a mesh, such as a face flux or an interpolated
+/// quantity: one value per mesh face.
+///
+/// Values are stored in mesh face order, so the internal faces come first,
+/// followed by the boundary faces of each patch in turn.
+#[derive(Clone)]
+pub struct SurfaceField<'mesh, T: FieldValue> {
+    mesh: &'mesh Mesh,
+    name: String,
+    dimensions: Dimensions,
+    values: Vec<T>,
+}
+
+impl<'mesh, T: FieldValue> SurfaceField<'mesh, T> {
+    /// Creates a field with `value` on every face.
+    pub fn uniform(
+        mesh: &'mesh Mesh,
+        name: impl Into<String>,
+        dimensions: Dimensions,
+        value: T,
+    ) -> Self {
+        Self {
+            mesh,
+            name: name.into(),
+            dimensions,
+            values: vec![value; mesh.n_faces()],
+        }
+    }
+
+    /// Creates a field from per-face values in mesh face order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::SizeMismatch`] if `values` does not have one
+    /// value per face.
+    pub fn new(
+        mesh: &'mesh Mesh,
+        name: impl Into<String>,
+        dimensions: Dimensions,
+        values: Vec<T>,
+    ) -> Result<Self, FieldError> {
+        let name = name.into();
+        if values.len() != mesh.n_faces() {
+            return Err(FieldError::SizeMismatch {
+                field: name,
+                expected: mesh.n_faces(),
+                got: values.len(),
+            });
+        }
+        Ok(Self {
+            mesh,
+            name,
+            dimensions,
+            values,
+        })
+    }
+
+    /// Linearly interpolates a cell field to the faces with
+    /// [`linear_weights`]. Boundary faces take the field's boundary values.
+    ///
+    /// The result is named `interpolate(<name>)`.
+    pub fn interpolate(field: &VolField<'mesh, T>) -> Self {
+        let mesh = field.mesh();
+        let weights = linear_weights(mesh);
+        let cells = field.internal();
+        let mut values = map_indexed(mesh.n_internal_faces(), |f| {
+            let (o, n) = (mesh.owner()[f], mesh.neighbor()[f]);
+            cells[o] * weights[f] + cells[n] * (1.0 - weights[f])
+        });
+        for pf in field.patch_fields() {
+            values.extend_from_slice(pf.values());
+        }
+        Self {
+            mesh,
+            name: format!("interpolate({})", field.name()),
+            dimensions: field.dimensions(),
+            values,
+        }
+    }
+
+    /// Returns the mesh the field is defined on.
+    pub fn mesh(&self) -> &'mesh Mesh {
+        self.mesh
+    }
+
+    /// Returns the field name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the physical dimensions of the values.
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    /// Returns the values of all faces, in mesh face order.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns the values of all faces for modification.
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    /// Returns the values of the internal faces.
+    pub fn internal(&self) -> &[T] {
+        &self.values[..self.mesh.n_internal_faces()]
+    }
+
+    /// Returns the values of the internal faces for modification.
+    pub fn internal_mut(&mut self) -> &mut [T] {
+        let n = self.mesh.n_internal_faces();
+        &mut self.values[..n]
+    }
+
+    /// Sets the face values from an expression, computed in one pass.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::DimensionMismatch`] if the expression's
+    /// dimensions are inconsistent or differ from the field's; the field is
+    /// then left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the expression does not have one value per face.
+    pub fn assign(&mut self, expr: impl FieldExpr<Value = T>) -> Result<(), FieldError> {
+        expr.dimensions()?
+            .check(self.dimensions, &format!("assignment to {}", self.name))?;
+        assert_eq!(
+            expr.len(),
+            self.values.len(),
+            "expression length differs from face count"
+        );
+        update_indexed(&mut self.values, |i, _| expr.at(i));
+        Ok(())
+    }
+
+    /// Returns the values on the faces of patch index `patch`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `patch` is out of range.
+    pub fn patch_values(&self, patch: usize) -> &[T] {
+        &self.values[self.mesh.patches()[patch].range()]
+    }
+
+    /// Returns the values on the faces of patch index `patch` for
+    /// modification.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `patch` is out of range.
+    pub fn patch_values_mut(&mut self, patch: usize) -> &mut [T] {
+        let range = self.mesh.patches()[patch].range();
+        &mut self.values[range]
+    }
+
+    /// Returns the values on the faces of the named patch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::PatchNotFound`] for unknown patch names.
+    pub fn patch(&self, name: &str) -> Result<&[T], FieldError> {
+        self.mesh
+            .patch_index(name)
+            .map(|i| self.patch_values(i))
+            .ok_or_else(|| FieldError::PatchNotFound {
+                field: self.name.clone(),
+                patch: name.to_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_mesh::column_mesh;
+
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_surface_field_interpolate_is_exact_for_linear_field() {
+        // Graded cells make the linear weights differ from one half.
+        let mesh = column_mesh(6, 3.0, 4.0).unwrap();
+        let cells = mesh
+            .cell_centers()
+            .iter()
+            .map(|c| 2.0 * c.x() + 1.0)
+            .collect();
+        let field = VolField::new(&mesh, "T", Dimensions::default(), cells).unwrap();
+        let faces = SurfaceField::interpolate(&field);
+        assert_eq!(faces.name(), "interpolate(T)");
+        for (f, &v) in faces.internal().iter().enumerate() {
+            let x = mesh.face_centers()[f].x();
+            assert!((v - (2.0 * x + 1.0)).abs() < 1e-12);
+        }
+        // Boundary faces carry the field's boundary values.
+        let x_max = field.patch_field("x-max").unwrap().values();
+        assert_eq!(faces.patch("x-max").unwrap(), x_max);
+    }
+
+    #[test]
+    fn test_surface_field_splits_values_by_patch() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let values = (0..mesh.n_faces()).map(|f| f as f64).collect();
+        let mut phi = SurfaceField::new(&mesh, "phi", Dimensions::mlt(0, 3, -1), values).unwrap();
+        assert_eq!(phi.internal(), &[0.0]);
+        let x_min = mesh.patch_index("x-min").unwrap();
+        let start = mesh.patches()[x_min].start() as f64;
+        assert_eq!(phi.patch_values(x_min), &[start]);
+        phi.patch_values_mut(x_min)[0] = -1.0;
+        assert_eq!(phi.patch("x-min").unwrap(), &[-1.0]);
+        assert!(matches!(
+            phi.patch("inlet"),
+            Err(FieldError::PatchNotFound { .. })
+        ));
+        assert!(matches!(
+            SurfaceField::new(&mesh, "phi", Dimensions::default(), vec![0.0; 3]),
+            Err(FieldError::SizeMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt::Debug;
+
+use dugong_runtime::{Dictionary, Value};
+use dugong_types::FieldValue;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::error::FieldError;
+use crate::parallel::map_indexed;
+use crate::surface_field::SurfaceField;
+use crate::value::Components;
+use crate::vol_field::VolField;
+
+/// A scheme interpolating cell values to the internal faces.
+///
+/// The face value is the weighted mean `w φ_P + (1 − w) φ_N` of the owner
+/// and neighbor cell values, plus an optional explicit correction. Keeping
+/// the weights separate lets implicit operators build their matrix from
+/// them and treat the correction as a source.
+pub trait SurfaceInterpolation<T: FieldValue>: Debug + Send + Sync {
+    /// Returns the name used in case files, such as `linear`.
+    fn type_name(&self) -> &'static str;
+
+    /// Returns the weight of the owner value on each internal face.
+    ///
+    /// `flux` is the face flux, used by schemes that follow the flow.
+    fn weights(&self, field: &VolField<'_, T>, flux: &SurfaceField<'_, f64>) -> Vec<f64>;
+
+    /// Returns the explicit correction added to the weighted value of each
+    /// internal face, if the scheme has one. Returns `None` by default.
+    fn correction(&self, field: &VolField<'_, T>, flux: &SurfaceField<'_, f64>) -> Option<Vec<T>> {
+        let _ = (field, flux);
+        None
+    }
+
+    /// Interpolates the cell values to the internal faces.
+    fn interpolate(&self, field: &VolField<'_, T>, flux: &SurfaceField<'_, f64>) -> Vec<T> {
+        weighted_values(
+            field,
+            &self.weights(field, flux),
+            self.correction(field, flux),
+        )
+    }
+}
+
+/// Returns `w φ_P + (1 − w) φ_N` on each internal face, plus `correction`.
+pub(crate) fn weighted_values<T: FieldValue>(
+    field: &VolField<'_, T>,
+    weights: &[f64],
+    correction: Option<Vec<T>>,
+) -> Vec<T> {
+    let mesh = field.mesh();
+    let cells = field.internal();
+    let mut values = map_indexed(weights.len(), |f| {
+        let w = weights[f];
+        cells[mesh.owner()[f]] * w + cells[mesh.neighbor()[f]] * (1.0 - w)
+    });
+    if let Some(correction) = correction {
+        for (v, c) in values.iter_mut().zip(correction) {
+            *v = *v + c;
+        }
+    }
+    values
+}
+
+/// Builds an interpolation scheme from the tokens following its name in a
+/// case file, such as `phi` in `upwind phi`.
+pub type SchemeConstructor<T> =
+    fn(&[Value]) -> Result<Box<dyn SurfaceInterpolation<T>>, FieldError>;
+
+/// An interpolation scheme selectable by name at run time.
+///
+/// Submit one per value type with `inventory::submit!`; the first word of
+/// a scheme specification selects it.
+pub struct SurfaceInterpolationFactory<T: 'static> {
+    /// The scheme name used in case files.
+    pub name: &'static str,
+    /// Builds the scheme.
+    pub constructor: SchemeConstructor<T>,
+}
+
+inventory::collect!(SurfaceInterpolationFactory<f64>);
+inventory::collect!(SurfaceInterpolationFactory<Vector>);
+inventory::collect!(SurfaceInterpolationFactory<Tensor>);
+inventory::collect!(SurfaceInterpolationFactory<SymmTensor>);
+inventory::collect!(SurfaceInterpolationFactory<SphericalTensor>);
+
+/// Value types with a registry of run-time selectable interpolation schemes.
+pub trait InterpolationValue: Components {
+    /// Iterates over the registered interpolation schemes.
+    fn schemes() -> impl Iterator<Item = &'static SurfaceInterpolationFactory<Self>>;
+}
+
+macro_rules! impl_interpolation_value {
+    ($($ty:ty),*) => {
+        $(
+            impl InterpolationValue for $ty {
+                fn schemes() -> impl Iterator<Item = &'static SurfaceInterpolationFactory<Self>> {
+                    inventory::iter::<SurfaceInterpolationFactory<Self>>.into_iter()
+                }
+            }
+        )*
+    };
+}
+
+impl_interpolation_value!(f64, Vector, Tensor, SymmTensor, SphericalTensor);
+
+/// Builds the scheme named by the first word of `spec`, passing it the
+/// remaining tokens.
+///
+/// # Errors
+///
+/// Returns [`FieldError::InvalidEntry`] if `spec` does not start with a
+/// word, [`FieldError::UnknownScheme`] if no scheme of that name is
+/// registered for `T`, and any error of the scheme's constructor.
+pub fn new_surface_interpolation<T: InterpolationValue>(
+    spec: &[Value],
+) -> Result<Box<dyn SurfaceInterpolation<T>>, FieldError> {
+    let (name, args) = match spec {
+        [Value::Word(name), args @ ..] => (name, args),
+        _ => {
+            return Err(FieldError::InvalidEntry {
+                keyword: "scheme".into(),
+                reason: "expected a scheme name".into(),
+            });
+        }
+    };
+    let factory =
+        T::schemes()
+            .find(|f| f.name == name)
+            .ok_or_else(|| FieldError::UnknownScheme {
+                name: name.to_string(),
+            })?;
+    (factory.constructor)(args)
+}
+
+/// Builds the scheme selected for `term` in a schemes dictionary such as
+/// `interpolationSchemes`, falling back to its `default` entry.
+///
+/// # Errors
+///
+/// Returns [`FieldError::InvalidEntry`] if neither `term` nor `default` is
+/// present, and the errors of [`new_surface_interpolation`].
+pub fn select_surface_interpolation<T: InterpolationValue>(
+    schemes: &Dictionary,
+    term: &str,
+) -> Result<Box<dyn SurfaceInterpolation<T>>, FieldError> {
+    let spec = schemes
+        .get(term)
+        .or_else(|| schemes.get("default"))
+        .ok_or_else(|| FieldError::InvalidEntry {
+            keyword: term.to_string(),
+            reason: "no scheme and no default".into(),
+        })?;
+    new_surface_interpolation(spec)
+}
+
+/// Rejects tokens after the name of a scheme that takes none.
+pub(crate) fn no_arguments(name: &str, args: &[Value]) -> Result<(), FieldError> {
+    if args.is_empty() {
+        Ok(())
+    } else {
+        Err(FieldError::InvalidEntry {
+            keyword: name.to_string(),
+            reason: "unexpected arguments".into(),
+        })
+    }
+}
+
+impl<'mesh, T: FieldValue> VolField<'mesh, T> {
+    /// Interpolates the field to the faces with `scheme`. Boundary faces
+    /// take the field's boundary values.
+    ///
+    /// The result is named `interpolate(<name>)`.
+    pub fn interpolate(
+        &self,
+        flux: &SurfaceField<'_, f64>,
+        scheme: &dyn SurfaceInterpolation<T>,
+    ) -> SurfaceField<'mesh, T> {
+        let mut values = scheme.interpolate(self, flux);
+        for pf in self.patch_fields() {
+            values.extend_from_slice(pf.values());
+        }
+        // Safety: one value per internal face, then one per boundary face.
+        SurfaceField::new(
+            self.mesh(),
+            format!("interpolate({})", self.name()),
+            self.dimensions(),
+            values,
+        )
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::{box_mesh, spec};
+
+    #[test]
+    fn test_new_surface_interpolation_selects_registered_scheme() {
+        for name in ["linear", "midPoint", "upwind", "harmonic", "skewCorrected"] {
+            let mut words = vec![name];
+            if name == "skewCorrected" {
+                words.push("linear");
+            }
+            let scheme = new_surface_interpolation::<f64>(&spec(&words)).unwrap();
+            assert_eq!(scheme.type_name(), name);
+        }
+        assert!(new_surface_interpolation::<Vector>(&spec(&["upwind", "phi"])).is_ok());
+        assert!(matches!(
+            new_surface_interpolation::<Vector>(&spec(&["harmonic"])),
+            Err(FieldError::UnknownScheme { .. })
+        ));
+        assert!(matches!(
+            new_surface_interpolation::<f64>(&spec(&["linear", "phi"])),
+            Err(FieldError::InvalidEntry { .. })
+        ));
+        assert!(matches!(
+            new_surface_interpolation::<f64>(&[]),
+            Err(FieldError::InvalidEntry { .. })
+        ));
+    }
+
+    #[test]
+    fn test_select_surface_interpolation_falls_back_to_default() {
+        let mut schemes = Dictionary::new();
+        schemes.insert("interpolate(U)", spec(&["upwind", "phi"]));
+        let scheme = select_surface_interpolation::<Vector>(&schemes, "interpolate(U)").unwrap();
+        assert_eq!(scheme.type_name(), "upwind");
+        assert!(select_surface_interpolation::<f64>(&schemes, "interpolate(p)").is_err());
+        schemes.insert("default", spec(&["linear"]));
+        let scheme = select_surface_interpolation::<f64>(&schemes, "interpolate(p)").unwrap();
+        assert_eq!(scheme.type_name(), "linear");
+    }
+
+    #[test]
+    fn test_vol_field_interpolate_appends_boundary_values() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let field = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 2.0, 4.0]).unwrap();
+        let flux = SurfaceField::uniform(&mesh, "phi", Dimensions::default(), 1.0);
+        let scheme = new_surface_interpolation::<f64>(&spec(&["linear"])).unwrap();
+        let faces = field.interpolate(&flux, scheme.as_ref());
+        assert_eq!(faces.name(), "interpolate(T)");
+        for (&v, expected) in faces.internal().iter().zip([1.5, 3.0]) {
+            assert!((v - expected).abs() < 1e-12);
+        }
+        assert_eq!(faces.patch("x-max").unwrap(), &[4.0]);
+    }
+}
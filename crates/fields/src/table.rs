@@ -0,0 +1,372 @@
+use std::path::Path;
+
+use dugong_runtime::{Dictionary, Value};
+use dugong_types::FieldValue;
+
+use crate::error::FieldError;
+use crate::value::{Components, parse_value, to_value};
+
+/// How a [`Table`] interpolates between its points.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Piecewise linear.
+    #[default]
+    Linear,
+    /// Natural cubic spline: twice differentiable, with zero curvature at
+    /// the end points.
+    Spline,
+}
+
+impl Interpolation {
+    /// Returns the scheme named `linear` or `spline`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "linear" => Some(Self::Linear),
+            "spline" => Some(Self::Spline),
+            _ => None,
+        }
+    }
+}
+
+/// A value tabulated against one coordinate, such as time or a distance
+/// along a profile.
+///
+/// Outside the tabulated range the end values are held.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table<T> {
+    x: Vec<f64>,
+    values: Vec<T>,
+    interpolation: Interpolation,
+    /// Second derivatives at the points, for splines.
+    curvature: Vec<T>,
+}
+
+impl<T: FieldValue> Table<T> {
+    /// Creates a table from `(x, value)` points.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::InvalidTable`] if there are no points or the
+    /// coordinates are not strictly increasing.
+    pub fn new(points: Vec<(f64, T)>, interpolation: Interpolation) -> Result<Self, FieldError> {
+        if points.is_empty() {
+            return Err(FieldError::InvalidTable {
+                reason: "no points".into(),
+            });
+        }
+        if let Some(w) = points
+            .windows(2)
+            .find(|w| w[0].0.partial_cmp(&w[1].0).is_none_or(|o| o.is_ge()))
+        {
+            return Err(FieldError::InvalidTable {
+                reason: format!("coordinates not increasing at {}", w[1].0),
+            });
+        }
+        let (x, values): (Vec<f64>, Vec<T>) = points.into_iter().unzip();
+        let curvature = match interpolation {
+            Interpolation::Linear => Vec::new(),
+            Interpolation::Spline => spline_curvature(&x, &values),
+        };
+        Ok(Self {
+            x,
+            values,
+            interpolation,
+            curvature,
+        })
+    }
+
+    /// Creates a table holding `value` everywhere.
+    pub fn constant(value: T) -> Self {
+        Self {
+            x: vec![0.0],
+            values: vec![value],
+            interpolation: Interpolation::Linear,
+            curvature: Vec::new(),
+        }
+    }
+
+    /// Returns the interpolation scheme.
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Returns the `(x, value)` points.
+    pub fn points(&self) -> impl Iterator<Item = (f64, T)> + '_ {
+        self.x.iter().copied().zip(self.values.iter().copied())
+    }
+
+    /// Returns the interpolated value at `x`.
+    pub fn value(&self, x: f64) -> T {
+        let n = self.x.len();
+        if x <= self.x[0] {
+            return self.values[0];
+        }
+        if x >= self.x[n - 1] {
+            return self.values[n - 1];
+        }
+        // The first point strictly after x; 1 <= i < n here.
+        let i = self.x.partition_point(|&xi| xi <= x);
+        let h = self.x[i] - self.x[i - 1];
+        let b = (x - self.x[i - 1]) / h;
+        let a = 1.0 - b;
+        let linear = self.values[i - 1] * a + self.values[i] * b;
+        match self.interpolation {
+            Interpolation::Linear => linear,
+            Interpolation::Spline => {
+                let c = h * h / 6.0;
+                linear
+                    + self.curvature[i - 1] * ((a * a * a - a) * c)
+                    + self.curvature[i] * ((b * b * b - b) * c)
+            }
+        }
+    }
+}
+
+impl<T: Components> Table<T> {
+    /// Parses CSV text with the coordinate in the first column and the
+    /// value components in the following ones.
+    ///
+    /// Blank lines, lines starting with `#` and a leading header line are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::InvalidTable`] for malformed rows and the
+    /// errors of [`Table::new`].
+    pub fn from_csv(text: &str, interpolation: Interpolation) -> Result<Self, FieldError> {
+        let mut points = Vec::new();
+        let rows = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'));
+        for (k, (i, line)) in rows.enumerate() {
+            let row: Result<Vec<f64>, _> = line.split(',').map(|c| c.trim().parse()).collect();
+            let row = match row {
+                Ok(row) => row,
+                Err(_) if k == 0 => continue,
+                Err(_) => {
+                    return Err(FieldError::InvalidTable {
+                        reason: format!("line {}: not a number", i + 1),
+                    });
+                }
+            };
+            if row.len() != 1 + T::N_COMPONENTS {
+                return Err(FieldError::InvalidTable {
+                    reason: format!(
+                        "line {}: expected {} columns, got {}",
+                        i + 1,
+                        1 + T::N_COMPONENTS,
+                        row.len()
+                    ),
+                });
+            }
+            points.push((row[0], T::from_components(&row[1..])));
+        }
+        Self::new(points, interpolation)
+    }
+
+    /// Reads a CSV file with [`Table::from_csv`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::File`] if the file cannot be read, and the
+    /// errors of [`Table::from_csv`].
+    pub fn read_csv(
+        path: impl AsRef<Path>,
+        interpolation: Interpolation,
+    ) -> Result<Self, FieldError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|source| FieldError::File {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_csv(&text, interpolation)
+    }
+
+    /// Reads the table entry `keyword` of `dict`, one of
+    ///
+    /// - `<value>` or `constant <value>`,
+    /// - `table ((<x> <value>) ...)`,
+    /// - `csvFile "<path>"`,
+    ///
+    /// interpolated with the optional `interpolationScheme` entry, `linear`
+    /// by default.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::InvalidEntry`] if an entry is missing or
+    /// malformed, and the errors of building the table.
+    pub fn from_dict(dict: &Dictionary, keyword: &str) -> Result<Self, FieldError> {
+        let invalid = |reason: &str| FieldError::InvalidEntry {
+            keyword: keyword.to_string(),
+            reason: reason.to_string(),
+        };
+        let interpolation = match dict.get_word("interpolationScheme") {
+            None => Interpolation::default(),
+            Some(name) => {
+                Interpolation::from_name(name).ok_or_else(|| FieldError::InvalidEntry {
+                    keyword: "interpolationScheme".into(),
+                    reason: format!("unknown scheme {name}"),
+                })?
+            }
+        };
+        let values = dict.get(keyword).ok_or_else(|| invalid("missing"))?;
+        let word = values.first().and_then(Value::as_word);
+        match (word, values) {
+            (Some("table"), [_, Value::List(rows)]) => {
+                let points = rows
+                    .iter()
+                    .map(|row| match row.as_list() {
+                        Some([x, v]) => x.as_scalar().zip(parse_value(v)),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| invalid("expected (x value) rows"))?;
+                Self::new(points, interpolation)
+            }
+            (Some("csvFile"), [_, path]) => {
+                let path = path
+                    .as_word()
+                    .ok_or_else(|| invalid("expected a file name"))?;
+                Self::read_csv(path, interpolation)
+            }
+            (Some("constant"), [_, v]) | (None, [v]) => parse_value(v)
+                .map(Self::constant)
+                .ok_or_else(|| invalid(&format!("expected a {}", T::TYPE_NAME))),
+            _ => Err(invalid("expected a value, `table` or `csvFile`")),
+        }
+    }
+}
+
+impl<T: Components> Table<T> {
+    /// Writes the table as the entry `keyword` of `dict` in the
+    /// `table ((<x> <value>) ...)` form read by [`Table::from_dict`], with an
+    /// `interpolationScheme` entry for splines.
+    pub fn write_entries(&self, dict: &mut Dictionary, keyword: &str) {
+        let rows = self
+            .points()
+            .map(|(x, v)| Value::List(vec![Value::Scalar(x), to_value(&v)]))
+            .collect();
+        dict.insert(
+            keyword,
+            vec![Value::Word("table".into()), Value::List(rows)],
+        );
+        if self.interpolation == Interpolation::Spline {
+            dict.insert("interpolationScheme", vec![Value::Word("spline".into())]);
+        }
+    }
+}
+
+/// Returns the second derivatives of the natural cubic spline through the
+/// points, by the Thomas algorithm.
+fn spline_curvature<T: FieldValue>(x: &[f64], y: &[T]) -> Vec<T> {
+    let n = x.len();
+    let mut m = vec![T::zero(); n];
+    if n < 3 {
+        return m;
+    }
+    let h: Vec<f64> = x.windows(2).map(|w| w[1] - w[0]).collect();
+    let slope = |i: usize| (y[i + 1] - y[i]) * (1.0 / h[i]);
+    // Forward elimination over the interior points 1..n-1.
+    let mut diag = vec![0.0; n];
+    let mut rhs = vec![T::zero(); n];
+    for i in 1..n - 1 {
+        diag[i] = 2.0 * (h[i - 1] + h[i]);
+        rhs[i] = (slope(i) - slope(i - 1)) * 6.0;
+        if i > 1 {
+            let w = h[i - 1] / diag[i - 1];
+            diag[i] -= w * h[i - 1];
+            rhs[i] = rhs[i] - rhs[i - 1] * w;
+        }
+    }
+    for i in (1..n - 1).rev() {
+        m[i] = (rhs[i] - m[i + 1] * h[i]) * (1.0 / diag[i]);
+    }
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+
+    #[test]
+    fn test_table_linear_interpolates_and_clamps() {
+        let table = Table::new(
+            vec![(0.0, 0.0), (1.0, 10.0), (3.0, 30.0)],
+            Interpolation::Linear,
+        )
+        .unwrap();
+        assert_eq!(table.value(-1.0), 0.0);
+        assert_eq!(table.value(0.5), 5.0);
+        assert_eq!(table.value(2.0), 20.0);
+        assert_eq!(table.value(5.0), 30.0);
+        assert!(matches!(
+            Table::new(vec![(1.0, 0.0), (1.0, 1.0)], Interpolation::Linear),
+            Err(FieldError::InvalidTable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_table_spline_reproduces_cubic_curvature() {
+        // A natural spline through points of a straight line is the line.
+        let points = (0..5).map(|i| (i as f64, 2.0 * i as f64 + 1.0)).collect();
+        let line = Table::new(points, Interpolation::Spline).unwrap();
+        assert!((line.value(2.5) - 6.0).abs() < 1e-12);
+        // Through (0, 0), (1, 1), (2, 0) the natural spline peaks at x = 1
+        // and is symmetric: y(0.5) = 11/16.
+        let bump = Table::new(
+            vec![(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)],
+            Interpolation::Spline,
+        )
+        .unwrap();
+        assert!((bump.value(0.5) - 11.0 / 16.0).abs() < 1e-12);
+        assert!((bump.value(1.5) - 11.0 / 16.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_table_from_csv_skips_header_and_comments() {
+        let text = "t, ux, uy, uz\n# ramp\n0, 0, 0, 0\n\n2, 4, 0, 2\n";
+        let table = Table::<Vector>::from_csv(text, Interpolation::Linear).unwrap();
+        assert_eq!(table.value(1.0), Vector::new(2.0, 0.0, 1.0));
+        assert!(matches!(
+            Table::<f64>::from_csv("0, 1\n1, x\n", Interpolation::Linear),
+            Err(FieldError::InvalidTable { .. })
+        ));
+        assert!(Table::<f64>::from_csv("0, 1, 2\n", Interpolation::Linear).is_err());
+    }
+
+    #[test]
+    fn test_table_from_dict_reads_table_and_constant() {
+        let row = |t: f64, v: f64| Value::List(vec![Value::Scalar(t), Value::Scalar(v)]);
+        let mut dict = Dictionary::new();
+        dict.insert(
+            "uniformValue",
+            vec![
+                Value::Word("table".into()),
+                Value::List(vec![row(0.0, 1.0), row(1.0, 3.0)]),
+            ],
+        );
+        let table = Table::<f64>::from_dict(&dict, "uniformValue").unwrap();
+        assert_eq!(table.value(0.5), 2.0);
+
+        dict.insert("uniformValue", vec![Value::Scalar(4.0)]);
+        assert_eq!(
+            Table::<f64>::from_dict(&dict, "uniformValue")
+                .unwrap()
+                .value(9.0),
+            4.0
+        );
+
+        let spline = Table::new(vec![(0.0, 1.0), (1.0, 2.0)], Interpolation::Spline).unwrap();
+        spline.write_entries(&mut dict, "uniformValue");
+        assert_eq!(
+            Table::<f64>::from_dict(&dict, "uniformValue").unwrap(),
+            spline
+        );
+
+        dict.insert("interpolationScheme", vec![Value::Word("cubic".into())]);
+        assert!(Table::<f64>::from_dict(&dict, "uniformValue").is_err());
+    }
+}
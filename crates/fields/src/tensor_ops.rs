@@ -0,0 +1,113 @@
+use dugong_types::FieldValue;
+use dugong_types::tensor::Tensor;
+
+use crate::parallel::map_indexed;
+use crate::vol_field::VolField;
+
+impl<'mesh, T: FieldValue + Into<Tensor>> VolField<'mesh, T> {
+    /// Returns the trace of every value, named `tr(<name>)`.
+    pub fn tr(&self) -> VolField<'mesh, f64> {
+        self.map_values(format!("tr({})", self.name()), |v| v.into().trace())
+    }
+
+    /// Returns the double inner product `A : B = Σ_ij A_ij B_ij` of the
+    /// values of the two fields, named `(<A>&&<B>)`, with the product of
+    /// their dimensions.
+    ///
+    /// The production of turbulent kinetic energy, for instance, is
+    /// `R.double_dot(&grad_u)` with the Reynolds stress `R`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is on another mesh.
+    pub fn double_dot<U: FieldValue + Into<Tensor>>(
+        &self,
+        other: &VolField<'_, U>,
+    ) -> VolField<'mesh, f64> {
+        assert!(
+            std::ptr::eq(self.mesh(), other.mesh()),
+            "field {} is on another mesh",
+            other.name()
+        );
+        let product = |a: T, b: U| a.into().double_dot(&b.into());
+        let mesh = self.mesh();
+        let name = format!("({}&&{})", self.name(), other.name());
+        let dimensions = self.dimensions() * other.dimensions();
+        let mut result = match (self.uniform_value(), other.uniform_value()) {
+            (Some(a), Some(b)) => VolField::uniform(mesh, name, dimensions, product(a, b)),
+            _ => {
+                let values = map_indexed(mesh.n_cells(), |i| {
+                    product(self.cell_value(i), other.cell_value(i))
+                });
+                // Safety: one value per cell.
+                VolField::new(mesh, name, dimensions, values).unwrap()
+            }
+        };
+        for ((to, a), b) in result
+            .patch_fields_mut()
+            .iter_mut()
+            .zip(self.patch_fields())
+            .zip(other.patch_fields())
+        {
+            for ((v, &a), &b) in to.values_mut().iter_mut().zip(a.values()).zip(b.values()) {
+                *v = product(a, b);
+            }
+        }
+        result
+    }
+}
+
+impl<'mesh> VolField<'mesh, Tensor> {
+    /// Returns the antisymmetric part `(T − Tᵀ) / 2` of every value, named
+    /// `skew(<name>)`.
+    pub fn skew(&self) -> VolField<'mesh, Tensor> {
+        self.map_values(format!("skew({})", self.name()), |v| v.skew())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_types::tensor::SymmTensor;
+
+    use super::*;
+    use crate::dimensions::Dimensions;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_skew_tr_apply_per_value() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let t = Tensor::new(3.0, 2.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        let field = VolField::new(&mesh, "T", Dimensions::default(), vec![t; 2]).unwrap();
+        let skew = field.skew();
+        assert_eq!(skew.name(), "skew(T)");
+        assert_eq!(skew.internal()[0], t.skew());
+        assert_eq!(skew.patch_fields()[0].values(), &[t.skew()]);
+
+        let tr = field.tr();
+        assert_eq!(tr.name(), "tr(T)");
+        assert_eq!(tr.internal(), &[3.0, 3.0]);
+        assert_eq!(field.symm().tr().internal(), &[3.0, 3.0]);
+    }
+
+    #[test]
+    fn test_double_dot_combines_values_and_dimensions() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let stress = SymmTensor::new(1.0, 2.0, 0.0, 3.0, 0.0, 0.0);
+        let r = VolField::uniform(&mesh, "R", Dimensions::mlt(0, 2, -2), stress);
+        let gradients = vec![
+            Tensor::new(1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0),
+            Tensor::new(0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+        ];
+        let grad_u = VolField::new(&mesh, "grad(U)", Dimensions::mlt(0, 0, -1), gradients).unwrap();
+
+        let production = r.double_dot(&grad_u);
+        assert_eq!(production.name(), "(R&&grad(U))");
+        assert_eq!(production.dimensions(), Dimensions::mlt(0, 2, -3));
+        assert_eq!(production.internal(), &[4.0, 4.0]);
+        assert_eq!(production.patch_field("x-max").unwrap().values(), &[4.0]);
+        assert_eq!(
+            r.double_dot(&r).uniform_value(),
+            Some(stress.double_dot(&stress))
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use std::fmt::Debug;
+
+use dugong_runtime::{Dictionary, Value};
+use dugong_types::FieldValue;
+use dugong_types::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
+
+use crate::error::FieldError;
+
+/// Field value types made of a fixed number of scalar components.
+///
+/// Components are ordered as in OpenFOAM files: `(x y z)` for vectors,
+/// row-major for tensors and `(xx xy xz yy yz zz)` for symmetric tensors.
+pub trait Components: FieldValue + PartialEq + Debug + Send + Sync + 'static {
+    /// The OpenFOAM name of the value type, such as `vector`.
+    const TYPE_NAME: &'static str;
+
+    /// The number of scalar components.
+    const N_COMPONENTS: usize;
+
+    /// Returns component `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= N_COMPONENTS`.
+    fn component(&self, i: usize) -> f64;
+
+    /// Builds a value from its components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `components` is shorter than `N_COMPONENTS`.
+    fn from_components(components: &[f64]) -> Self;
+}
+
+impl Components for f64 {
+    const TYPE_NAME: &'static str = "scalar";
+    const N_COMPONENTS: usize = 1;
+
+    fn component(&self, i: usize) -> f64 {
+        assert_eq!(i, 0, "scalar component index out of range");
+        *self
+    }
+
+    fn from_components(components: &[f64]) -> Self {
+        components[0]
+    }
+}
+
+impl Components for Vector {
+    const TYPE_NAME: &'static str = "vector";
+    const N_COMPONENTS: usize = 3;
+
+    fn component(&self, i: usize) -> f64 {
+        self.as_array()[i]
+    }
+
+    fn from_components(c: &[f64]) -> Self {
+        Vector::new(c[0], c[1], c[2])
+    }
+}
+
+impl Components for Tensor {
+    const TYPE_NAME: &'static str = "tensor";
+    const N_COMPONENTS: usize = 9;
+
+    fn component(&self, i: usize) -> f64 {
+        self.as_array()[i]
+    }
+
+    fn from_components(c: &[f64]) -> Self {
+        Tensor::new(c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7], c[8])
+    }
+}
+
+impl Components for SymmTensor {
+    const TYPE_NAME: &'static str = "symmTensor";
+    const N_COMPONENTS: usize = 6;
+
+    fn component(&self, i: usize) -> f64 {
+        self.as_array()[i]
+    }
+
+    fn from_components(c: &[f64]) -> Self {
+        SymmTensor::new(c[0], c[1], c[2], c[3], c[4], c[5])
+    }
+}
+
+impl Components for SphericalTensor {
+    const TYPE_NAME: &'static str = "sphericalTensor";
+    const N_COMPONENTS: usize = 1;
+
+    fn component(&self, i: usize) -> f64 {
+        assert_eq!(i, 0, "spherical tensor component index out of range");
+        self.value()
+    }
+
+    fn from_components(c: &[f64]) -> Self {
+        SphericalTensor::new(c[0])
+    }
+}
+
+/// Field value types that can be expressed in a rotated or reflected frame.
+pub trait Transform {
+    /// Returns the value transformed by the orthogonal tensor `r`: `r · v`
+    /// for vectors and `r · t · rᵀ` for tensors. Scalars and spherical
+    /// tensors are invariant.
+    fn transform(&self, r: &Tensor) -> Self;
+}
+
+impl Transform for f64 {
+    fn transform(&self, _r: &Tensor) -> Self {
+        *self
+    }
+}
+
+impl Transform for Vector {
+    fn transform(&self, r: &Tensor) -> Self {
+        *r * *self
+    }
+}
+
+impl Transform for Tensor {
+    fn transform(&self, r: &Tensor) -> Self {
+        *r * *self * r.transpose()
+    }
+}
+
+impl Transform for SymmTensor {
+    fn transform(&self, r: &Tensor) -> Self {
+        (*r * Tensor::from(*self) * r.transpose()).symm()
+    }
+}
+
+impl Transform for SphericalTensor {
+    fn transform(&self, _r: &Tensor) -> Self {
+        *self
+    }
+}
+
+/// Converts a value to a number for scalars or a parenthesized list of
+/// components otherwise; the inverse of [`parse_value`].
+pub fn to_value<T: Components>(value: &T) -> Value {
+    if T::N_COMPONENTS == 1 {
+        Value::Scalar(value.component(0))
+    } else {
+        Value::List(
+            (0..T::N_COMPONENTS)
+                .map(|i| Value::Scalar(value.component(i)))
+                .collect(),
+        )
+    }
+}
+
+/// Converts field values to an entry: `uniform <value>` if all values are
+/// equal, `nonuniform List<type> (<values>)` otherwise; the inverse of
+/// [`parse_values`].
+pub fn format_values<T: Components>(values: &[T]) -> Vec<Value> {
+    match values {
+        [first, rest @ ..] if rest.iter().all(|v| v == first) => {
+            vec![Value::Word("uniform".into()), to_value(first)]
+        }
+        _ => vec![
+            Value::Word("nonuniform".into()),
+            Value::Word(format!("List<{}>", T::TYPE_NAME)),
+            Value::List(values.iter().map(to_value).collect()),
+        ],
+    }
+}
+
+/// Parses one value: a number for scalars, or a parenthesized list of
+/// components such as `(1 0 0)`.
+pub fn parse_value<T: Components>(value: &Value) -> Option<T> {
+    let components: Vec<f64> = match value {
+        Value::List(items) => items.iter().map(Value::as_scalar).collect::<Option<_>>()?,
+        _ if T::N_COMPONENTS == 1 => vec![value.as_scalar()?],
+        _ => return None,
+    };
+    (components.len() == T::N_COMPONENTS).then(|| T::from_components(&components))
+}
+
+/// Parses the `n` values of a field entry: `uniform <value>` or
+/// `nonuniform List<type> <n>(<values>)`.
+///
+/// # Errors
+///
+/// Returns [`FieldError::InvalidEntry`] naming `keyword` if the entry is
+/// malformed or a nonuniform list does not have `n` values.
+pub fn parse_values<T: Components>(
+    keyword: &str,
+    values: &[Value],
+    n: usize,
+) -> Result<Vec<T>, FieldError> {
+    let invalid = |reason: String| FieldError::InvalidEntry {
+        keyword: keyword.to_string(),
+        reason,
+    };
+    let word = values.first().and_then(Value::as_word);
+    match (word, values) {
+        (Some("uniform"), [_, v]) => parse_value(v)
+            .map(|v| vec![v; n])
+            .ok_or_else(|| invalid(format!("expected a uniform {}", T::TYPE_NAME))),
+        (Some("nonuniform"), [_, _, Value::List(items)]) => {
+            if items.len() != n {
+                return Err(invalid(format!("expected {n} values, got {}", items.len())));
+            }
+            items
+                .iter()
+                .map(|v| {
+                    parse_value(v).ok_or_else(|| invalid(format!("expected a {}", T::TYPE_NAME)))
+                })
+                .collect()
+        }
+        _ => Err(invalid("expected `uniform` or `nonuniform` values".into())),
+    }
+}
+
+/// Looks up and parses the field entry `keyword` of `dict` with
+/// [`parse_values`].
+///
+/// # Errors
+///
+/// Returns [`FieldError::InvalidEntry`] if the entry is missing or malformed.
+pub fn lookup_values<T: Components>(
+    dict: &Dictionary,
+    keyword: &str,
+    n: usize,
+) -> Result<Vec<T>, FieldError> {
+    let values = dict.get(keyword).ok_or_else(|| FieldError::InvalidEntry {
+        keyword: keyword.to_string(),
+        reason: "missing".into(),
+    })?;
+    parse_values(keyword, values, n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(items: &[f64]) -> Value {
+        Value::List(items.iter().map(|&x| Value::Scalar(x)).collect())
+    }
+
+    #[test]
+    fn test_components_round_trip() {
+        let t = SymmTensor::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+        let c: Vec<f64> = (0..SymmTensor::N_COMPONENTS)
+            .map(|i| t.component(i))
+            .collect();
+        assert_eq!(SymmTensor::from_components(&c), t);
+        assert_eq!(Vector::new(1.0, 2.0, 3.0).component(2), 3.0);
+    }
+
+    #[test]
+    fn test_parse_values_reads_uniform_and_nonuniform() {
+        let uniform = [Value::Word("uniform".into()), list(&[1.0, 0.0, 0.0])];
+        let v: Vec<Vector> = parse_values("value", &uniform, 2).unwrap();
+        assert_eq!(v, vec![Vector::new(1.0, 0.0, 0.0); 2]);
+
+        let nonuniform = [
+            Value::Word("nonuniform".into()),
+            Value::Word("List<scalar>".into()),
+            Value::List(vec![Value::Label(1), Value::Scalar(2.5)]),
+        ];
+        let s: Vec<f64> = parse_values("value", &nonuniform, 2).unwrap();
+        assert_eq!(s, vec![1.0, 2.5]);
+        assert!(matches!(
+            parse_values::<f64>("value", &nonuniform, 3),
+            Err(FieldError::InvalidEntry { .. })
+        ));
+        assert!(parse_values::<Vector>("value", &uniform[..1], 2).is_err());
+    }
+
+    #[test]
+    fn test_format_values_round_trips() {
+        let uniform = format_values(&[Vector::new(1.0, 0.0, 0.0); 3]);
+        assert_eq!(uniform[0], Value::Word("uniform".into()));
+        let v: Vec<Vector> = parse_values("value", &uniform, 3).unwrap();
+        assert_eq!(v, vec![Vector::new(1.0, 0.0, 0.0); 3]);
+
+        let nonuniform = format_values(&[1.0, 2.5]);
+        assert_eq!(nonuniform[1], Value::Word("List<scalar>".into()));
+        assert_eq!(
+            parse_values::<f64>("value", &nonuniform, 2).unwrap(),
+            vec![1.0, 2.5]
+        );
+        assert_eq!(
+            format_values::<f64>(&[])[0],
+            Value::Word("nonuniform".into())
+        );
+    }
+
+    #[test]
+    fn test_transform_reflects_vectors_and_tensors() {
+        // Reflection through the plane x = 0.
+        let r = Tensor::identity() - Vector::new(1.0, 0.0, 0.0).outer(&Vector::new(2.0, 0.0, 0.0));
+        let v = Vector::new(1.0, 2.0, 3.0);
+        assert_eq!(v.transform(&r), Vector::new(-1.0, 2.0, 3.0));
+        let s = SymmTensor::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+        assert_eq!(
+            s.transform(&r),
+            SymmTensor::new(1.0, -2.0, -3.0, 4.0, 5.0, 6.0)
+        );
+        assert_eq!(2.0.transform(&r), 2.0);
+    }
+}
@@ -0,0 +1,567 @@
+use dugong_mesh::Mesh;
+use dugong_runtime::Dictionary;
+use dugong_types::FieldValue;
+
+use crate::boundary::{
+    BoundaryCondition, BoundaryValue, FlowFields, PatchContext, new_boundary_condition,
+};
+use crate::boundary_conditions::Calculated;
+use crate::cell_values::CellValues;
+use crate::dimensions::Dimensions;
+use crate::error::FieldError;
+use crate::expr::FieldExpr;
+use crate::parallel::update_indexed;
+use crate::patch_field::PatchField;
+
+/// A cell-centered field on a mesh: one value per cell plus one value per
+/// boundary face, grouped by patch.
+///
+/// The field borrows its mesh, so it cannot outlive it. Boundary values are
+/// kept in one [`PatchField`] per mesh patch, in patch order, each with its
+/// [`BoundaryCondition`]. New fields start with [`Calculated`] conditions.
+#[derive(Clone)]
+pub struct VolField<'mesh, T: FieldValue> {
+    mesh: &'mesh Mesh,
+    name: String,
+    dimensions: Dimensions,
+    internal: CellValues<T>,
+    boundary: Vec<PatchField<T>>,
+    previous_iteration: Option<Vec<T>>,
+    old_time: Option<Box<VolField<'mesh, T>>>,
+}
+
+impl<'mesh, T: FieldValue> VolField<'mesh, T> {
+    /// Creates a field with `value` in every cell and on every boundary face.
+    pub fn uniform(
+        mesh: &'mesh Mesh,
+        name: impl Into<String>,
+        dimensions: Dimensions,
+        value: T,
+    ) -> Self {
+        let boundary = mesh
+            .patches()
+            .iter()
+            .enumerate()
+            .map(|(i, patch)| PatchField::new(i, vec![value; patch.size()], Box::new(Calculated)))
+            .collect();
+        Self {
+            mesh,
+            name: name.into(),
+            dimensions,
+            internal: CellValues::uniform(value, mesh.n_cells()),
+            boundary,
+            previous_iteration: None,
+            old_time: None,
+        }
+    }
+
+    /// Creates a field from per-cell values. Each boundary face takes the
+    /// value of its owner cell.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::SizeMismatch`] if `internal` does not have one
+    /// value per cell.
+    pub fn new(
+        mesh: &'mesh Mesh,
+        name: impl Into<String>,
+        dimensions: Dimensions,
+        internal: Vec<T>,
+    ) -> Result<Self, FieldError> {
+        let name = name.into();
+        if internal.len() != mesh.n_cells() {
+            return Err(FieldError::SizeMismatch {
+                field: name,
+                expected: mesh.n_cells(),
+                got: internal.len(),
+            });
+        }
+        let boundary = mesh
+            .patches()
+            .iter()
+            .enumerate()
+            .map(|(i, patch)| {
+                let values = patch.range().map(|f| internal[mesh.owner()[f]]).collect();
+                PatchField::new(i, values, Box::new(Calculated))
+            })
+            .collect();
+        Ok(Self {
+            mesh,
+            name,
+            dimensions,
+            internal: CellValues::Values(internal),
+            boundary,
+            previous_iteration: None,
+            old_time: None,
+        })
+    }
+
+    /// Returns the mesh the field is defined on.
+    pub fn mesh(&self) -> &'mesh Mesh {
+        self.mesh
+    }
+
+    /// Returns the field name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the physical dimensions of the values.
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    /// Returns the cell values.
+    ///
+    /// Uniform fields expand a cached copy on first access; prefer
+    /// [`cell_value`](Self::cell_value) where a slice is not needed.
+    pub fn internal(&self) -> &[T] {
+        self.internal.as_slice()
+    }
+
+    /// Returns the cell values for modification.
+    ///
+    /// Uniform fields are expanded to one value per cell first.
+    pub fn internal_mut(&mut self) -> &mut [T] {
+        self.internal.as_mut_slice()
+    }
+
+    /// Returns the value of cell `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not a cell index.
+    pub fn cell_value(&self, i: usize) -> T {
+        self.internal.get(i)
+    }
+
+    /// Returns `true` if the cell values are stored as a single value.
+    ///
+    /// Fields created with [`uniform`](Self::uniform) or
+    /// [`set_uniform`](Self::set_uniform) keep this storage until their
+    /// cell values are written.
+    pub fn is_uniform(&self) -> bool {
+        self.internal.uniform_value().is_some()
+    }
+
+    /// Returns the value of every cell if the field is stored as uniform.
+    pub fn uniform_value(&self) -> Option<T> {
+        self.internal.uniform_value()
+    }
+
+    /// Sets every cell to `value`, storing it once, and re-evaluates the
+    /// boundary conditions.
+    pub fn set_uniform(&mut self, value: T) {
+        self.internal = CellValues::uniform(value, self.mesh.n_cells());
+        self.evaluate_boundaries();
+    }
+
+    /// Sets the cell values from an expression, computed in one pass, and
+    /// re-evaluates the boundary conditions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::DimensionMismatch`] if the expression's
+    /// dimensions are inconsistent or differ from the field's; the field is
+    /// then left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the expression does not have one value per cell.
+    pub fn assign(&mut self, expr: impl FieldExpr<Value = T>) -> Result<(), FieldError> {
+        expr.dimensions()?
+            .check(self.dimensions, &format!("assignment to {}", self.name))?;
+        assert_eq!(
+            expr.len(),
+            self.internal.len(),
+            "expression length differs from cell count"
+        );
+        update_indexed(self.internal.as_mut_slice(), |i, _| expr.at(i));
+        self.evaluate_boundaries();
+        Ok(())
+    }
+
+    /// Stores the cell values as the previous iteration, for [`relax`](Self::relax).
+    pub fn store_prev_iter(&mut self) {
+        self.previous_iteration = Some(self.internal.to_slice().into_owned());
+    }
+
+    /// Returns the cell values stored by [`store_prev_iter`](Self::store_prev_iter).
+    pub fn prev_iter(&self) -> Option<&[T]> {
+        self.previous_iteration.as_deref()
+    }
+
+    /// Under-relaxes the cell values towards the previous iteration,
+    /// `φ ← φ_prev + α (φ − φ_prev)`, and re-evaluates the boundary
+    /// conditions.
+    ///
+    /// Does nothing if no previous iteration is stored.
+    pub fn relax(&mut self, alpha: f64) {
+        let Some(previous) = &self.previous_iteration else {
+            return;
+        };
+        update_indexed(self.internal.as_mut_slice(), |i, v| {
+            previous[i] + (v - previous[i]) * alpha
+        });
+        self.evaluate_boundaries();
+    }
+
+    /// Stores the current values as the old-time level at the start of a
+    /// time step. The previous old-time level becomes the old-old-time
+    /// level, and older levels are dropped.
+    ///
+    /// The levels are named `<name>_0` and `<name>_0_0`.
+    pub fn store_old_time(&mut self) {
+        let old_old = self.old_time.take().map(|mut level| {
+            level.old_time = None;
+            level.name = format!("{}_0_0", self.name);
+            level
+        });
+        let mut old = self.clone();
+        old.name = format!("{}_0", self.name);
+        old.previous_iteration = None;
+        old.old_time = old_old;
+        self.old_time = Some(Box::new(old));
+    }
+
+    /// Returns the number of stored old-time levels: 0, 1 or 2.
+    pub fn n_old_times(&self) -> usize {
+        match &self.old_time {
+            None => 0,
+            Some(old) => 1 + old.n_old_times(),
+        }
+    }
+
+    /// Returns the old-time level, or the field itself if none is stored,
+    /// so that time derivatives vanish before the first time step.
+    pub fn old_time(&self) -> &Self {
+        self.old_time.as_deref().unwrap_or(self)
+    }
+
+    /// Returns the old-old-time level, falling back to
+    /// [`old_time`](Self::old_time) if fewer than two levels are stored.
+    pub fn old_old_time(&self) -> &Self {
+        self.old_time().old_time()
+    }
+
+    /// Returns the stored old-time level for modification, such as when
+    /// restarting from files.
+    pub fn old_time_mut(&mut self) -> Option<&mut Self> {
+        self.old_time.as_deref_mut()
+    }
+
+    /// Returns the boundary values of every patch, in mesh patch order.
+    pub fn patch_fields(&self) -> &[PatchField<T>] {
+        &self.boundary
+    }
+
+    /// Returns the boundary values of every patch for modification.
+    pub fn patch_fields_mut(&mut self) -> &mut [PatchField<T>] {
+        &mut self.boundary
+    }
+
+    /// Returns the cell values and the boundary values of every patch,
+    /// borrowed separately.
+    pub(crate) fn internal_and_patch_fields_mut(&mut self) -> (&[T], &mut [PatchField<T>]) {
+        (self.internal.as_slice(), &mut self.boundary)
+    }
+
+    /// Returns the boundary values of the named patch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::PatchNotFound`] for unknown patch names.
+    pub fn patch_field(&self, name: &str) -> Result<&PatchField<T>, FieldError> {
+        self.find_patch(name).map(|i| &self.boundary[i])
+    }
+
+    /// Returns the context of patch index `patch` for its boundary
+    /// condition.
+    pub fn patch_context(&self, patch: usize) -> PatchContext<'mesh> {
+        PatchContext::new(self.mesh, patch)
+    }
+
+    /// Replaces the boundary condition of the named patch and evaluates it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::PatchNotFound`] for unknown patch names.
+    pub fn set_boundary_condition(
+        &mut self,
+        patch: &str,
+        condition: Box<dyn BoundaryCondition<T>>,
+    ) -> Result<(), FieldError> {
+        let i = self.find_patch(patch)?;
+        self.boundary[i].set_condition(condition);
+        self.evaluate_patch(i);
+        Ok(())
+    }
+
+    /// Sets the boundary condition of the named patch from its case-file
+    /// dictionary, selected by the `type` entry.
+    ///
+    /// A `value` entry, if present, initializes the face values before the
+    /// condition is evaluated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FieldError::PatchNotFound`] for unknown patch names, and
+    /// the errors of [`new_boundary_condition`] and of parsing `value`.
+    pub fn set_boundary_condition_from_dict(
+        &mut self,
+        patch: &str,
+        dict: &Dictionary,
+    ) -> Result<(), FieldError>
+    where
+        T: BoundaryValue,
+    {
+        let i = self.find_patch(patch)?;
+        let context = self.patch_context(i);
+        let condition = new_boundary_condition(&context, dict)?;
+        if dict.contains("value") {
+            let values = crate::value::lookup_values(dict, "value", context.size())?;
+            self.boundary[i].values_mut().copy_from_slice(&values);
+        }
+        self.boundary[i].set_condition(condition);
+        self.evaluate_patch(i);
+        Ok(())
+    }
+
+    /// Recomputes the boundary values of every patch from the cell values.
+    pub fn evaluate_boundaries(&mut self) {
+        self.evaluate_boundaries_with(FlowFields::default());
+    }
+
+    /// Recomputes the boundary values of every patch from the cell values,
+    /// giving flux-aware conditions the `flow` fields.
+    pub fn evaluate_boundaries_with(&mut self, flow: FlowFields<'_>) {
+        let internal = self.internal.to_slice();
+        for i in 0..self.boundary.len() {
+            let context = PatchContext::new(self.mesh, i).with_flow(flow);
+            let (condition, values) = self.boundary[i].split_mut();
+            condition.evaluate(&context, &internal, values);
+        }
+    }
+
+    /// Lets every boundary condition update its time-dependent data for
+    /// `time`, then evaluates the boundaries.
+    pub fn update_boundaries(&mut self, time: f64) {
+        for i in 0..self.boundary.len() {
+            let context = self.patch_context(i);
+            self.boundary[i].condition_mut().update(&context, time);
+        }
+        self.evaluate_boundaries();
+    }
+
+    fn evaluate_patch(&mut self, i: usize) {
+        let context = PatchContext::new(self.mesh, i);
+        let (condition, values) = self.boundary[i].split_mut();
+        condition.evaluate(&context, &self.internal.to_slice(), values);
+    }
+
+    pub(crate) fn find_patch(&self, name: &str) -> Result<usize, FieldError> {
+        self.mesh
+            .patch_index(name)
+            .ok_or_else(|| FieldError::PatchNotFound {
+                field: self.name.clone(),
+                patch: name.to_string(),
+            })
+    }
+
+    /// Returns the value on boundary face `face`, given as a mesh face index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `face` is not a boundary face.
+    pub fn boundary_value(&self, face: usize) -> T {
+        let patch = self
+            .mesh
+            .which_patch(face)
+            .unwrap_or_else(|| panic!("face {face} is not a boundary face"));
+        let start = self.mesh.patches()[patch].start();
+        self.boundary[patch].values()[face - start]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_runtime::Value;
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+    use crate::boundary::{
+        Coefficients, fixed_value_coefficients, fixed_value_gradient_coefficients,
+    };
+    use crate::boundary_conditions::ZeroGradient;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_vol_field_uniform_fills_cells_and_patches() {
+        let mesh = box_mesh([2, 2, 1], [1.0, 1.0, 1.0]);
+        let u = Vector::new(1.0, 0.0, 0.0);
+        let field = VolField::uniform(&mesh, "U", Dimensions::mlt(0, 1, -1), u);
+        assert_eq!(field.name(), "U");
+        assert_eq!(field.dimensions(), Dimensions::mlt(0, 1, -1));
+        assert_eq!(field.internal(), &[u; 4]);
+        assert_eq!(field.patch_fields().len(), mesh.patches().len());
+        for (pf, patch) in field.patch_fields().iter().zip(mesh.patches()) {
+            assert_eq!(pf.len(), patch.size());
+            assert!(pf.values().iter().all(|&v| v == u));
+        }
+        assert_eq!(field.patch_field("x-min").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_vol_field_new_copies_owner_values_to_boundary() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let field = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(field.patch_field("x-min").unwrap().values(), &[1.0]);
+        assert_eq!(field.patch_field("x-max").unwrap().values(), &[3.0]);
+        let x_max = mesh.patch("x-max").unwrap().start();
+        assert_eq!(field.boundary_value(x_max), 3.0);
+        assert!(matches!(
+            field.patch_field("inlet"),
+            Err(FieldError::PatchNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_vol_field_store_old_time_rotates_levels() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut field = VolField::new(&mesh, "T", Dimensions::default(), vec![1.0, 2.0]).unwrap();
+        assert_eq!(field.n_old_times(), 0);
+        assert_eq!(field.old_time().internal(), &[1.0, 2.0]);
+        assert_eq!(field.old_old_time().name(), "T");
+
+        for values in [[3.0, 4.0], [5.0, 6.0], [7.0, 8.0]] {
+            field.store_old_time();
+            field.internal_mut().copy_from_slice(&values);
+        }
+        assert_eq!(field.n_old_times(), 2);
+        assert_eq!(field.internal(), &[7.0, 8.0]);
+        assert_eq!(field.old_time().name(), "T_0");
+        assert_eq!(field.old_time().internal(), &[5.0, 6.0]);
+        assert_eq!(field.old_old_time().name(), "T_0_0");
+        assert_eq!(field.old_old_time().internal(), &[3.0, 4.0]);
+        assert_eq!(
+            field.old_time().patch_field("x-max").unwrap().values(),
+            &[2.0]
+        );
+    }
+
+    #[test]
+    fn test_vol_field_uniform_storage_expands_on_write() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let mut field = VolField::uniform(&mesh, "nu", Dimensions::default(), 1e-5);
+        assert_eq!(field.uniform_value(), Some(1e-5));
+        assert_eq!(field.cell_value(2), 1e-5);
+        assert_eq!(field.internal(), &[1e-5; 3]);
+        assert!(field.is_uniform());
+
+        field.internal_mut()[0] = 2e-5;
+        assert!(!field.is_uniform());
+        assert_eq!(field.internal(), &[2e-5, 1e-5, 1e-5]);
+
+        field
+            .set_boundary_condition("x-min", Box::new(ZeroGradient))
+            .unwrap();
+        field.set_uniform(3.0);
+        assert!(field.is_uniform());
+        assert_eq!(field.cell_value(1), 3.0);
+        assert_eq!(field.patch_field("x-min").unwrap().values(), &[3.0]);
+    }
+
+    #[test]
+    fn test_vol_field_new_rejects_wrong_length() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        assert!(matches!(
+            VolField::new(&mesh, "T", Dimensions::default(), vec![1.0; 2]),
+            Err(FieldError::SizeMismatch {
+                expected: 3,
+                got: 2,
+                ..
+            })
+        ));
+    }
+
+    /// Counts updates and sets the face values to the last update time.
+    #[derive(Debug, Clone, Default)]
+    struct Clock {
+        time: f64,
+    }
+
+    impl BoundaryCondition<f64> for Clock {
+        fn type_name(&self) -> &'static str {
+            "clock"
+        }
+
+        fn evaluate(&mut self, _patch: &PatchContext<'_>, _internal: &[f64], values: &mut [f64]) {
+            values.fill(self.time);
+        }
+
+        fn value_coefficients(
+            &self,
+            _patch: &PatchContext<'_>,
+            _internal: &[f64],
+            values: &[f64],
+        ) -> Coefficients<f64> {
+            fixed_value_coefficients(values)
+        }
+
+        fn gradient_coefficients(
+            &self,
+            patch: &PatchContext<'_>,
+            _internal: &[f64],
+            values: &[f64],
+        ) -> Coefficients<f64> {
+            fixed_value_gradient_coefficients(patch, values)
+        }
+
+        fn update(&mut self, _patch: &PatchContext<'_>, time: f64) {
+            self.time = time;
+        }
+
+        fn clone_box(&self) -> Box<dyn BoundaryCondition<f64>> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_vol_field_updates_and_evaluates_boundary_conditions() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut field = VolField::uniform(&mesh, "T", Dimensions::default(), 1.0);
+        field
+            .set_boundary_condition("x-min", Box::new(Clock::default()))
+            .unwrap();
+        assert_eq!(field.patch_field("x-min").unwrap().values(), &[0.0]);
+        field.update_boundaries(2.5);
+        assert_eq!(field.patch_field("x-min").unwrap().values(), &[2.5]);
+        assert_eq!(field.patch_field("x-max").unwrap().values(), &[1.0]);
+        // Clones carry their own copy of the condition.
+        let copy = field.clone();
+        field.update_boundaries(3.0);
+        assert_eq!(copy.patch_field("x-min").unwrap().values(), &[2.5]);
+        assert!(matches!(
+            field.set_boundary_condition("inlet", Box::new(Calculated)),
+            Err(FieldError::PatchNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_vol_field_sets_boundary_condition_from_dict() {
+        let mesh = box_mesh([2, 2, 1], [2.0, 2.0, 1.0]);
+        let mut field = VolField::uniform(&mesh, "T", Dimensions::default(), 1.0);
+        let mut dict = Dictionary::new();
+        dict.insert("type", vec![Value::Word("calculated".into())]);
+        dict.insert(
+            "value",
+            vec![Value::Word("uniform".into()), Value::Scalar(4.0)],
+        );
+        field
+            .set_boundary_condition_from_dict("y-max", &dict)
+            .unwrap();
+        let patch = field.patch_field("y-max").unwrap();
+        assert_eq!(patch.condition().type_name(), "calculated");
+        assert_eq!(patch.values(), &[4.0, 4.0]);
+    }
+}
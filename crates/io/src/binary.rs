@@ -0,0 +1,438 @@
+//! Binary mesh container read in place.
+//!
+//! The container stores the point and connectivity arrays as fixed-width
+//! little-endian values, so a [`BinaryMesh`] view over the file bytes (read
+//! into memory or memory-mapped) answers point, face, owner and neighbour
+//! queries without deserializing the arrays. Patches and zones are small and
+//! stored as OpenFOAM-format text.
+//!
+//! The header records the format version and the [`Mesh::fingerprint`] of
+//! the stored mesh, which [`BinaryMesh::to_mesh`] checks on reload, so the
+//! container doubles as a restart snapshot.
+//!
+//! Layout, all integers `u64` little-endian:
+//!
+//! | Section          | Contents                                                  |
+//! |------------------|-----------------------------------------------------------|
+//! | magic            | `b"DUGMESH\0"`                                            |
+//! | version          | [`VERSION`]                                               |
+//! | fingerprint      | [`Mesh::fingerprint`] of the stored mesh                  |
+//! | counts           | points, faces, internal faces, face vertices              |
+//! | text lengths     | boundary, cell zones, face zones, point zones (bytes)     |
+//! | points           | `3 * points` `f64` components                             |
+//! | face offsets     | `faces + 1` offsets into the face vertices                |
+//! | face vertices    | `face vertices` point labels                              |
+//! | owner            | `faces` cell labels                                       |
+//! | neighbour        | `internal faces` cell labels                              |
+//! | texts            | boundary, cell zones, face zones, point zones (UTF-8)     |
+
+use std::fs;
+use std::path::Path;
+
+use dugong_mesh::{Mesh, Patch, PrimitiveMesh, ZoneKind};
+use dugong_types::tensor::Vector;
+
+use crate::error::IoError;
+use crate::polymesh::{format_patches, format_zones, invalid, parse_patches, parse_zones};
+
+/// Leading bytes identifying the container.
+pub const MAGIC: &[u8; 8] = b"DUGMESH\0";
+
+/// Format version written by [`encode_binary_mesh`]; the only version read.
+pub const VERSION: u64 = 1;
+
+const WORD: usize = 8;
+/// Header words after the magic: version, fingerprint and eight counts.
+const N_HEADER_WORDS: usize = 10;
+const HEADER_LEN: usize = MAGIC.len() + N_HEADER_WORDS * WORD;
+const ZONE_KINDS: [ZoneKind; 3] = [ZoneKind::Cell, ZoneKind::Face, ZoneKind::Point];
+
+/// A read-only view of a binary mesh container.
+///
+/// Construction validates the header and section sizes only; array entries
+/// are decoded on access, directly from the borrowed bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct BinaryMesh<'a> {
+    fingerprint: u64,
+    n_points: usize,
+    n_faces: usize,
+    n_internal_faces: usize,
+    points: &'a [u8],
+    face_offsets: &'a [u8],
+    face_vertices: &'a [u8],
+    owner: &'a [u8],
+    neighbor: &'a [u8],
+    texts: [&'a str; 4],
+}
+
+impl<'a> BinaryMesh<'a> {
+    /// Creates a view over the bytes of a binary mesh container.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IoError::InvalidData`] if the magic bytes do not match, the
+    /// version is not [`VERSION`], the data is truncated, the face offsets
+    /// are out of range, or the text sections are not UTF-8.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, IoError> {
+        if bytes.len() < HEADER_LEN || &bytes[..MAGIC.len()] != MAGIC {
+            return Err(invalid("binary mesh", "missing DUGMESH header"));
+        }
+        let word = |i: usize| read_u64(bytes, MAGIC.len() + i * WORD);
+        let version = word(0);
+        if version != VERSION {
+            return Err(invalid(
+                "binary mesh",
+                format!("unsupported version {version}, expected {VERSION}"),
+            ));
+        }
+        let fingerprint = word(1);
+        let count = |i: usize| -> Result<usize, IoError> {
+            usize::try_from(word(i + 2))
+                .map_err(|_| invalid("binary mesh", "count does not fit in memory"))
+        };
+        let [n_points, n_faces, n_internal_faces, n_face_vertices] =
+            [count(0)?, count(1)?, count(2)?, count(3)?];
+        let text_lens = [count(4)?, count(5)?, count(6)?, count(7)?];
+        if n_internal_faces > n_faces {
+            return Err(invalid(
+                "binary mesh",
+                format!("{n_internal_faces} internal faces exceed {n_faces} faces"),
+            ));
+        }
+
+        let mut rest = &bytes[HEADER_LEN..];
+        let mut take = |len: Option<usize>, what: &str| -> Result<&'a [u8], IoError> {
+            match len {
+                Some(len) if len <= rest.len() => {
+                    let (head, tail) = rest.split_at(len);
+                    rest = tail;
+                    Ok(head)
+                }
+                _ => Err(invalid("binary mesh", format!("truncated {what} section"))),
+            }
+        };
+        let words = |n: usize, k: usize| n.checked_mul(k).and_then(|n| n.checked_mul(WORD));
+        let points = take(words(n_points, 3), "points")?;
+        let face_offsets = take(words(n_faces.saturating_add(1), 1), "face offset")?;
+        let face_vertices = take(words(n_face_vertices, 1), "face vertex")?;
+        let owner = take(words(n_faces, 1), "owner")?;
+        let neighbor = take(words(n_internal_faces, 1), "neighbour")?;
+        let mut texts = [""; 4];
+        for (text, len) in texts.iter_mut().zip(text_lens) {
+            *text = std::str::from_utf8(take(Some(len), "text")?)
+                .map_err(|_| invalid("binary mesh", "text section is not UTF-8"))?;
+        }
+
+        let mesh = Self {
+            fingerprint,
+            n_points,
+            n_faces,
+            n_internal_faces,
+            points,
+            face_offsets,
+            face_vertices,
+            owner,
+            neighbor,
+            texts,
+        };
+        let mut previous = 0;
+        for f in 0..=n_faces {
+            let offset = mesh.face_offset(f);
+            if offset < previous || offset > n_face_vertices || (f == 0 && offset != 0) {
+                return Err(invalid(
+                    "binary mesh",
+                    format!("invalid face offset {offset}"),
+                ));
+            }
+            previous = offset;
+        }
+        if previous != n_face_vertices {
+            return Err(invalid(
+                "binary mesh",
+                "face offsets do not cover the face vertices",
+            ));
+        }
+        Ok(mesh)
+    }
+
+    /// Returns the fingerprint recorded for the stored mesh.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Returns the number of points.
+    pub fn n_points(&self) -> usize {
+        self.n_points
+    }
+
+    /// Returns the number of faces.
+    pub fn n_faces(&self) -> usize {
+        self.n_faces
+    }
+
+    /// Returns the number of internal faces.
+    pub fn n_internal_faces(&self) -> usize {
+        self.n_internal_faces
+    }
+
+    /// Returns point `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= self.n_points()`.
+    pub fn point(&self, i: usize) -> Vector {
+        assert!(i < self.n_points, "point {i} out of range");
+        let at = 3 * i * WORD;
+        let c = |k: usize| f64::from_bits(read_u64(self.points, at + k * WORD));
+        Vector::new(c(0), c(1), c(2))
+    }
+
+    /// Returns the point labels of face `f`, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f >= self.n_faces()`.
+    pub fn face(&self, f: usize) -> impl ExactSizeIterator<Item = usize> + 'a {
+        assert!(f < self.n_faces, "face {f} out of range");
+        let (start, end) = (self.face_offset(f), self.face_offset(f + 1));
+        let vertices = self.face_vertices;
+        (start..end).map(move |i| read_label(vertices, i))
+    }
+
+    /// Returns the owner cell of face `f`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f >= self.n_faces()`.
+    pub fn owner(&self, f: usize) -> usize {
+        assert!(f < self.n_faces, "face {f} out of range");
+        read_label(self.owner, f)
+    }
+
+    /// Returns the neighbour cell of internal face `f`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f >= self.n_internal_faces()`.
+    pub fn neighbor(&self, f: usize) -> usize {
+        assert!(f < self.n_internal_faces, "face {f} is not internal");
+        read_label(self.neighbor, f)
+    }
+
+    /// Parses the boundary patches.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the boundary text is malformed.
+    pub fn patches(&self) -> Result<Vec<Patch>, IoError> {
+        parse_patches(self.texts[0])
+    }
+
+    /// Copies the view into an owned [`Mesh`], with its patches and zones.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if a text section is malformed, the topology is
+    /// invalid, or the mesh does not match the recorded fingerprint.
+    pub fn to_mesh(&self) -> Result<Mesh, IoError> {
+        let points = (0..self.n_points).map(|i| self.point(i)).collect();
+        let faces = (0..self.n_faces).map(|f| self.face(f).collect()).collect();
+        let owner = (0..self.n_faces).map(|f| self.owner(f)).collect();
+        let neighbor = (0..self.n_internal_faces)
+            .map(|f| self.neighbor(f))
+            .collect();
+        let primitive = PrimitiveMesh::new(points, faces, owner, neighbor)?;
+        let mut mesh = Mesh::new(primitive, self.patches()?)?;
+        for (kind, text) in ZONE_KINDS.into_iter().zip(&self.texts[1..]) {
+            if text.is_empty() {
+                continue;
+            }
+            for zone in parse_zones(text, kind)? {
+                mesh.add_zone(kind, zone)?;
+            }
+        }
+        if mesh.fingerprint() != self.fingerprint {
+            return Err(invalid(
+                "binary mesh",
+                "mesh does not match the recorded fingerprint",
+            ));
+        }
+        Ok(mesh)
+    }
+
+    fn face_offset(&self, f: usize) -> usize {
+        read_label(self.face_offsets, f)
+    }
+}
+
+pub(crate) fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    let mut word = [0; WORD];
+    word.copy_from_slice(&bytes[at..at + WORD]);
+    u64::from_le_bytes(word)
+}
+
+fn read_label(bytes: &[u8], i: usize) -> usize {
+    read_u64(bytes, i * WORD) as usize
+}
+
+/// Encodes a mesh, with its patches and zones, as a binary mesh container.
+pub fn encode_binary_mesh(mesh: &Mesh) -> Vec<u8> {
+    let n_face_vertices: usize = mesh.faces().iter().map(Vec::len).sum();
+    let mut texts = vec![format_patches(mesh.patches())];
+    for kind in ZONE_KINDS {
+        let zones = mesh.zones(kind);
+        texts.push(if zones.is_empty() {
+            String::new()
+        } else {
+            format_zones(zones, kind)
+        });
+    }
+
+    let mut bytes = Vec::with_capacity(
+        HEADER_LEN
+            + WORD
+                * (3 * mesh.n_points()
+                    + 2 * mesh.faces().len()
+                    + 1
+                    + n_face_vertices
+                    + mesh.neighbor().len())
+            + texts.iter().map(String::len).sum::<usize>(),
+    );
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&mesh.fingerprint().to_le_bytes());
+    let put = |bytes: &mut Vec<u8>, n: usize| bytes.extend_from_slice(&(n as u64).to_le_bytes());
+    for n in [
+        mesh.n_points(),
+        mesh.faces().len(),
+        mesh.neighbor().len(),
+        n_face_vertices,
+    ] {
+        put(&mut bytes, n);
+    }
+    for text in &texts {
+        put(&mut bytes, text.len());
+    }
+    for p in mesh.points() {
+        for c in p.as_array() {
+            bytes.extend_from_slice(&c.to_le_bytes());
+        }
+    }
+    let mut offset = 0;
+    put(&mut bytes, offset);
+    for face in mesh.faces() {
+        offset += face.len();
+        put(&mut bytes, offset);
+    }
+    for &v in mesh.faces().iter().flatten() {
+        put(&mut bytes, v);
+    }
+    for &c in mesh.owner().iter().chain(mesh.neighbor()) {
+        put(&mut bytes, c);
+    }
+    for text in &texts {
+        bytes.extend_from_slice(text.as_bytes());
+    }
+    bytes
+}
+
+/// Writes a mesh to a binary mesh container file.
+///
+/// # Errors
+///
+/// Returns `Err` if the file cannot be written.
+pub fn write_binary_mesh(mesh: &Mesh, path: &Path) -> Result<(), IoError> {
+    fs::write(path, encode_binary_mesh(mesh)).map_err(|source| IoError::File {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Reads a binary mesh container file into an owned [`Mesh`].
+///
+/// To query the arrays without copying them, read or map the file and use
+/// [`BinaryMesh::parse`].
+///
+/// # Errors
+///
+/// Returns `Err` if the file cannot be read or is not a valid container.
+pub fn read_binary_mesh(path: &Path) -> Result<Mesh, IoError> {
+    let bytes = fs::read(path).map_err(|source| IoError::File {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    BinaryMesh::parse(&bytes)?.to_mesh()
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_mesh::Zone;
+
+    use super::*;
+    use crate::polymesh::tests::{temp_dir, two_cell_mesh};
+
+    #[test]
+    fn test_binary_mesh_round_trip_preserves_mesh() {
+        let mut mesh = two_cell_mesh();
+        mesh.add_zone(ZoneKind::Cell, Zone::new("left", [0]))
+            .unwrap();
+        mesh.add_zone(ZoneKind::Face, Zone::new("middle", [0]))
+            .unwrap();
+        let path = temp_dir("binary-round-trip").join("mesh.dmesh");
+        write_binary_mesh(&mesh, &path).unwrap();
+        let read = read_binary_mesh(&path).unwrap();
+
+        assert_eq!(read.points(), mesh.points());
+        assert_eq!(read.faces(), mesh.faces());
+        assert_eq!(read.owner(), mesh.owner());
+        assert_eq!(read.neighbor(), mesh.neighbor());
+        assert_eq!(read.patches(), mesh.patches());
+        assert_eq!(read.cell_zones(), mesh.cell_zones());
+        assert_eq!(read.face_zones(), mesh.face_zones());
+        assert!(read.point_zones().is_empty());
+    }
+
+    #[test]
+    fn test_binary_mesh_view_reads_in_place() {
+        let mesh = two_cell_mesh();
+        let bytes = encode_binary_mesh(&mesh);
+        let view = BinaryMesh::parse(&bytes).unwrap();
+        assert_eq!(view.fingerprint(), mesh.fingerprint());
+        assert_eq!(view.n_points(), 12);
+        assert_eq!((view.n_faces(), view.n_internal_faces()), (11, 1));
+        assert_eq!(view.point(9), mesh.points()[9]);
+        assert_eq!(view.face(2).collect::<Vec<_>>(), mesh.faces()[2]);
+        assert_eq!(view.owner(7), 1);
+        assert_eq!(view.neighbor(0), 1);
+        assert_eq!(view.patches().unwrap(), mesh.patches());
+    }
+
+    #[test]
+    fn test_binary_mesh_rejects_truncated_data() {
+        let bytes = encode_binary_mesh(&two_cell_mesh());
+        assert!(matches!(
+            BinaryMesh::parse(&bytes[..bytes.len() - 1]),
+            Err(IoError::InvalidData { .. })
+        ));
+        assert!(matches!(
+            BinaryMesh::parse(b"not a mesh"),
+            Err(IoError::InvalidData { .. })
+        ));
+    }
+
+    #[test]
+    fn test_binary_mesh_rejects_version_and_fingerprint_mismatch() {
+        let mut bytes = encode_binary_mesh(&two_cell_mesh());
+        let mut other_version = bytes.clone();
+        other_version[MAGIC.len()] = 2;
+        assert!(matches!(
+            BinaryMesh::parse(&other_version),
+            Err(IoError::InvalidData { .. })
+        ));
+
+        // Corrupt the first point coordinate.
+        bytes[HEADER_LEN] ^= 1;
+        let view = BinaryMesh::parse(&bytes).unwrap();
+        assert!(matches!(view.to_mesh(), Err(IoError::InvalidData { .. })));
+    }
+}
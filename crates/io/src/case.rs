@@ -0,0 +1,266 @@
+//! The OpenFOAM case directory layout.
+//!
+//! A case holds `constant/` (the mesh in `constant/polyMesh` and physical
+//! properties), `system/` (run controls and schemes) and one directory per
+//! written time, named by the time value (`0`, `0.5`, `1e-05`).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use dugong_mesh::Mesh;
+use dugong_runtime::Dictionary;
+
+use crate::error::IoError;
+use crate::foam;
+use crate::polymesh::{read_polymesh, read_text};
+
+/// A time directory of a case.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeDir {
+    /// The time value.
+    pub time: f64,
+    /// The directory.
+    pub path: PathBuf,
+}
+
+/// An OpenFOAM case directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case {
+    root: PathBuf,
+}
+
+impl Case {
+    /// Creates a case rooted at `root`, without checking the layout.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Opens the case rooted at `root`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IoError::InvalidData`] if `root` has no `constant` or
+    /// `system` directory.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, IoError> {
+        let case = Self::new(root);
+        for dir in [case.constant_dir(), case.system_dir()] {
+            if !dir.is_dir() {
+                return Err(IoError::InvalidData {
+                    what: case.root.display().to_string(),
+                    message: format!("missing directory {}", dir.display()),
+                });
+            }
+        }
+        Ok(case)
+    }
+
+    /// Returns the case directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the `constant` directory.
+    pub fn constant_dir(&self) -> PathBuf {
+        self.root.join("constant")
+    }
+
+    /// Returns the `system` directory.
+    pub fn system_dir(&self) -> PathBuf {
+        self.root.join("system")
+    }
+
+    /// Returns the mesh directory `constant/polyMesh`.
+    pub fn polymesh_dir(&self) -> PathBuf {
+        self.constant_dir().join("polyMesh")
+    }
+
+    /// Reads the mesh from `constant/polyMesh`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`read_polymesh`].
+    pub fn read_mesh(&self) -> Result<Mesh, IoError> {
+        read_polymesh(&self.polymesh_dir())
+    }
+
+    /// Reads the dictionary file `system/<name>`, such as `controlDict`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IoError::File`] if the file cannot be read and
+    /// [`IoError::Parse`] if it is malformed.
+    pub fn read_system_dict(&self, name: &str) -> Result<Dictionary, IoError> {
+        foam::parse_dictionary(&read_text(&self.system_dir().join(name))?)
+    }
+
+    /// Reads the dictionary file `constant/<name>`, such as
+    /// `transportProperties`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IoError::File`] if the file cannot be read and
+    /// [`IoError::Parse`] if it is malformed.
+    pub fn read_constant_dict(&self, name: &str) -> Result<Dictionary, IoError> {
+        foam::parse_dictionary(&read_text(&self.constant_dir().join(name))?)
+    }
+
+    /// Returns the time directories, in increasing time order.
+    ///
+    /// Directories whose names are not numbers are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IoError::File`] if the case directory cannot be listed.
+    pub fn times(&self) -> Result<Vec<TimeDir>, IoError> {
+        let mut times: Vec<TimeDir> = list_dir(&self.root)?
+            .into_iter()
+            .filter(|path| path.is_dir())
+            .filter_map(|path| {
+                let time = path.file_name()?.to_str()?.parse::<f64>().ok()?;
+                time.is_finite().then_some(TimeDir { time, path })
+            })
+            .collect();
+        times.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Ok(times)
+    }
+
+    /// Returns the latest time directory, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IoError::File`] if the case directory cannot be listed.
+    pub fn latest_time(&self) -> Result<Option<TimeDir>, IoError> {
+        Ok(self.times()?.pop())
+    }
+
+    /// Returns the directory of `time`, named by [`time_name`].
+    pub fn time_dir(&self, time: f64) -> PathBuf {
+        self.root.join(time_name(time))
+    }
+
+    /// Creates the directory of `time` for writing, if it does not exist,
+    /// and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IoError::File`] if the directory cannot be created.
+    pub fn create_time_dir(&self, time: f64) -> Result<PathBuf, IoError> {
+        let dir = self.time_dir(time);
+        fs::create_dir_all(&dir).map_err(|source| IoError::File {
+            path: dir.clone(),
+            source,
+        })?;
+        Ok(dir)
+    }
+
+    /// Returns the path of field `name` at `time`.
+    pub fn field_path(&self, time: f64, name: &str) -> PathBuf {
+        self.time_dir(time).join(name)
+    }
+
+    /// Returns the names of the field files in the directory of `time`,
+    /// sorted. Subdirectories and hidden or backup files are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IoError::File`] if the directory cannot be listed.
+    pub fn fields(&self, time: f64) -> Result<Vec<String>, IoError> {
+        let mut names: Vec<String> = list_dir(&self.time_dir(time))?
+            .into_iter()
+            .filter(|path| path.is_file())
+            .filter_map(|path| path.file_name()?.to_str().map(str::to_string))
+            .filter(|name| !name.starts_with('.') && !name.ends_with('~'))
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+}
+
+/// Names the directory of `time` the shortest way that reads back exactly:
+/// `0`, `0.5`, `1e-5`.
+pub fn time_name(time: f64) -> String {
+    let plain = time.to_string();
+    let exponent = format!("{time:e}");
+    if exponent.len() < plain.len() {
+        exponent
+    } else {
+        plain
+    }
+}
+
+fn list_dir(dir: &Path) -> Result<Vec<PathBuf>, IoError> {
+    let error = |source| IoError::File {
+        path: dir.to_path_buf(),
+        source,
+    };
+    fs::read_dir(dir)
+        .map_err(error)?
+        .map(|entry| entry.map(|e| e.path()).map_err(error))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polymesh::tests::temp_dir;
+
+    #[test]
+    fn test_case_discovers_times_and_fields() {
+        let root = temp_dir("case-times");
+        for dir in [
+            "constant",
+            "system",
+            "0",
+            "0.5",
+            "10",
+            "1e-05",
+            "postProcessing",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join("system/controlDict"), "endTime 10;").unwrap();
+        for file in ["p", "U", ".hidden", "U~"] {
+            fs::write(root.join("0.5").join(file), "").unwrap();
+        }
+        fs::create_dir_all(root.join("0.5/uniform")).unwrap();
+
+        let case = Case::open(&root).unwrap();
+        let times: Vec<f64> = case.times().unwrap().iter().map(|t| t.time).collect();
+        assert_eq!(times, vec![0.0, 1e-5, 0.5, 10.0]);
+        let latest = case.latest_time().unwrap().unwrap();
+        assert_eq!(latest.path, root.join("10"));
+        assert_eq!(case.fields(0.5).unwrap(), vec!["U", "p"]);
+        assert_eq!(case.field_path(0.5, "U"), root.join("0.5/U"));
+        assert_eq!(
+            case.read_system_dict("controlDict")
+                .unwrap()
+                .get_scalar("endTime"),
+            Some(10.0)
+        );
+
+        let dir = case.create_time_dir(0.25).unwrap();
+        assert!(dir.is_dir());
+        assert_eq!(case.times().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_case_open_rejects_missing_layout() {
+        let root = temp_dir("case-missing");
+        assert!(matches!(
+            Case::open(&root),
+            Err(IoError::InvalidData { .. })
+        ));
+        assert!(matches!(Case::new(&root).latest_time(), Ok(None)));
+        assert!(Case::new(root.join("absent")).times().is_err());
+    }
+
+    #[test]
+    fn test_time_name_is_short_and_exact() {
+        assert_eq!(time_name(0.0), "0");
+        assert_eq!(time_name(2.0), "2");
+        assert_eq!(time_name(0.5), "0.5");
+        assert_eq!(time_name(1e-5), "1e-5");
+        assert_eq!(time_name(1e-5).parse::<f64>(), Ok(1e-5));
+        assert_eq!(time_name(1500.0), "1500");
+    }
+}
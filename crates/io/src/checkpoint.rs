@@ -0,0 +1,336 @@
+//! Binary field checkpoints for exact restarts.
+//!
+//! A checkpoint stores a volume field with its old-time levels so that a
+//! restarted run continues bit for bit: cell and boundary values as raw
+//! `f64` bits, the dimensions, and each level's boundary conditions as their
+//! `boundaryField` dictionary. Scalars in the dictionary are written in
+//! shortest round-trip form, so condition data is exact too. The header
+//! records the [`Mesh::fingerprint`] of the field's mesh, which
+//! [`decode_checkpoint`] checks against the mesh it restores onto.
+//!
+//! Layout, all integers `u64` little-endian:
+//!
+//! | Section      | Contents                                                       |
+//! |--------------|----------------------------------------------------------------|
+//! | magic        | `b"DUGFIELD"`                                                  |
+//! | version      | [`VERSION`]                                                    |
+//! | fingerprint  | [`Mesh::fingerprint`] of the field's mesh                      |
+//! | counts       | cells, boundary faces, levels (1 + old-time levels)            |
+//! | dimensions   | seven exponents, as `i64`                                      |
+//! | text lengths | class, name (bytes)                                            |
+//! | texts        | class, name (UTF-8)                                            |
+//! | levels       | current level first; each has a boundary text length, the cell |
+//! |              | values, the boundary values in patch order, and the text      |
+//!
+//! Values are stored component by component in the order of
+//! [`Components`](dugong_fields::Components).
+
+use std::fs;
+use std::path::Path;
+
+use dugong_fields::{BoundaryValue, Dimensions, VolField};
+use dugong_mesh::Mesh;
+
+use crate::binary::read_u64;
+use crate::error::IoError;
+use crate::field::{boundary_field, set_boundary_field, vol_field_class};
+use crate::foam;
+use crate::polymesh::invalid;
+
+/// Leading bytes identifying a checkpoint.
+pub const MAGIC: &[u8; 8] = b"DUGFIELD";
+
+/// Format version written by [`encode_checkpoint`]; the only version read.
+pub const VERSION: u64 = 1;
+
+const WORD: usize = 8;
+
+/// Encodes a field and its old-time levels as a checkpoint.
+pub fn encode_checkpoint<T: BoundaryValue>(field: &VolField<'_, T>) -> Vec<u8> {
+    let mesh = field.mesh();
+    let n_boundary = mesh.n_faces() - mesh.n_internal_faces();
+    let class = vol_field_class::<T>();
+    let mut bytes = Vec::new();
+    let put = |bytes: &mut Vec<u8>, n: u64| bytes.extend_from_slice(&n.to_le_bytes());
+    bytes.extend_from_slice(MAGIC);
+    put(&mut bytes, VERSION);
+    put(&mut bytes, mesh.fingerprint());
+    for n in [mesh.n_cells(), n_boundary, 1 + field.n_old_times()] {
+        put(&mut bytes, n as u64);
+    }
+    for e in field.dimensions().exponents() {
+        bytes.extend_from_slice(&i64::from(e).to_le_bytes());
+    }
+    put(&mut bytes, class.len() as u64);
+    put(&mut bytes, field.name().len() as u64);
+    bytes.extend_from_slice(class.as_bytes());
+    bytes.extend_from_slice(field.name().as_bytes());
+
+    let mut level = field;
+    for i in 0..=field.n_old_times() {
+        if i > 0 {
+            level = level.old_time();
+        }
+        let text = foam::format_dictionary(&boundary_field(level), 0);
+        put(&mut bytes, text.len() as u64);
+        let put_value = |bytes: &mut Vec<u8>, v: T| {
+            for j in 0..T::N_COMPONENTS {
+                bytes.extend_from_slice(&v.component(j).to_le_bytes());
+            }
+        };
+        for c in 0..mesh.n_cells() {
+            put_value(&mut bytes, level.cell_value(c));
+        }
+        for pf in level.patch_fields() {
+            for &v in pf.values() {
+                put_value(&mut bytes, v);
+            }
+        }
+        bytes.extend_from_slice(text.as_bytes());
+    }
+    bytes
+}
+
+/// Restores a field and its old-time levels from a checkpoint onto `mesh`.
+///
+/// Boundary conditions are rebuilt from their stored dictionaries, then the
+/// stored face values replace the ones they evaluate.
+///
+/// # Errors
+///
+/// Returns [`IoError::InvalidData`] if the magic bytes do not match, the
+/// version is not [`VERSION`], the data is truncated, the field class
+/// differs from that of `T`, or `mesh` does not match the recorded
+/// fingerprint, and [`IoError::Parse`] or [`IoError::Field`] for invalid
+/// boundary conditions.
+pub fn decode_checkpoint<'mesh, T: BoundaryValue>(
+    mesh: &'mesh Mesh,
+    bytes: &[u8],
+) -> Result<VolField<'mesh, T>, IoError> {
+    let mut reader = Reader { bytes, at: 0 };
+    if reader.take(MAGIC.len())? != MAGIC {
+        return Err(invalid("checkpoint", "missing DUGFIELD header"));
+    }
+    let version = reader.word()?;
+    if version != VERSION {
+        return Err(invalid(
+            "checkpoint",
+            format!("unsupported version {version}, expected {VERSION}"),
+        ));
+    }
+    if reader.word()? != mesh.fingerprint() {
+        return Err(invalid(
+            "checkpoint",
+            "mesh does not match the recorded fingerprint",
+        ));
+    }
+    let [n_cells, n_boundary, n_levels] = [reader.count()?, reader.count()?, reader.count()?];
+    if n_cells != mesh.n_cells() || n_boundary != mesh.n_faces() - mesh.n_internal_faces() {
+        return Err(invalid("checkpoint", "counts do not match the mesh"));
+    }
+    if !(1..=3).contains(&n_levels) {
+        return Err(invalid(
+            "checkpoint",
+            format!("invalid number of time levels {n_levels}"),
+        ));
+    }
+    let mut exponents = [0; 7];
+    for e in &mut exponents {
+        *e = i32::try_from(reader.word()? as i64)
+            .map_err(|_| invalid("checkpoint", "dimension exponent out of range"))?;
+    }
+    let dimensions = Dimensions::new(exponents);
+    let (class_len, name_len) = (reader.count()?, reader.count()?);
+    let class = reader.text(class_len)?;
+    let expected = vol_field_class::<T>();
+    if class != expected {
+        return Err(invalid(
+            "checkpoint",
+            format!("expected class {expected}, got {class}"),
+        ));
+    }
+    let name = reader.text(name_len)?;
+
+    let mut levels = Vec::with_capacity(n_levels);
+    for _ in 0..n_levels {
+        let text_len = reader.count()?;
+        let cells = reader.values::<T>(n_cells)?;
+        let faces = reader.values::<T>(n_boundary)?;
+        let boundary = foam::parse_dictionary(reader.text(text_len)?)?;
+        levels.push((cells, faces, boundary));
+    }
+
+    // Restore the oldest level first; storing it as old time moves it down.
+    let mut field: Option<VolField<'mesh, T>> = None;
+    for (cells, faces, boundary) in levels.into_iter().rev() {
+        let mut level = match field.take() {
+            Some(mut level) => {
+                level.store_old_time();
+                level.internal_mut().copy_from_slice(&cells);
+                level
+            }
+            None => VolField::new(mesh, name, dimensions, cells)?,
+        };
+        set_boundary_field(&mut level, &boundary)?;
+        let mut faces = faces.into_iter();
+        for pf in level.patch_fields_mut() {
+            for (v, w) in pf.values_mut().iter_mut().zip(&mut faces) {
+                *v = w;
+            }
+        }
+        field = Some(level);
+    }
+    // Safety: there is at least one level.
+    Ok(field.unwrap())
+}
+
+/// Writes a field and its old-time levels to a checkpoint file.
+///
+/// # Errors
+///
+/// Returns `Err` if the file cannot be written.
+pub fn write_checkpoint<T: BoundaryValue>(
+    field: &VolField<'_, T>,
+    path: &Path,
+) -> Result<(), IoError> {
+    fs::write(path, encode_checkpoint(field)).map_err(|source| IoError::File {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Reads a checkpoint file onto `mesh`; see [`decode_checkpoint`].
+///
+/// # Errors
+///
+/// Returns `Err` if the file cannot be read or is not a valid checkpoint
+/// of a field of `T` on `mesh`.
+pub fn read_checkpoint<'mesh, T: BoundaryValue>(
+    mesh: &'mesh Mesh,
+    path: &Path,
+) -> Result<VolField<'mesh, T>, IoError> {
+    let bytes = fs::read(path).map_err(|source| IoError::File {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    decode_checkpoint(mesh, &bytes)
+}
+
+/// Sequential reads from checkpoint bytes.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], IoError> {
+        let end = self
+            .at
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| invalid("checkpoint", "truncated data"))?;
+        let slice = &self.bytes[self.at..end];
+        self.at = end;
+        Ok(slice)
+    }
+
+    fn word(&mut self) -> Result<u64, IoError> {
+        Ok(read_u64(self.take(WORD)?, 0))
+    }
+
+    fn count(&mut self) -> Result<usize, IoError> {
+        usize::try_from(self.word()?)
+            .map_err(|_| invalid("checkpoint", "count does not fit in memory"))
+    }
+
+    fn text(&mut self, len: usize) -> Result<&'a str, IoError> {
+        std::str::from_utf8(self.take(len)?)
+            .map_err(|_| invalid("checkpoint", "text section is not UTF-8"))
+    }
+
+    fn values<T: BoundaryValue>(&mut self, n: usize) -> Result<Vec<T>, IoError> {
+        let mut components = vec![0.0; T::N_COMPONENTS];
+        (0..n)
+            .map(|_| {
+                for c in &mut components {
+                    *c = f64::from_bits(self.word()?);
+                }
+                Ok(T::from_components(&components))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_fields::FixedValue;
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+    use crate::polymesh::tests::{temp_dir, two_cell_mesh};
+
+    fn patch_name(mesh: &Mesh, i: usize) -> String {
+        mesh.patches()[i].name().to_string()
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip_is_bit_exact_with_old_times() {
+        let mesh = two_cell_mesh();
+        let dimensions = Dimensions::mlt(0, 1, -1);
+        let value = |s: f64| Vector::new(0.1 * s, 1.0 / 3.0 * s, -std::f64::consts::PI * s);
+        let mut u = VolField::new(&mesh, "U", dimensions, vec![value(1.0), value(2.0)]).unwrap();
+        let inlet = patch_name(&mesh, 0);
+        let n_inlet = mesh.patches()[0].size();
+        u.set_boundary_condition(&inlet, Box::new(FixedValue::new(vec![value(0.7); n_inlet])))
+            .unwrap();
+        u.store_old_time();
+        u.internal_mut()[0] = value(3.0);
+        u.store_old_time();
+        u.internal_mut()[1] = value(4.0);
+        u.evaluate_boundaries();
+
+        let path = temp_dir("checkpoint-round-trip").join("U.chk");
+        write_checkpoint(&u, &path).unwrap();
+        let read: VolField<Vector> = read_checkpoint(&mesh, &path).unwrap();
+
+        assert_eq!(read.name(), "U");
+        assert_eq!(read.dimensions(), dimensions);
+        assert_eq!(read.n_old_times(), 2);
+        for (a, b) in [(&read, &u), (read.old_time(), u.old_time())]
+            .into_iter()
+            .chain([(read.old_old_time(), u.old_old_time())])
+        {
+            assert_eq!(a.name(), b.name());
+            assert_eq!(a.internal(), b.internal());
+            for (pa, pb) in a.patch_fields().iter().zip(b.patch_fields()) {
+                assert_eq!(pa.values(), pb.values());
+                assert_eq!(pa.condition().type_name(), pb.condition().type_name());
+            }
+        }
+        assert_eq!(encode_checkpoint(&read), encode_checkpoint(&u));
+    }
+
+    #[test]
+    fn test_checkpoint_rejects_other_mesh_class_and_truncation() {
+        let mesh = two_cell_mesh();
+        let p = VolField::uniform(&mesh, "p", Dimensions::default(), 1.5);
+        let bytes = encode_checkpoint(&p);
+        assert!(decode_checkpoint::<f64>(&mesh, &bytes).is_ok());
+        assert!(matches!(
+            decode_checkpoint::<Vector>(&mesh, &bytes),
+            Err(IoError::InvalidData { .. })
+        ));
+        assert!(matches!(
+            decode_checkpoint::<f64>(&mesh, &bytes[..bytes.len() - 1]),
+            Err(IoError::InvalidData { .. })
+        ));
+
+        let mut other_bytes = bytes.clone();
+        // Corrupt the recorded fingerprint.
+        other_bytes[MAGIC.len() + WORD] ^= 1;
+        assert!(matches!(
+            decode_checkpoint::<f64>(&mesh, &other_bytes),
+            Err(IoError::InvalidData { .. })
+        ));
+    }
+}
@@ -0,0 +1,21 @@
+use std::path::PathBuf;
+
+use dugong_fields::FieldError;
+use dugong_mesh::MeshError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum IoError {
+    #[error("failed to access {path}: {source}")]
+    File {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("parse error at line {line}: {message}")]
+    Parse { line: usize, message: String },
+    #[error("invalid {what}: {message}")]
+    InvalidData { what: String, message: String },
+    #[error(transparent)]
+    Mesh(#[from] MeshError),
+    #[error(transparent)]
+    Field(#[from] FieldError),
+}
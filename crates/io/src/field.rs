@@ -0,0 +1,451 @@
+//! Reader and writer for OpenFOAM field files (ASCII), such as `0/U` and
+//! `0/p`.
+//!
+//! A field file holds `dimensions`, an `internalField` (`uniform`,
+//! `nonuniform List<...>` or `expression`) and a `boundaryField` dictionary
+//! with one entry per patch, whose `type` selects the boundary condition.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use dugong_fields::{
+    BoundaryValue, Dimensions, PatchField, VolField, evaluate_at_points, format_values,
+    lookup_values, parse_expressions, to_value,
+};
+use dugong_mesh::{Mesh, PatchKind};
+use dugong_runtime::{Dictionary, Value};
+
+use crate::error::IoError;
+use crate::foam;
+use crate::polymesh::{invalid, read_text, write_text};
+
+/// Returns the OpenFOAM class name of a volume field of `T`, such as
+/// `volVectorField`.
+pub fn vol_field_class<T: BoundaryValue>() -> String {
+    let mut chars = T::TYPE_NAME.chars();
+    // Safety: type names are non-empty.
+    let first = chars.next().unwrap().to_ascii_uppercase();
+    format!("vol{first}{}Field", chars.as_str())
+}
+
+/// Reads a volume field file.
+///
+/// The field is named after the file. See [`parse_vol_field`].
+///
+/// # Errors
+///
+/// Returns [`IoError::File`] if the file cannot be read, and the errors of
+/// [`parse_vol_field`].
+pub fn read_vol_field<'mesh, T: BoundaryValue>(
+    mesh: &'mesh Mesh,
+    path: &Path,
+) -> Result<VolField<'mesh, T>, IoError> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    parse_vol_field(mesh, &name, &read_text(path)?)
+}
+
+/// Parses the text of a volume field file into a field named `name`.
+///
+/// An `internalField expression "..."` entry sets the cell values from
+/// expressions of the cell center `x`, `y`, `z` at `t = 0`, one per
+/// component as in `expression ("4*y*(1-y)" 0 0)`; see
+/// [`parse_expressions`].
+///
+/// Each patch takes the `boundaryField` entry with its name or, failing
+/// that, the last entry whose keyword is a pattern matching it, such as
+/// `".*"` or `"(inlet|outlet)"`. Patches of a constraint type (`empty`,
+/// `wedge`, `cyclic`, `cyclicAMI`, `processor`) whose entry names that type
+/// keep the default `calculated` condition.
+///
+/// # Errors
+///
+/// Returns [`IoError::Parse`] on malformed syntax, [`IoError::InvalidData`]
+/// for a mismatched `class`, missing entries or invalid dimensions, and
+/// [`IoError::Field`] for invalid values or boundary conditions.
+pub fn parse_vol_field<'mesh, T: BoundaryValue>(
+    mesh: &'mesh Mesh,
+    name: &str,
+    text: &str,
+) -> Result<VolField<'mesh, T>, IoError> {
+    let dict = foam::parse_dictionary(text)?;
+    let class = vol_field_class::<T>();
+    if let Some(found) = dict.get_dict("FoamFile").and_then(|h| h.get_word("class"))
+        && found != class
+    {
+        return Err(invalid(
+            name,
+            format!("expected class {class}, got {found}"),
+        ));
+    }
+    let dimensions = match dict.get("dimensions") {
+        Some([Value::Dimensions(d)]) => parse_dimensions(d).ok_or_else(|| {
+            invalid(
+                name,
+                format!("invalid dimensions {}", Value::Dimensions(d.clone())),
+            )
+        })?,
+        _ => return Err(invalid(name, "missing dimensions")),
+    };
+    let internal = match dict.get("internalField") {
+        Some([Value::Word(w), expressions @ ..]) if w == "expression" => {
+            let expressions = parse_expressions::<T>("internalField", expressions)?;
+            evaluate_at_points(&expressions, mesh.cell_centers(), 0.0)?
+        }
+        _ => lookup_values(&dict, "internalField", mesh.n_cells())?,
+    };
+    let mut field = VolField::new(mesh, name, dimensions, internal)?;
+
+    let boundary = dict
+        .get_dict("boundaryField")
+        .ok_or_else(|| invalid(name, "missing boundaryField"))?;
+    set_boundary_field(&mut field, boundary)?;
+    Ok(field)
+}
+
+/// Sets the boundary conditions of every patch from a `boundaryField`
+/// dictionary, as [`parse_vol_field`] does.
+pub(crate) fn set_boundary_field<T: BoundaryValue>(
+    field: &mut VolField<'_, T>,
+    boundary: &Dictionary,
+) -> Result<(), IoError> {
+    let mesh = field.mesh();
+    for patch in mesh.patches() {
+        let entry = patch_entry(boundary, patch.name()).ok_or_else(|| {
+            invalid(
+                field.name(),
+                format!("no boundaryField entry for {}", patch.name()),
+            )
+        })?;
+        if is_constraint(patch.kind()) && entry.get_word("type") == Some(patch.kind().type_name()) {
+            continue;
+        }
+        field.set_boundary_condition_from_dict(patch.name(), entry)?;
+    }
+    Ok(())
+}
+
+/// Writing of volume fields in the OpenFOAM format.
+pub trait WriteOpenFoam {
+    /// Writes the field to `<time_dir>/<name>`, creating the directory if
+    /// needed. The file is readable by [`read_vol_field`], by OpenFOAM
+    /// utilities and by ParaView's OpenFOAM reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IoError::File`] if the directory or file cannot be written.
+    fn write_openfoam(&self, time_dir: &Path) -> Result<(), IoError>;
+}
+
+impl<T: BoundaryValue> WriteOpenFoam for VolField<'_, T> {
+    fn write_openfoam(&self, time_dir: &Path) -> Result<(), IoError> {
+        fs::create_dir_all(time_dir).map_err(|source| IoError::File {
+            path: time_dir.to_path_buf(),
+            source,
+        })?;
+        let location = time_dir.file_name().map(|n| n.to_string_lossy());
+        let text = format_vol_field(self, location.as_deref());
+        write_text(&time_dir.join(self.name()), &text)
+    }
+}
+
+/// Formats a volume field file, with `location` (the time directory name)
+/// in the header if given.
+///
+/// The internal field is written one value per line. Each patch is written
+/// with the `type` of its condition, the condition's own entries and its
+/// face values; patches of a constraint type with the default `calculated`
+/// condition are written with the patch type instead.
+pub fn format_vol_field<T: BoundaryValue>(
+    field: &VolField<'_, T>,
+    location: Option<&str>,
+) -> String {
+    let mut s = foam::header(&vol_field_class::<T>(), location, field.name());
+    let exponents = field.dimensions().exponents().map(f64::from).to_vec();
+    let _ = writeln!(s, "dimensions      {};\n", Value::Dimensions(exponents));
+    match format_values(field.internal()).as_slice() {
+        [_, v] => {
+            let _ = writeln!(s, "internalField   uniform {v};\n");
+        }
+        _ => {
+            let _ = writeln!(s, "internalField   nonuniform List<{}>", T::TYPE_NAME);
+            let _ = writeln!(s, "{}\n(", field.internal().len());
+            for v in field.internal() {
+                let _ = writeln!(s, "{}", to_value(v));
+            }
+            s.push_str(")\n;\n\n");
+        }
+    }
+    s.push_str("boundaryField\n{\n");
+    s.push_str(&foam::format_dictionary(&boundary_field(field), 4));
+    s.push_str("}\n");
+    s
+}
+
+/// Returns the `boundaryField` dictionary of a field, with one entry per
+/// patch.
+pub(crate) fn boundary_field<T: BoundaryValue>(field: &VolField<'_, T>) -> Dictionary {
+    let mut boundary = Dictionary::new();
+    for (pf, patch) in field.patch_fields().iter().zip(field.mesh().patches()) {
+        boundary.insert(
+            patch.name(),
+            vec![Value::Dict(patch_dict(pf, patch.kind()))],
+        );
+    }
+    boundary
+}
+
+/// Returns the `boundaryField` entry of one patch.
+fn patch_dict<T: BoundaryValue>(pf: &PatchField<T>, kind: &PatchKind) -> Dictionary {
+    let condition = pf.condition();
+    let mut dict = Dictionary::new();
+    if is_constraint(kind) && condition.type_name() == "calculated" {
+        dict.insert("type", vec![Value::Word(kind.type_name().into())]);
+        if *kind == PatchKind::Empty {
+            return dict;
+        }
+    } else {
+        dict.insert("type", vec![Value::Word(condition.type_name().into())]);
+        let mut entries = Dictionary::new();
+        condition.write_entries(&mut entries);
+        for (keyword, values) in entries.iter() {
+            dict.insert(keyword, counted(values.to_vec()));
+        }
+    }
+    dict.insert("value", counted(format_values(pf.values())));
+    dict
+}
+
+/// Prefixes the list of a `nonuniform` field entry with its length, as OpenFOAM
+/// writes it.
+fn counted(mut values: Vec<Value>) -> Vec<Value> {
+    if let [Value::Word(w), _, Value::List(items)] = values.as_slice()
+        && w == "nonuniform"
+    {
+        let n = items.len() as i64;
+        values.insert(2, Value::Label(n));
+    }
+    values
+}
+
+/// Converts OpenFOAM dimension exponents: seven, or the first five with
+/// the rest zero. Exponents must be integers.
+fn parse_dimensions(exponents: &[f64]) -> Option<Dimensions> {
+    if exponents.len() != 5 && exponents.len() != 7 {
+        return None;
+    }
+    let mut out = [0; 7];
+    for (o, &e) in out.iter_mut().zip(exponents) {
+        if e.fract() != 0.0 {
+            return None;
+        }
+        *o = e as i32;
+    }
+    Some(Dimensions::new(out))
+}
+
+fn is_constraint(kind: &PatchKind) -> bool {
+    matches!(kind, PatchKind::Empty | PatchKind::Wedge) || kind.is_coupled()
+}
+
+/// Returns the sub-dictionary of `boundary` for `patch`: the exact entry,
+/// else the last matching pattern entry.
+fn patch_entry<'a>(boundary: &'a Dictionary, patch: &str) -> Option<&'a Dictionary> {
+    boundary.get_dict(patch).or_else(|| {
+        boundary
+            .iter()
+            .filter(|(keyword, _)| pattern_matches(keyword, patch))
+            .filter_map(|(_, values)| match values {
+                [Value::Dict(d)] => Some(d),
+                _ => None,
+            })
+            .last()
+    })
+}
+
+/// Matches `name` against the regular expression subset used in
+/// `boundaryField` keywords: literals, `.`, `.*` and `(a|b)` groups.
+fn pattern_matches(pattern: &str, name: &str) -> bool {
+    expand_groups(pattern)
+        .iter()
+        .any(|p| wildcard_matches(p.as_bytes(), name.as_bytes()))
+}
+
+/// Expands `(a|b)` groups into the alternative patterns without groups.
+fn expand_groups(pattern: &str) -> Vec<String> {
+    let Some(open) = pattern.find('(') else {
+        return vec![pattern.to_string()];
+    };
+    let mut depth = 0;
+    let mut close = None;
+    let mut splits = vec![open];
+    for (i, c) in pattern.char_indices().skip_while(|&(i, _)| i < open) {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(i);
+                    break;
+                }
+            }
+            '|' if depth == 1 => splits.push(i),
+            _ => {}
+        }
+    }
+    let Some(close) = close else {
+        return vec![pattern.to_string()];
+    };
+    splits.push(close);
+    let (head, tail) = (&pattern[..open], &pattern[close + 1..]);
+    splits
+        .windows(2)
+        .flat_map(|w| expand_groups(&format!("{head}{}{tail}", &pattern[w[0] + 1..w[1]])))
+        .collect()
+}
+
+/// Matches literals, `.` (any character) and `.*` (any sequence).
+fn wildcard_matches(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern {
+        [] => name.is_empty(),
+        [b'.', b'*', rest @ ..] => (0..=name.len()).any(|i| wildcard_matches(rest, &name[i..])),
+        [b'.', rest @ ..] => !name.is_empty() && wildcard_matches(rest, &name[1..]),
+        [c, rest @ ..] => name.first() == Some(c) && wildcard_matches(rest, &name[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+    use crate::polymesh::tests::{temp_dir, two_cell_mesh};
+
+    const U: &str = r#"
+        FoamFile { version 2.0; format ascii; class volVectorField; object U; }
+        dimensions [0 1 -1 0 0 0 0];
+        internalField nonuniform List<vector> 2((1 0 0) (2 0 0));
+        boundaryField
+        {
+            inlet { type fixedValue; value uniform (3 0 0); }
+            outlet { type zeroGradient; }
+            ".*" { type noSlip; }
+        }
+    "#;
+
+    #[test]
+    fn test_parse_vol_field_reads_values_and_conditions() {
+        let mesh = two_cell_mesh();
+        let u: VolField<Vector> = parse_vol_field(&mesh, "U", U).unwrap();
+        assert_eq!(u.dimensions(), Dimensions::mlt(0, 1, -1));
+        assert_eq!(
+            u.internal(),
+            &[Vector::new(1.0, 0.0, 0.0), Vector::new(2.0, 0.0, 0.0)]
+        );
+        let inlet = u.patch_field("inlet").unwrap();
+        assert_eq!(inlet.condition().type_name(), "fixedValue");
+        assert_eq!(inlet.values(), &[Vector::new(3.0, 0.0, 0.0)]);
+        let outlet = u.patch_field("outlet").unwrap();
+        assert_eq!(outlet.values(), &[Vector::new(2.0, 0.0, 0.0)]);
+        let walls = u.patch_field("walls").unwrap();
+        assert_eq!(walls.condition().type_name(), "noSlip");
+        assert!(walls.values().iter().all(|&v| v == Vector::zero()));
+    }
+
+    #[test]
+    fn test_parse_vol_field_evaluates_internal_expression() {
+        let mesh = two_cell_mesh();
+        let text = U.replace(
+            "nonuniform List<vector> 2((1 0 0) (2 0 0))",
+            r#"expression ("2*x" 0 "y - 0.5")"#,
+        );
+        let u: VolField<Vector> = parse_vol_field(&mesh, "U", &text).unwrap();
+        let expected = [Vector::new(1.0, 0.0, 0.0), Vector::new(3.0, 0.0, 0.0)];
+        for (&v, e) in u.internal().iter().zip(expected) {
+            assert!((v - e).mag() < 1e-12);
+        }
+        let bad = U.replace(
+            "nonuniform List<vector> 2((1 0 0) (2 0 0))",
+            r#"expression "2*x""#,
+        );
+        assert!(parse_vol_field::<Vector>(&mesh, "U", &bad).is_err());
+    }
+
+    #[test]
+    fn test_read_vol_field_rejects_bad_files() {
+        let mesh = two_cell_mesh();
+        let dir = temp_dir("field-read");
+        std::fs::write(dir.join("U"), U).unwrap();
+        assert_eq!(
+            read_vol_field::<Vector>(&mesh, &dir.join("U"))
+                .unwrap()
+                .name(),
+            "U"
+        );
+        assert!(matches!(
+            read_vol_field::<f64>(&mesh, &dir.join("U")),
+            Err(IoError::InvalidData { .. })
+        ));
+        let missing_patch = U.replace("\".*\"", "front");
+        assert!(matches!(
+            parse_vol_field::<Vector>(&mesh, "U", &missing_patch),
+            Err(IoError::InvalidData { .. })
+        ));
+        let unknown = U.replace("noSlip", "noSuchCondition");
+        assert!(matches!(
+            parse_vol_field::<Vector>(&mesh, "U", &unknown),
+            Err(IoError::Field(_))
+        ));
+        assert!(matches!(
+            read_vol_field::<Vector>(&mesh, &dir.join("p")),
+            Err(IoError::File { .. })
+        ));
+    }
+
+    #[test]
+    fn test_write_openfoam_round_trips() {
+        let mesh = two_cell_mesh();
+        let mut u: VolField<Vector> = parse_vol_field(&mesh, "U", U).unwrap();
+        let dict = foam::parse_dictionary(
+            "type mixed; refValue uniform (1 0 0); refGradient uniform (0 0 0);
+             valueFraction nonuniform List<scalar> 1(0.5);",
+        )
+        .unwrap();
+        u.set_boundary_condition_from_dict("outlet", &dict).unwrap();
+
+        let dir = temp_dir("field-write").join("0");
+        u.write_openfoam(&dir).unwrap();
+        let text = std::fs::read_to_string(dir.join("U")).unwrap();
+        assert!(text.contains("class       volVectorField;"));
+        assert!(text.contains("location    \"0\";"));
+        assert!(text.contains("nonuniform List<vector>\n2\n(\n(1.0 0.0 0.0)\n"));
+        assert!(text.contains("valueFraction uniform 0.5;"));
+
+        let read: VolField<Vector> = read_vol_field(&mesh, &dir.join("U")).unwrap();
+        assert_eq!(read.dimensions(), u.dimensions());
+        assert_eq!(read.internal(), u.internal());
+        for (a, b) in read.patch_fields().iter().zip(u.patch_fields()) {
+            assert_eq!(a.condition().type_name(), b.condition().type_name());
+            assert_eq!(a.values(), b.values());
+        }
+    }
+
+    #[test]
+    fn test_pattern_matches_groups_and_wildcards() {
+        assert!(pattern_matches(".*", "inlet"));
+        assert!(pattern_matches("(inlet|outlet)", "outlet"));
+        assert!(pattern_matches("wall_.*", "wall_top"));
+        assert!(pattern_matches("(in|out)let.", "inlet1"));
+        assert!(!pattern_matches("(inlet|outlet)", "walls"));
+        assert!(!pattern_matches("in.", "inlet"));
+        assert_eq!(vol_field_class::<f64>(), "volScalarField");
+        assert_eq!(
+            parse_dimensions(&[1.0, -1.0, -2.0, 0.0, 0.0]),
+            Some(Dimensions::mlt(1, -1, -2))
+        );
+        assert_eq!(parse_dimensions(&[0.5, 0.0, 0.0, 0.0, 0.0]), None);
+    }
+}
@@ -0,0 +1,451 @@
+//! Reader and writer for the OpenFOAM dictionary file format (ASCII).
+//!
+//! The parser produces the [`Dictionary`]/[`Value`] data model of
+//! `dugong-runtime`. Top-level keyword entries go into a dictionary (the
+//! `FoamFile` header included); bare top-level values, such as the list body
+//! of a `polyMesh/points` file, are returned separately.
+
+use std::fmt::Write as _;
+
+use dugong_runtime::{Dictionary, Value};
+
+use crate::error::IoError;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+    Label(i64),
+    Scalar(f64),
+    Punct(char),
+}
+
+struct Lexer<'a> {
+    src: &'a [u8],
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            src: text.as_bytes(),
+            pos: 0,
+            line: 1,
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> IoError {
+        IoError::Parse {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn skip_whitespace_and_comments(&mut self) -> Result<(), IoError> {
+        while self.pos < self.src.len() {
+            let c = self.src[self.pos];
+            if c == b'\n' {
+                self.line += 1;
+                self.pos += 1;
+            } else if c.is_ascii_whitespace() {
+                self.pos += 1;
+            } else if self.src[self.pos..].starts_with(b"//") {
+                while self.pos < self.src.len() && self.src[self.pos] != b'\n' {
+                    self.pos += 1;
+                }
+            } else if self.src[self.pos..].starts_with(b"/*") {
+                self.pos += 2;
+                loop {
+                    if self.pos + 1 >= self.src.len() {
+                        return Err(self.error("unterminated block comment"));
+                    }
+                    if self.src[self.pos..].starts_with(b"*/") {
+                        self.pos += 2;
+                        break;
+                    }
+                    if self.src[self.pos] == b'\n' {
+                        self.line += 1;
+                    }
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, IoError> {
+        self.skip_whitespace_and_comments()?;
+        let Some(&c) = self.src.get(self.pos) else {
+            return Ok(None);
+        };
+        match c {
+            b'(' | b')' | b'{' | b'}' | b'[' | b']' | b';' => {
+                self.pos += 1;
+                Ok(Some(Token::Punct(c as char)))
+            }
+            b'"' => {
+                self.pos += 1;
+                let mut s = String::new();
+                loop {
+                    let Some(&b) = self.src.get(self.pos) else {
+                        return Err(self.error("unterminated string"));
+                    };
+                    self.pos += 1;
+                    match b {
+                        b'"' => break,
+                        b'\\' if self.src.get(self.pos) == Some(&b'"') => {
+                            s.push('"');
+                            self.pos += 1;
+                        }
+                        b'\n' => {
+                            self.line += 1;
+                            s.push('\n');
+                        }
+                        _ => s.push(b as char),
+                    }
+                }
+                Ok(Some(Token::Str(s)))
+            }
+            _ => {
+                let start = self.pos;
+                // Only identifier-like words may contain parentheses, so that a
+                // counted list such as `4(0 1 2 3)` still lexes as label + list.
+                let identifier = c.is_ascii_alphabetic() || c == b'_';
+                let mut depth = 0usize;
+                while let Some(&b) = self.src.get(self.pos) {
+                    match b {
+                        // Parentheses inside a word (e.g. `div(phi,U)`) belong to the word.
+                        b'(' if identifier && self.pos > start => depth += 1,
+                        b')' if depth > 0 => depth -= 1,
+                        b'(' | b')' | b'{' | b'}' | b'[' | b']' | b';' | b'"' if depth == 0 => {
+                            break;
+                        }
+                        _ if b.is_ascii_whitespace() && depth == 0 => break,
+                        _ => {}
+                    }
+                    self.pos += 1;
+                }
+                let text = std::str::from_utf8(&self.src[start..self.pos])
+                    .map_err(|_| self.error("invalid UTF-8"))?;
+                Ok(Some(classify_word(text)))
+            }
+        }
+    }
+}
+
+fn classify_word(text: &str) -> Token {
+    let bytes = text.as_bytes();
+    let starts_numeric = matches!(
+        bytes,
+        [b'-' | b'+', b'0'..=b'9' | b'.', ..] | [b'0'..=b'9', ..] | [b'.', b'0'..=b'9', ..]
+    );
+    if starts_numeric {
+        if let Ok(l) = text.parse::<i64>() {
+            return Token::Label(l);
+        }
+        if let Ok(s) = text.parse::<f64>() {
+            return Token::Scalar(s);
+        }
+    }
+    Token::Word(text.to_string())
+}
+
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    peeked: Option<Token>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            lexer: Lexer::new(text),
+            peeked: None,
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<&Token>, IoError> {
+        if self.peeked.is_none() {
+            self.peeked = self.lexer.next_token()?;
+        }
+        Ok(self.peeked.as_ref())
+    }
+
+    fn next(&mut self) -> Result<Option<Token>, IoError> {
+        match self.peeked.take() {
+            Some(t) => Ok(Some(t)),
+            None => self.lexer.next_token(),
+        }
+    }
+
+    fn expect_next(&mut self) -> Result<Token, IoError> {
+        self.next()?
+            .ok_or_else(|| self.lexer.error("unexpected end of input"))
+    }
+
+    /// Parses entries until `}` (when `nested`) or end of input.
+    fn parse_entries(&mut self, nested: bool) -> Result<(Dictionary, Vec<Value>), IoError> {
+        let mut dict = Dictionary::new();
+        let mut data = Vec::new();
+        loop {
+            match self.peek()? {
+                None if nested => return Err(self.lexer.error("missing closing '}'")),
+                None => break,
+                Some(Token::Punct('}')) if nested => {
+                    self.next()?;
+                    break;
+                }
+                Some(Token::Punct(';')) => {
+                    self.next()?;
+                }
+                Some(Token::Word(_) | Token::Str(_)) => {
+                    let keyword = match self.expect_next()? {
+                        Token::Word(w) | Token::Str(w) => w,
+                        _ => unreachable!("peeked a word or string"),
+                    };
+                    if let Some(Token::Punct('{')) = self.peek()? {
+                        self.next()?;
+                        let (sub, _) = self.parse_entries(true)?;
+                        dict.insert(keyword, vec![Value::Dict(sub)]);
+                    } else {
+                        let mut values = Vec::new();
+                        loop {
+                            match self.peek()? {
+                                Some(Token::Punct(';')) => {
+                                    self.next()?;
+                                    break;
+                                }
+                                Some(Token::Punct('}')) | None => {
+                                    return Err(self
+                                        .lexer
+                                        .error(format!("missing ';' after entry '{keyword}'")));
+                                }
+                                Some(_) => values.push(self.parse_value()?),
+                            }
+                        }
+                        dict.insert(keyword, values);
+                    }
+                }
+                Some(_) => data.push(self.parse_value()?),
+            }
+        }
+        Ok((dict, data))
+    }
+
+    fn parse_value(&mut self) -> Result<Value, IoError> {
+        match self.expect_next()? {
+            Token::Word(w) => Ok(Value::Word(w)),
+            Token::Str(s) => Ok(Value::Str(s)),
+            Token::Scalar(s) => Ok(Value::Scalar(s)),
+            Token::Label(n) => match self.peek()? {
+                Some(Token::Punct('(')) => {
+                    self.next()?;
+                    let (items, count) = self.parse_list_items()?;
+                    if count as i64 != n {
+                        return Err(self
+                            .lexer
+                            .error(format!("list size mismatch: declared {n}, found {count}")));
+                    }
+                    Ok(Value::List(items))
+                }
+                Some(Token::Punct('{')) => {
+                    // Uniform list shorthand: `N{value}`.
+                    self.next()?;
+                    let v = self.parse_value()?;
+                    match self.expect_next()? {
+                        Token::Punct('}') => {}
+                        _ => return Err(self.lexer.error("expected '}' in uniform list")),
+                    }
+                    let n = usize::try_from(n)
+                        .map_err(|_| self.lexer.error("negative uniform list size"))?;
+                    Ok(Value::List(vec![v; n]))
+                }
+                _ => Ok(Value::Label(n)),
+            },
+            Token::Punct('(') => Ok(Value::List(self.parse_list_items()?.0)),
+            Token::Punct('[') => {
+                let mut dims = Vec::new();
+                loop {
+                    match self.expect_next()? {
+                        Token::Punct(']') => break,
+                        Token::Label(l) => dims.push(l as f64),
+                        Token::Scalar(s) => dims.push(s),
+                        _ => return Err(self.lexer.error("expected number in dimension set")),
+                    }
+                }
+                Ok(Value::Dimensions(dims))
+            }
+            Token::Punct('{') => Ok(Value::Dict(self.parse_entries(true)?.0)),
+            Token::Punct(c) => Err(self.lexer.error(format!("unexpected '{c}'"))),
+        }
+    }
+
+    /// Parses list items up to the closing `)`, returning the items and the
+    /// logical element count.
+    ///
+    /// A word directly followed by a `{...}` dictionary (the `name { ... }`
+    /// entries of `boundary` and zone files) is stored as two items but counts
+    /// as one element.
+    fn parse_list_items(&mut self) -> Result<(Vec<Value>, usize), IoError> {
+        let mut items = Vec::new();
+        let mut count = 0;
+        loop {
+            match self.peek()? {
+                Some(Token::Punct(')')) => {
+                    self.next()?;
+                    return Ok((items, count));
+                }
+                None => return Err(self.lexer.error("missing closing ')'")),
+                Some(_) => {
+                    let item = self.parse_value()?;
+                    let named_dict = matches!(item, Value::Dict(_))
+                        && matches!(items.last(), Some(Value::Word(_)));
+                    if !named_dict {
+                        count += 1;
+                    }
+                    items.push(item);
+                }
+            }
+        }
+    }
+}
+
+/// Parses a dictionary file. Bare top-level values are rejected.
+///
+/// # Errors
+///
+/// Returns [`IoError::Parse`] on malformed input or if the file contains
+/// top-level values that are not keyword entries.
+pub fn parse_dictionary(text: &str) -> Result<Dictionary, IoError> {
+    let mut parser = Parser::new(text);
+    let (dict, data) = parser.parse_entries(false)?;
+    if !data.is_empty() {
+        return Err(IoError::Parse {
+            line: parser.lexer.line,
+            message: "unexpected top-level value in dictionary file".into(),
+        });
+    }
+    Ok(dict)
+}
+
+/// Parses a file that may contain both keyword entries and bare top-level
+/// values, returning `(entries, values)`.
+///
+/// # Errors
+///
+/// Returns [`IoError::Parse`] on malformed input.
+pub fn parse_file(text: &str) -> Result<(Dictionary, Vec<Value>), IoError> {
+    Parser::new(text).parse_entries(false)
+}
+
+/// Formats the standard `FoamFile` header block.
+pub fn header(class: &str, location: Option<&str>, object: &str) -> String {
+    let mut s = String::new();
+    s.push_str("FoamFile\n{\n    version     2.0;\n    format      ascii;\n");
+    let _ = writeln!(s, "    class       {class};");
+    if let Some(loc) = location {
+        let _ = writeln!(s, "    location    \"{loc}\";");
+    }
+    let _ = writeln!(s, "    object      {object};");
+    s.push_str("}\n\n");
+    s
+}
+
+/// Formats a dictionary with one entry per line, recursing into sub-dictionaries.
+pub fn format_dictionary(dict: &Dictionary, indent: usize) -> String {
+    let mut s = String::new();
+    let pad = " ".repeat(indent);
+    for (keyword, values) in dict.iter() {
+        if let [Value::Dict(sub)] = values {
+            let _ = writeln!(s, "{pad}{keyword}\n{pad}{{");
+            s.push_str(&format_dictionary(sub, indent + 4));
+            let _ = writeln!(s, "{pad}}}");
+        } else {
+            let _ = write!(s, "{pad}{keyword:<11} ");
+            for (i, v) in values.iter().enumerate() {
+                if i > 0 {
+                    s.push(' ');
+                }
+                let _ = write!(s, "{v}");
+            }
+            s.push_str(";\n");
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dictionary_entries_and_subdict() {
+        let text = r#"
+            FoamFile { version 2.0; format ascii; class dictionary; object test; }
+            // comment
+            type    fixedValue; /* block
+            comment */
+            value   uniform (1 0 -2.5);
+            dimensions [0 1 -1 0 0 0 0];
+            inlet { type zeroGradient; name "in let"; }
+            div(phi,U) Gauss linear;
+        "#;
+        let d = parse_dictionary(text).unwrap();
+        assert_eq!(d.get_word("type"), Some("fixedValue"));
+        assert_eq!(
+            d.get("value").unwrap(),
+            &[
+                Value::Word("uniform".into()),
+                Value::List(vec![Value::Label(1), Value::Label(0), Value::Scalar(-2.5)])
+            ]
+        );
+        assert_eq!(
+            d.get("dimensions").unwrap(),
+            &[Value::Dimensions(vec![0.0, 1.0, -1.0, 0.0, 0.0, 0.0, 0.0])]
+        );
+        let inlet = d.get_dict("inlet").unwrap();
+        assert_eq!(inlet.get_word("name"), Some("in let"));
+        assert!(d.contains("div(phi,U)"));
+        assert_eq!(
+            d.get_dict("FoamFile").unwrap().get_word("class"),
+            Some("dictionary")
+        );
+    }
+
+    #[test]
+    fn test_parse_file_counted_lists() {
+        let text = "FoamFile { class faceList; }\n2\n(\n4(0 1 2 3)\n3{7}\n)\n";
+        let (dict, data) = parse_file(text).unwrap();
+        assert!(dict.contains("FoamFile"));
+        assert_eq!(data.len(), 1);
+        let items = data[0].as_list().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_list().unwrap().len(), 4);
+        assert_eq!(
+            items[1].as_list().unwrap(),
+            vec![Value::Label(7); 3].as_slice()
+        );
+    }
+
+    #[test]
+    fn test_parse_list_size_mismatch_returns_err() {
+        let result = parse_file("3(1 2)");
+        assert!(matches!(result, Err(IoError::Parse { .. })));
+    }
+
+    #[test]
+    fn test_parse_missing_semicolon_returns_err_with_line() {
+        let result = parse_dictionary("a 1;\nb 2\n}");
+        assert!(matches!(result, Err(IoError::Parse { line: 3, .. })));
+    }
+
+    #[test]
+    fn test_format_dictionary_round_trip() {
+        let text = "a 1; b (1 2.5 x); c { d [0 1 0]; e \"s\"; }";
+        let d = parse_dictionary(text).unwrap();
+        let formatted = format_dictionary(&d, 0);
+        assert_eq!(parse_dictionary(&formatted).unwrap(), d);
+    }
+}
@@ -0,0 +1,549 @@
+//! Reader for Gmsh ASCII mesh files (MSH 2.x).
+//!
+//! Volume elements (tetrahedra, hexahedra, prisms and pyramids) become cells.
+//! Surface elements carrying a physical tag assign the boundary faces they
+//! cover to a patch named after the physical group; boundary faces without a
+//! surface element go to a `defaultFaces` patch.
+//!
+//! Second-order elements are linearized: their corner nodes are kept and the
+//! mid-side, mid-face and interior nodes are dropped, so curved boundaries
+//! become piecewise flat. Each linearized element type is reported in
+//! [`GmshMesh::warnings`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use dugong_mesh::{BoundaryGroup, InternalFace, Mesh, PatchKind, assemble, boundary_cell_last};
+use dugong_types::tensor::Vector;
+
+use crate::error::IoError;
+use crate::polymesh::{invalid, read_text};
+
+/// Name of the patch collecting boundary faces without a surface element.
+const DEFAULT_PATCH: &str = "defaultFaces";
+
+/// A mesh read from a Gmsh file, with any import warnings.
+pub struct GmshMesh {
+    /// The imported mesh.
+    pub mesh: Mesh,
+    /// Human-readable notes on lossy conversions, such as linearized
+    /// second-order elements or ignored surface elements.
+    pub warnings: Vec<String>,
+}
+
+/// The linear shape underlying a Gmsh element type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Point,
+    Line,
+    Tri,
+    Quad,
+    Tet,
+    Hex,
+    Prism,
+    Pyramid,
+}
+
+impl Shape {
+    fn n_corners(self) -> usize {
+        match self {
+            Shape::Point => 1,
+            Shape::Line => 2,
+            Shape::Tri => 3,
+            Shape::Quad | Shape::Tet => 4,
+            Shape::Pyramid => 5,
+            Shape::Prism => 6,
+            Shape::Hex => 8,
+        }
+    }
+
+    fn dim(self) -> usize {
+        match self {
+            Shape::Point => 0,
+            Shape::Line => 1,
+            Shape::Tri | Shape::Quad => 2,
+            Shape::Tet | Shape::Hex | Shape::Prism | Shape::Pyramid => 3,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Shape::Point => "points",
+            Shape::Line => "lines",
+            Shape::Tri => "triangles",
+            Shape::Quad => "quadrilaterals",
+            Shape::Tet => "tetrahedra",
+            Shape::Hex => "hexahedra",
+            Shape::Prism => "prisms",
+            Shape::Pyramid => "pyramids",
+        }
+    }
+
+    /// Returns the outward faces of a positively oriented element, as
+    /// indices into its corner nodes.
+    fn faces(self) -> &'static [&'static [usize]] {
+        match self {
+            Shape::Tet => &[&[0, 2, 1], &[0, 1, 3], &[0, 3, 2], &[1, 2, 3]],
+            Shape::Hex => &[
+                &[0, 3, 2, 1],
+                &[4, 5, 6, 7],
+                &[0, 1, 5, 4],
+                &[1, 2, 6, 5],
+                &[2, 3, 7, 6],
+                &[3, 0, 4, 7],
+            ],
+            Shape::Prism => &[
+                &[0, 2, 1],
+                &[3, 4, 5],
+                &[0, 1, 4, 3],
+                &[1, 2, 5, 4],
+                &[2, 0, 3, 5],
+            ],
+            Shape::Pyramid => &[
+                &[0, 3, 2, 1],
+                &[0, 1, 4],
+                &[1, 2, 4],
+                &[2, 3, 4],
+                &[3, 0, 4],
+            ],
+            Shape::Point | Shape::Line | Shape::Tri | Shape::Quad => &[],
+        }
+    }
+}
+
+/// Returns the shape, node count and whether the type is second order, for
+/// the supported Gmsh element types.
+fn element_type(code: usize) -> Option<(Shape, usize, bool)> {
+    Some(match code {
+        1 => (Shape::Line, 2, false),
+        2 => (Shape::Tri, 3, false),
+        3 => (Shape::Quad, 4, false),
+        4 => (Shape::Tet, 4, false),
+        5 => (Shape::Hex, 8, false),
+        6 => (Shape::Prism, 6, false),
+        7 => (Shape::Pyramid, 5, false),
+        8 => (Shape::Line, 3, true),
+        9 => (Shape::Tri, 6, true),
+        10 => (Shape::Quad, 9, true),
+        11 => (Shape::Tet, 10, true),
+        12 => (Shape::Hex, 27, true),
+        13 => (Shape::Prism, 18, true),
+        14 => (Shape::Pyramid, 14, true),
+        15 => (Shape::Point, 1, false),
+        16 => (Shape::Quad, 8, true),
+        17 => (Shape::Hex, 20, true),
+        18 => (Shape::Prism, 15, true),
+        19 => (Shape::Pyramid, 13, true),
+        _ => return None,
+    })
+}
+
+/// Reads a Gmsh ASCII MSH 2.x file.
+///
+/// # Errors
+///
+/// Returns `Err` if the file cannot be read or parsed; see [`parse_gmsh`].
+pub fn read_gmsh(path: &Path) -> Result<GmshMesh, IoError> {
+    parse_gmsh(&read_text(path)?)
+}
+
+/// Parses Gmsh ASCII MSH 2.x data.
+///
+/// # Errors
+///
+/// Returns `Err` if the data is not ASCII MSH 2.x, contains an unsupported
+/// element type or an unknown node, has a face shared by more than two
+/// cells, or yields an invalid mesh.
+pub fn parse_gmsh(text: &str) -> Result<GmshMesh, IoError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty());
+    let mut version_ok = false;
+    let mut names: HashMap<(usize, usize), String> = HashMap::new();
+    let mut nodes: Vec<Vector> = Vec::new();
+    let mut node_slot: HashMap<usize, usize> = HashMap::new();
+    // (shape, second order, physical tag, node ids)
+    let mut elements: Vec<(Shape, bool, usize, Vec<usize>)> = Vec::new();
+
+    while let Some((line, header)) = lines.next() {
+        let Some(section) = header.strip_prefix('$') else {
+            return Err(parse_error(
+                line,
+                format!("expected a section, got {header:?}"),
+            ));
+        };
+        let end = format!("$End{section}");
+        let body: Vec<(usize, &str)> = lines.by_ref().take_while(|(_, l)| *l != end).collect();
+        match section {
+            "MeshFormat" => {
+                let (line, format) = body
+                    .first()
+                    .ok_or_else(|| parse_error(line, "empty $MeshFormat"))?;
+                let fields: Vec<&str> = format.split_whitespace().collect();
+                if !(fields.len() >= 2 && fields[0].starts_with("2.") && fields[1] == "0") {
+                    return Err(parse_error(
+                        *line,
+                        format!("only ASCII MSH 2.x is supported, got {format:?}"),
+                    ));
+                }
+                version_ok = true;
+            }
+            "PhysicalNames" => {
+                for &(line, entry) in body.iter().skip(1) {
+                    let mut fields = entry.splitn(3, char::is_whitespace);
+                    let (Some(dim), Some(tag), Some(name)) =
+                        (fields.next(), fields.next(), fields.next())
+                    else {
+                        return Err(parse_error(line, "expected `dim tag \"name\"`"));
+                    };
+                    names.insert(
+                        (parse_num(line, dim)?, parse_num(line, tag)?),
+                        name.trim().trim_matches('"').into(),
+                    );
+                }
+            }
+            "Nodes" => {
+                for &(line, entry) in body.iter().skip(1) {
+                    let v = entry.split_whitespace().collect::<Vec<_>>();
+                    let [id, x, y, z] = v[..] else {
+                        return Err(parse_error(line, "expected `id x y z`"));
+                    };
+                    node_slot.insert(parse_num(line, id)?, nodes.len());
+                    nodes.push(Vector::new(
+                        parse_num(line, x)?,
+                        parse_num(line, y)?,
+                        parse_num(line, z)?,
+                    ));
+                }
+            }
+            "Elements" => {
+                for &(line, entry) in body.iter().skip(1) {
+                    let v = entry
+                        .split_whitespace()
+                        .map(|s| parse_num::<usize>(line, s))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if v.len() < 3 || v.len() < 3 + v[2] {
+                        return Err(parse_error(line, "truncated element"));
+                    }
+                    let (shape, n_nodes, second_order) = element_type(v[1]).ok_or_else(|| {
+                        parse_error(line, format!("unsupported element type {}", v[1]))
+                    })?;
+                    let node_ids = &v[3 + v[2]..];
+                    if node_ids.len() != n_nodes {
+                        return Err(parse_error(
+                            line,
+                            format!("expected {n_nodes} nodes, got {}", node_ids.len()),
+                        ));
+                    }
+                    let physical = if v[2] > 0 { v[3] } else { 0 };
+                    let corners = node_ids[..shape.n_corners()].to_vec();
+                    elements.push((shape, second_order, physical, corners));
+                }
+            }
+            // Other sections ($NodeData, $Periodic, ...) are not needed.
+            _ => {}
+        }
+    }
+    if !version_ok {
+        return Err(invalid("Gmsh file", "missing $MeshFormat"));
+    }
+
+    let mut warnings = Vec::new();
+    let mut linearized: BTreeMap<&str, usize> = BTreeMap::new();
+    for (shape, _, _, _) in elements.iter().filter(|e| e.1 && e.0.dim() >= 2) {
+        *linearized.entry(shape.name()).or_default() += 1;
+    }
+    for (name, count) in linearized {
+        warnings.push(format!(
+            "linearized {count} second-order {name} by dropping their higher-order nodes"
+        ));
+    }
+
+    // Renumber the corner nodes of volume and surface elements in file
+    // order; higher-order nodes are dropped.
+    let mut used = vec![false; nodes.len()];
+    for (_, _, _, corners) in elements.iter().filter(|e| e.0.dim() >= 2) {
+        for id in corners {
+            let slot = *node_slot
+                .get(id)
+                .ok_or_else(|| invalid("Gmsh file", format!("unknown node {id}")))?;
+            used[slot] = true;
+        }
+    }
+    let mut points = Vec::new();
+    let mut point_of = vec![usize::MAX; nodes.len()];
+    for slot in (0..nodes.len()).filter(|&s| used[s]) {
+        point_of[slot] = points.len();
+        points.push(nodes[slot]);
+    }
+    let local = |corners: &[usize]| -> Vec<usize> {
+        // Safety: every corner id was resolved above.
+        corners.iter().map(|id| point_of[node_slot[id]]).collect()
+    };
+
+    // Every cell face, keyed by its sorted vertices so that the two sides
+    // of an internal face sort next to each other.
+    let mut cell_faces: Vec<(Vec<usize>, usize, Vec<usize>)> = Vec::new();
+    let volumes = elements.iter().filter(|e| e.0.dim() == 3);
+    for (cell, (shape, _, _, corners)) in volumes.enumerate() {
+        let corners = local(corners);
+        for face in shape.faces() {
+            let face: Vec<usize> = face.iter().map(|&i| corners[i]).collect();
+            let mut key = face.clone();
+            key.sort_unstable();
+            cell_faces.push((key, cell, face));
+        }
+    }
+    let order = {
+        let mut order: Vec<usize> = (0..cell_faces.len()).collect();
+        order.sort_by(|&a, &b| cell_faces[a].0.cmp(&cell_faces[b].0).then(a.cmp(&b)));
+        order
+    };
+
+    let mut internal = Vec::new();
+    let mut boundary = Vec::new();
+    let mut i = 0;
+    while i < order.len() {
+        let first = &cell_faces[order[i]];
+        let shared = order[i + 1..]
+            .iter()
+            .take_while(|&&j| cell_faces[j].0 == first.0)
+            .count();
+        match shared {
+            0 => boundary.push(order[i]),
+            1 => internal.push((first.1, cell_faces[order[i + 1]].1, order[i])),
+            _ => {
+                return Err(invalid(
+                    "Gmsh file",
+                    format!("face {:?} is shared by more than two cells", first.2),
+                ));
+            }
+        }
+        i += shared + 1;
+    }
+    internal.sort_unstable();
+    boundary.sort_unstable();
+
+    // Patches from physical surface groups, in tag order.
+    let mut patch_of: HashMap<Vec<usize>, usize> = HashMap::new();
+    for (_, _, physical, corners) in elements.iter().filter(|e| e.0.dim() == 2) {
+        let mut key = local(corners);
+        key.sort_unstable();
+        patch_of.insert(key, *physical);
+    }
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    let mut default_faces = Vec::new();
+    let mut matched = 0;
+    for &f in &boundary {
+        match patch_of.get(&cell_faces[f].0) {
+            Some(&tag) => {
+                groups.entry(tag).or_default().push(f);
+                matched += 1;
+            }
+            None => default_faces.push(f),
+        }
+    }
+    if matched < patch_of.len() {
+        warnings.push(format!(
+            "ignored {} surface elements not on the boundary",
+            patch_of.len() - matched
+        ));
+    }
+    if !default_faces.is_empty() {
+        warnings.push(format!(
+            "{} boundary faces have no surface element and were put in `{DEFAULT_PATCH}`",
+            default_faces.len()
+        ));
+    }
+
+    let mut internal: Vec<InternalFace> = internal
+        .into_iter()
+        .map(|(o, n, f)| (cell_faces[f].2.clone(), o, n))
+        .collect();
+    let named = groups.into_iter().map(|(tag, fs)| {
+        let name = names
+            .get(&(2, tag))
+            .cloned()
+            .unwrap_or_else(|| format!("patch{tag}"));
+        (name, fs)
+    });
+    let default = (!default_faces.is_empty()).then(|| (DEFAULT_PATCH.to_string(), default_faces));
+    let mut patches: Vec<BoundaryGroup> = named
+        .chain(default)
+        .map(|(name, fs)| BoundaryGroup {
+            name,
+            kind: PatchKind::Patch,
+            faces: fs
+                .into_iter()
+                .map(|f| (cell_faces[f].2.clone(), cell_faces[f].1))
+                .collect(),
+        })
+        .collect();
+
+    // Cells are numbered in element order; make sure the last one is not
+    // lost for owning no face.
+    boundary_cell_last(&mut internal, &mut patches);
+    let (mesh, _) = assemble(points, internal, patches)?;
+    Ok(GmshMesh { mesh, warnings })
+}
+
+fn parse_error(line: usize, message: impl Into<String>) -> IoError {
+    IoError::Parse {
+        line,
+        message: message.into(),
+    }
+}
+
+fn parse_num<T: std::str::FromStr>(line: usize, s: &str) -> Result<T, IoError> {
+    s.parse()
+        .map_err(|_| parse_error(line, format!("invalid number {s:?}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two unit hexahedra along x, with physical surfaces `inlet` (x = 0)
+    /// and `outlet` (x = 2). The remaining boundary has no surface elements.
+    const TWO_HEXES: &str = "\
+$MeshFormat
+2.2 0 8
+$EndMeshFormat
+$PhysicalNames
+3
+2 1 \"inlet\"
+2 2 \"outlet\"
+3 3 \"fluid\"
+$EndPhysicalNames
+$Nodes
+12
+1 0 0 0
+2 1 0 0
+3 2 0 0
+4 0 1 0
+5 1 1 0
+6 2 1 0
+7 0 0 1
+8 1 0 1
+9 2 0 1
+10 0 1 1
+11 1 1 1
+12 2 1 1
+$EndNodes
+$Elements
+4
+1 3 2 1 1 1 7 10 4
+2 3 2 2 2 3 6 12 9
+3 5 2 3 1 1 2 5 4 7 8 11 10
+4 5 2 3 1 2 3 6 5 8 9 12 11
+$EndElements
+";
+
+    #[test]
+    fn test_parse_gmsh_linear_hexes() {
+        let GmshMesh { mesh, warnings } = parse_gmsh(TWO_HEXES).unwrap();
+        assert_eq!(mesh.n_cells(), 2);
+        assert_eq!(mesh.n_internal_faces(), 1);
+        let names: Vec<&str> = mesh.patches().iter().map(|p| p.name()).collect();
+        assert_eq!(names, ["inlet", "outlet", DEFAULT_PATCH]);
+        assert_eq!(mesh.patch("inlet").unwrap().size(), 1);
+        assert_eq!(mesh.patch(DEFAULT_PATCH).unwrap().size(), 8);
+        assert!(mesh.cell_volumes().iter().all(|&v| (v - 1.0).abs() < 1e-12));
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_gmsh_linearizes_second_order_tet() {
+        // A 10-node tetrahedron with all four faces as 6-node triangles.
+        let text = "\
+$MeshFormat
+2.2 0 8
+$EndMeshFormat
+$Nodes
+10
+1 0 0 0
+2 1 0 0
+3 0 1 0
+4 0 0 1
+5 0.5 0 0
+6 0.5 0.5 0
+7 0 0.5 0
+8 0 0 0.5
+9 0 0.5 0.5
+10 0.5 0 0.5
+$EndNodes
+$Elements
+5
+1 9 2 7 1 1 3 2 7 6 5
+2 9 2 7 1 1 2 4 5 10 8
+3 9 2 7 1 1 4 3 8 9 7
+4 9 2 7 1 2 3 4 6 9 10
+5 11 2 1 1 1 2 3 4 5 6 7 8 9 10
+$EndElements
+";
+        let GmshMesh { mesh, warnings } = parse_gmsh(text).unwrap();
+        assert_eq!(mesh.n_points(), 4);
+        assert_eq!(mesh.n_cells(), 1);
+        assert!((mesh.cell_volumes()[0] - 1.0 / 6.0).abs() < 1e-12);
+        let names: Vec<&str> = mesh.patches().iter().map(|p| p.name()).collect();
+        assert_eq!(names, ["patch7"]);
+        assert_eq!(mesh.patches()[0].size(), 4);
+        assert_eq!(
+            warnings,
+            [
+                "linearized 1 second-order tetrahedra by dropping their higher-order nodes",
+                "linearized 4 second-order triangles by dropping their higher-order nodes",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_gmsh_interior_last_element_keeps_all_cells() {
+        // A 3x3x3 block of unit hexahedra listing the centre hex last, so the
+        // last element owns no boundary face.
+        let node = |i: usize, j: usize, k: usize| 1 + i + 4 * j + 16 * k;
+        let mut text = String::from("$MeshFormat\n2.2 0 8\n$EndMeshFormat\n$Nodes\n64\n");
+        for k in 0..4 {
+            for j in 0..4 {
+                for i in 0..4 {
+                    text += &format!("{} {i} {j} {k}\n", node(i, j, k));
+                }
+            }
+        }
+        let mut hexes: Vec<[usize; 3]> = (0..27)
+            .map(|c| [c % 3, c / 3 % 3, c / 9])
+            .filter(|&c| c != [1, 1, 1])
+            .collect();
+        hexes.push([1, 1, 1]);
+        text += "$EndNodes\n$Elements\n27\n";
+        for (e, [i, j, k]) in hexes.into_iter().enumerate() {
+            text += &format!(
+                "{} 5 2 1 1 {} {} {} {} {} {} {} {}\n",
+                e + 1,
+                node(i, j, k),
+                node(i + 1, j, k),
+                node(i + 1, j + 1, k),
+                node(i, j + 1, k),
+                node(i, j, k + 1),
+                node(i + 1, j, k + 1),
+                node(i + 1, j + 1, k + 1),
+                node(i, j + 1, k + 1),
+            );
+        }
+        text += "$EndElements\n";
+
+        let GmshMesh { mesh, .. } = parse_gmsh(&text).unwrap();
+        assert_eq!(mesh.n_cells(), 27);
+        assert_eq!(mesh.n_internal_faces(), 54);
+        assert_eq!(mesh.patch(DEFAULT_PATCH).unwrap().size(), 54);
+        assert!(mesh.cell_volumes().iter().all(|&v| (v - 1.0).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_parse_gmsh_rejects_msh4() {
+        let result = parse_gmsh("$MeshFormat\n4.1 0 8\n$EndMeshFormat\n");
+        assert!(matches!(result, Err(IoError::Parse { line: 2, .. })));
+    }
+}
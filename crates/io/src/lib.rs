@@ -1,5 +1,20 @@
 //! Input/output operations
 //!
-//! Provides configuration file parsing, field I/O, and mesh reading.
+//! Provides case directory management, configuration file parsing, field
+//! I/O, binary field checkpoints, mesh reading (OpenFOAM `polyMesh`, Gmsh and
+//! a binary container), mesh writing as VTK unstructured grids, and surface
+//! geometry reading.
 
-// TODO: Implement I/O operations
+pub mod binary;
+pub mod case;
+pub mod checkpoint;
+mod error;
+pub mod field;
+pub mod foam;
+pub mod gmsh;
+pub mod polymesh;
+pub mod surface;
+pub mod vtu;
+
+pub use case::Case;
+pub use error::IoError;
@@ -0,0 +1,594 @@
+//! Reader and writer for OpenFOAM `constant/polyMesh` directories (ASCII).
+//!
+//! Handles `points`, `faces`, `owner`, `neighbour`, `boundary`, and the
+//! optional `cellZones`, `faceZones`, and `pointZones` files.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use dugong_mesh::{CoupledTransform, Mesh, Patch, PatchKind, PrimitiveMesh, Zone, ZoneKind};
+use dugong_runtime::{Dictionary, Value};
+use dugong_types::tensor::Vector;
+
+use crate::error::IoError;
+use crate::foam;
+
+pub(crate) fn read_text(path: &Path) -> Result<String, IoError> {
+    fs::read_to_string(path).map_err(|source| IoError::File {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub(crate) fn write_text(path: &Path, text: &str) -> Result<(), IoError> {
+    fs::write(path, text).map_err(|source| IoError::File {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub(crate) fn invalid(what: &str, message: impl Into<String>) -> IoError {
+    IoError::InvalidData {
+        what: what.to_string(),
+        message: message.into(),
+    }
+}
+
+/// Reads the single top-level list of a list-valued file such as `points`.
+fn read_list_file(path: &Path) -> Result<Vec<Value>, IoError> {
+    parse_list(&read_text(path)?, &path.display().to_string())
+}
+
+/// Parses text holding a single top-level list, with or without a
+/// `FoamFile` header.
+fn parse_list(text: &str, what: &str) -> Result<Vec<Value>, IoError> {
+    let (_, mut data) = foam::parse_file(text)?;
+    match (data.pop(), data.is_empty()) {
+        (Some(Value::List(items)), true) => Ok(items),
+        _ => Err(invalid(what, "expected exactly one top-level list")),
+    }
+}
+
+pub(crate) fn value_to_vector(v: &Value, what: &str) -> Result<Vector, IoError> {
+    match v.as_list() {
+        Some([x, y, z]) => match (x.as_scalar(), y.as_scalar(), z.as_scalar()) {
+            (Some(x), Some(y), Some(z)) => Ok(Vector::new(x, y, z)),
+            _ => Err(invalid(what, "non-numeric vector component")),
+        },
+        _ => Err(invalid(what, format!("expected a 3-vector, got {v}"))),
+    }
+}
+
+pub(crate) fn value_to_labels(v: &Value, what: &str) -> Result<Vec<usize>, IoError> {
+    v.as_list()
+        .ok_or_else(|| invalid(what, format!("expected a label list, got {v}")))?
+        .iter()
+        .map(|l| {
+            l.as_label()
+                .ok_or_else(|| invalid(what, format!("expected a label, got {l}")))
+        })
+        .collect()
+}
+
+/// Splits a `name { ... } name { ... }` list into `(name, dict)` pairs.
+fn named_dicts<'a>(
+    items: &'a [Value],
+    what: &str,
+) -> Result<Vec<(&'a str, &'a Dictionary)>, IoError> {
+    if !items.len().is_multiple_of(2) {
+        return Err(invalid(what, "expected alternating names and dictionaries"));
+    }
+    items
+        .chunks_exact(2)
+        .map(|pair| match (pair[0].as_word(), pair[1].as_dict()) {
+            (Some(name), Some(dict)) => Ok((name, dict)),
+            _ => Err(invalid(what, "expected alternating names and dictionaries")),
+        })
+        .collect()
+}
+
+/// Reads the labels of a `keyword List<label> N(...)` entry.
+fn dict_labels(dict: &Dictionary, keyword: &str, what: &str) -> Result<Vec<usize>, IoError> {
+    let values = dict
+        .get(keyword)
+        .ok_or_else(|| invalid(what, format!("missing '{keyword}'")))?;
+    match values {
+        [list] | [Value::Word(_), list] => value_to_labels(list, what),
+        _ => Err(invalid(what, format!("malformed '{keyword}'"))),
+    }
+}
+
+/// Builds the patch kind from the `type` keyword and, for coupled patches,
+/// the additional entries of the patch dictionary.
+fn read_patch_kind(name: &str, type_name: &str, dict: &Dictionary) -> Result<PatchKind, IoError> {
+    let label = |key: &str| {
+        dict.get_label(key)
+            .ok_or_else(|| invalid("boundary", format!("patch '{name}' needs {key}")))
+    };
+    match type_name {
+        "cyclic" | "cyclicAMI" => {
+            let neighbor_patch = dict
+                .get_word("neighbourPatch")
+                .ok_or_else(|| invalid("boundary", format!("patch '{name}' needs neighbourPatch")))?
+                .to_string();
+            let transform = read_transform(name, dict)?;
+            Ok(if type_name == "cyclic" {
+                PatchKind::Cyclic {
+                    neighbor_patch,
+                    transform,
+                }
+            } else {
+                PatchKind::CyclicAmi {
+                    neighbor_patch,
+                    transform,
+                }
+            })
+        }
+        "processor" => Ok(PatchKind::Processor {
+            my_rank: label("myProcNo")?,
+            neighbor_rank: label("neighbProcNo")?,
+            transform: read_transform(name, dict)?,
+        }),
+        _ => PatchKind::from_type_name(type_name).ok_or_else(|| {
+            invalid(
+                "boundary",
+                format!("patch '{name}' has unknown type '{type_name}'"),
+            )
+        }),
+    }
+}
+
+/// Reads the optional `transform` entries of a coupled patch. Rotation
+/// angles are given in degrees, as in OpenFOAM.
+fn read_transform(name: &str, dict: &Dictionary) -> Result<CoupledTransform, IoError> {
+    let vector = |key: &str| match dict.get(key) {
+        Some([v]) => value_to_vector(v, "boundary"),
+        _ => Err(invalid("boundary", format!("patch '{name}' needs {key}"))),
+    };
+    match dict.get_word("transform").unwrap_or("none") {
+        "translational" => Ok(CoupledTransform::Translational {
+            separation: vector("separationVector")?,
+        }),
+        "rotational" => Ok(CoupledTransform::Rotational {
+            axis: vector("rotationAxis")?,
+            center: vector("rotationCentre")?,
+            angle: dict
+                .get_scalar("rotationAngle")
+                .ok_or_else(|| invalid("boundary", format!("patch '{name}' needs rotationAngle")))?
+                .to_radians(),
+        }),
+        _ => Ok(CoupledTransform::None),
+    }
+}
+
+/// Formats the type-specific entries of a patch dictionary, one per line.
+fn format_patch_kind(kind: &PatchKind) -> String {
+    match kind {
+        PatchKind::Cyclic {
+            neighbor_patch,
+            transform,
+        }
+        | PatchKind::CyclicAmi {
+            neighbor_patch,
+            transform,
+        } => format!(
+            "\n    neighbourPatch {neighbor_patch};{}",
+            format_transform(transform)
+        ),
+        PatchKind::Processor {
+            my_rank,
+            neighbor_rank,
+            transform,
+        } => format!(
+            "\n    myProcNo {my_rank};\n    neighbProcNo {neighbor_rank};{}",
+            format_transform(transform)
+        ),
+        _ => String::new(),
+    }
+}
+
+fn format_transform(transform: &CoupledTransform) -> String {
+    match transform {
+        CoupledTransform::None => String::new(),
+        CoupledTransform::Translational { separation } => format!(
+            "\n    transform translational;\n    separationVector {};",
+            format_vector(separation)
+        ),
+        CoupledTransform::Rotational {
+            axis,
+            center,
+            angle,
+        } => format!(
+            "\n    transform rotational;\n    rotationAxis {};\n    rotationCentre {};\n    rotationAngle {:?};",
+            format_vector(axis),
+            format_vector(center),
+            angle.to_degrees()
+        ),
+    }
+}
+
+/// Parses the contents of a `boundary` file.
+pub(crate) fn parse_patches(text: &str) -> Result<Vec<Patch>, IoError> {
+    let items = parse_list(text, "boundary")?;
+    let mut patches = Vec::new();
+    for (name, dict) in named_dicts(&items, "boundary")? {
+        let type_name = dict
+            .get_word("type")
+            .ok_or_else(|| invalid("boundary", format!("patch '{name}' has no type")))?;
+        let kind = read_patch_kind(name, type_name, dict)?;
+        let (Some(start), Some(size)) = (dict.get_label("startFace"), dict.get_label("nFaces"))
+        else {
+            return Err(invalid(
+                "boundary",
+                format!("patch '{name}' needs startFace and nFaces"),
+            ));
+        };
+        patches.push(Patch::new(name, kind, start, size));
+    }
+    Ok(patches)
+}
+
+/// Parses the contents of a `cellZones`, `faceZones` or `pointZones` file.
+pub(crate) fn parse_zones(text: &str, kind: ZoneKind) -> Result<Vec<Zone>, IoError> {
+    let (what, keyword) = match kind {
+        ZoneKind::Cell => ("cellZones", "cellLabels"),
+        ZoneKind::Face => ("faceZones", "faceLabels"),
+        ZoneKind::Point => ("pointZones", "pointLabels"),
+    };
+    let items = parse_list(text, what)?;
+    named_dicts(&items, what)?
+        .into_iter()
+        .map(|(name, dict)| Ok(Zone::new(name, dict_labels(dict, keyword, what)?)))
+        .collect()
+}
+
+/// Reads a mesh from an OpenFOAM `polyMesh` directory.
+///
+/// Zone files are optional; missing zone files yield no zones.
+///
+/// # Errors
+///
+/// Returns `Err` if a required file is missing or malformed, if a patch has an
+/// unsupported type, or if the resulting topology is invalid.
+pub fn read_polymesh(dir: &Path) -> Result<Mesh, IoError> {
+    let points = read_list_file(&dir.join("points"))?
+        .iter()
+        .map(|v| value_to_vector(v, "points"))
+        .collect::<Result<Vec<_>, _>>()?;
+    let faces = read_list_file(&dir.join("faces"))?
+        .iter()
+        .map(|v| value_to_labels(v, "faces"))
+        .collect::<Result<Vec<_>, _>>()?;
+    let owner = value_to_labels(&Value::List(read_list_file(&dir.join("owner"))?), "owner")?;
+    let neighbor = value_to_labels(
+        &Value::List(read_list_file(&dir.join("neighbour"))?),
+        "neighbour",
+    )?;
+    let patches = parse_patches(&read_text(&dir.join("boundary"))?)?;
+
+    let primitive = PrimitiveMesh::new(points, faces, owner, neighbor)?;
+    let mut mesh = Mesh::new(primitive, patches)?;
+
+    for (file, kind) in [
+        ("cellZones", ZoneKind::Cell),
+        ("faceZones", ZoneKind::Face),
+        ("pointZones", ZoneKind::Point),
+    ] {
+        let path = dir.join(file);
+        if path.exists() {
+            for zone in parse_zones(&read_text(&path)?, kind)? {
+                mesh.add_zone(kind, zone)?;
+            }
+        }
+    }
+    Ok(mesh)
+}
+
+pub(crate) fn format_labels(labels: &[usize]) -> String {
+    let mut s = String::with_capacity(labels.len() * 4 + 8);
+    let _ = write!(s, "{}(", labels.len());
+    for (i, l) in labels.iter().enumerate() {
+        if i > 0 {
+            s.push(' ');
+        }
+        let _ = write!(s, "{l}");
+    }
+    s.push(')');
+    s
+}
+
+pub(crate) fn format_vector(v: &Vector) -> String {
+    format!("({:?} {:?} {:?})", v.x(), v.y(), v.z())
+}
+
+fn format_list<T>(items: &[T], mut item: impl FnMut(&mut String, &T)) -> String {
+    let mut s = String::new();
+    let _ = writeln!(s, "{}\n(", items.len());
+    for x in items {
+        item(&mut s, x);
+        s.push('\n');
+    }
+    s.push_str(")\n");
+    s
+}
+
+/// Formats the body of a zone file.
+pub(crate) fn format_zones(zones: &[Zone], kind: ZoneKind) -> String {
+    let (type_name, keyword) = match kind {
+        ZoneKind::Cell => ("cellZone", "cellLabels"),
+        ZoneKind::Face => ("faceZone", "faceLabels"),
+        ZoneKind::Point => ("pointZone", "pointLabels"),
+    };
+    format_list(zones, |s, z| {
+        let _ = write!(
+            s,
+            "{}\n{{\n    type {type_name};\n    {keyword} List<label> {};\n",
+            z.name(),
+            format_labels(z.indices())
+        );
+        if kind == ZoneKind::Face {
+            let flips = vec![0usize; z.len()];
+            let _ = writeln!(s, "    flipMap List<bool> {};", format_labels(&flips));
+        }
+        s.push('}');
+    })
+}
+
+/// Formats the body of a `boundary` file.
+pub(crate) fn format_patches(patches: &[Patch]) -> String {
+    format_list(patches, |s, p| {
+        let _ = write!(
+            s,
+            "{}\n{{\n    type {};{}\n    nFaces {};\n    startFace {};\n}}",
+            p.name(),
+            p.kind().type_name(),
+            format_patch_kind(p.kind()),
+            p.size(),
+            p.start()
+        );
+    })
+}
+
+/// Writes a mesh to an OpenFOAM `polyMesh` directory, creating it if needed.
+///
+/// Zone files are written only for zone kinds that are non-empty.
+///
+/// # Errors
+///
+/// Returns `Err` if the directory cannot be created or a file cannot be written.
+pub fn write_polymesh(mesh: &Mesh, dir: &Path) -> Result<(), IoError> {
+    fs::create_dir_all(dir).map_err(|source| IoError::File {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+    let loc = Some("constant/polyMesh");
+
+    let mut text = foam::header("vectorField", loc, "points");
+    text.push_str(&format_list(mesh.points(), |s, p| {
+        s.push_str(&format_vector(p))
+    }));
+    write_text(&dir.join("points"), &text)?;
+
+    let mut text = foam::header("faceList", loc, "faces");
+    text.push_str(&format_list(mesh.faces(), |s, f| {
+        s.push_str(&format_labels(f))
+    }));
+    write_text(&dir.join("faces"), &text)?;
+
+    let mut text = foam::header("labelList", loc, "owner");
+    text.push_str(&format_list(mesh.owner(), |s, o| {
+        let _ = write!(s, "{o}");
+    }));
+    write_text(&dir.join("owner"), &text)?;
+
+    let mut text = foam::header("labelList", loc, "neighbour");
+    text.push_str(&format_list(mesh.neighbor(), |s, n| {
+        let _ = write!(s, "{n}");
+    }));
+    write_text(&dir.join("neighbour"), &text)?;
+
+    let mut text = foam::header("polyBoundaryMesh", loc, "boundary");
+    text.push_str(&format_patches(mesh.patches()));
+    write_text(&dir.join("boundary"), &text)?;
+
+    for (file, kind) in [
+        ("cellZones", ZoneKind::Cell),
+        ("faceZones", ZoneKind::Face),
+        ("pointZones", ZoneKind::Point),
+    ] {
+        let zones = mesh.zones(kind);
+        if zones.is_empty() {
+            continue;
+        }
+        let mut text = foam::header("regIOobject", loc, file);
+        text.push_str(&format_zones(zones, kind));
+        write_text(&dir.join(file), &text)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Returns a fresh, empty directory under the system temp directory.
+    pub(crate) fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dugong-io-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Two unit cubes side by side along x, with `inlet`, `outlet` and `walls` patches.
+    pub(crate) fn two_cell_mesh() -> Mesh {
+        let points = vec![
+            Vector::new(0.0, 0.0, 0.0),
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(1.0, 1.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+            Vector::new(0.0, 0.0, 1.0),
+            Vector::new(1.0, 0.0, 1.0),
+            Vector::new(1.0, 1.0, 1.0),
+            Vector::new(0.0, 1.0, 1.0),
+            Vector::new(2.0, 0.0, 0.0),
+            Vector::new(2.0, 1.0, 0.0),
+            Vector::new(2.0, 0.0, 1.0),
+            Vector::new(2.0, 1.0, 1.0),
+        ];
+        let faces = vec![
+            vec![1, 2, 6, 5],
+            vec![0, 4, 7, 3],
+            vec![8, 9, 11, 10],
+            vec![0, 3, 2, 1],
+            vec![4, 5, 6, 7],
+            vec![0, 1, 5, 4],
+            vec![3, 7, 6, 2],
+            vec![1, 8, 10, 5],
+            vec![2, 6, 11, 9],
+            vec![1, 2, 9, 8],
+            vec![5, 10, 11, 6],
+        ];
+        let owner = vec![0, 0, 1, 0, 0, 0, 0, 1, 1, 1, 1];
+        let primitive = PrimitiveMesh::new(points, faces, owner, vec![1]).unwrap();
+        let patches = vec![
+            Patch::new("inlet", PatchKind::Patch, 1, 1),
+            Patch::new("outlet", PatchKind::Patch, 2, 1),
+            Patch::new("walls", PatchKind::Wall, 3, 8),
+        ];
+        Mesh::new(primitive, patches).unwrap()
+    }
+
+    #[test]
+    fn test_polymesh_round_trip_preserves_topology() {
+        let mesh = two_cell_mesh();
+        let dir = temp_dir("polymesh-round-trip");
+        write_polymesh(&mesh, &dir).unwrap();
+        let read = read_polymesh(&dir).unwrap();
+
+        assert_eq!(read.points(), mesh.points());
+        assert_eq!(read.faces(), mesh.faces());
+        assert_eq!(read.owner(), mesh.owner());
+        assert_eq!(read.neighbor(), mesh.neighbor());
+        assert_eq!(read.patches(), mesh.patches());
+        assert!(!dir.join("cellZones").exists());
+    }
+
+    #[test]
+    fn test_polymesh_round_trip_preserves_zones() {
+        let mut mesh = two_cell_mesh();
+        mesh.add_cell_zone(Zone::new("porous", [1])).unwrap();
+        mesh.add_cell_zone(Zone::new("all", [0, 1])).unwrap();
+        mesh.add_face_zone(Zone::new("baffle", [0])).unwrap();
+        mesh.add_point_zone(Zone::new("corners", [0, 11])).unwrap();
+        let dir = temp_dir("polymesh-zones");
+        write_polymesh(&mesh, &dir).unwrap();
+        let read = read_polymesh(&dir).unwrap();
+
+        assert_eq!(read.cell_zones(), mesh.cell_zones());
+        assert_eq!(read.face_zones(), mesh.face_zones());
+        assert_eq!(read.point_zones(), mesh.point_zones());
+    }
+
+    #[test]
+    fn test_read_polymesh_unknown_patch_type_returns_err() {
+        let mesh = two_cell_mesh();
+        let dir = temp_dir("polymesh-bad-type");
+        write_polymesh(&mesh, &dir).unwrap();
+        let boundary = read_text(&dir.join("boundary")).unwrap();
+        write_text(
+            &dir.join("boundary"),
+            &boundary.replace("type wall", "type bogus"),
+        )
+        .unwrap();
+        assert!(matches!(
+            read_polymesh(&dir),
+            Err(IoError::InvalidData { .. })
+        ));
+    }
+
+    #[test]
+    fn test_polymesh_round_trip_preserves_processor_patch() {
+        let mesh = two_cell_mesh();
+        let mut patches = mesh.patches().to_vec();
+        patches[1] = Patch::new(
+            "procBoundary0to1",
+            PatchKind::Processor {
+                my_rank: 0,
+                neighbor_rank: 1,
+                transform: CoupledTransform::Translational {
+                    separation: Vector::new(0.0, 0.0, 2.5),
+                },
+            },
+            2,
+            1,
+        );
+        let primitive = PrimitiveMesh::new(
+            mesh.points().to_vec(),
+            mesh.faces().to_vec(),
+            mesh.owner().to_vec(),
+            mesh.neighbor().to_vec(),
+        )
+        .unwrap();
+        let mesh = Mesh::new(primitive, patches).unwrap();
+        let dir = temp_dir("polymesh-processor");
+        write_polymesh(&mesh, &dir).unwrap();
+        let read = read_polymesh(&dir).unwrap();
+        assert_eq!(read.patches(), mesh.patches());
+    }
+
+    #[test]
+    fn test_polymesh_round_trip_preserves_cyclic_pair() {
+        let mesh = two_cell_mesh();
+        let cyclic = |other: &str, angle: f64| PatchKind::Cyclic {
+            neighbor_patch: other.to_string(),
+            transform: CoupledTransform::Rotational {
+                axis: Vector::new(0.0, 0.0, 1.0),
+                center: Vector::new(0.0, 0.0, 0.0),
+                angle,
+            },
+        };
+        let patches = vec![
+            Patch::new("left", cyclic("right", 0.5), 1, 1),
+            Patch::new("right", cyclic("left", -0.5), 2, 1),
+            mesh.patches()[2].clone(),
+        ];
+        let primitive = PrimitiveMesh::new(
+            mesh.points().to_vec(),
+            mesh.faces().to_vec(),
+            mesh.owner().to_vec(),
+            mesh.neighbor().to_vec(),
+        )
+        .unwrap();
+        let mesh = Mesh::new(primitive, patches).unwrap();
+        let dir = temp_dir("polymesh-cyclic");
+        write_polymesh(&mesh, &dir).unwrap();
+        let read = read_polymesh(&dir).unwrap();
+        for (a, b) in read.patches().iter().zip(mesh.patches()) {
+            assert_eq!(a.name(), b.name());
+            match (a.kind(), b.kind()) {
+                (
+                    PatchKind::Cyclic {
+                        neighbor_patch: na,
+                        transform: CoupledTransform::Rotational { angle: ta, .. },
+                    },
+                    PatchKind::Cyclic {
+                        neighbor_patch: nb,
+                        transform: CoupledTransform::Rotational { angle: tb, .. },
+                    },
+                ) => {
+                    assert_eq!(na, nb);
+                    assert!((ta - tb).abs() < 1e-12);
+                }
+                (ka, kb) => assert_eq!(ka, kb),
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_polymesh_missing_file_returns_err() {
+        let dir = temp_dir("polymesh-missing");
+        assert!(matches!(read_polymesh(&dir), Err(IoError::File { .. })));
+    }
+}
@@ -0,0 +1,454 @@
+//! Readers and writers for triangulated surfaces in STL (ASCII and binary)
+//! and Wavefront OBJ formats.
+//!
+//! STL stores each triangle with its own copy of the corners; coincident
+//! corners are merged on reading so the resulting [`TriSurface`] is
+//! connected. OBJ polygons are fan-triangulated.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use dugong_mesh::TriSurface;
+use dugong_types::tensor::Vector;
+
+use crate::error::IoError;
+use crate::polymesh::{invalid, read_text};
+
+/// Reads a surface file, choosing the format from the extension (`.stl` or
+/// `.obj`, case-insensitive).
+///
+/// # Errors
+///
+/// Returns `Err` if the extension is unknown or the file cannot be read or
+/// parsed.
+pub fn read_surface(path: &Path) -> Result<TriSurface, IoError> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    match ext.as_deref() {
+        Some("stl") => read_stl(path),
+        Some("obj") => read_obj(path),
+        _ => Err(invalid(
+            "surface file",
+            format!("unknown extension: {}", path.display()),
+        )),
+    }
+}
+
+/// Reads an ASCII or binary STL file.
+///
+/// # Errors
+///
+/// Returns `Err` if the file cannot be read or is malformed.
+pub fn read_stl(path: &Path) -> Result<TriSurface, IoError> {
+    let bytes = fs::read(path).map_err(|source| IoError::File {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    parse_stl(&bytes)
+}
+
+/// Parses STL data, detecting binary files by their size: an 80-byte
+/// header, a triangle count `n`, and `50 * n` bytes of triangles.
+///
+/// # Errors
+///
+/// Returns `Err` if the data is neither valid binary nor valid ASCII STL.
+pub fn parse_stl(bytes: &[u8]) -> Result<TriSurface, IoError> {
+    if bytes.len() >= 84 {
+        // Safety: the slice has exactly four bytes.
+        let n = u32::from_le_bytes(bytes[80..84].try_into().unwrap()) as usize;
+        if bytes.len() == 84 + 50 * n {
+            return parse_binary_stl(&bytes[84..], n);
+        }
+    }
+    let text = std::str::from_utf8(bytes).map_err(|e| invalid("STL", format!("not ASCII: {e}")))?;
+    parse_ascii_stl(text)
+}
+
+fn parse_binary_stl(data: &[u8], n: usize) -> Result<TriSurface, IoError> {
+    let mut builder = CornerMerger::default();
+    for record in data.chunks_exact(50).take(n) {
+        // Skip the 12-byte normal; STL normals are recomputed from corners.
+        let corner = |k: usize| {
+            let f = |i: usize| {
+                let at = 12 + 12 * k + 4 * i;
+                // Safety: the slice has exactly four bytes.
+                f32::from_le_bytes(record[at..at + 4].try_into().unwrap()) as f64
+            };
+            Vector::new(f(0), f(1), f(2))
+        };
+        builder.push([corner(0), corner(1), corner(2)]);
+    }
+    Ok(builder.finish()?)
+}
+
+fn parse_ascii_stl(text: &str) -> Result<TriSurface, IoError> {
+    let mut builder = CornerMerger::default();
+    let mut corners = Vec::with_capacity(3);
+    for (i, line) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("vertex") => corners.push(parse_vector(tokens, line_no)?),
+            Some("endloop") => {
+                let triangle: [Vector; 3] =
+                    corners.as_slice().try_into().map_err(|_| IoError::Parse {
+                        line: line_no,
+                        message: format!("facet has {} vertices, expected 3", corners.len()),
+                    })?;
+                builder.push(triangle);
+                corners.clear();
+            }
+            _ => {}
+        }
+    }
+    if !text.trim_start().starts_with("solid") {
+        return Err(invalid("STL", "missing 'solid' header"));
+    }
+    Ok(builder.finish()?)
+}
+
+/// Reads a Wavefront OBJ file. Only `v` and `f` records are used.
+///
+/// # Errors
+///
+/// Returns `Err` if the file cannot be read or is malformed.
+pub fn read_obj(path: &Path) -> Result<TriSurface, IoError> {
+    parse_obj(&read_text(path)?)
+}
+
+/// Parses Wavefront OBJ text. Face corners may be written as `v`, `v/vt`,
+/// `v//vn` or `v/vt/vn`, with negative indices counting back from the
+/// latest vertex.
+///
+/// # Errors
+///
+/// Returns `Err` on malformed records or out-of-range vertex indices.
+pub fn parse_obj(text: &str) -> Result<TriSurface, IoError> {
+    let mut points = Vec::new();
+    let mut triangles = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => points.push(parse_vector(tokens, line_no)?),
+            Some("f") => {
+                let corners = tokens
+                    .map(|t| obj_index(t, points.len(), line_no))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if corners.len() < 3 {
+                    return Err(IoError::Parse {
+                        line: line_no,
+                        message: "face has fewer than 3 vertices".to_string(),
+                    });
+                }
+                for k in 1..corners.len() - 1 {
+                    triangles.push([corners[0], corners[k], corners[k + 1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(TriSurface::new(points, triangles)?)
+}
+
+/// Writes a surface file, choosing the format from the extension (`.stl`
+/// for ASCII STL or `.obj`, case-insensitive).
+///
+/// # Errors
+///
+/// Returns `Err` if the extension is unknown or the file cannot be written.
+pub fn write_surface(surface: &TriSurface, path: &Path) -> Result<(), IoError> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    match ext.as_deref() {
+        Some("stl") => write_stl(surface, path),
+        Some("obj") => write_obj(surface, path),
+        _ => Err(invalid(
+            "surface file",
+            format!("unknown extension: {}", path.display()),
+        )),
+    }
+}
+
+/// Writes an ASCII STL file.
+///
+/// # Errors
+///
+/// Returns `Err` if the file cannot be written.
+pub fn write_stl(surface: &TriSurface, path: &Path) -> Result<(), IoError> {
+    let name = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("surface");
+    write_file(path, format_stl(surface, name).as_bytes())
+}
+
+/// Writes a binary STL file.
+///
+/// # Errors
+///
+/// Returns `Err` if the file cannot be written.
+pub fn write_binary_stl(surface: &TriSurface, path: &Path) -> Result<(), IoError> {
+    write_file(path, &encode_binary_stl(surface))
+}
+
+/// Writes a Wavefront OBJ file.
+///
+/// # Errors
+///
+/// Returns `Err` if the file cannot be written.
+pub fn write_obj(surface: &TriSurface, path: &Path) -> Result<(), IoError> {
+    write_file(path, format_obj(surface).as_bytes())
+}
+
+fn write_file(path: &Path, contents: &[u8]) -> Result<(), IoError> {
+    fs::write(path, contents).map_err(|source| IoError::File {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Formats a surface as an ASCII STL solid named `name`.
+pub fn format_stl(surface: &TriSurface, name: &str) -> String {
+    let mut out = format!("solid {name}\n");
+    for (t, n) in surface.normals().iter().enumerate() {
+        let [x, y, z] = *n.as_array();
+        out.push_str(&format!(
+            "  facet normal {x:e} {y:e} {z:e}\n    outer loop\n"
+        ));
+        for c in surface.corners(t) {
+            let [x, y, z] = *c.as_array();
+            out.push_str(&format!("      vertex {x:e} {y:e} {z:e}\n"));
+        }
+        out.push_str("    endloop\n  endfacet\n");
+    }
+    out.push_str(&format!("endsolid {name}\n"));
+    out
+}
+
+/// Encodes a surface as binary STL, with single-precision coordinates.
+pub fn encode_binary_stl(surface: &TriSurface) -> Vec<u8> {
+    let mut bytes = vec![0u8; 80];
+    bytes.extend((surface.n_triangles() as u32).to_le_bytes());
+    for (t, n) in surface.normals().iter().enumerate() {
+        for v in std::iter::once(*n).chain(surface.corners(t)) {
+            for c in v.as_array() {
+                bytes.extend((*c as f32).to_le_bytes());
+            }
+        }
+        bytes.extend([0u8; 2]);
+    }
+    bytes
+}
+
+/// Formats a surface as Wavefront OBJ text.
+pub fn format_obj(surface: &TriSurface) -> String {
+    let mut out = String::new();
+    for p in surface.points() {
+        let [x, y, z] = *p.as_array();
+        out.push_str(&format!("v {x:e} {y:e} {z:e}\n"));
+    }
+    for [a, b, c] in surface.triangles() {
+        out.push_str(&format!("f {} {} {}\n", a + 1, b + 1, c + 1));
+    }
+    out
+}
+
+/// Resolves a one-based (or negative, relative) OBJ vertex reference.
+fn obj_index(token: &str, n_points: usize, line: usize) -> Result<usize, IoError> {
+    let error = || IoError::Parse {
+        line,
+        message: format!("invalid face vertex: {token}"),
+    };
+    // Safety: `split` always yields at least one item.
+    let index: i64 = token
+        .split('/')
+        .next()
+        .unwrap()
+        .parse()
+        .map_err(|_| error())?;
+    let resolved = match index {
+        i if i > 0 => i - 1,
+        i if i < 0 => n_points as i64 + i,
+        _ => return Err(error()),
+    };
+    usize::try_from(resolved)
+        .ok()
+        .filter(|&i| i < n_points)
+        .ok_or_else(error)
+}
+
+fn parse_vector<'a>(
+    mut tokens: impl Iterator<Item = &'a str>,
+    line: usize,
+) -> Result<Vector, IoError> {
+    let mut next = || -> Result<f64, IoError> {
+        let token = tokens.next().ok_or_else(|| IoError::Parse {
+            line,
+            message: "expected 3 coordinates".to_string(),
+        })?;
+        token.parse().map_err(|_| IoError::Parse {
+            line,
+            message: format!("invalid number: {token}"),
+        })
+    };
+    Ok(Vector::new(next()?, next()?, next()?))
+}
+
+/// Collects triangles given by corner coordinates, merging bitwise-equal
+/// corners into shared points.
+#[derive(Default)]
+struct CornerMerger {
+    points: Vec<Vector>,
+    index: HashMap<[u64; 3], usize>,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl CornerMerger {
+    fn push(&mut self, corners: [Vector; 3]) {
+        let triangle = corners.map(|c| {
+            let key = c.as_array().map(f64::to_bits);
+            *self.index.entry(key).or_insert_with(|| {
+                self.points.push(c);
+                self.points.len() - 1
+            })
+        });
+        self.triangles.push(triangle);
+    }
+
+    fn finish(self) -> Result<TriSurface, dugong_mesh::MeshError> {
+        TriSurface::new(self.points, self.triangles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polymesh::tests::temp_dir;
+
+    const ASCII_STL: &str = "solid tet
+  facet normal 0 0 -1
+    outer loop
+      vertex 0 0 0
+      vertex 0 1 0
+      vertex 1 0 0
+    endloop
+  endfacet
+  facet normal 0 -1 0
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 0 0 1
+    endloop
+  endfacet
+  facet normal -1 0 0
+    outer loop
+      vertex 0 0 0
+      vertex 0 0 1
+      vertex 0 1 0
+    endloop
+  endfacet
+  facet normal 1 1 1
+    outer loop
+      vertex 1 0 0
+      vertex 0 1 0
+      vertex 0 0 1
+    endloop
+  endfacet
+endsolid tet
+";
+
+    fn binary_stl(triangles: &[[[f32; 3]; 3]]) -> Vec<u8> {
+        let mut bytes = vec![0u8; 80];
+        bytes.extend((triangles.len() as u32).to_le_bytes());
+        for t in triangles {
+            bytes.extend([0u8; 12]);
+            for c in t.iter().flatten() {
+                bytes.extend(c.to_le_bytes());
+            }
+            bytes.extend([0u8; 2]);
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_parse_ascii_stl_merges_corners() {
+        let s = parse_stl(ASCII_STL.as_bytes()).unwrap();
+        assert_eq!(s.points().len(), 4);
+        assert_eq!(s.n_triangles(), 4);
+        assert!(s.is_inside(Vector::new(0.1, 0.1, 0.1)));
+        assert!(!s.is_inside(Vector::new(0.5, 0.5, 0.5)));
+    }
+
+    #[test]
+    fn test_parse_binary_stl() {
+        let bytes = binary_stl(&[
+            [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            [[1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+        ]);
+        let s = parse_stl(&bytes).unwrap();
+        assert_eq!(s.points().len(), 4);
+        assert!((s.area() - 1.0).abs() < 1e-12);
+        assert!(s.normals().iter().all(|n| (n.z() - 1.0).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_parse_ascii_stl_bad_facet_returns_err() {
+        let text = "solid x\nouter loop\nvertex 0 0 0\nvertex 1 0 0\nendloop\nendsolid x\n";
+        assert!(matches!(
+            parse_stl(text.as_bytes()),
+            Err(IoError::Parse { line: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_obj_triangulates_and_resolves_indices() {
+        let text =
+            "# square\nv 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nvn 0 0 1\nf 1//1 2//1 3//1 -1//1\n";
+        let s = parse_obj(text).unwrap();
+        assert_eq!(s.triangles(), &[[0, 1, 2], [0, 2, 3]]);
+        assert!((s.area() - 1.0).abs() < 1e-12);
+        assert!(matches!(
+            parse_obj("v 0 0 0\nf 1 2 3\n"),
+            Err(IoError::Parse { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_written_surfaces_read_back() {
+        let surface = parse_stl(ASCII_STL.as_bytes()).unwrap();
+        let ascii = parse_stl(format_stl(&surface, "tet").as_bytes()).unwrap();
+        let binary = parse_stl(&encode_binary_stl(&surface)).unwrap();
+        let obj = parse_obj(&format_obj(&surface)).unwrap();
+        for copy in [&ascii, &binary, &obj] {
+            assert_eq!(copy.points(), surface.points());
+            assert_eq!(copy.triangles(), surface.triangles());
+        }
+
+        let dir = temp_dir("surface-write");
+        for name in ["tet.stl", "tet.OBJ"] {
+            let path = dir.join(name);
+            write_surface(&surface, &path).unwrap();
+            assert_eq!(read_surface(&path).unwrap().n_triangles(), 4);
+        }
+        assert!(write_surface(&surface, &dir.join("tet.ply")).is_err());
+    }
+
+    #[test]
+    fn test_read_surface_dispatches_on_extension() {
+        let dir = temp_dir("surface");
+        let path = dir.join("tet.STL");
+        fs::write(&path, ASCII_STL).unwrap();
+        assert_eq!(read_surface(&path).unwrap().n_triangles(), 4);
+        assert!(matches!(
+            read_surface(&dir.join("tet.ply")),
+            Err(IoError::InvalidData { .. })
+        ));
+    }
+}
@@ -0,0 +1,275 @@
+//! Writer for meshes as VTK XML unstructured grids (`.vtu`).
+//!
+//! Cells recognized by [`Mesh::cell_shapes`] are written as the matching
+//! linear VTK cell, with their points in VTK order. Any other cell is a VTK
+//! polyhedron whose outward-oriented faces are listed explicitly.
+
+use std::fmt::Display;
+use std::path::Path;
+
+use dugong_mesh::{CellShape, Mesh};
+
+use crate::error::IoError;
+use crate::polymesh::write_text;
+
+/// The cell arrays of a VTK unstructured grid.
+#[derive(Debug, Default)]
+struct VtkCells {
+    connectivity: Vec<usize>,
+    offsets: Vec<usize>,
+    types: Vec<u8>,
+    /// The face stream of the polyhedra; empty if there are none.
+    faces: Vec<usize>,
+    /// The end of each cell in `faces`, or `-1` for cells that are not
+    /// polyhedra.
+    face_offsets: Vec<i64>,
+}
+
+/// Writes a mesh as an ASCII VTK XML unstructured grid.
+///
+/// # Errors
+///
+/// Returns `Err` if the file cannot be written.
+pub fn write_vtu(mesh: &Mesh, path: &Path) -> Result<(), IoError> {
+    write_text(path, &format_vtu(mesh))
+}
+
+/// Formats a mesh as an ASCII VTK XML unstructured grid.
+pub fn format_vtu(mesh: &Mesh) -> String {
+    let cells = vtk_cells(mesh);
+    let mut out = String::from("<?xml version=\"1.0\"?>\n");
+    out.push_str(
+        "<VTKFile type=\"UnstructuredGrid\" version=\"0.1\" byte_order=\"LittleEndian\">\n",
+    );
+    out.push_str("  <UnstructuredGrid>\n");
+    out.push_str(&format!(
+        "    <Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">\n",
+        mesh.n_points(),
+        mesh.n_cells()
+    ));
+    out.push_str("      <Points>\n");
+    out.push_str(
+        "        <DataArray type=\"Float64\" NumberOfComponents=\"3\" format=\"ascii\">\n",
+    );
+    for p in mesh.points() {
+        let [x, y, z] = *p.as_array();
+        out.push_str(&format!("          {x:e} {y:e} {z:e}\n"));
+    }
+    out.push_str("        </DataArray>\n      </Points>\n      <Cells>\n");
+    push_array(&mut out, "Int64", "connectivity", &cells.connectivity);
+    push_array(&mut out, "Int64", "offsets", &cells.offsets);
+    push_array(&mut out, "UInt8", "types", &cells.types);
+    if !cells.faces.is_empty() {
+        push_array(&mut out, "Int64", "faces", &cells.faces);
+        push_array(&mut out, "Int64", "faceoffsets", &cells.face_offsets);
+    }
+    out.push_str("      </Cells>\n    </Piece>\n  </UnstructuredGrid>\n</VTKFile>\n");
+    out
+}
+
+fn push_array<T: Display>(out: &mut String, ty: &str, name: &str, values: &[T]) {
+    out.push_str(&format!(
+        "        <DataArray type=\"{ty}\" Name=\"{name}\" format=\"ascii\">\n"
+    ));
+    for line in values.chunks(12) {
+        let line: Vec<String> = line.iter().map(ToString::to_string).collect();
+        out.push_str(&format!("          {}\n", line.join(" ")));
+    }
+    out.push_str("        </DataArray>\n");
+}
+
+fn vtk_cells(mesh: &Mesh) -> VtkCells {
+    let mut cells = VtkCells::default();
+    for (c, shape) in mesh.cell_shapes().into_iter().enumerate() {
+        let faces: Vec<Vec<usize>> = mesh.cell_faces()[c]
+            .iter()
+            .map(|&f| {
+                let mut face = mesh.faces()[f].clone();
+                if mesh.owner()[f] != c {
+                    face.reverse();
+                }
+                face
+            })
+            .collect();
+        match shape {
+            CellShape::Polyhedron => {
+                cells.connectivity.extend(&mesh.cell_points()[c]);
+                cells.faces.push(faces.len());
+                for face in &faces {
+                    cells.faces.push(face.len());
+                    cells.faces.extend(face);
+                }
+                cells.face_offsets.push(cells.faces.len() as i64);
+            }
+            shape => {
+                cells.connectivity.extend(shape_points(shape, &faces));
+                cells.face_offsets.push(-1);
+            }
+        }
+        cells.offsets.push(cells.connectivity.len());
+        cells.types.push(shape.vtk_cell_type());
+    }
+    cells
+}
+
+/// Returns the points of a cell of a standard shape in VTK order, given its
+/// outward-oriented faces.
+fn shape_points(shape: CellShape, faces: &[Vec<usize>]) -> Vec<usize> {
+    let base_size = match shape {
+        CellShape::Tet | CellShape::Prism => 3,
+        _ => 4,
+    };
+    // Safety: every standard shape has a face of its base size.
+    let base = faces.iter().find(|f| f.len() == base_size).unwrap();
+    // VTK turns the base of a tetrahedron, pyramid or hexahedron towards the
+    // rest of the cell, and the base of a wedge away from it.
+    let mut points = base.clone();
+    if shape != CellShape::Prism {
+        points.reverse();
+    }
+    match shape {
+        CellShape::Tet | CellShape::Pyramid => {
+            // Safety: the apex is the one point off the base.
+            let apex = faces.iter().flatten().find(|p| !base.contains(p)).unwrap();
+            points.push(*apex);
+        }
+        _ => {
+            // Each base point is joined by a side face edge to the top point
+            // above it.
+            let top: Vec<usize> = points
+                .iter()
+                .map(|&p| {
+                    faces
+                        .iter()
+                        .filter(|f| f.len() == 4 && *f != base)
+                        .find_map(|f| {
+                            let i = f.iter().position(|&q| q == p)?;
+                            let n = f.len();
+                            [f[(i + 1) % n], f[(i + n - 1) % n]]
+                                .into_iter()
+                                .find(|q| !base.contains(q))
+                        })
+                        // Safety: every base point lies on a side face.
+                        .unwrap()
+                })
+                .collect();
+            points.extend(top);
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_mesh::{BoundaryGroup, HexRefiner, PatchKind, assemble};
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+    use crate::polymesh::tests::{temp_dir, two_cell_mesh};
+
+    /// Returns the signed volume spanned by three edges from point `a`.
+    fn spanned(mesh: &Mesh, a: usize, b: usize, c: usize, d: usize) -> f64 {
+        let p = mesh.points();
+        let (u, v, w) = (p[b] - p[a], p[c] - p[a], p[d] - p[a]);
+        u.cross(&v) * w
+    }
+
+    #[test]
+    fn test_vtk_cells_orders_hexahedra_in_vtk_order() {
+        let mesh = two_cell_mesh();
+        let cells = vtk_cells(&mesh);
+        assert_eq!(cells.types, [12, 12]);
+        assert_eq!(cells.offsets, [8, 16]);
+        assert!(cells.faces.is_empty());
+        for hex in cells.connectivity.chunks(8) {
+            // The base faces the top, and point i + 4 lies above point i.
+            assert!(spanned(&mesh, hex[0], hex[1], hex[3], hex[4]) > 0.0);
+            for i in 0..4 {
+                let edge = mesh.points()[hex[i + 4]] - mesh.points()[hex[i]];
+                assert!((edge.mag() - 1.0).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_vtk_cells_orients_tet_and_wedge_bases() {
+        let p = |x, y, z| Vector::new(x, y, z);
+        let single_cell = |points: Vec<Vector>, faces: Vec<Vec<usize>>| {
+            let group = BoundaryGroup {
+                name: "walls".to_string(),
+                kind: PatchKind::Wall,
+                faces: faces.into_iter().map(|f| (f, 0)).collect(),
+            };
+            assemble(points, vec![], vec![group]).unwrap().0
+        };
+        let tet = single_cell(
+            vec![
+                p(0.0, 0.0, 0.0),
+                p(1.0, 0.0, 0.0),
+                p(0.0, 1.0, 0.0),
+                p(0.0, 0.0, 1.0),
+            ],
+            vec![vec![0, 2, 1], vec![0, 1, 3], vec![0, 3, 2], vec![1, 2, 3]],
+        );
+        let cells = vtk_cells(&tet);
+        assert_eq!(cells.types, [10]);
+        let c = &cells.connectivity;
+        assert!(spanned(&tet, c[0], c[1], c[2], c[3]) > 0.0);
+
+        let wedge = single_cell(
+            vec![
+                p(0.0, 0.0, 0.0),
+                p(1.0, 0.0, 0.0),
+                p(0.0, 1.0, 0.0),
+                p(0.0, 0.0, 1.0),
+                p(1.0, 0.0, 1.0),
+                p(0.0, 1.0, 1.0),
+            ],
+            vec![
+                vec![0, 2, 1],
+                vec![3, 4, 5],
+                vec![0, 1, 4, 3],
+                vec![1, 2, 5, 4],
+                vec![2, 0, 3, 5],
+            ],
+        );
+        let cells = vtk_cells(&wedge);
+        assert_eq!(cells.types, [13]);
+        let c = &cells.connectivity;
+        // The base faces away from the top, and point i + 3 lies above point i.
+        assert!(spanned(&wedge, c[0], c[1], c[2], c[3]) < 0.0);
+        for i in 0..3 {
+            let edge = wedge.points()[c[i + 3]] - wedge.points()[c[i]];
+            assert!((edge.mag() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_vtk_cells_lists_polyhedron_faces() {
+        let mut refiner = HexRefiner::new(&two_cell_mesh()).unwrap();
+        refiner.refine(&[0]);
+        let mesh = refiner.mesh().unwrap();
+        let cells = vtk_cells(&mesh);
+        let polyhedra: Vec<usize> = (0..mesh.n_cells())
+            .filter(|&c| cells.types[c] == 42)
+            .collect();
+        assert_eq!(polyhedra.len(), 1);
+        let c = polyhedra[0];
+        let end = cells.face_offsets[c] as usize;
+        assert_eq!(cells.faces[0], mesh.cell_faces()[c].len());
+        assert_eq!(end, cells.faces.len());
+        assert_eq!(cells.face_offsets.iter().filter(|&&o| o == -1).count(), 8);
+    }
+
+    #[test]
+    fn test_write_vtu_writes_grid() {
+        let mesh = two_cell_mesh();
+        let path = temp_dir("vtu").join("mesh.vtu");
+        write_vtu(&mesh, &path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.contains("<Piece NumberOfPoints=\"12\" NumberOfCells=\"2\">"));
+        assert!(text.contains("Name=\"types\""));
+        assert!(!text.contains("Name=\"faces\""));
+        assert!(text.ends_with("</VTKFile>\n"));
+    }
+}
@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+
+use dugong_types::FieldValue;
+use dugong_types::tensor::Vector;
+
+use crate::mesh::Mesh;
+
+/// Settings for [`Mesh::agglomerate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AgglomerationSettings {
+    /// Coarsening stops once a level has at most this many cells.
+    pub n_cells_in_coarsest_level: usize,
+    /// Maximum number of coarse levels.
+    pub max_levels: usize,
+    /// Number of pairwise merging passes per level: each pass roughly halves
+    /// the number of cells, so `2` builds clusters of about four cells.
+    pub merge_levels: usize,
+}
+
+impl Default for AgglomerationSettings {
+    fn default() -> Self {
+        Self {
+            n_cells_in_coarsest_level: 10,
+            max_levels: 50,
+            merge_levels: 1,
+        }
+    }
+}
+
+/// One coarse level of an [`Agglomeration`]: clusters of the cells of the
+/// next finer level, with face addressing between the clusters.
+///
+/// Coarse faces are ordered by owner, then neighbor, with the owner the
+/// lower-numbered cluster. Boundary faces are not agglomerated; they belong
+/// to the cluster of their owner cell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoarseLevel {
+    cell_map: Vec<usize>,
+    face_map: Vec<Option<(usize, bool)>>,
+    owner: Vec<usize>,
+    neighbor: Vec<usize>,
+    face_weights: Vec<f64>,
+    volumes: Vec<f64>,
+    centers: Vec<Vector>,
+}
+
+impl CoarseLevel {
+    /// Returns the number of coarse cells.
+    pub fn n_cells(&self) -> usize {
+        self.volumes.len()
+    }
+
+    /// Returns the number of coarse faces.
+    pub fn n_faces(&self) -> usize {
+        self.owner.len()
+    }
+
+    /// Returns the coarse cell of each cell of the finer level.
+    pub fn cell_map(&self) -> &[usize] {
+        &self.cell_map
+    }
+
+    /// Returns, for each internal face of the finer level, the coarse face it
+    /// is part of and whether it is oriented opposite to it, or `None` for
+    /// faces inside a cluster.
+    pub fn face_map(&self) -> &[Option<(usize, bool)>] {
+        &self.face_map
+    }
+
+    /// Returns the owner cluster of each coarse face.
+    pub fn owner(&self) -> &[usize] {
+        &self.owner
+    }
+
+    /// Returns the neighbor cluster of each coarse face.
+    pub fn neighbor(&self) -> &[usize] {
+        &self.neighbor
+    }
+
+    /// Returns the weight of each coarse face: the summed area magnitude of
+    /// the fine faces it consists of.
+    pub fn face_weights(&self) -> &[f64] {
+        &self.face_weights
+    }
+
+    /// Returns the volume of each coarse cell.
+    pub fn volumes(&self) -> &[f64] {
+        &self.volumes
+    }
+
+    /// Returns the volume-weighted center of each coarse cell.
+    pub fn centers(&self) -> &[Vector] {
+        &self.centers
+    }
+
+    /// Sums per-cell values of the finer level over each cluster.
+    pub fn restrict<T: FieldValue>(&self, fine: &[T]) -> Vec<T> {
+        let mut coarse = vec![T::zero(); self.n_cells()];
+        for (&c, &v) in self.cell_map.iter().zip(fine) {
+            coarse[c] = coarse[c] + v;
+        }
+        coarse
+    }
+
+    /// Sums per-face values of the finer level over each coarse face,
+    /// negating those of faces oriented opposite to their coarse face.
+    pub fn restrict_faces<T: FieldValue>(&self, fine: &[T]) -> Vec<T> {
+        let mut coarse = vec![T::zero(); self.n_faces()];
+        for (map, &v) in self.face_map.iter().zip(fine) {
+            if let Some((f, flip)) = *map {
+                coarse[f] = if flip { coarse[f] - v } else { coarse[f] + v };
+            }
+        }
+        coarse
+    }
+
+    /// Copies the value of each cluster to its cells on the finer level.
+    pub fn prolong<T: Clone>(&self, coarse: &[T]) -> Vec<T> {
+        self.cell_map.iter().map(|&c| coarse[c].clone()).collect()
+    }
+}
+
+/// A hierarchy of coarse levels built by [`Mesh::agglomerate`], from finest
+/// to coarsest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Agglomeration {
+    levels: Vec<CoarseLevel>,
+}
+
+impl Agglomeration {
+    /// Returns the coarse levels, from finest to coarsest.
+    pub fn levels(&self) -> &[CoarseLevel] {
+        &self.levels
+    }
+
+    /// Returns the number of coarse levels.
+    pub fn n_levels(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns the cluster of coarse level `level` containing each mesh cell.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `level` is out of range.
+    pub fn cluster_of_cells(&self, level: usize) -> Vec<usize> {
+        let mut map = self.levels[0].cell_map.clone();
+        for coarse in &self.levels[1..=level] {
+            for c in &mut map {
+                *c = coarse.cell_map[*c];
+            }
+        }
+        map
+    }
+}
+
+/// Cell graph of one level: internal faces as `(owner, neighbor)` with a
+/// weight each.
+struct Graph {
+    n_cells: usize,
+    faces: Vec<[usize; 2]>,
+    weights: Vec<f64>,
+}
+
+/// Merges each cell with the unmatched neighbor sharing the heaviest face,
+/// or, if all neighbors are taken, with the cluster of its heaviest
+/// neighbor. Returns the cluster of each cell and the number of clusters.
+fn pair(graph: &Graph) -> (Vec<usize>, usize) {
+    let mut adjacent = vec![Vec::new(); graph.n_cells];
+    for (&[a, b], &w) in graph.faces.iter().zip(&graph.weights) {
+        adjacent[a].push((b, w));
+        adjacent[b].push((a, w));
+    }
+    const UNSET: usize = usize::MAX;
+    let mut cluster = vec![UNSET; graph.n_cells];
+    let mut n_clusters = 0;
+    for c in 0..graph.n_cells {
+        if cluster[c] != UNSET {
+            continue;
+        }
+        let heaviest = |free: bool| {
+            adjacent[c]
+                .iter()
+                .filter(|&&(n, _)| (cluster[n] == UNSET) == free)
+                .max_by(|x, y| x.1.total_cmp(&y.1))
+                .map(|&(n, _)| n)
+        };
+        if let Some(n) = heaviest(true) {
+            cluster[c] = n_clusters;
+            cluster[n] = n_clusters;
+            n_clusters += 1;
+        } else if let Some(n) = heaviest(false) {
+            cluster[c] = cluster[n];
+        } else {
+            cluster[c] = n_clusters;
+            n_clusters += 1;
+        }
+    }
+    (cluster, n_clusters)
+}
+
+/// Builds the graph of the clusters `cell_map` of `graph`, and the coarse
+/// face of each face of `graph` with its relative orientation.
+fn coarsen(
+    graph: &Graph,
+    cell_map: &[usize],
+    n_coarse: usize,
+) -> (Graph, Vec<Option<(usize, bool)>>) {
+    let mut keys: Vec<[usize; 2]> = graph
+        .faces
+        .iter()
+        .map(|&[a, b]| (cell_map[a], cell_map[b]))
+        .filter(|(ca, cb)| ca != cb)
+        .map(|(ca, cb)| [ca.min(cb), ca.max(cb)])
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+    let index: HashMap<[usize; 2], usize> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+
+    let mut weights = vec![0.0; keys.len()];
+    let face_map = graph
+        .faces
+        .iter()
+        .zip(&graph.weights)
+        .map(|(&[a, b], &w)| {
+            let (ca, cb) = (cell_map[a], cell_map[b]);
+            (ca != cb).then(|| {
+                let f = index[&[ca.min(cb), ca.max(cb)]];
+                weights[f] += w;
+                (f, ca > cb)
+            })
+        })
+        .collect();
+    let coarse = Graph {
+        n_cells: n_coarse,
+        faces: keys,
+        weights,
+    };
+    (coarse, face_map)
+}
+
+impl Mesh {
+    /// Builds a hierarchy of coarse levels by pairwise agglomeration
+    /// weighted by face area, as used by geometric-agglomerated multigrid.
+    ///
+    /// Each level is made of [`AgglomerationSettings::merge_levels`] passes
+    /// over the cells of the level below, each pairing every cell with the
+    /// free neighbor it shares the largest face with. Coarsening stops at
+    /// [`AgglomerationSettings::max_levels`] levels, once a level is small
+    /// enough, or when a pass no longer merges any cells.
+    pub fn agglomerate(&self, settings: &AgglomerationSettings) -> Agglomeration {
+        let mut graph = Graph {
+            n_cells: self.n_cells(),
+            faces: self
+                .neighbor()
+                .iter()
+                .enumerate()
+                .map(|(f, &n)| [self.owner()[f], n])
+                .collect(),
+            weights: self.face_areas()[..self.n_internal_faces()]
+                .iter()
+                .map(|s| s.mag())
+                .collect(),
+        };
+        let mut volumes = self.cell_volumes().to_vec();
+        let mut centers = self.cell_centers().to_vec();
+        let mut levels: Vec<CoarseLevel> = Vec::new();
+        while levels.len() < settings.max_levels
+            && graph.n_cells > settings.n_cells_in_coarsest_level
+        {
+            let mut cell_map: Vec<usize> = (0..graph.n_cells).collect();
+            let mut n_coarse = graph.n_cells;
+            let mut pass_graph: Option<Graph> = None;
+            for _ in 0..settings.merge_levels.max(1) {
+                let g = pass_graph.as_ref().unwrap_or(&graph);
+                let (cluster, n) = pair(g);
+                let (next, _) = coarsen(g, &cluster, n);
+                for c in &mut cell_map {
+                    *c = cluster[*c];
+                }
+                n_coarse = n;
+                pass_graph = Some(next);
+                if n <= settings.n_cells_in_coarsest_level {
+                    break;
+                }
+            }
+            if n_coarse == graph.n_cells {
+                break;
+            }
+
+            let (coarse, face_map) = coarsen(&graph, &cell_map, n_coarse);
+            let mut coarse_volumes = vec![0.0; n_coarse];
+            let mut moments = vec![Vector::zero(); n_coarse];
+            for (c, &cc) in cell_map.iter().enumerate() {
+                coarse_volumes[cc] += volumes[c];
+                moments[cc] += centers[c] * volumes[c];
+            }
+            let coarse_centers: Vec<Vector> = moments
+                .iter()
+                .zip(&coarse_volumes)
+                .map(|(&m, &v)| if v > 0.0 { m / v } else { m })
+                .collect();
+            levels.push(CoarseLevel {
+                cell_map,
+                face_map,
+                owner: coarse.faces.iter().map(|f| f[0]).collect(),
+                neighbor: coarse.faces.iter().map(|f| f[1]).collect(),
+                face_weights: coarse.weights.clone(),
+                volumes: coarse_volumes.clone(),
+                centers: coarse_centers.clone(),
+            });
+            graph = coarse;
+            volumes = coarse_volumes;
+            centers = coarse_centers;
+        }
+        Agglomeration { levels }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_agglomerate_halves_cells_per_level() {
+        let mesh = box_mesh([8, 4, 2], [8.0, 4.0, 2.0]);
+        let settings = AgglomerationSettings {
+            n_cells_in_coarsest_level: 4,
+            ..AgglomerationSettings::default()
+        };
+        let agglomeration = mesh.agglomerate(&settings);
+        assert!(agglomeration.n_levels() >= 3);
+        let mut n_fine = mesh.n_cells();
+        for level in agglomeration.levels() {
+            assert!(level.n_cells() * 2 <= n_fine + 1);
+            assert!(level.n_cells() * 3 >= n_fine);
+            let total: f64 = level.volumes().iter().sum();
+            assert!((total - 64.0).abs() < 1e-9);
+            for (&o, &n) in level.owner().iter().zip(level.neighbor()) {
+                assert!(o < n);
+            }
+            n_fine = level.n_cells();
+        }
+        assert!(n_fine <= 4);
+        let last = agglomeration.n_levels() - 1;
+        let clusters = agglomeration.cluster_of_cells(last);
+        assert_eq!(clusters.len(), mesh.n_cells());
+        assert!(clusters.iter().all(|&c| c < n_fine));
+    }
+
+    #[test]
+    fn test_coarse_level_restricts_faces_with_orientation() {
+        let mesh = box_mesh([4, 1, 1], [4.0, 1.0, 1.0]);
+        let settings = AgglomerationSettings {
+            n_cells_in_coarsest_level: 2,
+            max_levels: 1,
+            merge_levels: 1,
+        };
+        let agglomeration = mesh.agglomerate(&settings);
+        let level = &agglomeration.levels()[0];
+        assert_eq!(level.n_cells(), 2);
+        assert_eq!(level.n_faces(), 1);
+        assert_eq!(level.face_weights(), &[1.0]);
+        // A unit flux through every fine face leaves one through the coarse
+        // face between the two clusters.
+        let flux = level.restrict_faces(&vec![1.0; mesh.n_internal_faces()]);
+        assert_eq!(flux, vec![1.0]);
+        assert!((level.centers()[0] - Vector::new(1.0, 0.5, 0.5)).mag() < 1e-12);
+
+        let fine: Vec<f64> = (0..4).map(|c| c as f64).collect();
+        let coarse = level.restrict(&fine);
+        assert_eq!(coarse, vec![1.0, 5.0]);
+        assert_eq!(level.prolong(&coarse), vec![1.0, 1.0, 5.0, 5.0]);
+    }
+}
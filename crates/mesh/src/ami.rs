@@ -0,0 +1,472 @@
+use dugong_types::FieldValue;
+use dugong_types::tensor::Vector;
+
+use crate::mesh::Mesh;
+use crate::patch::{CoupledTransform, PatchKind};
+
+/// Faces whose overlap covers less than this fraction of their area are
+/// treated as uncoupled by the interpolation helpers.
+const LOW_WEIGHT: f64 = 1e-3;
+
+/// Area-weighted coupling between two non-conformal patch surfaces
+/// (Arbitrary Mesh Interface).
+///
+/// For every source face `i` and overlapping target face `k`, the weight
+/// `source_weights(i)[..]` is the overlap area divided by the area of face
+/// `i`; target weights are defined symmetrically. A fully covered face has
+/// weights summing to one.
+///
+/// Overlaps are computed by projecting each candidate target face onto the
+/// plane of the source face and clipping the fan triangulations of both
+/// polygons against each other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ami {
+    source_addressing: Vec<Vec<usize>>,
+    source_weights: Vec<Vec<f64>>,
+    target_addressing: Vec<Vec<usize>>,
+    target_weights: Vec<Vec<f64>>,
+}
+
+impl Ami {
+    /// Couples patch `source_patch` of `source` with patch `target_patch` of
+    /// `target`. `transform` maps source positions into the target frame.
+    pub fn between(
+        source: &Mesh,
+        source_patch: usize,
+        target: &Mesh,
+        target_patch: usize,
+        transform: &CoupledTransform,
+    ) -> Self {
+        let polygons = |mesh: &Mesh, patch: usize, map: &dyn Fn(Vector) -> Vector| {
+            mesh.patches()[patch]
+                .range()
+                .map(|f| {
+                    mesh.faces()[f]
+                        .iter()
+                        .map(|&p| map(mesh.points()[p]))
+                        .collect()
+                })
+                .collect::<Vec<Vec<Vector>>>()
+        };
+        Self::from_polygons(
+            &polygons(source, source_patch, &|p| transform.transform_point(p)),
+            &polygons(target, target_patch, &|p| p),
+        )
+    }
+
+    /// Computes the coupling between two sets of planar polygons.
+    pub(crate) fn from_polygons(source: &[Vec<Vector>], target: &[Vec<Vector>]) -> Self {
+        let source_areas: Vec<f64> = source.iter().map(|p| polygon_area(p).mag()).collect();
+        let target_areas: Vec<f64> = target.iter().map(|p| polygon_area(p).mag()).collect();
+        let target_boxes: Vec<BoundingBox> = target.iter().map(|p| BoundingBox::of(p)).collect();
+        let mut by_min_x: Vec<usize> = (0..target.len()).collect();
+        by_min_x.sort_by(|&a, &b| target_boxes[a].lo[0].total_cmp(&target_boxes[b].lo[0]));
+        let min_x: Vec<f64> = by_min_x.iter().map(|&k| target_boxes[k].lo[0]).collect();
+
+        let mut source_addressing = vec![Vec::new(); source.len()];
+        let mut source_weights = vec![Vec::new(); source.len()];
+        let mut target_addressing = vec![Vec::new(); target.len()];
+        let mut target_weights = vec![Vec::new(); target.len()];
+        for (i, polygon) in source.iter().enumerate() {
+            if source_areas[i] == 0.0 {
+                continue;
+            }
+            let bb = BoundingBox::of(polygon);
+            let end = min_x.partition_point(|&x| x <= bb.hi[0]);
+            let mut candidates: Vec<usize> = by_min_x[..end]
+                .iter()
+                .copied()
+                .filter(|&k| bb.overlaps(&target_boxes[k]))
+                .collect();
+            candidates.sort_unstable();
+            let plane = Plane::of(polygon);
+            for k in candidates {
+                let area = plane.overlap_area(polygon, &target[k]);
+                // Faces sharing only an edge can yield round-off sized overlaps.
+                if area <= 1e-12 * source_areas[i] || target_areas[k] == 0.0 {
+                    continue;
+                }
+                source_addressing[i].push(k);
+                source_weights[i].push(area / source_areas[i]);
+                target_addressing[k].push(i);
+                target_weights[k].push(area / target_areas[k]);
+            }
+        }
+        Self {
+            source_addressing,
+            source_weights,
+            target_addressing,
+            target_weights,
+        }
+    }
+
+    /// Returns the number of source faces.
+    pub fn n_source(&self) -> usize {
+        self.source_addressing.len()
+    }
+
+    /// Returns the number of target faces.
+    pub fn n_target(&self) -> usize {
+        self.target_addressing.len()
+    }
+
+    /// Returns the target faces overlapping source face `face`.
+    pub fn source_addressing(&self, face: usize) -> &[usize] {
+        &self.source_addressing[face]
+    }
+
+    /// Returns the weights matching [`Ami::source_addressing`].
+    pub fn source_weights(&self, face: usize) -> &[f64] {
+        &self.source_weights[face]
+    }
+
+    /// Returns the source faces overlapping target face `face`.
+    pub fn target_addressing(&self, face: usize) -> &[usize] {
+        &self.target_addressing[face]
+    }
+
+    /// Returns the weights matching [`Ami::target_addressing`].
+    pub fn target_weights(&self, face: usize) -> &[f64] {
+        &self.target_weights[face]
+    }
+
+    /// Returns the covered fraction of each source face.
+    pub fn source_weight_sums(&self) -> Vec<f64> {
+        self.source_weights.iter().map(|w| w.iter().sum()).collect()
+    }
+
+    /// Returns the covered fraction of each target face.
+    pub fn target_weight_sums(&self) -> Vec<f64> {
+        self.target_weights.iter().map(|w| w.iter().sum()).collect()
+    }
+
+    /// Interpolates target face values onto the source faces.
+    ///
+    /// Weights are normalized per face, so partially covered faces receive
+    /// the mean over their overlap; faces with (almost) no overlap receive
+    /// `fallback`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target_values.len()` differs from the number of target faces.
+    pub fn interpolate_to_source<T: FieldValue>(&self, target_values: &[T], fallback: T) -> Vec<T> {
+        assert_eq!(
+            target_values.len(),
+            self.n_target(),
+            "target value count mismatch"
+        );
+        interpolate(
+            &self.source_addressing,
+            &self.source_weights,
+            target_values,
+            fallback,
+        )
+    }
+
+    /// Interpolates source face values onto the target faces. See
+    /// [`Ami::interpolate_to_source`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source_values.len()` differs from the number of source faces.
+    pub fn interpolate_to_target<T: FieldValue>(&self, source_values: &[T], fallback: T) -> Vec<T> {
+        assert_eq!(
+            source_values.len(),
+            self.n_source(),
+            "source value count mismatch"
+        );
+        interpolate(
+            &self.target_addressing,
+            &self.target_weights,
+            source_values,
+            fallback,
+        )
+    }
+}
+
+fn interpolate<T: FieldValue>(
+    addressing: &[Vec<usize>],
+    weights: &[Vec<f64>],
+    values: &[T],
+    fallback: T,
+) -> Vec<T> {
+    addressing
+        .iter()
+        .zip(weights)
+        .map(|(addr, w)| {
+            let sum: f64 = w.iter().sum();
+            if sum < LOW_WEIGHT {
+                return fallback;
+            }
+            addr.iter()
+                .zip(w)
+                .fold(T::zero(), |acc, (&k, &wk)| acc + values[k] * (wk / sum))
+        })
+        .collect()
+}
+
+impl Mesh {
+    /// Builds the AMI coupling of cyclic AMI patch `patch` with its neighbor
+    /// patch, or returns `None` if the patch is not a cyclic AMI patch.
+    pub fn ami(&self, patch: usize) -> Option<Ami> {
+        let PatchKind::CyclicAmi {
+            neighbor_patch,
+            transform,
+        } = self.patches()[patch].kind()
+        else {
+            return None;
+        };
+        let neighbor = self.patch_index(neighbor_patch)?;
+        Some(Ami::between(self, patch, self, neighbor, transform))
+    }
+}
+
+/// Returns the area vector of a polygon (right-hand rule).
+fn polygon_area(polygon: &[Vector]) -> Vector {
+    let n = polygon.len();
+    (0..n).fold(Vector::zero(), |s, i| {
+        s + polygon[i].cross(&polygon[(i + 1) % n]) * 0.5
+    })
+}
+
+fn polygon_centroid(polygon: &[Vector]) -> Vector {
+    polygon.iter().fold(Vector::zero(), |s, &p| s + p) * (1.0 / polygon.len() as f64)
+}
+
+/// An axis-aligned bounding box, slightly enlarged so that touching faces
+/// on curved or imperfectly matched surfaces are still paired.
+struct BoundingBox {
+    lo: [f64; 3],
+    hi: [f64; 3],
+}
+
+impl BoundingBox {
+    fn of(polygon: &[Vector]) -> Self {
+        let mut lo = [f64::INFINITY; 3];
+        let mut hi = [f64::NEG_INFINITY; 3];
+        for p in polygon {
+            for (d, &x) in p.as_array().iter().enumerate() {
+                lo[d] = lo[d].min(x);
+                hi[d] = hi[d].max(x);
+            }
+        }
+        let margin = 0.1 * polygon_area(polygon).mag().sqrt();
+        for d in 0..3 {
+            lo[d] -= margin;
+            hi[d] += margin;
+        }
+        Self { lo, hi }
+    }
+
+    fn overlaps(&self, other: &BoundingBox) -> bool {
+        (0..3).all(|d| self.lo[d] <= other.hi[d] && other.lo[d] <= self.hi[d])
+    }
+}
+
+/// A local 2D frame in the plane of a polygon.
+struct Plane {
+    origin: Vector,
+    e1: Vector,
+    e2: Vector,
+}
+
+impl Plane {
+    fn of(polygon: &[Vector]) -> Self {
+        let area = polygon_area(polygon);
+        let n = area * (1.0 / area.mag());
+        let origin = polygon_centroid(polygon);
+        // Any in-plane direction works; use the one most orthogonal to n.
+        let trial = if n.x().abs() < 0.9 {
+            Vector::new(1.0, 0.0, 0.0)
+        } else {
+            Vector::new(0.0, 1.0, 0.0)
+        };
+        let e1 = trial - n * (trial * n);
+        let e1 = e1 * (1.0 / e1.mag());
+        Self {
+            origin,
+            e1,
+            e2: n.cross(&e1),
+        }
+    }
+
+    fn project(&self, p: Vector) -> [f64; 2] {
+        let d = p - self.origin;
+        [d * self.e1, d * self.e2]
+    }
+
+    /// Returns the area of overlap of two polygons projected onto this plane.
+    fn overlap_area(&self, a: &[Vector], b: &[Vector]) -> f64 {
+        let ta = self.fan(a);
+        let tb = self.fan(b);
+        ta.iter()
+            .flat_map(|x| tb.iter().map(move |y| clip_area(x, y)))
+            .sum()
+    }
+
+    /// Splits a polygon into counter-clockwise triangles around its centroid.
+    fn fan(&self, polygon: &[Vector]) -> Vec<[[f64; 2]; 3]> {
+        let c = self.project(polygon_centroid(polygon));
+        let n = polygon.len();
+        (0..n)
+            .filter_map(|i| {
+                let p = self.project(polygon[i]);
+                let q = self.project(polygon[(i + 1) % n]);
+                match signed_area(&[c, p, q]) {
+                    a if a > 0.0 => Some([c, p, q]),
+                    a if a < 0.0 => Some([c, q, p]),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+fn signed_area(polygon: &[[f64; 2]]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| {
+            let (p, q) = (polygon[i], polygon[(i + 1) % n]);
+            p[0] * q[1] - q[0] * p[1]
+        })
+        .sum::<f64>()
+        * 0.5
+}
+
+/// Returns the overlap area of two counter-clockwise triangles
+/// (Sutherland–Hodgman clipping).
+fn clip_area(subject: &[[f64; 2]; 3], clip: &[[f64; 2]; 3]) -> f64 {
+    let mut poly: Vec<[f64; 2]> = subject.to_vec();
+    for i in 0..3 {
+        if poly.is_empty() {
+            return 0.0;
+        }
+        let (a, b) = (clip[i], clip[(i + 1) % 3]);
+        let side = |p: [f64; 2]| (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0]);
+        let input = std::mem::take(&mut poly);
+        for j in 0..input.len() {
+            let (p, q) = (input[j], input[(j + 1) % input.len()]);
+            let (sp, sq) = (side(p), side(q));
+            if sp >= 0.0 {
+                poly.push(p);
+            }
+            if (sp >= 0.0) != (sq >= 0.0) {
+                let t = sp / (sp - sq);
+                poly.push([p[0] + t * (q[0] - p[0]), p[1] + t * (q[1] - p[1])]);
+            }
+        }
+    }
+    if poly.len() < 3 {
+        0.0
+    } else {
+        signed_area(&poly).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patch::Patch;
+    use crate::test_meshes::box_mesh;
+
+    /// Splits the unit square in the plane `z = 0` into an `nx x ny` grid of
+    /// quads with the given normal orientation.
+    fn grid(nx: usize, ny: usize, shift: f64, up: bool) -> Vec<Vec<Vector>> {
+        let mut out = Vec::new();
+        for j in 0..ny {
+            for i in 0..nx {
+                let (x0, x1) = (
+                    i as f64 / nx as f64 + shift,
+                    (i + 1) as f64 / nx as f64 + shift,
+                );
+                let (y0, y1) = (j as f64 / ny as f64, (j + 1) as f64 / ny as f64);
+                let mut quad = vec![
+                    Vector::new(x0, y0, 0.0),
+                    Vector::new(x1, y0, 0.0),
+                    Vector::new(x1, y1, 0.0),
+                    Vector::new(x0, y1, 0.0),
+                ];
+                if !up {
+                    quad.reverse();
+                }
+                out.push(quad);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_ami_weights_of_nonmatching_grids() {
+        let ami = Ami::from_polygons(&grid(2, 1, 0.0, true), &grid(1, 3, 0.0, false));
+        for i in 0..2 {
+            assert_eq!(ami.source_addressing(i), &[0, 1, 2]);
+            for &w in ami.source_weights(i) {
+                assert!((w - 1.0 / 3.0).abs() < 1e-12);
+            }
+        }
+        for s in ami
+            .source_weight_sums()
+            .into_iter()
+            .chain(ami.target_weight_sums())
+        {
+            assert!((s - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_ami_interpolation_is_conservative_when_fully_covered() {
+        let source = grid(3, 2, 0.0, true);
+        let target = grid(2, 5, 0.0, false);
+        let ami = Ami::from_polygons(&source, &target);
+        let values: Vec<f64> = (0..target.len()).map(|k| k as f64 * 1.5 - 2.0).collect();
+        let mapped = ami.interpolate_to_source(&values, 0.0);
+        let area = |p: &Vec<Vector>| polygon_area(p).mag();
+        let total_target: f64 = target.iter().zip(&values).map(|(p, v)| area(p) * v).sum();
+        let total_source: f64 = source.iter().zip(&mapped).map(|(p, v)| area(p) * v).sum();
+        assert!((total_target - total_source).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ami_partial_overlap_uses_fallback() {
+        let ami = Ami::from_polygons(&grid(2, 1, 0.0, true), &grid(2, 1, 1.0, false));
+        let sums = ami.source_weight_sums();
+        assert!(sums[0] < LOW_WEIGHT);
+        assert!((sums[1] - 0.0).abs() < 1e-12);
+        let shifted = Ami::from_polygons(&grid(2, 1, 0.0, true), &grid(2, 1, 0.25, false));
+        let sums = shifted.source_weight_sums();
+        assert!((sums[0] - 0.5).abs() < 1e-12);
+        assert!((sums[1] - 1.0).abs() < 1e-12);
+        let mapped = ami.interpolate_to_source(&[Vector::new(1.0, 0.0, 0.0); 2], Vector::zero());
+        assert_eq!(mapped, vec![Vector::zero(); 2]);
+    }
+
+    #[test]
+    fn test_mesh_ami_couples_translated_patches() {
+        let mut mesh = box_mesh([2, 3, 2], [1.0, 1.0, 1.0]);
+        let separation = Vector::new(1.0, 0.0, 0.0);
+        let kinds = [
+            ("x-max", CoupledTransform::Translational { separation }),
+            (
+                "x-min",
+                CoupledTransform::Translational {
+                    separation: -separation,
+                },
+            ),
+        ];
+        for (i, (other, transform)) in kinds.into_iter().enumerate() {
+            let p = &mesh.patches[i];
+            let kind = PatchKind::CyclicAmi {
+                neighbor_patch: other.to_string(),
+                transform,
+            };
+            mesh.patches[i] = Patch::new(p.name(), kind, p.start(), p.size());
+        }
+        let ami = mesh.ami(0).unwrap();
+        assert_eq!(ami.n_source(), 6);
+        for i in 0..6 {
+            assert_eq!(ami.source_addressing(i), &[i]);
+            assert!((ami.source_weights(i)[0] - 1.0).abs() < 1e-12);
+        }
+        assert!(mesh.ami(2).is_none());
+    }
+}
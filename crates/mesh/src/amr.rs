@@ -0,0 +1,184 @@
+use dugong_types::FieldValue;
+
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::refine::{HexRefiner, RefinementMap};
+
+/// Thresholds controlling [`AdaptiveMesh::update`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmrSettings {
+    /// Cells whose indicator is below this value are coarsened.
+    pub lower: f64,
+    /// Cells whose indicator is above this value are refined.
+    pub upper: f64,
+    /// Maximum refinement level.
+    pub max_level: usize,
+}
+
+/// A per-cell field that follows the mesh through refinement and coarsening.
+pub trait FieldMapper {
+    /// Maps the field onto the new cells. `old_volumes` are the cell volumes
+    /// before the change.
+    fn map_fields(&mut self, map: &RefinementMap, old_volumes: &[f64]);
+}
+
+impl<T: FieldValue> FieldMapper for Vec<T> {
+    fn map_fields(&mut self, map: &RefinementMap, old_volumes: &[f64]) {
+        *self = map.map_conservative(self, old_volumes);
+    }
+}
+
+/// An indicator-driven adaptive hex mesh.
+///
+/// Wraps a [`HexRefiner`] and the mesh it produces. Between time steps,
+/// [`AdaptiveMesh::update`] coarsens sibling groups whose indicator is below
+/// [`AmrSettings::lower`] and refines cells above [`AmrSettings::upper`],
+/// then maps registered fields conservatively.
+pub struct AdaptiveMesh {
+    refiner: HexRefiner,
+    mesh: Mesh,
+    settings: AmrSettings,
+}
+
+impl AdaptiveMesh {
+    /// Starts adaptive refinement from a hexahedral mesh.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::NotHexahedral`] if a cell is not a hexahedron.
+    pub fn new(mesh: &Mesh, settings: AmrSettings) -> Result<Self, MeshError> {
+        let refiner = HexRefiner::new(mesh)?;
+        let mesh = refiner.mesh()?;
+        Ok(Self {
+            refiner,
+            mesh,
+            settings,
+        })
+    }
+
+    /// Returns the current mesh.
+    pub fn mesh(&self) -> &Mesh {
+        &self.mesh
+    }
+
+    /// Returns the settings.
+    pub fn settings(&self) -> &AmrSettings {
+        &self.settings
+    }
+
+    /// Returns the refinement level of each cell.
+    pub fn cell_levels(&self) -> Vec<usize> {
+        self.refiner.cell_levels()
+    }
+
+    /// Adapts the mesh to a per-cell indicator and maps `fields` onto it.
+    ///
+    /// Coarsening is applied before refinement. Returns the combined cell
+    /// map, or `None` if the mesh did not change.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the adapted mesh fails validation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `indicator.len()` differs from the number of cells.
+    pub fn update(
+        &mut self,
+        indicator: &[f64],
+        fields: &mut [&mut dyn FieldMapper],
+    ) -> Result<Option<RefinementMap>, MeshError> {
+        let n_cells = self.mesh.n_cells();
+        assert_eq!(indicator.len(), n_cells, "indicator length mismatch");
+        let levels = self.refiner.cell_levels();
+        let coarsen: Vec<usize> = (0..n_cells)
+            .filter(|&c| indicator[c] < self.settings.lower && levels[c] > 0)
+            .collect();
+        let coarsened = self.refiner.unrefine(&coarsen);
+
+        let refine: Vec<usize> = (0..coarsened.len())
+            .filter(|&c| {
+                let sources = coarsened.sources(c);
+                sources.len() == 1
+                    && indicator[sources[0]] > self.settings.upper
+                    && levels[sources[0]] < self.settings.max_level
+            })
+            .collect();
+        let refined = self.refiner.refine(&refine);
+
+        let map = coarsened.then(&refined);
+        if map.len() == n_cells && (0..n_cells).all(|c| map.sources(c) == [c]) {
+            return Ok(None);
+        }
+        let mesh = self.refiner.mesh()?;
+        let old = std::mem::replace(&mut self.mesh, mesh);
+        for field in fields {
+            field.map_fields(&map, old.cell_volumes());
+        }
+        Ok(Some(map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+    use dugong_types::tensor::Vector;
+
+    fn settings() -> AmrSettings {
+        AmrSettings {
+            lower: 0.1,
+            upper: 0.5,
+            max_level: 2,
+        }
+    }
+
+    #[test]
+    fn test_update_refines_and_maps_fields_conservatively() {
+        let mesh = box_mesh([4, 1, 1], [4.0, 1.0, 1.0]);
+        let mut amr = AdaptiveMesh::new(&mesh, settings()).unwrap();
+        let mut rho = vec![1.0, 2.0, 3.0, 4.0];
+        let mut u = vec![Vector::new(1.0, 0.0, 0.0); 4];
+        let map = amr
+            .update(&[0.9, 0.3, 0.3, 0.3], &mut [&mut rho, &mut u])
+            .unwrap()
+            .unwrap();
+        assert_eq!(amr.mesh().n_cells(), 11);
+        assert_eq!(map.len(), 11);
+        assert_eq!(rho.len(), 11);
+        let mass: f64 = amr
+            .mesh()
+            .cell_volumes()
+            .iter()
+            .zip(&rho)
+            .map(|(v, r)| v * r)
+            .sum();
+        assert!((mass - 10.0).abs() < 1e-12);
+        assert!(u.iter().all(|&v| v == Vector::new(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_update_respects_max_level_and_coarsens() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut amr = AdaptiveMesh::new(&mesh, settings()).unwrap();
+        for _ in 0..4 {
+            let n = amr.mesh().n_cells();
+            amr.update(&vec![1.0; n], &mut []).unwrap();
+        }
+        assert!(amr.cell_levels().iter().all(|&l| l == 2));
+        assert!(amr.update(&vec![1.0; 128], &mut []).unwrap().is_none());
+
+        let mut phi: Vec<f64> = (0..128).map(|c| c as f64).collect();
+        let total: f64 = phi.iter().sum::<f64>() / 64.0;
+        amr.update(&vec![0.0; 128], &mut [&mut phi]).unwrap();
+        assert_eq!(amr.mesh().n_cells(), 16);
+        let mapped: f64 = amr
+            .mesh()
+            .cell_volumes()
+            .iter()
+            .zip(&phi)
+            .map(|(v, p)| v * p)
+            .sum();
+        assert!((mapped - total).abs() < 1e-9);
+    }
+}
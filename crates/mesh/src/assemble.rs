@@ -0,0 +1,188 @@
+use dugong_types::tensor::Vector;
+
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::patch::{Patch, PatchKind};
+use crate::primitive_mesh::PrimitiveMesh;
+
+/// An internal face as `(vertices, cell_a, cell_b)`, oriented from `cell_a` to `cell_b`.
+pub type InternalFace = (Vec<usize>, usize, usize);
+
+/// The faces of one boundary patch, used as input to [`assemble`].
+pub struct BoundaryGroup {
+    /// Name of the patch.
+    pub name: String,
+    /// Kind of the patch.
+    pub kind: PatchKind,
+    /// `(face vertices, owner cell)` pairs, in the desired patch-local order.
+    pub faces: Vec<(Vec<usize>, usize)>,
+}
+
+/// Assembles a [`Mesh`] from unordered internal faces and per-patch boundary faces.
+///
+/// Internal faces are given as `(vertices, cell_a, cell_b)` with the area
+/// vector (right-hand rule) pointing from `cell_a` to `cell_b`. They are
+/// reordered into upper-triangular order — sorted by `(owner, neighbor)` with
+/// `owner < neighbor` — flipping the vertex order where `cell_a > cell_b`.
+/// Boundary faces keep their group order.
+///
+/// Returns the mesh and the face map: `face_map[new_face]` is the input index
+/// of that face, where internal faces are numbered first in input order,
+/// followed by the boundary faces of each group in turn.
+///
+/// # Errors
+///
+/// Returns `Err` if the resulting topology fails [`PrimitiveMesh::new`] or
+/// [`Mesh::new`] validation.
+pub fn assemble(
+    points: Vec<Vector>,
+    internal: Vec<InternalFace>,
+    groups: Vec<BoundaryGroup>,
+) -> Result<(Mesh, Vec<usize>), MeshError> {
+    let n_internal = internal.len();
+    let mut keyed: Vec<(usize, usize, usize, Vec<usize>)> = internal
+        .into_iter()
+        .enumerate()
+        .map(|(i, (mut f, a, b))| {
+            if a > b {
+                f.reverse();
+                (b, a, i, f)
+            } else {
+                (a, b, i, f)
+            }
+        })
+        .collect();
+    keyed.sort_unstable_by_key(|&(o, n, i, _)| (o, n, i));
+
+    let n_boundary: usize = groups.iter().map(|g| g.faces.len()).sum();
+    let mut faces = Vec::with_capacity(n_internal + n_boundary);
+    let mut owner = Vec::with_capacity(n_internal + n_boundary);
+    let mut neighbor = Vec::with_capacity(n_internal);
+    let mut face_map = Vec::with_capacity(n_internal + n_boundary);
+    for (o, n, i, f) in keyed {
+        faces.push(f);
+        owner.push(o);
+        neighbor.push(n);
+        face_map.push(i);
+    }
+
+    let mut patches = Vec::with_capacity(groups.len());
+    let mut next_input = n_internal;
+    for group in groups {
+        patches.push(Patch::new(
+            group.name,
+            group.kind,
+            faces.len(),
+            group.faces.len(),
+        ));
+        for (f, o) in group.faces {
+            faces.push(f);
+            owner.push(o);
+            face_map.push(next_input);
+            next_input += 1;
+        }
+    }
+
+    let primitive = PrimitiveMesh::new(points, faces, owner, neighbor)?;
+    Ok((Mesh::new(primitive, patches)?, face_map))
+}
+
+/// Relabels cells so that the highest-numbered cell owns a boundary face.
+///
+/// [`PrimitiveMesh::new`] derives the cell count from the owners, and in
+/// upper-triangular order the last cell owns no internal face, so a last
+/// cell without boundary faces would be lost. Swaps its label with the owner
+/// of the first boundary face if needed.
+pub fn boundary_cell_last(internal: &mut [InternalFace], groups: &mut [BoundaryGroup]) {
+    let last = internal
+        .iter()
+        .flat_map(|&(_, a, b)| [a, b])
+        .chain(groups.iter().flat_map(|g| g.faces.iter().map(|&(_, c)| c)))
+        .max();
+    let first = groups.iter().find_map(|g| g.faces.first().map(|&(_, c)| c));
+    let (Some(last), Some(first)) = (last, first) else {
+        return;
+    };
+    if groups
+        .iter()
+        .any(|g| g.faces.iter().any(|&(_, c)| c == last))
+    {
+        return;
+    }
+    let swap = |c: &mut usize| {
+        if *c == first {
+            *c = last;
+        } else if *c == last {
+            *c = first;
+        }
+    };
+    for (_, a, b) in internal.iter_mut() {
+        swap(a);
+        swap(b);
+    }
+    for group in groups.iter_mut() {
+        group.faces.iter_mut().for_each(|(_, c)| swap(c));
+    }
+}
+
+/// Splits a mesh into the inputs of [`assemble`], with cells relabelled by
+/// `cell_of` (old cell → new cell).
+pub(crate) fn disassemble(
+    mesh: &Mesh,
+    cell_of: impl Fn(usize) -> usize,
+) -> (Vec<InternalFace>, Vec<BoundaryGroup>) {
+    let faces = mesh.faces();
+    let owner = mesh.owner();
+    let internal = mesh
+        .neighbor()
+        .iter()
+        .enumerate()
+        .map(|(f, &n)| (faces[f].clone(), cell_of(owner[f]), cell_of(n)))
+        .collect();
+    let groups = mesh
+        .patches()
+        .iter()
+        .map(|p| BoundaryGroup {
+            name: p.name().to_string(),
+            kind: p.kind().clone(),
+            faces: p
+                .range()
+                .map(|f| (faces[f].clone(), cell_of(owner[f])))
+                .collect(),
+        })
+        .collect();
+    (internal, groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_disassemble_assemble_identity() {
+        let mesh = box_mesh([3, 2, 2], [1.0, 1.0, 1.0]);
+        let (internal, groups) = disassemble(&mesh, |c| c);
+        let (rebuilt, face_map) = assemble(mesh.points().to_vec(), internal, groups).unwrap();
+        assert_eq!(rebuilt.faces(), mesh.faces());
+        assert_eq!(rebuilt.owner(), mesh.owner());
+        assert_eq!(rebuilt.neighbor(), mesh.neighbor());
+        assert_eq!(rebuilt.patches(), mesh.patches());
+        assert_eq!(face_map, (0..mesh.n_faces()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_assemble_flips_faces_with_owner_above_neighbor() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        // Swap the two cells: the internal face now has owner 1 → neighbor 0.
+        let (internal, groups) = disassemble(&mesh, |c| 1 - c);
+        let (rebuilt, _) = assemble(mesh.points().to_vec(), internal, groups).unwrap();
+        assert_eq!(rebuilt.owner()[0], 0);
+        assert_eq!(rebuilt.neighbor()[0], 1);
+        let d = rebuilt.cell_centers()[1] - rebuilt.cell_centers()[0];
+        assert!(rebuilt.face_areas()[0] * d > 0.0);
+        for &v in rebuilt.cell_volumes() {
+            assert!((v - 1.0).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::assemble::{BoundaryGroup, InternalFace, assemble};
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::patch::PatchKind;
+use crate::zone::Zone;
+
+impl Mesh {
+    /// Converts the internal faces of face zone `face_zone` into baffles:
+    /// pairs of coincident wall faces, one on each side, with no connection
+    /// between the cells they separate.
+    ///
+    /// The owner-side faces form the wall patch `patch_names[0]` and keep
+    /// their orientation; the neighbor-side faces form `patch_names[1]` and
+    /// are reversed so they point out of their new owner. Both patches are
+    /// appended after the existing ones. Points are shared by both sides, and
+    /// boundary faces in the zone are left unchanged. Cells, points and zones
+    /// keep their indices, except that the face zone lists both sides of each
+    /// baffle.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::ZoneNotFound`] if there is no face zone
+    /// `face_zone`, and [`MeshError::DuplicatePatchName`] if a patch name is
+    /// taken or both names are equal.
+    pub fn create_baffles(
+        &self,
+        face_zone: &str,
+        patch_names: [&str; 2],
+    ) -> Result<Mesh, MeshError> {
+        let zone = self
+            .face_zone(face_zone)
+            .ok_or_else(|| MeshError::ZoneNotFound {
+                name: face_zone.to_string(),
+            })?;
+        for (i, name) in patch_names.iter().enumerate() {
+            if self.patch(name).is_some() || patch_names[..i].contains(name) {
+                return Err(MeshError::DuplicatePatchName {
+                    name: name.to_string(),
+                });
+            }
+        }
+
+        let (faces, owner) = (self.faces(), self.owner());
+        // `sources[input]` is the original face of each `assemble` input.
+        let mut sources = Vec::with_capacity(self.n_faces() + zone.len());
+        let mut internal: Vec<InternalFace> = Vec::new();
+        let mut sides = [Vec::new(), Vec::new()];
+        let mut side_sources = [Vec::new(), Vec::new()];
+        for (f, &n) in self.neighbor().iter().enumerate() {
+            if zone.contains(f) {
+                sides[0].push((faces[f].clone(), owner[f]));
+                sides[1].push((faces[f].iter().rev().copied().collect(), n));
+                side_sources[0].push(f);
+                side_sources[1].push(f);
+            } else {
+                internal.push((faces[f].clone(), owner[f], n));
+                sources.push(f);
+            }
+        }
+        let mut groups: Vec<BoundaryGroup> = self
+            .patches()
+            .iter()
+            .map(|p| {
+                sources.extend(p.range());
+                BoundaryGroup {
+                    name: p.name().to_string(),
+                    kind: p.kind().clone(),
+                    faces: p.range().map(|f| (faces[f].clone(), owner[f])).collect(),
+                }
+            })
+            .collect();
+        for ((name, side), side_sources) in patch_names.iter().zip(sides).zip(side_sources) {
+            groups.push(BoundaryGroup {
+                name: name.to_string(),
+                kind: PatchKind::Wall,
+                faces: side,
+            });
+            sources.extend(side_sources);
+        }
+
+        let (mut mesh, face_map) = assemble(self.points().to_vec(), internal, groups)?;
+        let mut new_faces = vec![Vec::new(); self.n_faces()];
+        for (new, &input) in face_map.iter().enumerate() {
+            new_faces[sources[input]].push(new);
+        }
+        mesh.cell_zones = self.cell_zones().to_vec();
+        mesh.point_zones = self.point_zones().to_vec();
+        mesh.face_zones = self
+            .face_zones()
+            .iter()
+            .map(|z| {
+                Zone::new(
+                    z.name(),
+                    z.indices()
+                        .iter()
+                        .flat_map(|&f| new_faces[f].iter().copied()),
+                )
+            })
+            .collect();
+        Ok(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_create_baffles_splits_internal_faces() {
+        let mut mesh = box_mesh([2, 2, 1], [2.0, 2.0, 1.0]);
+        // The two faces on the plane x = 1.
+        let wall: Vec<usize> = (0..mesh.n_internal_faces())
+            .filter(|&f| (mesh.face_centers()[f].as_array()[0] - 1.0).abs() < 1e-12)
+            .collect();
+        assert_eq!(wall.len(), 2);
+        mesh.add_face_zone(Zone::new("wall", wall)).unwrap();
+        let baffled = mesh.create_baffles("wall", ["wall-a", "wall-b"]).unwrap();
+
+        assert_eq!(baffled.n_cells(), 4);
+        assert_eq!(baffled.n_points(), mesh.n_points());
+        assert_eq!(baffled.n_internal_faces(), mesh.n_internal_faces() - 2);
+        assert_eq!(baffled.n_faces(), mesh.n_faces() + 2);
+        assert!(baffled.check().is_ok());
+        let (a, b) = (
+            baffled.patch("wall-a").unwrap(),
+            baffled.patch("wall-b").unwrap(),
+        );
+        assert_eq!((a.kind(), a.size(), b.size()), (&PatchKind::Wall, 2, 2));
+        for (fa, fb) in a.range().zip(b.range()) {
+            let sum = baffled.face_areas()[fa] + baffled.face_areas()[fb];
+            assert!(sum.mag() < 1e-12);
+            assert_ne!(baffled.owner()[fa], baffled.owner()[fb]);
+        }
+        assert_eq!(baffled.face_zone("wall").unwrap().len(), 4);
+        assert_eq!(baffled.cell_volumes(), mesh.cell_volumes());
+    }
+
+    #[test]
+    fn test_create_baffles_rejects_invalid_names() {
+        let mut mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        mesh.add_face_zone(Zone::new("wall", [0])).unwrap();
+        assert!(matches!(
+            mesh.create_baffles("fan", ["a", "b"]),
+            Err(MeshError::ZoneNotFound { .. })
+        ));
+        assert!(matches!(
+            mesh.create_baffles("wall", ["x-min", "b"]),
+            Err(MeshError::DuplicatePatchName { .. })
+        ));
+        assert!(matches!(
+            mesh.create_baffles("wall", ["a", "a"]),
+            Err(MeshError::DuplicatePatchName { .. })
+        ));
+    }
+}
@@ -0,0 +1,89 @@
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::tri_surface::TriSurface;
+
+impl Mesh {
+    /// Extracts the faces of the named patches as a triangulated surface.
+    ///
+    /// Faces are fan-triangulated from their first vertex, in patch order,
+    /// and keep their outward orientation. Only the points of those faces
+    /// are kept, in mesh order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::PatchNotFound`] for unknown patch names.
+    pub fn boundary_surface(&self, patches: &[&str]) -> Result<TriSurface, MeshError> {
+        let mut faces = Vec::new();
+        for name in patches {
+            let patch = self.patch(name).ok_or_else(|| MeshError::PatchNotFound {
+                name: name.to_string(),
+            })?;
+            faces.extend(patch.range());
+        }
+
+        let mut compact = vec![usize::MAX; self.n_points()];
+        for &f in &faces {
+            for &p in &self.faces()[f] {
+                compact[p] = 0;
+            }
+        }
+        let mut points = Vec::new();
+        for (p, new) in compact.iter_mut().enumerate() {
+            if *new == 0 {
+                *new = points.len();
+                points.push(self.points()[p]);
+            }
+        }
+        let triangles = faces
+            .iter()
+            .flat_map(|&f| {
+                let face = &self.faces()[f];
+                (1..face.len() - 1).map(move |k| [face[0], face[k], face[k + 1]])
+            })
+            .map(|t| t.map(|p| compact[p]))
+            .collect();
+        TriSurface::new(points, triangles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_boundary_surface_of_box_is_closed() {
+        let mesh = box_mesh([2, 2, 1], [2.0, 2.0, 1.0]);
+        let all: Vec<&str> = mesh.patches().iter().map(|p| p.name()).collect();
+        let surface = mesh.boundary_surface(&all).unwrap();
+        // Every point of a 2x2x1 box lies on its boundary.
+        assert_eq!(surface.points().len(), mesh.n_points());
+        assert_eq!(
+            surface.n_triangles(),
+            2 * (mesh.n_faces() - mesh.n_internal_faces())
+        );
+        assert!((surface.area() - 16.0).abs() < 1e-12);
+        assert!(surface.is_inside(Vector::new(1.0, 1.0, 0.5)));
+        assert!(!surface.is_inside(Vector::new(3.0, 1.0, 0.5)));
+    }
+
+    #[test]
+    fn test_boundary_surface_selects_patches() {
+        let mesh = box_mesh([2, 2, 1], [2.0, 2.0, 1.0]);
+        let surface = mesh.boundary_surface(&["z-max"]).unwrap();
+        assert_eq!(surface.points().len(), 9);
+        assert_eq!(surface.n_triangles(), 8);
+        assert!(
+            surface
+                .normals()
+                .iter()
+                .all(|n| (n.z() - 1.0).abs() < 1e-12)
+        );
+        assert!(matches!(
+            mesh.boundary_surface(&["inlet"]),
+            Err(MeshError::PatchNotFound { .. })
+        ));
+    }
+}
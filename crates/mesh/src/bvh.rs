@@ -0,0 +1,295 @@
+use dugong_types::tensor::Vector;
+
+/// Items per leaf below which nodes are not split further.
+const LEAF_SIZE: usize = 4;
+
+/// An axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Aabb {
+    pub(crate) min: Vector,
+    pub(crate) max: Vector,
+}
+
+impl Aabb {
+    /// Returns an empty box, the identity of [`Aabb::union`].
+    pub(crate) fn empty() -> Self {
+        Self {
+            min: Vector::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            max: Vector::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        }
+    }
+
+    /// Returns the bounding box of `points`.
+    pub(crate) fn of_points(points: impl IntoIterator<Item = Vector>) -> Self {
+        points
+            .into_iter()
+            .fold(Self::empty(), |b, p| b.union(&Self { min: p, max: p }))
+    }
+
+    /// Returns the smallest box containing both boxes.
+    pub(crate) fn union(&self, other: &Self) -> Self {
+        let [a, b] = [self.min.as_array(), other.min.as_array()];
+        let [c, d] = [self.max.as_array(), other.max.as_array()];
+        Self {
+            min: Vector::new(a[0].min(b[0]), a[1].min(b[1]), a[2].min(b[2])),
+            max: Vector::new(c[0].max(d[0]), c[1].max(d[1]), c[2].max(d[2])),
+        }
+    }
+
+    /// Returns the box center.
+    pub(crate) fn center(&self) -> Vector {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Returns `true` if `p` lies inside the box grown by `tol`.
+    pub(crate) fn contains(&self, p: Vector, tol: f64) -> bool {
+        let (p, lo, hi) = (p.as_array(), self.min.as_array(), self.max.as_array());
+        (0..3).all(|i| p[i] >= lo[i] - tol && p[i] <= hi[i] + tol)
+    }
+
+    /// Returns `true` if the ray `origin + t * direction`, `0 <= t <= t_max`,
+    /// passes through the box (slab test).
+    pub(crate) fn hits_ray(&self, origin: Vector, direction: Vector, t_max: f64) -> bool {
+        let (o, d) = (origin.as_array(), direction.as_array());
+        let (lo, hi) = (self.min.as_array(), self.max.as_array());
+        let (mut t0, mut t1) = (0.0_f64, t_max);
+        for i in 0..3 {
+            if d[i] == 0.0 {
+                if o[i] < lo[i] || o[i] > hi[i] {
+                    return false;
+                }
+                continue;
+            }
+            let (a, b) = ((lo[i] - o[i]) / d[i], (hi[i] - o[i]) / d[i]);
+            t0 = t0.max(a.min(b));
+            t1 = t1.min(a.max(b));
+            if t0 > t1 {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Returns the distance from `p` to the box (zero inside).
+    pub(crate) fn distance(&self, p: Vector) -> f64 {
+        let (p, lo, hi) = (p.as_array(), self.min.as_array(), self.max.as_array());
+        let d: [f64; 3] = std::array::from_fn(|i| (lo[i] - p[i]).max(p[i] - hi[i]).max(0.0));
+        Vector::new(d[0], d[1], d[2]).mag()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf {
+        bbox: Aabb,
+        start: usize,
+        end: usize,
+    },
+    Inner {
+        bbox: Aabb,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl Node {
+    fn bbox(&self) -> &Aabb {
+        match self {
+            Node::Leaf { bbox, .. } | Node::Inner { bbox, .. } => bbox,
+        }
+    }
+}
+
+/// A bounding volume hierarchy over a set of boxes.
+///
+/// Items are identified by their index in the slice passed to
+/// [`Bvh::new`]. Nodes are split at the median of the item centers along the
+/// longest axis of the node box.
+#[derive(Debug, Clone)]
+pub(crate) struct Bvh {
+    nodes: Vec<Node>,
+    items: Vec<usize>,
+}
+
+impl Bvh {
+    /// Builds the hierarchy over `boxes`.
+    pub(crate) fn new(boxes: &[Aabb]) -> Self {
+        let mut bvh = Self {
+            nodes: Vec::new(),
+            items: (0..boxes.len()).collect(),
+        };
+        if !boxes.is_empty() {
+            bvh.build(boxes, 0, boxes.len());
+        }
+        bvh
+    }
+
+    /// Builds the subtree over `items[start..end]` and returns its node index.
+    fn build(&mut self, boxes: &[Aabb], start: usize, end: usize) -> usize {
+        let bbox = self.items[start..end]
+            .iter()
+            .fold(Aabb::empty(), |b, &i| b.union(&boxes[i]));
+        let index = self.nodes.len();
+        if end - start <= LEAF_SIZE {
+            self.nodes.push(Node::Leaf { bbox, start, end });
+            return index;
+        }
+        let extent = *(bbox.max - bbox.min).as_array();
+        let axis = (0..3)
+            .max_by(|&a, &b| extent[a].total_cmp(&extent[b]))
+            .unwrap_or(0);
+        let mid = (start + end) / 2;
+        self.items[start..end].select_nth_unstable_by(mid - start, |&a, &b| {
+            let (ca, cb) = (*boxes[a].center().as_array(), *boxes[b].center().as_array());
+            ca[axis].total_cmp(&cb[axis])
+        });
+        // Reserve the slot, then fill it once the children exist.
+        self.nodes.push(Node::Leaf { bbox, start, end });
+        let left = self.build(boxes, start, mid);
+        let right = self.build(boxes, mid, end);
+        self.nodes[index] = Node::Inner { bbox, left, right };
+        index
+    }
+
+    /// Returns the item minimizing `distance(item)` and that distance, or
+    /// `None` if the hierarchy is empty.
+    ///
+    /// `distance` must never be smaller than the distance from `p` to the
+    /// item's box, which is used to prune the search.
+    pub(crate) fn nearest(
+        &self,
+        p: Vector,
+        mut distance: impl FnMut(usize) -> f64,
+    ) -> Option<(usize, f64)> {
+        let mut best: Option<(usize, f64)> = None;
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if best.is_some_and(|(_, d)| node.bbox().distance(p) >= d) {
+                continue;
+            }
+            match *node {
+                Node::Leaf { start, end, .. } => {
+                    for &item in &self.items[start..end] {
+                        let d = distance(item);
+                        if best.is_none_or(|(_, b)| d < b) {
+                            best = Some((item, d));
+                        }
+                    }
+                }
+                Node::Inner { left, right, .. } => {
+                    // Visit the closer child first.
+                    let (dl, dr) = (
+                        self.nodes[left].bbox().distance(p),
+                        self.nodes[right].bbox().distance(p),
+                    );
+                    if dl < dr {
+                        stack.extend([right, left]);
+                    } else {
+                        stack.extend([left, right]);
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// Calls `visit` for every item whose box passes `accept`, pruning
+    /// subtrees whose box fails it.
+    pub(crate) fn visit(&self, accept: impl Fn(&Aabb) -> bool, mut visit: impl FnMut(usize)) {
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if !accept(node.bbox()) {
+                continue;
+            }
+            match *node {
+                Node::Leaf { start, end, .. } => {
+                    for &item in &self.items[start..end] {
+                        visit(item);
+                    }
+                }
+                Node::Inner { left, right, .. } => stack.extend([left, right]),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_boxes(n: usize) -> Vec<Aabb> {
+        (0..n)
+            .map(|i| {
+                let p = Vector::new(i as f64, (i % 3) as f64, 0.0);
+                Aabb {
+                    min: p,
+                    max: p + Vector::new(0.5, 0.5, 0.5),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_aabb_distance_zero_inside() {
+        let b = Aabb::of_points([Vector::zero(), Vector::new(1.0, 2.0, 3.0)]);
+        assert_eq!(b.distance(Vector::new(0.5, 0.5, 0.5)), 0.0);
+        assert!((b.distance(Vector::new(4.0, 6.0, 3.0)) - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_bvh_nearest_matches_brute_force() {
+        let boxes = unit_boxes(37);
+        let bvh = Bvh::new(&boxes);
+        for q in [
+            Vector::new(10.2, 1.3, 0.1),
+            Vector::new(-5.0, 0.0, 0.0),
+            Vector::new(40.0, 9.0, -2.0),
+        ] {
+            let (item, d) = bvh.nearest(q, |i| boxes[i].distance(q)).unwrap();
+            let brute = boxes
+                .iter()
+                .map(|b| b.distance(q))
+                .fold(f64::INFINITY, f64::min);
+            assert_eq!(d, brute);
+            assert_eq!(boxes[item].distance(q), brute);
+        }
+    }
+
+    #[test]
+    fn test_aabb_contains_and_hits_ray() {
+        let b = Aabb::of_points([Vector::zero(), Vector::new(1.0, 1.0, 1.0)]);
+        assert!(b.contains(Vector::new(1.0, 0.5, 0.0), 0.0));
+        assert!(!b.contains(Vector::new(1.1, 0.5, 0.0), 0.0));
+        let o = Vector::new(-1.0, 0.5, 0.5);
+        assert!(b.hits_ray(o, Vector::new(1.0, 0.0, 0.0), 10.0));
+        assert!(!b.hits_ray(o, Vector::new(1.0, 0.0, 0.0), 0.5));
+        assert!(!b.hits_ray(o, Vector::new(-1.0, 0.0, 0.0), 10.0));
+        assert!(!b.hits_ray(o, Vector::new(1.0, 2.0, 0.0), 10.0));
+    }
+
+    #[test]
+    fn test_bvh_visit_prunes_by_box() {
+        let boxes = unit_boxes(20);
+        let bvh = Bvh::new(&boxes);
+        let q = Vector::new(7.2, 1.2, 0.2);
+        let mut hits = Vec::new();
+        bvh.visit(|b| b.contains(q, 0.0), |i| hits.push(i));
+        hits.retain(|&i| boxes[i].contains(q, 0.0));
+        assert_eq!(hits, vec![7]);
+    }
+
+    #[test]
+    fn test_bvh_empty_has_no_nearest() {
+        assert!(Bvh::new(&[]).nearest(Vector::zero(), |_| 0.0).is_none());
+    }
+}
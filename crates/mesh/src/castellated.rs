@@ -0,0 +1,234 @@
+use crate::assemble::{BoundaryGroup, InternalFace, assemble, boundary_cell_last};
+use crate::bvh::Aabb;
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::patch::PatchKind;
+use crate::refine::HexRefiner;
+use crate::tri_surface::TriSurface;
+
+/// Settings for [`castellated_mesh`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastellatedSettings {
+    /// Number of times cells near the surface are split.
+    pub refinement_levels: usize,
+    /// Whether points of the body patch are moved onto the surface.
+    pub snap: bool,
+    /// Name of the wall patch created where the body was removed.
+    pub patch: String,
+}
+
+/// Meshes the region outside a closed surface by carving it out of a
+/// hexahedral background mesh.
+///
+/// Cells within half their diagonal of the surface are refined
+/// `refinement_levels` times (with 2:1 balance), cells whose center lies
+/// inside the surface are removed, and the exposed faces form the wall
+/// patch [`CastellatedSettings::patch`], appended after the background
+/// patches. With [`CastellatedSettings::snap`], the body patch points are
+/// then projected onto the surface, which smooths the staircase but may
+/// distort the cells next to it; run [`Mesh::check`] on the result. Zones
+/// of the background mesh are not carried over.
+///
+/// # Errors
+///
+/// Returns [`MeshError::NotHexahedral`] if a background cell is not a
+/// hexahedron, [`MeshError::DuplicatePatchName`] if the body patch name is
+/// taken, and [`MeshError::InvalidMeshing`] if no cell is left outside the
+/// surface.
+pub fn castellated_mesh(
+    background: &Mesh,
+    surface: &TriSurface,
+    settings: &CastellatedSettings,
+) -> Result<Mesh, MeshError> {
+    if background.patch(&settings.patch).is_some() {
+        return Err(MeshError::DuplicatePatchName {
+            name: settings.patch.clone(),
+        });
+    }
+    let mut refiner = HexRefiner::new(background)?;
+    for _ in 0..settings.refinement_levels {
+        let mesh = refiner.mesh()?;
+        let near: Vec<usize> = (0..mesh.n_cells())
+            .filter(|&c| {
+                let bounds =
+                    Aabb::of_points(mesh.cell_points()[c].iter().map(|&p| mesh.points()[p]));
+                let center = mesh.cell_centers()[c];
+                surface.nearest(center).is_some_and(|(_, q)| {
+                    (q - center).mag() <= 0.5 * (bounds.max - bounds.min).mag()
+                })
+            })
+            .collect();
+        if near.is_empty() {
+            break;
+        }
+        refiner.refine(&near);
+    }
+    let mesh = refiner.mesh()?;
+
+    // Renumber the kept cells in order.
+    let mut cell_of = vec![usize::MAX; mesh.n_cells()];
+    let mut n_kept = 0;
+    for (c, &center) in mesh.cell_centers().iter().enumerate() {
+        if !surface.is_inside(center) {
+            cell_of[c] = n_kept;
+            n_kept += 1;
+        }
+    }
+    if n_kept == 0 {
+        return Err(MeshError::InvalidMeshing {
+            reason: "no cell lies outside the surface".to_string(),
+        });
+    }
+
+    let (faces, owner) = (mesh.faces(), mesh.owner());
+    let mut internal: Vec<InternalFace> = Vec::new();
+    let mut body = Vec::new();
+    for (f, &n) in mesh.neighbor().iter().enumerate() {
+        match (cell_of[owner[f]], cell_of[n]) {
+            (usize::MAX, usize::MAX) => {}
+            (a, usize::MAX) => body.push((faces[f].clone(), a)),
+            (usize::MAX, b) => body.push((faces[f].iter().rev().copied().collect(), b)),
+            (a, b) => internal.push((faces[f].clone(), a, b)),
+        }
+    }
+    let mut groups: Vec<BoundaryGroup> = mesh
+        .patches()
+        .iter()
+        .map(|p| BoundaryGroup {
+            name: p.name().to_string(),
+            kind: p.kind().clone(),
+            faces: p
+                .range()
+                .filter(|&f| cell_of[owner[f]] != usize::MAX)
+                .map(|f| (faces[f].clone(), cell_of[owner[f]]))
+                .collect(),
+        })
+        .collect();
+    groups.push(BoundaryGroup {
+        name: settings.patch.clone(),
+        kind: PatchKind::Wall,
+        faces: body,
+    });
+
+    // Compact the points to those of the kept faces.
+    let mut point_of = vec![usize::MAX; mesh.n_points()];
+    let mut points = Vec::new();
+    let kept_faces = internal.iter_mut().map(|(f, _, _)| f).chain(
+        groups
+            .iter_mut()
+            .flat_map(|g| g.faces.iter_mut().map(|(f, _)| f)),
+    );
+    for face in kept_faces {
+        for v in face.iter_mut() {
+            if point_of[*v] == usize::MAX {
+                point_of[*v] = points.len();
+                points.push(mesh.points()[*v]);
+            }
+            *v = point_of[*v];
+        }
+    }
+    boundary_cell_last(&mut internal, &mut groups);
+    let (mut mesh, _) = assemble(points, internal, groups)?;
+
+    if settings.snap {
+        // Safety: the body patch was added above.
+        let range = mesh.patch(&settings.patch).unwrap().range();
+        let mut points = mesh.points().to_vec();
+        let mut moved = vec![false; points.len()];
+        for f in range {
+            for &v in &mesh.faces()[f] {
+                if !moved[v] {
+                    moved[v] = true;
+                    if let Some((_, q)) = surface.nearest(points[v]) {
+                        points[v] = q;
+                    }
+                }
+            }
+        }
+        mesh.primitive.set_points(points);
+    }
+    Ok(mesh)
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+    use crate::test_meshes::box_mesh;
+    use crate::tri_surface::tests::unit_cube;
+
+    /// The cube `[lo, hi]^3` as a closed surface.
+    fn cube(lo: f64, hi: f64) -> TriSurface {
+        let unit = unit_cube();
+        let points = unit
+            .points()
+            .iter()
+            .map(|&p| p * (hi - lo) + Vector::new(lo, lo, lo))
+            .collect();
+        TriSurface::new(points, unit.triangles().to_vec()).unwrap()
+    }
+
+    fn settings(snap: bool) -> CastellatedSettings {
+        CastellatedSettings {
+            refinement_levels: 1,
+            snap,
+            patch: "body".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_castellated_mesh_carves_aligned_body() {
+        let background = box_mesh([5, 5, 5], [1.0, 1.0, 1.0]);
+        let mesh = castellated_mesh(&background, &cube(0.3, 0.7), &settings(false)).unwrap();
+        assert!(mesh.check().is_ok());
+        // The body faces lie on the refined grid, so the carved volume is
+        // exact.
+        let volume: f64 = mesh.cell_volumes().iter().sum();
+        assert!((volume - (1.0 - 0.4f64.powi(3))).abs() < 1e-12);
+
+        let names: Vec<&str> = mesh.patches().iter().map(|p| p.name()).collect();
+        assert_eq!(
+            names,
+            ["x-min", "x-max", "y-min", "y-max", "z-min", "z-max", "body"]
+        );
+        let body = mesh.patch("body").unwrap();
+        let area: f64 = body.range().map(|f| mesh.face_areas()[f].mag()).sum();
+        assert!((area - 6.0 * 0.16).abs() < 1e-12);
+        // Body faces point into the body, away from the fluid cells.
+        let center = Vector::new(0.5, 0.5, 0.5);
+        for f in body.range() {
+            assert!((center - mesh.face_centers()[f]) * mesh.face_areas()[f] > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_castellated_mesh_snaps_to_surface() {
+        let background = box_mesh([5, 5, 5], [1.0, 1.0, 1.0]);
+        let surface = cube(0.33, 0.67);
+        let exact = 1.0 - 0.34f64.powi(3);
+        let coarse = castellated_mesh(&background, &surface, &settings(false)).unwrap();
+        let snapped = castellated_mesh(&background, &surface, &settings(true)).unwrap();
+        let error = |m: &Mesh| (m.cell_volumes().iter().sum::<f64>() - exact).abs();
+        assert!(error(&snapped) < error(&coarse));
+        assert!(snapped.cell_volumes().iter().all(|&v| v > 0.0));
+        let body = snapped.patch("body").unwrap();
+        for f in body.range() {
+            for &v in &snapped.faces()[f] {
+                let (_, q) = surface.nearest(snapped.points()[v]).unwrap();
+                assert!((q - snapped.points()[v]).mag() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_castellated_mesh_rejects_taken_patch_name() {
+        let background = box_mesh([2, 2, 2], [1.0, 1.0, 1.0]);
+        let mut s = settings(false);
+        s.patch = "x-min".to_string();
+        assert!(matches!(
+            castellated_mesh(&background, &cube(0.3, 0.7), &s),
+            Err(MeshError::DuplicatePatchName { .. })
+        ));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::mesh::Mesh;
+
+/// The shape of a cell, recognized from its topology.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellShape {
+    /// Four triangles, four points.
+    Tet,
+    /// One quadrilateral and four triangles, five points.
+    Pyramid,
+    /// Two triangles and three quadrilaterals, six points (a wedge).
+    Prism,
+    /// Six quadrilaterals, eight points.
+    Hex,
+    /// Any other cell.
+    Polyhedron,
+}
+
+impl CellShape {
+    /// Returns the VTK cell type identifier of the shape.
+    pub fn vtk_cell_type(self) -> u8 {
+        match self {
+            CellShape::Tet => 10,
+            CellShape::Hex => 12,
+            CellShape::Prism => 13,
+            CellShape::Pyramid => 14,
+            CellShape::Polyhedron => 42,
+        }
+    }
+}
+
+impl Mesh {
+    /// Classifies each cell by its face sizes, point count and edge count.
+    ///
+    /// A cell is given a named shape only if it has exactly the faces,
+    /// points and edges of that shape, so cells with hanging nodes or split
+    /// faces are polyhedra.
+    pub fn cell_shapes(&self) -> Vec<CellShape> {
+        (0..self.n_cells())
+            .map(|c| {
+                // Count triangles and quadrilaterals; anything else is a
+                // polyhedron.
+                let (mut tris, mut quads) = (0, 0);
+                for &f in &self.cell_faces()[c] {
+                    match self.faces()[f].len() {
+                        3 => tris += 1,
+                        4 => quads += 1,
+                        _ => return CellShape::Polyhedron,
+                    }
+                }
+                let counts = (self.cell_points()[c].len(), self.cell_edges()[c].len());
+                match (tris, quads, counts) {
+                    (4, 0, (4, 6)) => CellShape::Tet,
+                    (4, 1, (5, 8)) => CellShape::Pyramid,
+                    (2, 3, (6, 9)) => CellShape::Prism,
+                    (0, 6, (8, 12)) => CellShape::Hex,
+                    _ => CellShape::Polyhedron,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+    use crate::assemble::{BoundaryGroup, assemble};
+    use crate::patch::PatchKind;
+    use crate::refine::HexRefiner;
+    use crate::test_meshes::box_mesh;
+
+    /// Builds a single-cell mesh from outward boundary faces.
+    fn single_cell(points: Vec<Vector>, faces: Vec<Vec<usize>>) -> Mesh {
+        let group = BoundaryGroup {
+            name: "walls".to_string(),
+            kind: PatchKind::Wall,
+            faces: faces.into_iter().map(|f| (f, 0)).collect(),
+        };
+        assemble(points, vec![], vec![group]).unwrap().0
+    }
+
+    #[test]
+    fn test_cell_shapes_recognizes_standard_cells() {
+        let p = |x, y, z| Vector::new(x, y, z);
+        let tet = single_cell(
+            vec![
+                p(0.0, 0.0, 0.0),
+                p(1.0, 0.0, 0.0),
+                p(0.0, 1.0, 0.0),
+                p(0.0, 0.0, 1.0),
+            ],
+            vec![vec![0, 2, 1], vec![0, 1, 3], vec![0, 3, 2], vec![1, 2, 3]],
+        );
+        assert_eq!(tet.cell_shapes(), vec![CellShape::Tet]);
+
+        let mut pyramid_points = vec![
+            p(0.0, 0.0, 0.0),
+            p(1.0, 0.0, 0.0),
+            p(1.0, 1.0, 0.0),
+            p(0.0, 1.0, 0.0),
+        ];
+        pyramid_points.push(p(0.5, 0.5, 1.0));
+        let pyramid = single_cell(
+            pyramid_points,
+            vec![
+                vec![0, 3, 2, 1],
+                vec![0, 1, 4],
+                vec![1, 2, 4],
+                vec![2, 3, 4],
+                vec![3, 0, 4],
+            ],
+        );
+        assert_eq!(pyramid.cell_shapes(), vec![CellShape::Pyramid]);
+
+        let prism = single_cell(
+            vec![
+                p(0.0, 0.0, 0.0),
+                p(1.0, 0.0, 0.0),
+                p(0.0, 1.0, 0.0),
+                p(0.0, 0.0, 1.0),
+                p(1.0, 0.0, 1.0),
+                p(0.0, 1.0, 1.0),
+            ],
+            vec![
+                vec![0, 2, 1],
+                vec![3, 4, 5],
+                vec![0, 1, 4, 3],
+                vec![1, 2, 5, 4],
+                vec![2, 0, 3, 5],
+            ],
+        );
+        assert_eq!(prism.cell_shapes(), vec![CellShape::Prism]);
+
+        let hex = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        assert_eq!(hex.cell_shapes(), vec![CellShape::Hex; 2]);
+        assert_eq!(CellShape::Prism.vtk_cell_type(), 13);
+    }
+
+    #[test]
+    fn test_cell_shapes_hanging_nodes_make_polyhedra() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut refiner = HexRefiner::new(&mesh).unwrap();
+        refiner.refine(&[0]);
+        let refined = refiner.mesh().unwrap();
+        let shapes = refined.cell_shapes();
+        assert_eq!(shapes.iter().filter(|&&s| s == CellShape::Hex).count(), 8);
+        assert_eq!(
+            shapes
+                .iter()
+                .filter(|&&s| s == CellShape::Polyhedron)
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use std::fmt;
+
+use dugong_types::tensor::Vector;
+
+use crate::mesh::Mesh;
+
+/// Relative tolerance on the sum of a cell's outward face area vectors.
+const CLOSED_TOL: f64 = 1e-6;
+
+/// Quality thresholds above which [`Mesh::check_with`] reports faces and
+/// cells.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CheckThresholds {
+    /// Face non-orthogonality, in degrees.
+    pub non_orthogonality: f64,
+    /// Face skewness.
+    pub skewness: f64,
+    /// Face warpage.
+    pub warpage: f64,
+    /// Cell volume ratio; see [`Mesh::cell_volume_ratio`].
+    pub volume_ratio: f64,
+}
+
+impl Default for CheckThresholds {
+    fn default() -> Self {
+        Self {
+            non_orthogonality: 70.0,
+            skewness: 4.0,
+            warpage: 0.01,
+            volume_ratio: 10.0,
+        }
+    }
+}
+
+/// The result of [`Mesh::check`].
+///
+/// Topological and geometric errors (open or inverted cells) make the mesh
+/// unusable; quality warnings (high non-orthogonality, skewness, warpage or
+/// volume ratio) degrade accuracy and convergence. The [`Display`](fmt::Display) implementation
+/// formats a report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshCheck {
+    /// Cells whose faces do not enclose a closed volume.
+    pub open_cells: Vec<usize>,
+    /// Cells with zero or negative volume.
+    pub inverted_cells: Vec<usize>,
+    /// Largest angle between an internal face normal and the line joining
+    /// its cell centers, in degrees.
+    pub max_non_orthogonality: f64,
+    /// Area-weighted average of the same angle, in degrees.
+    pub average_non_orthogonality: f64,
+    /// Internal faces whose non-orthogonality exceeds the threshold.
+    pub severely_non_orthogonal_faces: Vec<usize>,
+    /// Largest face skewness: the skewness vector length over the distance
+    /// between the cell centers.
+    pub max_skewness: f64,
+    /// Internal faces whose skewness exceeds the threshold.
+    pub highly_skewed_faces: Vec<usize>,
+    /// Largest face warpage; see [`Mesh::face_warpage`].
+    pub max_warpage: f64,
+    /// Faces whose warpage exceeds the threshold, with their warpage.
+    pub warped_faces: Vec<(usize, f64)>,
+    /// Largest cell volume ratio; see [`Mesh::cell_volume_ratio`].
+    pub max_volume_ratio: f64,
+    /// Cells whose volume ratio exceeds the threshold, with their ratio.
+    pub high_volume_ratio_cells: Vec<(usize, f64)>,
+    /// The thresholds the warnings were judged against.
+    pub thresholds: CheckThresholds,
+}
+
+impl MeshCheck {
+    /// Returns `true` if no errors were found. Quality warnings do not
+    /// count.
+    pub fn is_ok(&self) -> bool {
+        self.open_cells.is_empty() && self.inverted_cells.is_empty()
+    }
+}
+
+impl Mesh {
+    /// Returns the volume ratio of each cell: the largest ratio of the larger
+    /// to the smaller volume between the cell and any face neighbor.
+    ///
+    /// Cells without neighbors have ratio 1; a neighbor with non-positive
+    /// volume gives an infinite ratio.
+    pub fn cell_volume_ratio(&self) -> Vec<f64> {
+        let volumes = self.cell_volumes();
+        let mut ratio = vec![1.0; self.n_cells()];
+        for (f, &n) in self.neighbor().iter().enumerate() {
+            let o = self.owner()[f];
+            let (small, large) = (volumes[o].min(volumes[n]), volumes[o].max(volumes[n]));
+            let r = if small > 0.0 {
+                large / small
+            } else {
+                f64::INFINITY
+            };
+            ratio[o] = f64::max(ratio[o], r);
+            ratio[n] = f64::max(ratio[n], r);
+        }
+        ratio
+    }
+
+    /// Checks the mesh against the default [`CheckThresholds`].
+    pub fn check(&self) -> MeshCheck {
+        self.check_with(&CheckThresholds::default())
+    }
+
+    /// Checks the mesh for open or inverted cells and measures face
+    /// non-orthogonality, skewness and warpage and cell volume ratio,
+    /// reporting faces and cells above `thresholds`.
+    pub fn check_with(&self, thresholds: &CheckThresholds) -> MeshCheck {
+        let (sf, cc) = (self.face_areas(), self.cell_centers());
+        let (owner, neighbor) = (self.owner(), self.neighbor());
+
+        let mut sum = vec![Vector::zero(); self.n_cells()];
+        let mut sum_mag = vec![0.0; self.n_cells()];
+        for f in 0..self.n_faces() {
+            sum[owner[f]] += sf[f];
+            sum_mag[owner[f]] += sf[f].mag();
+            if let Some(&n) = neighbor.get(f) {
+                sum[n] -= sf[f];
+                sum_mag[n] += sf[f].mag();
+            }
+        }
+        let open_cells = (0..self.n_cells())
+            .filter(|&c| sum[c].mag() > CLOSED_TOL * sum_mag[c])
+            .collect();
+        let inverted_cells = (0..self.n_cells())
+            .filter(|&c| self.cell_volumes()[c] <= 0.0)
+            .collect();
+
+        let mut max_non_orthogonality: f64 = 0.0;
+        let (mut weighted, mut total_area) = (0.0, 0.0);
+        let mut severely_non_orthogonal_faces = Vec::new();
+        let mut max_skewness: f64 = 0.0;
+        let mut highly_skewed_faces = Vec::new();
+        for (f, &n) in neighbor.iter().enumerate() {
+            let d = cc[n] - cc[owner[f]];
+            let (s_mag, d_mag) = (sf[f].mag(), d.mag());
+            if s_mag == 0.0 || d_mag == 0.0 {
+                continue;
+            }
+            let cos = ((sf[f] * d) / (s_mag * d_mag)).clamp(-1.0, 1.0);
+            let angle = cos.acos().to_degrees();
+            max_non_orthogonality = max_non_orthogonality.max(angle);
+            weighted += angle * s_mag;
+            total_area += s_mag;
+            if angle > thresholds.non_orthogonality {
+                severely_non_orthogonal_faces.push(f);
+            }
+            let skewness = self.skewness_vectors()[f].mag() / d_mag;
+            max_skewness = max_skewness.max(skewness);
+            if skewness > thresholds.skewness {
+                highly_skewed_faces.push(f);
+            }
+        }
+
+        let warpage = self.face_warpage();
+        let max_warpage = warpage.iter().copied().fold(0.0, f64::max);
+        let warped_faces = warpage
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, w)| w > thresholds.warpage)
+            .collect();
+
+        let ratio = self.cell_volume_ratio();
+        let max_volume_ratio = ratio.iter().copied().fold(1.0, f64::max);
+        let high_volume_ratio_cells = ratio
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, r)| r > thresholds.volume_ratio)
+            .collect();
+
+        MeshCheck {
+            open_cells,
+            inverted_cells,
+            max_non_orthogonality,
+            average_non_orthogonality: if total_area > 0.0 {
+                weighted / total_area
+            } else {
+                0.0
+            },
+            severely_non_orthogonal_faces,
+            max_skewness,
+            highly_skewed_faces,
+            max_warpage,
+            warped_faces,
+            max_volume_ratio,
+            high_volume_ratio_cells,
+            thresholds: *thresholds,
+        }
+    }
+}
+
+impl fmt::Display for MeshCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let t = &self.thresholds;
+        writeln!(f, "Mesh check")?;
+        writeln!(f, "    open cells:           {}", self.open_cells.len())?;
+        writeln!(f, "    inverted cells:       {}", self.inverted_cells.len())?;
+        writeln!(
+            f,
+            "    non-orthogonality:    max {:.2}, average {:.2} degrees",
+            self.max_non_orthogonality, self.average_non_orthogonality
+        )?;
+        writeln!(
+            f,
+            "    faces above {} deg:   {}",
+            t.non_orthogonality,
+            self.severely_non_orthogonal_faces.len()
+        )?;
+        writeln!(f, "    max skewness:         {:.4}", self.max_skewness)?;
+        writeln!(
+            f,
+            "    faces above skew {}:  {}",
+            t.skewness,
+            self.highly_skewed_faces.len()
+        )?;
+        writeln!(f, "    max warpage:          {:.4}", self.max_warpage)?;
+        writeln!(
+            f,
+            "    faces above warp {}: {}",
+            t.warpage,
+            self.warped_faces.len()
+        )?;
+        writeln!(f, "    max volume ratio:     {:.4}", self.max_volume_ratio)?;
+        writeln!(
+            f,
+            "    cells above ratio {}: {}",
+            t.volume_ratio,
+            self.high_volume_ratio_cells.len()
+        )?;
+        if self.is_ok() {
+            writeln!(f, "Mesh OK.")
+        } else {
+            writeln!(f, "Failed mesh checks.")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_check_box_mesh_is_ok() {
+        let check = box_mesh([3, 2, 2], [3.0, 2.0, 2.0]).check();
+        assert!(check.is_ok());
+        assert!(check.max_non_orthogonality < 1e-6);
+        assert!(check.max_skewness < 1e-12);
+        assert!(check.to_string().contains("Mesh OK."));
+    }
+
+    #[test]
+    fn test_check_reports_sheared_and_inverted_cells() {
+        let mut mesh = box_mesh([2, 2, 1], [2.0, 2.0, 1.0]);
+        let points = mesh
+            .points()
+            .iter()
+            .map(|p| *p + Vector::new(0.5 * p.y(), 0.0, 0.0))
+            .collect();
+        mesh.move_points(points).unwrap();
+        let check = mesh.check();
+        assert!(check.is_ok());
+        assert!(check.max_non_orthogonality > 10.0);
+
+        // Mirroring the points without reversing the faces inverts every
+        // cell.
+        mesh.primitive.transform_points(|p| -p, false);
+        let check = mesh.check();
+        assert_eq!(check.inverted_cells.len(), 4);
+        assert!(!check.is_ok());
+    }
+
+    #[test]
+    fn test_check_with_reports_abrupt_volume_jumps() {
+        let mut mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        // Squeeze the middle cell to a tenth of the width of the first.
+        let points = mesh
+            .points()
+            .iter()
+            .map(|p| {
+                let x = match p.x() {
+                    x if x < 1.5 => x,
+                    x if x < 2.5 => 1.1,
+                    x => x,
+                };
+                Vector::new(x, p.y(), p.z())
+            })
+            .collect();
+        mesh.move_points(points).unwrap();
+        let ratio = mesh.cell_volume_ratio();
+        assert!((ratio[0] - 10.0).abs() < 1e-9);
+        assert!((ratio[1] - 19.0).abs() < 1e-9);
+        assert!((ratio[2] - 19.0).abs() < 1e-9);
+
+        let check = mesh.check();
+        assert!((check.max_volume_ratio - 19.0).abs() < 1e-9);
+        assert_eq!(check.high_volume_ratio_cells.len(), 2);
+        let thresholds = CheckThresholds {
+            volume_ratio: 5.0,
+            ..CheckThresholds::default()
+        };
+        let check = mesh.check_with(&thresholds);
+        assert_eq!(check.high_volume_ratio_cells.len(), 3);
+        assert!(check.to_string().contains("cells above ratio 5: 3"));
+        assert!(check.is_ok());
+    }
+}
@@ -0,0 +1,134 @@
+use dugong_types::tensor::Vector;
+
+use crate::error::MeshError;
+use crate::extrude::{ExtrudeModel, extrude};
+use crate::mesh::Mesh;
+use crate::patch::{Patch, PatchKind};
+
+/// Builds a one-dimensional column of `n_cells` hexahedra along x, spanning
+/// `[0, length]` with a unit square cross-section.
+///
+/// `grading` is the ratio of the last cell length to the first. The ends
+/// form the patches `x-min` and `x-max` (of kind [`PatchKind::Patch`]), and
+/// the four sides the [`PatchKind::Empty`] patch `sides`, so the mesh solves
+/// in x only. Cells are numbered from `x-min` to `x-max`.
+///
+/// # Errors
+///
+/// Returns [`MeshError::InvalidExtrusion`] if `n_cells` is zero or `grading`
+/// is not positive.
+pub fn column_mesh(n_cells: usize, length: f64, grading: f64) -> Result<Mesh, MeshError> {
+    let points = [
+        Vector::new(0.0, 0.0, 0.0),
+        Vector::new(0.0, 1.0, 0.0),
+        Vector::new(0.0, 1.0, 1.0),
+        Vector::new(0.0, 0.0, 1.0),
+    ];
+    let model = ExtrudeModel::Linear {
+        direction: Vector::new(1.0, 0.0, 0.0),
+        thickness: length,
+    };
+    let mut mesh = extrude(&points, &[vec![0, 1, 2, 3]], &model, n_cells, grading)?;
+    for patch in &mut mesh.patches {
+        let (name, kind) = match patch.name() {
+            "bottom" => ("x-min", PatchKind::Patch),
+            "top" => ("x-max", PatchKind::Patch),
+            _ => ("sides", PatchKind::Empty),
+        };
+        *patch = Patch::new(name, kind, patch.start(), patch.size());
+    }
+    Ok(mesh)
+}
+
+/// Volume-weighted error norms of a cell field against an analytic profile,
+/// from [`Mesh::profile_error`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfileError {
+    /// Mean absolute error.
+    pub l1: f64,
+    /// Root-mean-square error.
+    pub l2: f64,
+    /// Largest absolute error.
+    pub max: f64,
+}
+
+impl Mesh {
+    /// Compares per-cell `values` with the analytic profile `exact`,
+    /// evaluated at the cell centers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` does not have one entry per cell.
+    pub fn profile_error(&self, values: &[f64], exact: impl Fn(Vector) -> f64) -> ProfileError {
+        assert_eq!(values.len(), self.n_cells(), "one value per cell");
+        let (mut l1, mut l2, mut max, mut total) = (0.0, 0.0, 0.0_f64, 0.0);
+        for ((&v, &c), &vol) in values
+            .iter()
+            .zip(self.cell_centers())
+            .zip(self.cell_volumes())
+        {
+            let e = (v - exact(c)).abs();
+            l1 += e * vol;
+            l2 += e * e * vol;
+            max = max.max(e);
+            total += vol;
+        }
+        if total > 0.0 {
+            l1 /= total;
+            l2 /= total;
+        }
+        ProfileError {
+            l1,
+            l2: l2.sqrt(),
+            max,
+        }
+    }
+}
+
+/// Returns the observed order of accuracy from errors on two meshes whose
+/// cell sizes differ by `refinement_ratio` (coarse over fine).
+pub fn observed_order(coarse_error: f64, fine_error: f64, refinement_ratio: f64) -> f64 {
+    (coarse_error / fine_error).ln() / refinement_ratio.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_mesh_is_one_dimensional() {
+        let mesh = column_mesh(10, 2.0, 4.0).unwrap();
+        assert_eq!(mesh.n_cells(), 10);
+        assert_eq!(mesh.n_internal_faces(), 9);
+        assert_eq!(mesh.solution_directions(), [true, false, false]);
+        assert_eq!(mesh.patch("sides").unwrap().size(), 40);
+        assert_eq!(mesh.patch("x-min").unwrap().size(), 1);
+        let total: f64 = mesh.cell_volumes().iter().sum();
+        assert!((total - 2.0).abs() < 1e-12);
+        let (first, last) = (mesh.cell_volumes()[0], mesh.cell_volumes()[9]);
+        assert!((last / first - 4.0).abs() < 1e-9);
+        let x: Vec<f64> = mesh.cell_centers().iter().map(|c| c.x()).collect();
+        assert!(x.windows(2).all(|w| w[0] < w[1]));
+        assert!(matches!(
+            column_mesh(0, 1.0, 1.0),
+            Err(MeshError::InvalidExtrusion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_profile_error_recovers_second_order() {
+        // Cell averages of sin(x) against point values at the centers differ
+        // by O(h^2).
+        let error = |n: usize| {
+            let mesh = column_mesh(n, 1.0, 1.0).unwrap();
+            let h = 1.0 / n as f64;
+            let averages: Vec<f64> = (0..n)
+                .map(|i| ((i as f64 * h).cos() - ((i + 1) as f64 * h).cos()) / h)
+                .collect();
+            mesh.profile_error(&averages, |c| c.x().sin())
+        };
+        let (coarse, fine) = (error(10), error(20));
+        assert!(coarse.max >= coarse.l2 && coarse.l2 >= coarse.l1);
+        assert!((observed_order(coarse.l2, fine.l2, 2.0) - 2.0).abs() < 0.05);
+    }
+}
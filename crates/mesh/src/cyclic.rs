@@ -0,0 +1,321 @@
+use dugong_types::tensor::Vector;
+
+use crate::assemble::{assemble, disassemble};
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::patch::{CoupledTransform, Patch, PatchKind};
+use crate::zone::Zone;
+
+/// Returns the neighbor patch name of a same-mesh coupled patch.
+fn coupled_patch_name(kind: &PatchKind) -> Option<&str> {
+    match kind {
+        PatchKind::Cyclic { neighbor_patch, .. } | PatchKind::CyclicAmi { neighbor_patch, .. } => {
+            Some(neighbor_patch)
+        }
+        _ => None,
+    }
+}
+
+/// Checks that every cyclic or cyclic AMI patch names an existing patch of
+/// the same kind that names it back, and that cyclic pairs have equal sizes.
+pub(crate) fn validate_cyclic_pairs(patches: &[Patch]) -> Result<(), MeshError> {
+    for patch in patches {
+        let Some(neighbor_patch) = coupled_patch_name(patch.kind()) else {
+            continue;
+        };
+        let Some(neighbor) = patches.iter().find(|p| p.name() == neighbor_patch) else {
+            return Err(MeshError::CyclicNeighborNotFound {
+                patch: patch.name().to_string(),
+                neighbor: neighbor_patch.to_string(),
+            });
+        };
+        let couples_back = coupled_patch_name(neighbor.kind()) == Some(patch.name())
+            && std::mem::discriminant(neighbor.kind()) == std::mem::discriminant(patch.kind());
+        if !couples_back || neighbor.name() == patch.name() {
+            return Err(MeshError::CyclicNeighborMismatch {
+                patch: patch.name().to_string(),
+                neighbor: neighbor_patch.to_string(),
+            });
+        }
+        if matches!(patch.kind(), PatchKind::Cyclic { .. }) && neighbor.size() != patch.size() {
+            return Err(MeshError::CyclicSizeMismatch {
+                patch: patch.name().to_string(),
+                size: patch.size(),
+                neighbor_size: neighbor.size(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Finds, for each source point, the unused destination point within its
+/// tolerance. Returns the index of the first unmatched source point on failure.
+fn match_points(src: &[Vector], tol: &[f64], dst: &[Vector]) -> Result<Vec<usize>, usize> {
+    let max_tol = tol.iter().copied().fold(0.0, f64::max);
+    let mut sorted: Vec<usize> = (0..dst.len()).collect();
+    sorted.sort_by(|&a, &b| dst[a].x().total_cmp(&dst[b].x()));
+    let keys: Vec<f64> = sorted.iter().map(|&j| dst[j].x()).collect();
+    let mut used = vec![false; dst.len()];
+
+    let mut out = Vec::with_capacity(src.len());
+    for (i, (&p, &t)) in src.iter().zip(tol).enumerate() {
+        let lo = keys.partition_point(|&x| x < p.x() - max_tol);
+        let hi = keys.partition_point(|&x| x <= p.x() + max_tol);
+        let best = sorted[lo..hi]
+            .iter()
+            .copied()
+            .filter(|&j| !used[j])
+            .map(|j| (j, (dst[j] - p).mag()))
+            .filter(|&(_, d)| d <= t)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let Some((j, _)) = best else {
+            return Err(i);
+        };
+        used[j] = true;
+        out.push(j);
+    }
+    Ok(out)
+}
+
+impl Mesh {
+    /// Returns the neighbor patch index and transform of cyclic patch `patch`,
+    /// or `None` if the patch is not cyclic.
+    pub fn cyclic_neighbor(&self, patch: usize) -> Option<(usize, &CoupledTransform)> {
+        match self.patches()[patch].kind() {
+            PatchKind::Cyclic {
+                neighbor_patch,
+                transform,
+            } => Some((self.patch_index(neighbor_patch)?, transform)),
+            _ => None,
+        }
+    }
+
+    /// Returns the cells across the faces of cyclic patch `patch`, in patch
+    /// face order, or `None` if the patch is not cyclic.
+    pub fn coupled_neighbor_cells(&self, patch: usize) -> Option<&[usize]> {
+        let (neighbor, _) = self.cyclic_neighbor(patch)?;
+        Some(&self.owner()[self.patches()[neighbor].range()])
+    }
+
+    /// Returns the centers of the cells across cyclic patch `patch`,
+    /// transformed into the frame of this patch.
+    pub fn coupled_neighbor_centers(&self, patch: usize) -> Option<Vec<Vector>> {
+        let (_, transform) = self.cyclic_neighbor(patch)?;
+        let cells = self.coupled_neighbor_cells(patch)?;
+        let centers = self.cell_centers();
+        Some(
+            cells
+                .iter()
+                .map(|&c| transform.inv_transform_point(centers[c]))
+                .collect(),
+        )
+    }
+
+    /// Returns the owner-to-neighbor cell center vectors across the faces of
+    /// cyclic patch `patch`, with the neighbor center transformed into the
+    /// frame of this patch.
+    pub fn coupled_deltas(&self, patch: usize) -> Option<Vec<Vector>> {
+        let neighbor_centers = self.coupled_neighbor_centers(patch)?;
+        let centers = self.cell_centers();
+        let owners = &self.owner()[self.patches()[patch].range()];
+        Some(
+            owners
+                .iter()
+                .zip(neighbor_centers)
+                .map(|(&c, n)| n - centers[c])
+                .collect(),
+        )
+    }
+
+    /// Reorders the faces of each cyclic neighbor patch so that face `i` of
+    /// every cyclic patch maps onto face `i` of its neighbor.
+    ///
+    /// The patch listed first in each pair keeps its order. Faces are matched
+    /// by transformed face centers within `tolerance` times the face length
+    /// scale `sqrt(|Sf|)`. Face zones are remapped. Returns the new → old
+    /// face map.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::CyclicMatchFailed`] if a face has no counterpart.
+    pub fn match_cyclic_faces(&mut self, tolerance: f64) -> Result<Vec<usize>, MeshError> {
+        let mut old_of_input: Vec<usize> = (0..self.n_faces()).collect();
+        let (internal, mut groups) = disassemble(self, |c| c);
+
+        for (a, patch) in self.patches().iter().enumerate() {
+            let Some((b, transform)) = self.cyclic_neighbor(a) else {
+                continue;
+            };
+            if b < a {
+                continue;
+            }
+            let src: Vec<Vector> = patch
+                .range()
+                .map(|f| transform.transform_point(self.face_centers()[f]))
+                .collect();
+            let tol: Vec<f64> = patch
+                .range()
+                .map(|f| tolerance * self.face_areas()[f].mag().sqrt())
+                .collect();
+            let neighbor = &self.patches()[b];
+            let dst: Vec<Vector> = neighbor.range().map(|f| self.face_centers()[f]).collect();
+            let order =
+                match_points(&src, &tol, &dst).map_err(|i| MeshError::CyclicMatchFailed {
+                    patch: patch.name().to_string(),
+                    face: patch.start() + i,
+                })?;
+
+            let mut faces = std::mem::take(&mut groups[b].faces);
+            groups[b].faces = order
+                .iter()
+                .map(|&j| std::mem::take(&mut faces[j]))
+                .collect();
+            let start = neighbor.start();
+            for (i, &j) in order.iter().enumerate() {
+                old_of_input[start + i] = start + j;
+            }
+        }
+
+        let (mut mesh, input_of_face) = assemble(self.points().to_vec(), internal, groups)?;
+        let face_map: Vec<usize> = input_of_face.iter().map(|&i| old_of_input[i]).collect();
+        let mut reverse = vec![0; face_map.len()];
+        for (new, &old) in face_map.iter().enumerate() {
+            reverse[old] = new;
+        }
+        mesh.cell_zones = std::mem::take(&mut self.cell_zones);
+        mesh.face_zones = self
+            .face_zones
+            .iter()
+            .map(|z| Zone::new(z.name(), z.indices().iter().map(|&f| reverse[f])))
+            .collect();
+        mesh.point_zones = std::mem::take(&mut self.point_zones);
+        *self = mesh;
+        Ok(face_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    /// A box mesh with `x-min`/`x-max` turned into a translational cyclic pair.
+    fn periodic_box(n: [usize; 3], l: [f64; 3]) -> Mesh {
+        let mut mesh = box_mesh(n, l);
+        let separation = Vector::new(l[0], 0.0, 0.0);
+        for (i, (name, other, transform)) in [
+            (
+                "x-min",
+                "x-max",
+                CoupledTransform::Translational { separation },
+            ),
+            (
+                "x-max",
+                "x-min",
+                CoupledTransform::Translational {
+                    separation: -separation,
+                },
+            ),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let p = &mesh.patches[i];
+            let kind = PatchKind::Cyclic {
+                neighbor_patch: other.to_string(),
+                transform,
+            };
+            mesh.patches[i] = Patch::new(name, kind, p.start(), p.size());
+        }
+        validate_cyclic_pairs(&mesh.patches).unwrap();
+        mesh
+    }
+
+    #[test]
+    fn test_coupled_deltas_wrap_around_periodic_direction() {
+        let mesh = periodic_box([4, 2, 1], [2.0, 1.0, 1.0]);
+        let cells = mesh.coupled_neighbor_cells(0).unwrap();
+        let owners = &mesh.owner()[mesh.patches()[0].range()];
+        for (&c, &n) in owners.iter().zip(cells) {
+            assert_eq!(n, c + 3);
+        }
+        for d in mesh.coupled_deltas(0).unwrap() {
+            assert!((d - Vector::new(-0.5, 0.0, 0.0)).mag() < 1e-12);
+        }
+        for d in mesh.coupled_deltas(1).unwrap() {
+            assert!((d - Vector::new(0.5, 0.0, 0.0)).mag() < 1e-12);
+        }
+        assert!(mesh.coupled_deltas(2).is_none());
+    }
+
+    #[test]
+    fn test_match_cyclic_faces_restores_pairing() {
+        let mut mesh = periodic_box([3, 3, 2], [1.0, 1.0, 1.0]);
+        let expected = mesh.coupled_neighbor_cells(0).unwrap().to_vec();
+        mesh.add_face_zone(Zone::new("outlet", mesh.patches()[1].range()))
+            .unwrap();
+
+        // Scramble the x-max faces.
+        let (internal, mut groups) = disassemble(&mesh, |c| c);
+        groups[1].faces.reverse();
+        groups[1].faces.swap(0, 3);
+        let (mut scrambled, _) = assemble(mesh.points().to_vec(), internal, groups).unwrap();
+        scrambled.face_zones = mesh.face_zones.clone();
+        assert_ne!(scrambled.coupled_neighbor_cells(0).unwrap(), expected);
+
+        scrambled.match_cyclic_faces(1e-6).unwrap();
+        assert_eq!(scrambled.coupled_neighbor_cells(0).unwrap(), expected);
+        assert_eq!(
+            scrambled.face_zone("outlet").unwrap().indices(),
+            scrambled.patches()[1].range().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_match_cyclic_faces_wrong_transform_returns_err() {
+        let mut mesh = periodic_box([2, 2, 1], [1.0, 1.0, 1.0]);
+        let p = &mesh.patches[0];
+        mesh.patches[0] = Patch::new(
+            p.name(),
+            PatchKind::Cyclic {
+                neighbor_patch: "x-max".to_string(),
+                transform: CoupledTransform::None,
+            },
+            p.start(),
+            p.size(),
+        );
+        assert!(matches!(
+            mesh.match_cyclic_faces(1e-6),
+            Err(MeshError::CyclicMatchFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_cyclic_pairs_rejects_inconsistent_pairs() {
+        let kind = |n: &str| PatchKind::Cyclic {
+            neighbor_patch: n.to_string(),
+            transform: CoupledTransform::None,
+        };
+        let missing = [Patch::new("a", kind("b"), 0, 2)];
+        assert!(matches!(
+            validate_cyclic_pairs(&missing),
+            Err(MeshError::CyclicNeighborNotFound { .. })
+        ));
+        let one_way = [
+            Patch::new("a", kind("b"), 0, 2),
+            Patch::new("b", PatchKind::Wall, 2, 2),
+        ];
+        assert!(matches!(
+            validate_cyclic_pairs(&one_way),
+            Err(MeshError::CyclicNeighborMismatch { .. })
+        ));
+        let sizes = [
+            Patch::new("a", kind("b"), 0, 2),
+            Patch::new("b", kind("a"), 2, 3),
+        ];
+        assert!(matches!(
+            validate_cyclic_pairs(&sizes),
+            Err(MeshError::CyclicSizeMismatch { .. })
+        ));
+    }
+}
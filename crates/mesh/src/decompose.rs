@@ -0,0 +1,551 @@
+use std::collections::BTreeMap;
+
+use dugong_types::tensor::Vector;
+
+use crate::assemble::{BoundaryGroup, InternalFace, assemble};
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::patch::{CoupledTransform, Patch, PatchKind};
+use crate::renumber::{level_structure, pseudo_peripheral};
+use crate::zone::Zone;
+
+/// Algorithm used to assign cells to subdomains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecompositionMethod {
+    /// Recursive coordinate bisection of the cell centers, always splitting
+    /// across the longest extent of the current bounding box.
+    Hierarchical,
+    /// Recursive bisection of the cell-cell graph. Each half is grown
+    /// breadth-first from a pseudo-peripheral cell, which keeps subdomains
+    /// compact and connected where the graph allows.
+    Graph,
+}
+
+/// One subdomain of a [`Decomposition`] together with its addressing back
+/// into the undecomposed mesh.
+pub struct SubMesh {
+    rank: usize,
+    mesh: Mesh,
+    cell_map: Vec<usize>,
+    face_map: Vec<usize>,
+    point_map: Vec<usize>,
+    flipped: Vec<bool>,
+}
+
+impl SubMesh {
+    /// Returns the rank of this subdomain.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// Returns the local mesh.
+    pub fn mesh(&self) -> &Mesh {
+        &self.mesh
+    }
+
+    /// Consumes the subdomain and returns the local mesh.
+    pub fn into_mesh(self) -> Mesh {
+        self.mesh
+    }
+
+    /// Returns the local → global cell map.
+    pub fn cell_map(&self) -> &[usize] {
+        &self.cell_map
+    }
+
+    /// Returns the local → global face map.
+    pub fn face_map(&self) -> &[usize] {
+        &self.face_map
+    }
+
+    /// Returns the local → global point map.
+    pub fn point_map(&self) -> &[usize] {
+        &self.point_map
+    }
+
+    /// Returns `true` if local face `face` is oriented opposite to its global
+    /// counterpart. This happens on interface faces whose global owner lies on
+    /// the neighboring rank; face fluxes must be negated when mapped.
+    pub fn is_face_flipped(&self, face: usize) -> bool {
+        self.flipped[face]
+    }
+}
+
+/// A mesh split into subdomains for distributed-memory runs.
+pub struct Decomposition {
+    cell_rank: Vec<usize>,
+    cell_local: Vec<usize>,
+    patches: Vec<Patch>,
+    parts: Vec<SubMesh>,
+}
+
+impl Decomposition {
+    /// Returns the number of subdomains.
+    pub fn n_parts(&self) -> usize {
+        self.parts.len()
+    }
+
+    /// Returns the subdomains, indexed by rank.
+    pub fn parts(&self) -> &[SubMesh] {
+        &self.parts
+    }
+
+    /// Consumes the decomposition and returns the subdomains.
+    pub fn into_parts(self) -> Vec<SubMesh> {
+        self.parts
+    }
+
+    /// Returns the rank assigned to each global cell.
+    pub fn cell_rank(&self) -> &[usize] {
+        &self.cell_rank
+    }
+
+    /// Returns `(rank, local cell)` for a global cell.
+    pub fn locate_cell(&self, cell: usize) -> (usize, usize) {
+        (self.cell_rank[cell], self.cell_local[cell])
+    }
+
+    /// Returns the patches of the undecomposed mesh.
+    pub fn patches(&self) -> &[Patch] {
+        &self.patches
+    }
+}
+
+impl Mesh {
+    /// Assigns each cell to one of `n_parts` subdomains of near-equal size.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::InvalidPartCount`] if `n_parts` is zero or
+    /// exceeds the number of cells.
+    pub fn partition(
+        &self,
+        n_parts: usize,
+        method: DecompositionMethod,
+    ) -> Result<Vec<usize>, MeshError> {
+        let n_cells = self.n_cells();
+        if n_parts == 0 || n_parts > n_cells {
+            return Err(MeshError::InvalidPartCount { n_parts, n_cells });
+        }
+        let mut cell_rank = vec![0; n_cells];
+        let order = |cells: &[usize]| match method {
+            DecompositionMethod::Hierarchical => coordinate_order(self.cell_centers(), cells),
+            DecompositionMethod::Graph => graph_order(self.cell_cells(), cells),
+        };
+        bisect((0..n_cells).collect(), n_parts, 0, &order, &mut cell_rank);
+        Ok(cell_rank)
+    }
+
+    /// Partitions the mesh with `method` and builds the subdomain meshes.
+    ///
+    /// # Errors
+    ///
+    /// See [`Mesh::partition`].
+    pub fn decompose(
+        &self,
+        n_parts: usize,
+        method: DecompositionMethod,
+    ) -> Result<Decomposition, MeshError> {
+        let cell_rank = self.partition(n_parts, method)?;
+        self.decompose_with(&cell_rank, n_parts)
+    }
+
+    /// Builds subdomain meshes from an explicit cell → rank assignment.
+    ///
+    /// Each subdomain keeps every patch of the global mesh (possibly empty)
+    /// followed by one `procBoundary<rank>to<neighbor>` [`PatchKind::Processor`]
+    /// patch per neighboring rank, in ascending neighbor order. Processor patch
+    /// faces are sorted by global face index, so both sides of an interface
+    /// list the shared faces in the same order. Cells, points, and patch faces keep
+    /// their global relative order; zones are restricted to the subdomain.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::CellRankLengthMismatch`] or
+    /// [`MeshError::CellRankOutOfRange`] for an invalid assignment, and
+    /// [`MeshError::CyclicSplitAcrossRanks`] if the cells on the two sides of
+    /// a cyclic face pair are assigned to different ranks.
+    pub fn decompose_with(
+        &self,
+        cell_rank: &[usize],
+        n_parts: usize,
+    ) -> Result<Decomposition, MeshError> {
+        if cell_rank.len() != self.n_cells() {
+            return Err(MeshError::CellRankLengthMismatch {
+                expected: self.n_cells(),
+                got: cell_rank.len(),
+            });
+        }
+        let mut counts = vec![0; n_parts];
+        let mut cell_local = Vec::with_capacity(cell_rank.len());
+        for (cell, &rank) in cell_rank.iter().enumerate() {
+            if rank >= n_parts {
+                return Err(MeshError::CellRankOutOfRange {
+                    cell,
+                    rank,
+                    n_parts,
+                });
+            }
+            cell_local.push(counts[rank]);
+            counts[rank] += 1;
+        }
+        for (a, patch) in self.patches().iter().enumerate() {
+            let Some((b, _)) = self.cyclic_neighbor(a) else {
+                continue;
+            };
+            let owner = self.owner();
+            let pairs = patch.range().zip(self.patches()[b].range());
+            if let Some((face, _)) = pairs
+                .into_iter()
+                .find(|&(fa, fb)| cell_rank[owner[fa]] != cell_rank[owner[fb]])
+            {
+                return Err(MeshError::CyclicSplitAcrossRanks {
+                    patch: patch.name().to_string(),
+                    face,
+                });
+            }
+        }
+
+        let parts = (0..n_parts)
+            .map(|rank| self.extract_part(rank, cell_rank, &cell_local))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Decomposition {
+            cell_rank: cell_rank.to_vec(),
+            cell_local,
+            patches: self.patches().to_vec(),
+            parts,
+        })
+    }
+
+    /// Builds the subdomain of `rank`.
+    fn extract_part(
+        &self,
+        rank: usize,
+        cell_rank: &[usize],
+        cell_local: &[usize],
+    ) -> Result<SubMesh, MeshError> {
+        let faces = self.faces();
+        let owner = self.owner();
+        let local = |c: usize| cell_local[c];
+
+        // Input faces for `assemble`, with their global index and flip flag.
+        let mut global_of_input = Vec::new();
+        let mut flipped_of_input = Vec::new();
+        let mut internal: Vec<InternalFace> = Vec::new();
+        let mut shared: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (f, &n) in self.neighbor().iter().enumerate() {
+            let (ro, rn) = (cell_rank[owner[f]], cell_rank[n]);
+            if ro == rank && rn == rank {
+                internal.push((faces[f].clone(), local(owner[f]), local(n)));
+                global_of_input.push(f);
+                flipped_of_input.push(false);
+            } else if ro == rank {
+                shared.entry(rn).or_default().push(f);
+            } else if rn == rank {
+                shared.entry(ro).or_default().push(f);
+            }
+        }
+
+        let mut groups = Vec::with_capacity(self.patches().len() + shared.len());
+        for patch in self.patches() {
+            let mut group_faces = Vec::new();
+            for f in patch.range().filter(|&f| cell_rank[owner[f]] == rank) {
+                group_faces.push((faces[f].clone(), local(owner[f])));
+                global_of_input.push(f);
+                flipped_of_input.push(false);
+            }
+            groups.push(BoundaryGroup {
+                name: patch.name().to_string(),
+                kind: patch.kind().clone(),
+                faces: group_faces,
+            });
+        }
+        for (&other, shared_faces) in &shared {
+            let mut group_faces = Vec::with_capacity(shared_faces.len());
+            for &f in shared_faces {
+                let flip = cell_rank[owner[f]] != rank;
+                if flip {
+                    let mut verts = faces[f].clone();
+                    verts.reverse();
+                    group_faces.push((verts, local(self.neighbor()[f])));
+                } else {
+                    group_faces.push((faces[f].clone(), local(owner[f])));
+                }
+                global_of_input.push(f);
+                flipped_of_input.push(flip);
+            }
+            groups.push(BoundaryGroup {
+                name: format!("procBoundary{rank}to{other}"),
+                kind: PatchKind::Processor {
+                    my_rank: rank,
+                    neighbor_rank: other,
+                    transform: CoupledTransform::None,
+                },
+                faces: group_faces,
+            });
+        }
+
+        // Compact the point numbering to the points used by local faces.
+        let mut point_local = vec![usize::MAX; self.n_points()];
+        let face_lists = internal
+            .iter()
+            .map(|(f, _, _)| f)
+            .chain(groups.iter().flat_map(|g| g.faces.iter().map(|(f, _)| f)));
+        for verts in face_lists {
+            for &p in verts {
+                point_local[p] = 0;
+            }
+        }
+        let mut point_map = Vec::new();
+        for (p, slot) in point_local.iter_mut().enumerate() {
+            if *slot == 0 {
+                *slot = point_map.len();
+                point_map.push(p);
+            }
+        }
+        let relabel = |verts: &mut Vec<usize>| {
+            for p in verts.iter_mut() {
+                *p = point_local[*p];
+            }
+        };
+        for (verts, _, _) in &mut internal {
+            relabel(verts);
+        }
+        for group in &mut groups {
+            for (verts, _) in &mut group.faces {
+                relabel(verts);
+            }
+        }
+        let points: Vec<Vector> = point_map.iter().map(|&p| self.points()[p]).collect();
+
+        let (mut mesh, input_of_face) = assemble(points, internal, groups)?;
+        let face_map: Vec<usize> = input_of_face.iter().map(|&i| global_of_input[i]).collect();
+        let flipped = input_of_face.iter().map(|&i| flipped_of_input[i]).collect();
+        let cell_map: Vec<usize> = (0..cell_rank.len())
+            .filter(|&c| cell_rank[c] == rank)
+            .collect();
+
+        let mut face_local = vec![usize::MAX; self.n_faces()];
+        for (l, &g) in face_map.iter().enumerate() {
+            face_local[g] = l;
+        }
+        let restrict = |zones: &[Zone], map: &dyn Fn(usize) -> Option<usize>| {
+            zones
+                .iter()
+                .map(|z| Zone::new(z.name(), z.indices().iter().filter_map(|&i| map(i))))
+                .collect()
+        };
+        mesh.cell_zones = restrict(&self.cell_zones, &|c| {
+            (cell_rank[c] == rank).then(|| cell_local[c])
+        });
+        mesh.face_zones = restrict(&self.face_zones, &|f| {
+            (face_local[f] != usize::MAX).then(|| face_local[f])
+        });
+        mesh.point_zones = restrict(&self.point_zones, &|p| {
+            (point_local[p] != usize::MAX).then(|| point_local[p])
+        });
+
+        Ok(SubMesh {
+            rank,
+            mesh,
+            cell_map,
+            face_map,
+            point_map,
+            flipped,
+        })
+    }
+}
+
+/// Recursively splits `cells` into `n_parts` ranks starting at `first_rank`.
+///
+/// `order` arranges a cell set so that a prefix/suffix split yields two
+/// compact halves. The split point is proportional to the number of ranks on
+/// each side, so no part is empty as long as `cells.len() >= n_parts`.
+fn bisect(
+    cells: Vec<usize>,
+    n_parts: usize,
+    first_rank: usize,
+    order: &dyn Fn(&[usize]) -> Vec<usize>,
+    cell_rank: &mut [usize],
+) {
+    if n_parts == 1 {
+        for c in cells {
+            cell_rank[c] = first_rank;
+        }
+        return;
+    }
+    let lower = n_parts / 2;
+    let mut ordered = order(&cells);
+    let upper_cells = ordered.split_off(cells.len() * lower / n_parts);
+    bisect(ordered, lower, first_rank, order, cell_rank);
+    bisect(
+        upper_cells,
+        n_parts - lower,
+        first_rank + lower,
+        order,
+        cell_rank,
+    );
+}
+
+/// Orders `cells` along the longest bounding-box axis of their centers.
+fn coordinate_order(centers: &[Vector], cells: &[usize]) -> Vec<usize> {
+    let mut lo = [f64::INFINITY; 3];
+    let mut hi = [f64::NEG_INFINITY; 3];
+    for &c in cells {
+        for (d, &x) in centers[c].as_array().iter().enumerate() {
+            lo[d] = lo[d].min(x);
+            hi[d] = hi[d].max(x);
+        }
+    }
+    let axis = (0..3)
+        .max_by(|&a, &b| (hi[a] - lo[a]).total_cmp(&(hi[b] - lo[b])))
+        .unwrap_or(0);
+    let mut ordered = cells.to_vec();
+    ordered.sort_by(|&a, &b| {
+        let (xa, xb) = (centers[a].as_array()[axis], centers[b].as_array()[axis]);
+        xa.total_cmp(&xb).then(a.cmp(&b))
+    });
+    ordered
+}
+
+/// Orders `cells` by breadth-first level from a pseudo-peripheral cell of the
+/// induced subgraph, one connected component after another.
+fn graph_order(adjacency: &[Vec<usize>], cells: &[usize]) -> Vec<usize> {
+    let mut visited = vec![true; adjacency.len()];
+    for &c in cells {
+        visited[c] = false;
+    }
+    let mut ordered = Vec::with_capacity(cells.len());
+    for &seed in cells {
+        if visited[seed] {
+            continue;
+        }
+        let root = pseudo_peripheral(adjacency, seed, &visited);
+        for level in level_structure(adjacency, root, &visited) {
+            for c in level {
+                visited[c] = true;
+                ordered.push(c);
+            }
+        }
+    }
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_partition_hierarchical_splits_into_quadrants() {
+        let mesh = box_mesh([4, 4, 1], [1.0, 1.0, 0.25]);
+        let ranks = mesh
+            .partition(4, DecompositionMethod::Hierarchical)
+            .unwrap();
+        for rank in 0..4 {
+            let cells: Vec<usize> = (0..16).filter(|&c| ranks[c] == rank).collect();
+            assert_eq!(cells.len(), 4);
+            // Each quadrant is a 2x2 block: all centers within 0.25 of their mean.
+            let mean = cells
+                .iter()
+                .fold(Vector::zero(), |s, &c| s + mesh.cell_centers()[c])
+                * 0.25;
+            for &c in &cells {
+                assert!((mesh.cell_centers()[c] - mean).mag() < 0.25);
+            }
+        }
+    }
+
+    #[test]
+    fn test_partition_graph_is_balanced_and_connected() {
+        let mesh = box_mesh([6, 5, 4], [1.0, 1.0, 1.0]);
+        let ranks = mesh.partition(3, DecompositionMethod::Graph).unwrap();
+        for rank in 0..3 {
+            let cells: Vec<usize> = (0..120).filter(|&c| ranks[c] == rank).collect();
+            assert_eq!(cells.len(), 40);
+            let mut excluded: Vec<bool> = ranks.iter().map(|&r| r != rank).collect();
+            excluded[cells[0]] = true;
+            let reached: usize = level_structure(mesh.cell_cells(), cells[0], &excluded)
+                .iter()
+                .map(Vec::len)
+                .sum();
+            assert_eq!(reached, cells.len());
+        }
+    }
+
+    #[test]
+    fn test_decompose_interfaces_match_across_ranks() {
+        let mesh = box_mesh([4, 3, 2], [4.0, 3.0, 2.0]);
+        let decomp = mesh.decompose(3, DecompositionMethod::Graph).unwrap();
+        let parts = decomp.parts();
+
+        let n_cells: usize = parts.iter().map(|p| p.mesh().n_cells()).sum();
+        assert_eq!(n_cells, mesh.n_cells());
+        let mut n_shared = 0;
+        for part in parts {
+            assert_eq!(part.mesh().patches()[..6].len(), mesh.patches().len());
+            for (local, &global) in part.cell_map().iter().enumerate() {
+                assert_eq!(decomp.locate_cell(global), (part.rank(), local));
+                let d = part.mesh().cell_centers()[local] - mesh.cell_centers()[global];
+                assert!(d.mag() < 1e-12);
+            }
+            for link in part.mesh().halo_links() {
+                let other = &parts[link.neighbor_rank()];
+                let back = other
+                    .mesh()
+                    .halo_links()
+                    .into_iter()
+                    .find(|l| l.neighbor_rank() == part.rank())
+                    .unwrap();
+                let mine = link.recv_faces();
+                let theirs = back.recv_faces();
+                assert_eq!(mine.len(), theirs.len());
+                n_shared += mine.len();
+                for (a, b) in mine.zip(theirs) {
+                    assert_eq!(part.face_map()[a], other.face_map()[b]);
+                    assert_ne!(part.is_face_flipped(a), other.is_face_flipped(b));
+                    let sum = part.mesh().face_areas()[a] + other.mesh().face_areas()[b];
+                    assert!(sum.mag() < 1e-12);
+                }
+            }
+        }
+        let n_internal: usize = parts.iter().map(|p| p.mesh().n_internal_faces()).sum();
+        assert_eq!(n_internal + n_shared / 2, mesh.n_internal_faces());
+    }
+
+    #[test]
+    fn test_decompose_with_restricts_zones_and_points() {
+        let mut mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        mesh.add_cell_zone(Zone::new("right", [1])).unwrap();
+        mesh.add_point_zone(Zone::new("origin", [0])).unwrap();
+        let decomp = mesh.decompose_with(&[0, 1], 2).unwrap();
+        let [left, right] = decomp.parts() else {
+            panic!("expected two parts");
+        };
+        assert!(left.mesh().cell_zone("right").unwrap().is_empty());
+        assert_eq!(right.mesh().cell_zone("right").unwrap().indices(), &[0]);
+        assert_eq!(left.mesh().point_zone("origin").unwrap().indices(), &[0]);
+        assert!(right.mesh().point_zone("origin").unwrap().is_empty());
+        assert_eq!(left.mesh().n_points(), 8);
+        for (l, &g) in right.point_map().iter().enumerate() {
+            assert_eq!(right.mesh().points()[l], mesh.points()[g]);
+        }
+        assert_eq!(left.mesh().patches()[6].name(), "procBoundary0to1");
+    }
+
+    #[test]
+    fn test_partition_rejects_invalid_part_count() {
+        let mesh = box_mesh([2, 1, 1], [1.0, 1.0, 1.0]);
+        assert!(matches!(
+            mesh.partition(3, DecompositionMethod::Hierarchical),
+            Err(MeshError::InvalidPartCount {
+                n_parts: 3,
+                n_cells: 2
+            })
+        ));
+        assert!(matches!(
+            mesh.decompose_with(&[0, 2], 2),
+            Err(MeshError::CellRankOutOfRange { cell: 1, .. })
+        ));
+    }
+}
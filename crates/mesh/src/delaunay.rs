@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use dugong_types::tensor::Vector;
+
+use crate::assemble::{BoundaryGroup, InternalFace, assemble, boundary_cell_last};
+use crate::bvh::Aabb;
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::patch::PatchKind;
+use crate::tri_surface::TriSurface;
+
+/// Outward faces of a positively oriented tetrahedron, as local vertex
+/// indices.
+const TET_FACES: [[usize; 3]; 4] = [[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]];
+
+/// Maximum jitter of the interior lattice points, relative to the spacing.
+const LATTICE_JITTER: f64 = 0.1;
+
+/// Perturbation applied to every point for the Delaunay predicates,
+/// relative to the domain size. It breaks the co-spherical and coplanar
+/// configurations of lattice-like inputs.
+const PREDICATE_JITTER: f64 = 1e-7;
+
+/// Relative tolerance on the meshed volume against the enclosed volume.
+const VOLUME_TOL: f64 = 1e-6;
+
+/// Meshes the volume enclosed by `surface` with tetrahedra.
+///
+/// The surface points are kept and interior points are added on a jittered
+/// lattice of the given `spacing`, no closer than half a spacing to the
+/// surface. The points are triangulated with the Bowyer–Watson algorithm
+/// and tetrahedra whose centroid lies outside the surface are removed. All
+/// boundary faces form one wall patch named `patch`.
+///
+/// Boundary faces are facets of the Delaunay triangulation, so the surface
+/// is recovered for convex domains and for simple non-convex domains whose
+/// surface triangles are Delaunay. The result is checked by comparing its
+/// volume with the enclosed volume.
+///
+/// # Errors
+///
+/// Returns [`MeshError::InvalidMeshing`] if `spacing` is not positive, the
+/// surface is empty, or the boundary is not recovered. Returns other errors
+/// if the resulting mesh fails validation.
+pub fn delaunay_mesh(surface: &TriSurface, spacing: f64, patch: &str) -> Result<Mesh, MeshError> {
+    let invalid = |reason: String| MeshError::InvalidMeshing { reason };
+    if !(spacing > 0.0 && spacing.is_finite()) {
+        return Err(invalid(format!("spacing must be positive, got {spacing}")));
+    }
+    if surface.n_triangles() == 0 {
+        return Err(invalid("the surface is empty".to_string()));
+    }
+
+    let mut points = surface.points().to_vec();
+    let bounds = Aabb::of_points(points.iter().copied());
+    let size = (bounds.max - bounds.min).mag();
+    let extent = (bounds.max - bounds.min)
+        .as_array()
+        .map(|e| (e / spacing) as usize + 1);
+    let mut seed = 0;
+    for k in 0..extent[2] {
+        for j in 0..extent[1] {
+            for i in 0..extent[0] {
+                let p = bounds.min
+                    + Vector::new(i as f64 + 0.5, j as f64 + 0.5, k as f64 + 0.5) * spacing
+                    + jitter(&mut seed) * (LATTICE_JITTER * spacing);
+                if !surface.is_inside(p) {
+                    continue;
+                }
+                if surface
+                    .nearest(p)
+                    .is_some_and(|(_, q)| (q - p).mag() >= 0.5 * spacing)
+                {
+                    points.push(p);
+                }
+            }
+        }
+    }
+
+    let mut seed = 0;
+    let perturbed: Vec<Vector> = points
+        .iter()
+        .map(|&p| p + jitter(&mut seed) * (PREDICATE_JITTER * size))
+        .collect();
+    let tets = bowyer_watson(&perturbed, bounds.center(), size);
+
+    // Keep the tetrahedra inside the surface. Flat tetrahedra spanning
+    // coplanar surface points are dropped first, as their centroid lies on
+    // the surface.
+    let min_volume = 1e-9 * spacing.powi(3);
+    let tets: Vec<[usize; 4]> = tets
+        .into_iter()
+        .filter(|t| {
+            let [a, b, c, d] = t.map(|v| points[v]);
+            tet_volume(a, b, c, d) > min_volume && surface.is_inside((a + b + c + d) * 0.25)
+        })
+        .collect();
+
+    let mut faces: Vec<([usize; 3], usize, Vec<usize>)> = tets
+        .iter()
+        .enumerate()
+        .flat_map(|(cell, t)| {
+            TET_FACES.map(|f| {
+                let face = f.map(|i| t[i]);
+                let mut key = face;
+                key.sort_unstable();
+                (key, cell, face.to_vec())
+            })
+        })
+        .collect();
+    faces.sort_by_key(|&(key, cell, _)| (key, cell));
+    let mut internal: Vec<InternalFace> = Vec::new();
+    let mut boundary = Vec::new();
+    let mut faces = faces.into_iter().peekable();
+    while let Some((key, cell, face)) = faces.next() {
+        match faces.next_if(|f| f.0 == key) {
+            Some((_, other, _)) => internal.push((face, cell, other)),
+            None => boundary.push((face, cell)),
+        }
+    }
+    let mut point_of = vec![usize::MAX; points.len()];
+    let mut used = Vec::new();
+    for t in &tets {
+        for &v in t {
+            if point_of[v] == usize::MAX {
+                point_of[v] = used.len();
+                used.push(points[v]);
+            }
+        }
+    }
+    let renumber = |face: &mut Vec<usize>| face.iter_mut().for_each(|v| *v = point_of[*v]);
+    internal.iter_mut().for_each(|(f, _, _)| renumber(f));
+    boundary.iter_mut().for_each(|(f, _)| renumber(f));
+    let mut groups = vec![BoundaryGroup {
+        name: patch.to_string(),
+        kind: PatchKind::Wall,
+        faces: boundary,
+    }];
+    boundary_cell_last(&mut internal, &mut groups);
+    let (mesh, _) = assemble(used, internal, groups)?;
+
+    let enclosed: f64 = (0..surface.n_triangles())
+        .map(|t| {
+            let [a, b, c] = surface.corners(t);
+            a * b.cross(&c) / 6.0
+        })
+        .sum();
+    let volume: f64 = mesh.cell_volumes().iter().sum();
+    if (volume - enclosed).abs() > VOLUME_TOL * enclosed.abs() {
+        return Err(invalid(format!(
+            "boundary not recovered: meshed volume {volume} differs from enclosed volume {enclosed}"
+        )));
+    }
+    Ok(mesh)
+}
+
+/// Returns the Delaunay tetrahedra of `points`, positively oriented.
+///
+/// Points are inserted in order into a super-tetrahedron enclosing the
+/// sphere of diameter `size` about `center`. Each point removes the
+/// tetrahedra whose circumsphere contains it; the cavity is grown until
+/// every boundary face sees the point, and is then filled with tetrahedra
+/// joining the point to those faces.
+fn bowyer_watson(points: &[Vector], center: Vector, size: f64) -> Vec<[usize; 4]> {
+    let n = points.len();
+    let mut all = points.to_vec();
+    let far = 1e3 * size.max(f64::MIN_POSITIVE);
+    for s in [
+        [1.0, 1.0, 1.0],
+        [1.0, -1.0, -1.0],
+        [-1.0, 1.0, -1.0],
+        [-1.0, -1.0, 1.0],
+    ] {
+        all.push(center + Vector::new(s[0], s[1], s[2]) * far);
+    }
+
+    struct Tet {
+        v: [usize; 4],
+        center: Vector,
+        radius2: f64,
+        alive: bool,
+    }
+    let make = |all: &[Vector], v: [usize; 4]| {
+        let c = circumcenter(v.map(|i| all[i]));
+        Tet {
+            v,
+            center: c,
+            radius2: (all[v[0]] - c) * (all[v[0]] - c),
+            alive: true,
+        }
+    };
+    let mut tets = vec![make(&all, [n, n + 1, n + 2, n + 3])];
+    if oriented_volume(&all, tets[0].v) < 0.0 {
+        tets[0] = make(&all, [n, n + 2, n + 1, n + 3]);
+    }
+
+    for (p, &x) in points.iter().enumerate() {
+        let mut cavity: Vec<usize> = (0..tets.len())
+            .filter(|&t| {
+                tets[t].alive && (x - tets[t].center) * (x - tets[t].center) < tets[t].radius2
+            })
+            .collect();
+        let boundary = loop {
+            let mut count: HashMap<[usize; 3], ([usize; 3], usize)> = HashMap::new();
+            for &t in &cavity {
+                for f in TET_FACES {
+                    let face = f.map(|i| tets[t].v[i]);
+                    let mut key = face;
+                    key.sort_unstable();
+                    count.entry(key).or_insert((face, 0)).1 += 1;
+                }
+            }
+            let boundary: Vec<[usize; 3]> = count
+                .into_values()
+                .filter(|&(_, c)| c == 1)
+                .map(|(face, _)| face)
+                .collect();
+            // Grow the cavity across faces that do not see the point.
+            let hidden = boundary.iter().find_map(|face| {
+                let [a, b, c] = face.map(|i| all[i]);
+                if (b - a).cross(&(c - a)) * (x - a) < 0.0 {
+                    return None;
+                }
+                (0..tets.len()).find(|&t| {
+                    tets[t].alive
+                        && !cavity.contains(&t)
+                        && face.iter().all(|v| tets[t].v.contains(v))
+                })
+            });
+            match hidden {
+                Some(t) => cavity.push(t),
+                None => break boundary,
+            }
+        };
+        for &t in &cavity {
+            tets[t].alive = false;
+        }
+        for [a, b, c] in boundary {
+            tets.push(make(&all, [a, c, b, p]));
+        }
+    }
+
+    tets.into_iter()
+        .filter(|t| t.alive && t.v.iter().all(|&v| v < n))
+        .map(|t| t.v)
+        .collect()
+}
+
+fn circumcenter([a, b, c, d]: [Vector; 4]) -> Vector {
+    let (b, c, d) = (b - a, c - a, d - a);
+    let num = b.cross(&c) * (d * d) + c.cross(&d) * (b * b) + d.cross(&b) * (c * c);
+    a + num / (2.0 * (b * c.cross(&d)))
+}
+
+fn oriented_volume(points: &[Vector], t: [usize; 4]) -> f64 {
+    let [a, b, c, d] = t.map(|i| points[i]);
+    tet_volume(a, b, c, d)
+}
+
+fn tet_volume(a: Vector, b: Vector, c: Vector, d: Vector) -> f64 {
+    (b - a).cross(&(c - a)) * (d - a) / 6.0
+}
+
+/// Returns a pseudo-random vector in `[-1, 1)^3`, advancing `seed`
+/// (SplitMix64), so meshes are reproducible.
+fn jitter(seed: &mut u64) -> Vector {
+    let mut next = || {
+        *seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = *seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+    };
+    Vector::new(next(), next(), next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell_shape::CellShape;
+    use crate::tri_surface::tests::unit_cube;
+
+    #[test]
+    fn test_delaunay_mesh_unit_cube() {
+        let mesh = delaunay_mesh(&unit_cube(), 0.25, "walls").unwrap();
+        assert!(mesh.n_cells() > 6);
+        assert!(mesh.check().is_ok());
+        assert!(mesh.cell_volumes().iter().all(|&v| v > 0.0));
+        assert!((mesh.cell_volumes().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(mesh.cell_shapes().iter().all(|&s| s == CellShape::Tet));
+
+        let walls = mesh.patch("walls").unwrap();
+        assert_eq!(walls.kind(), &PatchKind::Wall);
+        let area: f64 = walls.range().map(|f| mesh.face_areas()[f].mag()).sum();
+        assert!((area - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_delaunay_mesh_rejects_bad_spacing() {
+        assert!(matches!(
+            delaunay_mesh(&unit_cube(), 0.0, "walls"),
+            Err(MeshError::InvalidMeshing { .. })
+        ));
+    }
+}
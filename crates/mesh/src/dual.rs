@@ -0,0 +1,146 @@
+use dugong_types::tensor::Vector;
+
+use crate::mesh::Mesh;
+
+/// The median-dual mesh of a [`Mesh`]: one control volume around each point,
+/// bounded by the triangles joining edge midpoints, face centers and cell
+/// centers.
+///
+/// Dual faces are indexed by the primal edges they cross, and dual boundary
+/// faces by the primal boundary faces they lie on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DualMesh {
+    volumes: Vec<f64>,
+    edge_areas: Vec<Vector>,
+    boundary_areas: Vec<(usize, usize, Vector)>,
+}
+
+impl DualMesh {
+    /// Returns the volume of the dual cell of each point.
+    pub fn volumes(&self) -> &[f64] {
+        &self.volumes
+    }
+
+    /// Returns the area vector of the dual face crossing each edge of
+    /// [`Mesh::edges`], pointing from its first point to its second.
+    pub fn edge_areas(&self) -> &[Vector] {
+        &self.edge_areas
+    }
+
+    /// Returns the dual boundary faces as `(point, primal face, area vector)`:
+    /// the part of each boundary face nearest to each of its points, pointing
+    /// out of the domain.
+    pub fn boundary_areas(&self) -> &[(usize, usize, Vector)] {
+        &self.boundary_areas
+    }
+}
+
+impl Mesh {
+    /// Builds the median-dual mesh.
+    ///
+    /// Each primal cell is split into tetrahedra spanning its center, a face
+    /// center and a face edge; each of these is halved at the edge midpoint
+    /// between the two edge points. The dual cells therefore partition the
+    /// primal cells, and each dual cell is closed by its dual faces together
+    /// with its boundary areas.
+    pub fn dual_mesh(&self) -> DualMesh {
+        let (points, edges) = (self.points(), self.edges());
+        let (face_centers, cell_centers) = (self.face_centers(), self.cell_centers());
+        let mut volumes = vec![0.0; self.n_points()];
+        let mut edge_areas = vec![Vector::zero(); edges.len()];
+        let mut boundary_areas = Vec::new();
+
+        for (f, face) in self.faces().iter().enumerate() {
+            let xf = face_centers[f];
+            let n = face.len();
+            let mut cells = vec![(self.owner()[f], 1.0)];
+            if let Some(&nb) = self.neighbor().get(f) {
+                cells.push((nb, -1.0));
+            }
+            for i in 0..n {
+                let (a, b) = (face[i], face[(i + 1) % n]);
+                let mid = (points[a] + points[b]) / 2.0;
+                let e = self.face_edges()[f][i];
+                let [first, second] = edges[e];
+                let along = points[second] - points[first];
+                let tri = (points[a] - xf).cross(&(points[b] - xf));
+                for &(c, sign) in &cells {
+                    let xc = cell_centers[c];
+                    let half = sign * (tri * (xf - xc)) / 12.0;
+                    volumes[a] += half;
+                    volumes[b] += half;
+                    let area = (xf - mid).cross(&(xc - mid)) / 2.0;
+                    edge_areas[e] += if area * along < 0.0 { -area } else { area };
+                }
+            }
+            if f >= self.n_internal_faces() {
+                for i in 0..n {
+                    let (prev, v, next) = (face[(i + n - 1) % n], face[i], face[(i + 1) % n]);
+                    let before = (points[prev] + points[v]) / 2.0;
+                    let after = (points[v] + points[next]) / 2.0;
+                    let area = (xf - points[v]).cross(&(before - after)) / 2.0;
+                    boundary_areas.push((v, f, area));
+                }
+            }
+        }
+        DualMesh {
+            volumes,
+            edge_areas,
+            boundary_areas,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_dual_mesh_of_box_partitions_volume() {
+        let mesh = box_mesh([2, 2, 2], [2.0, 2.0, 2.0]);
+        let dual = mesh.dual_mesh();
+        let total: f64 = dual.volumes().iter().sum();
+        assert!((total - 8.0).abs() < 1e-12);
+        // The center point owns a unit cube; corners own an eighth of it.
+        let center = mesh
+            .points()
+            .iter()
+            .position(|p| (*p - Vector::new(1.0, 1.0, 1.0)).mag() < 1e-12)
+            .unwrap();
+        assert!((dual.volumes()[center] - 1.0).abs() < 1e-12);
+        assert!((dual.volumes()[0] - 0.125).abs() < 1e-12);
+        for (e, &[a, b]) in mesh.edges().iter().enumerate() {
+            let area = dual.edge_areas()[e];
+            let along = mesh.points()[b] - mesh.points()[a];
+            assert!(area.cross(&along).mag() < 1e-12);
+            assert!(area * along > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_dual_cells_are_closed() {
+        let mut mesh = box_mesh([3, 2, 2], [3.0, 2.0, 2.0]);
+        let mut points = mesh.points().to_vec();
+        for (i, p) in points.iter_mut().enumerate() {
+            let [x, y, z] = *p.as_array();
+            let interior = x > 0.0 && x < 3.0 && y > 0.0 && y < 2.0 && z > 0.0 && z < 2.0;
+            if interior {
+                *p += Vector::new(0.1, 0.05 * (i % 3) as f64, -0.07);
+            }
+        }
+        mesh.primitive.set_points(points);
+        let dual = mesh.dual_mesh();
+        let mut net = vec![Vector::zero(); mesh.n_points()];
+        for (e, &[a, b]) in mesh.edges().iter().enumerate() {
+            net[a] += dual.edge_areas()[e];
+            net[b] += -dual.edge_areas()[e];
+        }
+        for &(p, _, area) in dual.boundary_areas() {
+            net[p] += area;
+        }
+        assert!(net.iter().all(|s| s.mag() < 1e-12));
+        let total: f64 = dual.volumes().iter().sum();
+        assert!((total - mesh.cell_volumes().iter().sum::<f64>()).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,132 @@
+use dugong_types::tensor::Vector;
+
+use crate::mesh::Mesh;
+use crate::patch::PatchKind;
+
+/// Components of the summed empty-face normals above this value mark an
+/// inactive direction (the OpenFOAM threshold).
+const EMPTY_DIRECTION_TOL: f64 = 1e-6;
+
+impl Mesh {
+    /// Returns `true` if `face` belongs to an [`PatchKind::Empty`] patch.
+    pub fn is_empty_face(&self, face: usize) -> bool {
+        self.which_patch(face)
+            .is_some_and(|p| self.patches[p].kind() == &PatchKind::Empty)
+    }
+
+    /// Returns, per Cartesian direction, whether it is part of the solution.
+    ///
+    /// A direction is inactive when the faces of the empty patches have a
+    /// normal component along it, so a one-cell-thick mesh with empty front
+    /// and back planes normal to `z` yields `[true, true, false]`. Empty
+    /// patches are expected to be aligned with the coordinate axes. Meshes
+    /// without empty patches are fully 3D.
+    pub fn solution_directions(&self) -> [bool; 3] {
+        let mut sum = Vector::zero();
+        for patch in &self.patches {
+            if patch.kind() != &PatchKind::Empty {
+                continue;
+            }
+            for &area in &self.face_areas()[patch.range()] {
+                let n = area / area.mag();
+                sum += Vector::new(n.x().abs(), n.y().abs(), n.z().abs());
+            }
+        }
+        let mag = sum.mag();
+        if mag == 0.0 {
+            return [true; 3];
+        }
+        sum.as_array().map(|c| c / mag <= EMPTY_DIRECTION_TOL)
+    }
+
+    /// Returns the number of active directions (3 unless the mesh has empty
+    /// patches).
+    pub fn n_solution_dims(&self) -> usize {
+        self.solution_directions().iter().filter(|&&d| d).count()
+    }
+
+    /// Zeroes the components of `v` along inactive directions.
+    ///
+    /// Applied to vector unknowns (velocity, gradients) so that no motion or
+    /// variation develops normal to the empty planes.
+    pub fn constrain_to_solution(&self, v: Vector) -> Vector {
+        let active = self.solution_directions();
+        let c = v.as_array();
+        let pick = |i: usize| if active[i] { c[i] } else { 0.0 };
+        Vector::new(pick(0), pick(1), pick(2))
+    }
+
+    /// Returns the in-plane area of each cell of a 2D mesh, or `None` unless
+    /// exactly two directions are active.
+    ///
+    /// Cell volumes keep their 3D meaning (in-plane area times thickness);
+    /// the in-plane area is half the total area of a cell's empty faces.
+    pub fn cell_planar_areas(&self) -> Option<Vec<f64>> {
+        if self.n_solution_dims() != 2 {
+            return None;
+        }
+        let mut areas = vec![0.0; self.n_cells()];
+        for patch in &self.patches {
+            if patch.kind() != &PatchKind::Empty {
+                continue;
+            }
+            for face in patch.range() {
+                areas[self.owner()[face]] += 0.5 * self.face_areas()[face].mag();
+            }
+        }
+        Some(areas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patch::Patch;
+    use crate::test_meshes::box_mesh;
+
+    /// A `3 x 2 x 1` box of thickness 0.1 with empty `z` planes.
+    fn slab_mesh() -> Mesh {
+        let mut mesh = box_mesh([3, 2, 1], [3.0, 2.0, 0.1]);
+        for i in 4..6 {
+            let p = &mesh.patches[i];
+            mesh.patches[i] = Patch::new(p.name(), PatchKind::Empty, p.start(), p.size());
+        }
+        mesh
+    }
+
+    #[test]
+    fn test_solution_directions_full_3d_without_empty_patches() {
+        let mesh = box_mesh([2, 2, 2], [1.0, 1.0, 1.0]);
+        assert_eq!(mesh.solution_directions(), [true; 3]);
+        assert_eq!(mesh.n_solution_dims(), 3);
+        assert!(mesh.cell_planar_areas().is_none());
+    }
+
+    #[test]
+    fn test_solution_directions_slab_excludes_normal() {
+        let mesh = slab_mesh();
+        assert_eq!(mesh.solution_directions(), [true, true, false]);
+        assert_eq!(mesh.n_solution_dims(), 2);
+        let v = mesh.constrain_to_solution(Vector::new(1.0, 2.0, 3.0));
+        assert_eq!(v, Vector::new(1.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn test_is_empty_face_only_on_empty_patches() {
+        let mesh = slab_mesh();
+        let empty = mesh.patch("z-min").unwrap().range();
+        assert!(empty.clone().all(|f| mesh.is_empty_face(f)));
+        assert!(!mesh.is_empty_face(0));
+        assert!(!mesh.is_empty_face(mesh.patch("x-min").unwrap().start()));
+    }
+
+    #[test]
+    fn test_cell_planar_areas_divide_out_thickness() {
+        let mesh = slab_mesh();
+        let areas = mesh.cell_planar_areas().unwrap();
+        for (a, v) in areas.iter().zip(mesh.cell_volumes()) {
+            assert!((a - 1.0).abs() < 1e-12);
+            assert!((v - 0.1).abs() < 1e-12);
+        }
+    }
+}
@@ -14,4 +14,78 @@ pub enum MeshError {
         point: usize,
         n_points: usize,
     },
+    #[error("patch start mismatch: patch {patch}, expected {expected}, got {got}")]
+    PatchStartMismatch {
+        patch: String,
+        expected: usize,
+        got: usize,
+    },
+    #[error("boundary face count mismatch: expected {expected}, got {got}")]
+    BoundaryFaceCountMismatch { expected: usize, got: usize },
+    #[error("duplicate patch name: {name}")]
+    DuplicatePatchName { name: String },
+    #[error("patch not found: {name}")]
+    PatchNotFound { name: String },
+    #[error("zone index out of range: zone {zone}, index {index}, len {len}")]
+    ZoneIndexOutOfRange {
+        zone: String,
+        index: usize,
+        len: usize,
+    },
+    #[error("duplicate zone name: {name}")]
+    DuplicateZoneName { name: String },
+    #[error("zone not found: {name}")]
+    ZoneNotFound { name: String },
+    #[error("invalid permutation: expected a permutation of 0..{len}")]
+    InvalidPermutation { len: usize },
+    #[error("invalid part count: {n_parts} parts for {n_cells} cells")]
+    InvalidPartCount { n_parts: usize, n_cells: usize },
+    #[error("cell rank length mismatch: expected {expected}, got {got}")]
+    CellRankLengthMismatch { expected: usize, got: usize },
+    #[error("cell rank out of range: cell {cell}, rank {rank}, n_parts {n_parts}")]
+    CellRankOutOfRange {
+        cell: usize,
+        rank: usize,
+        n_parts: usize,
+    },
+    #[error("cyclic patch {patch}: neighbor patch {neighbor} not found")]
+    CyclicNeighborNotFound { patch: String, neighbor: String },
+    #[error("cyclic patch {patch}: neighbor patch {neighbor} does not couple back")]
+    CyclicNeighborMismatch { patch: String, neighbor: String },
+    #[error("cyclic patch {patch}: size {size} differs from neighbor size {neighbor_size}")]
+    CyclicSizeMismatch {
+        patch: String,
+        size: usize,
+        neighbor_size: usize,
+    },
+    #[error("cyclic patch {patch}: no matching neighbor face for face {face}")]
+    CyclicMatchFailed { patch: String, face: usize },
+    #[error("cyclic patch {patch}: face {face} and its neighbor face lie on different ranks")]
+    CyclicSplitAcrossRanks { patch: String, face: usize },
+    #[error("cell {cell} is not a hexahedron")]
+    NotHexahedral { cell: usize },
+    #[error("point count mismatch: expected {expected}, got {got}")]
+    PointCountMismatch { expected: usize, got: usize },
+    #[error("wedge mesh needs exactly two wedge patches, found {count}")]
+    WedgePatchCount { count: usize },
+    #[error("wedge patch {patch} is not planar")]
+    WedgeNotPlanar { patch: String },
+    #[error("wedge angle {degrees} degrees is outside (0, {max}]")]
+    WedgeAngleOutOfRange { degrees: f64, max: f64 },
+    #[error("cell {cell} does not have exactly one face on each wedge patch")]
+    WedgeNotSingleCell { cell: usize },
+    #[error("invalid extrusion: {reason}")]
+    InvalidExtrusion { reason: String },
+    #[error("invalid scale factor: {factor}")]
+    InvalidScaleFactor { factor: f64 },
+    #[error("invalid merge: {reason}")]
+    InvalidMerge { reason: String },
+    #[error("invalid meshing: {reason}")]
+    InvalidMeshing { reason: String },
+    #[error("duplicate region name: {name}")]
+    DuplicateRegionName { name: String },
+    #[error("region not found: {name}")]
+    RegionNotFound { name: String },
+    #[error("invalid region interface: {reason}")]
+    InvalidInterface { reason: String },
 }
@@ -0,0 +1,452 @@
+use std::collections::HashMap;
+
+use dugong_types::tensor::Vector;
+
+use crate::assemble::{BoundaryGroup, InternalFace, assemble};
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::patch::{CoupledTransform, PatchKind};
+
+/// How the points of a surface move as it is extruded into layers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExtrudeModel {
+    /// Translation by `thickness` along `direction`.
+    Linear { direction: Vector, thickness: f64 },
+    /// Translation by `thickness` away from `center`, along each point's
+    /// radial direction.
+    Radial { center: Vector, thickness: f64 },
+    /// Rotation by `angle` radians about the axis through `center` along
+    /// `axis`. Points on the axis are shared by all layers, so cells touching
+    /// the axis degenerate into prisms. The caps become wedge patches.
+    Wedge {
+        axis: Vector,
+        center: Vector,
+        angle: f64,
+    },
+}
+
+impl ExtrudeModel {
+    /// Returns the position of `p` after extruding a fraction `s` of the way.
+    fn displace(&self, p: Vector, s: f64) -> Vector {
+        match self {
+            ExtrudeModel::Linear {
+                direction,
+                thickness,
+            } => p + *direction * (thickness * s / direction.mag()),
+            ExtrudeModel::Radial { center, thickness } => {
+                let r = p - *center;
+                p + r * (thickness * s / r.mag())
+            }
+            ExtrudeModel::Wedge {
+                axis,
+                center,
+                angle,
+            } => CoupledTransform::Rotational {
+                axis: *axis,
+                center: *center,
+                angle: angle * s,
+            }
+            .transform_point(p),
+        }
+    }
+
+    /// Returns the local extrusion direction at `p` (not normalized).
+    fn direction(&self, p: Vector) -> Vector {
+        match self {
+            ExtrudeModel::Linear { direction, .. } => *direction,
+            ExtrudeModel::Radial { center, .. } => p - *center,
+            ExtrudeModel::Wedge {
+                axis,
+                center,
+                angle,
+            } => axis.cross(&(p - *center)) * angle.signum(),
+        }
+    }
+
+    /// Returns the kind of the `bottom` and `top` patches.
+    fn cap_kind(&self) -> PatchKind {
+        match self {
+            ExtrudeModel::Wedge { .. } => PatchKind::Wedge,
+            _ => PatchKind::Patch,
+        }
+    }
+}
+
+/// A directed use `(face, from, to)` of a surface edge.
+type EdgeUse = (usize, usize, usize);
+
+/// Returns the layer fractions `0 = s_0 < ... < s_n = 1` for `n` layers whose
+/// thickness grows geometrically from the first layer to `grading` times
+/// that in the last.
+pub(crate) fn layer_fractions(n_layers: usize, grading: f64) -> Vec<f64> {
+    let ratio = if n_layers > 1 {
+        grading.powf(1.0 / (n_layers - 1) as f64)
+    } else {
+        1.0
+    };
+    let mut fractions = Vec::with_capacity(n_layers + 1);
+    let mut sum = 0.0;
+    let mut thickness = 1.0;
+    fractions.push(0.0);
+    for _ in 0..n_layers {
+        sum += thickness;
+        fractions.push(sum);
+        thickness *= ratio;
+    }
+    fractions.iter().map(|s| s / sum).collect()
+}
+
+/// Extrudes a polygonal surface into a volume mesh of `n_layers` cell layers.
+///
+/// Each surface face becomes a column of cells; faces are reoriented as
+/// needed so that all columns grow in the extrusion direction. `grading` is
+/// the ratio of the last layer thickness to the first. The mesh has the
+/// patches `bottom` (the original surface), `top` (the extruded surface) and
+/// `sides` (swept from the open edges of the surface). Cell `l * n_faces + f`
+/// is layer `l` of the column above face `f`. For a 2D mesh, extrude one
+/// layer and mark the caps with [`Mesh::set_patch_kind`] as
+/// [`PatchKind::Empty`].
+///
+/// # Errors
+///
+/// Returns [`MeshError::InvalidExtrusion`] if `n_layers` is zero, `grading`
+/// is not positive, a face is parallel to the extrusion direction, an edge
+/// is shared by more than two faces, or a radial extrusion contains its
+/// center. Returns other errors if the resulting mesh fails validation.
+pub fn extrude(
+    points: &[Vector],
+    faces: &[Vec<usize>],
+    model: &ExtrudeModel,
+    n_layers: usize,
+    grading: f64,
+) -> Result<Mesh, MeshError> {
+    let invalid = |reason: String| MeshError::InvalidExtrusion { reason };
+    if n_layers == 0 {
+        return Err(invalid("at least one layer is required".into()));
+    }
+    if grading.is_nan() || grading <= 0.0 {
+        return Err(invalid(format!("grading {grading} is not positive")));
+    }
+    if let ExtrudeModel::Radial { center, .. } = model
+        && points.contains(center)
+    {
+        return Err(invalid("a point coincides with the radial center".into()));
+    }
+
+    // Points on the rotation axis do not move and are not duplicated.
+    let scale = points.iter().map(|p| p.mag()).fold(0.0, f64::max).max(1.0);
+    let fixed: Vec<bool> = match model {
+        ExtrudeModel::Wedge { axis, center, .. } => points
+            .iter()
+            .map(|&p| axis.cross(&(p - *center)).mag() / axis.mag() <= 1e-9 * scale)
+            .collect(),
+        _ => vec![false; points.len()],
+    };
+    let mut point_id = vec![Vec::with_capacity(n_layers + 1); points.len()];
+    let fractions = layer_fractions(n_layers, grading);
+    let mut new_points = Vec::new();
+    for (l, &s) in fractions.iter().enumerate() {
+        for (i, &p) in points.iter().enumerate() {
+            if fixed[i] && l > 0 {
+                let base = point_id[i][0];
+                point_id[i].push(base);
+            } else {
+                point_id[i].push(new_points.len());
+                new_points.push(model.displace(p, s));
+            }
+        }
+    }
+
+    // Orient every face so that its normal points along the extrusion.
+    let mut oriented = Vec::with_capacity(faces.len());
+    for (fi, face) in faces.iter().enumerate() {
+        let (center, area) = crate::geometry::compute_face_geometry(points, face);
+        let dir = model.direction(center);
+        let along = area * dir;
+        if along.abs() <= 1e-9 * area.mag() * dir.mag() || area.mag() == 0.0 {
+            return Err(invalid(format!(
+                "face {fi} is parallel to the extrusion direction"
+            )));
+        }
+        let mut face = face.clone();
+        if along < 0.0 {
+            face.reverse();
+        }
+        oriented.push(face);
+    }
+
+    let n_faces = faces.len();
+    let cell = |f: usize, l: usize| l * n_faces + f;
+    let at =
+        |face: &[usize], l: usize| -> Vec<usize> { face.iter().map(|&p| point_id[p][l]).collect() };
+    // Drops repeated vertices of faces touching the wedge axis; faces left
+    // with fewer than three vertices have collapsed onto the axis.
+    let side = |p: usize, q: usize, l: usize| -> Option<Vec<usize>> {
+        let mut quad = vec![
+            point_id[p][l],
+            point_id[q][l],
+            point_id[q][l + 1],
+            point_id[p][l + 1],
+        ];
+        quad.dedup();
+        if quad.len() > 1 && quad[0] == quad[quad.len() - 1] {
+            quad.pop();
+        }
+        (quad.len() >= 3).then_some(quad)
+    };
+
+    let mut edges: HashMap<(usize, usize), Vec<EdgeUse>> = HashMap::new();
+    for (f, face) in oriented.iter().enumerate() {
+        for i in 0..face.len() {
+            let (p, q) = (face[i], face[(i + 1) % face.len()]);
+            edges
+                .entry((p.min(q), p.max(q)))
+                .or_default()
+                .push((f, p, q));
+        }
+    }
+    let mut edge_keys: Vec<_> = edges.keys().copied().collect();
+    edge_keys.sort_unstable();
+
+    let mut internal: Vec<InternalFace> = Vec::new();
+    let mut sides = Vec::new();
+    for l in 0..n_layers {
+        for (f, face) in oriented.iter().enumerate() {
+            if l > 0 {
+                internal.push((at(face, l), cell(f, l - 1), cell(f, l)));
+            }
+        }
+        for key in &edge_keys {
+            match edges[key][..] {
+                [(f, p, q)] => {
+                    if let Some(quad) = side(p, q, l) {
+                        sides.push((quad, cell(f, l)));
+                    }
+                }
+                [(f, p, q), (g, _, _)] => {
+                    if let Some(quad) = side(p, q, l) {
+                        internal.push((quad, cell(f, l), cell(g, l)));
+                    }
+                }
+                _ => {
+                    return Err(invalid(format!(
+                        "edge ({}, {}) is shared by more than two faces",
+                        key.0, key.1
+                    )));
+                }
+            }
+        }
+    }
+
+    let bottom = oriented
+        .iter()
+        .enumerate()
+        .map(|(f, face)| {
+            let mut face = at(face, 0);
+            face.reverse();
+            (face, cell(f, 0))
+        })
+        .collect();
+    let top = oriented
+        .iter()
+        .enumerate()
+        .map(|(f, face)| (at(face, n_layers), cell(f, n_layers - 1)))
+        .collect();
+    let groups = vec![
+        BoundaryGroup {
+            name: "bottom".into(),
+            kind: model.cap_kind(),
+            faces: bottom,
+        },
+        BoundaryGroup {
+            name: "top".into(),
+            kind: model.cap_kind(),
+            faces: top,
+        },
+        BoundaryGroup {
+            name: "sides".into(),
+            kind: PatchKind::Patch,
+            faces: sides,
+        },
+    ];
+    let (mesh, _) = assemble(new_points, internal, groups)?;
+    Ok(mesh)
+}
+
+impl Mesh {
+    /// Extrudes the faces of a boundary patch into a new volume mesh.
+    ///
+    /// See [`extrude`] for the layout of the result. Returns `None` if no
+    /// patch is named `patch`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` under the same conditions as [`extrude`].
+    pub fn extrude_patch(
+        &self,
+        patch: &str,
+        model: &ExtrudeModel,
+        n_layers: usize,
+        grading: f64,
+    ) -> Option<Result<Mesh, MeshError>> {
+        let patch = self.patch(patch)?;
+        let mut local = HashMap::new();
+        let mut points = Vec::new();
+        let faces: Vec<Vec<usize>> = self.faces()[patch.range()]
+            .iter()
+            .map(|face| {
+                face.iter()
+                    .map(|&p| {
+                        *local.entry(p).or_insert_with(|| {
+                            points.push(self.points()[p]);
+                            points.len() - 1
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        Some(extrude(&points, &faces, model, n_layers, grading))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refine::tests::assert_closed;
+    use crate::test_meshes::box_mesh;
+
+    /// A 2 x 1 grid of unit quads in the z = 0 plane.
+    fn quads() -> (Vec<Vector>, Vec<Vec<usize>>) {
+        let points = (0..2)
+            .flat_map(|j| (0..3).map(move |i| Vector::new(i as f64, j as f64, 0.0)))
+            .collect();
+        (points, vec![vec![0, 1, 4, 3], vec![1, 2, 5, 4]])
+    }
+
+    #[test]
+    fn test_layer_fractions_grading() {
+        let s = layer_fractions(3, 4.0);
+        // Thicknesses 1, 2, 4 out of 7.
+        let expected = [0.0, 1.0 / 7.0, 3.0 / 7.0, 1.0];
+        for (a, b) in s.iter().zip(expected) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        assert_eq!(layer_fractions(1, 5.0), vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_extrude_linear_builds_layered_hexes() {
+        let (points, faces) = quads();
+        let model = ExtrudeModel::Linear {
+            direction: Vector::new(0.0, 0.0, 2.0),
+            thickness: 0.5,
+        };
+        let mesh = extrude(&points, &faces, &model, 3, 1.0).unwrap();
+        assert_eq!(mesh.n_cells(), 6);
+        assert_eq!(mesh.n_points(), 24);
+        assert_closed(&mesh);
+        for &v in mesh.cell_volumes() {
+            assert!((v - 0.5 / 3.0).abs() < 1e-12);
+        }
+        assert_eq!(mesh.patch("bottom").unwrap().size(), 2);
+        assert_eq!(mesh.patch("top").unwrap().size(), 2);
+        assert_eq!(mesh.patch("sides").unwrap().size(), 18);
+        // Layer 2 of the column above face 1.
+        assert!((mesh.cell_centers()[5] - Vector::new(1.5, 0.5, 5.0 / 12.0)).mag() < 1e-12);
+    }
+
+    #[test]
+    fn test_extrude_flips_faces_against_direction() {
+        let (points, mut faces) = quads();
+        faces[1].reverse();
+        let model = ExtrudeModel::Linear {
+            direction: Vector::new(0.0, 0.0, -1.0),
+            thickness: 1.0,
+        };
+        let mesh = extrude(&points, &faces, &model, 1, 1.0).unwrap();
+        assert_closed(&mesh);
+        assert!(mesh.cell_volumes().iter().all(|&v| (v - 1.0).abs() < 1e-12));
+        assert_eq!(mesh.n_internal_faces(), 1);
+
+        let mut mesh = mesh;
+        mesh.set_patch_kind("bottom", PatchKind::Empty).unwrap();
+        mesh.set_patch_kind("top", PatchKind::Empty).unwrap();
+        assert_eq!(mesh.solution_directions(), [true, true, false]);
+    }
+
+    #[test]
+    fn test_extrude_radial_shell() {
+        let (points, faces) = quads();
+        let center = Vector::new(1.0, 0.5, -10.0);
+        let model = ExtrudeModel::Radial {
+            center,
+            thickness: 0.1,
+        };
+        let mesh = extrude(&points, &faces, &model, 2, 1.0).unwrap();
+        assert_closed(&mesh);
+        for (p, q) in points.iter().zip(&mesh.points()[2 * points.len()..]) {
+            assert!(((*q - center).mag() - (*p - center).mag() - 0.1).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_extrude_wedge_collapses_axis_points() {
+        // Quads touching the x axis, rotated 5 degrees about it.
+        let points = vec![
+            Vector::new(0.0, 0.0, 0.0),
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(1.0, 1.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+        ];
+        let angle = 5f64.to_radians();
+        let model = ExtrudeModel::Wedge {
+            axis: Vector::new(1.0, 0.0, 0.0),
+            center: Vector::zero(),
+            angle,
+        };
+        let mesh = extrude(&points, &[vec![0, 1, 2, 3]], &model, 1, 1.0).unwrap();
+        assert_eq!(mesh.n_points(), 6);
+        assert_eq!(mesh.n_faces(), 5);
+        assert_closed(&mesh);
+        let expected = 0.5 * angle.sin();
+        assert!((mesh.cell_volumes()[0] - expected).abs() < 1e-12);
+        assert_eq!(mesh.patch("top").unwrap().kind(), &PatchKind::Wedge);
+        let wedge = mesh.wedge().unwrap().unwrap();
+        assert!((wedge.angle() - angle).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_extrude_patch_of_box() {
+        let mesh = box_mesh([2, 3, 1], [2.0, 3.0, 1.0]);
+        let model = ExtrudeModel::Linear {
+            direction: Vector::new(0.0, 0.0, 1.0),
+            thickness: 2.0,
+        };
+        let layer = mesh
+            .extrude_patch("z-max", &model, 4, 2.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(layer.n_cells(), 24);
+        assert_closed(&layer);
+        let total: f64 = layer.cell_volumes().iter().sum();
+        assert!((total - 12.0).abs() < 1e-12);
+        assert!(mesh.extrude_patch("nope", &model, 1, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_extrude_invalid_input_returns_err() {
+        let (points, faces) = quads();
+        let model = ExtrudeModel::Linear {
+            direction: Vector::new(1.0, 0.0, 0.0),
+            thickness: 1.0,
+        };
+        let invalid = |r| matches!(r, Err(MeshError::InvalidExtrusion { .. }));
+        assert!(invalid(extrude(&points, &faces, &model, 1, 1.0)));
+        let up = ExtrudeModel::Linear {
+            direction: Vector::new(0.0, 0.0, 1.0),
+            thickness: 1.0,
+        };
+        assert!(invalid(extrude(&points, &faces, &up, 0, 1.0)));
+        assert!(invalid(extrude(&points, &faces, &up, 2, 0.0)));
+    }
+}
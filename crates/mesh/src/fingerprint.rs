@@ -0,0 +1,101 @@
+use crate::mesh::Mesh;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A 64-bit FNV-1a hasher.
+///
+/// `std::hash::DefaultHasher` may change between Rust releases, so it is
+/// unsuitable for values written to disk.
+struct Fnv(u64);
+
+impl Fnv {
+    fn bytes(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ u64::from(b)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn u64(&mut self, v: u64) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    fn usize(&mut self, v: usize) {
+        self.u64(v as u64);
+    }
+
+    fn str(&mut self, s: &str) {
+        self.usize(s.len());
+        self.bytes(s.as_bytes());
+    }
+}
+
+impl Mesh {
+    /// Returns a hash of the points, faces, owner, neighbor and patches.
+    ///
+    /// The value depends only on the mesh contents and is the same on every
+    /// platform and build, so checkpoints can record it and restarts can
+    /// refuse fields written for a different mesh. Point coordinates are
+    /// hashed bitwise: moving a point by any amount changes the result.
+    /// Zones are not included.
+    pub fn fingerprint(&self) -> u64 {
+        let mut h = Fnv(FNV_OFFSET);
+        h.usize(self.n_points());
+        for p in self.points() {
+            for x in p.as_array() {
+                h.u64(x.to_bits());
+            }
+        }
+        h.usize(self.n_faces());
+        for face in self.faces() {
+            h.usize(face.len());
+            face.iter().for_each(|&v| h.usize(v));
+        }
+        self.owner().iter().for_each(|&c| h.usize(c));
+        h.usize(self.n_internal_faces());
+        self.neighbor().iter().for_each(|&c| h.usize(c));
+        h.usize(self.patches().len());
+        for patch in self.patches() {
+            h.str(patch.name());
+            h.str(patch.kind().type_name());
+            h.usize(patch.start());
+            h.usize(patch.size());
+        }
+        h.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_types::tensor::Vector;
+
+    use crate::patch::PatchKind;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_fingerprint_is_deterministic() {
+        let a = box_mesh([2, 2, 2], [1.0, 1.0, 1.0]);
+        let b = box_mesh([2, 2, 2], [1.0, 1.0, 1.0]);
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_ne!(
+            a.fingerprint(),
+            box_mesh([2, 2, 1], [1.0, 1.0, 1.0]).fingerprint()
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_detects_moved_points_and_patch_changes() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let original = mesh.fingerprint();
+
+        let mut moved = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut points = moved.points().to_vec();
+        points[0] += Vector::new(1e-9, 0.0, 0.0);
+        moved.move_points(points).unwrap();
+        assert_ne!(moved.fingerprint(), original);
+
+        let mut retyped = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        retyped.set_patch_kind("x-min", PatchKind::Wall).unwrap();
+        assert_ne!(retyped.fingerprint(), original);
+    }
+}
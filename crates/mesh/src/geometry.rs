@@ -1,7 +1,9 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 
 use dugong_types::tensor::Vector;
 
+use crate::parallel;
+
 /// Computes the centroid and area vector of a single face.
 ///
 /// Uses fan triangulation from the average vertex position, which handles
@@ -48,7 +50,11 @@ pub(crate) fn compute_face_geometry(points: &[Vector], face: &[usize]) -> (Vecto
     (face_center, total_area_vec)
 }
 
-/// Computes volumes and centroids for all cells in one pass.
+/// Computes volumes and centroids for all cells.
+///
+/// Face geometry is computed first, then each cell is assembled from its
+/// faces with [`compute_single_cell_geometry`]. Both passes run in parallel
+/// with the `parallel` feature.
 ///
 /// Returns `(cell_volumes, cell_centers)`.
 ///
@@ -65,61 +71,288 @@ pub(crate) fn compute_cell_geometry(
     neighbor: &[usize],
     n_cells: usize,
 ) -> (Vec<f64>, Vec<Vector>) {
-    // Compute geometry for all faces up front
-    let face_geom: Vec<(Vector, Vector)> = faces
-        .iter()
-        .map(|f| compute_face_geometry(points, f))
-        .collect();
+    let (face_centers, face_areas) = compute_all_face_geometry(points, faces);
+    let cell_faces = compute_cell_faces(owner, neighbor, n_cells);
+    parallel::map_indexed(n_cells, |c| {
+        compute_single_cell_geometry(c, &cell_faces[c], &face_centers, &face_areas, owner)
+    })
+    .into_iter()
+    .unzip()
+}
 
-    // Reference point for each cell: average of its face centers
-    let mut cell_face_count = vec![0usize; n_cells];
-    let mut c_ref = vec![Vector::zero(); n_cells];
+/// Computes the centers and area vectors of all faces, in parallel with the
+/// `parallel` feature.
+///
+/// Returns `(face_centers, face_area_vectors)`.
+///
+/// # Panics
+///
+/// Panics if any point index in `faces` is not a valid index into `points`.
+pub(crate) fn compute_all_face_geometry(
+    points: &[Vector],
+    faces: &[Vec<usize>],
+) -> (Vec<Vector>, Vec<Vector>) {
+    parallel::map_indexed(faces.len(), |f| compute_face_geometry(points, &faces[f]))
+        .into_iter()
+        .unzip()
+}
 
-    for (fi, &o) in owner.iter().enumerate() {
-        c_ref[o] += face_geom[fi].0;
-        cell_face_count[o] += 1;
+/// Computes the volume and centroid of a single cell from cached face
+/// geometry.
+///
+/// Uses the same pyramid decomposition as [`compute_cell_geometry`].
+///
+/// Returns `(cell_volume, cell_center)`.
+///
+/// # Panics
+///
+/// Panics if any element of `cell_faces` is not a valid index into
+/// `face_centers`, `face_areas`, and `owner`.
+pub(crate) fn compute_single_cell_geometry(
+    cell: usize,
+    cell_faces: &[usize],
+    face_centers: &[Vector],
+    face_areas: &[Vector],
+    owner: &[usize],
+) -> (f64, Vector) {
+    let mut c_ref = Vector::zero();
+    for &fi in cell_faces {
+        c_ref += face_centers[fi];
     }
-    for (fi, &n) in neighbor.iter().enumerate() {
-        c_ref[n] += face_geom[fi].0;
-        cell_face_count[n] += 1;
+    if !cell_faces.is_empty() {
+        c_ref /= cell_faces.len() as f64;
     }
-    for ci in 0..n_cells {
-        if cell_face_count[ci] > 0 {
-            c_ref[ci] /= cell_face_count[ci] as f64;
+
+    let mut volume = 0.0;
+    let mut weighted_center = Vector::zero();
+    for &fi in cell_faces {
+        let fc = face_centers[fi];
+        let fa = if owner[fi] == cell {
+            face_areas[fi]
+        } else {
+            -face_areas[fi]
+        };
+        let pyr_vol = fa * (fc - c_ref) / 3.0;
+        volume += pyr_vol;
+        weighted_center += (c_ref * 0.75 + fc * 0.25) * pyr_vol;
+    }
+
+    if volume.abs() > 1e-30 {
+        (volume, weighted_center / volume)
+    } else {
+        (volume, c_ref)
+    }
+}
+
+/// Computes the volume swept by a face as its points move from `old_points`
+/// to `new_points`.
+///
+/// The face is fan-triangulated about its average vertex position, as in
+/// [`compute_face_geometry`], and each vertex is assumed to move linearly.
+/// The swept volume of each triangle is then integrated exactly, so the
+/// swept volumes of a closed cell with planar faces sum to its volume change.
+/// The result is positive when the face moves along its area vector.
+///
+/// # Panics
+///
+/// Panics if any element of `face` is not a valid index into both point
+/// slices.
+pub(crate) fn compute_face_swept_volume(
+    old_points: &[Vector],
+    new_points: &[Vector],
+    face: &[usize],
+) -> f64 {
+    let n = face.len();
+    let average = |points: &[Vector]| {
+        let mut p = Vector::zero();
+        for &idx in face {
+            p += points[idx];
         }
+        p / n as f64
+    };
+    let (old_ref, new_ref) = (average(old_points), average(new_points));
+
+    let mut volume = 0.0;
+    for i in 0..n {
+        let (a, b) = (face[i], face[(i + 1) % n]);
+        volume += triangle_swept_volume(
+            [old_points[a], old_points[b], old_ref],
+            [new_points[a], new_points[b], new_ref],
+        );
     }
+    volume
+}
 
-    let mut cell_volumes = vec![0.0_f64; n_cells];
-    let mut cell_centers_weighted = vec![Vector::zero(); n_cells];
+/// Computes the volume swept by a triangle whose vertices move linearly.
+///
+/// The area vector is quadratic in time and the mean velocity is constant,
+/// so Simpson's rule integrates the flux exactly.
+fn triangle_swept_volume(old: [Vector; 3], new: [Vector; 3]) -> f64 {
+    let area = |t: [Vector; 3]| (t[1] - t[0]).cross(&(t[2] - t[0])) * 0.5;
+    let mid = [0, 1, 2].map(|i| (old[i] + new[i]) * 0.5);
+    let displacement = (new[0] - old[0] + new[1] - old[1] + new[2] - old[2]) / 3.0;
+    (area(old) + area(mid) * 4.0 + area(new)) * displacement / 6.0
+}
 
-    // Owner-side contribution (area vector points away from owner)
-    for (fi, &o) in owner.iter().enumerate() {
-        let (fc, fa) = face_geom[fi];
-        let pyr_vol = fa * (fc - c_ref[o]) / 3.0;
-        let pyr_center = c_ref[o] * 0.75 + fc * 0.25;
-        cell_volumes[o] += pyr_vol;
-        cell_centers_weighted[o] += pyr_center * pyr_vol;
+/// Returns the point of triangle `(a, b, c)` closest to `p`.
+///
+/// Classifies `p` against the Voronoi regions of the vertices, edges and
+/// interior of the triangle (Ericson, *Real-Time Collision Detection*, 5.1.5).
+pub(crate) fn closest_point_on_triangle(p: Vector, a: Vector, b: Vector, c: Vector) -> Vector {
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = ab * ap;
+    let d2 = ac * ap;
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+    let bp = p - b;
+    let d3 = ab * bp;
+    let d4 = ac * bp;
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+    let cp = p - c;
+    let d5 = ab * cp;
+    let d6 = ac * cp;
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+    let denom = va + vb + vc;
+    if denom == 0.0 {
+        // Degenerate (zero-area) triangle: fall back to the nearest vertex.
+        return [a, b, c]
+            .into_iter()
+            .min_by(|x, y| (p - *x).mag().total_cmp(&(p - *y).mag()))
+            .unwrap_or(a);
     }
+    a + ab * (vb / denom) + ac * (vc / denom)
+}
 
-    // Neighbor-side contribution (area vector is reversed for the neighbor cell)
-    for (fi, &n) in neighbor.iter().enumerate() {
-        let (fc, fa) = face_geom[fi];
-        let pyr_vol = (-fa) * (fc - c_ref[n]) / 3.0;
-        let pyr_center = c_ref[n] * 0.75 + fc * 0.25;
-        cell_volumes[n] += pyr_vol;
-        cell_centers_weighted[n] += pyr_center * pyr_vol;
+/// Returns the point of a face closest to `p`.
+///
+/// The face is fan-triangulated about its average vertex position, as in
+/// [`compute_face_geometry`].
+///
+/// # Panics
+///
+/// Panics if any element of `face` is not a valid index into `points`.
+pub(crate) fn closest_point_on_face(points: &[Vector], face: &[usize], p: Vector) -> Vector {
+    let n = face.len();
+    let mut p_ref = Vector::zero();
+    for &idx in face {
+        p_ref += points[idx];
     }
+    p_ref /= n as f64;
 
-    let mut cell_centers = vec![Vector::zero(); n_cells];
-    for ci in 0..n_cells {
-        if cell_volumes[ci].abs() > 1e-30 {
-            cell_centers[ci] = cell_centers_weighted[ci] / cell_volumes[ci];
-        } else {
-            cell_centers[ci] = c_ref[ci];
+    let mut best = p_ref;
+    let mut best_dist = f64::INFINITY;
+    for i in 0..n {
+        let q = closest_point_on_triangle(p, p_ref, points[face[i]], points[face[(i + 1) % n]]);
+        let dist = (q - p).mag();
+        if dist < best_dist {
+            best = q;
+            best_dist = dist;
         }
     }
+    best
+}
 
-    (cell_volumes, cell_centers)
+/// Returns the parameter `t >= 0` at which the ray `origin + t * direction`
+/// crosses triangle `(a, b, c)`, from either side (Moller-Trumbore).
+pub(crate) fn ray_triangle_intersection(
+    origin: Vector,
+    direction: Vector,
+    a: Vector,
+    b: Vector,
+    c: Vector,
+) -> Option<f64> {
+    let (e1, e2) = (b - a, c - a);
+    let p = direction.cross(&e2);
+    let det = e1 * p;
+    if det.abs() <= 1e-14 * e1.mag() * e2.mag() * direction.mag() {
+        return None;
+    }
+    let s = origin - a;
+    let u = (s * p) / det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(&e1);
+    let v = (direction * q) / det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = (e2 * q) / det;
+    (t >= 0.0).then_some(t)
+}
+
+/// Returns the smallest `t >= 0` at which the ray `origin + t * direction`
+/// crosses a face, fan-triangulated as in [`compute_face_geometry`].
+///
+/// # Panics
+///
+/// Panics if any element of `face` is not a valid index into `points`.
+pub(crate) fn ray_face_intersection(
+    points: &[Vector],
+    face: &[usize],
+    origin: Vector,
+    direction: Vector,
+) -> Option<f64> {
+    let n = face.len();
+    let mut p_ref = Vector::zero();
+    for &idx in face {
+        p_ref += points[idx];
+    }
+    p_ref /= n as f64;
+    (0..n)
+        .filter_map(|i| {
+            let (a, b) = (points[face[i]], points[face[(i + 1) % n]]);
+            ray_triangle_intersection(origin, direction, p_ref, a, b)
+        })
+        .min_by(f64::total_cmp)
+}
+
+/// Computes the skewness correction vector of each face: the offset from
+/// the point where the owner-to-neighbor line crosses the face plane to the
+/// face center.
+///
+/// Linear interpolation along the cell-to-cell line is exact at the
+/// crossing point, so a skew-corrected face value is `phi_i + m . grad(phi)`
+/// with `m` the returned vector. Boundary faces, and faces whose plane is
+/// parallel to the cell-to-cell line, get a zero vector.
+pub(crate) fn compute_skewness_vectors(
+    face_areas: &[Vector],
+    face_centers: &[Vector],
+    cell_centers: &[Vector],
+    owner: &[usize],
+    neighbor: &[usize],
+) -> Vec<Vector> {
+    let mut skew = vec![Vector::zero(); face_areas.len()];
+    for (f, &n) in neighbor.iter().enumerate() {
+        let (s, p) = (face_areas[f], cell_centers[owner[f]]);
+        let d = cell_centers[n] - p;
+        let sd = s * d;
+        if sd.abs() <= 1e-300 {
+            continue;
+        }
+        let crossing = p + d * ((s * (face_centers[f] - p)) / sd);
+        skew[f] = face_centers[f] - crossing;
+    }
+    skew
 }
 
 /// Builds the list of face indices adjacent to each cell.
@@ -194,6 +427,72 @@ pub(crate) fn compute_cell_points(
     result
 }
 
+/// Derives the unique edges of the mesh and the edges of each face.
+///
+/// Each edge is stored as its two point indices in ascending order; edges
+/// are numbered in order of first appearance. `face_edges[f][i]` is the edge
+/// from vertex `i` to vertex `i + 1` of face `f`.
+///
+/// Returns `(edges, face_edges)`.
+pub(crate) fn compute_edges(faces: &[Vec<usize>]) -> (Vec<[usize; 2]>, Vec<Vec<usize>>) {
+    let mut index = HashMap::new();
+    let mut edges = Vec::new();
+    let face_edges = faces
+        .iter()
+        .map(|face| {
+            let n = face.len();
+            (0..n)
+                .map(|i| {
+                    let (a, b) = (face[i], face[(i + 1) % n]);
+                    let key = [a.min(b), a.max(b)];
+                    *index.entry(key).or_insert_with(|| {
+                        edges.push(key);
+                        edges.len() - 1
+                    })
+                })
+                .collect()
+        })
+        .collect();
+    (edges, face_edges)
+}
+
+/// Collects the edge indices of each cell from its faces, sorted and
+/// without duplicates.
+///
+/// # Panics
+///
+/// Panics if any face index in `cell_faces` is not a valid index into
+/// `face_edges`.
+pub(crate) fn compute_cell_edges(
+    cell_faces: &[Vec<usize>],
+    face_edges: &[Vec<usize>],
+) -> Vec<Vec<usize>> {
+    cell_faces
+        .iter()
+        .map(|faces| {
+            let edges: BTreeSet<usize> = faces
+                .iter()
+                .flat_map(|&f| face_edges[f].iter().copied())
+                .collect();
+            edges.into_iter().collect()
+        })
+        .collect()
+}
+
+/// Lists the edges meeting at each point, in ascending edge order.
+///
+/// # Panics
+///
+/// Panics if any edge references a point `>= n_points`.
+pub(crate) fn compute_point_edges(edges: &[[usize; 2]], n_points: usize) -> Vec<Vec<usize>> {
+    let mut result = vec![Vec::new(); n_points];
+    for (e, &[a, b]) in edges.iter().enumerate() {
+        result[a].push(e);
+        result[b].push(e);
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -317,6 +616,8 @@ mod tests {
     }
 
     #[test]
+    // The loops index by cell number to report the failing cell.
+    #[allow(clippy::needless_range_loop)]
     fn cell_geometry_two_cells() {
         // cell 0: x=0..1, cell 1: x=1..2
         let pts = vec![
@@ -363,6 +664,119 @@ mod tests {
         }
     }
 
+    // ===== compute_single_cell_geometry =====
+
+    #[test]
+    fn single_cell_geometry_matches_all_cells() {
+        let pts = cube_points();
+        let faces = cube_faces();
+        let owner = vec![0; 6];
+        let (fc, fa): (Vec<_>, Vec<_>) =
+            faces.iter().map(|f| compute_face_geometry(&pts, f)).unzip();
+        let (vol, center) = compute_single_cell_geometry(0, &[0, 1, 2, 3, 4, 5], &fc, &fa, &owner);
+        assert!((vol - 1.0).abs() < 1e-12);
+        assert!((center - Vector::new(0.5, 0.5, 0.5)).mag() < 1e-12);
+    }
+
+    // ===== compute_face_swept_volume =====
+
+    #[test]
+    fn face_swept_volume_translation_along_normal() {
+        let old = square_points();
+        let new: Vec<Vector> = old
+            .iter()
+            .map(|&p| p + Vector::new(0.0, 0.0, 0.5))
+            .collect();
+        let vol = compute_face_swept_volume(&old, &new, &[0, 1, 2, 3]);
+        // Unit area moved 0.5 along +z, the area vector direction
+        assert!((vol - 0.5).abs() < 1e-12, "swept volume {vol}");
+    }
+
+    #[test]
+    fn face_swept_volume_tangential_motion_is_zero() {
+        let old = square_points();
+        let new: Vec<Vector> = old
+            .iter()
+            .map(|&p| p + Vector::new(0.3, -0.2, 0.0))
+            .collect();
+        let vol = compute_face_swept_volume(&old, &new, &[0, 1, 2, 3]);
+        assert!(vol.abs() < 1e-12, "swept volume {vol}");
+    }
+
+    #[test]
+    fn face_swept_volumes_sum_to_volume_change() {
+        let old = cube_points();
+        let new: Vec<Vector> = old
+            .iter()
+            .map(|&p| Vector::new(p.x() * (1.0 + 0.5 * p.z()), p.y() * 2.0, p.z() + 0.1))
+            .collect();
+        let faces = cube_faces();
+        let swept: f64 = faces
+            .iter()
+            .map(|f| compute_face_swept_volume(&old, &new, f))
+            .sum();
+        let owner = vec![0; 6];
+        let (v_old, _) = compute_cell_geometry(&old, &faces, &owner, &[], 1);
+        let (v_new, _) = compute_cell_geometry(&new, &faces, &owner, &[], 1);
+        assert!((swept - (v_new[0] - v_old[0])).abs() < 1e-12);
+    }
+
+    // ===== closest_point_on_triangle / closest_point_on_face =====
+
+    #[test]
+    fn closest_point_on_triangle_regions() {
+        let (a, b, c) = (
+            Vector::new(0.0, 0.0, 0.0),
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+        );
+        let q = |x, y, z| closest_point_on_triangle(Vector::new(x, y, z), a, b, c);
+        assert_eq!(q(-1.0, -1.0, 0.0), a);
+        assert_eq!(q(2.0, -0.5, 1.0), b);
+        assert!((q(0.2, 0.3, 4.0) - Vector::new(0.2, 0.3, 0.0)).mag() < 1e-12);
+        assert!((q(0.5, -2.0, 0.0) - Vector::new(0.5, 0.0, 0.0)).mag() < 1e-12);
+        assert!((q(1.0, 1.0, 0.0) - Vector::new(0.5, 0.5, 0.0)).mag() < 1e-12);
+    }
+
+    #[test]
+    fn closest_point_on_face_square() {
+        let pts = square_points();
+        let q = closest_point_on_face(&pts, &[0, 1, 2, 3], Vector::new(0.9, 0.8, -3.0));
+        assert!((q - Vector::new(0.9, 0.8, 0.0)).mag() < 1e-12);
+        let q = closest_point_on_face(&pts, &[0, 1, 2, 3], Vector::new(2.0, 2.0, 1.0));
+        assert!((q - Vector::new(1.0, 1.0, 0.0)).mag() < 1e-12);
+    }
+
+    // ===== ray_triangle_intersection / ray_face_intersection =====
+
+    #[test]
+    fn ray_face_intersection_square() {
+        let pts = square_points();
+        let face = [0, 1, 2, 3];
+        let hit = |o, d| ray_face_intersection(&pts, &face, o, d);
+        let t = hit(Vector::new(0.3, 0.6, 2.0), Vector::new(0.0, 0.0, -0.5)).unwrap();
+        assert!((t - 4.0).abs() < 1e-12);
+        // Back side hits too.
+        assert!(hit(Vector::new(0.3, 0.6, -1.0), Vector::new(0.0, 0.0, 1.0)).is_some());
+        assert!(hit(Vector::new(1.3, 0.6, 2.0), Vector::new(0.0, 0.0, -1.0)).is_none());
+        assert!(hit(Vector::new(0.3, 0.6, 2.0), Vector::new(0.0, 0.0, 1.0)).is_none());
+        assert!(hit(Vector::new(0.3, 0.6, 2.0), Vector::new(1.0, 0.0, 0.0)).is_none());
+    }
+
+    // ===== compute_skewness_vectors =====
+
+    #[test]
+    fn skewness_vectors_offset_face_center() {
+        // Owner at the origin, neighbor at (2, 2, 0); the face is the x = 1
+        // plane with center (1, 0, 0), crossed by the line at (1, 1, 0).
+        let areas = [Vector::new(1.0, 0.0, 0.0), Vector::new(-1.0, 0.0, 0.0)];
+        let centers = [Vector::new(1.0, 0.0, 0.0), Vector::new(-1.0, 0.0, 0.0)];
+        let cells = [Vector::zero(), Vector::new(2.0, 2.0, 0.0)];
+        let skew = compute_skewness_vectors(&areas, &centers, &cells, &[0, 0], &[1]);
+        assert!((skew[0] - Vector::new(0.0, -1.0, 0.0)).mag() < 1e-12);
+        assert_eq!(skew[1], Vector::zero());
+    }
+
     // ===== compute_cell_faces =====
 
     #[test]
@@ -440,4 +854,24 @@ mod tests {
         sorted.sort();
         assert_eq!(cp[0], sorted);
     }
+
+    // ===== compute_edges / compute_cell_edges / compute_point_edges =====
+
+    #[test]
+    fn edges_single_cube() {
+        let faces = cube_faces();
+        let (edges, face_edges) = compute_edges(&faces);
+        assert_eq!(edges.len(), 12);
+        assert!(edges.iter().all(|e| e[0] < e[1]));
+        for (face, fe) in faces.iter().zip(&face_edges) {
+            for (i, &e) in fe.iter().enumerate() {
+                let (a, b) = (face[i], face[(i + 1) % face.len()]);
+                assert_eq!(edges[e], [a.min(b), a.max(b)]);
+            }
+        }
+        let cell_edges = compute_cell_edges(&[vec![0, 1, 2, 3, 4, 5]], &face_edges);
+        assert_eq!(cell_edges[0], (0..12).collect::<Vec<_>>());
+        let point_edges = compute_point_edges(&edges, 8);
+        assert!(point_edges.iter().all(|pe| pe.len() == 3));
+    }
 }
@@ -0,0 +1,314 @@
+use dugong_types::tensor::Vector;
+
+use crate::decompose::Decomposition;
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::patch::PatchKind;
+
+/// Where the values of a [`GhostCell`] come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GhostSource {
+    /// A local cell seen across a cyclic patch.
+    Cyclic { cell: usize },
+    /// A cell of another subdomain seen across a processor patch, as
+    /// `(rank, local cell on that rank)`.
+    Processor { rank: usize, cell: usize },
+    /// The mirror image of a local cell in the plane of a boundary face, for
+    /// boundary extrapolation.
+    Mirror { cell: usize },
+}
+
+/// A cell outside the local mesh, next to one of its boundary faces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GhostCell {
+    /// Patch through which the ghost is reached.
+    pub patch: usize,
+    /// Boundary face of that patch from which the ghost's layer grows.
+    pub face: usize,
+    /// Layer number, starting at 1 for the cells touching the boundary.
+    pub layer: usize,
+    /// Source of the ghost's values.
+    pub source: GhostSource,
+    /// Cell center in the local frame: transformed across cyclic patches,
+    /// reflected for mirror ghosts.
+    pub center: Vector,
+    /// Cell volume.
+    pub volume: f64,
+}
+
+/// The ghost cells of a mesh, ordered by patch, then layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GhostCells {
+    cells: Vec<GhostCell>,
+}
+
+impl GhostCells {
+    /// Returns the ghost cells.
+    pub fn cells(&self) -> &[GhostCell] {
+        &self.cells
+    }
+
+    /// Returns the number of ghost cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns `true` if there are no ghost cells.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Collects the values of the ghost cells from per-cell values of the
+    /// local mesh and, for processor ghosts, of each rank.
+    ///
+    /// Vector values are not transformed across rotational cyclics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a processor ghost refers to a rank missing from `remote`.
+    pub fn gather<T: Clone>(&self, local: &[T], remote: &[Vec<T>]) -> Vec<T> {
+        self.cells
+            .iter()
+            .map(|g| match g.source {
+                GhostSource::Cyclic { cell } | GhostSource::Mirror { cell } => local[cell].clone(),
+                GhostSource::Processor { rank, cell } => remote[rank][cell].clone(),
+            })
+            .collect()
+    }
+}
+
+/// Grows `layers` layers of cells from `seeds` (`(cell, face)` pairs) across
+/// the cell graph, skipping `excluded` cells, and returns `(cell, face,
+/// layer)` with each cell reached once through the first face to reach it.
+fn grow(
+    cell_cells: &[Vec<usize>],
+    seeds: Vec<(usize, usize)>,
+    layers: usize,
+    excluded: impl Fn(usize) -> bool,
+) -> Vec<(usize, usize, usize)> {
+    let mut visited = vec![false; cell_cells.len()];
+    let mut out = Vec::new();
+    let mut front = Vec::new();
+    for (c, f) in seeds {
+        if !visited[c] && !excluded(c) {
+            visited[c] = true;
+            front.push((c, f));
+        }
+    }
+    for layer in 1..=layers {
+        let mut next = Vec::new();
+        for &(c, f) in &front {
+            out.push((c, f, layer));
+            for &n in &cell_cells[c] {
+                if !visited[n] && !excluded(n) {
+                    visited[n] = true;
+                    next.push((n, f));
+                }
+            }
+        }
+        front = next;
+    }
+    out
+}
+
+impl Mesh {
+    /// Builds `layers` layers of ghost cells across the boundary patches.
+    ///
+    /// Across a cyclic patch, the ghosts are the cells next to the neighbor
+    /// patch, grown inward layer by layer and transformed into this patch's
+    /// frame. Across wall, symmetry and generic patches, they are the cells
+    /// next to the patch reflected in the plane of the boundary face each
+    /// layer grows from. Empty, wedge, cyclic AMI and processor patches get
+    /// no ghosts here; see [`Decomposition::ghost_cells`] for processor
+    /// patches.
+    pub fn ghost_cells(&self, layers: usize) -> GhostCells {
+        let (owner, centers, volumes) = (self.owner(), self.cell_centers(), self.cell_volumes());
+        let mut cells = Vec::new();
+        for (p, patch) in self.patches().iter().enumerate() {
+            match patch.kind() {
+                PatchKind::Cyclic { .. } => {
+                    // Safety: the patch is cyclic and its neighbor was
+                    // validated by `Mesh::new`.
+                    let (neighbor, transform) = self.cyclic_neighbor(p).unwrap();
+                    let seeds = patch
+                        .range()
+                        .zip(self.patches()[neighbor].range())
+                        .map(|(f, g)| (owner[g], f))
+                        .collect();
+                    for (cell, face, layer) in grow(self.cell_cells(), seeds, layers, |_| false) {
+                        cells.push(GhostCell {
+                            patch: p,
+                            face,
+                            layer,
+                            source: GhostSource::Cyclic { cell },
+                            center: transform.inv_transform_point(centers[cell]),
+                            volume: volumes[cell],
+                        });
+                    }
+                }
+                PatchKind::Patch
+                | PatchKind::Wall
+                | PatchKind::SymmetryPlane
+                | PatchKind::Symmetry => {
+                    let seeds = patch.range().map(|f| (owner[f], f)).collect();
+                    for (cell, face, layer) in grow(self.cell_cells(), seeds, layers, |_| false) {
+                        let normal = self.face_areas()[face] / self.face_areas()[face].mag();
+                        let offset = (self.face_centers()[face] - centers[cell]) * normal;
+                        cells.push(GhostCell {
+                            patch: p,
+                            face,
+                            layer,
+                            source: GhostSource::Mirror { cell },
+                            center: centers[cell] + normal * (2.0 * offset),
+                            volume: volumes[cell],
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        GhostCells { cells }
+    }
+}
+
+impl Decomposition {
+    /// Builds `layers` layers of ghost cells for every subdomain, indexed by
+    /// rank.
+    ///
+    /// Each subdomain gets the ghosts of [`Mesh::ghost_cells`], followed by
+    /// the cells of other subdomains within `layers` cells of each processor
+    /// patch, in patch order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the undecomposed mesh cannot be reconstructed; see
+    /// [`Decomposition::reconstruct`].
+    pub fn ghost_cells(&self, layers: usize) -> Result<Vec<GhostCells>, MeshError> {
+        let global = self.reconstruct()?;
+        let cell_rank = self.cell_rank();
+        let ghosts = self
+            .parts()
+            .iter()
+            .map(|part| {
+                let mesh = part.mesh();
+                let mut ghosts = mesh.ghost_cells(layers);
+                for (p, patch) in mesh.patches().iter().enumerate() {
+                    if !matches!(patch.kind(), PatchKind::Processor { .. }) {
+                        continue;
+                    }
+                    let seeds = patch
+                        .range()
+                        .map(|f| {
+                            let g = part.face_map()[f];
+                            let o = global.owner()[g];
+                            let across = if cell_rank[o] == part.rank() {
+                                global.neighbor()[g]
+                            } else {
+                                o
+                            };
+                            (across, f)
+                        })
+                        .collect();
+                    let own = |c: usize| cell_rank[c] == part.rank();
+                    for (cell, face, layer) in grow(global.cell_cells(), seeds, layers, own) {
+                        let (rank, local) = self.locate_cell(cell);
+                        ghosts.cells.push(GhostCell {
+                            patch: p,
+                            face,
+                            layer,
+                            source: GhostSource::Processor { rank, cell: local },
+                            center: global.cell_centers()[cell],
+                            volume: global.cell_volumes()[cell],
+                        });
+                    }
+                }
+                ghosts
+            })
+            .collect();
+        Ok(ghosts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decompose::DecompositionMethod;
+    use crate::patch::{CoupledTransform, Patch};
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_ghost_cells_mirror_and_cyclic() {
+        let mut mesh = box_mesh([4, 1, 1], [4.0, 1.0, 1.0]);
+        for (i, (neighbor, s)) in [("x-max", 4.0), ("x-min", -4.0)].into_iter().enumerate() {
+            let p = &mesh.patches[i];
+            let kind = PatchKind::Cyclic {
+                neighbor_patch: neighbor.to_string(),
+                transform: CoupledTransform::Translational {
+                    separation: Vector::new(s, 0.0, 0.0),
+                },
+            };
+            mesh.patches[i] = Patch::new(p.name(), kind, p.start(), p.size());
+        }
+        let ghosts = mesh.ghost_cells(2);
+        let x_min = mesh.patch_index("x-min").unwrap();
+        let periodic: Vec<&GhostCell> =
+            ghosts.cells().iter().filter(|g| g.patch == x_min).collect();
+        assert_eq!(periodic.len(), 2);
+        // Across x-min lie cells 3 and 2, shifted to x = -0.5 and -1.5.
+        assert_eq!(periodic[0].source, GhostSource::Cyclic { cell: 3 });
+        assert_eq!(periodic[1].source, GhostSource::Cyclic { cell: 2 });
+        assert_eq!(periodic[1].layer, 2);
+        assert!((periodic[0].center - Vector::new(-0.5, 0.5, 0.5)).mag() < 1e-12);
+        assert!((periodic[1].center - Vector::new(-1.5, 0.5, 0.5)).mag() < 1e-12);
+
+        // Two layers mirrored across each side face.
+        let y_min = mesh.patch_index("y-min").unwrap();
+        let mirrored: Vec<&GhostCell> =
+            ghosts.cells().iter().filter(|g| g.patch == y_min).collect();
+        assert_eq!(mirrored.len(), 4);
+        for g in mirrored {
+            assert_eq!(g.layer, 1);
+            assert!((g.center.y() + 0.5).abs() < 1e-12);
+        }
+
+        let values: Vec<f64> = (0..4).map(|c| c as f64).collect();
+        let gathered = ghosts.gather(&values, &[]);
+        assert_eq!(gathered.len(), ghosts.len());
+    }
+
+    #[test]
+    fn test_decomposition_ghost_cells_cross_processor_patches() {
+        let mesh = box_mesh([6, 1, 1], [6.0, 1.0, 1.0]);
+        let decomp = mesh
+            .decompose(2, DecompositionMethod::Hierarchical)
+            .unwrap();
+        let ghosts = decomp.ghost_cells(2).unwrap();
+        let values: Vec<Vec<usize>> = decomp
+            .parts()
+            .iter()
+            .map(|p| p.cell_map().to_vec())
+            .collect();
+        for part in decomp.parts() {
+            let ghosts = &ghosts[part.rank()];
+            let remote: Vec<&GhostCell> = ghosts
+                .cells()
+                .iter()
+                .filter(|g| matches!(g.source, GhostSource::Processor { .. }))
+                .collect();
+            assert_eq!(remote.len(), 2);
+            let gathered = ghosts.gather(&values[part.rank()], &values);
+            let global: Vec<usize> = ghosts
+                .cells()
+                .iter()
+                .zip(&gathered)
+                .filter(|(g, _)| matches!(g.source, GhostSource::Processor { .. }))
+                .map(|(_, &c)| c)
+                .collect();
+            let expected = if part.rank() == 0 { [3, 4] } else { [2, 1] };
+            assert_eq!(global, expected);
+            for (g, &c) in remote.iter().zip(&global) {
+                assert!((g.center - mesh.cell_centers()[c]).mag() < 1e-12);
+            }
+        }
+    }
+}
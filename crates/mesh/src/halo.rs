@@ -0,0 +1,122 @@
+use std::ops::Range;
+
+use crate::mesh::Mesh;
+use crate::patch::PatchKind;
+
+/// Send/receive addressing for one processor patch.
+///
+/// Values are exchanged face by face in patch order. The sender packs the
+/// values of [`HaloLink::send_cells`] (the cells owning the patch faces); the
+/// receiver stores the incoming values at the faces [`HaloLink::recv_faces`]
+/// of its matching patch, which lists the shared faces in the same order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HaloLink {
+    patch: usize,
+    neighbor_rank: usize,
+    send_cells: Vec<usize>,
+    recv_faces: Range<usize>,
+}
+
+impl HaloLink {
+    /// Returns the index of the processor patch in the mesh patch list.
+    pub fn patch(&self) -> usize {
+        self.patch
+    }
+
+    /// Returns the rank on the other side of the patch.
+    pub fn neighbor_rank(&self) -> usize {
+        self.neighbor_rank
+    }
+
+    /// Returns the local cells whose values are sent, one per patch face.
+    pub fn send_cells(&self) -> &[usize] {
+        &self.send_cells
+    }
+
+    /// Returns the mesh faces at which received values are stored.
+    pub fn recv_faces(&self) -> Range<usize> {
+        self.recv_faces.clone()
+    }
+
+    /// Returns the number of values exchanged in each direction.
+    pub fn len(&self) -> usize {
+        self.send_cells.len()
+    }
+
+    /// Returns `true` if nothing is exchanged over this link.
+    pub fn is_empty(&self) -> bool {
+        self.send_cells.is_empty()
+    }
+
+    /// Packs the send buffer from per-cell values.
+    pub fn gather<T: Clone>(&self, cell_values: &[T]) -> Vec<T> {
+        self.send_cells
+            .iter()
+            .map(|&c| cell_values[c].clone())
+            .collect()
+    }
+}
+
+impl Mesh {
+    /// Builds the send/receive lists of all processor patches, in patch order.
+    pub fn halo_links(&self) -> Vec<HaloLink> {
+        self.patches()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| match p.kind() {
+                PatchKind::Processor { neighbor_rank, .. } => Some(HaloLink {
+                    patch: i,
+                    neighbor_rank: *neighbor_rank,
+                    send_cells: self.owner()[p.range()].to_vec(),
+                    recv_faces: p.range(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decompose::DecompositionMethod;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_halo_links_deliver_neighbor_cell_values() {
+        let mesh = box_mesh([5, 4, 2], [1.0, 1.0, 1.0]);
+        let decomp = mesh
+            .decompose(4, DecompositionMethod::Hierarchical)
+            .unwrap();
+        let parts = decomp.parts();
+        for sender in parts {
+            for link in sender.mesh().halo_links() {
+                // Global cell ids stand in for field values.
+                let buffer = link.gather(sender.cell_map());
+                let receiver = &parts[link.neighbor_rank()];
+                let recv = receiver
+                    .mesh()
+                    .halo_links()
+                    .into_iter()
+                    .find(|l| l.neighbor_rank() == sender.rank())
+                    .unwrap();
+                assert_eq!(recv.len(), buffer.len());
+                for (face, &value) in recv.recv_faces().zip(&buffer) {
+                    let g = receiver.face_map()[face];
+                    let (o, n) = (mesh.owner()[g], mesh.neighbor()[g]);
+                    let across = if decomp.cell_rank()[o] == sender.rank() {
+                        o
+                    } else {
+                        n
+                    };
+                    assert_eq!(value, across);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_halo_links_empty_for_serial_mesh() {
+        let mesh = box_mesh([2, 2, 2], [1.0, 1.0, 1.0]);
+        assert!(mesh.halo_links().is_empty());
+    }
+}
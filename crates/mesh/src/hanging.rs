@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+
+use dugong_types::tensor::Vector;
+
+use crate::assemble::{BoundaryGroup, InternalFace, assemble};
+use crate::bvh::{Aabb, Bvh};
+use crate::error::MeshError;
+use crate::merge::{invert, merge_zones};
+use crate::mesh::Mesh;
+
+/// Relative tolerance on the area of a coarse face covered by fine faces.
+const COVER_TOL: f64 = 1e-6;
+
+impl Mesh {
+    /// Joins the faces of `fine_patch` to the larger faces of `coarse_patch`
+    /// they tile, turning a hanging-node interface into internal faces.
+    ///
+    /// Each fine face is paired with the coarse face it lies on: facing it
+    /// within `tol` of its plane with every vertex inside it. The fine faces
+    /// of each paired coarse face must cover it exactly; they become internal
+    /// faces from the fine cells to the coarse cell, which thereby becomes a
+    /// polyhedron, and the coarse face is removed. Fine points within `tol`
+    /// of a coarse face vertex are merged into it, and the remaining fine
+    /// points on the coarse face edges are inserted into the vertex lists of
+    /// every face sharing those edges, so the mesh stays face-addressed and
+    /// edge-conforming. Unpaired faces stay in their patches. Face zones keep
+    /// the fine faces but lose the removed coarse faces.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::PatchNotFound`] for unknown patch names, and
+    /// [`MeshError::InvalidMerge`] if the two names are equal, a paired
+    /// coarse face is not fully covered, or both faces of a pair belong to
+    /// the same cell.
+    pub fn stitch_hanging(
+        &self,
+        coarse_patch: &str,
+        fine_patch: &str,
+        tol: f64,
+    ) -> Result<Mesh, MeshError> {
+        let find = |name: &str| {
+            self.patch(name).ok_or_else(|| MeshError::PatchNotFound {
+                name: name.to_string(),
+            })
+        };
+        let (a, b) = (find(coarse_patch)?, find(fine_patch)?);
+        if coarse_patch == fine_patch {
+            return Err(MeshError::InvalidMerge {
+                reason: format!("cannot stitch patch {coarse_patch} to itself"),
+            });
+        }
+        let invalid = |reason: String| MeshError::InvalidMerge { reason };
+        let (points, faces) = (self.points(), self.faces());
+        let (centers, areas) = (self.face_centers(), self.face_areas());
+        let boxes: Vec<Aabb> = a
+            .range()
+            .map(|f| Aabb::of_points(faces[f].iter().map(|&p| points[p])))
+            .collect();
+        let bvh = Bvh::new(&boxes);
+
+        // Whether `x` lies within `tol` of the plane and inside the polygon
+        // of coarse face `fa`.
+        let on_face = |fa: usize, x: Vector| {
+            let normal = areas[fa] / areas[fa].mag();
+            let face = &faces[fa];
+            ((x - centers[fa]) * normal).abs() <= tol
+                && (0..face.len()).all(|i| {
+                    let (p, q) = (points[face[i]], points[face[(i + 1) % face.len()]]);
+                    (q - p).cross(&(x - p)) * normal >= -tol * (q - p).mag()
+                })
+        };
+
+        // coarse[fb] is the coarse face under fine face fb.
+        let mut coarse = vec![None; self.n_faces()];
+        let mut covered = vec![0.0; self.n_faces()];
+        for fb in b.range() {
+            let mut found = None;
+            bvh.visit(
+                |bb| bb.distance(centers[fb]) <= tol,
+                |i| {
+                    let fa = a.start() + i;
+                    if found.is_none()
+                        && areas[fa] * areas[fb] < 0.0
+                        && faces[fb].iter().all(|&v| on_face(fa, points[v]))
+                    {
+                        found = Some(fa);
+                    }
+                },
+            );
+            if let Some(fa) = found {
+                if self.owner()[fa] == self.owner()[fb] {
+                    return Err(invalid(format!(
+                        "faces {fa} and {fb} belong to the same cell"
+                    )));
+                }
+                coarse[fb] = Some(fa);
+                covered[fa] += areas[fb].mag();
+            }
+        }
+        for fa in a.range().filter(|&fa| covered[fa] > 0.0) {
+            if (covered[fa] - areas[fa].mag()).abs() > COVER_TOL * areas[fa].mag() {
+                return Err(invalid(format!(
+                    "fine faces do not cover face {fa} of {coarse_patch}"
+                )));
+            }
+        }
+
+        // Merge fine points onto coarse vertices; the rest on coarse edges
+        // hang there.
+        let mut point_map: Vec<usize> = (0..self.n_points()).collect();
+        let mut hanging: HashMap<[usize; 2], Vec<(f64, usize)>> = HashMap::new();
+        let mut seen = vec![false; self.n_points()];
+        for fb in b.range() {
+            let Some(fa) = coarse[fb] else { continue };
+            let face_a = &faces[fa];
+            for &v in &faces[fb] {
+                if let Some(&u) = face_a
+                    .iter()
+                    .find(|&&u| (points[u] - points[v]).mag() <= tol)
+                {
+                    point_map[v] = u;
+                    continue;
+                }
+                if std::mem::replace(&mut seen[v], true) {
+                    continue;
+                }
+                for i in 0..face_a.len() {
+                    let (p, q) = (face_a[i], face_a[(i + 1) % face_a.len()]);
+                    let (lo, hi) = (p.min(q), p.max(q));
+                    let edge = points[hi] - points[lo];
+                    let t = ((points[v] - points[lo]) * edge) / (edge * edge);
+                    let off = points[v] - (points[lo] + edge * t);
+                    if t > 0.0 && t < 1.0 && off.mag() <= tol {
+                        hanging.entry([lo, hi]).or_default().push((t, v));
+                    }
+                }
+            }
+        }
+        for list in hanging.values_mut() {
+            list.sort_by(|x, y| x.0.total_cmp(&y.0));
+            list.dedup_by_key(|x| x.1);
+        }
+
+        // Compact the points to those still referenced.
+        let mut used = vec![false; self.n_points()];
+        for face in faces {
+            for &p in face {
+                used[point_map[p]] = true;
+            }
+        }
+        let mut compact = vec![usize::MAX; self.n_points()];
+        let mut new_points = Vec::new();
+        for (p, _) in used.iter().enumerate().filter(|&(_, &u)| u) {
+            compact[p] = new_points.len();
+            new_points.push(points[p]);
+        }
+        let new_point = |p: usize| compact[point_map[p]];
+        let remap = |f: usize| -> Vec<usize> {
+            let face = &faces[f];
+            let mut out = Vec::with_capacity(face.len());
+            for i in 0..face.len() {
+                let (p, q) = (point_map[face[i]], point_map[face[(i + 1) % face.len()]]);
+                out.push(compact[p]);
+                if let Some(list) = hanging.get(&[p.min(q), p.max(q)]) {
+                    let inserted = list.iter().map(|&(_, v)| compact[v]);
+                    if p < q {
+                        out.extend(inserted);
+                    } else {
+                        out.extend(inserted.rev());
+                    }
+                }
+            }
+            out
+        };
+
+        let mut sources = Vec::with_capacity(self.n_faces());
+        let mut internal: Vec<InternalFace> = (0..self.n_internal_faces())
+            .map(|f| (remap(f), self.owner()[f], self.neighbor()[f]))
+            .collect();
+        sources.extend(0..self.n_internal_faces());
+        for fb in b.range() {
+            if let Some(fa) = coarse[fb] {
+                internal.push((remap(fb), self.owner()[fb], self.owner()[fa]));
+                sources.push(fb);
+            }
+        }
+        let groups = self
+            .patches()
+            .iter()
+            .map(|patch| {
+                let kept: Vec<usize> = patch
+                    .range()
+                    .filter(|&f| coarse[f].is_none() && covered[f] == 0.0)
+                    .collect();
+                sources.extend(&kept);
+                BoundaryGroup {
+                    name: patch.name().to_string(),
+                    kind: patch.kind().clone(),
+                    faces: kept.iter().map(|&f| (remap(f), self.owner()[f])).collect(),
+                }
+            })
+            .collect();
+
+        let (mut mesh, face_map) = assemble(new_points, internal, groups)?;
+        let new_face = invert(&face_map);
+        let mut face_of = vec![usize::MAX; self.n_faces()];
+        for (input, &f) in sources.iter().enumerate() {
+            face_of[f] = new_face[input];
+        }
+        mesh.cell_zones = self.cell_zones().to_vec();
+        mesh.face_zones = merge_zones(self.face_zones(), &[], |f| face_of[f], |f| f);
+        mesh.point_zones = merge_zones(self.point_zones(), &[], new_point, |p| p);
+        Ok(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patch::Patch;
+    use crate::refine::tests::assert_closed;
+    use crate::test_meshes::box_mesh;
+
+    /// Returns a box of `n` cells over `[1, 2] x [0, 1] x [0, l]` with its
+    /// patch names prefixed by `fine-`.
+    fn fine_block(n: [usize; 3], l: f64) -> Mesh {
+        let mut fine = box_mesh(n, [1.0, 1.0, l]);
+        fine.translate(Vector::new(1.0, 0.0, 0.0));
+        for patch in &mut fine.patches {
+            let name = format!("fine-{}", patch.name());
+            *patch = Patch::new(name, patch.kind().clone(), patch.start(), patch.size());
+        }
+        fine
+    }
+
+    /// A unit cube next to a 2x2x2 block of half-size cubes, merged along
+    /// the plane x = 1 without stitching.
+    fn coarse_and_fine() -> Mesh {
+        let coarse = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        coarse.merge(&fine_block([2, 2, 2], 1.0), 1e-9).unwrap()
+    }
+
+    #[test]
+    fn test_stitch_hanging_builds_polyhedral_coarse_cell() {
+        let mesh = coarse_and_fine()
+            .stitch_hanging("x-max", "fine-x-min", 1e-9)
+            .unwrap();
+        assert_closed(&mesh);
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.n_cells(), 9);
+        assert_eq!(mesh.n_internal_faces(), 12 + 4);
+        assert_eq!(mesh.patch("x-max").unwrap().size(), 0);
+        assert_eq!(mesh.patch("fine-x-min").unwrap().size(), 0);
+        // 8 + 27 points, of which the 4 coarse corners were merged.
+        assert_eq!(mesh.n_points(), 31);
+        assert_eq!(mesh.cell_cells()[0].len(), 4);
+
+        // The coarse cell's four side faces gained the hanging edge
+        // midpoints, so every edge of every cell is shared by two of its
+        // faces.
+        let sides = mesh.cell_faces()[0]
+            .iter()
+            .filter(|&&f| mesh.faces()[f].len() == 5)
+            .count();
+        assert_eq!(sides, 4);
+        for faces in mesh.cell_faces() {
+            let mut count: HashMap<usize, usize> = HashMap::new();
+            for &f in faces {
+                for &e in &mesh.face_edges()[f] {
+                    *count.entry(e).or_default() += 1;
+                }
+            }
+            assert!(count.values().all(|&n| n == 2));
+        }
+    }
+
+    #[test]
+    fn test_stitch_hanging_rejects_partial_cover() {
+        // The fine block only covers the lower half of the coarse face.
+        let coarse = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        let mesh = coarse.merge(&fine_block([2, 2, 1], 0.5), 1e-9).unwrap();
+        assert!(matches!(
+            mesh.stitch_hanging("x-max", "fine-x-min", 1e-9),
+            Err(MeshError::InvalidMerge { .. })
+        ));
+        assert!(matches!(
+            mesh.stitch_hanging("x-max", "x-max", 1e-9),
+            Err(MeshError::InvalidMerge { .. })
+        ));
+        assert!(matches!(
+            mesh.stitch_hanging("x-max", "inlet", 1e-9),
+            Err(MeshError::PatchNotFound { .. })
+        ));
+    }
+}
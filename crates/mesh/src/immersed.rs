@@ -0,0 +1,212 @@
+use dugong_types::tensor::Vector;
+
+use crate::mesh::{Mesh, ZoneKind};
+use crate::tri_surface::TriSurface;
+use crate::zone::Zone;
+
+/// Cell zone names written by [`Mesh::classify_immersed`], indexed by
+/// [`CellClass`] discriminant.
+const ZONE_NAMES: [&str; 3] = ["ibFluid", "ibSolid", "ibCut"];
+
+/// Where a cell lies relative to an immersed surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellClass {
+    /// All vertices outside (or on) the surface.
+    Fluid = 0,
+    /// All vertices inside (or on) the surface.
+    Solid = 1,
+    /// Vertices on both sides: the surface crosses the cell.
+    Cut = 2,
+}
+
+impl CellClass {
+    /// Returns the name of the cell zone holding cells of this class.
+    pub fn zone_name(self) -> &'static str {
+        ZONE_NAMES[self as usize]
+    }
+}
+
+/// The classification of a mesh against an immersed surface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImmersedBoundary {
+    point_distances: Vec<f64>,
+    cell_classes: Vec<CellClass>,
+    face_fluid_fractions: Vec<f64>,
+}
+
+impl ImmersedBoundary {
+    /// Returns the signed distance from each mesh point to the surface,
+    /// negative inside.
+    pub fn point_distances(&self) -> &[f64] {
+        &self.point_distances
+    }
+
+    /// Returns the class of each cell.
+    pub fn cell_classes(&self) -> &[CellClass] {
+        &self.cell_classes
+    }
+
+    /// Returns the fraction of each face's area lying outside the surface,
+    /// in `[0, 1]`.
+    pub fn face_fluid_fractions(&self) -> &[f64] {
+        &self.face_fluid_fractions
+    }
+}
+
+impl Mesh {
+    /// Classifies cells as fluid, solid or cut by the immersed `surface`,
+    /// computes face fluid fractions, and stores the classes as the cell
+    /// zones `ibFluid`, `ibSolid` and `ibCut` (replacing earlier ones).
+    ///
+    /// Points are classified by the surface winding number and measured by
+    /// the distance to the nearest triangle. Face fractions treat the signed
+    /// distance as linear on each fan triangle of the face, which is exact
+    /// for planar cuts. Features thinner than a cell that pass between the
+    /// vertices are not detected.
+    pub fn classify_immersed(&mut self, surface: &TriSurface) -> ImmersedBoundary {
+        let signed_distance = |p: Vector| {
+            let distance = surface
+                .nearest(p)
+                .map_or(f64::INFINITY, |(_, q)| (q - p).mag());
+            if surface.is_inside(p) {
+                -distance
+            } else {
+                distance
+            }
+        };
+        let point_distances: Vec<f64> = self.points().iter().map(|&p| signed_distance(p)).collect();
+
+        let cell_classes: Vec<CellClass> = self
+            .cell_points()
+            .iter()
+            .map(|cp| {
+                let inside = cp.iter().any(|&p| point_distances[p] < 0.0);
+                let outside = cp.iter().any(|&p| point_distances[p] > 0.0);
+                match (inside, outside) {
+                    (true, true) => CellClass::Cut,
+                    (true, false) => CellClass::Solid,
+                    _ => CellClass::Fluid,
+                }
+            })
+            .collect();
+
+        let face_fluid_fractions = (0..self.n_faces())
+            .map(|f| {
+                let face = &self.faces()[f];
+                let d: Vec<f64> = face.iter().map(|&p| point_distances[p]).collect();
+                if d.iter().all(|&d| d >= 0.0) {
+                    return 1.0;
+                }
+                if d.iter().all(|&d| d <= 0.0) {
+                    return 0.0;
+                }
+                let center = self.face_centers()[f];
+                let dc = signed_distance(center);
+                let n = face.len();
+                let (mut fluid, mut total) = (0.0, 0.0);
+                for i in 0..n {
+                    let (a, b) = (self.points()[face[i]], self.points()[face[(i + 1) % n]]);
+                    let area = (a - center).cross(&(b - center)).mag();
+                    fluid += area * positive_fraction([dc, d[i], d[(i + 1) % n]]);
+                    total += area;
+                }
+                if total > 0.0 { fluid / total } else { 1.0 }
+            })
+            .collect();
+
+        for (class, name) in ZONE_NAMES.iter().enumerate() {
+            self.remove_zone(ZoneKind::Cell, name);
+            let members = (0..self.n_cells()).filter(|&c| cell_classes[c] as usize == class);
+            // Safety: the name was just removed and the members are cell
+            // indices, so the zone is valid.
+            self.add_zone(ZoneKind::Cell, Zone::new(*name, members))
+                .unwrap();
+        }
+
+        ImmersedBoundary {
+            point_distances,
+            cell_classes,
+            face_fluid_fractions,
+        }
+    }
+}
+
+/// Returns the fraction of a triangle's area where a linear function with
+/// corner values `d` is non-negative.
+fn positive_fraction(d: [f64; 3]) -> f64 {
+    let n_positive = d.iter().filter(|&&x| x >= 0.0).count();
+    match n_positive {
+        0 => 0.0,
+        3 => 1.0,
+        _ => {
+            // The corner whose sign differs from the other two cuts off a
+            // similar triangle of relative area d_i^2 / ((d_i - d_j)(d_i - d_k)).
+            let lonely_positive = n_positive == 1;
+            // Safety: exactly one corner has the minority sign.
+            let i = (0..3).find(|&i| (d[i] >= 0.0) == lonely_positive).unwrap();
+            let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+            let corner = d[i] * d[i] / ((d[i] - d[j]) * (d[i] - d[k]));
+            if lonely_positive {
+                corner
+            } else {
+                1.0 - corner
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+    use crate::tri_surface::tests::unit_cube;
+
+    #[test]
+    fn test_positive_fraction_linear_cuts() {
+        assert_eq!(positive_fraction([1.0, 2.0, 3.0]), 1.0);
+        assert_eq!(positive_fraction([-1.0, -2.0, -3.0]), 0.0);
+        // Zero crossing at the midpoints of the edges from corner 0.
+        assert!((positive_fraction([1.0, -1.0, -1.0]) - 0.25).abs() < 1e-12);
+        assert!((positive_fraction([-1.0, 1.0, 1.0]) - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_classify_immersed_cube_in_box() {
+        // Cells of size 0.5 over [-0.75, 1.75]^3; the unit cube covers
+        // whole cells in the middle and cuts the layer around them.
+        let mut mesh = box_mesh([5, 5, 5], [2.5, 2.5, 2.5]);
+        mesh.translate(Vector::new(-0.75, -0.75, -0.75));
+        let ib = mesh.classify_immersed(&unit_cube());
+        let class_at = |i: usize, j: usize, k: usize| ib.cell_classes()[i + 5 * (j + 5 * k)];
+        assert_eq!(class_at(2, 2, 2), CellClass::Solid);
+        assert_eq!(class_at(1, 2, 2), CellClass::Cut);
+        assert_eq!(class_at(0, 2, 2), CellClass::Fluid);
+        assert_eq!(mesh.cell_zone("ibSolid").unwrap().indices(), &[62]);
+        assert_eq!(mesh.cell_zone("ibCut").unwrap().len(), 26);
+        assert_eq!(mesh.cell_zone("ibFluid").unwrap().len(), 125 - 27);
+
+        // Reclassifying replaces the zones instead of failing.
+        mesh.classify_immersed(&unit_cube());
+        assert_eq!(mesh.cell_zones().len(), 3);
+    }
+
+    #[test]
+    fn test_classify_immersed_face_fractions() {
+        let mut mesh = box_mesh([5, 5, 5], [2.5, 2.5, 2.5]);
+        mesh.translate(Vector::new(-0.75, -0.75, -0.75));
+        let ib = mesh.classify_immersed(&unit_cube());
+        for f in 0..mesh.n_faces() {
+            let c = mesh.face_centers()[f];
+            let fraction = ib.face_fluid_fractions()[f];
+            assert!((0.0..=1.0).contains(&fraction));
+            // A face normal to x spanning y in [-0.25, 0.25] at x = 0.25 is
+            // half inside the cube.
+            if (c.x() - 0.25).abs() < 1e-9 && c.y().abs() < 1e-9 && (c.z() - 0.5).abs() < 1e-9 {
+                assert!((fraction - 0.5).abs() < 1e-9, "fraction {fraction}");
+            }
+            if (c - Vector::new(0.5, 0.5, 0.5)).mag() < 0.3 {
+                assert_eq!(fraction, 0.0);
+            }
+        }
+    }
+}
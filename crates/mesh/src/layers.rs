@@ -0,0 +1,332 @@
+use std::collections::{HashMap, HashSet};
+
+use dugong_types::tensor::Vector;
+
+use crate::assemble::{BoundaryGroup, InternalFace, assemble};
+use crate::error::MeshError;
+use crate::extrude::layer_fractions;
+use crate::mesh::Mesh;
+use crate::zone::Zone;
+
+/// An edge as its sorted point pair.
+type Edge = (usize, usize);
+
+/// Settings for [`Mesh::add_layers`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayerSettings {
+    /// Number of prism layers.
+    pub n_layers: usize,
+    /// Thickness ratio of each layer to the one below it (nearer the wall).
+    pub expansion_ratio: f64,
+    /// Total thickness of the layers.
+    pub thickness: f64,
+    /// Smallest total thickness accepted where the adjacent cells leave
+    /// less room than [`LayerSettings::thickness`].
+    pub min_thickness: f64,
+}
+
+impl Mesh {
+    /// Inserts prism layers along the boundary patches named `patches`.
+    ///
+    /// Each wall point is pushed into the domain along its point normal
+    /// (the area-weighted mean of the adjacent wall face normals), shrinking
+    /// the cells next to the wall, and the gap is filled with
+    /// `n_layers` cells on every wall face. The total thickness at a point
+    /// is [`LayerSettings::thickness`], limited to half the shortest edge
+    /// leaving it into the domain. Layer cells are numbered after the
+    /// existing cells, outermost layer first; existing cells, points and
+    /// zones keep their indices. Side faces of the layers that reach another
+    /// boundary join the patch of the boundary face there.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::PatchNotFound`] for unknown patch names, and
+    /// [`MeshError::InvalidMeshing`] if the settings are invalid, a wall
+    /// edge is shared by more than two wall faces or borders no other
+    /// boundary, or the room at a point is below
+    /// [`LayerSettings::min_thickness`].
+    pub fn add_layers(
+        &self,
+        patches: &[&str],
+        settings: &LayerSettings,
+    ) -> Result<Mesh, MeshError> {
+        let invalid = |reason: String| MeshError::InvalidMeshing { reason };
+        let LayerSettings {
+            n_layers,
+            expansion_ratio,
+            thickness,
+            min_thickness,
+        } = *settings;
+        if n_layers == 0 || !(expansion_ratio > 0.0 && thickness > 0.0) {
+            return Err(invalid(format!("invalid layer settings {settings:?}")));
+        }
+        let mut wall_faces = Vec::new();
+        for &name in patches {
+            let patch = self.patch(name).ok_or_else(|| MeshError::PatchNotFound {
+                name: name.to_string(),
+            })?;
+            wall_faces.extend(patch.range());
+        }
+        wall_faces.sort_unstable();
+        wall_faces.dedup();
+        let is_wall = |f: usize| wall_faces.binary_search(&f).is_ok();
+
+        let edge_key = |a: usize, b: usize| (a.min(b), a.max(b));
+        let mut normals: HashMap<usize, Vector> = HashMap::new();
+        let mut wall_edges = HashSet::new();
+        for &f in &wall_faces {
+            let face = &self.faces()[f];
+            for (i, &p) in face.iter().enumerate() {
+                *normals.entry(p).or_insert_with(Vector::zero) += self.face_areas()[f];
+                wall_edges.insert(edge_key(p, face[(i + 1) % face.len()]));
+            }
+        }
+        let mut wall_points: Vec<usize> = normals.keys().copied().collect();
+        wall_points.sort_unstable();
+        let slot: HashMap<usize, usize> = wall_points
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (p, i))
+            .collect();
+
+        // Inward displacement of each wall point.
+        let mut too_thin = 0;
+        let mut displacement = Vec::with_capacity(wall_points.len());
+        for &p in &wall_points {
+            let room = self.point_edges()[p]
+                .iter()
+                .map(|&e| self.edges()[e])
+                .filter(|&[a, b]| !wall_edges.contains(&edge_key(a, b)))
+                .map(|[a, b]| 0.5 * (self.points()[a] - self.points()[b]).mag())
+                .fold(thickness, f64::min);
+            if room < min_thickness {
+                too_thin += 1;
+            }
+            let n = normals[&p];
+            displacement.push(-n / n.mag() * room);
+        }
+        if too_thin > 0 {
+            return Err(invalid(format!(
+                "{too_thin} wall points have less than {min_thickness} room for layers"
+            )));
+        }
+
+        // Points: the existing ones, with wall points moved to the top of
+        // the layers, followed by the wall points of layer levels
+        // 0..n_layers.
+        let fractions = layer_fractions(n_layers, expansion_ratio.powi(n_layers as i32 - 1));
+        let mut points = self.points().to_vec();
+        for (i, &p) in wall_points.iter().enumerate() {
+            points[p] += displacement[i];
+        }
+        for &s in &fractions[..n_layers] {
+            for (i, &p) in wall_points.iter().enumerate() {
+                points.push(self.points()[p] + displacement[i] * s);
+            }
+        }
+        let n_wall_points = wall_points.len();
+        let level = |p: usize, k: usize| {
+            if k == n_layers {
+                p
+            } else {
+                self.n_points() + k * n_wall_points + slot[&p]
+            }
+        };
+        let n_wall = wall_faces.len();
+        let layer_cell = |k: usize, i: usize| self.n_cells() + (n_layers - 1 - k) * n_wall + i;
+
+        let mut internal: Vec<InternalFace> = self
+            .neighbor()
+            .iter()
+            .enumerate()
+            .map(|(f, &n)| (self.faces()[f].clone(), self.owner()[f], n))
+            .collect();
+        let mut sources: Vec<usize> = (0..self.n_internal_faces()).collect();
+        let mut groups: Vec<BoundaryGroup> = self
+            .patches()
+            .iter()
+            .map(|p| BoundaryGroup {
+                name: p.name().to_string(),
+                kind: p.kind().clone(),
+                faces: Vec::new(),
+            })
+            .collect();
+        let mut group_sources = vec![Vec::new(); groups.len()];
+        let mut sides: HashMap<(Edge, usize), (Vec<usize>, usize)> = HashMap::new();
+        for (i, &f) in wall_faces.iter().enumerate() {
+            let face = &self.faces()[f];
+            let at = |k: usize| face.iter().map(|&p| level(p, k)).collect::<Vec<_>>();
+            // Safety: wall faces are boundary faces.
+            let patch = self.which_patch(f).unwrap();
+            groups[patch].faces.push((at(0), layer_cell(0, i)));
+            group_sources[patch].push(usize::MAX);
+            for k in 1..n_layers {
+                let mut between = at(k);
+                between.reverse();
+                internal.push((between, layer_cell(k - 1, i), layer_cell(k, i)));
+                sources.push(usize::MAX);
+            }
+            internal.push((face.clone(), self.owner()[f], layer_cell(n_layers - 1, i)));
+            sources.push(f);
+
+            for (j, &a) in face.iter().enumerate() {
+                let b = face[(j + 1) % face.len()];
+                for k in 0..n_layers {
+                    let quad = vec![level(a, k), level(a, k + 1), level(b, k + 1), level(b, k)];
+                    let cell = layer_cell(k, i);
+                    match sides.remove(&(edge_key(a, b), k)) {
+                        Some((first, other)) => {
+                            if other == usize::MAX {
+                                return Err(invalid(format!(
+                                    "wall edge ({a}, {b}) is shared by more than two wall faces"
+                                )));
+                            }
+                            internal.push((first, other, cell));
+                            sources.push(usize::MAX);
+                            sides.insert((edge_key(a, b), k), (Vec::new(), usize::MAX));
+                        }
+                        None => {
+                            sides.insert((edge_key(a, b), k), (quad, cell));
+                        }
+                    }
+                }
+            }
+        }
+
+        // Side faces on the edge of the wall region join the boundary face
+        // across that edge.
+        let mut edge_patch = HashMap::new();
+        for (p, patch) in self.patches().iter().enumerate() {
+            for f in patch.range().filter(|&f| !is_wall(f)) {
+                let face = &self.faces()[f];
+                for (j, &a) in face.iter().enumerate() {
+                    edge_patch.insert(edge_key(a, face[(j + 1) % face.len()]), p);
+                }
+            }
+        }
+        let mut open: Vec<_> = sides
+            .into_iter()
+            .filter(|(_, (_, cell))| *cell != usize::MAX)
+            .collect();
+        open.sort_unstable_by_key(|&((edge, k), (_, cell))| (cell, edge, k));
+        let mut side_faces = vec![Vec::new(); groups.len()];
+        for ((edge, _), (quad, cell)) in open {
+            let patch = *edge_patch.get(&edge).ok_or_else(|| {
+                invalid(format!("wall edge {edge:?} borders no other boundary face"))
+            })?;
+            side_faces[patch].push((quad, cell));
+        }
+        for (p, patch) in self.patches().iter().enumerate() {
+            let kept: Vec<usize> = patch.range().filter(|&f| !is_wall(f)).collect();
+            let mut faces: Vec<(Vec<usize>, usize)> = kept
+                .iter()
+                .map(|&f| (self.faces()[f].clone(), self.owner()[f]))
+                .collect();
+            faces.append(&mut groups[p].faces);
+            faces.append(&mut side_faces[p]);
+            let mut src = kept;
+            src.append(&mut group_sources[p]);
+            src.resize(faces.len(), usize::MAX);
+            groups[p].faces = faces;
+            sources.extend(src);
+        }
+
+        let (mut mesh, face_map) = assemble(points, internal, groups)?;
+        let mut new_face = vec![usize::MAX; self.n_faces()];
+        for (new, &input) in face_map.iter().enumerate() {
+            if sources[input] != usize::MAX {
+                new_face[sources[input]] = new;
+            }
+        }
+        mesh.cell_zones = self.cell_zones().to_vec();
+        mesh.point_zones = self.point_zones().to_vec();
+        mesh.face_zones = self
+            .face_zones()
+            .iter()
+            .map(|z| {
+                Zone::new(
+                    z.name(),
+                    z.indices().iter().map(|&f| new_face[f]).collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        Ok(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    fn settings(thickness: f64) -> LayerSettings {
+        LayerSettings {
+            n_layers: 3,
+            expansion_ratio: 1.2,
+            thickness,
+            min_thickness: 0.01,
+        }
+    }
+
+    #[test]
+    fn test_add_layers_single_wall() {
+        let mesh = box_mesh([3, 3, 3], [1.0, 1.0, 1.0]);
+        let layered = mesh.add_layers(&["y-min"], &settings(0.1)).unwrap();
+        assert_eq!(layered.n_cells(), 27 + 3 * 9);
+        assert!(layered.check().is_ok());
+        assert!(layered.cell_volumes().iter().all(|&v| v > 0.0));
+        assert!((layered.cell_volumes().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        // Patch sizes: the wall keeps its faces, sides gain one face per
+        // layer along each wall edge they share.
+        let size = |name: &str| layered.patch(name).unwrap().size();
+        assert_eq!(size("y-min"), 9);
+        assert_eq!(size("x-min"), 9 + 3 * 3);
+        assert_eq!(size("y-max"), 9);
+
+        // The outermost layer is numbered last and touches the wall with the
+        // first layer thickness.
+        let first = 0.1 / (1.0 + 1.2 + 1.44);
+        let last = layered.n_cells() - 1;
+        assert!((layered.cell_volumes()[last] - first / 9.0).abs() < 1e-12);
+        let wall = layered.patch("y-min").unwrap();
+        assert!(wall.range().all(|f| layered.owner()[f] >= 27 + 18));
+    }
+
+    #[test]
+    fn test_add_layers_corner_walls() {
+        let mut mesh = box_mesh([3, 3, 2], [1.0, 1.0, 1.0]);
+        mesh.add_face_zone(Zone::new(
+            "wall",
+            vec![mesh.patch("x-min").unwrap().start()],
+        ))
+        .unwrap();
+        let layered = mesh
+            .add_layers(&["x-min", "y-min"], &settings(0.1))
+            .unwrap();
+        assert_eq!(layered.n_cells(), 18 + 2 * 3 * 6);
+        assert!(layered.check().is_ok());
+        assert!(layered.cell_volumes().iter().all(|&v| v > 0.0));
+        assert!((layered.cell_volumes().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        // The wall face in the zone became the internal face on top of the
+        // layers.
+        let f = layered.face_zone("wall").unwrap().indices()[0];
+        assert!(f < layered.n_internal_faces());
+        assert_eq!(layered.owner()[f], 0);
+    }
+
+    #[test]
+    fn test_add_layers_rejects_too_thin_room() {
+        let mesh = box_mesh([3, 3, 3], [1.0, 1.0, 1.0]);
+        let mut s = settings(0.5);
+        s.min_thickness = 0.2;
+        assert!(matches!(
+            mesh.add_layers(&["y-min"], &s),
+            Err(MeshError::InvalidMeshing { .. })
+        ));
+        assert!(matches!(
+            mesh.add_layers(&["wall"], &settings(0.1)),
+            Err(MeshError::PatchNotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,151 @@
+use dugong_types::tensor::{Tensor, Vector};
+
+/// Relative determinant below which a normal matrix counts as singular.
+const SINGULAR_TOLERANCE: f64 = 1e-12;
+
+/// The weighted least-squares gradient vectors of each face.
+///
+/// For a cell `P` with face neighbors `N` at offsets `d = x_N − x_P` (to the
+/// face center on boundary faces), the gradient minimizing
+/// `Σ w |φ_N − φ_P − d · ∇φ|²` with weights `w = 1 / |d|²` is
+///
+/// ```text
+/// ∇φ_P = Σ_f w M_P⁻¹ d (φ_N − φ_P),    M_P = Σ_f w d ⊗ d
+/// ```
+///
+/// so each face carries a fixed vector for its owner, `w M_P⁻¹ d`, and one
+/// for its neighbor, `−w M_N⁻¹ d`: the face difference `φ_N − φ_P` times
+/// the owner vector is added to the owner's gradient, and times the
+/// neighbor vector subtracted from the neighbor's. The gradient is exact
+/// for linear fields whatever the skewness or stretching of the cells.
+///
+/// Faces of every patch take part, empty ones included, so the normal
+/// matrices stay invertible on one- and two-dimensional meshes. A cell
+/// whose matrix is nevertheless singular gets a zero inverse.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeastSquaresVectors {
+    inverse_matrices: Vec<Tensor>,
+    owner: Vec<Vector>,
+    neighbor: Vec<Vector>,
+}
+
+impl LeastSquaresVectors {
+    /// Computes the normal matrices and face vectors.
+    pub(crate) fn compute(
+        face_centers: &[Vector],
+        cell_centers: &[Vector],
+        owner: &[usize],
+        neighbor: &[usize],
+    ) -> Self {
+        let offset = |f: usize| {
+            let to = neighbor
+                .get(f)
+                .map_or(face_centers[f], |&n| cell_centers[n]);
+            to - cell_centers[owner[f]]
+        };
+        let weight = |d: Vector| {
+            let d2 = d * d;
+            if d2 > 0.0 { 1.0 / d2 } else { 0.0 }
+        };
+        let mut matrices = vec![Tensor::zero(); cell_centers.len()];
+        for f in 0..face_centers.len() {
+            let d = offset(f);
+            let m = d.outer(&d) * weight(d);
+            matrices[owner[f]] += m;
+            if let Some(&n) = neighbor.get(f) {
+                matrices[n] += m;
+            }
+        }
+        let inverse_matrices: Vec<Tensor> = matrices.iter().map(invert).collect();
+        let owner_vectors = (0..face_centers.len())
+            .map(|f| {
+                let d = offset(f);
+                inverse_matrices[owner[f]] * d * weight(d)
+            })
+            .collect();
+        let neighbor_vectors = neighbor
+            .iter()
+            .enumerate()
+            .map(|(f, &n)| {
+                let d = offset(f);
+                -(inverse_matrices[n] * d * weight(d))
+            })
+            .collect();
+        Self {
+            inverse_matrices,
+            owner: owner_vectors,
+            neighbor: neighbor_vectors,
+        }
+    }
+
+    /// Returns the inverted normal matrix `M⁻¹` of each cell.
+    pub fn inverse_matrices(&self) -> &[Tensor] {
+        &self.inverse_matrices
+    }
+
+    /// Returns the owner-side vector `w M_P⁻¹ d` of each face.
+    pub fn owner(&self) -> &[Vector] {
+        &self.owner
+    }
+
+    /// Returns the neighbor-side vector `−w M_N⁻¹ d` of each internal face.
+    pub fn neighbor(&self) -> &[Vector] {
+        &self.neighbor
+    }
+}
+
+/// Returns the inverse of `m`, or zero if `m` is singular.
+fn invert(m: &Tensor) -> Tensor {
+    let det = m.det();
+    let scale = m.mag();
+    if det.abs() <= SINGULAR_TOLERANCE * scale * scale * scale {
+        return Tensor::zero();
+    }
+    m.inv()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_invert_returns_inverse_or_zero() {
+        let m = Tensor::new(2.0, 1.0, 0.0, 0.0, 3.0, 1.0, 1.0, 0.0, 4.0);
+        let product = invert(&m) * m;
+        let identity = Tensor::identity();
+        assert!((product - identity).mag() < 1e-12);
+        let singular = Tensor::new(1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0);
+        assert_eq!(invert(&singular), Tensor::zero());
+    }
+
+    #[test]
+    fn test_least_squares_vectors_reproduce_linear_gradient() {
+        let mut mesh = box_mesh([3, 2, 1], [3.0, 1.0, 0.5]);
+        // Stretch and shear the cells.
+        let points: Vec<Vector> = mesh
+            .points()
+            .iter()
+            .map(|p| Vector::new(p.x() * p.x() + 0.3 * p.y(), p.y(), p.z()))
+            .collect();
+        mesh.move_points(points).unwrap();
+        let vectors = mesh.least_squares_vectors();
+        let expected = Vector::new(1.0, -2.0, 0.5);
+        let phi = |x: Vector| expected * x;
+        let (centers, faces) = (mesh.cell_centers(), mesh.face_centers());
+        let mut gradients = vec![Vector::zero(); mesh.n_cells()];
+        for (f, (&o, &x)) in mesh.owner().iter().zip(faces).enumerate() {
+            match mesh.neighbor().get(f) {
+                Some(&n) => {
+                    let delta = phi(centers[n]) - phi(centers[o]);
+                    gradients[o] += vectors.owner()[f] * delta;
+                    gradients[n] -= vectors.neighbor()[f] * delta;
+                }
+                None => gradients[o] += vectors.owner()[f] * (phi(x) - phi(centers[o])),
+            }
+        }
+        for g in gradients {
+            assert!((g - expected).mag() < 1e-10);
+        }
+    }
+}
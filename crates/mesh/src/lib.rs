@@ -2,9 +2,93 @@
 //!
 //! Provides finite volume mesh representation with cells, faces, and points.
 
+mod agglomeration;
+mod ami;
+mod amr;
+mod assemble;
+mod baffles;
+mod boundary_surface;
+mod bvh;
+mod castellated;
+mod cell_shape;
+mod check;
+mod column;
+mod cyclic;
+mod decompose;
+mod delaunay;
+mod dual;
+mod empty;
 mod error;
+mod extrude;
+mod fingerprint;
 mod geometry;
+mod ghost;
+mod halo;
+mod hanging;
+mod immersed;
+mod layers;
+mod least_squares;
+mod merge;
+mod mesh;
+mod motion;
+mod non_ortho;
+mod orientation;
+pub mod parallel;
+mod patch;
+mod planarity;
 mod primitive_mesh;
+mod redistribute;
+mod refine;
+mod region;
+mod renumber;
+mod search;
+mod selection;
+mod smooth;
+mod stats;
+#[cfg(test)]
+mod test_meshes;
+mod tet_decomposition;
+mod transform;
+mod tri_surface;
+mod voxel;
+mod wall_distance;
+mod wedge;
+mod zone;
 
+pub use agglomeration::{Agglomeration, AgglomerationSettings, CoarseLevel};
+pub use ami::Ami;
+pub use amr::{AdaptiveMesh, AmrSettings, FieldMapper};
+pub use assemble::{BoundaryGroup, InternalFace, assemble, boundary_cell_last};
+pub use castellated::{CastellatedSettings, castellated_mesh};
+pub use cell_shape::CellShape;
+pub use check::{CheckThresholds, MeshCheck};
+pub use column::{ProfileError, column_mesh, observed_order};
+pub use decompose::{Decomposition, DecompositionMethod, SubMesh};
+pub use delaunay::delaunay_mesh;
+pub use dual::DualMesh;
 pub use error::MeshError;
+pub use extrude::{ExtrudeModel, extrude};
+pub use ghost::{GhostCell, GhostCells, GhostSource};
+pub use halo::HaloLink;
+pub use immersed::{CellClass, ImmersedBoundary};
+pub use layers::LayerSettings;
+pub use least_squares::LeastSquaresVectors;
+pub use merge::MergePointsReport;
+pub use mesh::{Mesh, ZoneKind};
+pub use non_ortho::NonOrthoCorrection;
+pub use patch::{CoupledTransform, Patch, PatchKind};
 pub use primitive_mesh::PrimitiveMesh;
+pub use redistribute::Migration;
+pub use refine::{HexRefiner, RefinementMap};
+pub use region::{MeshRegion, MultiRegionMesh, RegionInterface};
+pub use renumber::{Renumbering, reverse_cuthill_mckee};
+pub use search::MeshSearch;
+pub use selection::Selector;
+pub use smooth::SmoothSettings;
+pub use stats::{MeshStats, PatchStats};
+pub use tet_decomposition::TetDecomposition;
+pub use tri_surface::TriSurface;
+pub use voxel::voxel_mesh;
+pub use wall_distance::WallDistanceMethod;
+pub use wedge::Wedge;
+pub use zone::Zone;
@@ -0,0 +1,538 @@
+use dugong_types::tensor::Vector;
+
+use crate::assemble::{BoundaryGroup, InternalFace, assemble};
+use crate::bvh::{Aabb, Bvh};
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::zone::Zone;
+
+/// Builds a hierarchy over single points, for tolerance-based matching.
+fn point_bvh(points: &[Vector]) -> Bvh {
+    let boxes: Vec<Aabb> = points.iter().map(|&p| Aabb { min: p, max: p }).collect();
+    Bvh::new(&boxes)
+}
+
+/// Inverts the face map of [`assemble`]: `result[input] = new face`.
+pub(crate) fn invert(face_map: &[usize]) -> Vec<usize> {
+    let mut inverse = vec![0; face_map.len()];
+    for (new, &input) in face_map.iter().enumerate() {
+        inverse[input] = new;
+    }
+    inverse
+}
+
+/// Maps the zones of two meshes into a combined mesh, joining zones with
+/// the same name. Members mapped to `usize::MAX` are dropped.
+pub(crate) fn merge_zones(
+    a: &[Zone],
+    b: &[Zone],
+    map_a: impl Fn(usize) -> usize,
+    map_b: impl Fn(usize) -> usize,
+) -> Vec<Zone> {
+    let mapped = |z: &Zone, map: &dyn Fn(usize) -> usize| -> Vec<usize> {
+        z.indices()
+            .iter()
+            .map(|&i| map(i))
+            .filter(|&i| i != usize::MAX)
+            .collect()
+    };
+    let mut zones: Vec<Zone> = a
+        .iter()
+        .map(|z| {
+            let mut members = mapped(z, &map_a);
+            if let Some(other) = b.iter().find(|o| o.name() == z.name()) {
+                members.extend(mapped(other, &map_b));
+            }
+            Zone::new(z.name(), members)
+        })
+        .collect();
+    for z in b {
+        if !a.iter().any(|o| o.name() == z.name()) {
+            zones.push(Zone::new(z.name(), mapped(z, &map_b)));
+        }
+    }
+    zones
+}
+
+/// The result of [`Mesh::merge_points`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergePointsReport {
+    /// Number of points merged into another point.
+    pub merged_points: usize,
+    /// Number of faces removed for having fewer than three distinct
+    /// vertices after merging.
+    pub removed_faces: usize,
+}
+
+impl Mesh {
+    /// Combines this mesh with `other` into a new mesh.
+    ///
+    /// Cells of `other` are numbered after the cells of `self`. Points of
+    /// `other` within `tol` of a point of `self` are merged into it, so the
+    /// meshes share vertices along touching boundaries; the boundary faces
+    /// there stay in their patches until joined with [`Mesh::stitch`].
+    /// Patches and zones with the same name are joined, with the faces of
+    /// `self` first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::InvalidMerge`] if two patches share a name but
+    /// differ in kind, or `Err` if the combined topology is invalid.
+    pub fn merge(&self, other: &Mesh, tol: f64) -> Result<Mesh, MeshError> {
+        let bvh = point_bvh(self.points());
+        let mut points = self.points().to_vec();
+        let other_point: Vec<usize> = other
+            .points()
+            .iter()
+            .map(
+                |&p| match bvh.nearest(p, |i| (self.points()[i] - p).mag()) {
+                    Some((i, d)) if d <= tol => i,
+                    _ => {
+                        points.push(p);
+                        points.len() - 1
+                    }
+                },
+            )
+            .collect();
+        let remap = |face: &[usize]| face.iter().map(|&p| other_point[p]).collect::<Vec<_>>();
+        let cell_offset = self.n_cells();
+
+        // Input faces in `assemble` order, with their source as
+        // `(is_other, face)`.
+        let mut sources = Vec::with_capacity(self.n_faces() + other.n_faces());
+        let mut internal: Vec<InternalFace> = Vec::new();
+        for f in 0..self.n_internal_faces() {
+            internal.push((self.faces()[f].clone(), self.owner()[f], self.neighbor()[f]));
+            sources.push((false, f));
+        }
+        for f in 0..other.n_internal_faces() {
+            let (o, n) = (other.owner()[f], other.neighbor()[f]);
+            internal.push((remap(&other.faces()[f]), o + cell_offset, n + cell_offset));
+            sources.push((true, f));
+        }
+
+        let mut groups = Vec::new();
+        for patch in self.patches() {
+            let mut faces: Vec<(Vec<usize>, usize)> = patch
+                .range()
+                .map(|f| (self.faces()[f].clone(), self.owner()[f]))
+                .collect();
+            sources.extend(patch.range().map(|f| (false, f)));
+            if let Some(twin) = other.patch(patch.name()) {
+                if twin.kind() != patch.kind() {
+                    return Err(MeshError::InvalidMerge {
+                        reason: format!("patch {} has different kinds", patch.name()),
+                    });
+                }
+                faces.extend(
+                    twin.range()
+                        .map(|f| (remap(&other.faces()[f]), other.owner()[f] + cell_offset)),
+                );
+                sources.extend(twin.range().map(|f| (true, f)));
+            }
+            groups.push(BoundaryGroup {
+                name: patch.name().to_string(),
+                kind: patch.kind().clone(),
+                faces,
+            });
+        }
+        for patch in other.patches() {
+            if self.patch(patch.name()).is_some() {
+                continue;
+            }
+            groups.push(BoundaryGroup {
+                name: patch.name().to_string(),
+                kind: patch.kind().clone(),
+                faces: patch
+                    .range()
+                    .map(|f| (remap(&other.faces()[f]), other.owner()[f] + cell_offset))
+                    .collect(),
+            });
+            sources.extend(patch.range().map(|f| (true, f)));
+        }
+
+        let (mut mesh, face_map) = assemble(points, internal, groups)?;
+        let new_face = invert(&face_map);
+        let mut face_of = [vec![0; self.n_faces()], vec![0; other.n_faces()]];
+        for (input, &(is_other, f)) in sources.iter().enumerate() {
+            face_of[is_other as usize][f] = new_face[input];
+        }
+        mesh.cell_zones = merge_zones(
+            self.cell_zones(),
+            other.cell_zones(),
+            |c| c,
+            |c| c + cell_offset,
+        );
+        mesh.face_zones = merge_zones(
+            self.face_zones(),
+            other.face_zones(),
+            |f| face_of[0][f],
+            |f| face_of[1][f],
+        );
+        mesh.point_zones = merge_zones(
+            self.point_zones(),
+            other.point_zones(),
+            |p| p,
+            |p| other_point[p],
+        );
+        Ok(mesh)
+    }
+
+    /// Joins conformal boundary faces of `patch_a` and `patch_b` into
+    /// internal faces, merging their points.
+    ///
+    /// Each face of `patch_a` is paired with the face of `patch_b` whose
+    /// center lies within `tol`; every vertex of the paired face must then
+    /// lie within `tol` of a vertex of the `patch_a` face. Stitched faces
+    /// keep the vertices and orientation of the `patch_a` face. Unpaired
+    /// faces stay in their patches, which are kept even when emptied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::PatchNotFound`] for unknown patch names, and
+    /// [`MeshError::InvalidMerge`] if the two names are equal, a paired face
+    /// does not conform, or both faces of a pair belong to the same cell.
+    pub fn stitch(&self, patch_a: &str, patch_b: &str, tol: f64) -> Result<Mesh, MeshError> {
+        let find = |name: &str| {
+            self.patch(name).ok_or_else(|| MeshError::PatchNotFound {
+                name: name.to_string(),
+            })
+        };
+        let (a, b) = (find(patch_a)?, find(patch_b)?);
+        if patch_a == patch_b {
+            return Err(MeshError::InvalidMerge {
+                reason: format!("cannot stitch patch {patch_a} to itself"),
+            });
+        }
+        let invalid = |reason: String| MeshError::InvalidMerge { reason };
+        let centers = self.face_centers();
+        let bvh = point_bvh(&centers[b.range()]);
+
+        // pair[f] is the patch_b partner of patch_a face f, and vice versa.
+        let mut pair = vec![None; self.n_faces()];
+        let mut point_map: Vec<usize> = (0..self.n_points()).collect();
+        for fa in a.range() {
+            let Some((i, d)) = bvh.nearest(centers[fa], |i| {
+                (centers[b.start() + i] - centers[fa]).mag()
+            }) else {
+                break;
+            };
+            let fb = b.start() + i;
+            if d > tol || pair[fb].is_some() {
+                continue;
+            }
+            let (face_a, face_b) = (&self.faces()[fa], &self.faces()[fb]);
+            if self.owner()[fa] == self.owner()[fb] {
+                return Err(invalid(format!(
+                    "faces {fa} and {fb} belong to the same cell"
+                )));
+            }
+            if face_a.len() != face_b.len() {
+                return Err(invalid(format!("face {fb} does not conform to face {fa}")));
+            }
+            for &v in face_b {
+                let p = self.points()[v];
+                let u = face_a
+                    .iter()
+                    .copied()
+                    .find(|&u| (self.points()[u] - p).mag() <= tol)
+                    .ok_or_else(|| invalid(format!("face {fb} does not conform to face {fa}")))?;
+                point_map[v] = u;
+            }
+            pair[fa] = Some(fb);
+            pair[fb] = Some(fa);
+        }
+
+        // Compact the points to those still referenced.
+        let mut used = vec![false; self.n_points()];
+        for face in self.faces() {
+            for &p in face {
+                used[point_map[p]] = true;
+            }
+        }
+        let mut compact = vec![usize::MAX; self.n_points()];
+        let mut points = Vec::new();
+        for (p, _) in used.iter().enumerate().filter(|&(_, &u)| u) {
+            compact[p] = points.len();
+            points.push(self.points()[p]);
+        }
+        let new_point = |p: usize| compact[point_map[p]];
+        let remap = |f: usize| self.faces()[f].iter().map(|&p| new_point(p)).collect();
+
+        let mut sources = Vec::with_capacity(self.n_faces());
+        let mut internal: Vec<InternalFace> = (0..self.n_internal_faces())
+            .map(|f| (remap(f), self.owner()[f], self.neighbor()[f]))
+            .collect();
+        sources.extend(0..self.n_internal_faces());
+        for fa in a.range() {
+            if let Some(fb) = pair[fa] {
+                internal.push((remap(fa), self.owner()[fa], self.owner()[fb]));
+                sources.push(fa);
+            }
+        }
+        let groups = self
+            .patches()
+            .iter()
+            .map(|patch| {
+                let kept: Vec<usize> = patch.range().filter(|&f| pair[f].is_none()).collect();
+                sources.extend(&kept);
+                BoundaryGroup {
+                    name: patch.name().to_string(),
+                    kind: patch.kind().clone(),
+                    faces: kept.iter().map(|&f| (remap(f), self.owner()[f])).collect(),
+                }
+            })
+            .collect();
+
+        let (mut mesh, face_map) = assemble(points, internal, groups)?;
+        let new_face = invert(&face_map);
+        let mut face_of = vec![0; self.n_faces()];
+        for (input, &f) in sources.iter().enumerate() {
+            face_of[f] = new_face[input];
+        }
+        for f in b.range() {
+            if let Some(fa) = pair[f] {
+                face_of[f] = face_of[fa];
+            }
+        }
+        mesh.cell_zones = self.cell_zones().to_vec();
+        mesh.face_zones = merge_zones(self.face_zones(), &[], |f| face_of[f], |f| f);
+        mesh.point_zones = merge_zones(self.point_zones(), &[], new_point, |p| p);
+        Ok(mesh)
+    }
+
+    /// Merges points lying within `tol` of each other.
+    ///
+    /// Points are visited in order, and each is merged into the first
+    /// earlier unmerged point within `tol`, keeping that point's position.
+    /// Face vertices are renumbered and repeated vertices dropped; faces
+    /// left with fewer than three vertices are removed. Faces that become
+    /// coincident stay separate; join them with [`Mesh::stitch`]. Cells keep
+    /// their indices, and zones are mapped onto the new points and faces.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the merged topology is invalid, for example when a
+    /// cell collapses.
+    pub fn merge_points(&self, tol: f64) -> Result<(Mesh, MergePointsReport), MeshError> {
+        let points = self.points();
+        let bvh = point_bvh(points);
+        let mut target: Vec<usize> = (0..points.len()).collect();
+        for (p, &x) in points.iter().enumerate() {
+            let mut first = p;
+            bvh.visit(
+                |b| b.distance(x) <= tol,
+                |q| {
+                    if q < first && target[q] == q && (points[q] - x).mag() <= tol {
+                        first = q;
+                    }
+                },
+            );
+            target[p] = first;
+        }
+        let mut compact = vec![usize::MAX; points.len()];
+        let mut new_points = Vec::new();
+        for p in (0..points.len()).filter(|&p| target[p] == p) {
+            compact[p] = new_points.len();
+            new_points.push(points[p]);
+        }
+        let new_point = |p: usize| compact[target[p]];
+        let remap = |f: usize| -> Option<Vec<usize>> {
+            let mut face: Vec<usize> = self.faces()[f].iter().map(|&p| new_point(p)).collect();
+            face.dedup();
+            while face.len() > 1 && face.first() == face.last() {
+                face.pop();
+            }
+            (face.len() >= 3).then_some(face)
+        };
+
+        let mut sources = Vec::with_capacity(self.n_faces());
+        let mut internal: Vec<InternalFace> = Vec::new();
+        for (f, &n) in self.neighbor().iter().enumerate() {
+            if let Some(face) = remap(f) {
+                internal.push((face, self.owner()[f], n));
+                sources.push(f);
+            }
+        }
+        let groups = self
+            .patches()
+            .iter()
+            .map(|patch| {
+                let mut faces = Vec::with_capacity(patch.size());
+                for f in patch.range() {
+                    if let Some(face) = remap(f) {
+                        faces.push((face, self.owner()[f]));
+                        sources.push(f);
+                    }
+                }
+                BoundaryGroup {
+                    name: patch.name().to_string(),
+                    kind: patch.kind().clone(),
+                    faces,
+                }
+            })
+            .collect();
+        let removed_faces = self.n_faces() - sources.len();
+
+        let merged_points = points.len() - new_points.len();
+        let (mut mesh, face_map) = assemble(new_points, internal, groups)?;
+        let new_face = invert(&face_map);
+        let mut face_of = vec![usize::MAX; self.n_faces()];
+        for (input, &f) in sources.iter().enumerate() {
+            face_of[f] = new_face[input];
+        }
+        mesh.cell_zones = self.cell_zones().to_vec();
+        mesh.face_zones = merge_zones(self.face_zones(), &[], |f| face_of[f], |f| f);
+        mesh.point_zones = merge_zones(self.point_zones(), &[], new_point, |p| p);
+        let report = MergePointsReport {
+            merged_points,
+            removed_faces,
+        };
+        Ok((mesh, report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refine::tests::assert_closed;
+    use crate::test_meshes::box_mesh;
+
+    /// Two unit-cell boxes side by side along x, merged but not stitched.
+    fn merged_pair() -> Mesh {
+        let left = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut right = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        right.translate(Vector::new(2.0, 0.0, 0.0));
+        left.merge(&right, 1e-9).unwrap()
+    }
+
+    #[test]
+    fn test_merge_shares_touching_points() {
+        let mesh = merged_pair();
+        assert_eq!(mesh.n_cells(), 4);
+        // 12 + 12 points, 4 shared on the x = 2 plane.
+        assert_eq!(mesh.n_points(), 20);
+        assert_eq!(mesh.n_internal_faces(), 2);
+        assert_eq!(mesh.patch("x-min").unwrap().size(), 2);
+        let total: f64 = mesh.cell_volumes().iter().sum();
+        assert!((total - 4.0).abs() < 1e-12);
+        assert!((mesh.cell_centers()[2] - Vector::new(2.5, 0.5, 0.5)).mag() < 1e-12);
+    }
+
+    #[test]
+    fn test_merge_joins_zones() {
+        let mut left = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        let mut right = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        right.translate(Vector::new(5.0, 0.0, 0.0));
+        left.add_cell_zone(Zone::new("porous", [0])).unwrap();
+        right.add_cell_zone(Zone::new("porous", [0])).unwrap();
+        right.add_face_zone(Zone::new("inlet", [0])).unwrap();
+        let mesh = left.merge(&right, 1e-9).unwrap();
+        assert_eq!(mesh.n_points(), 16);
+        assert_eq!(mesh.cell_zone("porous").unwrap().indices(), &[0, 1]);
+        let f = mesh.face_zone("inlet").unwrap().indices()[0];
+        assert_eq!(mesh.owner()[f], 1);
+        assert!((mesh.face_centers()[f] - Vector::new(5.0, 0.5, 0.5)).mag() < 1e-12);
+    }
+
+    #[test]
+    fn test_stitch_joins_matched_faces() {
+        let merged = merged_pair();
+        // The left box's x-max and the right box's x-min both lie at x = 2;
+        // split them into separate patches by rebuilding the right box with
+        // renamed patches.
+        let left = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut right = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        right.translate(Vector::new(2.0, 0.0, 0.0));
+        for i in 0..right.patches.len() {
+            let p = &right.patches[i];
+            let name = format!("right-{}", p.name());
+            right.patches[i] = crate::Patch::new(name, p.kind().clone(), p.start(), p.size());
+        }
+        let mesh = left
+            .merge(&right, 1e-9)
+            .unwrap()
+            .stitch("x-max", "right-x-min", 1e-9)
+            .unwrap();
+        assert_closed(&mesh);
+        assert_eq!(mesh.n_internal_faces(), 3);
+        assert_eq!(mesh.n_points(), 20);
+        assert_eq!(mesh.patch("x-max").unwrap().size(), 0);
+        let mut neighbors = mesh.cell_cells()[1].clone();
+        neighbors.sort_unstable();
+        assert_eq!(neighbors, vec![0, 2]);
+        assert_eq!(merged.n_faces(), mesh.n_faces() + 1);
+    }
+
+    #[test]
+    fn test_stitch_rejects_bad_input() {
+        let mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        assert!(matches!(
+            mesh.stitch("x-min", "nope", 1e-9),
+            Err(MeshError::PatchNotFound { .. })
+        ));
+        assert!(matches!(
+            mesh.stitch("x-min", "x-min", 1e-9),
+            Err(MeshError::InvalidMerge { .. })
+        ));
+        // Faces of the same cell cannot be joined.
+        assert!(matches!(
+            mesh.stitch("x-min", "x-max", 2.0),
+            Err(MeshError::InvalidMerge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_merge_points_joins_coincident_points() {
+        let left = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut right = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        right.translate(Vector::new(2.0, 0.0, 0.0));
+        let separate = left.merge(&right, -1.0).unwrap();
+        assert_eq!(separate.n_points(), 24);
+
+        let (mesh, report) = separate.merge_points(1e-9).unwrap();
+        assert_eq!(
+            report,
+            MergePointsReport {
+                merged_points: 4,
+                removed_faces: 0,
+            }
+        );
+        assert_eq!(mesh.points(), merged_pair().points());
+        assert_eq!(mesh.faces(), merged_pair().faces());
+    }
+
+    #[test]
+    fn test_merge_points_removes_collapsed_faces() {
+        let mut mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        let sides = ["x-min", "x-max"].map(|name| mesh.patch(name).unwrap().start());
+        mesh.add_face_zone(Zone::new("sides", sides)).unwrap();
+        // Fold the x-max face onto its edge at y = 0, leaving a prism.
+        let points = mesh
+            .points()
+            .iter()
+            .map(|p| {
+                if p.x() == 1.0 && p.y() == 1.0 {
+                    Vector::new(1.0, 0.0, p.z())
+                } else {
+                    *p
+                }
+            })
+            .collect();
+        mesh.primitive.set_points(points);
+
+        let (prism, report) = mesh.merge_points(1e-9).unwrap();
+        assert_eq!(
+            report,
+            MergePointsReport {
+                merged_points: 2,
+                removed_faces: 1,
+            }
+        );
+        assert_closed(&prism);
+        assert!(prism.check().is_ok());
+        assert_eq!(prism.patch("x-max").unwrap().size(), 0);
+        assert!((prism.cell_volumes()[0] - 0.5).abs() < 1e-12);
+        assert_eq!(prism.face_zone("sides").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,489 @@
+use dugong_types::tensor::Vector;
+
+use crate::cyclic::validate_cyclic_pairs;
+use crate::error::MeshError;
+use crate::least_squares::LeastSquaresVectors;
+use crate::motion::MotionState;
+use crate::non_ortho::NonOrthoCorrection;
+use crate::patch::{Patch, PatchKind};
+use crate::primitive_mesh::PrimitiveMesh;
+use crate::zone::Zone;
+
+/// A polyhedral mesh with boundary patches and zones.
+///
+/// Composes a [`PrimitiveMesh`] (topology and lazily derived geometry) with
+/// the boundary description (an ordered list of [`Patch`]es partitioning the
+/// boundary faces) and named cell, face, and point [`Zone`]s. Frequently used
+/// [`PrimitiveMesh`] accessors are forwarded for convenience; the full
+/// primitive interface is available through [`Mesh::primitive`].
+pub struct Mesh {
+    pub(crate) primitive: PrimitiveMesh,
+    pub(crate) patches: Vec<Patch>,
+    pub(crate) cell_zones: Vec<Zone>,
+    pub(crate) face_zones: Vec<Zone>,
+    pub(crate) point_zones: Vec<Zone>,
+    pub(crate) motion: Option<MotionState>,
+}
+
+/// Selects one of the three zone lists of a [`Mesh`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoneKind {
+    /// Zones of cell indices.
+    Cell,
+    /// Zones of face indices.
+    Face,
+    /// Zones of point indices.
+    Point,
+}
+
+impl Mesh {
+    /// Constructs a mesh from its primitive topology and boundary patches.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the patches do not partition the boundary faces:
+    /// - the first patch must start at `n_internal_faces()`,
+    /// - each subsequent patch must start where the previous one ends,
+    /// - the patches together must cover exactly `n_faces() - n_internal_faces()` faces,
+    /// - patch names must be unique,
+    /// - cyclic patches must form consistent, equally sized pairs.
+    pub fn new(primitive: PrimitiveMesh, patches: Vec<Patch>) -> Result<Self, MeshError> {
+        let mut expected = primitive.n_internal_faces();
+        for (i, patch) in patches.iter().enumerate() {
+            if patch.start() != expected {
+                return Err(MeshError::PatchStartMismatch {
+                    patch: patch.name().to_string(),
+                    expected,
+                    got: patch.start(),
+                });
+            }
+            if patches[..i].iter().any(|p| p.name() == patch.name()) {
+                return Err(MeshError::DuplicatePatchName {
+                    name: patch.name().to_string(),
+                });
+            }
+            expected += patch.size();
+        }
+        if expected != primitive.n_faces() {
+            return Err(MeshError::BoundaryFaceCountMismatch {
+                expected: primitive.n_faces() - primitive.n_internal_faces(),
+                got: expected - primitive.n_internal_faces(),
+            });
+        }
+        validate_cyclic_pairs(&patches)?;
+
+        Ok(Self {
+            primitive,
+            patches,
+            cell_zones: Vec::new(),
+            face_zones: Vec::new(),
+            point_zones: Vec::new(),
+            motion: None,
+        })
+    }
+
+    /// Returns the underlying primitive mesh.
+    pub fn primitive(&self) -> &PrimitiveMesh {
+        &self.primitive
+    }
+
+    // Boundary patches
+
+    /// Returns the boundary patches in face order.
+    pub fn patches(&self) -> &[Patch] {
+        &self.patches
+    }
+
+    /// Returns the index of the patch with the given name.
+    pub fn patch_index(&self, name: &str) -> Option<usize> {
+        self.patches.iter().position(|p| p.name() == name)
+    }
+
+    /// Returns the patch with the given name.
+    pub fn patch(&self, name: &str) -> Option<&Patch> {
+        self.patches.iter().find(|p| p.name() == name)
+    }
+
+    /// Returns the index of the patch containing boundary face `face`, or
+    /// `None` for internal faces.
+    pub fn which_patch(&self, face: usize) -> Option<usize> {
+        if face < self.n_internal_faces() {
+            return None;
+        }
+        // Patches are contiguous and ordered, so a binary search on start works.
+        let i = self.patches.partition_point(|p| p.start() <= face);
+        i.checked_sub(1)
+            .filter(|&i| self.patches[i].range().contains(&face))
+    }
+
+    /// Changes the kind of the patch named `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if no patch has that name, or if the change leaves the
+    /// cyclic patches inconsistent (the mesh is then left unchanged).
+    pub fn set_patch_kind(&mut self, name: &str, kind: PatchKind) -> Result<(), MeshError> {
+        let i = self
+            .patch_index(name)
+            .ok_or_else(|| MeshError::PatchNotFound {
+                name: name.to_string(),
+            })?;
+        let p = &self.patches[i];
+        let patch = Patch::new(p.name(), kind, p.start(), p.size());
+        let old = std::mem::replace(&mut self.patches[i], patch);
+        if let Err(e) = validate_cyclic_pairs(&self.patches) {
+            self.patches[i] = old;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    // Zones
+
+    /// Returns the zones of the given kind.
+    pub fn zones(&self, kind: ZoneKind) -> &[Zone] {
+        match kind {
+            ZoneKind::Cell => &self.cell_zones,
+            ZoneKind::Face => &self.face_zones,
+            ZoneKind::Point => &self.point_zones,
+        }
+    }
+
+    /// Returns the zone of the given kind and name.
+    pub fn zone(&self, kind: ZoneKind, name: &str) -> Option<&Zone> {
+        self.zones(kind).iter().find(|z| z.name() == name)
+    }
+
+    /// Returns the cell zones.
+    pub fn cell_zones(&self) -> &[Zone] {
+        &self.cell_zones
+    }
+
+    /// Returns the face zones.
+    pub fn face_zones(&self) -> &[Zone] {
+        &self.face_zones
+    }
+
+    /// Returns the point zones.
+    pub fn point_zones(&self) -> &[Zone] {
+        &self.point_zones
+    }
+
+    /// Returns the cell zone with the given name.
+    pub fn cell_zone(&self, name: &str) -> Option<&Zone> {
+        self.zone(ZoneKind::Cell, name)
+    }
+
+    /// Returns the face zone with the given name.
+    pub fn face_zone(&self, name: &str) -> Option<&Zone> {
+        self.zone(ZoneKind::Face, name)
+    }
+
+    /// Returns the point zone with the given name.
+    pub fn point_zone(&self, name: &str) -> Option<&Zone> {
+        self.zone(ZoneKind::Point, name)
+    }
+
+    /// Adds a zone of the given kind.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if a zone of the same kind and name already exists, or
+    /// if any member index is out of range for the zone kind (`n_cells()`,
+    /// `n_faces()` or `n_points()`).
+    pub fn add_zone(&mut self, kind: ZoneKind, zone: Zone) -> Result<(), MeshError> {
+        let len = match kind {
+            ZoneKind::Cell => self.n_cells(),
+            ZoneKind::Face => self.n_faces(),
+            ZoneKind::Point => self.n_points(),
+        };
+        if let Some(index) = zone.max_index().filter(|&i| i >= len) {
+            return Err(MeshError::ZoneIndexOutOfRange {
+                zone: zone.name().to_string(),
+                index,
+                len,
+            });
+        }
+        if self.zone(kind, zone.name()).is_some() {
+            return Err(MeshError::DuplicateZoneName {
+                name: zone.name().to_string(),
+            });
+        }
+        match kind {
+            ZoneKind::Cell => self.cell_zones.push(zone),
+            ZoneKind::Face => self.face_zones.push(zone),
+            ZoneKind::Point => self.point_zones.push(zone),
+        }
+        Ok(())
+    }
+
+    /// Adds a cell zone. See [`Mesh::add_zone`].
+    pub fn add_cell_zone(&mut self, zone: Zone) -> Result<(), MeshError> {
+        self.add_zone(ZoneKind::Cell, zone)
+    }
+
+    /// Adds a face zone. See [`Mesh::add_zone`].
+    pub fn add_face_zone(&mut self, zone: Zone) -> Result<(), MeshError> {
+        self.add_zone(ZoneKind::Face, zone)
+    }
+
+    /// Adds a point zone. See [`Mesh::add_zone`].
+    pub fn add_point_zone(&mut self, zone: Zone) -> Result<(), MeshError> {
+        self.add_zone(ZoneKind::Point, zone)
+    }
+
+    /// Removes and returns the zone of the given kind and name.
+    pub fn remove_zone(&mut self, kind: ZoneKind, name: &str) -> Option<Zone> {
+        let zones = match kind {
+            ZoneKind::Cell => &mut self.cell_zones,
+            ZoneKind::Face => &mut self.face_zones,
+            ZoneKind::Point => &mut self.point_zones,
+        };
+        let i = zones.iter().position(|z| z.name() == name)?;
+        Some(zones.remove(i))
+    }
+
+    // Forwarded primitive accessors
+
+    /// See [`PrimitiveMesh::points`].
+    pub fn points(&self) -> &[Vector] {
+        self.primitive.points()
+    }
+
+    /// See [`PrimitiveMesh::faces`].
+    pub fn faces(&self) -> &[Vec<usize>] {
+        self.primitive.faces()
+    }
+
+    /// See [`PrimitiveMesh::owner`].
+    pub fn owner(&self) -> &[usize] {
+        self.primitive.owner()
+    }
+
+    /// See [`PrimitiveMesh::neighbor`].
+    pub fn neighbor(&self) -> &[usize] {
+        self.primitive.neighbor()
+    }
+
+    /// See [`PrimitiveMesh::n_internal_faces`].
+    pub fn n_internal_faces(&self) -> usize {
+        self.primitive.n_internal_faces()
+    }
+
+    /// See [`PrimitiveMesh::n_cells`].
+    pub fn n_cells(&self) -> usize {
+        self.primitive.n_cells()
+    }
+
+    /// See [`PrimitiveMesh::n_faces`].
+    pub fn n_faces(&self) -> usize {
+        self.primitive.n_faces()
+    }
+
+    /// See [`PrimitiveMesh::n_points`].
+    pub fn n_points(&self) -> usize {
+        self.primitive.n_points()
+    }
+
+    /// See [`PrimitiveMesh::cell_volumes`].
+    pub fn cell_volumes(&self) -> &[f64] {
+        self.primitive.cell_volumes()
+    }
+
+    /// See [`PrimitiveMesh::cell_centers`].
+    pub fn cell_centers(&self) -> &[Vector] {
+        self.primitive.cell_centers()
+    }
+
+    /// See [`PrimitiveMesh::face_areas`].
+    pub fn face_areas(&self) -> &[Vector] {
+        self.primitive.face_areas()
+    }
+
+    /// See [`PrimitiveMesh::face_centers`].
+    pub fn face_centers(&self) -> &[Vector] {
+        self.primitive.face_centers()
+    }
+
+    /// See [`PrimitiveMesh::non_ortho_correction`].
+    pub fn non_ortho_correction(&self) -> &NonOrthoCorrection {
+        self.primitive.non_ortho_correction()
+    }
+
+    /// See [`PrimitiveMesh::skewness_vectors`].
+    pub fn skewness_vectors(&self) -> &[Vector] {
+        self.primitive.skewness_vectors()
+    }
+
+    /// See [`PrimitiveMesh::least_squares_vectors`].
+    pub fn least_squares_vectors(&self) -> &LeastSquaresVectors {
+        self.primitive.least_squares_vectors()
+    }
+
+    /// See [`PrimitiveMesh::cell_cells`].
+    pub fn cell_cells(&self) -> &[Vec<usize>] {
+        self.primitive.cell_cells()
+    }
+
+    /// See [`PrimitiveMesh::cell_faces`].
+    pub fn cell_faces(&self) -> &[Vec<usize>] {
+        self.primitive.cell_faces()
+    }
+
+    /// See [`PrimitiveMesh::cell_points`].
+    pub fn cell_points(&self) -> &[Vec<usize>] {
+        self.primitive.cell_points()
+    }
+
+    /// See [`PrimitiveMesh::edges`].
+    pub fn edges(&self) -> &[[usize; 2]] {
+        self.primitive.edges()
+    }
+
+    /// See [`PrimitiveMesh::face_edges`].
+    pub fn face_edges(&self) -> &[Vec<usize>] {
+        self.primitive.face_edges()
+    }
+
+    /// See [`PrimitiveMesh::cell_edges`].
+    pub fn cell_edges(&self) -> &[Vec<usize>] {
+        self.primitive.cell_edges()
+    }
+
+    /// See [`PrimitiveMesh::point_edges`].
+    pub fn point_edges(&self) -> &[Vec<usize>] {
+        self.primitive.point_edges()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_box_mesh_patches_partition_boundary() {
+        let mesh = box_mesh([2, 2, 1], [2.0, 2.0, 1.0]);
+        assert_eq!(mesh.n_cells(), 4);
+        assert_eq!(mesh.patches().len(), 6);
+        let covered: usize = mesh.patches().iter().map(Patch::size).sum();
+        assert_eq!(covered, mesh.n_faces() - mesh.n_internal_faces());
+    }
+
+    #[test]
+    fn test_box_mesh_geometry_is_consistent() {
+        let mesh = box_mesh([3, 2, 2], [3.0, 1.0, 2.0]);
+        for &v in mesh.cell_volumes() {
+            assert!((v - 0.5).abs() < 1e-12, "cell volume {v}");
+        }
+        // Internal face normals point from owner to neighbor.
+        let cc = mesh.cell_centers();
+        for f in 0..mesh.n_internal_faces() {
+            let d = cc[mesh.neighbor()[f]] - cc[mesh.owner()[f]];
+            assert!(mesh.face_areas()[f] * d > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_new_patch_start_mismatch_returns_err() {
+        let mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        let prim = PrimitiveMesh::new(
+            mesh.points().to_vec(),
+            mesh.faces().to_vec(),
+            mesh.owner().to_vec(),
+            mesh.neighbor().to_vec(),
+        )
+        .unwrap();
+        let patches = vec![Patch::new("all", PatchKind::Wall, 1, 5)];
+        assert!(matches!(
+            Mesh::new(prim, patches),
+            Err(MeshError::PatchStartMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_new_uncovered_boundary_returns_err() {
+        let mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        let prim = PrimitiveMesh::new(
+            mesh.points().to_vec(),
+            mesh.faces().to_vec(),
+            mesh.owner().to_vec(),
+            mesh.neighbor().to_vec(),
+        )
+        .unwrap();
+        let patches = vec![Patch::new("all", PatchKind::Wall, 0, 5)];
+        assert!(matches!(
+            Mesh::new(prim, patches),
+            Err(MeshError::BoundaryFaceCountMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_which_patch_locates_boundary_faces() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        assert_eq!(mesh.which_patch(0), None);
+        for (pi, patch) in mesh.patches().iter().enumerate() {
+            for f in patch.range() {
+                assert_eq!(mesh.which_patch(f), Some(pi));
+            }
+        }
+        assert_eq!(mesh.which_patch(mesh.n_faces()), None);
+    }
+
+    #[test]
+    fn test_add_cell_zone_and_lookup() {
+        let mut mesh = box_mesh([2, 2, 1], [2.0, 2.0, 1.0]);
+        mesh.add_cell_zone(Zone::new("porous", [3, 1])).unwrap();
+        let z = mesh.cell_zone("porous").unwrap();
+        assert_eq!(z.indices(), &[1, 3]);
+        assert!(mesh.face_zone("porous").is_none());
+    }
+
+    #[test]
+    fn test_add_zone_out_of_range_returns_err() {
+        let mut mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        let result = mesh.add_point_zone(Zone::new("p", [8]));
+        assert!(matches!(
+            result,
+            Err(MeshError::ZoneIndexOutOfRange {
+                index: 8,
+                len: 8,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_add_zone_duplicate_name_returns_err() {
+        let mut mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        mesh.add_face_zone(Zone::new("f", [0])).unwrap();
+        assert!(matches!(
+            mesh.add_face_zone(Zone::new("f", [1])),
+            Err(MeshError::DuplicateZoneName { .. })
+        ));
+        assert!(mesh.remove_zone(ZoneKind::Face, "f").is_some());
+        assert!(mesh.face_zones().is_empty());
+    }
+
+    #[test]
+    fn test_set_patch_kind_changes_kind() {
+        let mut mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        mesh.set_patch_kind("z-min", PatchKind::Empty).unwrap();
+        assert_eq!(mesh.patch("z-min").unwrap().kind(), &PatchKind::Empty);
+        assert!(matches!(
+            mesh.set_patch_kind("nope", PatchKind::Wall),
+            Err(MeshError::PatchNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_set_patch_kind_inconsistent_cyclic_is_rolled_back() {
+        let mut mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        let kind = PatchKind::Cyclic {
+            neighbor_patch: "x-max".to_string(),
+            transform: crate::patch::CoupledTransform::None,
+        };
+        assert!(mesh.set_patch_kind("x-min", kind).is_err());
+        assert_eq!(mesh.patch("x-min").unwrap().kind(), &PatchKind::Patch);
+    }
+}
@@ -0,0 +1,221 @@
+use dugong_types::tensor::Vector;
+
+use crate::error::MeshError;
+use crate::geometry;
+use crate::mesh::Mesh;
+use crate::renumber::Renumbering;
+
+/// Geometry retained across a point motion for ALE time schemes.
+pub(crate) struct MotionState {
+    old_points: Vec<Vector>,
+    old_face_centers: Vec<Vector>,
+    old_face_areas: Vec<Vector>,
+    old_cell_centers: Vec<Vector>,
+    old_cell_volumes: Vec<f64>,
+    old_old_cell_volumes: Vec<f64>,
+    swept_volumes: Vec<f64>,
+}
+
+impl MotionState {
+    /// Carries the old-time geometry over to a renumbered mesh, turning the
+    /// area vectors and swept volumes of flipped faces with the faces.
+    pub(crate) fn renumbered(self, renumbering: &Renumbering) -> MotionState {
+        MotionState {
+            old_points: self.old_points,
+            old_face_centers: renumbering.map_face_values(&self.old_face_centers),
+            old_face_areas: renumbering.map_face_fluxes(&self.old_face_areas),
+            old_cell_centers: renumbering.map_cell_values(&self.old_cell_centers),
+            old_cell_volumes: renumbering.map_cell_values(&self.old_cell_volumes),
+            old_old_cell_volumes: renumbering.map_cell_values(&self.old_old_cell_volumes),
+            swept_volumes: renumbering.map_face_fluxes(&self.swept_volumes),
+        }
+    }
+}
+
+impl Mesh {
+    /// Moves the mesh points to `points`, keeping the topology.
+    ///
+    /// Each call is treated as one time step: the geometry before the call
+    /// becomes the old-time geometry, and the volume swept by each face
+    /// between the two configurations is recorded. Cached geometry is updated
+    /// only for faces and cells touching a moved point.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::PointCountMismatch`] if `points.len()` differs
+    /// from [`Mesh::n_points`].
+    pub fn move_points(&mut self, points: Vec<Vector>) -> Result<(), MeshError> {
+        if points.len() != self.n_points() {
+            return Err(MeshError::PointCountMismatch {
+                expected: self.n_points(),
+                got: points.len(),
+            });
+        }
+        let swept_volumes = self
+            .faces()
+            .iter()
+            .map(|f| geometry::compute_face_swept_volume(self.points(), &points, f))
+            .collect();
+        let old_cell_volumes = self.cell_volumes().to_vec();
+        let old_old_cell_volumes = match self.motion.take() {
+            Some(state) => state.old_cell_volumes,
+            None => old_cell_volumes.clone(),
+        };
+        self.motion = Some(MotionState {
+            old_points: self.points().to_vec(),
+            old_face_centers: self.face_centers().to_vec(),
+            old_face_areas: self.face_areas().to_vec(),
+            old_cell_centers: self.cell_centers().to_vec(),
+            old_cell_volumes,
+            old_old_cell_volumes,
+            swept_volumes,
+        });
+        self.primitive.set_points(points);
+        Ok(())
+    }
+
+    /// Returns `true` once [`Mesh::move_points`] has been called.
+    pub fn is_moving(&self) -> bool {
+        self.motion.is_some()
+    }
+
+    /// Returns the point coordinates before the last motion.
+    pub fn old_points(&self) -> Option<&[Vector]> {
+        self.motion.as_ref().map(|m| m.old_points.as_slice())
+    }
+
+    /// Returns the face centers before the last motion.
+    pub fn old_face_centers(&self) -> Option<&[Vector]> {
+        self.motion.as_ref().map(|m| m.old_face_centers.as_slice())
+    }
+
+    /// Returns the face area vectors before the last motion.
+    pub fn old_face_areas(&self) -> Option<&[Vector]> {
+        self.motion.as_ref().map(|m| m.old_face_areas.as_slice())
+    }
+
+    /// Returns the cell centers before the last motion.
+    pub fn old_cell_centers(&self) -> Option<&[Vector]> {
+        self.motion.as_ref().map(|m| m.old_cell_centers.as_slice())
+    }
+
+    /// Returns the cell volumes before the last motion.
+    pub fn old_cell_volumes(&self) -> Option<&[f64]> {
+        self.motion.as_ref().map(|m| m.old_cell_volumes.as_slice())
+    }
+
+    /// Returns the cell volumes before the motion preceding the last one.
+    ///
+    /// After the first motion these equal [`Mesh::old_cell_volumes`].
+    pub fn old_old_cell_volumes(&self) -> Option<&[f64]> {
+        self.motion
+            .as_ref()
+            .map(|m| m.old_old_cell_volumes.as_slice())
+    }
+
+    /// Returns the volume swept by each face during the last motion.
+    ///
+    /// Positive when the face moves along its area vector, i.e. out of its
+    /// owner cell. For cells with planar faces, the owner-minus-neighbor sum
+    /// over a cell's faces equals its volume change, so dividing by the time
+    /// step gives mesh fluxes that satisfy the space conservation law.
+    pub fn swept_volumes(&self) -> Option<&[f64]> {
+        self.motion.as_ref().map(|m| m.swept_volumes.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive_mesh::PrimitiveMesh;
+    use crate::test_meshes::box_mesh;
+
+    fn stretch(points: &[Vector], factor: f64) -> Vec<Vector> {
+        points
+            .iter()
+            .map(|p| Vector::new(p.x() * (1.0 + factor * p.y()), p.y(), p.z()))
+            .collect()
+    }
+
+    #[test]
+    fn test_move_points_updates_geometry_incrementally() {
+        let mut mesh = box_mesh([3, 2, 2], [3.0, 2.0, 2.0]);
+        // Populate the caches before moving.
+        let _ = mesh.cell_volumes();
+        let mut points = mesh.points().to_vec();
+        points[0] = Vector::new(-0.5, 0.0, 0.0);
+        mesh.move_points(points.clone()).unwrap();
+
+        let fresh = PrimitiveMesh::new(
+            points,
+            mesh.faces().to_vec(),
+            mesh.owner().to_vec(),
+            mesh.neighbor().to_vec(),
+        )
+        .unwrap();
+        for (a, b) in mesh.cell_volumes().iter().zip(fresh.cell_volumes()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        for (a, b) in mesh.cell_centers().iter().zip(fresh.cell_centers()) {
+            assert!((*a - *b).mag() < 1e-12);
+        }
+        for (a, b) in mesh.face_areas().iter().zip(fresh.face_areas()) {
+            assert!((*a - *b).mag() < 1e-12);
+        }
+        assert!(mesh.cell_volumes()[0] > 1.0);
+        assert!((mesh.cell_volumes()[11] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_swept_volumes_satisfy_space_conservation() {
+        let mut mesh = box_mesh([2, 2, 1], [2.0, 2.0, 1.0]);
+        assert!(!mesh.is_moving());
+        assert!(mesh.swept_volumes().is_none());
+        for step in 1..=2 {
+            let points = stretch(mesh.points(), 0.1 * step as f64);
+            mesh.move_points(points).unwrap();
+            let swept = mesh.swept_volumes().unwrap();
+            let old = mesh.old_cell_volumes().unwrap();
+            let mut change = vec![0.0; mesh.n_cells()];
+            for (f, &o) in mesh.owner().iter().enumerate() {
+                change[o] += swept[f];
+            }
+            for (f, &n) in mesh.neighbor().iter().enumerate() {
+                change[n] -= swept[f];
+            }
+            for c in 0..mesh.n_cells() {
+                let expected = mesh.cell_volumes()[c] - old[c];
+                assert!((change[c] - expected).abs() < 1e-12, "cell {c}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_move_points_keeps_old_time_levels() {
+        let mut mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        let grow = |points: &[Vector]| points.iter().map(|&p| p * 2.0).collect::<Vec<_>>();
+        mesh.move_points(grow(mesh.points())).unwrap();
+        assert!((mesh.old_cell_volumes().unwrap()[0] - 1.0).abs() < 1e-12);
+        assert_eq!(mesh.old_old_cell_volumes(), mesh.old_cell_volumes());
+        mesh.move_points(grow(mesh.points())).unwrap();
+        assert!((mesh.cell_volumes()[0] - 64.0).abs() < 1e-9);
+        assert!((mesh.old_cell_volumes().unwrap()[0] - 8.0).abs() < 1e-12);
+        assert!((mesh.old_old_cell_volumes().unwrap()[0] - 1.0).abs() < 1e-12);
+        let c = mesh.old_cell_centers().unwrap()[0];
+        assert!((c - Vector::new(1.0, 1.0, 1.0)).mag() < 1e-12);
+    }
+
+    #[test]
+    fn test_move_points_count_mismatch_returns_err() {
+        let mut mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        let result = mesh.move_points(vec![Vector::zero(); 3]);
+        assert!(matches!(
+            result,
+            Err(MeshError::PointCountMismatch {
+                expected: 8,
+                got: 3
+            })
+        ));
+        assert!(!mesh.is_moving());
+    }
+}
@@ -0,0 +1,60 @@
+use dugong_types::tensor::Vector;
+
+use crate::mesh::Mesh;
+
+impl Mesh {
+    /// Reverses the vertex order of faces whose area vector points the wrong
+    /// way, and returns the number of faces reversed.
+    ///
+    /// An internal face must point from its owner toward its neighbor, and
+    /// a boundary face out of its owner. Directions are judged against the
+    /// mean face center of each cell, which does not depend on face
+    /// orientation, so cells with several reversed faces are repaired in one
+    /// call.
+    pub fn fix_orientation(&mut self) -> usize {
+        let face_centers = self.face_centers();
+        let areas = self.face_areas();
+        let owner = self.owner();
+        let mut centers = vec![Vector::zero(); self.n_cells()];
+        for (c, faces) in self.cell_faces().iter().enumerate() {
+            let sum = faces
+                .iter()
+                .fold(Vector::zero(), |s, &f| s + face_centers[f]);
+            centers[c] = sum / faces.len().max(1) as f64;
+        }
+
+        let reversed: Vec<usize> = (0..self.n_faces())
+            .filter(|&f| {
+                let outward = match self.neighbor().get(f) {
+                    Some(&n) => centers[n] - centers[owner[f]],
+                    None => face_centers[f] - centers[owner[f]],
+                };
+                areas[f] * outward < 0.0
+            })
+            .collect();
+        if !reversed.is_empty() {
+            self.primitive.reverse_faces(&reversed);
+        }
+        reversed.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_fix_orientation_repairs_reversed_faces() {
+        let reference = box_mesh([3, 2, 2], [3.0, 2.0, 2.0]);
+        let mut mesh = box_mesh([3, 2, 2], [3.0, 2.0, 2.0]);
+        let boundary = mesh.n_internal_faces();
+        mesh.primitive
+            .reverse_faces(&[0, 4, boundary, boundary + 7]);
+        assert!(mesh.cell_volumes().iter().any(|&v| (v - 1.0).abs() > 1e-9));
+
+        assert_eq!(mesh.fix_orientation(), 4);
+        assert_eq!(mesh.faces(), reference.faces());
+        assert_eq!(mesh.cell_volumes(), reference.cell_volumes());
+        assert_eq!(mesh.fix_orientation(), 0);
+    }
+}
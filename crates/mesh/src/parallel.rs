@@ -0,0 +1,119 @@
+//! Data-parallel kernels for the `parallel` feature, shared by the mesh
+//! geometry and the field crates.
+//!
+//! Per-cell and per-face loops are split into contiguous chunks over
+//! scoped standard-library threads, at most one per available core. Each
+//! thread gets at least [`chunk_size`] items, so small inputs run on the
+//! calling thread. Without the feature everything runs on the calling
+//! thread.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CHUNK_SIZE: AtomicUsize = AtomicUsize::new(4096);
+
+/// Returns the smallest number of items given to a worker thread.
+pub fn chunk_size() -> usize {
+    CHUNK_SIZE.load(Ordering::Relaxed)
+}
+
+/// Sets the smallest number of items given to a worker thread; values
+/// below 1 are raised to 1. Larger chunks amortize thread start-up on
+/// cheap kernels.
+pub fn set_chunk_size(size: usize) {
+    CHUNK_SIZE.store(size.max(1), Ordering::Relaxed);
+}
+
+/// Returns the number of items per thread for `n` items, or `None` to run
+/// on the calling thread.
+#[cfg(feature = "parallel")]
+fn split(n: usize) -> Option<usize> {
+    let threads = std::thread::available_parallelism().map_or(1, |t| t.get());
+    let chunk = n.div_ceil(threads.max(1)).max(chunk_size());
+    (threads > 1 && chunk < n).then_some(chunk)
+}
+
+/// Returns `(0..n).map(f).collect()`.
+pub fn map_indexed<T: Send>(n: usize, f: impl Fn(usize) -> T + Sync) -> Vec<T> {
+    #[cfg(feature = "parallel")]
+    if let Some(chunk) = split(n) {
+        let f = &f;
+        return std::thread::scope(|s| {
+            let handles: Vec<_> = (0..n)
+                .step_by(chunk)
+                .map(|start| {
+                    s.spawn(move || (start..(start + chunk).min(n)).map(f).collect::<Vec<T>>())
+                })
+                .collect();
+            handles
+                .into_iter()
+                // Safety: a worker only panics if `f` panics; propagate it.
+                .flat_map(|h| h.join().unwrap())
+                .collect()
+        });
+    }
+    (0..n).map(f).collect()
+}
+
+/// Sets `values[i] = f(i, values[i])` for every index.
+pub fn update_indexed<T: Copy + Send>(values: &mut [T], f: impl Fn(usize, T) -> T + Sync) {
+    #[cfg(feature = "parallel")]
+    if let Some(chunk) = split(values.len()) {
+        let f = &f;
+        std::thread::scope(|s| {
+            for (c, part) in values.chunks_mut(chunk).enumerate() {
+                s.spawn(move || {
+                    for (j, v) in part.iter_mut().enumerate() {
+                        *v = f(c * chunk + j, *v);
+                    }
+                });
+            }
+        });
+        return;
+    }
+    for (i, v) in values.iter_mut().enumerate() {
+        *v = f(i, *v);
+    }
+}
+
+/// Returns the sum of `f(i)` over `0..n`, starting from `zero`.
+///
+/// In parallel, each chunk is summed separately and the chunk sums are
+/// added in order, so the result depends on the chunking only through
+/// rounding.
+pub fn sum_indexed<T: Copy + Send + std::ops::Add<Output = T>>(
+    n: usize,
+    zero: T,
+    f: impl Fn(usize) -> T + Sync,
+) -> T {
+    #[cfg(feature = "parallel")]
+    if let Some(chunk) = split(n) {
+        let f = &f;
+        return std::thread::scope(|s| {
+            let handles: Vec<_> = (0..n)
+                .step_by(chunk)
+                .map(|start| {
+                    s.spawn(move || (start..(start + chunk).min(n)).fold(zero, |acc, i| acc + f(i)))
+                })
+                .collect();
+            handles
+                .into_iter()
+                // Safety: a worker only panics if `f` panics; propagate it.
+                .fold(zero, |acc, h| acc + h.join().unwrap())
+        });
+    }
+    (0..n).fold(zero, |acc, i| acc + f(i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_indexed_preserves_order() {
+        let n = 10_007;
+        let out = map_indexed(n, |i| i * i);
+        assert_eq!(out.len(), n);
+        assert!(out.iter().enumerate().all(|(i, &x)| x == i * i));
+        assert!(map_indexed(0, |i| i).is_empty());
+    }
+}
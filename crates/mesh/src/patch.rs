@@ -0,0 +1,286 @@
+use dugong_types::tensor::{Tensor, Vector};
+
+/// The geometric relation between a coupled patch and its neighbor patch.
+///
+/// [`CoupledTransform::transform_point`] maps a position on this patch to
+/// the matching position on the neighbor patch; the `inv_` variants map back.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CoupledTransform {
+    /// The neighbor faces coincide with the local faces.
+    None,
+    /// The neighbor patch is this patch translated by `separation`.
+    Translational { separation: Vector },
+    /// The neighbor patch is this patch rotated by `angle` radians about the
+    /// axis through `center` along `axis` (right-hand rule).
+    Rotational {
+        axis: Vector,
+        center: Vector,
+        angle: f64,
+    },
+}
+
+impl CoupledTransform {
+    /// Returns the rotation tensor of the transform (identity unless rotational).
+    pub fn rotation(&self) -> Tensor {
+        match self {
+            CoupledTransform::Rotational { axis, angle, .. } => {
+                let k = *axis * (1.0 / axis.mag());
+                let (s, c) = angle.sin_cos();
+                let skew = Tensor::new(0.0, -k.z(), k.y(), k.z(), 0.0, -k.x(), -k.y(), k.x(), 0.0);
+                Tensor::identity() * c + skew * s + k.outer(&k) * (1.0 - c)
+            }
+            _ => Tensor::identity(),
+        }
+    }
+
+    /// Returns the transform from the neighbor patch back to this patch.
+    pub fn inverse(&self) -> Self {
+        match self {
+            CoupledTransform::None => CoupledTransform::None,
+            CoupledTransform::Translational { separation } => CoupledTransform::Translational {
+                separation: -*separation,
+            },
+            CoupledTransform::Rotational {
+                axis,
+                center,
+                angle,
+            } => CoupledTransform::Rotational {
+                axis: *axis,
+                center: *center,
+                angle: -angle,
+            },
+        }
+    }
+
+    /// Maps a position on this patch to the neighbor patch.
+    pub fn transform_point(&self, p: Vector) -> Vector {
+        match self {
+            CoupledTransform::None => p,
+            CoupledTransform::Translational { separation } => p + *separation,
+            CoupledTransform::Rotational { center, .. } => {
+                *center + self.rotation() * (p - *center)
+            }
+        }
+    }
+
+    /// Maps a position on the neighbor patch back to this patch.
+    pub fn inv_transform_point(&self, p: Vector) -> Vector {
+        match self {
+            CoupledTransform::None => p,
+            CoupledTransform::Translational { separation } => p - *separation,
+            CoupledTransform::Rotational { center, .. } => {
+                *center + self.rotation().transpose() * (p - *center)
+            }
+        }
+    }
+
+    /// Maps a direction (or vector-valued quantity) on this patch to the
+    /// neighbor patch. Translations leave vectors unchanged.
+    pub fn transform_vector(&self, v: Vector) -> Vector {
+        self.rotation() * v
+    }
+
+    /// Maps a direction on the neighbor patch back to this patch.
+    pub fn inv_transform_vector(&self, v: Vector) -> Vector {
+        self.rotation().transpose() * v
+    }
+}
+
+/// The geometric/constraint type of a boundary patch.
+///
+/// Mirrors the OpenFOAM `type` keyword of a `boundary` file entry. Coupled
+/// and constraint types carry the data they need directly in their variant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchKind {
+    /// A generic boundary with no geometric constraint (`patch`).
+    Patch,
+    /// A solid wall (`wall`). Used by wall-distance and wall-function code.
+    Wall,
+    /// A planar symmetry boundary (`symmetryPlane`).
+    SymmetryPlane,
+    /// A general symmetry boundary (`symmetry`).
+    Symmetry,
+    /// The front and back planes of a one-cell-thick 2D mesh (`empty`).
+    ///
+    /// No fluxes are computed through empty faces; the direction normal to
+    /// them is excluded from the solution (see
+    /// [`Mesh::solution_directions`](crate::Mesh::solution_directions)).
+    Empty,
+    /// One side of an axisymmetric wedge (`wedge`).
+    ///
+    /// A wedge mesh is one cell thick between two wedge patches whose planes
+    /// meet at the symmetry axis with a small angle; the rotation between
+    /// them is derived from the geometry (see [`Mesh::wedge`](crate::Mesh::wedge)).
+    Wedge,
+    /// One half of a periodic pair (`cyclic`).
+    ///
+    /// Face `i` of this patch is coupled to face `i` of `neighbor_patch`,
+    /// which must be a cyclic patch of the same size naming this patch back.
+    /// `transform` maps this patch onto the neighbor patch; the neighbor
+    /// carries the inverse transform.
+    Cyclic {
+        neighbor_patch: String,
+        transform: CoupledTransform,
+    },
+    /// One side of a non-conformal coupling (`cyclicAMI`).
+    ///
+    /// Like [`PatchKind::Cyclic`], but the two patches may be meshed
+    /// independently; values are exchanged through area-weighted
+    /// interpolation (see [`Ami`](crate::Ami)).
+    CyclicAmi {
+        neighbor_patch: String,
+        transform: CoupledTransform,
+    },
+    /// An inter-processor boundary (`processor`) of a decomposed mesh.
+    ///
+    /// The faces are matched one-to-one, in order, with the faces of the
+    /// processor patch on `neighbor_rank` that points back to `my_rank`. The
+    /// transform is [`CoupledTransform::None`] except where the processor
+    /// boundary cuts through a periodic coupling.
+    Processor {
+        my_rank: usize,
+        neighbor_rank: usize,
+        transform: CoupledTransform,
+    },
+}
+
+impl PatchKind {
+    /// Returns the OpenFOAM type name of this patch kind.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            PatchKind::Patch => "patch",
+            PatchKind::Wall => "wall",
+            PatchKind::SymmetryPlane => "symmetryPlane",
+            PatchKind::Symmetry => "symmetry",
+            PatchKind::Empty => "empty",
+            PatchKind::Wedge => "wedge",
+            PatchKind::Cyclic { .. } => "cyclic",
+            PatchKind::CyclicAmi { .. } => "cyclicAMI",
+            PatchKind::Processor { .. } => "processor",
+        }
+    }
+
+    /// Returns `true` for patches whose faces are coupled to faces elsewhere.
+    pub fn is_coupled(&self) -> bool {
+        matches!(
+            self,
+            PatchKind::Cyclic { .. } | PatchKind::CyclicAmi { .. } | PatchKind::Processor { .. }
+        )
+    }
+
+    /// Parses an OpenFOAM type name of a kind that carries no data. Unknown
+    /// names and kinds that need extra data (coupled patches) yield `None`.
+    pub fn from_type_name(name: &str) -> Option<Self> {
+        match name {
+            "patch" => Some(PatchKind::Patch),
+            "wall" => Some(PatchKind::Wall),
+            "symmetryPlane" => Some(PatchKind::SymmetryPlane),
+            "symmetry" => Some(PatchKind::Symmetry),
+            "empty" => Some(PatchKind::Empty),
+            "wedge" => Some(PatchKind::Wedge),
+            _ => None,
+        }
+    }
+}
+
+/// A named, contiguous range of boundary faces.
+///
+/// Patches partition the boundary faces `n_internal_faces()..n_faces()` of a
+/// [`Mesh`](crate::Mesh) in order; patch `i + 1` starts where patch `i` ends.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Patch {
+    name: String,
+    kind: PatchKind,
+    start: usize,
+    size: usize,
+}
+
+impl Patch {
+    /// Creates a patch covering faces `start..start + size`.
+    pub fn new(name: impl Into<String>, kind: PatchKind, start: usize, size: usize) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            start,
+            size,
+        }
+    }
+
+    /// Returns the patch name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the patch type.
+    pub fn kind(&self) -> &PatchKind {
+        &self.kind
+    }
+
+    /// Returns the index of the first face of this patch in the mesh face list.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the number of faces in this patch.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the mesh face index range covered by this patch.
+    pub fn range(&self) -> std::ops::Range<usize> {
+        self.start..self.start + self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patch_kind_type_name_round_trip() {
+        for kind in [
+            PatchKind::Patch,
+            PatchKind::Wall,
+            PatchKind::SymmetryPlane,
+            PatchKind::Symmetry,
+            PatchKind::Empty,
+            PatchKind::Wedge,
+        ] {
+            assert_eq!(PatchKind::from_type_name(kind.type_name()), Some(kind));
+        }
+        assert_eq!(PatchKind::from_type_name("bogus"), None);
+        assert_eq!(PatchKind::from_type_name("processor"), None);
+    }
+
+    #[test]
+    fn test_coupled_transform_rotational_round_trip() {
+        let t = CoupledTransform::Rotational {
+            axis: Vector::new(0.0, 0.0, 2.0),
+            center: Vector::new(1.0, 0.0, 0.0),
+            angle: std::f64::consts::FRAC_PI_2,
+        };
+        let q = t.transform_point(Vector::new(2.0, 0.0, 3.0));
+        assert!((q - Vector::new(1.0, 1.0, 3.0)).mag() < 1e-12);
+        assert!((t.inv_transform_point(q) - Vector::new(2.0, 0.0, 3.0)).mag() < 1e-12);
+        let v = t.transform_vector(Vector::new(1.0, 0.0, 0.0));
+        assert!((v - Vector::new(0.0, 1.0, 0.0)).mag() < 1e-12);
+    }
+
+    #[test]
+    fn test_coupled_transform_translational_leaves_vectors() {
+        let t = CoupledTransform::Translational {
+            separation: Vector::new(0.0, 2.0, 0.0),
+        };
+        let p = Vector::new(1.0, 1.0, 1.0);
+        assert_eq!(t.transform_point(p), Vector::new(1.0, 3.0, 1.0));
+        assert_eq!(t.inv_transform_point(t.transform_point(p)), p);
+        assert_eq!(t.transform_vector(p), p);
+        assert_eq!(t.inverse().transform_point(t.transform_point(p)), p);
+    }
+
+    #[test]
+    fn test_patch_range() {
+        let p = Patch::new("inlet", PatchKind::Patch, 10, 4);
+        assert_eq!(p.range(), 10..14);
+    }
+}
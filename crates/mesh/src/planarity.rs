@@ -0,0 +1,172 @@
+use dugong_types::tensor::Vector;
+
+use crate::assemble::{BoundaryGroup, InternalFace, assemble};
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::zone::Zone;
+
+impl Mesh {
+    /// Returns the warpage of each face: the largest distance of a vertex
+    /// from the plane through the face center normal to the area vector,
+    /// divided by the square root of the face area.
+    ///
+    /// Planar faces, including all triangles, have zero warpage.
+    pub fn face_warpage(&self) -> Vec<f64> {
+        let (points, centers, areas) = (self.points(), self.face_centers(), self.face_areas());
+        self.faces()
+            .iter()
+            .enumerate()
+            .map(|(f, face)| {
+                let area = areas[f].mag();
+                if area == 0.0 {
+                    return 0.0;
+                }
+                let normal = areas[f] / area;
+                face.iter()
+                    .map(|&v| ((points[v] - centers[f]) * normal).abs())
+                    .fold(0.0, f64::max)
+                    / area.sqrt()
+            })
+            .collect()
+    }
+
+    /// Splits every face whose [warpage](Mesh::face_warpage) exceeds
+    /// `max_warpage` into triangles.
+    ///
+    /// A point is added at the vertex average of each split face, and the
+    /// face is replaced by the triangles joining it to each edge. These are
+    /// the triangles over which face areas are integrated, so the triangle
+    /// area vectors sum to that of the original face. Cell volumes change by
+    /// the integration error of the warped faces. Cells and points keep their indices, with the new points appended;
+    /// face zones list every triangle of a split face.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the resulting mesh fails validation.
+    pub fn triangulate_warped_faces(&self, max_warpage: f64) -> Result<Mesh, MeshError> {
+        let warpage = self.face_warpage();
+        let (faces, owner) = (self.faces(), self.owner());
+        let mut points = self.points().to_vec();
+        let mut split = |f: usize| -> Vec<Vec<usize>> {
+            let face = &faces[f];
+            if face.len() <= 3 || warpage[f] <= max_warpage {
+                return vec![face.clone()];
+            }
+            let center =
+                face.iter().fold(Vector::zero(), |s, &v| s + points[v]) / face.len() as f64;
+            points.push(center);
+            let c = points.len() - 1;
+            (0..face.len())
+                .map(|i| vec![c, face[i], face[(i + 1) % face.len()]])
+                .collect()
+        };
+
+        // `sources[input]` is the original face of each `assemble` input.
+        let mut sources = Vec::new();
+        let mut internal: Vec<InternalFace> = Vec::new();
+        for (f, &n) in self.neighbor().iter().enumerate() {
+            for tri in split(f) {
+                internal.push((tri, owner[f], n));
+                sources.push(f);
+            }
+        }
+        let mut groups = Vec::with_capacity(self.patches().len());
+        for patch in self.patches() {
+            let mut group_faces = Vec::with_capacity(patch.size());
+            for f in patch.range() {
+                for tri in split(f) {
+                    group_faces.push((tri, owner[f]));
+                    sources.push(f);
+                }
+            }
+            groups.push(BoundaryGroup {
+                name: patch.name().to_string(),
+                kind: patch.kind().clone(),
+                faces: group_faces,
+            });
+        }
+
+        let (mut mesh, face_map) = assemble(points, internal, groups)?;
+        let mut new_faces = vec![Vec::new(); self.n_faces()];
+        for (new, &input) in face_map.iter().enumerate() {
+            new_faces[sources[input]].push(new);
+        }
+        mesh.cell_zones = self.cell_zones().to_vec();
+        mesh.point_zones = self.point_zones().to_vec();
+        mesh.face_zones = self
+            .face_zones()
+            .iter()
+            .map(|z| {
+                Zone::new(
+                    z.name(),
+                    z.indices()
+                        .iter()
+                        .flat_map(|&f| new_faces[f].iter().copied()),
+                )
+            })
+            .collect();
+        Ok(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    /// A 2x1x1 box with the corner point (2, 1, 1) pulled outward, so the
+    /// three faces meeting there are warped.
+    fn warped() -> Mesh {
+        let mut mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut points = mesh.points().to_vec();
+        // The point (2, 1, 1).
+        let p = points.len() - 1;
+        points[p] += Vector::new(0.2, 0.2, 0.2);
+        mesh.primitive.set_points(points);
+        mesh
+    }
+
+    #[test]
+    fn test_face_warpage_detects_lifted_corner() {
+        let mesh = warped();
+        let warpage = mesh.face_warpage();
+        let warped: Vec<usize> = (0..mesh.n_faces()).filter(|&f| warpage[f] > 1e-9).collect();
+        assert_eq!(warped.len(), 3);
+        let check = mesh.check();
+        assert_eq!(check.warped_faces.len(), 3);
+        assert!(
+            (check.max_warpage - warped.iter().map(|&f| warpage[f]).fold(0.0, f64::max)).abs()
+                < 1e-12
+        );
+        assert!(
+            box_mesh([2, 2, 2], [1.0, 1.0, 1.0])
+                .check()
+                .warped_faces
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_triangulate_warped_faces_preserves_geometry() {
+        let mut mesh = warped();
+        mesh.add_face_zone(Zone::new("all", 0..mesh.n_faces()))
+            .unwrap();
+        let split = mesh.triangulate_warped_faces(0.01).unwrap();
+        assert_eq!(split.n_cells(), 2);
+        assert_eq!(split.n_points(), mesh.n_points() + 3);
+        assert_eq!(split.n_faces(), mesh.n_faces() + 3 * 3);
+        assert!(split.face_warpage().iter().all(|&w| w < 1e-12));
+        assert!(split.check().is_ok());
+        for (a, b) in split.cell_volumes().iter().zip(mesh.cell_volumes()) {
+            assert!((a - b).abs() < 0.01 * b);
+        }
+        // The triangles of each face add up to its area vector.
+        for (p, q) in split.patches().iter().zip(mesh.patches()) {
+            let sum = |m: &Mesh, r: std::ops::Range<usize>| {
+                r.fold(Vector::zero(), |s, f| s + m.face_areas()[f])
+            };
+            assert!((sum(&split, p.range()) - sum(&mesh, q.range())).mag() < 1e-12);
+        }
+        assert_eq!(split.face_zone("all").unwrap().len(), split.n_faces());
+    }
+}
@@ -4,13 +4,17 @@ use dugong_types::tensor::Vector;
 
 use crate::error::MeshError;
 use crate::geometry;
+use crate::least_squares::LeastSquaresVectors;
+use crate::non_ortho::NonOrthoCorrection;
 
 /// The topology engine for polyhedral meshes.
 ///
 /// Stores the minimal set of mesh data — point coordinates, face-vertex
 /// connectivity, and owner/neighbor cell indices — and lazily derives
-/// geometry (cell volumes, cell centers, face area vectors, face centers)
-/// and connectivity (cell-cells, cell-faces, cell-points) on first access.
+/// geometry (cell volumes, cell centers, face area vectors, face centers,
+/// non-orthogonal and skewness correction vectors)
+/// and connectivity (cell-cells, cell-faces, cell-points, edges) on first
+/// access.
 ///
 /// # Mesh topology conventions (OpenFOAM-compatible)
 ///
@@ -24,8 +28,9 @@ use crate::geometry;
 /// The `neighbor` slice contains exactly one entry per internal face, so
 /// `neighbor.len()` defines the number of internal faces.
 ///
-/// All data is immutable after construction. Lazy fields use [`OnceLock`] so
-/// the struct is `Send + Sync` without `unsafe`.
+/// Topology is immutable after construction; only point coordinates can
+/// change, through [`Mesh::move_points`](crate::Mesh::move_points). Lazy
+/// fields use [`OnceLock`] so the struct is `Send + Sync` without `unsafe`.
 pub struct PrimitiveMesh {
     points: Vec<Vector>,
     faces: Vec<Vec<usize>>,
@@ -37,10 +42,17 @@ pub struct PrimitiveMesh {
     cell_volumes: OnceLock<Vec<f64>>,
     face_centers: OnceLock<Vec<Vector>>,
     face_areas: OnceLock<Vec<Vector>>,
+    non_ortho: OnceLock<NonOrthoCorrection>,
+    skewness_vectors: OnceLock<Vec<Vector>>,
+    least_squares: OnceLock<LeastSquaresVectors>,
 
     cell_cells: OnceLock<Vec<Vec<usize>>>,
     cell_faces: OnceLock<Vec<Vec<usize>>>,
     cell_points: OnceLock<Vec<Vec<usize>>>,
+    edges: OnceLock<Vec<[usize; 2]>>,
+    face_edges: OnceLock<Vec<Vec<usize>>>,
+    cell_edges: OnceLock<Vec<Vec<usize>>>,
+    point_edges: OnceLock<Vec<Vec<usize>>>,
 }
 
 impl PrimitiveMesh {
@@ -117,9 +129,16 @@ impl PrimitiveMesh {
             cell_volumes: OnceLock::new(),
             face_centers: OnceLock::new(),
             face_areas: OnceLock::new(),
+            non_ortho: OnceLock::new(),
+            skewness_vectors: OnceLock::new(),
+            least_squares: OnceLock::new(),
             cell_cells: OnceLock::new(),
             cell_faces: OnceLock::new(),
             cell_points: OnceLock::new(),
+            edges: OnceLock::new(),
+            face_edges: OnceLock::new(),
+            cell_edges: OnceLock::new(),
+            point_edges: OnceLock::new(),
         })
     }
 
@@ -188,13 +207,7 @@ impl PrimitiveMesh {
     /// `points` bounds by `new()`.
     fn ensure_face_geometry(&self) {
         self.face_centers.get_or_init(|| {
-            let mut centers = Vec::with_capacity(self.faces.len());
-            let mut areas = Vec::with_capacity(self.faces.len());
-            for f in &self.faces {
-                let (fc, fa) = geometry::compute_face_geometry(&self.points, f);
-                centers.push(fc);
-                areas.push(fa);
-            }
+            let (centers, areas) = geometry::compute_all_face_geometry(&self.points, &self.faces);
             let _ = self.face_areas.set(areas);
             centers
         });
@@ -262,6 +275,173 @@ impl PrimitiveMesh {
         self.cell_centers.get().unwrap()
     }
 
+    /// Returns the over-relaxed non-orthogonality split of each face area
+    /// vector. Lazily computed on first access.
+    pub fn non_ortho_correction(&self) -> &NonOrthoCorrection {
+        self.non_ortho.get_or_init(|| {
+            NonOrthoCorrection::compute(
+                self.face_areas(),
+                self.face_centers(),
+                self.cell_centers(),
+                &self.owner,
+                &self.neighbor,
+            )
+        })
+    }
+
+    /// Returns the skewness correction vector of each face, from the point
+    /// where the owner-to-neighbor line crosses the face to the face center.
+    /// Lazily computed on first access.
+    ///
+    /// Zero on boundary faces. The returned slice has length `n_faces()`.
+    pub fn skewness_vectors(&self) -> &[Vector] {
+        self.skewness_vectors.get_or_init(|| {
+            geometry::compute_skewness_vectors(
+                self.face_areas(),
+                self.face_centers(),
+                self.cell_centers(),
+                &self.owner,
+                &self.neighbor,
+            )
+        })
+    }
+
+    /// Returns the weighted least-squares gradient vectors of each face.
+    /// Lazily computed on first access.
+    pub fn least_squares_vectors(&self) -> &LeastSquaresVectors {
+        self.least_squares.get_or_init(|| {
+            LeastSquaresVectors::compute(
+                self.face_centers(),
+                self.cell_centers(),
+                &self.owner,
+                &self.neighbor,
+            )
+        })
+    }
+
+    /// Maps every point through `f`, optionally reversing the vertex order of
+    /// all faces, and discards the cached geometry.
+    ///
+    /// Reversal is needed when `f` changes handedness (a reflection), so that
+    /// area vectors keep pointing out of their owner cells. Connectivity
+    /// caches are kept since the topology is unchanged, except the edge
+    /// caches when faces are reversed.
+    pub(crate) fn transform_points(&mut self, f: impl Fn(Vector) -> Vector, reverse_faces: bool) {
+        for p in &mut self.points {
+            *p = f(*p);
+        }
+        if reverse_faces {
+            for face in &mut self.faces {
+                face.reverse();
+            }
+            // Edge numbering follows the face vertex order.
+            self.edges = OnceLock::new();
+            self.face_edges = OnceLock::new();
+            self.cell_edges = OnceLock::new();
+            self.point_edges = OnceLock::new();
+        }
+        self.cell_centers = OnceLock::new();
+        self.cell_volumes = OnceLock::new();
+        self.face_centers = OnceLock::new();
+        self.face_areas = OnceLock::new();
+        self.non_ortho = OnceLock::new();
+        self.skewness_vectors = OnceLock::new();
+        self.least_squares = OnceLock::new();
+    }
+
+    /// Reverses the vertex order of the given faces and discards the cached
+    /// geometry and edges.
+    ///
+    /// Connectivity between cells is unchanged; only the direction of the
+    /// face area vectors flips.
+    pub(crate) fn reverse_faces(&mut self, faces: &[usize]) {
+        for &f in faces {
+            self.faces[f].reverse();
+        }
+        self.edges = OnceLock::new();
+        self.face_edges = OnceLock::new();
+        self.cell_edges = OnceLock::new();
+        self.point_edges = OnceLock::new();
+        self.cell_centers = OnceLock::new();
+        self.cell_volumes = OnceLock::new();
+        self.face_centers = OnceLock::new();
+        self.face_areas = OnceLock::new();
+        self.non_ortho = OnceLock::new();
+        self.skewness_vectors = OnceLock::new();
+        self.least_squares = OnceLock::new();
+    }
+
+    /// Replaces the point coordinates, keeping the topology.
+    ///
+    /// Cached geometry is updated incrementally: only faces touching a moved
+    /// point, and the cells adjacent to those faces, are recomputed. Caches
+    /// that have not been computed yet stay lazy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points.len() != n_points()`.
+    pub(crate) fn set_points(&mut self, points: Vec<Vector>) {
+        assert_eq!(points.len(), self.points.len(), "point count mismatch");
+        self.non_ortho = OnceLock::new();
+        self.skewness_vectors = OnceLock::new();
+        self.least_squares = OnceLock::new();
+        let had_cells = self.cell_volumes.get().is_some();
+        if self.face_centers.get().is_none() && !had_cells {
+            self.points = points;
+            return;
+        }
+        // Cell updates read face geometry, so bring it up to date first.
+        self.ensure_face_geometry();
+        let moved: Vec<bool> = self
+            .points
+            .iter()
+            .zip(&points)
+            .map(|(old, new)| old != new)
+            .collect();
+        self.points = points;
+
+        let changed_faces: Vec<usize> = (0..self.faces.len())
+            .filter(|&f| self.faces[f].iter().any(|&p| moved[p]))
+            .collect();
+        // Safety: ensure_face_geometry() above initialized both face caches.
+        let centers = self.face_centers.get_mut().unwrap();
+        // Safety: as above.
+        let areas = self.face_areas.get_mut().unwrap();
+        for &f in &changed_faces {
+            (centers[f], areas[f]) = geometry::compute_face_geometry(&self.points, &self.faces[f]);
+        }
+        if !had_cells {
+            return;
+        }
+
+        let mut changed_cells: Vec<usize> = changed_faces
+            .iter()
+            .flat_map(|&f| {
+                let neighbor = self.neighbor.get(f).copied();
+                std::iter::once(self.owner[f]).chain(neighbor)
+            })
+            .collect();
+        changed_cells.sort_unstable();
+        changed_cells.dedup();
+        let cell_faces = self.cell_faces.get_or_init(|| {
+            geometry::compute_cell_faces(&self.owner, &self.neighbor, self.n_cells)
+        });
+        // Safety: had_cells guarantees cell_volumes is initialized, and
+        // ensure_cell_geometry() always sets cell_centers alongside it.
+        let volumes = self.cell_volumes.get_mut().unwrap();
+        // Safety: as above.
+        let cell_centers = self.cell_centers.get_mut().unwrap();
+        for c in changed_cells {
+            (volumes[c], cell_centers[c]) = geometry::compute_single_cell_geometry(
+                c,
+                &cell_faces[c],
+                centers,
+                areas,
+                &self.owner,
+            );
+        }
+    }
+
     // Lazy connectivity accessors
 
     /// Computes and caches the cell-to-face connectivity.
@@ -325,6 +505,55 @@ impl PrimitiveMesh {
             geometry::compute_cell_points(cf, &self.faces)
         })
     }
+
+    /// Computes and caches the unique edges and the face-to-edge addressing.
+    fn ensure_edges(&self) {
+        self.edges.get_or_init(|| {
+            let (edges, face_edges) = geometry::compute_edges(&self.faces);
+            let _ = self.face_edges.set(face_edges);
+            edges
+        });
+    }
+
+    /// Returns the unique edges of the mesh as point index pairs in
+    /// ascending order. Lazily computed on first access.
+    ///
+    /// Edges are numbered in order of first appearance in `faces()`.
+    pub fn edges(&self) -> &[[usize; 2]] {
+        self.ensure_edges();
+        // Safety: ensure_edges() initializes edges via get_or_init(), so it is never None.
+        self.edges.get().unwrap()
+    }
+
+    /// Returns the edge indices of each face. Lazily computed on first
+    /// access.
+    ///
+    /// `face_edges()[f][i]` is the edge from vertex `i` to vertex `i + 1`
+    /// (cyclically) of face `f`. The returned slice has length `n_faces()`.
+    pub fn face_edges(&self) -> &[Vec<usize>] {
+        self.ensure_edges();
+        // Safety: ensure_edges() sets face_edges as a side effect and guarantees it is initialized.
+        self.face_edges.get().unwrap()
+    }
+
+    /// Returns the edge indices of each cell, sorted and without
+    /// duplicates. Lazily computed on first access.
+    ///
+    /// The returned slice has length `n_cells()`.
+    pub fn cell_edges(&self) -> &[Vec<usize>] {
+        self.cell_edges.get_or_init(|| {
+            geometry::compute_cell_edges(self.ensure_cell_faces(), self.face_edges())
+        })
+    }
+
+    /// Returns the edge indices meeting at each point, in ascending order.
+    /// Lazily computed on first access.
+    ///
+    /// The returned slice has length `n_points()`.
+    pub fn point_edges(&self) -> &[Vec<usize>] {
+        self.point_edges
+            .get_or_init(|| geometry::compute_point_edges(self.edges(), self.points.len()))
+    }
 }
 
 #[cfg(test)]
@@ -507,6 +736,8 @@ mod tests {
     }
 
     #[test]
+    // Spelled with `+` to exercise `Add` rather than `AddAssign`.
+    #[allow(clippy::assign_op_pattern)]
     fn test_face_areas_sum_zero_for_closed_cell() {
         // For a single closed cell, the sum of all face area vectors (outward) should be zero
         let mesh = make_unit_cube_mesh();
@@ -555,6 +786,23 @@ mod tests {
         assert_eq!(pts.len(), 8, "unit cube should have 8 points");
     }
 
+    #[test]
+    fn test_edges_two_cells_share_four() {
+        let mesh = make_two_cell_mesh();
+        assert_eq!(mesh.edges().len(), 20);
+        assert_eq!(mesh.cell_edges()[0].len(), 12);
+        assert_eq!(mesh.cell_edges()[1].len(), 12);
+        let shared = mesh.cell_edges()[0]
+            .iter()
+            .filter(|e| mesh.cell_edges()[1].contains(e))
+            .count();
+        assert_eq!(shared, 4);
+        // Points on the shared face meet four edges, the others three.
+        assert_eq!(mesh.point_edges()[1].len(), 4);
+        assert_eq!(mesh.point_edges()[0].len(), 3);
+        assert_eq!(mesh.face_edges()[0].len(), 4);
+    }
+
     #[test]
     fn test_primitive_mesh_is_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
@@ -0,0 +1,278 @@
+use dugong_types::FieldValue;
+use dugong_types::tensor::Vector;
+
+use crate::decompose::{Decomposition, DecompositionMethod, SubMesh};
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::primitive_mesh::PrimitiveMesh;
+use crate::zone::Zone;
+
+/// Addressing from the subdomains of a new [`Decomposition`] back to those
+/// of the one it was redistributed from.
+///
+/// Global cell and face indices are unchanged by redistribution, so data is
+/// migrated by looking up where each new local entity lived before.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    cell_sources: Vec<Vec<(usize, usize)>>,
+    face_sources: Vec<Vec<(usize, usize, bool)>>,
+}
+
+impl Migration {
+    /// Returns the `(old rank, old local cell)` of local cell `cell` on new
+    /// rank `rank`.
+    pub fn cell_source(&self, rank: usize, cell: usize) -> (usize, usize) {
+        self.cell_sources[rank][cell]
+    }
+
+    /// Returns the `(old rank, old local face)` of local face `face` on new
+    /// rank `rank`, and whether the two are oriented oppositely.
+    pub fn face_source(&self, rank: usize, face: usize) -> (usize, usize, bool) {
+        self.face_sources[rank][face]
+    }
+
+    /// Moves per-cell values, indexed by old rank, onto the new ranks.
+    pub fn migrate_cells<T: Clone>(&self, old: &[Vec<T>]) -> Vec<Vec<T>> {
+        self.cell_sources
+            .iter()
+            .map(|cells| cells.iter().map(|&(r, c)| old[r][c].clone()).collect())
+            .collect()
+    }
+
+    /// Moves per-face values, indexed by old rank, onto the new ranks.
+    ///
+    /// Values of faces whose orientation changed are negated, as for face
+    /// fluxes.
+    pub fn migrate_faces<T: FieldValue>(&self, old: &[Vec<T>]) -> Vec<Vec<T>> {
+        self.face_sources
+            .iter()
+            .map(|faces| {
+                faces
+                    .iter()
+                    .map(|&(r, f, flip)| if flip { -old[r][f] } else { old[r][f] })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl Decomposition {
+    /// Reassembles the undecomposed mesh from the subdomains.
+    ///
+    /// Cells, faces and points take their global indices from the subdomain
+    /// maps, and zones are the union of the subdomain zones.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the subdomains do not form a valid mesh.
+    pub fn reconstruct(&self) -> Result<Mesh, MeshError> {
+        let parts = self.parts();
+        let n_points = parts
+            .iter()
+            .flat_map(|p| p.point_map().iter().map(|&g| g + 1))
+            .max()
+            .unwrap_or(0);
+        let n_faces = parts
+            .iter()
+            .flat_map(|p| p.face_map().iter().map(|&g| g + 1))
+            .max()
+            .unwrap_or(0);
+        let n_internal = self.patches().first().map_or(n_faces, |p| p.start());
+
+        let mut points = vec![Vector::zero(); n_points];
+        let mut faces = vec![Vec::new(); n_faces];
+        let mut owner = vec![0; n_faces];
+        let mut neighbor = vec![0; n_internal];
+        for part in parts {
+            let mesh = part.mesh();
+            for (l, &g) in part.point_map().iter().enumerate() {
+                points[g] = mesh.points()[l];
+            }
+            let cell = |c: usize| part.cell_map()[c];
+            for (l, &g) in part.face_map().iter().enumerate() {
+                let own = cell(mesh.owner()[l]);
+                let flipped = part.is_face_flipped(l);
+                if faces[g].is_empty() {
+                    faces[g] = mesh.faces()[l]
+                        .iter()
+                        .map(|&p| part.point_map()[p])
+                        .collect();
+                    if flipped {
+                        faces[g].reverse();
+                    }
+                }
+                if let Some(&n) = mesh.neighbor().get(l) {
+                    owner[g] = own;
+                    neighbor[g] = cell(n);
+                } else if flipped {
+                    neighbor[g] = own;
+                } else {
+                    owner[g] = own;
+                }
+            }
+        }
+
+        let primitive = PrimitiveMesh::new(points, faces, owner, neighbor)?;
+        let mut mesh = Mesh::new(primitive, self.patches().to_vec())?;
+        let Some(first) = parts.first() else {
+            return Ok(mesh);
+        };
+        let gather = |zones: fn(&Mesh) -> &[Zone], map: fn(&SubMesh) -> &[usize]| {
+            (0..zones(first.mesh()).len())
+                .map(|z| {
+                    let name = zones(first.mesh())[z].name();
+                    Zone::new(
+                        name,
+                        parts
+                            .iter()
+                            .flat_map(|p| zones(p.mesh())[z].indices().iter().map(|&i| map(p)[i])),
+                    )
+                })
+                .collect()
+        };
+        mesh.cell_zones = gather(Mesh::cell_zones, SubMesh::cell_map);
+        mesh.face_zones = gather(Mesh::face_zones, SubMesh::face_map);
+        mesh.point_zones = gather(Mesh::point_zones, SubMesh::point_map);
+        Ok(mesh)
+    }
+
+    /// Moves cells between subdomains according to a new global cell → rank
+    /// assignment, for example to restore balance after adaptive refinement
+    /// or to restart on a different number of ranks.
+    ///
+    /// Global cell, face and point indices are preserved, so the returned
+    /// [`Migration`] maps data from the old subdomains to the new ones.
+    ///
+    /// # Errors
+    ///
+    /// See [`Mesh::decompose_with`].
+    pub fn redistribute(
+        &self,
+        cell_rank: &[usize],
+        n_parts: usize,
+    ) -> Result<(Decomposition, Migration), MeshError> {
+        let mesh = self.reconstruct()?;
+        let decomposition = mesh.decompose_with(cell_rank, n_parts)?;
+
+        let mut face_location = vec![(0, 0, false); mesh.n_faces()];
+        for part in self.parts().iter().rev() {
+            for (l, &g) in part.face_map().iter().enumerate() {
+                face_location[g] = (part.rank(), l, part.is_face_flipped(l));
+            }
+        }
+        let cell_sources = decomposition
+            .parts()
+            .iter()
+            .map(|p| p.cell_map().iter().map(|&g| self.locate_cell(g)).collect())
+            .collect();
+        let face_sources = decomposition
+            .parts()
+            .iter()
+            .map(|p| {
+                p.face_map()
+                    .iter()
+                    .enumerate()
+                    .map(|(l, &g)| {
+                        let (rank, face, flipped) = face_location[g];
+                        (rank, face, flipped != p.is_face_flipped(l))
+                    })
+                    .collect()
+            })
+            .collect();
+        let migration = Migration {
+            cell_sources,
+            face_sources,
+        };
+        Ok((decomposition, migration))
+    }
+
+    /// Repartitions the cells into `n_parts` balanced subdomains with
+    /// `method` and redistributes them.
+    ///
+    /// # Errors
+    ///
+    /// See [`Mesh::partition`] and [`Decomposition::redistribute`].
+    pub fn rebalance(
+        &self,
+        n_parts: usize,
+        method: DecompositionMethod,
+    ) -> Result<(Decomposition, Migration), MeshError> {
+        let cell_rank = self.reconstruct()?.partition(n_parts, method)?;
+        self.redistribute(&cell_rank, n_parts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_reconstruct_recovers_mesh() {
+        let mut mesh = box_mesh([4, 3, 2], [4.0, 3.0, 2.0]);
+        mesh.add_cell_zone(Zone::new("corner", [0, 23])).unwrap();
+        let decomp = mesh.decompose(3, DecompositionMethod::Graph).unwrap();
+        let rebuilt = decomp.reconstruct().unwrap();
+        assert_eq!(rebuilt.points(), mesh.points());
+        assert_eq!(rebuilt.faces(), mesh.faces());
+        assert_eq!(rebuilt.owner(), mesh.owner());
+        assert_eq!(rebuilt.neighbor(), mesh.neighbor());
+        assert_eq!(rebuilt.patches(), mesh.patches());
+        assert_eq!(rebuilt.cell_zones(), mesh.cell_zones());
+    }
+
+    #[test]
+    fn test_redistribute_migrates_cell_and_face_data() {
+        let mesh = box_mesh([6, 2, 1], [6.0, 2.0, 1.0]);
+        let decomp = mesh
+            .decompose(2, DecompositionMethod::Hierarchical)
+            .unwrap();
+        // Tag each cell with its global index and each face with its global
+        // flux, as seen from the local orientation.
+        let flux = |g: usize| g as f64 + 1.0;
+        let cells: Vec<Vec<usize>> = decomp
+            .parts()
+            .iter()
+            .map(|p| p.cell_map().to_vec())
+            .collect();
+        let faces: Vec<Vec<f64>> = decomp
+            .parts()
+            .iter()
+            .map(|p| {
+                (0..p.face_map().len())
+                    .map(|l| {
+                        let v = flux(p.face_map()[l]);
+                        if p.is_face_flipped(l) { -v } else { v }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let (new, migration) = decomp.rebalance(3, DecompositionMethod::Graph).unwrap();
+        assert_eq!(new.n_parts(), 3);
+        let cells = migration.migrate_cells(&cells);
+        let faces = migration.migrate_faces(&faces);
+        for part in new.parts() {
+            assert_eq!(part.mesh().n_cells(), 4);
+            assert_eq!(cells[part.rank()], part.cell_map());
+            for (l, &g) in part.face_map().iter().enumerate() {
+                let expected = if part.is_face_flipped(l) {
+                    -flux(g)
+                } else {
+                    flux(g)
+                };
+                assert_eq!(faces[part.rank()][l], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_redistribute_rejects_invalid_assignment() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let decomp = mesh.decompose_with(&[0, 1], 2).unwrap();
+        assert!(matches!(
+            decomp.redistribute(&[0], 1),
+            Err(MeshError::CellRankLengthMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,836 @@
+use std::collections::HashMap;
+
+use dugong_types::FieldValue;
+use dugong_types::tensor::Vector;
+
+use crate::assemble::{BoundaryGroup, InternalFace, assemble};
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::patch::PatchKind;
+use crate::zone::Zone;
+
+/// Parametric position of each hex corner in the unit cube.
+const CORNER_PARAM: [[usize; 3]; 8] = [
+    [0, 0, 0],
+    [1, 0, 0],
+    [1, 1, 0],
+    [0, 1, 0],
+    [0, 0, 1],
+    [1, 0, 1],
+    [1, 1, 1],
+    [0, 1, 1],
+];
+
+/// Outward-oriented hex faces. Face `s` lies on the side where parametric
+/// coordinate `s / 2` equals `s % 2`.
+const HEX_FACES: [[usize; 4]; 6] = [
+    [0, 4, 7, 3],
+    [1, 2, 6, 5],
+    [0, 1, 5, 4],
+    [3, 7, 6, 2],
+    [0, 3, 2, 1],
+    [4, 5, 6, 7],
+];
+
+const HEX_EDGES: [[usize; 2]; 12] = [
+    [0, 1],
+    [3, 2],
+    [4, 5],
+    [7, 6],
+    [0, 3],
+    [1, 2],
+    [4, 7],
+    [5, 6],
+    [0, 4],
+    [1, 5],
+    [2, 6],
+    [3, 7],
+];
+
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+fn face_key(quad: [usize; 4]) -> [usize; 4] {
+    let mut key = quad;
+    key.sort_unstable();
+    key
+}
+
+/// A current (leaf) cell of the refinement tree.
+#[derive(Debug, Clone)]
+pub(crate) struct LeafCell {
+    pub(crate) corners: [usize; 8],
+    pub(crate) level: usize,
+    /// History node of the parent this cell was split from.
+    pub(crate) parent: Option<usize>,
+    /// Indices of the cell zones containing this cell.
+    pub(crate) zones: Vec<usize>,
+}
+
+impl LeafCell {
+    fn face(&self, slot: usize) -> [usize; 4] {
+        HEX_FACES[slot].map(|v| self.corners[v])
+    }
+}
+
+/// The map from new cells to the cells they replaced after a refinement or
+/// unrefinement step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefinementMap {
+    sources: Vec<Vec<usize>>,
+}
+
+impl RefinementMap {
+    /// Returns the identity map over `n` cells.
+    pub fn identity(n: usize) -> Self {
+        Self {
+            sources: (0..n).map(|c| vec![c]).collect(),
+        }
+    }
+
+    /// Returns the number of new cells.
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Returns `true` if there are no new cells.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Returns the old cells each new cell was formed from: the parent for a
+    /// child of a split cell, all former children for a merged cell, and the
+    /// cell itself otherwise.
+    pub fn sources(&self, cell: usize) -> &[usize] {
+        &self.sources[cell]
+    }
+
+    /// Returns the new → old cell map, taking the first source of each cell.
+    pub fn cell_map(&self) -> Vec<usize> {
+        self.sources.iter().map(|s| s[0]).collect()
+    }
+
+    /// Returns the composition of `self` followed by `next`.
+    pub fn then(&self, next: &RefinementMap) -> RefinementMap {
+        RefinementMap {
+            sources: next
+                .sources
+                .iter()
+                .map(|mid| {
+                    let mut old: Vec<usize> = mid
+                        .iter()
+                        .flat_map(|&m| self.sources[m].iter().copied())
+                        .collect();
+                    old.sort_unstable();
+                    old.dedup();
+                    old
+                })
+                .collect(),
+        }
+    }
+
+    /// Transfers per-cell values by injection from the first source cell.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a mapped index is out of range of `old`.
+    pub fn map_cell_values<T: Clone>(&self, old: &[T]) -> Vec<T> {
+        self.sources.iter().map(|s| old[s[0]].clone()).collect()
+    }
+
+    /// Transfers per-cell values conservatively: each new cell receives the
+    /// volume-weighted mean of its sources. Children inherit their parent's
+    /// value and merged cells the mean of their children, so the integral
+    /// `sum(V * value)` is preserved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a mapped index is out of range of `old` or `old_volumes`.
+    pub fn map_conservative<T: FieldValue>(&self, old: &[T], old_volumes: &[f64]) -> Vec<T> {
+        self.sources
+            .iter()
+            .map(|s| {
+                let volume: f64 = s.iter().map(|&c| old_volumes[c]).sum();
+                s.iter()
+                    .fold(T::zero(), |acc, &c| acc + old[c] * old_volumes[c])
+                    * (1.0 / volume)
+            })
+            .collect()
+    }
+}
+
+/// Hierarchical 2:1 balanced refinement of hexahedral meshes.
+///
+/// Keeps the refinement tree of an initially all-hex mesh. Each selected
+/// cell is split into 8 children at its edge midpoints, face centers, and
+/// center; complete sibling groups can later be merged back. Neighboring cells sharing a point never differ by more than one
+/// level; the selection is extended as needed to maintain this. A coarse
+/// cell next to refined neighbors becomes a polyhedron: its face is replaced
+/// by the four child faces and hanging edge midpoints are inserted into the
+/// vertex lists of the faces around it.
+///
+/// Boundary faces of split cells inherit the patch of their parent face;
+/// cell zones are inherited by children. Face zones are not carried over.
+pub struct HexRefiner {
+    pub(crate) points: Vec<Vector>,
+    pub(crate) cells: Vec<LeafCell>,
+    edge_points: HashMap<(usize, usize), usize>,
+    face_points: HashMap<[usize; 4], usize>,
+    center_points: HashMap<[usize; 8], usize>,
+    /// Cells that have been split, indexed by history node.
+    history: Vec<LeafCell>,
+    patches: Vec<(String, PatchKind)>,
+    boundary: HashMap<[usize; 4], usize>,
+    cell_zone_names: Vec<String>,
+    point_zones: Vec<Zone>,
+}
+
+impl HexRefiner {
+    /// Starts a refinement tree from a mesh of hexahedra, all at level 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::NotHexahedral`] if a cell is not a hexahedron
+    /// with six quadrilateral faces.
+    pub fn new(mesh: &Mesh) -> Result<Self, MeshError> {
+        let mut cells = Vec::with_capacity(mesh.n_cells());
+        for cell in 0..mesh.n_cells() {
+            let corners = hex_corners(mesh, cell).ok_or(MeshError::NotHexahedral { cell })?;
+            let zones = mesh
+                .cell_zones()
+                .iter()
+                .enumerate()
+                .filter(|(_, z)| z.contains(cell))
+                .map(|(i, _)| i)
+                .collect();
+            cells.push(LeafCell {
+                corners,
+                level: 0,
+                parent: None,
+                zones,
+            });
+        }
+        let mut boundary = HashMap::new();
+        for (i, patch) in mesh.patches().iter().enumerate() {
+            for f in patch.range() {
+                let quad: [usize; 4] = mesh.faces()[f].as_slice().try_into().map_err(|_| {
+                    MeshError::NotHexahedral {
+                        cell: mesh.owner()[f],
+                    }
+                })?;
+                boundary.insert(face_key(quad), i);
+            }
+        }
+        Ok(Self {
+            points: mesh.points().to_vec(),
+            cells,
+            edge_points: HashMap::new(),
+            face_points: HashMap::new(),
+            center_points: HashMap::new(),
+            history: Vec::new(),
+            patches: mesh
+                .patches()
+                .iter()
+                .map(|p| (p.name().to_string(), p.kind().clone()))
+                .collect(),
+            boundary,
+            cell_zone_names: mesh
+                .cell_zones()
+                .iter()
+                .map(|z| z.name().to_string())
+                .collect(),
+            point_zones: mesh.point_zones().to_vec(),
+        })
+    }
+
+    /// Returns the number of current cells.
+    pub fn n_cells(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns the refinement level of each current cell.
+    pub fn cell_levels(&self) -> Vec<usize> {
+        self.cells.iter().map(|c| c.level).collect()
+    }
+
+    /// Splits the given cells (plus any needed to keep 2:1 balance) into 8.
+    ///
+    /// Children replace their parent in the cell order, so unaffected cells
+    /// keep their relative order.
+    pub fn refine(&mut self, cells: &[usize]) -> RefinementMap {
+        let selected = self.balance_refinement(cells);
+        let old = std::mem::take(&mut self.cells);
+        let mut sources = Vec::with_capacity(old.len() + 7 * cells.len());
+        for (i, cell) in old.into_iter().enumerate() {
+            if selected[i] {
+                let children = self.split(&cell);
+                self.history.push(cell);
+                sources.extend((0..8).map(|_| vec![i]));
+                self.cells.extend(children);
+            } else {
+                sources.push(vec![i]);
+                self.cells.push(cell);
+            }
+        }
+        RefinementMap { sources }
+    }
+
+    /// Merges complete groups of 8 sibling cells back into their parent.
+    ///
+    /// A group is merged only if all its members are listed in `cells` and
+    /// the merged parent stays within one level of every cell it touches.
+    /// Each merged parent takes the place of its first child in the cell
+    /// order.
+    pub fn unrefine(&mut self, cells: &[usize]) -> RefinementMap {
+        let mut listed = vec![false; self.cells.len()];
+        for &c in cells {
+            listed[c] = true;
+        }
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (c, cell) in self.cells.iter().enumerate() {
+            if let Some(node) = cell.parent {
+                groups.entry(node).or_default().push(c);
+            }
+        }
+        let active = self.active_points();
+        let point_cells = self.point_cells(&active);
+        let mut merged_into = vec![None; self.cells.len()];
+        for (&node, members) in &groups {
+            let level = self.history[node].level;
+            let mergeable = members.len() == 8
+                && members
+                    .iter()
+                    .all(|&c| listed[c] && self.cells[c].level == level + 1)
+                && members.iter().all(|&c| {
+                    self.touching_points(&self.cells[c], &active)
+                        .iter()
+                        .all(|&p| {
+                            point_cells[p]
+                                .iter()
+                                .all(|&d| self.cells[d].level <= level + 1)
+                        })
+                });
+            if mergeable {
+                for &c in members {
+                    merged_into[c] = Some(node);
+                }
+            }
+        }
+
+        let old = std::mem::take(&mut self.cells);
+        let mut sources: Vec<Vec<usize>> = Vec::with_capacity(old.len());
+        let mut slot_of_node: HashMap<usize, usize> = HashMap::new();
+        for (i, cell) in old.iter().enumerate() {
+            match merged_into[i] {
+                Some(node) => match slot_of_node.get(&node) {
+                    Some(&slot) => sources[slot].push(i),
+                    None => {
+                        slot_of_node.insert(node, self.cells.len());
+                        self.merge_boundary(node, &old, &groups[&node]);
+                        self.cells.push(self.history[node].clone());
+                        sources.push(vec![i]);
+                    }
+                },
+                None => {
+                    self.cells.push(cell.clone());
+                    sources.push(vec![i]);
+                }
+            }
+        }
+        RefinementMap { sources }
+    }
+
+    /// Moves the boundary faces of merged children back to their parent.
+    fn merge_boundary(&mut self, node: usize, cells: &[LeafCell], members: &[usize]) {
+        let parent = &self.history[node];
+        for slot in 0..6 {
+            let child_keys: Vec<[usize; 4]> = members
+                .iter()
+                .map(|&c| face_key(cells[c].face(slot)))
+                .filter(|k| self.boundary.contains_key(k))
+                .collect();
+            if child_keys.len() == 4 {
+                let patch = self.boundary[&child_keys[0]];
+                for key in &child_keys {
+                    self.boundary.remove(key);
+                }
+                self.boundary.insert(face_key(parent.face(slot)), patch);
+            }
+        }
+    }
+
+    /// Returns `true` for each point used as a corner of a current cell.
+    pub(crate) fn active_points(&self) -> Vec<bool> {
+        let mut active = vec![false; self.points.len()];
+        for cell in &self.cells {
+            for &p in &cell.corners {
+                active[p] = true;
+            }
+        }
+        active
+    }
+
+    /// Returns the active points on the closure of a cell: its corners and any
+    /// active edge midpoints and face centers.
+    pub(crate) fn touching_points(&self, cell: &LeafCell, active: &[bool]) -> Vec<usize> {
+        let mut points = cell.corners.to_vec();
+        for [a, b] in HEX_EDGES {
+            if let Some(&m) = self
+                .edge_points
+                .get(&edge_key(cell.corners[a], cell.corners[b]))
+            {
+                points.push(m);
+            }
+        }
+        for slot in 0..6 {
+            if let Some(&m) = self.face_points.get(&face_key(cell.face(slot))) {
+                points.push(m);
+            }
+        }
+        points.retain(|&p| active[p]);
+        points
+    }
+
+    /// Returns, for each active point, the current cells touching it.
+    pub(crate) fn point_cells(&self, active: &[bool]) -> Vec<Vec<usize>> {
+        let mut point_cells = vec![Vec::new(); self.points.len()];
+        for (c, cell) in self.cells.iter().enumerate() {
+            for p in self.touching_points(cell, active) {
+                point_cells[p].push(c);
+            }
+        }
+        point_cells
+    }
+
+    /// Extends a refinement selection until splitting it keeps every pair of
+    /// point-sharing cells within one level.
+    fn balance_refinement(&self, cells: &[usize]) -> Vec<bool> {
+        let active = self.active_points();
+        let point_cells = self.point_cells(&active);
+        let mut selected = vec![false; self.cells.len()];
+        let mut queue = Vec::new();
+        for &c in cells {
+            if !selected[c] {
+                selected[c] = true;
+                queue.push(c);
+            }
+        }
+        while let Some(c) = queue.pop() {
+            let level = self.cells[c].level;
+            for p in self.touching_points(&self.cells[c], &active) {
+                for &d in &point_cells[p] {
+                    if !selected[d] && self.cells[d].level < level {
+                        selected[d] = true;
+                        queue.push(d);
+                    }
+                }
+            }
+        }
+        selected
+    }
+
+    fn add_point(&mut self, members: &[usize]) -> usize {
+        let sum = members
+            .iter()
+            .fold(Vector::zero(), |s, &p| s + self.points[p]);
+        self.points.push(sum * (1.0 / members.len() as f64));
+        self.points.len() - 1
+    }
+
+    /// Splits a cell into its 8 children, creating (or reusing) the edge
+    /// midpoints and face centers, and passes boundary faces down.
+    fn split(&mut self, cell: &LeafCell) -> [LeafCell; 8] {
+        let c = cell.corners;
+        let mut lattice = [[[0; 3]; 3]; 3];
+        for (i, plane) in lattice.iter_mut().enumerate() {
+            for (j, row) in plane.iter_mut().enumerate() {
+                for (k, slot) in row.iter_mut().enumerate() {
+                    let members: Vec<usize> = (0..8)
+                        .filter(|&v| {
+                            [i, j, k]
+                                .iter()
+                                .zip(CORNER_PARAM[v])
+                                .all(|(&l, q)| l == 1 || l == 2 * q)
+                        })
+                        .map(|v| c[v])
+                        .collect();
+                    *slot = match members.len() {
+                        1 => members[0],
+                        2 => match self.edge_points.get(&edge_key(members[0], members[1])) {
+                            Some(&m) => m,
+                            None => {
+                                let m = self.add_point(&members);
+                                self.edge_points.insert(edge_key(members[0], members[1]), m);
+                                m
+                            }
+                        },
+                        4 => {
+                            let key = face_key([members[0], members[1], members[2], members[3]]);
+                            match self.face_points.get(&key) {
+                                Some(&m) => m,
+                                None => {
+                                    let m = self.add_point(&members);
+                                    self.face_points.insert(key, m);
+                                    m
+                                }
+                            }
+                        }
+                        _ => {
+                            let mut key = c;
+                            key.sort_unstable();
+                            match self.center_points.get(&key) {
+                                Some(&m) => m,
+                                None => {
+                                    let m = self.add_point(&members);
+                                    self.center_points.insert(key, m);
+                                    m
+                                }
+                            }
+                        }
+                    };
+                }
+            }
+        }
+
+        let node = self.history.len();
+        let children: [LeafCell; 8] = std::array::from_fn(|v| {
+            let p = CORNER_PARAM[v];
+            LeafCell {
+                corners: std::array::from_fn(|w| {
+                    let q = CORNER_PARAM[w];
+                    lattice[p[0] + q[0]][p[1] + q[1]][p[2] + q[2]]
+                }),
+                level: cell.level + 1,
+                parent: Some(node),
+                zones: cell.zones.clone(),
+            }
+        });
+
+        for slot in 0..6 {
+            if let Some(patch) = self.boundary.remove(&face_key(cell.face(slot))) {
+                for (v, child) in children.iter().enumerate() {
+                    if CORNER_PARAM[v][slot / 2] == slot % 2 {
+                        self.boundary.insert(face_key(child.face(slot)), patch);
+                    }
+                }
+            }
+        }
+        children
+    }
+
+    /// Inserts active edge midpoints into a face's vertex list.
+    fn face_vertices(&self, quad: [usize; 4], active: &[bool]) -> Vec<usize> {
+        let mut verts = Vec::with_capacity(8);
+        for i in 0..4 {
+            let (a, b) = (quad[i], quad[(i + 1) % 4]);
+            verts.push(a);
+            if let Some(&m) = self.edge_points.get(&edge_key(a, b))
+                && active[m]
+            {
+                verts.push(m);
+            }
+        }
+        verts
+    }
+
+    /// Returns the four sub-quads of a face that has a face center.
+    fn sub_quads(&self, quad: [usize; 4]) -> Option<[[usize; 4]; 4]> {
+        let f = *self.face_points.get(&face_key(quad))?;
+        let m: [usize; 4] = std::array::from_fn(|i| {
+            self.edge_points
+                .get(&edge_key(quad[i], quad[(i + 1) % 4]))
+                .copied()
+                .unwrap_or(usize::MAX)
+        });
+        if m.contains(&usize::MAX) {
+            return None;
+        }
+        Some([
+            [quad[0], m[0], f, m[3]],
+            [m[0], quad[1], m[1], f],
+            [f, m[1], quad[2], m[2]],
+            [m[3], f, m[2], quad[3]],
+        ])
+    }
+
+    /// Builds the polyhedral mesh of the current cells.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the generated topology fails mesh validation.
+    pub fn mesh(&self) -> Result<Mesh, MeshError> {
+        let active = self.active_points();
+        let mut by_key: HashMap<[usize; 4], Vec<usize>> = HashMap::new();
+        for (c, cell) in self.cells.iter().enumerate() {
+            for slot in 0..6 {
+                by_key.entry(face_key(cell.face(slot))).or_default().push(c);
+            }
+        }
+
+        let mut internal: Vec<InternalFace> = Vec::new();
+        let mut groups: Vec<Vec<(Vec<usize>, usize)>> = vec![Vec::new(); self.patches.len()];
+        let mut coarse_of_sub: HashMap<[usize; 4], usize> = HashMap::new();
+        let mut hanging: Vec<([usize; 4], usize)> = Vec::new();
+        for (c, cell) in self.cells.iter().enumerate() {
+            for slot in 0..6 {
+                let quad = cell.face(slot);
+                let key = face_key(quad);
+                let sharing = &by_key[&key];
+                if sharing.len() == 2 {
+                    if sharing[0] == c {
+                        internal.push((self.face_vertices(quad, &active), c, sharing[1]));
+                    }
+                } else if let Some(&patch) = self.boundary.get(&key) {
+                    groups[patch].push((self.face_vertices(quad, &active), c));
+                } else if let Some(subs) = self
+                    .sub_quads(quad)
+                    .filter(|subs| subs.iter().all(|s| by_key.contains_key(&face_key(*s))))
+                {
+                    for sub in subs {
+                        coarse_of_sub.insert(face_key(sub), c);
+                    }
+                } else {
+                    hanging.push((quad, c));
+                }
+            }
+        }
+        for (quad, fine) in hanging {
+            // Safety: with 2:1 balance, every unmatched non-boundary face is
+            // a quarter of a coarse neighbor's face.
+            let coarse = coarse_of_sub[&face_key(quad)];
+            internal.push((self.face_vertices(quad, &active), fine, coarse));
+        }
+
+        // Compact the points to those in use.
+        let mut point_map = vec![usize::MAX; self.points.len()];
+        let mut points = Vec::new();
+        for (p, _) in active.iter().enumerate().filter(|&(_, &a)| a) {
+            point_map[p] = points.len();
+            points.push(self.points[p]);
+        }
+        for (verts, _, _) in &mut internal {
+            verts.iter_mut().for_each(|p| *p = point_map[*p]);
+        }
+        let groups = self
+            .patches
+            .iter()
+            .zip(groups)
+            .map(|((name, kind), mut faces)| {
+                for (verts, _) in &mut faces {
+                    verts.iter_mut().for_each(|p| *p = point_map[*p]);
+                }
+                BoundaryGroup {
+                    name: name.clone(),
+                    kind: kind.clone(),
+                    faces,
+                }
+            })
+            .collect();
+
+        let (mut mesh, _) = assemble(points, internal, groups)?;
+        for (i, name) in self.cell_zone_names.iter().enumerate() {
+            let members = (0..self.cells.len()).filter(|&c| self.cells[c].zones.contains(&i));
+            mesh.cell_zones.push(Zone::new(name, members));
+        }
+        for zone in &self.point_zones {
+            let members = zone
+                .indices()
+                .iter()
+                .map(|&p| point_map[p])
+                .filter(|&p| p != usize::MAX);
+            mesh.point_zones.push(Zone::new(zone.name(), members));
+        }
+        Ok(mesh)
+    }
+}
+
+/// Recovers the corners of a hexahedral cell in [`CORNER_PARAM`] order.
+fn hex_corners(mesh: &Mesh, cell: usize) -> Option<[usize; 8]> {
+    let faces = &mesh.cell_faces()[cell];
+    if faces.len() != 6 || faces.iter().any(|&f| mesh.faces()[f].len() != 4) {
+        return None;
+    }
+    let mut bottom = mesh.faces()[faces[0]].clone();
+    if mesh.owner()[faces[0]] != cell {
+        bottom.reverse();
+    }
+    // The outward bottom face is corners [0, 3, 2, 1].
+    let base = [bottom[0], bottom[3], bottom[2], bottom[1]];
+    let mut corners = [usize::MAX; 8];
+    corners[..4].copy_from_slice(&base);
+    for (v, &b) in base.iter().enumerate() {
+        let mut partners = faces.iter().flat_map(|&f| {
+            let verts = &mesh.faces()[f];
+            (0..4).filter_map(move |i| {
+                let (p, q) = (verts[i], verts[(i + 1) % 4]);
+                if p == b {
+                    Some(q)
+                } else if q == b {
+                    Some(p)
+                } else {
+                    None
+                }
+            })
+        });
+        corners[v + 4] = partners.find(|p| !base.contains(p))?;
+    }
+    let mut sorted = corners;
+    sorted.sort_unstable();
+    if sorted.windows(2).any(|w| w[0] == w[1]) {
+        return None;
+    }
+    Some(corners)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    /// Asserts that every cell is closed: its outward face area vectors sum to zero.
+    pub(crate) fn assert_closed(mesh: &Mesh) {
+        for (c, faces) in mesh.cell_faces().iter().enumerate() {
+            let sum = faces.iter().fold(Vector::zero(), |s, &f| {
+                let sf = mesh.face_areas()[f];
+                if mesh.owner()[f] == c { s + sf } else { s - sf }
+            });
+            assert!(sum.mag() < 1e-12, "cell {c} is not closed: {sum:?}");
+        }
+    }
+
+    #[test]
+    fn test_refine_single_cell_creates_hanging_faces() {
+        let mesh = box_mesh([2, 2, 2], [1.0, 1.0, 1.0]);
+        let mut refiner = HexRefiner::new(&mesh).unwrap();
+        let map = refiner.refine(&[0]);
+        assert_eq!(
+            map.cell_map(),
+            &[0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7]
+        );
+        let refined = refiner.mesh().unwrap();
+        assert_eq!(refined.n_cells(), 15);
+        assert_closed(&refined);
+        let total: f64 = refined.cell_volumes().iter().sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        for &v in &refined.cell_volumes()[..8] {
+            assert!((v - 1.0 / 64.0).abs() < 1e-12);
+        }
+        // Cell 1 (now 8) sees four child faces in place of its x-min face.
+        let neighbors = &refined.cell_cells()[8];
+        assert_eq!(neighbors.iter().filter(|&&n| n < 8).count(), 4);
+    }
+
+    #[test]
+    fn test_refine_keeps_two_to_one_balance() {
+        let mesh = box_mesh([3, 1, 1], [3.0, 1.0, 1.0]);
+        let mut refiner = HexRefiner::new(&mesh).unwrap();
+        refiner.refine(&[0]);
+        // Child 1 of the first cell touches the second cell, so refining it
+        // forces the second cell to be refined as well.
+        refiner.refine(&[1]);
+        let levels = refiner.cell_levels();
+        assert_eq!(levels.iter().filter(|&&l| l == 2).count(), 8);
+        assert_eq!(levels.iter().filter(|&&l| l == 1).count(), 7 + 8);
+        assert_eq!(levels.iter().filter(|&&l| l == 0).count(), 1);
+        let refined = refiner.mesh().unwrap();
+        assert_closed(&refined);
+        let total: f64 = refined.cell_volumes().iter().sum();
+        assert!((total - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_refine_preserves_patch_areas_and_zones() {
+        let mut mesh = box_mesh([2, 2, 1], [2.0, 2.0, 1.0]);
+        mesh.add_cell_zone(Zone::new("corner", [3])).unwrap();
+        let mut refiner = HexRefiner::new(&mesh).unwrap();
+        refiner.refine(&[0, 3]);
+        let refined = refiner.mesh().unwrap();
+        for (old, new) in mesh.patches().iter().zip(refined.patches()) {
+            assert_eq!(old.name(), new.name());
+            let area = |m: &Mesh, r: std::ops::Range<usize>| -> f64 {
+                r.map(|f| m.face_areas()[f].mag()).sum()
+            };
+            assert!((area(&mesh, old.range()) - area(&refined, new.range())).abs() < 1e-12);
+        }
+        let zone = refined.cell_zone("corner").unwrap();
+        assert_eq!(zone.len(), 8);
+        let volume: f64 = zone
+            .indices()
+            .iter()
+            .map(|&c| refined.cell_volumes()[c])
+            .sum();
+        assert!((volume - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_unrefine_restores_original_mesh() {
+        let mesh = box_mesh([2, 2, 1], [2.0, 2.0, 1.0]);
+        let mut refiner = HexRefiner::new(&mesh).unwrap();
+        refiner.refine(&[1]);
+        let n_points = refiner.points.len();
+        let map = refiner.unrefine(&(1..9).collect::<Vec<_>>());
+        assert_eq!(map.sources(0), &[0]);
+        assert_eq!(map.sources(1), &[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(map.sources(2), &[9]);
+        let coarse = refiner.mesh().unwrap();
+        assert_eq!(coarse.n_cells(), 4);
+        assert_eq!(coarse.n_faces(), mesh.n_faces());
+        assert_eq!(coarse.n_points(), mesh.n_points());
+        for (a, b) in coarse.patches().iter().zip(mesh.patches()) {
+            assert_eq!(a.size(), b.size());
+        }
+        // Refining again reuses the points created the first time.
+        refiner.refine(&[1]);
+        assert_eq!(refiner.points.len(), n_points);
+    }
+
+    #[test]
+    fn test_unrefine_skips_incomplete_or_unbalanced_groups() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut refiner = HexRefiner::new(&mesh).unwrap();
+        refiner.refine(&[0]);
+        // Only seven of the eight siblings listed.
+        assert_eq!(refiner.unrefine(&[0, 1, 2, 3, 4, 5, 6]).len(), 9);
+        // Refining child 1 (touching the second cell) refines that cell too;
+        // merging the second cell's children would then leave a level-0 cell
+        // next to level-2 cells.
+        refiner.refine(&[1]);
+        let n = refiner.n_cells();
+        let second: Vec<usize> = (0..n).filter(|&c| refiner.cell_levels()[c] == 1).collect();
+        refiner.unrefine(&second);
+        assert_eq!(refiner.n_cells(), n);
+    }
+
+    #[test]
+    fn test_map_conservative_preserves_integral() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut refiner = HexRefiner::new(&mesh).unwrap();
+        let refine = refiner.refine(&[0]);
+        let fine = refiner.mesh().unwrap();
+        let values = refine.map_conservative(&[3.0, 5.0], mesh.cell_volumes());
+        let integral = |m: &Mesh, v: &[f64]| -> f64 {
+            m.cell_volumes().iter().zip(v).map(|(a, b)| a * b).sum()
+        };
+        assert!((integral(&fine, &values) - 8.0).abs() < 1e-12);
+
+        let varied: Vec<f64> = (0..fine.n_cells()).map(|c| c as f64).collect();
+        let coarsen = refiner.unrefine(&(0..8).collect::<Vec<_>>());
+        let coarse = refiner.mesh().unwrap();
+        let mapped = coarsen.map_conservative(&varied, fine.cell_volumes());
+        assert!((integral(&coarse, &mapped) - integral(&fine, &varied)).abs() < 1e-12);
+        assert_eq!(refine.then(&coarsen).sources(0), &[0]);
+    }
+
+    #[test]
+    fn test_hex_refiner_rejects_non_hex_cells() {
+        let mesh = box_mesh([2, 1, 1], [1.0, 1.0, 1.0]);
+        let mut refiner = HexRefiner::new(&mesh).unwrap();
+        refiner.refine(&[0]);
+        let polyhedral = refiner.mesh().unwrap();
+        assert!(matches!(
+            HexRefiner::new(&polyhedral),
+            Err(MeshError::NotHexahedral { cell: 8 })
+        ));
+    }
+}
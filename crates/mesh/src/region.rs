@@ -0,0 +1,262 @@
+use crate::ami::Ami;
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::patch::CoupledTransform;
+
+/// A named mesh in a multi-region case, such as one fluid or solid body.
+pub struct MeshRegion {
+    name: String,
+    mesh: Mesh,
+}
+
+impl MeshRegion {
+    /// Creates a region named `name`.
+    pub fn new(name: impl Into<String>, mesh: Mesh) -> Self {
+        Self {
+            name: name.into(),
+            mesh,
+        }
+    }
+
+    /// Returns the region name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the region mesh.
+    pub fn mesh(&self) -> &Mesh {
+        &self.mesh
+    }
+
+    /// Returns the region mesh mutably.
+    pub fn mesh_mut(&mut self) -> &mut Mesh {
+        &mut self.mesh
+    }
+}
+
+/// A mapped coupling between a boundary patch of one region and a boundary
+/// patch of another.
+///
+/// The patches need not be conformal: face values are transferred with the
+/// area weights of an [`Ami`] whose source side is `a` and target side `b`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionInterface {
+    region_a: usize,
+    patch_a: usize,
+    region_b: usize,
+    patch_b: usize,
+    ami: Ami,
+}
+
+impl RegionInterface {
+    /// Returns the region and patch indices of side `a`.
+    pub fn side_a(&self) -> (usize, usize) {
+        (self.region_a, self.patch_a)
+    }
+
+    /// Returns the region and patch indices of side `b`.
+    pub fn side_b(&self) -> (usize, usize) {
+        (self.region_b, self.patch_b)
+    }
+
+    /// Returns the face coupling from side `a` (source) to side `b`
+    /// (target).
+    pub fn ami(&self) -> &Ami {
+        &self.ami
+    }
+}
+
+/// Several named region meshes in one case, with the interfaces coupling
+/// them.
+pub struct MultiRegionMesh {
+    regions: Vec<MeshRegion>,
+    interfaces: Vec<RegionInterface>,
+}
+
+impl MultiRegionMesh {
+    /// Creates a case from `regions`, without interfaces.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::DuplicateRegionName`] if two regions share a name.
+    pub fn new(regions: Vec<MeshRegion>) -> Result<Self, MeshError> {
+        for (i, region) in regions.iter().enumerate() {
+            if regions[..i].iter().any(|r| r.name == region.name) {
+                return Err(MeshError::DuplicateRegionName {
+                    name: region.name.clone(),
+                });
+            }
+        }
+        Ok(Self {
+            regions,
+            interfaces: Vec::new(),
+        })
+    }
+
+    /// Returns the regions.
+    pub fn regions(&self) -> &[MeshRegion] {
+        &self.regions
+    }
+
+    /// Returns the index of the region named `name`.
+    pub fn region_index(&self, name: &str) -> Option<usize> {
+        self.regions.iter().position(|r| r.name == name)
+    }
+
+    /// Returns the region named `name`.
+    pub fn region(&self, name: &str) -> Option<&MeshRegion> {
+        self.regions.iter().find(|r| r.name == name)
+    }
+
+    /// Returns the interfaces, in the order they were added.
+    pub fn interfaces(&self) -> &[RegionInterface] {
+        &self.interfaces
+    }
+
+    /// Returns the interface containing patch `patch` of region `region`,
+    /// and whether that patch is side `a`.
+    pub fn interface_of(&self, region: usize, patch: usize) -> Option<(&RegionInterface, bool)> {
+        self.interfaces.iter().find_map(|i| {
+            if i.side_a() == (region, patch) {
+                Some((i, true))
+            } else if i.side_b() == (region, patch) {
+                Some((i, false))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Couples patch `patch_a` of region `region_a` with patch `patch_b` of
+    /// region `region_b` and returns the interface index.
+    ///
+    /// The patches must overlap in space; they are matched without a
+    /// transform.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::RegionNotFound`] or [`MeshError::PatchNotFound`]
+    /// for unknown names, and [`MeshError::InvalidInterface`] if both sides
+    /// are the same region, or a patch is already part of an interface.
+    pub fn add_interface(
+        &mut self,
+        region_a: &str,
+        patch_a: &str,
+        region_b: &str,
+        patch_b: &str,
+    ) -> Result<usize, MeshError> {
+        let find = |region: &str, patch: &str| -> Result<(usize, usize), MeshError> {
+            let r = self
+                .region_index(region)
+                .ok_or_else(|| MeshError::RegionNotFound {
+                    name: region.to_string(),
+                })?;
+            let p = self.regions[r].mesh.patch_index(patch).ok_or_else(|| {
+                MeshError::PatchNotFound {
+                    name: patch.to_string(),
+                }
+            })?;
+            Ok((r, p))
+        };
+        let (a, b) = (find(region_a, patch_a)?, find(region_b, patch_b)?);
+        if a.0 == b.0 {
+            return Err(MeshError::InvalidInterface {
+                reason: format!("both sides lie in region {region_a}"),
+            });
+        }
+        for (side, region, patch) in [(a, region_a, patch_a), (b, region_b, patch_b)] {
+            if self.interface_of(side.0, side.1).is_some() {
+                return Err(MeshError::InvalidInterface {
+                    reason: format!("patch {patch} of region {region} is already coupled"),
+                });
+            }
+        }
+
+        let ami = Ami::between(
+            &self.regions[a.0].mesh,
+            a.1,
+            &self.regions[b.0].mesh,
+            b.1,
+            &CoupledTransform::None,
+        );
+        self.interfaces.push(RegionInterface {
+            region_a: a.0,
+            patch_a: a.1,
+            region_b: b.0,
+            patch_b: b.1,
+            ami,
+        });
+        Ok(self.interfaces.len() - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dugong_types::tensor::Vector;
+
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    /// A fluid block on `[0, 1]` in x next to a finer solid block on
+    /// `[1, 2]`.
+    fn two_regions() -> MultiRegionMesh {
+        let fluid = box_mesh([2, 2, 1], [1.0, 1.0, 1.0]);
+        let mut solid = box_mesh([3, 3, 1], [1.0, 1.0, 1.0]);
+        solid.translate(Vector::new(1.0, 0.0, 0.0));
+        MultiRegionMesh::new(vec![
+            MeshRegion::new("fluid", fluid),
+            MeshRegion::new("solid", solid),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_add_interface_couples_non_conformal_patches() {
+        let mut case = two_regions();
+        let i = case
+            .add_interface("fluid", "x-max", "solid", "x-min")
+            .unwrap();
+        let interface = &case.interfaces()[i];
+        assert_eq!(interface.side_a(), (0, 1));
+        assert_eq!(interface.side_b(), (1, 0));
+        let ami = interface.ami();
+        assert_eq!((ami.n_source(), ami.n_target()), (2, 3));
+        for sum in ami
+            .source_weight_sums()
+            .iter()
+            .chain(&ami.target_weight_sums())
+        {
+            assert!((sum - 1.0).abs() < 1e-9);
+        }
+        assert!(matches!(case.interface_of(1, 0), Some((_, false))));
+        assert!(case.interface_of(1, 1).is_none());
+    }
+
+    #[test]
+    fn test_multi_region_rejects_invalid_input() {
+        let mesh = || box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        assert!(matches!(
+            MultiRegionMesh::new(vec![
+                MeshRegion::new("a", mesh()),
+                MeshRegion::new("a", mesh())
+            ]),
+            Err(MeshError::DuplicateRegionName { .. })
+        ));
+
+        let mut case = two_regions();
+        assert!(matches!(
+            case.add_interface("fluid", "x-max", "air", "x-min"),
+            Err(MeshError::RegionNotFound { .. })
+        ));
+        assert!(matches!(
+            case.add_interface("fluid", "x-max", "fluid", "x-min"),
+            Err(MeshError::InvalidInterface { .. })
+        ));
+        case.add_interface("fluid", "x-max", "solid", "x-min")
+            .unwrap();
+        assert!(matches!(
+            case.add_interface("solid", "x-min", "fluid", "y-min"),
+            Err(MeshError::InvalidInterface { .. })
+        ));
+    }
+}
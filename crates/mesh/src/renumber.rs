@@ -0,0 +1,407 @@
+use std::collections::VecDeque;
+use std::ops::Neg;
+
+use crate::assemble::{assemble, disassemble};
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::zone::Zone;
+
+/// Cell and face permutations produced by renumbering a mesh.
+///
+/// Maps follow the OpenFOAM convention: `cell_map()[new] == old` and
+/// `reverse_cell_map()[old] == new` (likewise for faces). Points are never
+/// renumbered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Renumbering {
+    cell_map: Vec<usize>,
+    reverse_cell_map: Vec<usize>,
+    face_map: Vec<usize>,
+    reverse_face_map: Vec<usize>,
+    flipped: Vec<bool>,
+}
+
+impl Renumbering {
+    /// Returns the new → old cell map.
+    pub fn cell_map(&self) -> &[usize] {
+        &self.cell_map
+    }
+
+    /// Returns the old → new cell map.
+    pub fn reverse_cell_map(&self) -> &[usize] {
+        &self.reverse_cell_map
+    }
+
+    /// Returns the new → old face map.
+    pub fn face_map(&self) -> &[usize] {
+        &self.face_map
+    }
+
+    /// Returns the old → new face map.
+    pub fn reverse_face_map(&self) -> &[usize] {
+        &self.reverse_face_map
+    }
+
+    /// Returns `true` if new face `face` is oriented opposite to its old
+    /// counterpart. This happens on internal faces whose owner and neighbor
+    /// swapped order; face fluxes must be negated when mapped.
+    pub fn is_face_flipped(&self, face: usize) -> bool {
+        self.flipped[face]
+    }
+
+    /// Reorders per-cell values from the old to the new cell numbering.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `old.len()` differs from the number of cells.
+    pub fn map_cell_values<T: Clone>(&self, old: &[T]) -> Vec<T> {
+        assert_eq!(old.len(), self.cell_map.len(), "cell value count mismatch");
+        self.cell_map.iter().map(|&c| old[c].clone()).collect()
+    }
+
+    /// Reorders per-face values from the old to the new face numbering.
+    ///
+    /// Values are copied as they are; use [`Renumbering::map_face_fluxes`]
+    /// for values that change sign with the face orientation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `old.len()` differs from the number of faces.
+    pub fn map_face_values<T: Clone>(&self, old: &[T]) -> Vec<T> {
+        assert_eq!(old.len(), self.face_map.len(), "face value count mismatch");
+        self.face_map.iter().map(|&f| old[f].clone()).collect()
+    }
+
+    /// Reorders per-face fluxes from the old to the new face numbering,
+    /// negating those on flipped faces.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `old.len()` differs from the number of faces.
+    pub fn map_face_fluxes<T: Clone + Neg<Output = T>>(&self, old: &[T]) -> Vec<T> {
+        assert_eq!(old.len(), self.face_map.len(), "face value count mismatch");
+        self.face_map
+            .iter()
+            .zip(&self.flipped)
+            .map(|(&f, &flip)| {
+                if flip {
+                    -old[f].clone()
+                } else {
+                    old[f].clone()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Computes a reverse Cuthill–McKee ordering of an undirected graph.
+///
+/// `adjacency[v]` lists the neighbors of vertex `v`. Each connected component
+/// is traversed breadth-first from a pseudo-peripheral vertex, visiting
+/// neighbors in order of increasing degree; the concatenated order is then
+/// reversed. Returns the new → old vertex order.
+pub fn reverse_cuthill_mckee(adjacency: &[Vec<usize>]) -> Vec<usize> {
+    let n = adjacency.len();
+    let degree = |v: usize| adjacency[v].len();
+    let mut visited = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+
+    // Process unvisited vertices by increasing degree so each component
+    // starts from a low-degree seed.
+    let mut seeds: Vec<usize> = (0..n).collect();
+    seeds.sort_by_key(|&v| (degree(v), v));
+
+    for seed in seeds {
+        if visited[seed] {
+            continue;
+        }
+        let start = pseudo_peripheral(adjacency, seed, &visited);
+        let mut queue = VecDeque::from([start]);
+        visited[start] = true;
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            let mut next: Vec<usize> = adjacency[v]
+                .iter()
+                .copied()
+                .filter(|&w| !visited[w])
+                .collect();
+            next.sort_by_key(|&w| (degree(w), w));
+            next.dedup();
+            for w in next {
+                visited[w] = true;
+                queue.push_back(w);
+            }
+        }
+    }
+    order.reverse();
+    order
+}
+
+/// Returns the BFS level structure rooted at `root`, skipping `excluded` vertices.
+pub(crate) fn level_structure(
+    adjacency: &[Vec<usize>],
+    root: usize,
+    excluded: &[bool],
+) -> Vec<Vec<usize>> {
+    let mut seen = excluded.to_vec();
+    seen[root] = true;
+    let mut levels = vec![vec![root]];
+    loop {
+        let mut next = Vec::new();
+        for &v in levels.last().unwrap_or(&Vec::new()) {
+            for &w in &adjacency[v] {
+                if !seen[w] {
+                    seen[w] = true;
+                    next.push(w);
+                }
+            }
+        }
+        if next.is_empty() {
+            return levels;
+        }
+        levels.push(next);
+    }
+}
+
+/// Finds a pseudo-peripheral vertex of the component containing `seed`
+/// (George–Liu heuristic).
+pub(crate) fn pseudo_peripheral(adjacency: &[Vec<usize>], seed: usize, excluded: &[bool]) -> usize {
+    let mut root = seed;
+    let mut depth = level_structure(adjacency, root, excluded).len();
+    loop {
+        let levels = level_structure(adjacency, root, excluded);
+        // Safety: a level structure always contains at least the root level.
+        let last = levels.last().unwrap();
+        let candidate = *last
+            .iter()
+            .min_by_key(|&&v| (adjacency[v].len(), v))
+            .unwrap_or(&root);
+        let candidate_depth = level_structure(adjacency, candidate, excluded).len();
+        if candidate_depth > depth {
+            root = candidate;
+            depth = candidate_depth;
+        } else {
+            return root;
+        }
+    }
+}
+
+impl Mesh {
+    /// Returns the matrix bandwidth of the cell numbering: the maximum
+    /// `|owner - neighbor|` over internal faces.
+    pub fn bandwidth(&self) -> usize {
+        self.owner()
+            .iter()
+            .zip(self.neighbor())
+            .map(|(&o, &n)| o.abs_diff(n))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Renumbers cells with reverse Cuthill–McKee over the cell-cell graph to
+    /// reduce matrix bandwidth.
+    ///
+    /// Internal faces are reordered into upper-triangular order for the new
+    /// cell numbering; boundary faces keep their patch order. Zones and the
+    /// old-time geometry of a moving mesh are remapped. Returns the
+    /// permutation so attached fields can be remapped.
+    pub fn renumber(&mut self) -> Renumbering {
+        let order = reverse_cuthill_mckee(self.cell_cells());
+        // Safety: reverse_cuthill_mckee returns a permutation of 0..n_cells.
+        self.renumber_cells(&order).unwrap()
+    }
+
+    /// Applies an arbitrary cell ordering, where `cell_map[new] == old`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::InvalidPermutation`] if `cell_map` is not a
+    /// permutation of `0..n_cells()`.
+    pub fn renumber_cells(&mut self, cell_map: &[usize]) -> Result<Renumbering, MeshError> {
+        let n_cells = self.n_cells();
+        let mut reverse_cell_map = vec![usize::MAX; n_cells];
+        if cell_map.len() != n_cells {
+            return Err(MeshError::InvalidPermutation { len: n_cells });
+        }
+        for (new, &old) in cell_map.iter().enumerate() {
+            if old >= n_cells || reverse_cell_map[old] != usize::MAX {
+                return Err(MeshError::InvalidPermutation { len: n_cells });
+            }
+            reverse_cell_map[old] = new;
+        }
+
+        let (internal, groups) = disassemble(self, |c| reverse_cell_map[c]);
+        let (mut mesh, face_map) = assemble(self.points().to_vec(), internal, groups)?;
+        let mut reverse_face_map = vec![0; face_map.len()];
+        for (new, &old) in face_map.iter().enumerate() {
+            reverse_face_map[old] = new;
+        }
+        // `assemble` flips an internal face exactly when its old owner is
+        // no longer the lower-numbered cell.
+        let flipped = face_map
+            .iter()
+            .enumerate()
+            .map(|(new, &old)| mesh.owner()[new] != reverse_cell_map[self.owner()[old]])
+            .collect();
+
+        mesh.cell_zones = self
+            .cell_zones
+            .iter()
+            .map(|z| Zone::new(z.name(), z.indices().iter().map(|&c| reverse_cell_map[c])))
+            .collect();
+        mesh.face_zones = self
+            .face_zones
+            .iter()
+            .map(|z| Zone::new(z.name(), z.indices().iter().map(|&f| reverse_face_map[f])))
+            .collect();
+        mesh.point_zones = std::mem::take(&mut self.point_zones);
+
+        let renumbering = Renumbering {
+            cell_map: cell_map.to_vec(),
+            reverse_cell_map,
+            face_map,
+            reverse_face_map,
+            flipped,
+        };
+        mesh.motion = self.motion.take().map(|m| m.renumbered(&renumbering));
+        *self = mesh;
+        Ok(renumbering)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    /// A deterministic scrambling permutation of `0..n`.
+    fn scrambled(n: usize) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..n).collect();
+        let mut state = 12345u64;
+        for i in (1..n).rev() {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            order.swap(i, (state >> 33) as usize % (i + 1));
+        }
+        order
+    }
+
+    #[test]
+    fn test_rcm_path_graph_is_banded() {
+        // Path 0-3-1-4-2 shuffled; RCM should give bandwidth 1.
+        let adjacency = vec![vec![3], vec![3, 4], vec![4], vec![0, 1], vec![1, 2]];
+        let order = reverse_cuthill_mckee(&adjacency);
+        let mut pos = [0; 5];
+        for (new, &old) in order.iter().enumerate() {
+            pos[old] = new;
+        }
+        for (v, nbrs) in adjacency.iter().enumerate() {
+            for &w in nbrs {
+                assert_eq!(pos[v].abs_diff(pos[w]), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_renumber_reduces_bandwidth_of_scrambled_mesh() {
+        let mut mesh = box_mesh([6, 5, 4], [1.0, 1.0, 1.0]);
+        mesh.renumber_cells(&scrambled(mesh.n_cells())).unwrap();
+        let scrambled_bw = mesh.bandwidth();
+        mesh.renumber();
+        assert!(
+            mesh.bandwidth() < scrambled_bw / 2,
+            "bandwidth {} not reduced from {scrambled_bw}",
+            mesh.bandwidth()
+        );
+        assert!(mesh.bandwidth() <= 6 * 5 + 6);
+    }
+
+    #[test]
+    fn test_renumber_keeps_upper_triangular_order_and_geometry() {
+        let mut mesh = box_mesh([4, 3, 2], [4.0, 3.0, 2.0]);
+        let old_centers = mesh.cell_centers().to_vec();
+        let r = mesh.renumber_cells(&scrambled(mesh.n_cells())).unwrap();
+
+        let new_centers = mesh.cell_centers();
+        for (new, &old) in r.cell_map().iter().enumerate() {
+            assert!((new_centers[new] - old_centers[old]).mag() < 1e-12);
+        }
+        let ids: Vec<usize> = (0..mesh.n_cells()).collect();
+        assert_eq!(r.map_cell_values(&ids), r.cell_map());
+        for f in 0..mesh.n_internal_faces() {
+            assert!(mesh.owner()[f] < mesh.neighbor()[f]);
+            if f > 0 {
+                let prev = (mesh.owner()[f - 1], mesh.neighbor()[f - 1]);
+                assert!(prev < (mesh.owner()[f], mesh.neighbor()[f]));
+            }
+        }
+        for &v in mesh.cell_volumes() {
+            assert!((v - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_renumber_reports_flipped_faces_for_fluxes() {
+        let mut mesh = box_mesh([4, 3, 2], [4.0, 3.0, 2.0]);
+        let old_areas = mesh.face_areas().to_vec();
+        let r = mesh.renumber_cells(&scrambled(mesh.n_cells())).unwrap();
+
+        let n_flipped = (0..mesh.n_faces())
+            .filter(|&f| r.is_face_flipped(f))
+            .count();
+        assert!(n_flipped > 0);
+        assert!((mesh.n_internal_faces()..mesh.n_faces()).all(|f| !r.is_face_flipped(f)));
+        for (new, old) in r.map_face_fluxes(&old_areas).into_iter().enumerate() {
+            assert!((mesh.face_areas()[new] - old).mag() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_renumber_carries_motion_state() {
+        let mut mesh = box_mesh([3, 2, 1], [3.0, 2.0, 1.0]);
+        let stretched = mesh.points().iter().map(|&p| p * 2.0).collect();
+        mesh.move_points(stretched).unwrap();
+        let old_volumes = mesh.old_cell_volumes().unwrap().to_vec();
+        let swept = mesh.swept_volumes().unwrap().to_vec();
+        let r = mesh.renumber_cells(&scrambled(mesh.n_cells())).unwrap();
+
+        assert!(mesh.is_moving());
+        assert_eq!(
+            mesh.old_cell_volumes().unwrap(),
+            r.map_cell_values(&old_volumes)
+        );
+        assert_eq!(mesh.swept_volumes().unwrap(), r.map_face_fluxes(&swept));
+        // Space conservation still holds in the new numbering.
+        let mut change: Vec<f64> = mesh
+            .cell_volumes()
+            .iter()
+            .zip(mesh.old_cell_volumes().unwrap())
+            .map(|(v, v0)| v - v0)
+            .collect();
+        for (f, &s) in mesh.swept_volumes().unwrap().iter().enumerate() {
+            change[mesh.owner()[f]] -= s;
+            if f < mesh.n_internal_faces() {
+                change[mesh.neighbor()[f]] += s;
+            }
+        }
+        assert!(change.iter().all(|c| c.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_renumber_remaps_zones() {
+        let mut mesh = box_mesh([3, 3, 1], [1.0, 1.0, 1.0]);
+        mesh.add_cell_zone(Zone::new("corner", [0])).unwrap();
+        let corner_center = mesh.cell_centers()[0];
+        let r = mesh.renumber_cells(&scrambled(9)).unwrap();
+        let zone = mesh.cell_zone("corner").unwrap();
+        assert_eq!(zone.indices(), &[r.reverse_cell_map()[0]]);
+        assert!((mesh.cell_centers()[zone.indices()[0]] - corner_center).mag() < 1e-12);
+    }
+
+    #[test]
+    fn test_renumber_cells_rejects_non_permutation() {
+        let mut mesh = box_mesh([2, 1, 1], [1.0, 1.0, 1.0]);
+        assert!(matches!(
+            mesh.renumber_cells(&[0, 0]),
+            Err(MeshError::InvalidPermutation { len: 2 })
+        ));
+    }
+}
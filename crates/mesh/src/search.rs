@@ -0,0 +1,224 @@
+use std::cell::Cell;
+
+use dugong_types::tensor::Vector;
+
+use crate::bvh::{Aabb, Bvh};
+use crate::geometry;
+use crate::mesh::Mesh;
+
+/// Relative tolerance for point-in-cell tests, so that points on a face
+/// are found in one of the cells sharing it.
+const INSIDE_TOL: f64 = 1e-10;
+
+/// Spatial queries over a mesh: point location, nearest boundary face, and
+/// ray casting against the boundary.
+///
+/// Built once from a mesh and borrowed alongside it; bounding volume
+/// hierarchies over the cells and the boundary faces keep each query close
+/// to logarithmic in the mesh size. Rebuild after the mesh points move.
+pub struct MeshSearch<'a> {
+    mesh: &'a Mesh,
+    cell_boxes: Vec<Aabb>,
+    cells: Bvh,
+    boundary: Bvh,
+}
+
+impl<'a> MeshSearch<'a> {
+    /// Builds the search structure over `mesh`.
+    pub fn new(mesh: &'a Mesh) -> Self {
+        let points = mesh.points();
+        let cell_boxes: Vec<Aabb> = mesh
+            .cell_points()
+            .iter()
+            .map(|cp| Aabb::of_points(cp.iter().map(|&p| points[p])))
+            .collect();
+        let face_boxes: Vec<Aabb> = mesh.faces()[mesh.n_internal_faces()..]
+            .iter()
+            .map(|face| Aabb::of_points(face.iter().map(|&p| points[p])))
+            .collect();
+        Self {
+            mesh,
+            cells: Bvh::new(&cell_boxes),
+            cell_boxes,
+            boundary: Bvh::new(&face_boxes),
+        }
+    }
+
+    /// Returns the mesh being searched.
+    pub fn mesh(&self) -> &'a Mesh {
+        self.mesh
+    }
+
+    /// Returns the cell containing `point`, or `None` if it lies outside the
+    /// mesh.
+    ///
+    /// Cells are split into tetrahedra from the cell center to the face
+    /// triangles, so the result is exact for star-shaped cells. Points on a
+    /// shared face or edge resolve to the lowest-indexed cell containing
+    /// them.
+    pub fn find_cell(&self, point: Vector) -> Option<usize> {
+        let mut found: Option<usize> = None;
+        self.cells.visit(
+            |b| b.contains(point, 0.0),
+            |cell| {
+                let tol =
+                    INSIDE_TOL * (self.cell_boxes[cell].max - self.cell_boxes[cell].min).mag();
+                if found.is_none_or(|f| cell < f)
+                    && self.cell_boxes[cell].contains(point, tol)
+                    && self.cell_contains(cell, point)
+                {
+                    found = Some(cell);
+                }
+            },
+        );
+        found
+    }
+
+    /// Returns `true` if `point` lies in one of the center-to-face-triangle
+    /// tetrahedra of `cell`.
+    fn cell_contains(&self, cell: usize, point: Vector) -> bool {
+        let mesh = self.mesh;
+        let center = mesh.cell_centers()[cell];
+        mesh.cell_faces()[cell].iter().any(|&f| {
+            let face = &mesh.faces()[f];
+            let n = face.len();
+            let p_ref = mesh.face_centers()[f];
+            (0..n).any(|i| {
+                let a = mesh.points()[face[i]];
+                let b = mesh.points()[face[(i + 1) % n]];
+                tet_contains([center, p_ref, a, b], point)
+            })
+        })
+    }
+
+    /// Returns the boundary face nearest to `point` and the distance to it,
+    /// or `None` if the mesh has no boundary faces.
+    pub fn nearest_boundary_face(&self, point: Vector) -> Option<(usize, f64)> {
+        let offset = self.mesh.n_internal_faces();
+        self.boundary
+            .nearest(point, |i| {
+                let face = &self.mesh.faces()[offset + i];
+                (geometry::closest_point_on_face(self.mesh.points(), face, point) - point).mag()
+            })
+            .map(|(i, d)| (offset + i, d))
+    }
+
+    /// Casts the ray from `origin` along `direction` and returns the first
+    /// boundary face it crosses and the distance to the crossing, or `None`
+    /// if it leaves without hitting the boundary.
+    ///
+    /// Faces are hit from either side, so a ray starting inside the mesh
+    /// returns where it exits. `direction` need not be normalized.
+    pub fn ray_boundary_intersection(
+        &self,
+        origin: Vector,
+        direction: Vector,
+    ) -> Option<(usize, f64)> {
+        let length = direction.mag();
+        if length == 0.0 {
+            return None;
+        }
+        let direction = direction / length;
+        let offset = self.mesh.n_internal_faces();
+        let best: Cell<Option<(usize, f64)>> = Cell::new(None);
+        let t_max = || best.get().map_or(f64::INFINITY, |(_, t)| t);
+        self.boundary.visit(
+            |b| b.hits_ray(origin, direction, t_max()),
+            |i| {
+                let face = &self.mesh.faces()[offset + i];
+                if let Some(t) =
+                    geometry::ray_face_intersection(self.mesh.points(), face, origin, direction)
+                    && t < t_max()
+                {
+                    best.set(Some((offset + i, t)));
+                }
+            },
+        );
+        best.get()
+    }
+}
+
+/// Returns `true` if `p` lies inside the tetrahedron `tet`, up to a relative
+/// tolerance. Degenerate tetrahedra contain nothing.
+fn tet_contains(tet: [Vector; 4], p: Vector) -> bool {
+    let volume = |a: Vector, b: Vector, c: Vector, d: Vector| (b - a).cross(&(c - a)) * (d - a);
+    let [a, b, c, d] = tet;
+    let v = volume(a, b, c, d);
+    if v == 0.0 {
+        return false;
+    }
+    let tol = -INSIDE_TOL * v.abs();
+    [
+        volume(p, b, c, d),
+        volume(a, p, c, d),
+        volume(a, b, p, d),
+        volume(a, b, c, p),
+    ]
+    .iter()
+    .all(|&w| w * v.signum() >= tol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_find_cell_matches_box_indexing() {
+        let mesh = box_mesh([4, 3, 2], [4.0, 3.0, 2.0]);
+        let search = MeshSearch::new(&mesh);
+        for (p, expected) in [
+            (Vector::new(0.5, 0.5, 0.5), 0),
+            (Vector::new(3.7, 2.1, 1.9), 3 + 4 * (2 + 3)),
+            (Vector::new(1.2, 1.8, 0.3), 1 + 4),
+        ] {
+            assert_eq!(search.find_cell(p), Some(expected));
+        }
+        assert_eq!(search.find_cell(Vector::new(4.5, 1.0, 1.0)), None);
+    }
+
+    #[test]
+    fn test_find_cell_on_shared_face_picks_lowest_index() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let search = MeshSearch::new(&mesh);
+        assert_eq!(search.find_cell(Vector::new(1.0, 0.5, 0.5)), Some(0));
+        assert_eq!(search.find_cell(Vector::new(2.0, 0.5, 0.5)), Some(1));
+    }
+
+    #[test]
+    fn test_nearest_boundary_face_distance() {
+        let mesh = box_mesh([3, 3, 3], [3.0, 3.0, 3.0]);
+        let search = MeshSearch::new(&mesh);
+        let (face, d) = search
+            .nearest_boundary_face(Vector::new(1.5, 0.4, 1.5))
+            .unwrap();
+        assert!((d - 0.4).abs() < 1e-12);
+        assert_eq!(mesh.which_patch(face), mesh.patch_index("y-min"));
+        let (_, d) = search
+            .nearest_boundary_face(Vector::new(5.0, 1.5, 1.5))
+            .unwrap();
+        assert!((d - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ray_boundary_intersection_exits_mesh() {
+        let mesh = box_mesh([3, 2, 2], [3.0, 2.0, 2.0]);
+        let search = MeshSearch::new(&mesh);
+        let (face, t) = search
+            .ray_boundary_intersection(Vector::new(0.5, 0.5, 0.5), Vector::new(2.0, 0.0, 0.0))
+            .unwrap();
+        assert!((t - 2.5).abs() < 1e-12);
+        assert_eq!(mesh.which_patch(face), mesh.patch_index("x-max"));
+
+        // From outside, the first crossing is the near side.
+        let (face, t) = search
+            .ray_boundary_intersection(Vector::new(1.5, -1.0, 0.5), Vector::new(0.0, 1.0, 0.0))
+            .unwrap();
+        assert!((t - 1.0).abs() < 1e-12);
+        assert_eq!(mesh.which_patch(face), mesh.patch_index("y-min"));
+
+        let miss = search
+            .ray_boundary_intersection(Vector::new(1.5, -1.0, 0.5), Vector::new(0.0, -1.0, 0.0));
+        assert!(miss.is_none());
+    }
+}
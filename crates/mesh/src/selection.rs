@@ -0,0 +1,213 @@
+use dugong_types::tensor::Vector;
+
+use crate::error::MeshError;
+use crate::mesh::{Mesh, ZoneKind};
+use crate::tri_surface::TriSurface;
+use crate::zone::Zone;
+
+/// A composable rule selecting cells, faces or points of a mesh.
+///
+/// Geometric selectors test cell centers, face centers or point positions,
+/// boundaries included. Selectors are combined with [`Selector::Union`],
+/// [`Selector::Intersection`] and [`Selector::Difference`] and evaluated by
+/// [`Mesh::select`].
+#[derive(Debug, Clone)]
+pub enum Selector<'a> {
+    /// Everything inside the axis-aligned box `[min, max]`.
+    Box { min: Vector, max: Vector },
+    /// Everything within `radius` of `center`.
+    Sphere { center: Vector, radius: f64 },
+    /// Everything within `radius` of the segment from `start` to `end`.
+    Cylinder {
+        start: Vector,
+        end: Vector,
+        radius: f64,
+    },
+    /// Everything inside (`inside: true`) or outside a closed surface.
+    Surface {
+        surface: &'a TriSurface,
+        inside: bool,
+    },
+    /// The faces of a patch, the cells owning them, or their points.
+    Patch(String),
+    /// The members of a zone of the selected kind.
+    Zone(String),
+    /// Everything selected by any of the selectors.
+    Union(Vec<Selector<'a>>),
+    /// Everything selected by all of the selectors; all entities if empty.
+    Intersection(Vec<Selector<'a>>),
+    /// Everything selected by the first selector but not the second.
+    Difference(Box<Selector<'a>>, Box<Selector<'a>>),
+}
+
+impl Selector<'_> {
+    /// Returns `true` if a geometric selector contains `p`. Other
+    /// selectors contain no position.
+    fn contains(&self, p: Vector) -> bool {
+        match self {
+            Selector::Box { min, max } => {
+                let (p, lo, hi) = (p.as_array(), min.as_array(), max.as_array());
+                (0..3).all(|d| lo[d] <= p[d] && p[d] <= hi[d])
+            }
+            Selector::Sphere { center, radius } => (p - *center).mag() <= *radius,
+            Selector::Cylinder { start, end, radius } => {
+                let axis = *end - *start;
+                let (along, length2) = ((p - *start) * axis, axis * axis);
+                length2 > 0.0
+                    && (0.0..=length2).contains(&along)
+                    && (p - (*start + axis * (along / length2))).mag() <= *radius
+            }
+            Selector::Surface { surface, inside } => surface.is_inside(p) == *inside,
+            _ => false,
+        }
+    }
+}
+
+impl Mesh {
+    /// Evaluates `selector` on the cells, faces or points and returns the
+    /// selection as a zone named `name`.
+    ///
+    /// The zone is not added to the mesh; see [`Mesh::add_zone`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::PatchNotFound`] or [`MeshError::ZoneNotFound`]
+    /// if a selector names a missing patch or zone of kind `kind`.
+    pub fn select(
+        &self,
+        kind: ZoneKind,
+        selector: &Selector,
+        name: impl Into<String>,
+    ) -> Result<Zone, MeshError> {
+        let name = name.into();
+        let (n, positions) = match kind {
+            ZoneKind::Cell => (self.n_cells(), self.cell_centers()),
+            ZoneKind::Face => (self.n_faces(), self.face_centers()),
+            ZoneKind::Point => (self.n_points(), self.points()),
+        };
+        Ok(match selector {
+            Selector::Box { .. }
+            | Selector::Sphere { .. }
+            | Selector::Cylinder { .. }
+            | Selector::Surface { .. } => {
+                Zone::new(name, (0..n).filter(|&i| selector.contains(positions[i])))
+            }
+            Selector::Patch(patch) => {
+                let range = self
+                    .patch(patch)
+                    .ok_or_else(|| MeshError::PatchNotFound {
+                        name: patch.clone(),
+                    })?
+                    .range();
+                match kind {
+                    ZoneKind::Cell => Zone::new(name, range.map(|f| self.owner()[f])),
+                    ZoneKind::Face => Zone::new(name, range),
+                    ZoneKind::Point => {
+                        Zone::new(name, range.flat_map(|f| self.faces()[f].iter().copied()))
+                    }
+                }
+            }
+            Selector::Zone(zone) => {
+                let zone = self
+                    .zone(kind, zone)
+                    .ok_or_else(|| MeshError::ZoneNotFound { name: zone.clone() })?;
+                Zone::new(name, zone.indices().iter().copied())
+            }
+            Selector::Union(selectors) => {
+                let mut out = Zone::new(name.clone(), []);
+                for s in selectors {
+                    out = out.union(&self.select(kind, s, "")?, name.clone());
+                }
+                out
+            }
+            Selector::Intersection(selectors) => {
+                let mut out = Zone::new(name.clone(), 0..n);
+                for s in selectors {
+                    out = out.intersection(&self.select(kind, s, "")?, name.clone());
+                }
+                out
+            }
+            Selector::Difference(a, b) => self
+                .select(kind, a, "")?
+                .difference(&self.select(kind, b, "")?, name),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_select_geometric_regions() {
+        let mesh = box_mesh([4, 4, 4], [1.0, 1.0, 1.0]);
+        let boxed = Selector::Box {
+            min: Vector::new(0.0, 0.0, 0.0),
+            max: Vector::new(0.5, 0.5, 1.0),
+        };
+        assert_eq!(
+            mesh.select(ZoneKind::Cell, &boxed, "box").unwrap().len(),
+            16
+        );
+
+        let sphere = Selector::Sphere {
+            center: Vector::new(0.5, 0.5, 0.5),
+            radius: 0.25,
+        };
+        assert_eq!(mesh.select(ZoneKind::Cell, &sphere, "s").unwrap().len(), 8);
+
+        let cylinder = Selector::Cylinder {
+            start: Vector::new(0.5, 0.5, 0.0),
+            end: Vector::new(0.5, 0.5, 0.5),
+            radius: 0.2,
+        };
+        let zone = mesh.select(ZoneKind::Cell, &cylinder, "c").unwrap();
+        assert_eq!(zone.len(), 8);
+        assert!(
+            zone.indices()
+                .iter()
+                .all(|&c| mesh.cell_centers()[c].as_array()[2] < 0.5)
+        );
+
+        let points = mesh.select(ZoneKind::Point, &boxed, "p").unwrap();
+        assert_eq!(points.len(), 3 * 3 * 5);
+    }
+
+    #[test]
+    fn test_select_combines_selectors() {
+        let mut mesh = box_mesh([4, 1, 1], [4.0, 1.0, 1.0]);
+        mesh.add_cell_zone(Zone::new("middle", [1, 2])).unwrap();
+        let left = Selector::Patch("x-min".to_string());
+        let middle = Selector::Zone("middle".to_string());
+        let union = Selector::Union(vec![left.clone(), middle.clone()]);
+        assert_eq!(
+            mesh.select(ZoneKind::Cell, &union, "u").unwrap().indices(),
+            [0, 1, 2]
+        );
+        let difference = Selector::Difference(Box::new(union), Box::new(middle));
+        let zone = mesh.select(ZoneKind::Cell, &difference, "d").unwrap();
+        assert_eq!((zone.name(), zone.indices()), ("d", &[0][..]));
+        let all = Selector::Intersection(Vec::new());
+        assert_eq!(
+            mesh.select(ZoneKind::Face, &all, "a").unwrap().len(),
+            3 + 18
+        );
+
+        let faces = mesh.select(ZoneKind::Face, &left, "f").unwrap();
+        assert_eq!(faces.indices(), [mesh.patch("x-min").unwrap().start()]);
+    }
+
+    #[test]
+    fn test_select_rejects_missing_names() {
+        let mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        assert!(matches!(
+            mesh.select(ZoneKind::Cell, &Selector::Patch("inlet".into()), "z"),
+            Err(MeshError::PatchNotFound { .. })
+        ));
+        assert!(matches!(
+            mesh.select(ZoneKind::Face, &Selector::Zone("baffle".into()), "z"),
+            Err(MeshError::ZoneNotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,187 @@
+use dugong_types::tensor::Vector;
+
+use crate::mesh::Mesh;
+
+/// Settings for [`Mesh::smooth`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmoothSettings {
+    /// Number of smoothing sweeps.
+    pub iterations: usize,
+    /// Fraction of the way each point moves toward the average of its edge
+    /// neighbors in one sweep, in `(0, 1]`.
+    pub relaxation: f64,
+    /// Non-orthogonality, in degrees, that a face may not exceed after a
+    /// move unless it already did before.
+    pub max_non_orthogonality: f64,
+}
+
+impl Mesh {
+    /// Relaxes interior points toward the average of their edge neighbors.
+    ///
+    /// Each sweep moves all interior points at once; points on boundary
+    /// faces stay fixed. Moves are then undone for every cell that became
+    /// inverted, and for both cells of every internal face whose
+    /// non-orthogonality grew past [`SmoothSettings::max_non_orthogonality`],
+    /// until no such cell remains. The points are replaced without recording
+    /// mesh motion.
+    ///
+    /// Returns the number of point moves kept over all sweeps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`SmoothSettings::relaxation`] is not in `(0, 1]`.
+    pub fn smooth(&mut self, settings: &SmoothSettings) -> usize {
+        assert!(
+            settings.relaxation > 0.0 && settings.relaxation <= 1.0,
+            "relaxation must be in (0, 1]"
+        );
+        let mut fixed = vec![false; self.n_points()];
+        for f in self.n_internal_faces()..self.n_faces() {
+            for &p in &self.faces()[f] {
+                fixed[p] = true;
+            }
+        }
+
+        let mut n_moved = 0;
+        for _ in 0..settings.iterations {
+            let old = self.points().to_vec();
+            let old_angles = non_orthogonality(self);
+            let old_inverted: Vec<bool> = self.cell_volumes().iter().map(|&v| v <= 0.0).collect();
+            let mut points = old.clone();
+            let mut moved = vec![false; points.len()];
+            for (p, point_edges) in self.point_edges().iter().enumerate() {
+                if fixed[p] || point_edges.is_empty() {
+                    continue;
+                }
+                let sum = point_edges.iter().fold(Vector::zero(), |s, &e| {
+                    let [a, b] = self.edges()[e];
+                    s + old[if a == p { b } else { a }]
+                });
+                let target = sum / point_edges.len() as f64;
+                points[p] = old[p] + (target - old[p]) * settings.relaxation;
+                moved[p] = points[p] != old[p];
+            }
+
+            loop {
+                self.primitive.set_points(points.clone());
+                let angles = non_orthogonality(self);
+                let mut bad = vec![false; self.n_cells()];
+                for (c, &v) in self.cell_volumes().iter().enumerate() {
+                    bad[c] = v <= 0.0 && !old_inverted[c];
+                }
+                for (f, &n) in self.neighbor().iter().enumerate() {
+                    if angles[f] > settings.max_non_orthogonality && angles[f] > old_angles[f] {
+                        bad[self.owner()[f]] = true;
+                        bad[n] = true;
+                    }
+                }
+                let mut reverted = false;
+                for c in (0..self.n_cells()).filter(|&c| bad[c]) {
+                    for &p in &self.cell_points()[c] {
+                        if moved[p] {
+                            moved[p] = false;
+                            points[p] = old[p];
+                            reverted = true;
+                        }
+                    }
+                }
+                if !reverted {
+                    break;
+                }
+            }
+            let n = moved.iter().filter(|&&m| m).count();
+            n_moved += n;
+            if n == 0 {
+                break;
+            }
+        }
+        n_moved
+    }
+}
+
+/// Returns the non-orthogonality of each internal face, in degrees.
+fn non_orthogonality(mesh: &Mesh) -> Vec<f64> {
+    let (sf, cc) = (mesh.face_areas(), mesh.cell_centers());
+    mesh.neighbor()
+        .iter()
+        .enumerate()
+        .map(|(f, &n)| {
+            let d = cc[n] - cc[mesh.owner()[f]];
+            let denom = sf[f].mag() * d.mag();
+            if denom > 0.0 {
+                ((sf[f] * d) / denom).clamp(-1.0, 1.0).acos().to_degrees()
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    /// A 4x4x4 unit box mesh with the interior points displaced.
+    fn distorted() -> Mesh {
+        let mut mesh = box_mesh([4, 4, 4], [1.0, 1.0, 1.0]);
+        let points = mesh
+            .points()
+            .iter()
+            .map(|&p| {
+                let [x, y, z] = *p.as_array();
+                let interior = [x, y, z].iter().all(|&c| c > 1e-9 && c < 1.0 - 1e-9);
+                if interior {
+                    p + Vector::new(0.08 * (7.0 * y).sin(), 0.08 * (5.0 * z).cos(), 0.05 * x)
+                } else {
+                    p
+                }
+            })
+            .collect();
+        mesh.primitive.set_points(points);
+        mesh
+    }
+
+    fn settings(max_non_orthogonality: f64) -> SmoothSettings {
+        SmoothSettings {
+            iterations: 20,
+            relaxation: 0.5,
+            max_non_orthogonality,
+        }
+    }
+
+    #[test]
+    fn test_smooth_restores_uniform_grid() {
+        let mut mesh = distorted();
+        let before = mesh.check().max_non_orthogonality;
+        let initial: Vec<Vector> = mesh.points().to_vec();
+        assert!(mesh.smooth(&settings(90.0)) > 0);
+        let check = mesh.check();
+        assert!(check.is_ok());
+        assert!(check.max_non_orthogonality < before);
+
+        let uniform = box_mesh([4, 4, 4], [1.0, 1.0, 1.0]);
+        for (p, (q, b)) in mesh
+            .points()
+            .iter()
+            .zip(uniform.points().iter().zip(&initial))
+        {
+            assert!((*p - *q).mag() < 1e-2);
+            // Boundary points were never displaced and stay fixed.
+            if b == q {
+                assert_eq!(p, q);
+            }
+        }
+    }
+
+    #[test]
+    fn test_smooth_never_worsens_faces_past_threshold() {
+        let mut mesh = distorted();
+        let before = non_orthogonality(&mesh);
+        mesh.smooth(&settings(0.0));
+        for (after, before) in non_orthogonality(&mesh).iter().zip(&before) {
+            assert!(*after <= before + 1e-9);
+        }
+        assert!(mesh.check().is_ok());
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt;
+
+use dugong_types::tensor::Vector;
+
+use crate::bvh::Aabb;
+use crate::mesh::Mesh;
+
+/// Face count and area of one boundary patch, part of [`MeshStats`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchStats {
+    /// Patch name.
+    pub name: String,
+    /// Patch type name, as in the OpenFOAM `boundary` file.
+    pub kind: String,
+    /// Number of faces.
+    pub n_faces: usize,
+    /// Sum of the face area magnitudes.
+    pub area: f64,
+}
+
+/// A global summary of a mesh, returned by [`Mesh::stats`].
+///
+/// The [`Display`](fmt::Display) implementation formats a human-readable
+/// report for sanity-checking imported meshes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshStats {
+    /// Number of points.
+    pub n_points: usize,
+    /// Number of faces, internal and boundary.
+    pub n_faces: usize,
+    /// Number of internal faces.
+    pub n_internal_faces: usize,
+    /// Number of cells.
+    pub n_cells: usize,
+    /// Lower corner of the bounding box of the points.
+    pub bounds_min: Vector,
+    /// Upper corner of the bounding box of the points.
+    pub bounds_max: Vector,
+    /// Sum of the cell volumes.
+    pub total_volume: f64,
+    /// Smallest cell volume, or zero for a mesh without cells.
+    pub min_cell_volume: f64,
+    /// Largest cell volume, or zero for a mesh without cells.
+    pub max_cell_volume: f64,
+    /// Per-patch statistics, in patch order.
+    pub patches: Vec<PatchStats>,
+}
+
+impl Mesh {
+    /// Returns global statistics of the mesh.
+    pub fn stats(&self) -> MeshStats {
+        let bounds = Aabb::of_points(self.points().iter().copied());
+        let (bounds_min, bounds_max) = if self.n_points() == 0 {
+            (Vector::zero(), Vector::zero())
+        } else {
+            (bounds.min, bounds.max)
+        };
+        let volumes = self.cell_volumes();
+        let (min_cell_volume, max_cell_volume) = if volumes.is_empty() {
+            (0.0, 0.0)
+        } else {
+            volumes
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                    (lo.min(v), hi.max(v))
+                })
+        };
+        let patches = self
+            .patches()
+            .iter()
+            .map(|p| PatchStats {
+                name: p.name().to_string(),
+                kind: p.kind().type_name().to_string(),
+                n_faces: p.size(),
+                area: self.face_areas()[p.range()].iter().map(|s| s.mag()).sum(),
+            })
+            .collect();
+        MeshStats {
+            n_points: self.n_points(),
+            n_faces: self.n_faces(),
+            n_internal_faces: self.n_internal_faces(),
+            n_cells: self.n_cells(),
+            bounds_min,
+            bounds_max,
+            total_volume: volumes.iter().sum(),
+            min_cell_volume,
+            max_cell_volume,
+            patches,
+        }
+    }
+}
+
+impl fmt::Display for MeshStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let v = |v: &Vector| format!("({:.6e} {:.6e} {:.6e})", v.x(), v.y(), v.z());
+        writeln!(f, "Mesh stats")?;
+        writeln!(f, "    points:           {}", self.n_points)?;
+        writeln!(f, "    faces:            {}", self.n_faces)?;
+        writeln!(f, "    internal faces:   {}", self.n_internal_faces)?;
+        writeln!(f, "    cells:            {}", self.n_cells)?;
+        writeln!(
+            f,
+            "    bounding box:     {} {}",
+            v(&self.bounds_min),
+            v(&self.bounds_max)
+        )?;
+        writeln!(f, "    total volume:     {:.6e}", self.total_volume)?;
+        writeln!(
+            f,
+            "    cell volume:      min {:.6e}, max {:.6e}",
+            self.min_cell_volume, self.max_cell_volume
+        )?;
+        writeln!(f, "Patches")?;
+        let width = self.patches.iter().map(|p| p.name.len()).max().unwrap_or(0);
+        for p in &self.patches {
+            writeln!(
+                f,
+                "    {:width$}  {:<16} faces {:>8}  area {:.6e}",
+                p.name, p.kind, p.n_faces, p.area
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_stats_box_mesh() {
+        let mesh = box_mesh([2, 3, 4], [2.0, 3.0, 4.0]);
+        let stats = mesh.stats();
+        assert_eq!(stats.n_cells, 24);
+        assert_eq!(stats.n_points, 3 * 4 * 5);
+        assert_eq!(stats.n_faces, mesh.n_faces());
+        assert_eq!(stats.bounds_max, Vector::new(2.0, 3.0, 4.0));
+        assert!((stats.total_volume - 24.0).abs() < 1e-12);
+        assert!((stats.min_cell_volume - 1.0).abs() < 1e-12);
+        assert!((stats.max_cell_volume - 1.0).abs() < 1e-12);
+        let z_min = &stats.patches[4];
+        assert_eq!((z_min.name.as_str(), z_min.n_faces), ("z-min", 6));
+        assert!((z_min.area - 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_stats_report_lists_patches() {
+        let report = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]).stats().to_string();
+        assert!(report.contains("cells:            1"));
+        for name in ["x-min", "x-max", "y-min", "y-max", "z-min", "z-max"] {
+            assert!(report.contains(name));
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Shared mesh fixtures for unit tests.
+
+use dugong_types::tensor::Vector;
+
+use crate::mesh::Mesh;
+use crate::patch::{Patch, PatchKind};
+use crate::primitive_mesh::PrimitiveMesh;
+
+/// Builds a structured `n[0] x n[1] x n[2]` hex mesh of the box
+/// `[0, l[0]] x [0, l[1]] x [0, l[2]]`.
+///
+/// Internal faces are in upper-triangular order. Boundary faces are grouped
+/// into the patches `x-min`, `x-max`, `y-min`, `y-max`, `z-min`, `z-max`
+/// (all of kind [`PatchKind::Patch`]).
+pub(crate) fn box_mesh(n: [usize; 3], l: [f64; 3]) -> Mesh {
+    let [nx, ny, nz] = n;
+    let pid = |i: usize, j: usize, k: usize| i + (nx + 1) * (j + (ny + 1) * k);
+    let cid = |i: usize, j: usize, k: usize| i + nx * (j + ny * k);
+
+    let mut points = Vec::with_capacity((nx + 1) * (ny + 1) * (nz + 1));
+    for k in 0..=nz {
+        for j in 0..=ny {
+            for i in 0..=nx {
+                points.push(Vector::new(
+                    l[0] * i as f64 / nx as f64,
+                    l[1] * j as f64 / ny as f64,
+                    l[2] * k as f64 / nz as f64,
+                ));
+            }
+        }
+    }
+
+    // Faces with +x, +y, +z normals at the lower corner (i, j, k).
+    let fx = |i, j, k| {
+        vec![
+            pid(i, j, k),
+            pid(i, j + 1, k),
+            pid(i, j + 1, k + 1),
+            pid(i, j, k + 1),
+        ]
+    };
+    let fy = |i, j, k| {
+        vec![
+            pid(i, j, k),
+            pid(i, j, k + 1),
+            pid(i + 1, j, k + 1),
+            pid(i + 1, j, k),
+        ]
+    };
+    let fz = |i, j, k| {
+        vec![
+            pid(i, j, k),
+            pid(i + 1, j, k),
+            pid(i + 1, j + 1, k),
+            pid(i, j + 1, k),
+        ]
+    };
+    let rev = |mut f: Vec<usize>| {
+        f.reverse();
+        f
+    };
+
+    let mut faces = Vec::new();
+    let mut owner = Vec::new();
+    let mut neighbor = Vec::new();
+    for k in 0..nz {
+        for j in 0..ny {
+            for i in 0..nx {
+                let c = cid(i, j, k);
+                if i + 1 < nx {
+                    faces.push(fx(i + 1, j, k));
+                    owner.push(c);
+                    neighbor.push(cid(i + 1, j, k));
+                }
+                if j + 1 < ny {
+                    faces.push(fy(i, j + 1, k));
+                    owner.push(c);
+                    neighbor.push(cid(i, j + 1, k));
+                }
+                if k + 1 < nz {
+                    faces.push(fz(i, j, k + 1));
+                    owner.push(c);
+                    neighbor.push(cid(i, j, k + 1));
+                }
+            }
+        }
+    }
+
+    let mut patches = Vec::new();
+    let mut add_patch = |name: &str, fs: Vec<(Vec<usize>, usize)>| {
+        patches.push(Patch::new(name, PatchKind::Patch, faces.len(), fs.len()));
+        for (f, c) in fs {
+            faces.push(f);
+            owner.push(c);
+        }
+    };
+    let mut xmin = Vec::new();
+    let mut xmax = Vec::new();
+    for k in 0..nz {
+        for j in 0..ny {
+            xmin.push((rev(fx(0, j, k)), cid(0, j, k)));
+            xmax.push((fx(nx, j, k), cid(nx - 1, j, k)));
+        }
+    }
+    add_patch("x-min", xmin);
+    add_patch("x-max", xmax);
+    let mut ymin = Vec::new();
+    let mut ymax = Vec::new();
+    for k in 0..nz {
+        for i in 0..nx {
+            ymin.push((rev(fy(i, 0, k)), cid(i, 0, k)));
+            ymax.push((fy(i, ny, k), cid(i, ny - 1, k)));
+        }
+    }
+    add_patch("y-min", ymin);
+    add_patch("y-max", ymax);
+    let mut zmin = Vec::new();
+    let mut zmax = Vec::new();
+    for j in 0..ny {
+        for i in 0..nx {
+            zmin.push((rev(fz(i, j, 0)), cid(i, j, 0)));
+            zmax.push((fz(i, j, nz), cid(i, j, nz - 1)));
+        }
+    }
+    add_patch("z-min", zmin);
+    add_patch("z-max", zmax);
+
+    let primitive = PrimitiveMesh::new(points, faces, owner, neighbor).unwrap();
+    Mesh::new(primitive, patches).unwrap()
+}
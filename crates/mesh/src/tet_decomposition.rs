@@ -0,0 +1,146 @@
+use std::ops::Range;
+
+use dugong_types::tensor::Vector;
+
+use crate::mesh::Mesh;
+
+/// A mesh split into tetrahedra, returned by [`Mesh::tet_decomposition`].
+///
+/// The points are the mesh points followed by one point per cell (the cell
+/// center). Every tetrahedron is positively oriented for a valid mesh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TetDecomposition {
+    points: Vec<Vector>,
+    tets: Vec<[usize; 4]>,
+    cell_offsets: Vec<usize>,
+}
+
+impl TetDecomposition {
+    /// Returns the points: mesh points, then cell centers.
+    pub fn points(&self) -> &[Vector] {
+        &self.points
+    }
+
+    /// Returns the tetrahedra as point indices.
+    pub fn tets(&self) -> &[[usize; 4]] {
+        &self.tets
+    }
+
+    /// Returns the number of tetrahedra.
+    pub fn n_tets(&self) -> usize {
+        self.tets.len()
+    }
+
+    /// Returns the tetrahedra of `cell`, which are contiguous.
+    pub fn cell_tets(&self, cell: usize) -> Range<usize> {
+        self.cell_offsets[cell]..self.cell_offsets[cell + 1]
+    }
+
+    /// Returns the cell each tetrahedron belongs to.
+    pub fn tet_cells(&self) -> Vec<usize> {
+        (0..self.cell_offsets.len() - 1)
+            .flat_map(|c| self.cell_tets(c).map(move |_| c))
+            .collect()
+    }
+}
+
+impl Mesh {
+    /// Splits every cell into tetrahedra.
+    ///
+    /// Each face is fan-triangulated from its first vertex and each triangle
+    /// is joined to the cell center. No face points are added, so the two
+    /// cells sharing a face triangulate it identically and the result is
+    /// conforming. Exact for cells that are star-shaped about their center
+    /// with planar faces.
+    pub fn tet_decomposition(&self) -> TetDecomposition {
+        let owner = self.owner();
+        let n_points = self.n_points();
+        let mut points = self.points().to_vec();
+        points.extend_from_slice(self.cell_centers());
+
+        let mut tets = Vec::new();
+        let mut cell_offsets = Vec::with_capacity(self.n_cells() + 1);
+        cell_offsets.push(0);
+        for (c, faces) in self.cell_faces().iter().enumerate() {
+            let center = n_points + c;
+            for &f in faces {
+                let face = &self.faces()[f];
+                // Owner faces point out of the cell; flip their triangles so
+                // the center lies on the positive side.
+                let outward = owner[f] == c;
+                for i in 1..face.len().saturating_sub(1) {
+                    let (a, b, d) = (face[0], face[i], face[i + 1]);
+                    tets.push(if outward {
+                        [a, d, b, center]
+                    } else {
+                        [a, b, d, center]
+                    });
+                }
+            }
+            cell_offsets.push(tets.len());
+        }
+
+        TetDecomposition {
+            points,
+            tets,
+            cell_offsets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refine::HexRefiner;
+    use crate::test_meshes::box_mesh;
+
+    fn tet_volume(points: &[Vector], tet: [usize; 4]) -> f64 {
+        let [a, b, c, d] = tet.map(|i| points[i]);
+        ((b - a).cross(&(c - a)) * (d - a)) / 6.0
+    }
+
+    #[test]
+    fn test_tet_decomposition_box_mesh_volumes() {
+        let mesh = box_mesh([2, 2, 1], [2.0, 1.0, 1.0]);
+        let tets = mesh.tet_decomposition();
+        // Six quadrilateral faces of two triangles each, per cell.
+        assert_eq!(tets.n_tets(), 4 * 12);
+        assert_eq!(tets.points().len(), mesh.n_points() + 4);
+        for c in 0..mesh.n_cells() {
+            let range = tets.cell_tets(c);
+            assert_eq!(range.len(), 12);
+            let volume: f64 = tets.tets()[range]
+                .iter()
+                .map(|&t| {
+                    let v = tet_volume(tets.points(), t);
+                    assert!(v > 0.0);
+                    v
+                })
+                .sum();
+            assert!((volume - mesh.cell_volumes()[c]).abs() < 1e-12);
+        }
+        assert_eq!(tets.tet_cells()[12], 1);
+    }
+
+    #[test]
+    fn test_tet_decomposition_polyhedral_cells() {
+        let mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let mut refiner = HexRefiner::new(&mesh).unwrap();
+        refiner.refine(&[0]);
+        let refined = refiner.mesh().unwrap();
+        let tets = refined.tet_decomposition();
+        let total: f64 = tets
+            .tets()
+            .iter()
+            .map(|&t| tet_volume(tets.points(), t))
+            .sum();
+        assert!((total - 2.0).abs() < 1e-12);
+        // Faces with hanging points have collinear vertices, which give
+        // flat tetrahedra.
+        assert!(
+            tets.tets()
+                .iter()
+                .all(|&t| tet_volume(tets.points(), t) > -1e-12)
+        );
+    }
+}
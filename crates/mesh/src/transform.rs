@@ -0,0 +1,201 @@
+use dugong_types::tensor::Vector;
+
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::patch::{CoupledTransform, Patch, PatchKind};
+
+impl Mesh {
+    /// Translates all points by `offset`.
+    pub fn translate(&mut self, offset: Vector) {
+        self.transform(|p| p + offset, |v| v, false);
+    }
+
+    /// Scales all points about the origin by `factor`, e.g. `1e-3` to convert
+    /// a mesh drawn in millimeters to meters.
+    ///
+    /// A negative factor inverts the mesh through the origin.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::InvalidScaleFactor`] if `factor` is zero or not
+    /// finite.
+    pub fn scale(&mut self, factor: f64) -> Result<(), MeshError> {
+        if factor == 0.0 || !factor.is_finite() {
+            return Err(MeshError::InvalidScaleFactor { factor });
+        }
+        self.transform(|p| p * factor, |v| v * factor, factor < 0.0);
+        Ok(())
+    }
+
+    /// Rotates all points by `angle` radians about the axis through `center`
+    /// along `axis` (right-hand rule).
+    pub fn rotate(&mut self, axis: Vector, center: Vector, angle: f64) {
+        let rotation = CoupledTransform::Rotational {
+            axis,
+            center,
+            angle,
+        };
+        let r = rotation.rotation();
+        self.transform(|p| rotation.transform_point(p), |v| r * v, false);
+    }
+
+    /// Reflects all points in the plane through `point` with normal `normal`.
+    ///
+    /// Face vertex orders are reversed so that area vectors still point from
+    /// owner to neighbor.
+    pub fn mirror(&mut self, point: Vector, normal: Vector) {
+        let n = normal * (1.0 / normal.mag());
+        let reflect = move |v: Vector| v - n * (2.0 * (v * n));
+        self.transform(move |p| point + reflect(p - point), reflect, true);
+    }
+
+    /// Applies an affine map, given by its action on points and on vectors,
+    /// to the points and to the transforms of coupled patches. `flips` marks
+    /// maps that change handedness.
+    ///
+    /// Any motion state is discarded, since old-time geometry would no
+    /// longer match the transformed mesh.
+    fn transform(
+        &mut self,
+        point_map: impl Fn(Vector) -> Vector,
+        vector_map: impl Fn(Vector) -> Vector,
+        flips: bool,
+    ) {
+        self.primitive.transform_points(&point_map, flips);
+        self.motion = None;
+        let map_transform = |t: &CoupledTransform| match t {
+            CoupledTransform::None => CoupledTransform::None,
+            CoupledTransform::Translational { separation } => CoupledTransform::Translational {
+                separation: vector_map(*separation),
+            },
+            CoupledTransform::Rotational {
+                axis,
+                center,
+                angle,
+            } => CoupledTransform::Rotational {
+                axis: vector_map(*axis),
+                center: point_map(*center),
+                angle: if flips { -angle } else { *angle },
+            },
+        };
+        for patch in &mut self.patches {
+            let kind = match patch.kind() {
+                PatchKind::Cyclic {
+                    neighbor_patch,
+                    transform,
+                } => PatchKind::Cyclic {
+                    neighbor_patch: neighbor_patch.clone(),
+                    transform: map_transform(transform),
+                },
+                PatchKind::CyclicAmi {
+                    neighbor_patch,
+                    transform,
+                } => PatchKind::CyclicAmi {
+                    neighbor_patch: neighbor_patch.clone(),
+                    transform: map_transform(transform),
+                },
+                PatchKind::Processor {
+                    my_rank,
+                    neighbor_rank,
+                    transform,
+                } => PatchKind::Processor {
+                    my_rank: *my_rank,
+                    neighbor_rank: *neighbor_rank,
+                    transform: map_transform(transform),
+                },
+                _ => continue,
+            };
+            *patch = Patch::new(patch.name(), kind, patch.start(), patch.size());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refine::tests::assert_closed;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_translate_moves_centers() {
+        let mut mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let _ = mesh.cell_centers();
+        mesh.translate(Vector::new(1.0, -1.0, 0.5));
+        assert!((mesh.cell_centers()[0] - Vector::new(1.5, -0.5, 1.0)).mag() < 1e-12);
+    }
+
+    #[test]
+    fn test_scale_converts_units() {
+        let mut mesh = box_mesh([2, 2, 2], [2000.0, 2000.0, 2000.0]);
+        mesh.scale(1e-3).unwrap();
+        let total: f64 = mesh.cell_volumes().iter().sum();
+        assert!((total - 8.0).abs() < 1e-12);
+        assert!(matches!(
+            mesh.scale(0.0),
+            Err(MeshError::InvalidScaleFactor { .. })
+        ));
+    }
+
+    #[test]
+    fn test_negative_scale_keeps_volumes_positive() {
+        let mut mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        mesh.scale(-2.0).unwrap();
+        assert_closed(&mesh);
+        assert!(mesh.cell_volumes().iter().all(|&v| (v - 8.0).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_rotate_quarter_turn() {
+        let mut mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        mesh.rotate(
+            Vector::new(0.0, 0.0, 1.0),
+            Vector::zero(),
+            std::f64::consts::FRAC_PI_2,
+        );
+        assert!((mesh.cell_centers()[0] - Vector::new(-0.5, 0.5, 0.5)).mag() < 1e-12);
+        assert!((mesh.cell_volumes()[0] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_mirror_fixes_face_orientation() {
+        let mut mesh = box_mesh([3, 2, 1], [3.0, 2.0, 1.0]);
+        mesh.mirror(Vector::new(0.0, 0.0, 0.0), Vector::new(1.0, 0.0, 0.0));
+        assert_closed(&mesh);
+        assert!(mesh.cell_volumes().iter().all(|&v| (v - 1.0).abs() < 1e-12));
+        let cc = mesh.cell_centers();
+        for f in 0..mesh.n_internal_faces() {
+            let d = cc[mesh.neighbor()[f]] - cc[mesh.owner()[f]];
+            assert!(mesh.face_areas()[f] * d > 0.0);
+        }
+        assert!((cc[0] - Vector::new(-0.5, 0.5, 0.5)).mag() < 1e-12);
+    }
+
+    #[test]
+    fn test_transforms_update_cyclic_transforms() {
+        let mut mesh = box_mesh([2, 1, 1], [2.0, 1.0, 1.0]);
+        let cyclic = |other: &str, x: f64| PatchKind::Cyclic {
+            neighbor_patch: other.to_string(),
+            transform: CoupledTransform::Translational {
+                separation: Vector::new(x, 0.0, 0.0),
+            },
+        };
+        for (i, kind) in [(0, cyclic("x-max", 2.0)), (1, cyclic("x-min", -2.0))] {
+            let p = &mesh.patches[i];
+            mesh.patches[i] = Patch::new(p.name(), kind, p.start(), p.size());
+        }
+        mesh.scale(0.5).unwrap();
+        mesh.rotate(
+            Vector::new(0.0, 0.0, 1.0),
+            Vector::zero(),
+            std::f64::consts::FRAC_PI_2,
+        );
+        let (b, transform) = mesh.cyclic_neighbor(0).unwrap();
+        let src = mesh.face_centers()[mesh.patches()[0].start()];
+        let dst = mesh.face_centers()[mesh.patches()[b].start()];
+        assert!((transform.transform_point(src) - dst).mag() < 1e-12);
+        let CoupledTransform::Translational { separation } = transform else {
+            panic!("transform should stay translational");
+        };
+        assert!((*separation - Vector::new(0.0, 1.0, 0.0)).mag() < 1e-12);
+    }
+}
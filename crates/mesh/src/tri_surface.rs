@@ -0,0 +1,204 @@
+use std::f64::consts::PI;
+
+use dugong_types::tensor::Vector;
+
+use crate::bvh::{Aabb, Bvh};
+use crate::error::MeshError;
+use crate::geometry;
+
+/// A triangulated surface, such as geometry read from STL or OBJ files.
+///
+/// Triangles are oriented counterclockwise seen from outside, so normals
+/// point out of closed surfaces. A bounding volume hierarchy over the
+/// triangles is built on construction for nearest-point queries.
+#[derive(Debug, Clone)]
+pub struct TriSurface {
+    points: Vec<Vector>,
+    triangles: Vec<[usize; 3]>,
+    bvh: Bvh,
+}
+
+impl TriSurface {
+    /// Constructs a surface from its points and triangles.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::PointIndexOutOfRange`] (with the triangle index
+    /// as `face`) if a triangle references a missing point.
+    pub fn new(points: Vec<Vector>, triangles: Vec<[usize; 3]>) -> Result<Self, MeshError> {
+        let n_points = points.len();
+        for (face, t) in triangles.iter().enumerate() {
+            if let Some(&point) = t.iter().find(|&&p| p >= n_points) {
+                return Err(MeshError::PointIndexOutOfRange {
+                    face,
+                    point,
+                    n_points,
+                });
+            }
+        }
+        let boxes: Vec<Aabb> = triangles
+            .iter()
+            .map(|t| Aabb::of_points(t.iter().map(|&p| points[p])))
+            .collect();
+        Ok(Self {
+            bvh: Bvh::new(&boxes),
+            points,
+            triangles,
+        })
+    }
+
+    /// Returns the vertex coordinates.
+    pub fn points(&self) -> &[Vector] {
+        &self.points
+    }
+
+    /// Returns the vertex indices of each triangle.
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// Returns the number of triangles.
+    pub fn n_triangles(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// Returns the corner coordinates of triangle `t`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `t >= n_triangles()`.
+    pub fn corners(&self, t: usize) -> [Vector; 3] {
+        self.triangles[t].map(|p| self.points[p])
+    }
+
+    /// Returns the area vector of each triangle: its normal scaled by its
+    /// area.
+    pub fn area_vectors(&self) -> Vec<Vector> {
+        (0..self.n_triangles())
+            .map(|t| {
+                let [a, b, c] = self.corners(t);
+                (b - a).cross(&(c - a)) * 0.5
+            })
+            .collect()
+    }
+
+    /// Returns the unit normal of each triangle, or zero for degenerate
+    /// triangles.
+    pub fn normals(&self) -> Vec<Vector> {
+        self.area_vectors()
+            .into_iter()
+            .map(|s| {
+                let mag = s.mag();
+                if mag > 0.0 { s / mag } else { s }
+            })
+            .collect()
+    }
+
+    /// Returns the total surface area.
+    pub fn area(&self) -> f64 {
+        self.area_vectors().iter().map(|s| s.mag()).sum()
+    }
+
+    /// Returns the triangle nearest to `p` and the closest point on it, or
+    /// `None` for an empty surface.
+    pub fn nearest(&self, p: Vector) -> Option<(usize, Vector)> {
+        let closest = |t: usize| {
+            let [a, b, c] = self.corners(t);
+            geometry::closest_point_on_triangle(p, a, b, c)
+        };
+        self.bvh
+            .nearest(p, |t| (closest(t) - p).mag())
+            .map(|(t, _)| (t, closest(t)))
+    }
+
+    /// Returns the generalized winding number of the surface around `p`:
+    /// one inside and zero outside a closed, outward-oriented surface.
+    ///
+    /// Small gaps and overlaps shift the value only near the defects, so
+    /// thresholding at one half stays robust for imperfect geometry. Costs
+    /// one solid angle per triangle.
+    pub fn winding_number(&self, p: Vector) -> f64 {
+        let solid_angle: f64 = (0..self.n_triangles())
+            .map(|t| {
+                let [a, b, c] = self.corners(t).map(|x| x - p);
+                let (la, lb, lc) = (a.mag(), b.mag(), c.mag());
+                let num = a * b.cross(&c);
+                let den = la * lb * lc + (a * b) * lc + (a * c) * lb + (b * c) * la;
+                2.0 * num.atan2(den)
+            })
+            .sum();
+        solid_angle / (4.0 * PI)
+    }
+
+    /// Returns `true` if `p` lies inside the surface, i.e. its winding
+    /// number exceeds one half.
+    pub fn is_inside(&self, p: Vector) -> bool {
+        self.winding_number(p) > 0.5
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Returns the closed unit cube `[0, 1]^3` with outward triangles.
+    pub(crate) fn unit_cube() -> TriSurface {
+        let points = (0..8)
+            .map(|i| Vector::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+            .collect();
+        let quads = [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+        ];
+        let triangles = quads
+            .iter()
+            .flat_map(|q| [[q[0], q[1], q[2]], [q[0], q[2], q[3]]])
+            .collect();
+        TriSurface::new(points, triangles).unwrap()
+    }
+
+    #[test]
+    fn test_tri_surface_rejects_bad_index() {
+        assert!(matches!(
+            TriSurface::new(vec![Vector::zero(); 2], vec![[0, 1, 2]]),
+            Err(MeshError::PointIndexOutOfRange { point: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_tri_surface_cube_area_and_normals() {
+        let cube = unit_cube();
+        assert!((cube.area() - 6.0).abs() < 1e-12);
+        let total = cube
+            .area_vectors()
+            .iter()
+            .fold(Vector::zero(), |s, &a| s + a);
+        assert!(total.mag() < 1e-12);
+        // Normals point away from the cube center.
+        let center = Vector::new(0.5, 0.5, 0.5);
+        for (t, n) in cube.normals().iter().enumerate() {
+            assert!((cube.corners(t)[0] - center) * *n > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_tri_surface_inside_outside() {
+        let cube = unit_cube();
+        assert!((cube.winding_number(Vector::new(0.3, 0.6, 0.2)) - 1.0).abs() < 1e-9);
+        assert!(cube.is_inside(Vector::new(0.9, 0.1, 0.5)));
+        assert!(!cube.is_inside(Vector::new(1.5, 0.5, 0.5)));
+        assert!(cube.winding_number(Vector::new(-3.0, 2.0, 1.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tri_surface_nearest() {
+        let cube = unit_cube();
+        let (t, q) = cube.nearest(Vector::new(0.4, 0.7, 2.0)).unwrap();
+        assert!((q - Vector::new(0.4, 0.7, 1.0)).mag() < 1e-12);
+        assert!(cube.normals()[t].z() > 0.99);
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+
+use dugong_types::tensor::Vector;
+
+use crate::assemble::{BoundaryGroup, InternalFace, assemble};
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::patch::PatchKind;
+use crate::zone::Zone;
+
+/// Names of the patches on the six sides of the voxel grid, in the order of
+/// [`voxel_mesh`]'s direction index (`2 * axis + side`).
+const SIDE_PATCHES: [&str; 6] = ["x-min", "x-max", "y-min", "y-max", "z-min", "z-max"];
+
+/// Builds a hexahedral mesh from a labelled voxel grid, such as segmented
+/// CT data or a thresholded level-set field.
+///
+/// `labels` holds `dims[0] * dims[1] * dims[2]` voxel labels with x varying
+/// fastest. Voxels labelled 0 are void and get no cell; every other voxel
+/// becomes a box of size `spacing` with its lowest corner at `origin + (i,
+/// j, k) * spacing`, and cells are numbered in voxel order. Each label `l`
+/// gets a cell zone `material-l`, in increasing label order. Faces on the
+/// sides of the grid form the patches `x-min` to `z-max`, and faces against
+/// void voxels the wall patch `void`; faces between labels stay internal.
+///
+/// # Errors
+///
+/// Returns [`MeshError::InvalidMeshing`] if `labels` does not match `dims`,
+/// a spacing component is not positive, or every voxel is void.
+pub fn voxel_mesh(
+    dims: [usize; 3],
+    labels: &[usize],
+    origin: Vector,
+    spacing: Vector,
+) -> Result<Mesh, MeshError> {
+    let invalid = |reason: String| MeshError::InvalidMeshing { reason };
+    let [nx, ny, nz] = dims;
+    if labels.len() != nx * ny * nz {
+        return Err(invalid(format!(
+            "{} labels do not fill a {nx}x{ny}x{nz} grid",
+            labels.len()
+        )));
+    }
+    let h = *spacing.as_array();
+    if h.iter().any(|&s| s.is_nan() || s <= 0.0) {
+        return Err(invalid(format!("voxel spacing {h:?} is not positive")));
+    }
+
+    let vid = |i: usize, j: usize, k: usize| i + nx * (j + ny * k);
+    let mut cell = vec![usize::MAX; labels.len()];
+    let mut zones: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    let mut n_cells = 0;
+    for (v, &label) in labels.iter().enumerate() {
+        if label != 0 {
+            cell[v] = n_cells;
+            zones.entry(label).or_default().push(n_cells);
+            n_cells += 1;
+        }
+    }
+    if n_cells == 0 {
+        return Err(invalid("every voxel is void".into()));
+    }
+
+    let pid = |i: usize, j: usize, k: usize| i + (nx + 1) * (j + (ny + 1) * k);
+    let mut point_of = vec![usize::MAX; (nx + 1) * (ny + 1) * (nz + 1)];
+    let mut points = Vec::new();
+    let mut point = |i: usize, j: usize, k: usize| {
+        let p = pid(i, j, k);
+        if point_of[p] == usize::MAX {
+            point_of[p] = points.len();
+            let offset = [i, j, k].map(|n| n as f64);
+            points.push(origin + Vector::new(offset[0] * h[0], offset[1] * h[1], offset[2] * h[2]));
+        }
+        point_of[p]
+    };
+    // The face of voxel `(i, j, k)` normal to `axis` at its lower corner,
+    // oriented toward +axis.
+    let mut lower_face = |[i, j, k]: [usize; 3], axis: usize| -> Vec<usize> {
+        let corners: [[usize; 3]; 4] = match axis {
+            0 => [[i, j, k], [i, j + 1, k], [i, j + 1, k + 1], [i, j, k + 1]],
+            1 => [[i, j, k], [i, j, k + 1], [i + 1, j, k + 1], [i + 1, j, k]],
+            _ => [[i, j, k], [i + 1, j, k], [i + 1, j + 1, k], [i, j + 1, k]],
+        };
+        corners.iter().map(|&[a, b, c]| point(a, b, c)).collect()
+    };
+
+    let mut internal: Vec<InternalFace> = Vec::new();
+    let mut boundary: [Vec<(Vec<usize>, usize)>; 7] = Default::default();
+    for k in 0..nz {
+        for j in 0..ny {
+            for i in 0..nx {
+                let c = cell[vid(i, j, k)];
+                if c == usize::MAX {
+                    continue;
+                }
+                let ijk = [i, j, k];
+                for axis in 0..3 {
+                    let mut lower = ijk;
+                    let mut upper = ijk;
+                    upper[axis] += 1;
+                    // Face at the upper side, pointing out of `c`.
+                    let neighbor =
+                        (upper[axis] < dims[axis]).then(|| cell[vid(upper[0], upper[1], upper[2])]);
+                    let face = lower_face(upper, axis);
+                    match neighbor {
+                        Some(n) if n != usize::MAX => internal.push((face, c, n)),
+                        Some(_) => boundary[6].push((face, c)),
+                        None => boundary[2 * axis + 1].push((face, c)),
+                    }
+                    // Face at the lower side; internal ones were added by
+                    // the lower neighbor.
+                    let below = (lower[axis] > 0).then(|| {
+                        lower[axis] -= 1;
+                        cell[vid(lower[0], lower[1], lower[2])]
+                    });
+                    let mut face = || {
+                        let mut f = lower_face(ijk, axis);
+                        f.reverse();
+                        f
+                    };
+                    match below {
+                        Some(n) if n != usize::MAX => {}
+                        Some(_) => boundary[6].push((face(), c)),
+                        None => boundary[2 * axis].push((face(), c)),
+                    }
+                }
+            }
+        }
+    }
+
+    let groups = boundary
+        .into_iter()
+        .enumerate()
+        .map(|(g, faces)| BoundaryGroup {
+            name: SIDE_PATCHES.get(g).copied().unwrap_or("void").to_string(),
+            kind: if g < 6 {
+                PatchKind::Patch
+            } else {
+                PatchKind::Wall
+            },
+            faces,
+        })
+        .collect();
+    let (mut mesh, _) = assemble(points, internal, groups)?;
+    mesh.cell_zones = zones
+        .into_iter()
+        .map(|(label, cells)| Zone::new(format!("material-{label}"), cells))
+        .collect();
+    Ok(mesh)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refine::tests::assert_closed;
+
+    #[test]
+    fn test_voxel_mesh_builds_labelled_cells() {
+        // A 3x2x1 grid: two materials and one void voxel in the middle of
+        // the first row.
+        let labels = [1, 0, 2, 1, 1, 2];
+        let mesh = voxel_mesh(
+            [3, 2, 1],
+            &labels,
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(0.5, 1.0, 2.0),
+        )
+        .unwrap();
+        assert_closed(&mesh);
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.n_cells(), 5);
+        assert!(mesh.cell_volumes().iter().all(|&v| (v - 1.0).abs() < 1e-12));
+        assert!((mesh.cell_centers()[0] - Vector::new(1.25, 0.5, 1.0)).mag() < 1e-12);
+        // The void voxel is bounded by three cells.
+        assert_eq!(mesh.patch("void").unwrap().size(), 3);
+        assert_eq!(mesh.patch("void").unwrap().kind(), &PatchKind::Wall);
+        assert_eq!(mesh.patch("x-min").unwrap().size(), 2);
+        assert_eq!(mesh.patch("y-min").unwrap().size(), 2);
+        assert_eq!(mesh.patch("z-max").unwrap().size(), 5);
+        assert_eq!(mesh.n_internal_faces(), 4);
+        assert_eq!(mesh.cell_zone("material-1").unwrap().indices(), &[0, 2, 3]);
+        assert_eq!(mesh.cell_zone("material-2").unwrap().indices(), &[1, 4]);
+        // The corners of the void voxel are shared with its neighbors.
+        assert_eq!(mesh.n_points(), 24);
+    }
+
+    #[test]
+    fn test_voxel_mesh_rejects_invalid_grids() {
+        let one = Vector::new(1.0, 1.0, 1.0);
+        for (dims, labels, spacing) in [
+            ([2, 1, 1], vec![1], one),
+            ([1, 1, 1], vec![0], one),
+            ([1, 1, 1], vec![1], Vector::new(1.0, 0.0, 1.0)),
+        ] {
+            assert!(matches!(
+                voxel_mesh(dims, &labels, Vector::zero(), spacing),
+                Err(MeshError::InvalidMeshing { .. })
+            ));
+        }
+    }
+}
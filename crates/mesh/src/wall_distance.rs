@@ -0,0 +1,224 @@
+use dugong_types::tensor::Vector;
+
+use crate::bvh::{Aabb, Bvh};
+use crate::error::MeshError;
+use crate::geometry;
+use crate::mesh::Mesh;
+
+/// How [`Mesh::wall_distance_with`] computes distances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WallDistanceMethod {
+    /// Exact distance from each cell center to the nearest wall face, found
+    /// with a bounding volume hierarchy over the wall faces.
+    #[default]
+    Exact,
+    /// Approximate distance from the solution of `laplacian(phi) = -1` with
+    /// `phi = 0` on the walls (Spalding's Poisson method). Cheaper to update
+    /// on moving meshes and smooth near sharp corners; exact for planar
+    /// channels.
+    Poisson,
+}
+
+impl Mesh {
+    /// Returns the distance from each cell center to the nearest face of the
+    /// named patches, computed exactly. See [`Mesh::wall_distance_with`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::PatchNotFound`] for unknown patch names.
+    pub fn wall_distance(&self, patches: &[&str]) -> Result<Vec<f64>, MeshError> {
+        self.wall_distance_with(patches, WallDistanceMethod::Exact)
+    }
+
+    /// Returns the distance from each cell center to the nearest face of the
+    /// named patches.
+    ///
+    /// Distances are `f64::INFINITY` when no faces are selected. Only the
+    /// local mesh is searched: on a decomposed mesh, walls owned by other
+    /// ranks are not seen.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeshError::PatchNotFound`] for unknown patch names.
+    pub fn wall_distance_with(
+        &self,
+        patches: &[&str],
+        method: WallDistanceMethod,
+    ) -> Result<Vec<f64>, MeshError> {
+        let mut is_wall = vec![false; self.n_faces()];
+        for &name in patches {
+            let patch = self.patch(name).ok_or_else(|| MeshError::PatchNotFound {
+                name: name.to_string(),
+            })?;
+            for face in patch.range() {
+                is_wall[face] = true;
+            }
+        }
+        if !is_wall.contains(&true) {
+            return Ok(vec![f64::INFINITY; self.n_cells()]);
+        }
+        Ok(match method {
+            WallDistanceMethod::Exact => self.exact_wall_distance(&is_wall),
+            WallDistanceMethod::Poisson => self.poisson_wall_distance(&is_wall),
+        })
+    }
+
+    fn exact_wall_distance(&self, is_wall: &[bool]) -> Vec<f64> {
+        let walls: Vec<usize> = (0..self.n_faces()).filter(|&f| is_wall[f]).collect();
+        let boxes: Vec<Aabb> = walls
+            .iter()
+            .map(|&f| Aabb::of_points(self.faces()[f].iter().map(|&p| self.points()[p])))
+            .collect();
+        let bvh = Bvh::new(&boxes);
+        self.cell_centers()
+            .iter()
+            .map(|&c| {
+                let nearest = bvh.nearest(c, |i| {
+                    let face = &self.faces()[walls[i]];
+                    (geometry::closest_point_on_face(self.points(), face, c) - c).mag()
+                });
+                // Safety: `walls` is non-empty, so the hierarchy has items.
+                nearest.unwrap().1
+            })
+            .collect()
+    }
+
+    fn poisson_wall_distance(&self, is_wall: &[bool]) -> Vec<f64> {
+        let n_internal = self.n_internal_faces();
+        let (owner, neighbor) = (self.owner(), self.neighbor());
+        let (sf, cf, cc) = (self.face_areas(), self.face_centers(), self.cell_centers());
+
+        // Two-point coefficients |S|^2 / (S . d) (over-relaxed) for the
+        // internal faces and the walls; other boundaries are zero-gradient.
+        let coeff = |f: usize, d: Vector| {
+            let s = sf[f];
+            (s * s) / (s * d).abs().max(1e-300)
+        };
+        let a_internal: Vec<f64> = (0..n_internal)
+            .map(|f| coeff(f, cc[neighbor[f]] - cc[owner[f]]))
+            .collect();
+        let mut diag = vec![0.0; self.n_cells()];
+        for f in n_internal..self.n_faces() {
+            if is_wall[f] {
+                diag[owner[f]] += coeff(f, cf[f] - cc[owner[f]]);
+            }
+        }
+        for (f, &a) in a_internal.iter().enumerate() {
+            diag[owner[f]] += a;
+            diag[neighbor[f]] += a;
+        }
+        let apply = |x: &[f64]| -> Vec<f64> {
+            let mut y: Vec<f64> = x.iter().zip(&diag).map(|(x, d)| x * d).collect();
+            for (f, &a) in a_internal.iter().enumerate() {
+                y[owner[f]] -= a * x[neighbor[f]];
+                y[neighbor[f]] -= a * x[owner[f]];
+            }
+            y
+        };
+        let phi = conjugate_gradient(apply, self.cell_volumes());
+
+        // Green-Gauss gradient with phi = 0 on the walls.
+        let mut grad = vec![Vector::zero(); self.n_cells()];
+        for f in 0..n_internal {
+            let (o, n) = (owner[f], neighbor[f]);
+            let (dp, dn) = (
+                (sf[f] * (cf[f] - cc[o])).abs(),
+                (sf[f] * (cc[n] - cf[f])).abs(),
+            );
+            let w = dn / (dp + dn);
+            let value = sf[f] * (w * phi[o] + (1.0 - w) * phi[n]);
+            grad[o] += value;
+            grad[n] -= value;
+        }
+        for f in n_internal..self.n_faces() {
+            if !is_wall[f] {
+                grad[owner[f]] += sf[f] * phi[owner[f]];
+            }
+        }
+        grad.iter()
+            .zip(self.cell_volumes())
+            .zip(&phi)
+            .map(|((g, v), &phi)| {
+                let g = (*g / *v).mag();
+                (g * g + 2.0 * phi.max(0.0)).sqrt() - g
+            })
+            .collect()
+    }
+}
+
+/// Solves `A x = b` for a symmetric positive definite `A` given as a
+/// matrix-vector product, to a relative residual of `1e-12`.
+fn conjugate_gradient(apply: impl Fn(&[f64]) -> Vec<f64>, b: &[f64]) -> Vec<f64> {
+    let dot = |x: &[f64], y: &[f64]| x.iter().zip(y).map(|(a, b)| a * b).sum::<f64>();
+    let mut x = vec![0.0; b.len()];
+    let mut r = b.to_vec();
+    let mut p = r.clone();
+    let mut rr = dot(&r, &r);
+    let tol = 1e-24 * rr;
+    for _ in 0..10 * b.len() + 100 {
+        if rr <= tol {
+            break;
+        }
+        let ap = apply(&p);
+        let alpha = rr / dot(&p, &ap);
+        for i in 0..x.len() {
+            x[i] += alpha * p[i];
+            r[i] -= alpha * ap[i];
+        }
+        let rr_new = dot(&r, &r);
+        let beta = rr_new / rr;
+        rr = rr_new;
+        for i in 0..p.len() {
+            p[i] = r[i] + beta * p[i];
+        }
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_meshes::box_mesh;
+
+    #[test]
+    fn test_wall_distance_exact_channel() {
+        let mesh = box_mesh([3, 8, 2], [3.0, 2.0, 1.0]);
+        let d = mesh.wall_distance(&["y-min", "y-max"]).unwrap();
+        for (c, d) in mesh.cell_centers().iter().zip(&d) {
+            let expected = c.y().min(2.0 - c.y());
+            assert!((d - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_wall_distance_exact_corner() {
+        let mesh = box_mesh([4, 4, 1], [1.0, 1.0, 1.0]);
+        let d = mesh.wall_distance(&["x-min", "y-min"]).unwrap();
+        for (c, d) in mesh.cell_centers().iter().zip(&d) {
+            assert!((d - c.x().min(c.y())).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_wall_distance_poisson_channel() {
+        let mesh = box_mesh([2, 20, 1], [1.0, 2.0, 1.0]);
+        let d = mesh
+            .wall_distance_with(&["y-min", "y-max"], WallDistanceMethod::Poisson)
+            .unwrap();
+        for (c, d) in mesh.cell_centers().iter().zip(&d) {
+            let expected = c.y().min(2.0 - c.y());
+            assert!((d - expected).abs() < 0.02, "y {} d {d}", c.y());
+        }
+    }
+
+    #[test]
+    fn test_wall_distance_without_walls_is_infinite() {
+        let mesh = box_mesh([2, 2, 2], [1.0, 1.0, 1.0]);
+        let d = mesh.wall_distance(&[]).unwrap();
+        assert!(d.iter().all(|d| d.is_infinite()));
+        assert!(matches!(
+            mesh.wall_distance(&["nope"]),
+            Err(MeshError::PatchNotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,301 @@
+use dugong_types::tensor::Vector;
+
+use crate::error::MeshError;
+use crate::mesh::Mesh;
+use crate::patch::{CoupledTransform, PatchKind};
+
+/// Largest accepted wedge angle, in degrees. Axisymmetric wedges are
+/// normally a few degrees wide; larger angles break the small-angle
+/// treatment of the wedge faces.
+const MAX_WEDGE_DEGREES: f64 = 10.0;
+
+/// Relative tolerance for the planarity of a wedge patch.
+const PLANAR_TOL: f64 = 1e-6;
+
+/// The geometry of a validated axisymmetric wedge mesh.
+///
+/// The two wedge patches are planes through the symmetry axis. Rotating the
+/// `front` plane by [`Wedge::angle`] about the axis maps it onto the `back`
+/// plane.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wedge {
+    front: usize,
+    back: usize,
+    axis: Vector,
+    center: Vector,
+    angle: f64,
+    center_normal: Vector,
+}
+
+impl Wedge {
+    /// Returns the patch index of the front wedge plane.
+    pub fn front(&self) -> usize {
+        self.front
+    }
+
+    /// Returns the patch index of the back wedge plane.
+    pub fn back(&self) -> usize {
+        self.back
+    }
+
+    /// Returns the unit direction of the symmetry axis.
+    pub fn axis(&self) -> Vector {
+        self.axis
+    }
+
+    /// Returns a point on the symmetry axis.
+    pub fn center(&self) -> Vector {
+        self.center
+    }
+
+    /// Returns the wedge angle in radians.
+    pub fn angle(&self) -> f64 {
+        self.angle
+    }
+
+    /// Returns the unit normal of the mid-plane between the wedge planes,
+    /// i.e. the circumferential direction, pointing from back to front.
+    pub fn center_normal(&self) -> Vector {
+        self.center_normal
+    }
+
+    /// Returns the rotation that maps wedge patch `patch` onto the other
+    /// wedge patch, or `None` if `patch` is neither.
+    pub fn transform(&self, patch: usize) -> Option<CoupledTransform> {
+        let front = CoupledTransform::Rotational {
+            axis: self.axis,
+            center: self.center,
+            angle: self.angle,
+        };
+        match patch {
+            p if p == self.front => Some(front),
+            p if p == self.back => Some(front.inverse()),
+            _ => None,
+        }
+    }
+}
+
+impl Mesh {
+    /// Validates the wedge patches and returns the wedge geometry.
+    ///
+    /// Returns `Ok(None)` for meshes without wedge patches.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the mesh is not a proper wedge:
+    /// - there must be exactly two wedge patches,
+    /// - each wedge patch must be planar,
+    /// - the angle between the planes must lie in `(0, 10]` degrees,
+    /// - every cell must have exactly one face on each wedge patch.
+    pub fn wedge(&self) -> Result<Option<Wedge>, MeshError> {
+        let wedges: Vec<usize> = (0..self.patches.len())
+            .filter(|&p| self.patches[p].kind() == &PatchKind::Wedge)
+            .collect();
+        let (front, back) = match wedges[..] {
+            [] => return Ok(None),
+            [front, back] => (front, back),
+            _ => {
+                return Err(MeshError::WedgePatchCount {
+                    count: wedges.len(),
+                });
+            }
+        };
+        let (n_front, d_front) = self.wedge_plane(front)?;
+        let (n_back, d_back) = self.wedge_plane(back)?;
+
+        // The front plane rotated onto the back plane turns its outward
+        // normal into the inward normal of the back plane.
+        let u = n_front.cross(&n_back);
+        let (sin, cos) = (u.mag(), n_front * -n_back);
+        let angle = sin.atan2(cos);
+        let degrees = angle.to_degrees();
+        if !(degrees > 0.0 && degrees <= MAX_WEDGE_DEGREES) {
+            return Err(MeshError::WedgeAngleOutOfRange {
+                degrees,
+                max: MAX_WEDGE_DEGREES,
+            });
+        }
+        // With u = n_f x n_b the rotation from n_f towards -n_b is about -u.
+        let axis = -u / sin;
+        let center = (n_back.cross(&u) * d_front + u.cross(&n_front) * d_back) / (sin * sin);
+
+        let mut count = vec![[0usize; 2]; self.n_cells()];
+        for (side, p) in [front, back].into_iter().enumerate() {
+            for face in self.patches[p].range() {
+                count[self.owner()[face]][side] += 1;
+            }
+        }
+        if let Some(cell) = count.iter().position(|&c| c != [1, 1]) {
+            return Err(MeshError::WedgeNotSingleCell { cell });
+        }
+
+        let mid = n_front - n_back;
+        Ok(Some(Wedge {
+            front,
+            back,
+            axis,
+            center,
+            angle,
+            center_normal: mid / mid.mag(),
+        }))
+    }
+
+    /// Returns the unit normal `n` and offset `d` of the plane `n * x = d`
+    /// through a wedge patch, checking that the patch is planar.
+    fn wedge_plane(&self, patch: usize) -> Result<(Vector, f64), MeshError> {
+        let patch = &self.patches[patch];
+        let areas = &self.face_areas()[patch.range()];
+        let centers = &self.face_centers()[patch.range()];
+        let not_planar = || MeshError::WedgeNotPlanar {
+            patch: patch.name().to_string(),
+        };
+        let total = areas.iter().fold(Vector::zero(), |s, &a| s + a);
+        let total_mag = areas.iter().map(|a| a.mag()).sum::<f64>();
+        if total_mag == 0.0 {
+            return Err(not_planar());
+        }
+        let n = total / total.mag();
+        let d = n * centers[0];
+        let scale = total_mag.sqrt();
+        for (a, c) in areas.iter().zip(centers) {
+            let misaligned = 1.0 - n * *a / a.mag() > PLANAR_TOL;
+            if misaligned || (n * *c - d).abs() > PLANAR_TOL * scale {
+                return Err(not_planar());
+            }
+        }
+        Ok((n, d))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patch::Patch;
+    use crate::test_meshes::box_mesh;
+
+    /// Bends a `4 x 2 x nz` box into an annular wedge of `degrees` about the
+    /// `x` axis, with radii 0.5..1.5 and the `z` planes as wedge patches.
+    fn wedge_mesh(degrees: f64, nz: usize) -> Mesh {
+        let box_ = box_mesh([4, 2, nz], [2.0, 1.0, 1.0]);
+        let theta = degrees.to_radians();
+        let points = box_
+            .points()
+            .iter()
+            .map(|p| {
+                let (r, phi) = (p.y() + 0.5, (p.z() - 0.5) * theta);
+                Vector::new(p.x(), r * phi.cos(), r * phi.sin())
+            })
+            .collect();
+        let primitive = crate::PrimitiveMesh::new(
+            points,
+            box_.faces().to_vec(),
+            box_.owner().to_vec(),
+            box_.neighbor().to_vec(),
+        )
+        .unwrap();
+        let patches = box_
+            .patches()
+            .iter()
+            .map(|p| {
+                let kind = if p.name().starts_with('z') {
+                    PatchKind::Wedge
+                } else {
+                    p.kind().clone()
+                };
+                Patch::new(p.name(), kind, p.start(), p.size())
+            })
+            .collect();
+        Mesh::new(primitive, patches).unwrap()
+    }
+
+    #[test]
+    fn test_wedge_none_without_wedge_patches() {
+        let mesh = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        assert!(mesh.wedge().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_wedge_recovers_axis_and_angle() {
+        let mesh = wedge_mesh(5.0, 1);
+        let wedge = mesh.wedge().unwrap().unwrap();
+        assert!((wedge.angle().to_degrees() - 5.0).abs() < 1e-9);
+        assert!((wedge.axis().cross(&Vector::new(1.0, 0.0, 0.0))).mag() < 1e-9);
+        // The center lies on the x axis.
+        assert!(wedge.center().y().abs() < 1e-9 && wedge.center().z().abs() < 1e-9);
+        // The front (z-min) plane lies on the -z side of the mid-plane.
+        assert!((wedge.center_normal() - Vector::new(0.0, 0.0, -1.0)).mag() < 1e-9);
+    }
+
+    #[test]
+    fn test_wedge_transform_maps_front_onto_back() {
+        let mesh = wedge_mesh(4.0, 1);
+        let wedge = mesh.wedge().unwrap().unwrap();
+        let front = &mesh.patches()[wedge.front()];
+        let back = &mesh.patches()[wedge.back()];
+        let t = wedge.transform(wedge.front()).unwrap();
+        for (f, b) in front.range().zip(back.range()) {
+            let mapped = t.transform_point(mesh.face_centers()[f]);
+            assert!((mapped - mesh.face_centers()[b]).mag() < 1e-9);
+        }
+        let back_t = wedge.transform(wedge.back()).unwrap();
+        let p = mesh.face_centers()[back.start()];
+        assert!((back_t.transform_point(p) - mesh.face_centers()[front.start()]).mag() < 1e-9);
+        assert!(wedge.transform(0).is_none());
+    }
+
+    #[test]
+    fn test_wedge_rejects_wide_angle() {
+        let mesh = wedge_mesh(30.0, 1);
+        assert!(matches!(
+            mesh.wedge(),
+            Err(MeshError::WedgeAngleOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_wedge_rejects_bad_patch_count_thickness_and_parallel_planes() {
+        let mut mesh = wedge_mesh(5.0, 1);
+        let p = &mesh.patches[5];
+        mesh.patches[5] = Patch::new(p.name(), PatchKind::Patch, p.start(), p.size());
+        assert!(matches!(
+            mesh.wedge(),
+            Err(MeshError::WedgePatchCount { count: 1 })
+        ));
+
+        let thick = wedge_mesh(5.0, 2);
+        assert!(matches!(
+            thick.wedge(),
+            Err(MeshError::WedgeNotSingleCell { cell: 0 })
+        ));
+
+        let mut parallel = box_mesh([1, 1, 1], [1.0, 1.0, 1.0]);
+        for i in 4..6 {
+            let p = &parallel.patches[i];
+            parallel.patches[i] = Patch::new(p.name(), PatchKind::Wedge, p.start(), p.size());
+        }
+        assert!(matches!(
+            parallel.wedge(),
+            Err(MeshError::WedgeAngleOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_wedge_rejects_non_planar_patch() {
+        let mesh = wedge_mesh(5.0, 1);
+        let mut points = mesh.points().to_vec();
+        let corner = mesh.faces()[mesh.patches()[4].start()][0];
+        points[corner] += Vector::new(0.0, 0.0, 0.05);
+        let primitive = crate::PrimitiveMesh::new(
+            points,
+            mesh.faces().to_vec(),
+            mesh.owner().to_vec(),
+            mesh.neighbor().to_vec(),
+        )
+        .unwrap();
+        let mesh = Mesh::new(primitive, mesh.patches().to_vec()).unwrap();
+        assert!(matches!(
+            mesh.wedge(),
+            Err(MeshError::WedgeNotPlanar { .. })
+        ));
+    }
+}
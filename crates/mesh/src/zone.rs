@@ -0,0 +1,153 @@
+/// A named set of cell, face, or point indices.
+///
+/// Indices are kept sorted in ascending order without duplicates, which makes
+/// membership tests `O(log n)` and the set-algebra helpers linear merges.
+/// Whether the indices refer to cells, faces, or points is determined by
+/// which zone list of the [`Mesh`](crate::Mesh) the zone is stored in.
+///
+/// Face zones do not track per-face orientation flags (OpenFOAM `flipMap`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Zone {
+    name: String,
+    indices: Vec<usize>,
+}
+
+impl Zone {
+    /// Creates a zone from arbitrary indices. The indices are sorted and
+    /// deduplicated.
+    pub fn new(name: impl Into<String>, indices: impl IntoIterator<Item = usize>) -> Self {
+        let mut indices: Vec<usize> = indices.into_iter().collect();
+        indices.sort_unstable();
+        indices.dedup();
+        Self {
+            name: name.into(),
+            indices,
+        }
+    }
+
+    /// Returns the zone name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the sorted, unique member indices.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Returns the number of members.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if the zone has no members.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns `true` if `index` is a member of the zone.
+    pub fn contains(&self, index: usize) -> bool {
+        self.indices.binary_search(&index).is_ok()
+    }
+
+    /// Returns the largest member index, or `None` for an empty zone.
+    pub fn max_index(&self) -> Option<usize> {
+        self.indices.last().copied()
+    }
+
+    /// Returns a new zone containing members of `self` or `other`.
+    pub fn union(&self, other: &Zone, name: impl Into<String>) -> Zone {
+        let mut out = Vec::with_capacity(self.len() + other.len());
+        let (a, b) = (&self.indices, &other.indices);
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            match a[i].cmp(&b[j]) {
+                std::cmp::Ordering::Less => {
+                    out.push(a[i]);
+                    i += 1;
+                }
+                std::cmp::Ordering::Greater => {
+                    out.push(b[j]);
+                    j += 1;
+                }
+                std::cmp::Ordering::Equal => {
+                    out.push(a[i]);
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        out.extend_from_slice(&a[i..]);
+        out.extend_from_slice(&b[j..]);
+        Zone {
+            name: name.into(),
+            indices: out,
+        }
+    }
+
+    /// Returns a new zone containing members of both `self` and `other`.
+    pub fn intersection(&self, other: &Zone, name: impl Into<String>) -> Zone {
+        Zone {
+            name: name.into(),
+            indices: self
+                .indices
+                .iter()
+                .copied()
+                .filter(|&i| other.contains(i))
+                .collect(),
+        }
+    }
+
+    /// Returns a new zone containing members of `self` that are not in `other`.
+    pub fn difference(&self, other: &Zone, name: impl Into<String>) -> Zone {
+        Zone {
+            name: name.into(),
+            indices: self
+                .indices
+                .iter()
+                .copied()
+                .filter(|&i| !other.contains(i))
+                .collect(),
+        }
+    }
+
+    /// Returns the complement of this zone within `0..n`.
+    pub fn complement(&self, n: usize, name: impl Into<String>) -> Zone {
+        Zone {
+            name: name.into(),
+            indices: (0..n).filter(|&i| !self.contains(i)).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zone_new_sorts_and_dedups() {
+        let z = Zone::new("z", [5, 1, 3, 1]);
+        assert_eq!(z.indices(), &[1, 3, 5]);
+        assert!(z.contains(3));
+        assert!(!z.contains(2));
+        assert_eq!(z.max_index(), Some(5));
+    }
+
+    #[test]
+    fn test_zone_set_algebra() {
+        let a = Zone::new("a", [0, 1, 2, 5]);
+        let b = Zone::new("b", [2, 3, 5, 7]);
+        assert_eq!(a.union(&b, "u").indices(), &[0, 1, 2, 3, 5, 7]);
+        assert_eq!(a.intersection(&b, "i").indices(), &[2, 5]);
+        assert_eq!(a.difference(&b, "d").indices(), &[0, 1]);
+        assert_eq!(a.complement(6, "c").indices(), &[3, 4]);
+        assert_eq!(a.union(&b, "u").name(), "u");
+    }
+
+    #[test]
+    fn test_zone_empty() {
+        let z = Zone::new("empty", []);
+        assert!(z.is_empty());
+        assert_eq!(z.max_index(), None);
+    }
+}
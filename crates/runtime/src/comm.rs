@@ -0,0 +1,229 @@
+use std::cell::RefCell;
+use std::sync::{Arc, Condvar, Mutex};
+
+/// A reduction applied elementwise across ranks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReduceOp {
+    /// The sum over ranks.
+    Sum,
+    /// The minimum over ranks.
+    Min,
+    /// The maximum over ranks.
+    Max,
+}
+
+impl ReduceOp {
+    /// Combines two values.
+    pub fn apply(self, a: f64, b: f64) -> f64 {
+        match self {
+            Self::Sum => a + b,
+            Self::Min => a.min(b),
+            Self::Max => a.max(b),
+        }
+    }
+}
+
+/// Collective communication between the ranks of a distributed run.
+///
+/// Every rank must make the same sequence of collective calls with slices
+/// of the same length. A message-passing backend such as MPI implements
+/// this trait; [`Serial`] is the single-rank case and [`LocalRanks`] runs
+/// ranks as threads of one process.
+pub trait Communicator: Send + Sync {
+    /// Returns the index of this rank.
+    fn rank(&self) -> usize;
+
+    /// Returns the number of ranks.
+    fn n_ranks(&self) -> usize;
+
+    /// Replaces `values` on every rank with their elementwise reduction
+    /// over all ranks.
+    fn all_reduce(&self, values: &mut [f64], op: ReduceOp);
+
+    /// Returns `true` on rank 0, which conventionally does the reporting.
+    fn is_master(&self) -> bool {
+        self.rank() == 0
+    }
+}
+
+/// The communicator of a run on one rank, whose reductions leave values
+/// unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Serial;
+
+impl Communicator for Serial {
+    fn rank(&self) -> usize {
+        0
+    }
+
+    fn n_ranks(&self) -> usize {
+        1
+    }
+
+    fn all_reduce(&self, _values: &mut [f64], _op: ReduceOp) {}
+}
+
+/// Ranks running as threads of one process, for decomposed runs without a
+/// message-passing library and for testing distributed code paths.
+///
+/// [`LocalRanks::new`] returns one communicator per rank; each must be
+/// moved to its own thread, since collective calls block until every rank
+/// has joined them.
+#[derive(Debug, Clone)]
+pub struct LocalRanks {
+    rank: usize,
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    n_ranks: usize,
+    state: Mutex<ReduceState>,
+    done: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct ReduceState {
+    /// Incremented when a reduction completes.
+    generation: u64,
+    arrived: usize,
+    accumulated: Vec<f64>,
+    result: Vec<f64>,
+}
+
+impl LocalRanks {
+    /// Returns the communicators of `n_ranks` ranks, in rank order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_ranks` is 0.
+    pub fn new(n_ranks: usize) -> Vec<Self> {
+        assert!(n_ranks > 0, "a run needs at least one rank");
+        let shared = Arc::new(Shared {
+            n_ranks,
+            state: Mutex::new(ReduceState::default()),
+            done: Condvar::new(),
+        });
+        (0..n_ranks)
+            .map(|rank| Self {
+                rank,
+                shared: Arc::clone(&shared),
+            })
+            .collect()
+    }
+}
+
+impl Communicator for LocalRanks {
+    fn rank(&self) -> usize {
+        self.rank
+    }
+
+    fn n_ranks(&self) -> usize {
+        self.shared.n_ranks
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the ranks pass slices of different lengths, or if another
+    /// rank panicked while holding the reduction state.
+    fn all_reduce(&self, values: &mut [f64], op: ReduceOp) {
+        let shared = &self.shared;
+        // Safety: the lock is only poisoned if another rank panicked.
+        let mut state = shared.state.lock().unwrap();
+        if state.arrived == 0 {
+            state.accumulated = values.to_vec();
+        } else {
+            assert_eq!(
+                state.accumulated.len(),
+                values.len(),
+                "ranks reduce slices of different lengths"
+            );
+            for (a, &v) in state.accumulated.iter_mut().zip(values.iter()) {
+                *a = op.apply(*a, v);
+            }
+        }
+        state.arrived += 1;
+        if state.arrived == shared.n_ranks {
+            state.result = std::mem::take(&mut state.accumulated);
+            state.arrived = 0;
+            state.generation += 1;
+            shared.done.notify_all();
+        } else {
+            let generation = state.generation;
+            // Safety: as above.
+            state = shared
+                .done
+                .wait_while(state, |s| s.generation == generation)
+                .unwrap();
+        }
+        // A rank cannot complete the next reduction before every rank,
+        // including this one, has joined it, so the result is still here.
+        values.copy_from_slice(&state.result);
+    }
+}
+
+thread_local! {
+    static WORLD: RefCell<Option<Arc<dyn Communicator>>> = const { RefCell::new(None) };
+}
+
+/// Returns the communicator of the calling thread's rank, or [`Serial`] if
+/// none was set.
+pub fn world() -> Arc<dyn Communicator> {
+    WORLD.with(|w| w.borrow().clone().unwrap_or_else(|| Arc::new(Serial)))
+}
+
+/// Sets the communicator returned by [`world`] on the calling thread.
+///
+/// Call it once on the thread running each rank, before any global
+/// reduction; with [`LocalRanks`], on each rank's thread.
+pub fn set_world(comm: Arc<dyn Communicator>) {
+    WORLD.with(|w| *w.borrow_mut() = Some(comm));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serial_reduction_keeps_values() {
+        let mut values = [1.0, -2.0];
+        world().all_reduce(&mut values, ReduceOp::Sum);
+        assert_eq!(values, [1.0, -2.0]);
+        assert!(world().is_master());
+        assert_eq!(world().n_ranks(), 1);
+    }
+
+    #[test]
+    fn test_local_ranks_reduce_across_threads() {
+        let results: Vec<Vec<f64>> = std::thread::scope(|s| {
+            let handles: Vec<_> = LocalRanks::new(3)
+                .into_iter()
+                .map(|comm| {
+                    s.spawn(move || {
+                        set_world(Arc::new(comm));
+                        let comm = world();
+                        let r = comm.rank() as f64;
+                        let mut out = Vec::new();
+                        // Repeated rounds check that results do not leak
+                        // between reductions.
+                        for round in 0..20 {
+                            let mut sum = [r, round as f64];
+                            comm.all_reduce(&mut sum, ReduceOp::Sum);
+                            let mut max = [r];
+                            comm.all_reduce(&mut max, ReduceOp::Max);
+                            let mut min = [r + 1.0];
+                            comm.all_reduce(&mut min, ReduceOp::Min);
+                            out = vec![sum[0], sum[1], max[0], min[0]];
+                            assert_eq!(sum[1], 3.0 * round as f64);
+                        }
+                        out
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        for r in results {
+            assert_eq!(r, [3.0, 57.0, 2.0, 1.0]);
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use std::fmt;
+
+/// A single value token in an OpenFOAM-style dictionary entry.
+///
+/// Entries such as `value uniform (1 0 0);` are stored as a sequence of
+/// values (`[Word("uniform"), List([...])]`), so that the grammar of each
+/// entry is interpreted by the consumer rather than by the parser.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A bare word such as `uniform`, `fixedValue` or `List<vector>`.
+    Word(String),
+    /// A double-quoted string with the quotes removed.
+    Str(String),
+    /// An integer literal (no decimal point or exponent).
+    Label(i64),
+    /// A floating-point literal.
+    Scalar(f64),
+    /// A parenthesized list, optionally prefixed by its length (`3(a b c)`).
+    List(Vec<Value>),
+    /// A bracketed dimension set such as `[0 1 -1 0 0 0 0]`.
+    Dimensions(Vec<f64>),
+    /// A brace-enclosed sub-dictionary.
+    Dict(Dictionary),
+}
+
+impl Value {
+    /// Returns the word or string content, if this value is a word or a string.
+    pub fn as_word(&self) -> Option<&str> {
+        match self {
+            Value::Word(w) | Value::Str(w) => Some(w),
+            _ => None,
+        }
+    }
+
+    /// Returns the numeric content as `f64`, accepting both labels and scalars.
+    pub fn as_scalar(&self) -> Option<f64> {
+        match self {
+            Value::Label(l) => Some(*l as f64),
+            Value::Scalar(s) => Some(*s),
+            _ => None,
+        }
+    }
+
+    /// Returns the content as a non-negative index, if this value is a label.
+    pub fn as_label(&self) -> Option<usize> {
+        match self {
+            Value::Label(l) => usize::try_from(*l).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the list items, if this value is a list.
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match self {
+            Value::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the sub-dictionary, if this value is a dictionary.
+    pub fn as_dict(&self) -> Option<&Dictionary> {
+        match self {
+            Value::Dict(d) => Some(d),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Word(w) => write!(f, "{w}"),
+            Value::Str(s) => write!(f, "\"{s}\""),
+            Value::Label(l) => write!(f, "{l}"),
+            Value::Scalar(s) => write!(f, "{s:?}"),
+            Value::List(items) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, ")")
+            }
+            Value::Dimensions(dims) => {
+                write!(f, "[")?;
+                for (i, d) in dims.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{d}")?;
+                }
+                write!(f, "]")
+            }
+            Value::Dict(d) => write!(f, "{{ {d}}}"),
+        }
+    }
+}
+
+/// An ordered keyword → values map in the OpenFOAM dictionary model.
+///
+/// Insertion order is preserved so that dictionaries can be written back out
+/// in the order they were read. Keyword lookup is linear, which is adequate
+/// for configuration-sized dictionaries.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dictionary {
+    entries: Vec<(String, Vec<Value>)>,
+}
+
+impl Dictionary {
+    /// Creates an empty dictionary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts an entry, replacing any existing entry with the same keyword
+    /// in place.
+    pub fn insert(&mut self, keyword: impl Into<String>, values: Vec<Value>) {
+        let keyword = keyword.into();
+        if let Some(entry) = self.entries.iter_mut().find(|(k, _)| *k == keyword) {
+            entry.1 = values;
+        } else {
+            self.entries.push((keyword, values));
+        }
+    }
+
+    /// Returns the values of the entry with the given keyword.
+    pub fn get(&self, keyword: &str) -> Option<&[Value]> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == keyword)
+            .map(|(_, v)| v.as_slice())
+    }
+
+    /// Returns `true` if an entry with the given keyword exists.
+    pub fn contains(&self, keyword: &str) -> bool {
+        self.get(keyword).is_some()
+    }
+
+    /// Returns the single word of a `keyword word;` entry.
+    pub fn get_word(&self, keyword: &str) -> Option<&str> {
+        match self.get(keyword)? {
+            [v] => v.as_word(),
+            _ => None,
+        }
+    }
+
+    /// Returns the single number of a `keyword 1.5;` entry.
+    pub fn get_scalar(&self, keyword: &str) -> Option<f64> {
+        match self.get(keyword)? {
+            [v] => v.as_scalar(),
+            _ => None,
+        }
+    }
+
+    /// Returns the single non-negative integer of a `keyword 3;` entry.
+    pub fn get_label(&self, keyword: &str) -> Option<usize> {
+        match self.get(keyword)? {
+            [v] => v.as_label(),
+            _ => None,
+        }
+    }
+
+    /// Returns the sub-dictionary of a `keyword { ... }` entry.
+    pub fn get_dict(&self, keyword: &str) -> Option<&Dictionary> {
+        match self.get(keyword)? {
+            [v] => v.as_dict(),
+            _ => None,
+        }
+    }
+
+    /// Iterates over `(keyword, values)` pairs in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[Value])> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_slice()))
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the dictionary has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl fmt::Display for Dictionary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (keyword, values) in &self.entries {
+            write!(f, "{keyword}")?;
+            if let [Value::Dict(d)] = values.as_slice() {
+                write!(f, " {{ {d}}} ")?;
+                continue;
+            }
+            for v in values {
+                write!(f, " {v}")?;
+            }
+            write!(f, "; ")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dictionary_insert_replaces_existing_entry() {
+        let mut d = Dictionary::new();
+        d.insert("type", vec![Value::Word("patch".into())]);
+        d.insert("nFaces", vec![Value::Label(4)]);
+        d.insert("type", vec![Value::Word("wall".into())]);
+        assert_eq!(d.len(), 2);
+        assert_eq!(d.get_word("type"), Some("wall"));
+        assert_eq!(d.iter().next().unwrap().0, "type");
+    }
+
+    #[test]
+    fn test_dictionary_typed_getters() {
+        let mut d = Dictionary::new();
+        d.insert("startFace", vec![Value::Label(12)]);
+        d.insert("alpha", vec![Value::Scalar(0.7)]);
+        let mut sub = Dictionary::new();
+        sub.insert("type", vec![Value::Word("zeroGradient".into())]);
+        d.insert("inlet", vec![Value::Dict(sub)]);
+
+        assert_eq!(d.get_label("startFace"), Some(12));
+        assert_eq!(d.get_scalar("startFace"), Some(12.0));
+        assert_eq!(d.get_scalar("alpha"), Some(0.7));
+        assert_eq!(d.get_label("alpha"), None);
+        assert_eq!(
+            d.get_dict("inlet").and_then(|s| s.get_word("type")),
+            Some("zeroGradient")
+        );
+        assert!(d.get("missing").is_none());
+    }
+
+    #[test]
+    fn test_value_negative_label_is_not_an_index() {
+        assert_eq!(Value::Label(-1).as_label(), None);
+        assert_eq!(Value::Label(-1).as_scalar(), Some(-1.0));
+    }
+
+    #[test]
+    fn test_value_display_list_and_dimensions() {
+        let v = Value::List(vec![Value::Label(1), Value::Scalar(0.5), Value::Label(0)]);
+        assert_eq!(v.to_string(), "(1 0.5 0)");
+        let d = Value::Dimensions(vec![0.0, 1.0, -1.0]);
+        assert_eq!(d.to_string(), "[0 1 -1]");
+    }
+}
@@ -1,5 +1,10 @@
 //! Runtime selection mechanisms
 //!
-//! Provides inventory-based factory registration and runtime selection utilities.
+//! Provides inventory-based factory registration and runtime selection
+//! utilities, and the communicator of distributed runs.
 
-// TODO: Implement runtime factory utilities
+mod comm;
+mod dictionary;
+
+pub use comm::{Communicator, LocalRanks, ReduceOp, Serial, set_world, world};
+pub use dictionary::{Dictionary, Value};
@@ -2,6 +2,9 @@ use std::marker::PhantomData;
 
 use typenum::Integer;
 
+/// 次元指数の型情報。`fn() -> _` で包み、`Send`/`Sync` を値の型 `V` だけで決める。
+type Exponents<M, L, T> = fn() -> (M, L, T);
+
 /// 物理次元付き量。M: 質量, L: 長さ, T: 時間の SI 次元指数を型パラメータで保持する。
 ///
 /// 次元指数は `typenum` の型レベル整数（`P1`, `N1`, `Z0` 等）で表現される。
@@ -22,7 +25,7 @@ use typenum::Integer;
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Dim<V, M: Integer, L: Integer, T: Integer> {
     value: V,
-    _phantom: PhantomData<(M, L, T)>,
+    _phantom: PhantomData<Exponents<M, L, T>>,
 }
 
 impl<V, M: Integer, L: Integer, T: Integer> Dim<V, M, L, T> {
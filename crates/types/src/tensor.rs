@@ -7,6 +7,8 @@ mod cross_ops;
 mod ops;
 mod special;
 #[cfg(test)]
+// tests.rs wraps its tests in an inner `mod tests` of its own.
+#[allow(clippy::module_inception)]
 mod tests;
 mod types;
 
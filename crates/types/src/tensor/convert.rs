@@ -52,6 +52,14 @@ impl Tensor {
         )
     }
 
+    /// ホッジ双対: `(T_yz, -T_xz, T_xy)`
+    ///
+    /// 反対称テンソル `W` に対して `W · v = v × (*W)` を満たす軸ベクトルを返す。
+    #[inline]
+    pub fn hodge_dual(&self) -> Vector {
+        Vector::new(self.yz(), -self.xz(), self.xy())
+    }
+
     /// 偏差部分: `T - (trace/3)*I`
     #[inline]
     pub fn dev(&self) -> Tensor {
@@ -83,6 +91,25 @@ impl Tensor {
             + self.xz() * (self.yx() * self.zy() - self.yy() * self.zx())
     }
 
+    /// 逆行列（余因子行列による）: `adj(T) / det(T)`
+    ///
+    /// 特異な場合は非有限の成分を返すため、呼び出し側で `det` を確認すること。
+    #[inline]
+    pub fn inv(&self) -> Tensor {
+        let adjugate = Tensor::new(
+            self.yy() * self.zz() - self.yz() * self.zy(),
+            self.xz() * self.zy() - self.xy() * self.zz(),
+            self.xy() * self.yz() - self.xz() * self.yy(),
+            self.yz() * self.zx() - self.yx() * self.zz(),
+            self.xx() * self.zz() - self.xz() * self.zx(),
+            self.xz() * self.yx() - self.xx() * self.yz(),
+            self.yx() * self.zy() - self.yy() * self.zx(),
+            self.xy() * self.zx() - self.xx() * self.zy(),
+            self.xx() * self.yy() - self.xy() * self.yx(),
+        );
+        adjugate * (1.0 / self.det())
+    }
+
     /// 転置: `T^T`
     #[inline]
     pub fn transpose(&self) -> Tensor {
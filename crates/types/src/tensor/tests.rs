@@ -70,6 +70,8 @@ mod tests {
     }
 
     #[test]
+    // 3.14 is an arbitrary value here, not an approximation of π.
+    #[allow(clippy::approx_constant)]
     fn test_spherical_tensor_value() {
         let s = SphericalTensor::new(3.14);
         assert_eq!(s.value(), 3.14);
@@ -378,6 +380,17 @@ mod tests {
         assert_tensor_approx_eq(symm_t + skew_t, t);
     }
 
+    #[test]
+    fn test_hodge_dual_of_skew_part() {
+        let t = Tensor::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        let w = t.skew();
+        let dual = w.hodge_dual();
+        assert_vec_approx_eq(dual, Vector::new(-1.0, 2.0, -1.0));
+        // W · v = v × (*W)
+        let v = Vector::new(0.5, -1.0, 2.0);
+        assert_vec_approx_eq(w * v, v.cross(&dual));
+    }
+
     #[test]
     fn test_two_symm() {
         let t = Tensor::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
@@ -429,6 +442,13 @@ mod tests {
         assert_approx_eq(s.det(), 24.0);
     }
 
+    #[test]
+    fn test_inv_times_original_is_identity() {
+        let t = Tensor::new(2.0, 1.0, 0.0, 0.0, 3.0, 1.0, 1.0, 0.0, 4.0);
+        assert_tensor_approx_eq(t.inv() * t, Tensor::identity());
+        assert_tensor_approx_eq(t * t.inv(), Tensor::identity());
+    }
+
     #[test]
     fn test_transpose() {
         let t = Tensor::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
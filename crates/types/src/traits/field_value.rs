@@ -6,9 +6,16 @@ use crate::tensor::{SphericalTensor, SymmTensor, Tensor, Vector};
 ///
 /// スーパートレイトバウンドとして加算・減算・スカラー倍・符号反転を要求し、
 /// 零元（加法単位元）とノルム（Euclidean / Frobenius）を定義する。
+/// フィールド演算をスレッド並列化できるよう `Send + Sync` も要求する。
 /// 静的ディスパッチ専用設計のため `dyn FieldValue` は意図的に非サポート。
 pub trait FieldValue:
-    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<f64, Output = Self> + Neg<Output = Self>
+    Copy
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<f64, Output = Self>
+    + Neg<Output = Self>
 {
     /// 加法単位元を返す。`Self::zero() + x == x` をすべての `x` について保証する。
     fn zero() -> Self;